@@ -1,4 +1,5 @@
 use crate::imports::*;
+use kaspa_rpc_core::api::auth::RpcCredentials;
 
 #[derive(Default, Handler)]
 #[help("Connect to a Kaspa network")]
@@ -45,6 +46,13 @@ impl Connect {
                 }
             }
 
+            // Optional credentials for nodes requiring RPC authentication: `<token>` or `<username>:<password>`
+            let credentials = argv.get(1).map(|credentials| match credentials.split_once(':') {
+                Some((username, password)) => RpcCredentials::Basic { username: username.to_string(), password: password.to_string() },
+                None => RpcCredentials::Token(credentials.to_string()),
+            });
+            wrpc_client.set_credentials(credentials).map_err(|e| e.to_string())?;
+
             let options = ConnectOptions {
                 block_async_connect: true,
                 strategy: ConnectStrategy::Fallback,
@@ -167,6 +167,19 @@ impl Node {
                 let path = regex.replace(cmd, "").trim().to_string();
                 self.select(ctx, path.is_not_empty().then_some(path)).await?;
             }
+            "backup" => {
+                let regex = Regex::new(r"(?i)^\s*node\s+backup\s+").unwrap();
+                let target_dir = regex.replace(cmd, "").trim().to_string();
+                if target_dir.is_empty() || argv.is_empty() {
+                    return Err(Error::custom("Please specify the backup target directory"));
+                }
+                tprintln!(ctx, "creating a backup of the node databases in {target_dir}...");
+                let databases = ctx.wallet().rpc_api().backup(target_dir.clone()).await?;
+                for database in databases {
+                    tprintln!(ctx, "{}: {} ({} ms)", database.name, database.path, database.duration_millis);
+                }
+                tprintln!(ctx, "backup complete, a node can be started from it with '--appdir={target_dir}'");
+            }
             "version" => {
                 kaspad.configure(self.create_config(&ctx).await?).await?;
                 let version = kaspad.version().await?;
@@ -193,6 +206,7 @@ impl Node {
                 ("kill", "Kill the local Kaspa node instance"),
                 ("status", "Get the status of the local Kaspa node instance"),
                 ("mute", "Toggle log output"),
+                ("backup <dir>", "Create a backup of the node databases in <dir> (requires the node to run with --unsaferpc)"),
             ],
             None,
         )?;
@@ -269,6 +269,68 @@ impl Rpc {
                 let result = rpc.get_current_block_color_call(None, GetCurrentBlockColorRequest { hash }).await?;
                 self.println(&ctx, result);
             }
+            RpcApiOps::GetTransaction => {
+                if argv.is_empty() {
+                    return Err(Error::custom("Missing transaction id argument"));
+                }
+                let transaction_id = argv.remove(0);
+                let transaction_id = RpcTransactionId::from_hex(transaction_id.as_str())?;
+                let include_verbose_data = if argv.is_empty() { false } else { argv.remove(0).parse().unwrap_or(false) };
+                let result = rpc.get_transaction_call(None, GetTransactionRequest { transaction_id, include_verbose_data }).await?;
+                self.println(&ctx, result);
+            }
+            RpcApiOps::GetTransactionAcceptance => {
+                if argv.is_empty() {
+                    return Err(Error::custom("Missing transaction id argument"));
+                }
+                let transaction_ids =
+                    argv.iter().map(|x| RpcTransactionId::from_hex(x.as_str())).collect::<std::result::Result<Vec<_>, _>>()?;
+                let result = rpc.get_transaction_acceptance_call(None, GetTransactionAcceptanceRequest { transaction_ids }).await?;
+                self.println(&ctx, result);
+            }
+            RpcApiOps::GetAddressHistory => {
+                if argv.is_empty() {
+                    return Err(Error::custom("Missing address argument"));
+                }
+                let address = Address::try_from(argv.remove(0).as_str())?;
+                let start_daa_score = if argv.is_empty() { 0 } else { argv.remove(0).parse::<u64>()? };
+                let limit = if argv.is_empty() { 100 } else { argv.remove(0).parse::<u32>()? };
+                let result = rpc.get_address_history_call(None, GetAddressHistoryRequest { address, start_daa_score, limit }).await?;
+                self.println(&ctx, result);
+            }
+            RpcApiOps::GetMempoolPolicy => {
+                let result = rpc.get_mempool_policy_call(None, GetMempoolPolicyRequest {}).await?;
+                self.println(&ctx, result);
+            }
+            RpcApiOps::SetMempoolPolicy => {
+                if argv.is_empty() {
+                    return Err(Error::custom("Missing <field>=<value> arguments"));
+                }
+                let mut request = SetMempoolPolicyRequest::default();
+                for arg in argv.iter() {
+                    let (field, value) = arg.split_once('=').ok_or_else(|| Error::custom(format!("Invalid argument '{arg}'")))?;
+                    match field {
+                        "maximum-transaction-count" => request.maximum_transaction_count = Some(value.parse::<u64>()?),
+                        "mempool-size-limit" => request.mempool_size_limit = Some(value.parse::<u64>()?),
+                        "maximum-orphan-transaction-mass" => request.maximum_orphan_transaction_mass = Some(value.parse::<u64>()?),
+                        "maximum-orphan-transaction-count" => request.maximum_orphan_transaction_count = Some(value.parse::<u64>()?),
+                        "accept-non-standard" => {
+                            request.accept_non_standard = Some(value.parse::<bool>().map_err(|e| Error::custom(e.to_string()))?)
+                        }
+                        "minimum-relay-transaction-fee" => request.minimum_relay_transaction_fee = Some(value.parse::<u64>()?),
+                        _ => return Err(Error::custom(format!("Unknown mempool policy field '{field}'"))),
+                    }
+                }
+                let result = rpc.set_mempool_policy_call(None, request).await?;
+                self.println(&ctx, result);
+            }
+            RpcApiOps::Backup => {
+                if argv.is_empty() {
+                    return Err(Error::custom("Please specify the backup target directory"));
+                }
+                let result = rpc.backup_call(None, BackupRequest { target_dir: argv.remove(0) }).await?;
+                self.println(&ctx, result);
+            }
             _ => {
                 tprintln!(ctx, "rpc method exists but is not supported by the cli: '{op_str}'\r\n");
                 return Ok(());
@@ -19,14 +19,23 @@ use kaspa_database::prelude::{CachePolicy, StoreResultExtensions, DB};
 use kaspa_utils::networking::IpAddress;
 use local_ip_address::list_afinet_netifas;
 use parking_lot::Mutex;
-use stores::banned_address_store::{BannedAddressesStore, BannedAddressesStoreReader, ConnectionBanTimestamp, DbBannedAddressesStore};
+use stores::banned_address_store::{BannedAddressesStore, BannedAddressesStoreReader, ConnectionBanExpiry, DbBannedAddressesStore};
 use thiserror::Error;
 
-pub use stores::NetAddress;
+pub use stores::{NamedNetAddress, NetAddress};
 
-const MAX_ADDRESSES: usize = 4096;
+/// Number of buckets of the table of addresses never connected to
+const NEW_BUCKET_COUNT: usize = 256;
+/// Number of buckets of the table of addresses connected to at least once
+const TRIED_BUCKET_COUNT: usize = 64;
+const BUCKET_SIZE: usize = 64;
+const MAX_ADDRESSES: usize = (NEW_BUCKET_COUNT + TRIED_BUCKET_COUNT) * BUCKET_SIZE;
+const MAX_NAMED_ADDRESSES: usize = 1024;
 const MAX_CONNECTION_FAILED_COUNT: u64 = 3;
 
+/// Duration of the bans requested explicitly, as opposed to the bans of misbehaving peers
+const DEFAULT_BAN_DURATION: Duration = Duration::from_secs(24 * 60 * 60);
+
 const UPNP_DEADLINE_SEC: u64 = 2 * 60;
 const UPNP_EXTEND_PERIOD: u64 = UPNP_DEADLINE_SEC / 2;
 
@@ -54,6 +63,7 @@ pub enum UpnpError {
 pub struct AddressManager {
     banned_address_store: DbBannedAddressesStore,
     address_store: address_store_with_cache::Store,
+    named_address_store: named_address_store_with_cache::Store,
     config: Arc<Config>,
     local_net_addresses: Vec<NetAddress>,
 }
@@ -62,10 +72,12 @@ impl AddressManager {
     pub fn new(config: Arc<Config>, db: Arc<DB>, tick_service: Arc<TickService>) -> (Arc<Mutex<Self>>, Option<Extender>) {
         let mut instance = Self {
             banned_address_store: DbBannedAddressesStore::new(db.clone(), CachePolicy::Count(MAX_ADDRESSES)),
-            address_store: address_store_with_cache::new(db),
+            address_store: address_store_with_cache::new(db.clone()),
+            named_address_store: named_address_store_with_cache::new(db),
             local_net_addresses: Vec::new(),
             config,
         };
+        instance.migrate_legacy_bans();
 
         let extender = instance.init_local_addresses(tick_service);
 
@@ -251,18 +263,14 @@ impl AddressManager {
         }
     }
 
-    pub fn add_address(&mut self, address: NetAddress) {
+    /// Adds an address learned from `source`, which is either the peer relaying it or the address itself
+    pub fn add_address(&mut self, address: NetAddress, source: IpAddress) {
         if address.ip.is_loopback() || address.ip.is_unspecified() {
             debug!("[Address manager] skipping local address {}", address.ip);
             return;
         }
 
-        if self.address_store.has(address) {
-            return;
-        }
-
-        // We mark `connection_failed_count` as 0 only after first success
-        self.address_store.set(address, 1);
+        self.address_store.add(address, source);
     }
 
     pub fn mark_connection_failure(&mut self, address: NetAddress) {
@@ -274,16 +282,12 @@ impl AddressManager {
         if new_count > MAX_CONNECTION_FAILED_COUNT {
             self.address_store.remove(address);
         } else {
-            self.address_store.set(address, new_count);
+            self.address_store.set_failed_count(address, new_count);
         }
     }
 
     pub fn mark_connection_success(&mut self, address: NetAddress) {
-        if !self.address_store.has(address) {
-            return;
-        }
-
-        self.address_store.set(address, 0);
+        self.address_store.mark_tried(address);
     }
 
     pub fn iterate_addresses(&self) -> impl Iterator<Item = NetAddress> + '_ {
@@ -294,8 +298,73 @@ impl AddressManager {
         self.address_store.iterate_prioritized_random_addresses(exceptions)
     }
 
+    /// Adds an address designated by host name, which can only be connected to through a proxy
+    pub fn add_named_address(&mut self, address: NamedNetAddress) {
+        if self.named_address_store.has(&address) {
+            return;
+        }
+
+        // We mark `connection_failed_count` as 0 only after first success
+        self.named_address_store.set(address, 1);
+    }
+
+    pub fn mark_named_connection_failure(&mut self, address: &NamedNetAddress) {
+        if !self.named_address_store.has(address) {
+            return;
+        }
+
+        let new_count = self.named_address_store.get(address).connection_failed_count + 1;
+        if new_count > MAX_CONNECTION_FAILED_COUNT {
+            self.named_address_store.remove(address);
+        } else {
+            self.named_address_store.set(address.clone(), new_count);
+        }
+    }
+
+    pub fn mark_named_connection_success(&mut self, address: &NamedNetAddress) {
+        if !self.named_address_store.has(address) {
+            return;
+        }
+
+        self.named_address_store.set(address.clone(), 0);
+    }
+
+    pub fn iterate_named_addresses(&self) -> impl Iterator<Item = &NamedNetAddress> + '_ {
+        self.named_address_store.iterate_addresses()
+    }
+
+    /// Returns the named addresses in random order, the ones with fewer connection failures first
+    pub fn iterate_prioritized_random_named_addresses(
+        &self,
+        exceptions: HashSet<NamedNetAddress>,
+    ) -> impl Iterator<Item = NamedNetAddress> {
+        self.named_address_store.iterate_prioritized_random_addresses(exceptions)
+    }
+
+    /// Converts the ban start times stored prior to the bans expiring at a given time, which all lasted [`DEFAULT_BAN_DURATION`]
+    fn migrate_legacy_bans(&mut self) {
+        let legacy_entries = self.banned_address_store.take_legacy_entries().unwrap();
+        if legacy_entries.is_empty() {
+            return;
+        }
+        info!("Migrating {} banned addresses", legacy_entries.len());
+        let now = unix_now();
+        for (ip, start) in legacy_entries {
+            let expiry = start.0.saturating_add(DEFAULT_BAN_DURATION.as_millis() as u64);
+            if expiry > now {
+                self.banned_address_store.set(ip, ConnectionBanExpiry(expiry)).unwrap();
+            }
+        }
+    }
+
     pub fn ban(&mut self, ip: IpAddress) {
-        self.banned_address_store.set(ip.into(), ConnectionBanTimestamp(unix_now())).unwrap();
+        self.ban_for(ip, DEFAULT_BAN_DURATION);
+    }
+
+    /// Bans the given IP for the given duration. The expiry is persisted, so that the ban outlives a restart.
+    pub fn ban_for(&mut self, ip: IpAddress, duration: Duration) {
+        let expiry = unix_now().saturating_add(duration.as_millis() as u64);
+        self.banned_address_store.set(ip.into(), ConnectionBanExpiry(expiry)).unwrap();
         self.address_store.remove_by_ip(ip.into());
     }
 
@@ -304,10 +373,9 @@ impl AddressManager {
     }
 
     pub fn is_banned(&mut self, ip: IpAddress) -> bool {
-        const MAX_BANNED_TIME: u64 = 24 * 60 * 60 * 1000;
         match self.banned_address_store.get(ip.into()).unwrap_option() {
-            Some(timestamp) => {
-                if unix_now() - timestamp.0 > MAX_BANNED_TIME {
+            Some(expiry) => {
+                if unix_now() >= expiry.0 {
                     self.unban(ip);
                     false
                 } else {
@@ -323,7 +391,139 @@ impl AddressManager {
     }
 
     pub fn get_all_banned_addresses(&self) -> Vec<IpAddress> {
-        self.banned_address_store.iterator().map(|x| IpAddress::from(x.unwrap().0)).collect_vec()
+        let now = unix_now();
+        self.banned_address_store
+            .iterator()
+            .map(|x| x.unwrap())
+            .filter(|(_, expiry)| expiry.0 > now)
+            .map(|(ip, _)| ip.into())
+            .collect_vec()
+    }
+}
+
+mod named_address_store_with_cache {
+    use std::{
+        collections::{HashMap, HashSet},
+        sync::Arc,
+    };
+
+    use itertools::Itertools;
+    use kaspa_database::prelude::{CachePolicy, DB};
+    use rand::seq::SliceRandom;
+
+    use crate::{
+        stores::named_address_store::{DbNamedAddressesStore, NamedAddressesStore, NamedEntry},
+        NamedNetAddress, MAX_NAMED_ADDRESSES,
+    };
+
+    pub struct Store {
+        db_store: DbNamedAddressesStore,
+        addresses: HashMap<NamedNetAddress, NamedEntry>,
+    }
+
+    impl Store {
+        fn new(db: Arc<DB>) -> Self {
+            // We manage the cache ourselves on this level, so we disable the inner builtin cache
+            let db_store = DbNamedAddressesStore::new(db, CachePolicy::Empty);
+            let addresses = db_store.iterator().map(|res| res.unwrap()).map(|entry| (entry.address.clone(), entry)).collect();
+            Self { db_store, addresses }
+        }
+
+        pub fn has(&self, address: &NamedNetAddress) -> bool {
+            self.addresses.contains_key(address)
+        }
+
+        pub fn set(&mut self, address: NamedNetAddress, connection_failed_count: u64) {
+            let entry = NamedEntry { connection_failed_count, address: address.clone() };
+            self.db_store.set(&address, entry.clone()).unwrap();
+            self.addresses.insert(address, entry);
+            self.keep_limit();
+        }
+
+        fn keep_limit(&mut self) {
+            while self.addresses.len() > MAX_NAMED_ADDRESSES {
+                let to_remove = self.addresses.values().max_by_key(|entry| entry.connection_failed_count).unwrap().address.clone();
+                self.remove(&to_remove);
+            }
+        }
+
+        pub fn get(&self, address: &NamedNetAddress) -> &NamedEntry {
+            self.addresses.get(address).unwrap()
+        }
+
+        pub fn remove(&mut self, address: &NamedNetAddress) {
+            self.addresses.remove(address);
+            self.db_store.remove(address).unwrap()
+        }
+
+        pub fn iterate_addresses(&self) -> impl Iterator<Item = &NamedNetAddress> + '_ {
+            self.addresses.keys()
+        }
+
+        pub fn iterate_prioritized_random_addresses(
+            &self,
+            exceptions: HashSet<NamedNetAddress>,
+        ) -> impl Iterator<Item = NamedNetAddress> {
+            let mut entries = self.addresses.values().filter(|entry| !exceptions.contains(&entry.address)).collect_vec();
+            entries.shuffle(&mut rand::thread_rng());
+            // The sort is stable, hence addresses remain shuffled among those of equal failure count
+            entries.sort_by_key(|entry| entry.connection_failed_count);
+            entries.into_iter().map(|entry| entry.address.clone()).collect_vec().into_iter()
+        }
+    }
+
+    pub fn new(db: Arc<DB>) -> Store {
+        Store::new(db)
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use std::str::FromStr;
+
+        use super::*;
+        use crate::stores::address_store::LegacyEntry;
+        use crate::MAX_CONNECTION_FAILED_COUNT;
+        use address_manager::{AddressManager, MAX_ADDRESSES};
+        use kaspa_consensus_core::config::{params::SIMNET_PARAMS, Config};
+        use kaspa_core::task::tick::TickService;
+        use kaspa_database::create_temp_db;
+        use kaspa_database::prelude::ConnBuilder;
+
+        #[test]
+        fn test_named_addresses() {
+            let db = create_temp_db!(ConnBuilder::default().with_files_limit(10));
+            let (am, _) = AddressManager::new(Arc::new(Config::new(SIMNET_PARAMS)), db.1.clone(), Arc::new(TickService::default()));
+            let (first, second) = (
+                NamedNetAddress::from_str("kaspadxyz2ruzvtwoiqbuzhvrdjmcvg7tgxnc5rplwhsbd5e2fkaxyad.onion:16111").unwrap(),
+                NamedNetAddress::from_str("node.kaspa.org:16111").unwrap(),
+            );
+
+            let mut am_guard = am.lock();
+            am_guard.add_named_address(first.clone());
+            am_guard.add_named_address(second.clone());
+            am_guard.mark_named_connection_success(&second);
+            // The successfully connected address comes first
+            assert_eq!(
+                am_guard.iterate_prioritized_random_named_addresses(HashSet::new()).collect_vec(),
+                vec![second.clone(), first.clone()]
+            );
+            assert_eq!(
+                am_guard.iterate_prioritized_random_named_addresses(HashSet::from([second.clone()])).collect_vec(),
+                vec![first.clone()]
+            );
+
+            // Failing addresses are eventually removed
+            for _ in 0..MAX_CONNECTION_FAILED_COUNT {
+                am_guard.mark_named_connection_failure(&first);
+            }
+            assert_eq!(am_guard.iterate_named_addresses().cloned().collect_vec(), vec![second.clone()]);
+            drop(am_guard);
+            drop(am);
+
+            // The addresses are persisted
+            let (am, _) = AddressManager::new(Arc::new(Config::new(SIMNET_PARAMS)), db.1, Arc::new(TickService::default()));
+            assert_eq!(am.lock().iterate_named_addresses().cloned().collect_vec(), vec![second]);
+        }
     }
 }
 
@@ -337,8 +537,9 @@ mod address_store_with_cache {
     };
 
     use itertools::Itertools;
+    use kaspa_core::info;
     use kaspa_database::prelude::{CachePolicy, DB};
-    use kaspa_utils::networking::PrefixBucket;
+    use kaspa_utils::networking::{IpAddress, PrefixBucket};
     use rand::{
         distributions::{WeightedError, WeightedIndex},
         prelude::Distribution,
@@ -346,49 +547,152 @@ mod address_store_with_cache {
 
     use crate::{
         stores::{
-            address_store::{AddressesStore, DbAddressesStore, Entry},
+            address_store::{AddressesStore, BucketingKey, DbAddressesStore, Entry},
             AddressKey,
         },
-        NetAddress, MAX_ADDRESSES, MAX_CONNECTION_FAILED_COUNT,
+        NetAddress, BUCKET_SIZE, MAX_CONNECTION_FAILED_COUNT, NEW_BUCKET_COUNT, TRIED_BUCKET_COUNT,
     };
 
+    /// Number of new buckets the addresses learned from a single source netgroup spread over
+    const NEW_BUCKETS_PER_SOURCE_GROUP: u64 = 64;
+    /// Number of tried buckets the addresses of a single netgroup spread over
+    const TRIED_BUCKETS_PER_GROUP: u64 = 8;
+
+    // Domain separation tags of the keyed hashes placing addresses
+    const NEW_GROUP_TAG: u8 = 0;
+    const NEW_BUCKET_TAG: u8 = 1;
+    const TRIED_GROUP_TAG: u8 = 2;
+    const TRIED_BUCKET_TAG: u8 = 3;
+    const POSITION_TAG: u8 = 4;
+
+    /// Address store split, in the spirit of the Bitcoin Core addrman, into a table of addresses never connected to
+    /// (`new`) and a table of addresses connected to at least once (`tried`). Each table is an array of fixed size
+    /// buckets, and the bucket and position of an address derive from a keyed hash of its netgroup ([`PrefixBucket`]),
+    /// and for the new table also of the netgroup of the peer the address was learned from. The key is a secret of
+    /// the node, so that an attacker cannot craft addresses colliding with the legit ones, and the addresses of a few
+    /// netgroups, or relayed by peers of a few netgroups, can only ever take a small share of the tables.
     pub struct Store {
         db_store: DbAddressesStore,
+        key: BucketingKey,
         addresses: HashMap<AddressKey, Entry>,
+        new_table: Vec<Option<AddressKey>>,
+        tried_table: Vec<Option<AddressKey>>,
     }
 
     impl Store {
         fn new(db: Arc<DB>) -> Self {
             // We manage the cache ourselves on this level, so we disable the inner builtin cache
-            let db_store = DbAddressesStore::new(db, CachePolicy::Empty);
-            let mut addresses = HashMap::new();
-            for (key, entry) in db_store.iterator().map(|res| res.unwrap()) {
-                addresses.insert(key, entry);
+            let mut db_store = DbAddressesStore::new(db, CachePolicy::Empty);
+            // The bucketing key is missing only when the store was written prior to the new and tried tables,
+            // in which case the entries of the legacy layout get migrated
+            let (key, legacy_entries) = match db_store.bucketing_key() {
+                Some(key) => (key, vec![]),
+                None => {
+                    let legacy_entries = db_store.take_legacy_entries().unwrap();
+                    let key = rand::random();
+                    db_store.set_bucketing_key(key).unwrap();
+                    (key, legacy_entries)
+                }
+            };
+
+            let mut store = Self {
+                db_store,
+                key,
+                addresses: HashMap::new(),
+                new_table: vec![None; NEW_BUCKET_COUNT * BUCKET_SIZE],
+                tried_table: vec![None; TRIED_BUCKET_COUNT * BUCKET_SIZE],
+            };
+            for (key, entry) in store.db_store.iterator().map(|res| res.unwrap()).collect_vec() {
+                let (table, slot) = store.slot_of(&entry);
+                match table[slot] {
+                    None => {
+                        table[slot] = Some(key);
+                        store.addresses.insert(key, entry);
+                    }
+                    // Only a store not written by this code can have colliding entries
+                    Some(_) => store.db_store.remove(key).unwrap(),
+                }
             }
 
-            Self { db_store, addresses }
+            if !legacy_entries.is_empty() {
+                info!("Migrating {} addresses to the new table", legacy_entries.len());
+            }
+            for entry in legacy_entries {
+                // The source of legacy addresses is unknown, so they are considered as learned from themselves. A legacy
+                // entry with no failed connection may never have been dialed, so all go to the new table and only a
+                // successful connection moves them to the tried one.
+                store.add(entry.address, entry.address.ip);
+                if entry.connection_failed_count > 0 {
+                    store.set_failed_count(entry.address, entry.connection_failed_count);
+                }
+            }
+
+            store
         }
 
-        pub fn has(&mut self, address: NetAddress) -> bool {
+        pub fn has(&self, address: NetAddress) -> bool {
             self.addresses.contains_key(&address.into())
         }
 
-        pub fn set(&mut self, address: NetAddress, connection_failed_count: u64) {
-            let entry = match self.addresses.get(&address.into()) {
-                Some(entry) => Entry { connection_failed_count, address: entry.address },
-                None => Entry { connection_failed_count, address },
+        /// Adds the address learned from `source` to the new table. If its slot is taken, the address is dropped,
+        /// unless the occupant is about to be removed for failing connections, in which case it gets replaced.
+        pub fn add(&mut self, address: NetAddress, source: IpAddress) {
+            let key = address.into();
+            if self.addresses.contains_key(&key) {
+                return;
+            }
+
+            let slot = self.new_slot(&address, &source);
+            if let Some(occupant) = self.new_table[slot] {
+                if self.addresses[&occupant].connection_failed_count < MAX_CONNECTION_FAILED_COUNT {
+                    return;
+                }
+                self.remove_by_key(occupant);
+            }
+            self.new_table[slot] = Some(key);
+            // We mark `connection_failed_count` as 0 only after first success
+            self.write(key, Entry { connection_failed_count: 1, address, source, is_tried: false });
+        }
+
+        pub fn set_failed_count(&mut self, address: NetAddress, connection_failed_count: u64) {
+            let key = address.into();
+            if let Some(&entry) = self.addresses.get(&key) {
+                self.write(key, Entry { connection_failed_count, ..entry });
+            }
+        }
+
+        /// Resets the failures of the address and moves it to the tried table. The address taking its tried slot,
+        /// if any, is moved back to the new table.
+        pub fn mark_tried(&mut self, address: NetAddress) {
+            let key = address.into();
+            let Some(&entry) = self.addresses.get(&key) else {
+                return;
             };
-            self.db_store.set(address.into(), entry).unwrap();
-            self.addresses.insert(address.into(), entry);
-            self.keep_limit();
+            if !entry.is_tried {
+                let new_slot = self.new_slot(&entry.address, &entry.source);
+                self.new_table[new_slot] = None;
+                let tried_slot = self.tried_slot(&entry.address);
+                if let Some(occupant) = self.tried_table[tried_slot].take() {
+                    self.move_to_new(occupant);
+                }
+                self.tried_table[tried_slot] = Some(key);
+            }
+            self.write(key, Entry { connection_failed_count: 0, is_tried: true, ..entry });
         }
 
-        fn keep_limit(&mut self) {
-            while self.addresses.len() > MAX_ADDRESSES {
-                let to_remove =
-                    self.addresses.iter().max_by(|a, b| (a.1).connection_failed_count.cmp(&(b.1).connection_failed_count)).unwrap();
-                self.remove_by_key(*to_remove.0);
+        fn move_to_new(&mut self, key: AddressKey) {
+            let entry = self.addresses[&key];
+            let slot = self.new_slot(&entry.address, &entry.source);
+            if let Some(occupant) = self.new_table[slot] {
+                self.remove_by_key(occupant);
             }
+            self.new_table[slot] = Some(key);
+            self.write(key, Entry { is_tried: false, ..entry });
+        }
+
+        fn write(&mut self, key: AddressKey, entry: Entry) {
+            self.db_store.set(key, entry).unwrap();
+            self.addresses.insert(key, entry);
         }
 
         pub fn get(&self, address: NetAddress) -> Entry {
@@ -400,10 +704,56 @@ mod address_store_with_cache {
         }
 
         fn remove_by_key(&mut self, key: AddressKey) {
-            self.addresses.remove(&key);
+            if let Some(entry) = self.addresses.remove(&key) {
+                let (table, slot) = self.slot_of(&entry);
+                if table[slot] == Some(key) {
+                    table[slot] = None;
+                }
+            }
             self.db_store.remove(key).unwrap()
         }
 
+        fn slot_of(&mut self, entry: &Entry) -> (&mut Vec<Option<AddressKey>>, usize) {
+            if entry.is_tried {
+                let slot = self.tried_slot(&entry.address);
+                (&mut self.tried_table, slot)
+            } else {
+                let slot = self.new_slot(&entry.address, &entry.source);
+                (&mut self.new_table, slot)
+            }
+        }
+
+        /// The addresses learned from a source netgroup spread over [`NEW_BUCKETS_PER_SOURCE_GROUP`] buckets
+        fn new_slot(&self, address: &NetAddress, source: &IpAddress) -> usize {
+            let group = address.prefix_bucket().as_u64().to_le_bytes();
+            let source_group = source.prefix_bucket().as_u64().to_le_bytes();
+            let spread = self.hash(NEW_GROUP_TAG, &[&group, &source_group]) % NEW_BUCKETS_PER_SOURCE_GROUP;
+            let bucket = self.hash(NEW_BUCKET_TAG, &[&source_group, &spread.to_le_bytes()]) % NEW_BUCKET_COUNT as u64;
+            self.slot(bucket, address)
+        }
+
+        /// The addresses of a netgroup spread over [`TRIED_BUCKETS_PER_GROUP`] buckets
+        fn tried_slot(&self, address: &NetAddress) -> usize {
+            let group = address.prefix_bucket().as_u64().to_le_bytes();
+            let spread = self.hash(TRIED_GROUP_TAG, &[&address_bytes(address)]) % TRIED_BUCKETS_PER_GROUP;
+            let bucket = self.hash(TRIED_BUCKET_TAG, &[&group, &spread.to_le_bytes()]) % TRIED_BUCKET_COUNT as u64;
+            self.slot(bucket, address)
+        }
+
+        fn slot(&self, bucket: u64, address: &NetAddress) -> usize {
+            let position = self.hash(POSITION_TAG, &[&bucket.to_le_bytes(), &address_bytes(address)]) % BUCKET_SIZE as u64;
+            (bucket * BUCKET_SIZE as u64 + position) as usize
+        }
+
+        fn hash(&self, tag: u8, data: &[&[u8]]) -> u64 {
+            let mut state = blake2b_simd::Params::new().hash_length(8).key(&self.key).to_state();
+            state.update(&[tag]);
+            data.iter().for_each(|bytes| {
+                state.update(bytes);
+            });
+            u64::from_le_bytes(state.finalize().as_bytes().try_into().unwrap())
+        }
+
         pub fn iterate_addresses(&self) -> impl Iterator<Item = NetAddress> + '_ {
             self.addresses.values().map(|entry| entry.address)
         }
@@ -411,40 +761,51 @@ mod address_store_with_cache {
         /// This iterator functions as the node's ip routing selection algo.
         /// It first adjusts in respect to the number of connection failures of each ip address,
         /// whereby each connection failure (up to [`MAX_CONNECTION_FAILED_COUNT`]) reduces an ip's selection weight by a factor of 64,
-        /// Afterwards the weights are normalized uniformly over the ip's [`PrefixBucket`] size.
+        /// Afterwards the weights are normalized uniformly over the ip's [`PrefixBucket`] size within its table, and
+        /// the tables are given the same total weight, so that tried and new addresses are selected evenly.
         ///
         /// This ensures a distributed selection across the global network, while respecting
         /// weight reductions due to ip connection failures.
         ///
         /// The exact weight formula for any given ip, is as follows:
         ///```ignore
-        ///         ip_weight = (64 ^ (x - y)) / n
+        ///         ip_weight = (64 ^ (x - y)) / n / w
         ///
         ///             whereby:
         ///                 x: max allowed connection failures.
         ///                 y: connection failures of the ip.
-        ///                 n: number of ips with the same prefix bytes.
+        ///                 n: number of ips of the table with the same prefix bytes.
+        ///                 w: sum of the weights of the table prior to this normalization.
         ///```
         pub fn iterate_prioritized_random_addresses(
             &self,
             exceptions: HashSet<NetAddress>,
         ) -> impl ExactSizeIterator<Item = NetAddress> {
             let exceptions: HashSet<AddressKey> = exceptions.into_iter().map(|addr| addr.into()).collect();
-            let mut prefix_counter: HashMap<PrefixBucket, usize> = HashMap::new();
-            let (mut weights, filtered_addresses): (Vec<f64>, Vec<NetAddress>) = self
-                .addresses
-                .iter()
-                .filter(|(addr_key, _)| !exceptions.contains(addr_key))
-                .map(|(_, e)| {
-                    let count = prefix_counter.entry(e.address.prefix_bucket()).or_insert(0);
-                    *count += 1;
-                    (64f64.powf((MAX_CONNECTION_FAILED_COUNT + 1 - e.connection_failed_count) as f64), e.address)
-                })
-                .unzip();
-
-            // Divide weights by size of bucket of the prefix bytes, to partially uniform the distribution over prefix buckets.
-            for (i, address) in filtered_addresses.iter().enumerate() {
-                *weights.get_mut(i).unwrap() /= *prefix_counter.get(&address.prefix_bucket()).unwrap() as f64;
+            let (mut weights, mut filtered_addresses) = (Vec::new(), Vec::new());
+            for table in [&self.new_table, &self.tried_table] {
+                let mut prefix_counter: HashMap<PrefixBucket, usize> = HashMap::new();
+                let (table_weights, table_addresses): (Vec<f64>, Vec<NetAddress>) = table
+                    .iter()
+                    .flatten()
+                    .filter(|&addr_key| !exceptions.contains(addr_key))
+                    .map(|addr_key| {
+                        let e = &self.addresses[addr_key];
+                        let count = prefix_counter.entry(e.address.prefix_bucket()).or_insert(0);
+                        *count += 1;
+                        (64f64.powf((MAX_CONNECTION_FAILED_COUNT + 1 - e.connection_failed_count) as f64), e.address)
+                    })
+                    .unzip();
+
+                // Divide weights by size of bucket of the prefix bytes, to partially uniform the distribution over prefix buckets.
+                let table_weights = table_weights
+                    .into_iter()
+                    .zip(table_addresses.iter())
+                    .map(|(weight, address)| weight / *prefix_counter.get(&address.prefix_bucket()).unwrap() as f64)
+                    .collect_vec();
+                let total: f64 = table_weights.iter().sum();
+                weights.extend(table_weights.into_iter().map(|weight| weight / total));
+                filtered_addresses.extend(table_addresses);
             }
 
             RandomWeightedIterator::new(weights, filtered_addresses)
@@ -457,6 +818,17 @@ mod address_store_with_cache {
         }
     }
 
+    fn address_bytes(address: &NetAddress) -> [u8; 18] {
+        let ip = match address.ip.0 {
+            IpAddr::V4(ip) => ip.to_ipv6_mapped(),
+            IpAddr::V6(ip) => ip,
+        };
+        let mut bytes = [0; 18];
+        bytes[..16].copy_from_slice(&ip.octets());
+        bytes[16..].copy_from_slice(&address.port.to_le_bytes());
+        bytes
+    }
+
     pub fn new(db: Arc<DB>) -> Store {
         Store::new(db)
     }
@@ -514,7 +886,8 @@ mod address_store_with_cache {
         use std::str::FromStr;
 
         use super::*;
-        use address_manager::AddressManager;
+        use crate::stores::address_store::LegacyEntry;
+        use address_manager::{AddressManager, MAX_ADDRESSES};
         use kaspa_consensus_core::config::{params::SIMNET_PARAMS, Config};
         use kaspa_core::task::tick::TickService;
         use kaspa_database::create_temp_db;
@@ -523,6 +896,76 @@ mod address_store_with_cache {
         use rv::{dist::Uniform, misc::ks_test as one_way_ks_test, traits::Cdf};
         use std::net::{IpAddr, Ipv6Addr};
 
+        #[test]
+        fn test_new_and_tried_tables() {
+            let db = create_temp_db!(ConnBuilder::default().with_files_limit(10));
+            let config = Arc::new(Config::new(SIMNET_PARAMS));
+            let (am, _) = AddressManager::new(config.clone(), db.1.clone(), Arc::new(TickService::default()));
+            let mut am_guard = am.lock();
+
+            // The addresses relayed from a single netgroup take at most a fixed share of the new table
+            let attacker: IpAddress = IpAddress::from_str("1.2.3.4").unwrap();
+            for i in 0..20_000u32 {
+                let [a, b, c, d] = (0x05000000 + i * 7).to_be_bytes();
+                am_guard.add_address(NetAddress::new(IpAddress::from_str(&format!("{a}.{b}.{c}.{d}")).unwrap(), 16111), attacker);
+            }
+            let attacker_count = am_guard.iterate_addresses().count();
+            assert!(attacker_count <= NEW_BUCKETS_PER_SOURCE_GROUP as usize * BUCKET_SIZE);
+
+            // Which leaves room for the addresses relayed from other netgroups
+            let honest: IpAddress = IpAddress::from_str("9.8.7.6").unwrap();
+            let honest_addresses = (0..200u8).map(|i| NetAddress::new(IpAddress::from_str(&format!("{i}.1.1.1")).unwrap(), 16111));
+            honest_addresses.clone().for_each(|address| am_guard.add_address(address, honest));
+            let honest_count = honest_addresses.clone().filter(|&address| am_guard.address_store.has(address)).count();
+            assert!(honest_count >= 100, "only {honest_count} honest addresses were kept");
+
+            // Connected addresses move to the tried table
+            let tried = honest_addresses.clone().find(|&address| am_guard.address_store.has(address)).unwrap();
+            am_guard.mark_connection_failure(tried);
+            assert_eq!(am_guard.address_store.get(tried).connection_failed_count, 2);
+            am_guard.mark_connection_success(tried);
+            let entry = am_guard.address_store.get(tried);
+            assert!(entry.is_tried);
+            assert_eq!(entry.connection_failed_count, 0);
+
+            let addresses: HashSet<NetAddress> = am_guard.iterate_addresses().collect();
+            drop(am_guard);
+            drop(am);
+
+            // The placement is deterministic, so all the addresses are restored
+            let (am, _) = AddressManager::new(config.clone(), db.1.clone(), Arc::new(TickService::default()));
+            assert_eq!(am.lock().iterate_addresses().collect::<HashSet<_>>(), addresses);
+            assert!(am.lock().address_store.get(tried).is_tried);
+        }
+
+        #[test]
+        fn test_legacy_addresses_migration() {
+            let db = create_temp_db!(ConnBuilder::default().with_files_limit(10));
+            let (unfailed, failed) = (
+                NetAddress::new(IpAddress::from_str("1.1.1.1").unwrap(), 16111),
+                NetAddress::new(IpAddress::from_str("2.2.2.2").unwrap(), 16111),
+            );
+            let mut db_store = DbAddressesStore::new(db.1.clone(), CachePolicy::Empty);
+            db_store.set_legacy(unfailed.into(), LegacyEntry { connection_failed_count: 0, address: unfailed }).unwrap();
+            db_store.set_legacy(failed.into(), LegacyEntry { connection_failed_count: 2, address: failed }).unwrap();
+
+            let (am, _) = AddressManager::new(Arc::new(Config::new(SIMNET_PARAMS)), db.1.clone(), Arc::new(TickService::default()));
+            let mut am_guard = am.lock();
+            // A legacy entry with no failed connection may never have been dialed, so it lands in the new table too
+            let entry = am_guard.address_store.get(unfailed);
+            assert!(!entry.is_tried);
+            assert_eq!(entry.connection_failed_count, 0);
+            assert_eq!(entry.source, unfailed.ip);
+            let entry = am_guard.address_store.get(failed);
+            assert!(!entry.is_tried);
+            assert_eq!(entry.connection_failed_count, 2);
+            assert_eq!(entry.source, failed.ip);
+
+            // Only a successful connection moves an address to the tried table
+            am_guard.mark_connection_success(unfailed);
+            assert!(am_guard.address_store.get(unfailed).is_tried);
+        }
+
         #[test]
         fn test_weighted_iterator() {
             let address = NetAddress::new(IpAddr::V6(Ipv6Addr::LOCALHOST).into(), 1);
@@ -561,14 +1004,12 @@ mod address_store_with_cache {
                 for current_suffix_bytes in 0..current_bucket_size {
                     let current_ip_bytes =
                         [current_prefix_bytes.to_be_bytes(), current_suffix_bytes.to_be_bytes()].concat().to_owned();
-                    am_guard.add_address(NetAddress::new(
-                        IpAddress::from_str(&format!(
-                            "{0}.{1}.{2}.{3}",
-                            current_ip_bytes[0], current_ip_bytes[1], current_ip_bytes[2], current_ip_bytes[3]
-                        ))
-                        .unwrap(),
-                        16111,
-                    ));
+                    let ip = IpAddress::from_str(&format!(
+                        "{0}.{1}.{2}.{3}",
+                        current_ip_bytes[0], current_ip_bytes[1], current_ip_bytes[2], current_ip_bytes[3]
+                    ))
+                    .unwrap();
+                    am_guard.add_address(NetAddress::new(ip, 16111), ip);
                     num_of_addresses += 1;
                 }
 
@@ -622,3 +1063,41 @@ mod address_store_with_cache {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+    use kaspa_consensus_core::config::params::SIMNET_PARAMS;
+    use kaspa_database::create_temp_db;
+    use kaspa_database::prelude::ConnBuilder;
+    use stores::banned_address_store::LegacyConnectionBanTimestamp;
+
+    #[test]
+    fn test_legacy_bans_migration() {
+        let db = create_temp_db!(ConnBuilder::default().with_files_limit(10));
+        let (recent, expired) = (IpAddress::from_str("1.1.1.1").unwrap(), IpAddress::from_str("2.2.2.2").unwrap());
+        let hour = 60 * 60 * 1000;
+        let mut db_store = DbBannedAddressesStore::new(db.1.clone(), CachePolicy::Empty);
+        db_store.set_legacy(recent.into(), LegacyConnectionBanTimestamp(unix_now() - hour)).unwrap();
+        db_store.set_legacy(expired.into(), LegacyConnectionBanTimestamp(unix_now() - 25 * hour)).unwrap();
+
+        let (am, _) = AddressManager::new(Arc::new(Config::new(SIMNET_PARAMS)), db.1.clone(), Arc::new(TickService::default()));
+        let mut am_guard = am.lock();
+        // Legacy bans expire a day after they started
+        let expiry = am_guard.banned_address_store.get(recent.into()).unwrap().0;
+        assert!(expiry.abs_diff(unix_now() + 23 * hour) < hour);
+        assert!(am_guard.is_banned(recent));
+        assert!(!am_guard.is_banned(expired));
+        assert_eq!(am_guard.get_all_banned_addresses(), vec![recent]);
+        drop(am_guard);
+        drop(am);
+
+        // The legacy entries are gone and the migrated bans persist
+        let mut db_store = DbBannedAddressesStore::new(db.1.clone(), CachePolicy::Empty);
+        assert!(db_store.take_legacy_entries().unwrap().is_empty());
+        let (am, _) = AddressManager::new(Arc::new(Config::new(SIMNET_PARAMS)), db.1, Arc::new(TickService::default()));
+        assert!(am.lock().is_banned(recent));
+    }
+}
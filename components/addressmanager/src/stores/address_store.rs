@@ -1,10 +1,10 @@
 use kaspa_database::{
     prelude::DB,
-    prelude::{CachePolicy, StoreError, StoreResult},
-    prelude::{CachedDbAccess, DirectDbWriter},
+    prelude::{CachePolicy, StoreError, StoreResult, StoreResultExtensions},
+    prelude::{CachedDbAccess, CachedDbItem, DirectDbWriter},
     registry::DatabaseStorePrefixes,
 };
-use kaspa_utils::mem_size::MemSizeEstimator;
+use kaspa_utils::{mem_size::MemSizeEstimator, networking::IpAddress};
 use serde::{Deserialize, Serialize};
 use std::net::Ipv6Addr;
 use std::{error::Error, fmt::Display, sync::Arc};
@@ -16,10 +16,26 @@ use crate::NetAddress;
 pub struct Entry {
     pub connection_failed_count: u64,
     pub address: NetAddress,
+    /// The IP the address was learned from, which determines its bucket in the new table
+    pub source: IpAddress,
+    /// Whether the address was ever connected to successfully, placing it in the tried table
+    pub is_tried: bool,
 }
 
 impl MemSizeEstimator for Entry {}
 
+/// The layout of the entries prior to the new and tried tables, migrated when first loaded
+#[derive(Clone, Copy, Serialize, Deserialize)]
+pub struct LegacyEntry {
+    pub connection_failed_count: u64,
+    pub address: NetAddress,
+}
+
+impl MemSizeEstimator for LegacyEntry {}
+
+/// The secret of the node keying the placement of addresses in buckets
+pub type BucketingKey = [u8; 32];
+
 pub trait AddressesStoreReader {
     #[allow(dead_code)]
     fn get(&self, key: AddressKey) -> Result<Entry, StoreError>;
@@ -76,11 +92,39 @@ impl From<DbAddressKey> for AddressKey {
 pub struct DbAddressesStore {
     db: Arc<DB>,
     access: CachedDbAccess<DbAddressKey, Entry>,
+    legacy_access: CachedDbAccess<DbAddressKey, LegacyEntry>,
+    bucketing_key: CachedDbItem<BucketingKey>,
 }
 
 impl DbAddressesStore {
     pub fn new(db: Arc<DB>, cache_policy: CachePolicy) -> Self {
-        Self { db: Arc::clone(&db), access: CachedDbAccess::new(db, cache_policy, DatabaseStorePrefixes::Addresses.into()) }
+        Self {
+            db: Arc::clone(&db),
+            access: CachedDbAccess::new(db.clone(), cache_policy, DatabaseStorePrefixes::Addresses.into()),
+            legacy_access: CachedDbAccess::new(db.clone(), CachePolicy::Empty, DatabaseStorePrefixes::Addresses.into()),
+            bucketing_key: CachedDbItem::new(db, DatabaseStorePrefixes::AddressBucketingKey.into()),
+        }
+    }
+
+    /// Returns the bucketing key, which is only missing prior to the migration of the legacy entries
+    pub fn bucketing_key(&self) -> Option<BucketingKey> {
+        self.bucketing_key.read().unwrap_option()
+    }
+
+    pub fn set_bucketing_key(&mut self, key: BucketingKey) -> StoreResult<()> {
+        self.bucketing_key.write(DirectDbWriter::new(&self.db), &key)
+    }
+
+    #[cfg(test)]
+    pub fn set_legacy(&mut self, key: AddressKey, entry: LegacyEntry) -> StoreResult<()> {
+        self.legacy_access.write(DirectDbWriter::new(&self.db), key.into(), entry)
+    }
+
+    /// Removes and returns all the entries stored in the legacy layout
+    pub fn take_legacy_entries(&mut self) -> StoreResult<Vec<LegacyEntry>> {
+        let entries = self.legacy_access.iterator().filter_map(|res| res.ok().map(|(_, entry)| entry)).collect();
+        self.access.delete_all(DirectDbWriter::new(&self.db))?;
+        Ok(entries)
     }
 
     pub fn iterator(&self) -> impl Iterator<Item = Result<(AddressKey, Entry), Box<dyn Error>>> + '_ {
@@ -115,6 +159,6 @@ impl AddressesStore for DbAddressesStore {
 
     fn set_failed_count(&mut self, key: AddressKey, connection_failed_count: u64) -> StoreResult<()> {
         let entry = self.get(key)?;
-        self.set(key, Entry { connection_failed_count, address: entry.address })
+        self.set(key, Entry { connection_failed_count, ..entry })
     }
 }
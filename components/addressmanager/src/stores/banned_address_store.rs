@@ -8,17 +8,25 @@ use serde::{Deserialize, Serialize};
 use std::net::{IpAddr, Ipv6Addr};
 use std::{error::Error, fmt::Display, sync::Arc};
 
+/// The unix time in milliseconds at which a ban expires
 #[derive(Clone, Copy, Serialize, Deserialize)]
-pub struct ConnectionBanTimestamp(pub u64);
+pub struct ConnectionBanExpiry(pub u64);
 
-impl MemSizeEstimator for ConnectionBanTimestamp {}
+impl MemSizeEstimator for ConnectionBanExpiry {}
+
+/// The unix time in milliseconds at which a ban started, as stored prior to the bans expiring at a given time.
+/// Both being bare numbers, the expiries are kept under their own prefix and the legacy entries get migrated.
+#[derive(Clone, Copy, Serialize, Deserialize)]
+pub struct LegacyConnectionBanTimestamp(pub u64);
+
+impl MemSizeEstimator for LegacyConnectionBanTimestamp {}
 
 pub trait BannedAddressesStoreReader {
-    fn get(&self, address: IpAddr) -> Result<ConnectionBanTimestamp, StoreError>;
+    fn get(&self, address: IpAddr) -> Result<ConnectionBanExpiry, StoreError>;
 }
 
 pub trait BannedAddressesStore: BannedAddressesStoreReader {
-    fn set(&mut self, ip: IpAddr, timestamp: ConnectionBanTimestamp) -> StoreResult<()>;
+    fn set(&mut self, ip: IpAddr, expiry: ConnectionBanExpiry) -> StoreResult<()>;
     fn remove(&mut self, ip: IpAddr) -> StoreResult<()>;
 }
 
@@ -69,21 +77,46 @@ impl From<AddressKey> for IpAddr {
 #[derive(Clone)]
 pub struct DbBannedAddressesStore {
     db: Arc<DB>,
-    access: CachedDbAccess<AddressKey, ConnectionBanTimestamp>,
+    access: CachedDbAccess<AddressKey, ConnectionBanExpiry>,
+    legacy_access: CachedDbAccess<AddressKey, LegacyConnectionBanTimestamp>,
 }
 
 impl DbBannedAddressesStore {
     pub fn new(db: Arc<DB>, cache_policy: CachePolicy) -> Self {
-        Self { db: Arc::clone(&db), access: CachedDbAccess::new(db, cache_policy, DatabaseStorePrefixes::BannedAddresses.into()) }
+        Self {
+            db: Arc::clone(&db),
+            access: CachedDbAccess::new(db.clone(), cache_policy, DatabaseStorePrefixes::BannedAddressExpiries.into()),
+            legacy_access: CachedDbAccess::new(db, CachePolicy::Empty, DatabaseStorePrefixes::BannedAddresses.into()),
+        }
+    }
+
+    #[cfg(test)]
+    pub fn set_legacy(&mut self, ip: IpAddr, timestamp: LegacyConnectionBanTimestamp) -> StoreResult<()> {
+        self.legacy_access.write(DirectDbWriter::new(&self.db), ip.into(), timestamp)
+    }
+
+    /// Removes and returns all the bans stored in the legacy layout
+    pub fn take_legacy_entries(&mut self) -> StoreResult<Vec<(IpAddr, LegacyConnectionBanTimestamp)>> {
+        let entries = self
+            .legacy_access
+            .iterator()
+            .filter_map(|res| {
+                let (key_bytes, timestamp) = res.ok()?;
+                let ip: IpAddr = AddressKey(<[u8; ADDRESS_KEY_SIZE]>::try_from(&key_bytes[..]).ok()?).into();
+                Some((ip, timestamp))
+            })
+            .collect();
+        self.legacy_access.delete_all(DirectDbWriter::new(&self.db))?;
+        Ok(entries)
     }
 
-    pub fn iterator(&self) -> impl Iterator<Item = Result<(IpAddr, ConnectionBanTimestamp), Box<dyn Error>>> + '_ {
+    pub fn iterator(&self) -> impl Iterator<Item = Result<(IpAddr, ConnectionBanExpiry), Box<dyn Error>>> + '_ {
         self.access.iterator().map(|iter_result| match iter_result {
-            Ok((key_bytes, connection_ban_timestamp)) => match <[u8; ADDRESS_KEY_SIZE]>::try_from(&key_bytes[..]) {
+            Ok((key_bytes, connection_ban_expiry)) => match <[u8; ADDRESS_KEY_SIZE]>::try_from(&key_bytes[..]) {
                 Ok(address_key_slice) => {
                     let addr_key = AddressKey(address_key_slice);
                     let address: IpAddr = addr_key.into();
-                    Ok((address, connection_ban_timestamp))
+                    Ok((address, connection_ban_expiry))
                 }
                 Err(e) => Err(e.into()),
             },
@@ -93,14 +126,14 @@ impl DbBannedAddressesStore {
 }
 
 impl BannedAddressesStoreReader for DbBannedAddressesStore {
-    fn get(&self, ip: IpAddr) -> Result<ConnectionBanTimestamp, StoreError> {
+    fn get(&self, ip: IpAddr) -> Result<ConnectionBanExpiry, StoreError> {
         self.access.read(ip.into())
     }
 }
 
 impl BannedAddressesStore for DbBannedAddressesStore {
-    fn set(&mut self, ip: IpAddr, timestamp: ConnectionBanTimestamp) -> StoreResult<()> {
-        self.access.write(DirectDbWriter::new(&self.db), ip.into(), timestamp)
+    fn set(&mut self, ip: IpAddr, expiry: ConnectionBanExpiry) -> StoreResult<()> {
+        self.access.write(DirectDbWriter::new(&self.db), ip.into(), expiry)
     }
 
     fn remove(&mut self, ip: IpAddr) -> StoreResult<()> {
@@ -1,9 +1,10 @@
 use std::net::{IpAddr, Ipv6Addr};
 
-pub use kaspa_utils::networking::NetAddress;
+pub use kaspa_utils::networking::{NamedNetAddress, NetAddress};
 
 pub(super) mod address_store;
 pub(super) mod banned_address_store;
+pub(super) mod named_address_store;
 
 #[derive(PartialEq, Eq, Hash, Clone, Copy)]
 pub struct AddressKey(Ipv6Addr, u16);
@@ -0,0 +1,70 @@
+use kaspa_database::{
+    prelude::DB,
+    prelude::{CachePolicy, StoreResult},
+    prelude::{CachedDbAccess, DirectDbWriter},
+    registry::DatabaseStorePrefixes,
+};
+use kaspa_utils::{mem_size::MemSizeEstimator, networking::NamedNetAddress};
+use serde::{Deserialize, Serialize};
+use std::{error::Error, fmt::Display, sync::Arc};
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct NamedEntry {
+    pub connection_failed_count: u64,
+    pub address: NamedNetAddress,
+}
+
+impl MemSizeEstimator for NamedEntry {}
+
+pub trait NamedAddressesStore {
+    fn set(&mut self, address: &NamedNetAddress, entry: NamedEntry) -> StoreResult<()>;
+    fn remove(&mut self, address: &NamedNetAddress) -> StoreResult<()>;
+}
+
+/// Named addresses are keyed by their `host:port` representation
+#[derive(Eq, Hash, PartialEq, Debug, Clone)]
+struct DbNamedAddressKey(String);
+
+impl AsRef<[u8]> for DbNamedAddressKey {
+    fn as_ref(&self) -> &[u8] {
+        self.0.as_bytes()
+    }
+}
+
+impl Display for DbNamedAddressKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl From<&NamedNetAddress> for DbNamedAddressKey {
+    fn from(address: &NamedNetAddress) -> Self {
+        Self(address.to_string())
+    }
+}
+
+#[derive(Clone)]
+pub struct DbNamedAddressesStore {
+    db: Arc<DB>,
+    access: CachedDbAccess<DbNamedAddressKey, NamedEntry>,
+}
+
+impl DbNamedAddressesStore {
+    pub fn new(db: Arc<DB>, cache_policy: CachePolicy) -> Self {
+        Self { db: Arc::clone(&db), access: CachedDbAccess::new(db, cache_policy, DatabaseStorePrefixes::NamedAddresses.into()) }
+    }
+
+    pub fn iterator(&self) -> impl Iterator<Item = Result<NamedEntry, Box<dyn Error>>> + '_ {
+        self.access.iterator().map(|iter_result| iter_result.map(|(_, entry)| entry))
+    }
+}
+
+impl NamedAddressesStore for DbNamedAddressesStore {
+    fn set(&mut self, address: &NamedNetAddress, entry: NamedEntry) -> StoreResult<()> {
+        self.access.write(DirectDbWriter::new(&self.db), address.into(), entry)
+    }
+
+    fn remove(&mut self, address: &NamedNetAddress) -> StoreResult<()> {
+        self.access.delete(DirectDbWriter::new(&self.db), address.into())
+    }
+}
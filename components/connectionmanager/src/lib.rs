@@ -1,3 +1,5 @@
+mod misbehavior;
+
 use std::{
     cmp::min,
     collections::{HashMap, HashSet},
@@ -9,10 +11,14 @@ use std::{
 use duration_string::DurationString;
 use futures_util::future::{join_all, try_join_all};
 use itertools::Itertools;
-use kaspa_addressmanager::{AddressManager, NetAddress};
-use kaspa_core::{debug, info, warn};
-use kaspa_p2p_lib::{common::ProtocolError, ConnectionError, Peer};
-use kaspa_utils::triggers::SingleTrigger;
+use kaspa_addressmanager::{AddressManager, NamedNetAddress, NetAddress};
+use kaspa_core::{debug, info, time::unix_now, warn};
+use kaspa_p2p_lib::{
+    common::{ProtocolError, MISBEHAVIOR_BAN_THRESHOLD},
+    ConnectionError, Peer,
+};
+use kaspa_utils::{networking::PrefixBucket, triggers::SingleTrigger};
+use misbehavior::MisbehaviorScores;
 use parking_lot::Mutex as ParkingLotMutex;
 use rand::{seq::SliceRandom, thread_rng};
 use tokio::{
@@ -24,6 +30,9 @@ use tokio::{
     time::{interval, MissedTickBehavior},
 };
 
+/// Duration of the ban of a peer whose misbehavior score reached [`MISBEHAVIOR_BAN_THRESHOLD`]
+const MISBEHAVIOR_BAN_DURATION: Duration = Duration::from_secs(60 * 60);
+
 pub struct ConnectionManager {
     p2p_adaptor: Arc<kaspa_p2p_lib::Adaptor>,
     outbound_target: usize,
@@ -32,6 +41,7 @@ pub struct ConnectionManager {
     default_port: u16,
     address_manager: Arc<ParkingLotMutex<AddressManager>>,
     connection_requests: TokioMutex<HashMap<SocketAddr, ConnectionRequest>>,
+    misbehavior_scores: ParkingLotMutex<MisbehaviorScores>,
     force_next_iteration: UnboundedSender<()>,
     shutdown_signal: SingleTrigger,
 }
@@ -65,6 +75,7 @@ impl ConnectionManager {
             inbound_limit,
             address_manager,
             connection_requests: Default::default(),
+            misbehavior_scores: Default::default(),
             force_next_iteration: tx,
             shutdown_signal: SingleTrigger::new(),
             dns_seeders,
@@ -95,12 +106,16 @@ impl ConnectionManager {
 
     async fn handle_event(self: Arc<Self>) {
         debug!("Starting connection loop iteration");
-        let peers = self.p2p_adaptor.active_peers();
+        // Peers dialed by host name all share the socket address of the proxy, so they are tracked by name
+        let (named_peers, peers): (Vec<Peer>, Vec<Peer>) =
+            self.p2p_adaptor.active_peers().into_iter().partition(|peer| peer.named_address().is_some());
         let peer_by_address: HashMap<SocketAddr, Peer> = peers.into_iter().map(|peer| (peer.net_address(), peer)).collect();
+        let active_named: HashSet<NamedNetAddress> = named_peers.iter().filter_map(|peer| peer.named_address().cloned()).collect();
 
         self.handle_connection_requests(&peer_by_address).await;
-        self.handle_outbound_connections(&peer_by_address).await;
+        self.handle_outbound_connections(&peer_by_address, active_named).await;
         self.handle_inbound_connections(&peer_by_address).await;
+        self.misbehavior_scores.lock().prune(unix_now());
     }
 
     pub async fn add_connection_request(&self, address: SocketAddr, is_permanent: bool) {
@@ -159,14 +174,22 @@ impl ConnectionManager {
         *requests = new_requests;
     }
 
-    async fn handle_outbound_connections(self: &Arc<Self>, peer_by_address: &HashMap<SocketAddr, Peer>) {
+    async fn handle_outbound_connections(
+        self: &Arc<Self>,
+        peer_by_address: &HashMap<SocketAddr, Peer>,
+        active_named: HashSet<NamedNetAddress>,
+    ) {
         let active_outbound: HashSet<kaspa_addressmanager::NetAddress> =
             peer_by_address.values().filter(|peer| peer.is_outbound()).map(|peer| peer.net_address().into()).collect();
-        if active_outbound.len() >= self.outbound_target {
+        if active_outbound.len() + active_named.len() >= self.outbound_target {
             return;
         }
 
-        let mut missing_connections = self.outbound_target - active_outbound.len();
+        let mut missing_connections = self.outbound_target - active_outbound.len() - active_named.len();
+        // Outbound peers are kept in distinct netgroups, so that an attacker controlling a few netgroups cannot take over
+        // our outbound connections. Addresses which are not publicly routable, such as those of local networks, are exempt.
+        let mut outbound_groups: HashSet<PrefixBucket> =
+            active_outbound.iter().filter(|addr| addr.ip.is_publicly_routable()).map(|addr| addr.prefix_bucket()).collect();
         let mut addr_iter = self.address_manager.lock().iterate_prioritized_random_addresses(active_outbound);
 
         let mut progressing = true;
@@ -178,10 +201,16 @@ impl ConnectionManager {
             let mut addrs_to_connect = Vec::with_capacity(missing_connections);
             let mut jobs = Vec::with_capacity(missing_connections);
             for _ in 0..missing_connections {
-                let Some(net_addr) = addr_iter.next() else {
+                let Some(net_addr) = addr_iter
+                    .by_ref()
+                    .find(|addr| !addr.ip.is_publicly_routable() || !outbound_groups.contains(&addr.prefix_bucket()))
+                else {
                     connecting = false;
                     break;
                 };
+                if net_addr.ip.is_publicly_routable() {
+                    outbound_groups.insert(net_addr.prefix_bucket());
+                }
                 let socket_addr = SocketAddr::new(net_addr.ip.into(), net_addr.port).to_string();
                 debug!("Connecting to {}", &socket_addr);
                 addrs_to_connect.push(net_addr);
@@ -221,12 +250,18 @@ impl ConnectionManager {
                     Err(err) => {
                         debug!("Failed connecting to {:?}, err: {}", net_addr, err);
                         self.address_manager.lock().mark_connection_failure(net_addr);
+                        outbound_groups.remove(&net_addr.prefix_bucket());
                     }
                 }
             }
         }
 
-        if missing_connections > 0 && !self.dns_seeders.is_empty() {
+        if missing_connections > 0 && self.p2p_adaptor.proxy().is_some() {
+            missing_connections = self.handle_named_outbound_connections(missing_connections, active_named).await;
+        }
+
+        // Seeders are resolved by the local resolver, which would leak the queries around the proxy
+        if missing_connections > 0 && !self.dns_seeders.is_empty() && self.p2p_adaptor.proxy().is_none() {
             if missing_connections > self.outbound_target / 2 {
                 // If we are missing more than half of our target, query all in parallel.
                 // This will always be the case on new node start-up and is the most resilient strategy in such a case.
@@ -238,6 +273,44 @@ impl ConnectionManager {
         }
     }
 
+    /// Dials addresses designated by host name, which are only reachable through the proxy.
+    /// Returns the number of outbound connections still missing.
+    async fn handle_named_outbound_connections(
+        self: &Arc<Self>,
+        mut missing_connections: usize,
+        active_named: HashSet<NamedNetAddress>,
+    ) -> usize {
+        let mut addr_iter = self.address_manager.lock().iterate_prioritized_random_named_addresses(active_named);
+        while missing_connections > 0 {
+            if self.shutdown_signal.trigger.is_triggered() {
+                break;
+            }
+            let addrs_to_connect = addr_iter.by_ref().take(missing_connections).collect_vec();
+            if addrs_to_connect.is_empty() {
+                break;
+            }
+            debug!("Connecting to {} named addresses through the proxy", addrs_to_connect.len());
+            let jobs = addrs_to_connect.iter().map(|named_addr| self.p2p_adaptor.connect_peer(named_addr.to_string())).collect_vec();
+            let results = join_all(jobs).await;
+            for (res, named_addr) in results.into_iter().zip(addrs_to_connect) {
+                match res {
+                    Ok(_) => {
+                        self.address_manager.lock().mark_named_connection_success(&named_addr);
+                        missing_connections -= 1;
+                    }
+                    Err(ConnectionError::ProtocolError(ProtocolError::PeerAlreadyExists(_))) => {
+                        debug!("Failed connecting to {}, peer already exists", named_addr);
+                    }
+                    Err(err) => {
+                        debug!("Failed connecting to {}, err: {}", named_addr, err);
+                        self.address_manager.lock().mark_named_connection_failure(&named_addr);
+                    }
+                }
+            }
+        }
+        missing_connections
+    }
+
     async fn handle_inbound_connections(self: &Arc<Self>, peer_by_address: &HashMap<SocketAddr, Peer>) {
         let active_inbound = peer_by_address.values().filter(|peer| !peer.is_outbound()).collect_vec();
         let active_inbound_len = active_inbound.len();
@@ -291,7 +364,7 @@ impl ConnectionManager {
         info!("Querying DNS seeder {}", seeder);
         // Since the DNS lookup protocol doesn't come with a port, we must assume that the default port is used.
         let addrs = match (seeder, self.default_port).to_socket_addrs() {
-            Ok(addrs) => addrs,
+            Ok(addrs) => addrs.collect_vec(),
             Err(e) => {
                 warn!("Error connecting to DNS seeder {}: {}", seeder, e);
                 return 0;
@@ -300,9 +373,13 @@ impl ConnectionManager {
 
         let addrs_len = addrs.len();
         info!("Retrieved {} addresses from DNS seeder {}", addrs_len, seeder);
+        // All the addresses of a seeder are attributed to a single source, so that they share a few buckets
+        let Some(source) = addrs.first().map(|addr| addr.ip().into()) else {
+            return 0;
+        };
         let mut amgr_lock = self.address_manager.lock();
         for addr in addrs {
-            amgr_lock.add_address(NetAddress::new(addr.ip().into(), addr.port()));
+            amgr_lock.add_address(NetAddress::new(addr.ip().into(), addr.port()), source);
         }
 
         addrs_len
@@ -315,12 +392,43 @@ impl ConnectionManager {
         if self.ip_has_permanent_connection(ip).await {
             return;
         }
+        self.disconnect_ip(ip).await;
+        self.address_manager.lock().ban(ip.into());
+    }
+
+    /// Adds the misbehavior penalty of the error a flow of the peer failed with to the score of the peer IP.
+    /// Once the score reaches [`MISBEHAVIOR_BAN_THRESHOLD`], disconnects from all the peers with that IP and
+    /// bans it for [`MISBEHAVIOR_BAN_DURATION`].
+    pub async fn report_misbehavior(&self, peer: &Peer, err: &ProtocolError) {
+        let penalty = err.misbehavior_penalty();
+        // Peers dialed by host name share the ip of the proxy, so they cannot be held accountable by ip
+        if penalty == 0 || peer.named_address().is_some() {
+            return;
+        }
+        let ip = peer.net_address().ip();
+        let score = self.misbehavior_scores.lock().add(ip, penalty, unix_now());
+        debug!("Peer {} misbehaved ({}), its misbehavior score is now {}", peer.net_address(), err, score);
+        if score < MISBEHAVIOR_BAN_THRESHOLD || self.ip_has_permanent_connection(ip).await {
+            return;
+        }
+        warn!("Banning {} for {} after reaching misbehavior score {}", ip, DurationString::from(MISBEHAVIOR_BAN_DURATION), score);
+        self.misbehavior_scores.lock().remove(ip);
+        self.disconnect_ip(ip).await;
+        self.address_manager.lock().ban_for(ip.into(), MISBEHAVIOR_BAN_DURATION);
+    }
+
+    /// Returns the current misbehavior score of the given IP.
+    pub fn misbehavior_score(&self, ip: IpAddr) -> u32 {
+        self.misbehavior_scores.lock().get(ip, unix_now())
+    }
+
+    async fn disconnect_ip(&self, ip: IpAddr) {
         for peer in self.p2p_adaptor.active_peers() {
-            if peer.net_address().ip() == ip {
+            // Peers dialed by host name share the ip of the proxy
+            if peer.named_address().is_none() && peer.net_address().ip() == ip {
                 self.p2p_adaptor.terminate(peer.key()).await;
             }
         }
-        self.address_manager.lock().ban(ip.into());
     }
 
     /// Returns whether the given address is banned.
@@ -338,3 +446,51 @@ impl ConnectionManager {
         self.connection_requests.lock().await.iter().any(|(address, request)| request.is_permanent && address.ip() == ip)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kaspa_consensus_core::config::{params::SIMNET_PARAMS, Config};
+    use kaspa_core::task::tick::TickService;
+    use kaspa_database::{create_temp_db, prelude::ConnBuilder};
+    use kaspa_p2p_lib::{echo::EchoFlowInitializer, Adaptor, Hub};
+    use kaspa_utils::networking::PeerId;
+    use std::time::Instant;
+
+    #[tokio::test]
+    async fn test_misbehavior_ban() {
+        let db = create_temp_db!(ConnBuilder::default().with_files_limit(10));
+        let (address_manager, _) = AddressManager::new(Arc::new(Config::new(SIMNET_PARAMS)), db.1, Arc::new(TickService::default()));
+        let adaptor = Adaptor::client_only(Hub::new(), Arc::new(EchoFlowInitializer::new()), Default::default(), None);
+        let connection_manager = ConnectionManager::new(adaptor, 0, 0, &[], 16111, address_manager);
+        let peer = |ip: &str| {
+            let address = SocketAddr::new(ip.parse().unwrap(), 16111);
+            (address, Peer::new(PeerId::default(), address, None, false, Instant::now(), Default::default(), 0, false))
+        };
+        let (address, misbehaving) = peer("1.2.3.4");
+        let (other_address, other) = peer("5.6.7.8");
+
+        // Errors not attributable to the peer carry no penalty
+        connection_manager.report_misbehavior(&misbehaving, &ProtocolError::ConnectionClosed).await;
+        assert_eq!(connection_manager.misbehavior_score(address.ip()), 0);
+
+        // Penalties add up across errors, and the peer gets banned once its score reaches the threshold
+        let err = ProtocolError::Timeout(Duration::from_secs(1));
+        let reports_to_ban = MISBEHAVIOR_BAN_THRESHOLD.div_ceil(err.misbehavior_penalty());
+        for reports in 1..reports_to_ban {
+            connection_manager.report_misbehavior(&misbehaving, &err).await;
+            assert_eq!(connection_manager.misbehavior_score(address.ip()), reports * err.misbehavior_penalty());
+            assert!(!connection_manager.is_banned(&address).await);
+        }
+        connection_manager.report_misbehavior(&misbehaving, &err).await;
+        assert!(connection_manager.is_banned(&address).await);
+        assert_eq!(connection_manager.misbehavior_score(address.ip()), 0);
+        assert!(!connection_manager.is_banned(&other_address).await);
+
+        // A misbehaving peer error alone reaches the threshold
+        connection_manager.report_misbehavior(&other, &ProtocolError::MisbehavingPeer("test".to_owned())).await;
+        assert!(connection_manager.is_banned(&other_address).await);
+
+        connection_manager.stop().await;
+    }
+}
@@ -0,0 +1,76 @@
+use std::{collections::HashMap, net::IpAddr};
+
+/// Points by which a misbehavior score decays every minute, so that the sporadic faults of an honest
+/// peer never add up to a ban
+const DECAY_PER_MINUTE: u64 = 1;
+
+const MINUTE_MILLIS: u64 = 60 * 1000;
+
+#[derive(Clone, Copy, Debug)]
+struct Score {
+    points: u32,
+    /// The unix time in milliseconds of the last update of `points`
+    updated_at: u64,
+}
+
+impl Score {
+    fn decayed(&self, now: u64) -> u32 {
+        let decay = now.saturating_sub(self.updated_at) / MINUTE_MILLIS * DECAY_PER_MINUTE;
+        (self.points as u64).saturating_sub(decay) as u32
+    }
+}
+
+/// Misbehavior scores of peers. Since a peer gets disconnected on its first protocol error, the scores are
+/// kept by IP and accumulate across connections.
+#[derive(Default, Debug)]
+pub(crate) struct MisbehaviorScores {
+    scores: HashMap<IpAddr, Score>,
+}
+
+impl MisbehaviorScores {
+    /// Adds the penalty to the score of the IP and returns the updated score
+    pub(crate) fn add(&mut self, ip: IpAddr, penalty: u32, now: u64) -> u32 {
+        let points = self.get(ip, now).saturating_add(penalty);
+        self.scores.insert(ip, Score { points, updated_at: now });
+        points
+    }
+
+    pub(crate) fn get(&self, ip: IpAddr, now: u64) -> u32 {
+        self.scores.get(&ip).map_or(0, |score| score.decayed(now))
+    }
+
+    pub(crate) fn remove(&mut self, ip: IpAddr) {
+        self.scores.remove(&ip);
+    }
+
+    /// Drops the scores which fully decayed
+    pub(crate) fn prune(&mut self, now: u64) {
+        self.scores.retain(|_, score| score.decayed(now) > 0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_misbehavior_scores() {
+        let (ip, other_ip): (IpAddr, IpAddr) = ("1.2.3.4".parse().unwrap(), "5.6.7.8".parse().unwrap());
+        let mut scores = MisbehaviorScores::default();
+        let now = 1_000 * MINUTE_MILLIS;
+
+        assert_eq!(scores.add(ip, 20, now), 20);
+        assert_eq!(scores.add(ip, 50, now + 5 * MINUTE_MILLIS), 65);
+        assert_eq!(scores.add(other_ip, 10, now), 10);
+
+        // Scores decay by whole minutes since their last update
+        assert_eq!(scores.get(ip, now + 5 * MINUTE_MILLIS + MINUTE_MILLIS - 1), 65);
+        assert_eq!(scores.get(ip, now + 15 * MINUTE_MILLIS), 55);
+        assert_eq!(scores.get(other_ip, now + 15 * MINUTE_MILLIS), 0);
+
+        scores.prune(now + 15 * MINUTE_MILLIS);
+        assert_eq!(scores.scores.len(), 1);
+        scores.remove(ip);
+        assert_eq!(scores.get(ip, now), 0);
+    }
+}
@@ -0,0 +1,177 @@
+use crate::{spawn_blocking, ConsensusManager, ConsensusProxy, StagingConsensus};
+use futures_util::future::{join_all, try_join_all};
+use kaspa_consensus_core::{
+    block::Block,
+    errors::{block::RuleError, consensus::ConsensusError, pruning::PruningImportError},
+    trusted::build_trusted_subdag,
+    Hash,
+};
+use kaspa_core::{info, warn};
+use kaspa_muhash::MuHash;
+use std::sync::Arc;
+use thiserror::Error;
+
+/// The number of blocks queued for processing before awaiting their validation
+const RESOLUTION_BATCH_SIZE: usize = 1000;
+
+/// The number of UTXOs read from the current consensus per pruning point UTXO set chunk
+const UTXO_CHUNK_SIZE: usize = 1000;
+
+#[derive(Error, Debug)]
+pub enum FinalityConflictResolutionError {
+    #[error("{0}")]
+    Consensus(#[from] ConsensusError),
+
+    #[error("{0}")]
+    Rule(#[from] RuleError),
+
+    #[error("{0}")]
+    PruningImport(#[from] PruningImportError),
+
+    #[error("the staging consensus is in use by an IBD with headers proof, a snapshot import or another resolution")]
+    StagingInUse,
+}
+
+type ResolutionResult<T> = std::result::Result<T, FinalityConflictResolutionError>;
+
+impl ConsensusManager {
+    /// Resolves a finality conflict in favor of `finality_block_hash` by rebuilding consensus from the current pruning
+    /// point with only the blocks which agree with it, i.e. its inclusive future and the past thereof. The rebuilt
+    /// consensus is staged and committed, after which the block bodies are replayed into it and the previous consensus
+    /// entries are deleted.
+    ///
+    /// The staging consensus is reserved throughout, so that the resolution fails with
+    /// [`FinalityConflictResolutionError::StagingInUse`] rather than racing another user of it.
+    pub async fn resolve_finality_conflict(self: &Arc<Self>, finality_block_hash: Hash) -> ResolutionResult<()> {
+        // Held until the previous consensus entries are deleted, as they occupy the inactive entries as well
+        let reservation = self.try_reserve_staging().ok_or(FinalityConflictResolutionError::StagingInUse)?;
+        let session = self.consensus().session().await;
+        let hashes = session.async_get_finality_conflict_resolution_hashes(finality_block_hash).await?;
+        let pruning_point_headers = session.async_pruning_point_headers().await;
+        // The pruning points list always starts with genesis, so a single entry means genesis is the pruning point
+        let is_genesis_pruning_point = pruning_point_headers.len() == 1;
+        info!(
+            "Resolving the finality conflict in favor of {} by rebuilding consensus over {} blocks",
+            finality_block_hash,
+            hashes.len()
+        );
+
+        let staging = self.new_staging_consensus_impl(&reservation, is_genesis_pruning_point);
+        match stage_resolution(&session, &staging, &hashes, is_genesis_pruning_point).await {
+            Ok(()) => spawn_blocking(|| staging.commit_retaining_inactive()).await.unwrap(),
+            Err(err) => {
+                spawn_blocking(|| staging.cancel()).await.unwrap();
+                return Err(err);
+            }
+        }
+        info!("Committed the rebuilt consensus, replaying block bodies");
+
+        // The previous consensus is inactive but still readable through `session`, so we use it to replay the bodies
+        let active = self.consensus().session().await;
+        let mut replayed = 0;
+        for chunk in hashes.chunks(RESOLUTION_BATCH_SIZE) {
+            let mut jobs = Vec::with_capacity(chunk.len());
+            for &hash in chunk {
+                // Header-only blocks have no body to replay
+                if let Ok(block) = session.async_get_block(hash).await {
+                    jobs.push((hash, active.validate_and_insert_block(block).virtual_state_task));
+                }
+            }
+            let (job_hashes, tasks): (Vec<_>, Vec<_>) = jobs.into_iter().unzip();
+            for (hash, result) in job_hashes.into_iter().zip(join_all(tasks).await) {
+                if let Err(err) = result {
+                    warn!("Replaying the body of block {} failed: {}", hash, err);
+                }
+            }
+            replayed += chunk.len();
+            info!("Replayed {} of {} blocks ({}%)", replayed, hashes.len(), replayed * 100 / hashes.len());
+        }
+
+        // Drop the previous consensus session so that the deletion below succeeds
+        drop(session);
+        let manager = self.clone();
+        spawn_blocking(move || manager.delete_inactive_consensus_entries()).await.unwrap();
+        drop(reservation);
+
+        active.async_notify_finality_conflict_resolved(finality_block_hash).await?;
+        info!("Finality conflict resolved in favor of {}", finality_block_hash);
+        Ok(())
+    }
+}
+
+/// Builds the staging consensus from the pruning point data of `current` followed by the headers of `hashes`
+async fn stage_resolution(
+    current: &ConsensusProxy,
+    staging: &StagingConsensus,
+    hashes: &[Hash],
+    is_genesis_pruning_point: bool,
+) -> ResolutionResult<()> {
+    let staging = staging.session().await;
+
+    if !is_genesis_pruning_point {
+        let proof = current.async_get_pruning_point_proof().await;
+        let pruning_points = current.async_pruning_point_headers().await;
+        let trusted_data = current.async_get_pruning_point_anticone_and_trusted_data().await?;
+        let mut blocks = Vec::with_capacity(trusted_data.anticone.len());
+        for &hash in trusted_data.anticone.iter() {
+            blocks.push(current.async_get_block(hash).await?);
+        }
+        let trusted_set = build_trusted_subdag(blocks, &trusted_data.daa_window_blocks, &trusted_data.ghostdag_blocks)
+            .ok_or(ConsensusError::General("missing ghostdag data for some trusted blocks"))?;
+        let trusted_set = staging
+            .clone()
+            .spawn_blocking(move |c| {
+                c.apply_pruning_proof(proof.as_ref().clone(), &trusted_set)?;
+                c.import_pruning_points(pruning_points);
+                ResolutionResult::Ok(trusted_set)
+            })
+            .await?;
+        for tb in trusted_set {
+            staging.validate_and_insert_trusted_block(tb).virtual_state_task.await?;
+        }
+    }
+
+    let mut staged = 0;
+    for chunk in hashes.chunks(RESOLUTION_BATCH_SIZE) {
+        let mut jobs = Vec::with_capacity(chunk.len());
+        for &hash in chunk {
+            let block = current.async_get_block_even_if_header_only(hash).await?;
+            jobs.push(staging.validate_and_insert_block(Block::from_header_arc(block.header)).virtual_state_task);
+        }
+        try_join_all(jobs).await?;
+        staged += chunk.len();
+        info!("Staged {} of {} headers ({}%)", staged, hashes.len(), staged * 100 / hashes.len());
+    }
+
+    if !is_genesis_pruning_point {
+        // Unlike in IBD, the pruning points are not validated against the headers: they were validated by the current
+        // consensus, while the headers of the conflicting chain may already point at a later pruning point. Such a
+        // pruning point is in the past of the finality block, and the staged consensus advances to it while the bodies
+        // are replayed.
+        let pruning_point = current.async_pruning_point().await;
+        let mut multiset = MuHash::new();
+        let mut from_outpoint = None;
+        let mut imported = 0;
+        loop {
+            let chunk =
+                current.async_get_pruning_point_utxos(pruning_point, from_outpoint, UTXO_CHUNK_SIZE, from_outpoint.is_some()).await?;
+            from_outpoint = chunk.last().map(|(outpoint, _)| *outpoint);
+            let is_last_chunk = chunk.len() < UTXO_CHUNK_SIZE;
+            imported += chunk.len();
+            multiset = staging
+                .clone()
+                .spawn_blocking(move |c| {
+                    c.append_imported_pruning_point_utxos(&chunk, &mut multiset);
+                    multiset
+                })
+                .await;
+            if is_last_chunk {
+                break;
+            }
+        }
+        info!("Imported {} pruning point UTXOs", imported);
+        staging.clone().spawn_blocking(move |c| c.import_pruning_point_utxo_set(pruning_point, multiset)).await?;
+    }
+
+    Ok(())
+}
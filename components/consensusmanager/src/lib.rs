@@ -2,16 +2,30 @@ use itertools::Itertools;
 use kaspa_consensus_core::api::{ConsensusApi, DynConsensus};
 use kaspa_core::{core::Core, debug, service::Service};
 use parking_lot::RwLock;
-use std::{collections::VecDeque, ops::Deref, sync::Arc, thread::JoinHandle};
+use std::{
+    collections::VecDeque,
+    io,
+    ops::Deref,
+    path::Path,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    thread::JoinHandle,
+};
 
 mod batch;
+mod finality;
 mod session;
+mod snapshot;
 
 pub use batch::BlockProcessingBatch;
+pub use finality::FinalityConflictResolutionError;
 pub use session::{
     spawn_blocking, ConsensusInstance, ConsensusProxy, ConsensusSessionBlocking, ConsensusSessionOwned, SessionLock, SessionReadGuard,
     SessionWriteGuard,
 };
+pub use snapshot::{UtxoSnapshotError, UtxoSnapshotResult};
 
 /// Consensus controller trait. Includes methods required to start/stop/control consensus, but which should not
 /// be exposed to ordinary users
@@ -24,6 +38,9 @@ pub trait ConsensusCtl: Sync + Send {
 
     /// Set as current active consensus
     fn make_active(&self);
+
+    /// Create a checkpoint of the consensus database within `consensus_db_dir`, named as the database directory
+    fn create_checkpoint(&self, consensus_db_dir: &Path) -> io::Result<()>;
 }
 
 pub type DynConsensusCtl = Arc<dyn ConsensusCtl>;
@@ -32,8 +49,9 @@ pub trait ConsensusFactory: Sync + Send {
     /// Load an instance of current active consensus or create one if no such exists
     fn new_active_consensus(&self) -> (ConsensusInstance, DynConsensusCtl);
 
-    /// Create a new empty staging consensus
-    fn new_staging_consensus(&self) -> (ConsensusInstance, DynConsensusCtl);
+    /// Create a new empty staging consensus. If `add_genesis` is set, the staging consensus is initialized with
+    /// the genesis block (as opposed to being synced from a pruning point proof)
+    fn new_staging_consensus(&self, add_genesis: bool) -> (ConsensusInstance, DynConsensusCtl);
 
     /// Close the factory and cleanup any shared resources used by it
     fn close(&self);
@@ -44,6 +62,9 @@ pub trait ConsensusFactory: Sync + Send {
     /// Delete the staging consensus entry and its database (this is done even if the node is archival
     /// since staging reflects non-final data)
     fn delete_staging_entry(&self);
+
+    /// Create a checkpoint of the consensus management database at `meta_db_dir`
+    fn create_management_checkpoint(&self, meta_db_dir: &Path) -> io::Result<()>;
 }
 
 /// Test-only mock factory
@@ -54,7 +75,7 @@ impl ConsensusFactory for MockFactory {
         unimplemented!()
     }
 
-    fn new_staging_consensus(&self) -> (ConsensusInstance, DynConsensusCtl) {
+    fn new_staging_consensus(&self, _add_genesis: bool) -> (ConsensusInstance, DynConsensusCtl) {
         unimplemented!()
     }
 
@@ -69,6 +90,10 @@ impl ConsensusFactory for MockFactory {
     fn delete_staging_entry(&self) {
         unimplemented!()
     }
+
+    fn create_management_checkpoint(&self, _meta_db_dir: &Path) -> io::Result<()> {
+        unimplemented!()
+    }
 }
 
 /// Defines a trait which handles consensus resets for external parts of the system. We avoid using
@@ -114,6 +139,21 @@ impl ManagerInner {
 pub struct ConsensusManager {
     factory: Arc<dyn ConsensusFactory>,
     inner: RwLock<ManagerInner>,
+
+    /// Whether the staging consensus is reserved, see [`ConsensusManager::try_reserve_staging`]
+    is_staging_reserved: Arc<AtomicBool>,
+}
+
+/// Reserves the staging consensus until dropped
+pub struct StagingReservation {
+    indicator: Arc<AtomicBool>,
+}
+
+impl Drop for StagingReservation {
+    fn drop(&mut self) {
+        let result = self.indicator.compare_exchange(true, false, Ordering::SeqCst, Ordering::SeqCst);
+        assert!(result.is_ok())
+    }
 }
 
 impl ConsensusManager {
@@ -121,7 +161,7 @@ impl ConsensusManager {
 
     pub fn new(factory: Arc<dyn ConsensusFactory>) -> Self {
         let (consensus, ctl) = factory.new_active_consensus();
-        Self { factory, inner: RwLock::new(ManagerInner::new(consensus, ctl)) }
+        Self { factory, inner: RwLock::new(ManagerInner::new(consensus, ctl)), is_staging_reserved: Default::default() }
     }
 
     /// Creates a consensus manager with a fixed consensus. Will panic if staging API is used. To be
@@ -131,6 +171,7 @@ impl ConsensusManager {
         Self {
             factory: Arc::new(MockFactory),
             inner: RwLock::new(ManagerInner::new(ConsensusInstance::new(SessionLock::new(), consensus), ctl)),
+            is_staging_reserved: Default::default(),
         }
     }
 
@@ -138,8 +179,23 @@ impl ConsensusManager {
         self.inner.read().current.consensus.clone()
     }
 
-    pub fn new_staging_consensus(self: &Arc<Self>) -> StagingConsensus {
-        let (consensus, ctl) = self.factory.new_staging_consensus();
+    /// Reserves the staging consensus for as long as the returned reservation is held, or returns `None` if it is
+    /// already reserved. There is a single staging consensus entry, so an IBD with headers proof, a UTXO snapshot
+    /// import and a finality conflict resolution exclude each other.
+    pub fn try_reserve_staging(&self) -> Option<StagingReservation> {
+        if self.is_staging_reserved.compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst).is_ok() {
+            Some(StagingReservation { indicator: self.is_staging_reserved.clone() })
+        } else {
+            None
+        }
+    }
+
+    pub fn new_staging_consensus(self: &Arc<Self>, reservation: &StagingReservation) -> StagingConsensus {
+        self.new_staging_consensus_impl(reservation, false)
+    }
+
+    fn new_staging_consensus_impl(self: &Arc<Self>, _reservation: &StagingReservation, add_genesis: bool) -> StagingConsensus {
+        let (consensus, ctl) = self.factory.new_staging_consensus(add_genesis);
         StagingConsensus::new(self.clone(), ConsensusInner::new(consensus, ctl))
     }
 
@@ -171,6 +227,18 @@ impl ConsensusManager {
     pub fn delete_staging_entry(&self) {
         self.factory.delete_staging_entry();
     }
+
+    /// Creates a consistent checkpoint of the active consensus database within `consensus_db_dir` and of the consensus
+    /// management database at `meta_db_dir`, so that the pair can be opened as a regular data directory.
+    ///
+    /// Note: this is a blocking call and must not be called from within an async context
+    pub fn create_checkpoint(&self, consensus_db_dir: &Path, meta_db_dir: &Path) -> io::Result<()> {
+        // Holding the read lock prevents a staging consensus from being committed in between the two checkpoints,
+        // which would leave the management checkpoint pointing at a consensus entry missing from the checkpoint
+        let g = self.inner.read();
+        g.current.ctl.create_checkpoint(consensus_db_dir)?;
+        self.factory.create_management_checkpoint(meta_db_dir)
+    }
 }
 
 impl Service for ConsensusManager {
@@ -200,6 +268,16 @@ impl StagingConsensus {
     }
 
     pub fn commit(self) {
+        let manager = self.manager.clone();
+        self.commit_retaining_inactive();
+        // Staging was committed and is now the active consensus so we can delete
+        // any pervious, now inactive, consensus entries
+        manager.delete_inactive_consensus_entries();
+    }
+
+    /// Commits the staging consensus without deleting the previous consensus entries, which thus remain readable
+    /// through previously acquired sessions. The caller is responsible for deleting them once done.
+    fn commit_retaining_inactive(self) {
         let mut g = self.manager.inner.write();
         let prev = std::mem::replace(&mut g.current, self.staging);
         g.handles.extend(self.handles);
@@ -210,11 +288,8 @@ impl StagingConsensus {
         for handler in handlers {
             handler.handle_consensus_reset();
         }
-        // Drop `prev` so that deletion below succeeds
+        // Drop `prev` so that a subsequent deletion succeeds
         drop(prev);
-        // Staging was committed and is now the active consensus so we can delete
-        // any pervious, now inactive, consensus entries
-        self.manager.delete_inactive_consensus_entries();
     }
 
     pub fn cancel(self) {
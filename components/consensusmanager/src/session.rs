@@ -448,6 +448,14 @@ impl ConsensusSessionOwned {
     pub async fn async_finality_point(&self) -> Hash {
         self.clone().spawn_blocking(move |c| c.finality_point()).await
     }
+
+    pub async fn async_get_finality_conflict_resolution_hashes(&self, finality_block_hash: Hash) -> ConsensusResult<Vec<Hash>> {
+        self.clone().spawn_blocking(move |c| c.get_finality_conflict_resolution_hashes(finality_block_hash)).await
+    }
+
+    pub async fn async_notify_finality_conflict_resolved(&self, finality_block_hash: Hash) -> ConsensusResult<()> {
+        self.clone().spawn_blocking(move |c| c.notify_finality_conflict_resolved(finality_block_hash)).await
+    }
 }
 
 pub type ConsensusProxy = ConsensusSessionOwned;
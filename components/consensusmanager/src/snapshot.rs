@@ -0,0 +1,419 @@
+//!
+//! UTXO snapshots allow bootstrapping a node from a file rather than fetching the pruning point
+//! UTXO set from peers.
+//!
+//! A snapshot is a sequence of length-prefixed bincode records: a [`SnapshotHeader`], the past pruning points, the
+//! pruning point proof, the trusted data of the pruning point and its anticone, the headers from the
+//! pruning point up to the headers selected tip of the exporting node (in chunks terminated by an
+//! empty chunk), the pruning point UTXO set (in chunks terminated by an empty chunk) and finally the
+//! MuHash of the UTXO set. The header hashes are recomputed on import, so that everything is verified
+//! against the header chain and the UTXO commitment of the pruning point exactly as in IBD.
+//!
+
+use crate::{spawn_blocking, ConsensusManager, ConsensusProxy};
+use futures_util::future::try_join_all;
+use kaspa_consensus_core::{
+    block::Block,
+    errors::{block::RuleError, consensus::ConsensusError, pruning::PruningImportError},
+    header::Header,
+    muhash::MuHashExtensions,
+    pruning::{PruningPointProof, PruningPointsList, PruningProofMetadata},
+    trusted::{build_trusted_subdag, ExternalGhostdagData, TrustedBlock, TrustedGhostdagData, TrustedHeader},
+    tx::{Transaction, TransactionOutpoint, UtxoEntry},
+    BlueWorkType, Hash,
+};
+use kaspa_core::info;
+use kaspa_muhash::MuHash;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::{
+    fs::File,
+    io::{BufReader, BufWriter, Read, Write},
+    path::Path,
+    sync::Arc,
+};
+use thiserror::Error;
+
+/// The version of the snapshot format
+const SNAPSHOT_VERSION: u16 = 1;
+
+/// The number of headers per snapshot chunk. Must be greater than the mergeset size limit.
+const HEADERS_CHUNK_SIZE: usize = 1 << 12;
+
+/// The number of UTXOs per snapshot chunk
+const UTXO_CHUNK_SIZE: usize = 1 << 12;
+
+// The maximal encoded length of each record type. The length prefix of a record is read from the file, so these bound
+// the memory an import may be made to allocate. They are checked on export as well, so that an exported snapshot is
+// always importable.
+const MAX_SNAPSHOT_HEADER_LEN: u64 = 1 << 10;
+const MAX_PRUNING_POINTS_LEN: u64 = 1 << 28;
+const MAX_PROOF_LEN: u64 = 1 << 30;
+const MAX_TRUSTED_DATA_LEN: u64 = 1 << 30;
+const MAX_HEADERS_CHUNK_LEN: u64 = 1 << 30;
+const MAX_UTXO_CHUNK_LEN: u64 = 1 << 29;
+const MAX_MULTISET_HASH_LEN: u64 = 1 << 6;
+
+#[derive(Error, Debug)]
+pub enum UtxoSnapshotError {
+    #[error("snapshot I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("snapshot encoding error: {0}")]
+    Encoding(#[from] bincode::Error),
+
+    #[error("unsupported snapshot version {0}, expected {SNAPSHOT_VERSION}")]
+    UnsupportedVersion(u16),
+
+    #[error("the snapshot was exported from a network with genesis {0} while the node genesis is {1}")]
+    GenesisMismatch(Hash, Hash),
+
+    #[error("the pruning point is genesis, there is no UTXO set to snapshot")]
+    GenesisPruningPoint,
+
+    #[error("the staging consensus is in use by an IBD with headers proof or a finality conflict resolution")]
+    StagingInUse,
+
+    #[error("snapshot record of {0} bytes exceeds the limit of {1} bytes")]
+    RecordTooLarge(u64, u64),
+
+    #[error("invalid snapshot: {0}")]
+    Invalid(&'static str),
+
+    #[error("{0}")]
+    Consensus(#[from] ConsensusError),
+
+    #[error("{0}")]
+    Rule(#[from] RuleError),
+
+    #[error("{0}")]
+    PruningImport(#[from] PruningImportError),
+}
+
+pub type UtxoSnapshotResult<T> = std::result::Result<T, UtxoSnapshotError>;
+
+#[derive(Serialize, Deserialize)]
+struct SnapshotHeader {
+    version: u16,
+    genesis: Hash,
+    pruning_point: Hash,
+    /// The headers selected tip of the exporting node, which the header chain of the snapshot leads to
+    headers_selected_tip: Hash,
+    headers_selected_tip_blue_work: BlueWorkType,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SnapshotTrustedData {
+    daa_window: Vec<(Arc<Header>, ExternalGhostdagData)>,
+    ghostdag_window: Vec<(Hash, ExternalGhostdagData)>,
+    /// The pruning point followed by its anticone
+    blocks: Vec<(Arc<Header>, Vec<Transaction>)>,
+}
+
+fn write_record<T: Serialize>(writer: &mut impl Write, record: &T, max_len: u64) -> UtxoSnapshotResult<()> {
+    let bytes = bincode::serialize(record)?;
+    let len = bytes.len() as u64;
+    if len > max_len {
+        return Err(UtxoSnapshotError::RecordTooLarge(len, max_len));
+    }
+    writer.write_all(&len.to_le_bytes())?;
+    Ok(writer.write_all(&bytes)?)
+}
+
+/// Reads a whole record before decoding it, since some types (e.g. script public keys) can only be decoded from a slice.
+/// The buffer grows with the bytes actually read, so a truncated file does not allocate its claimed record length.
+fn read_record<T: DeserializeOwned>(reader: &mut impl Read, max_len: u64) -> UtxoSnapshotResult<T> {
+    let mut len = [0u8; 8];
+    reader.read_exact(&mut len)?;
+    let len = u64::from_le_bytes(len);
+    if len > max_len {
+        return Err(UtxoSnapshotError::RecordTooLarge(len, max_len));
+    }
+    let mut bytes = Vec::new();
+    reader.by_ref().take(len).read_to_end(&mut bytes)?;
+    if bytes.len() as u64 != len {
+        return Err(std::io::Error::from(std::io::ErrorKind::UnexpectedEof).into());
+    }
+    Ok(bincode::deserialize(&bytes)?)
+}
+
+/// Recomputes the hash of a header read from a snapshot
+fn finalized(header: Arc<Header>) -> Arc<Header> {
+    let mut header = Arc::unwrap_or_clone(header);
+    header.finalize();
+    Arc::new(header)
+}
+
+impl ConsensusManager {
+    /// Exports the pruning point UTXO set of the current consensus along with all the data required for validating
+    /// it into a snapshot file at `path`
+    pub async fn export_utxo_snapshot(&self, path: &Path) -> UtxoSnapshotResult<()> {
+        let session = self.consensus().session().await;
+        let pruning_points = session.async_pruning_point_headers().await;
+        if pruning_points.len() == 1 {
+            return Err(UtxoSnapshotError::GenesisPruningPoint);
+        }
+        let pruning_point = pruning_points.last().unwrap().hash;
+        let headers_selected_tip = session.async_get_headers_selected_tip().await;
+        let header = SnapshotHeader {
+            version: SNAPSHOT_VERSION,
+            genesis: pruning_points[0].hash,
+            pruning_point,
+            headers_selected_tip,
+            headers_selected_tip_blue_work: session.async_get_header(headers_selected_tip).await?.blue_work,
+        };
+        info!("Exporting a UTXO snapshot of pruning point {} to {}", pruning_point, path.display());
+
+        let mut writer = BufWriter::new(File::create(path)?);
+        write_record(&mut writer, &header, MAX_SNAPSHOT_HEADER_LEN)?;
+        write_record(&mut writer, &pruning_points, MAX_PRUNING_POINTS_LEN)?;
+        write_record(&mut writer, session.async_get_pruning_point_proof().await.as_ref(), MAX_PROOF_LEN)?;
+
+        let trusted_data = session.async_get_pruning_point_anticone_and_trusted_data().await?;
+        let mut blocks = Vec::with_capacity(trusted_data.anticone.len());
+        for &hash in trusted_data.anticone.iter() {
+            let block = session.async_get_block(hash).await?;
+            blocks.push((block.header, block.transactions.as_ref().clone()));
+        }
+        write_record(
+            &mut writer,
+            &SnapshotTrustedData {
+                daa_window: trusted_data.daa_window_blocks.iter().map(|th| (th.header.clone(), th.ghostdag.clone())).collect(),
+                ghostdag_window: trusted_data.ghostdag_blocks.iter().map(|tg| (tg.hash, tg.ghostdag.clone())).collect(),
+                blocks,
+            },
+            MAX_TRUSTED_DATA_LEN,
+        )?;
+
+        let mut low = pruning_point;
+        let mut headers_count = 0;
+        while low != headers_selected_tip {
+            let hashes = session.async_get_hashes_between(low, headers_selected_tip, HEADERS_CHUNK_SIZE).await?.0;
+            low = *hashes.last().expect("low and high are valid and different");
+            let mut headers = Vec::with_capacity(hashes.len());
+            for hash in hashes {
+                headers.push(session.async_get_header(hash).await?);
+            }
+            headers_count += headers.len();
+            write_record(&mut writer, &headers, MAX_HEADERS_CHUNK_LEN)?;
+        }
+        write_record(&mut writer, &Vec::<Arc<Header>>::new(), MAX_HEADERS_CHUNK_LEN)?;
+
+        let mut multiset = MuHash::new();
+        let mut from_outpoint = None;
+        let mut utxos_count = 0;
+        loop {
+            let chunk =
+                session.async_get_pruning_point_utxos(pruning_point, from_outpoint, UTXO_CHUNK_SIZE, from_outpoint.is_some()).await?;
+            if chunk.is_empty() {
+                break;
+            }
+            chunk.iter().for_each(|(outpoint, entry)| multiset.add_utxo(outpoint, entry));
+            from_outpoint = chunk.last().map(|(outpoint, _)| *outpoint);
+            utxos_count += chunk.len();
+            write_record(&mut writer, &chunk, MAX_UTXO_CHUNK_LEN)?;
+        }
+        write_record(&mut writer, &Vec::<(TransactionOutpoint, UtxoEntry)>::new(), MAX_UTXO_CHUNK_LEN)?;
+        write_record(&mut writer, &multiset.finalize(), MAX_MULTISET_HASH_LEN)?;
+        writer.flush()?;
+
+        info!("Exported {} headers and {} UTXOs to the snapshot", headers_count, utxos_count);
+        Ok(())
+    }
+
+    /// Imports a snapshot file written by [`Self::export_utxo_snapshot`] into a staging consensus, validating
+    /// the pruning point proof, the header chain and the UTXO commitment, and commits it on success. Unless the
+    /// current consensus holds no header but genesis, the snapshot header chain must have more blue work than it.
+    pub async fn import_utxo_snapshot(self: &Arc<Self>, path: &Path) -> UtxoSnapshotResult<()> {
+        let reservation = self.try_reserve_staging().ok_or(UtxoSnapshotError::StagingInUse)?;
+        let mut reader = BufReader::new(File::open(path)?);
+        let header: SnapshotHeader = read_record(&mut reader, MAX_SNAPSHOT_HEADER_LEN)?;
+        if header.version != SNAPSHOT_VERSION {
+            return Err(UtxoSnapshotError::UnsupportedVersion(header.version));
+        }
+
+        let session = self.consensus().session().await;
+        let genesis = session.async_pruning_point_headers().await[0].hash;
+        if header.genesis != genesis {
+            return Err(UtxoSnapshotError::GenesisMismatch(header.genesis, genesis));
+        }
+        // The claimed blue work is verified against the imported headers selected tip below
+        let current_tip = session.async_get_headers_selected_tip().await;
+        if current_tip != genesis && header.headers_selected_tip_blue_work <= session.async_get_header(current_tip).await?.blue_work {
+            return Err(UtxoSnapshotError::Invalid("the snapshot does not have more blue work than the current consensus"));
+        }
+
+        let pruning_points: PruningPointsList =
+            read_record::<PruningPointsList>(&mut reader, MAX_PRUNING_POINTS_LEN)?.into_iter().map(finalized).collect();
+        if pruning_points.first().map(|h| h.hash) != Some(genesis) {
+            return Err(UtxoSnapshotError::Invalid("the first pruning point is expected to be genesis"));
+        }
+        if pruning_points.last().map(|h| h.hash) != Some(header.pruning_point) {
+            return Err(UtxoSnapshotError::Invalid("the last pruning point is expected to be the snapshot pruning point"));
+        }
+
+        let proof: PruningPointProof = read_record::<PruningPointProof>(&mut reader, MAX_PROOF_LEN)?
+            .into_iter()
+            .map(|level| level.into_iter().map(finalized).collect())
+            .collect();
+        let proof_metadata = PruningProofMetadata::new(header.headers_selected_tip_blue_work);
+        // The proof is validated in the context of current consensus
+        let proof = session.clone().spawn_blocking(move |c| c.validate_pruning_proof(&proof, &proof_metadata).map(|()| proof)).await?;
+        if proof[0].last().expect("was just ensured by validation").hash != header.pruning_point {
+            return Err(UtxoSnapshotError::Invalid("the proof pruning point is not the snapshot pruning point"));
+        }
+        if session.async_are_pruning_points_violating_finality(pruning_points.clone()).await {
+            return Err(UtxoSnapshotError::Invalid("the snapshot pruning points are violating finality"));
+        }
+        drop(session);
+
+        let trusted_data: SnapshotTrustedData = read_record(&mut reader, MAX_TRUSTED_DATA_LEN)?;
+        let daa_window = trusted_data.daa_window.into_iter().map(|(h, gd)| TrustedHeader::new(finalized(h), gd)).collect::<Vec<_>>();
+        let ghostdag_window =
+            trusted_data.ghostdag_window.into_iter().map(|(h, gd)| TrustedGhostdagData::new(h, gd)).collect::<Vec<_>>();
+        let blocks = trusted_data.blocks.into_iter().map(|(h, mut txs)| {
+            txs.iter_mut().for_each(|tx| tx.finalize());
+            Block::from_arcs(finalized(h), Arc::new(txs))
+        });
+        let trusted_set = build_trusted_subdag(blocks, &daa_window, &ghostdag_window)
+            .ok_or(UtxoSnapshotError::Invalid("missing ghostdag data for some trusted blocks"))?;
+        if trusted_set.first().map(|tb| tb.block.hash()) != Some(header.pruning_point) {
+            return Err(UtxoSnapshotError::Invalid("the trusted blocks are expected to start with the pruning point"));
+        }
+
+        let staging = self.new_staging_consensus(&reservation);
+        let staging_session = staging.session().await;
+        match import_staging(&staging_session, reader, header, proof, pruning_points, trusted_set).await {
+            Ok(()) => {
+                drop(staging_session);
+                spawn_blocking(|| staging.commit()).await.unwrap();
+                info!("UTXO snapshot imported successfully, committed the staging consensus");
+                Ok(())
+            }
+            Err(err) => {
+                drop(staging_session);
+                spawn_blocking(|| staging.cancel()).await.unwrap();
+                Err(err)
+            }
+        }
+    }
+}
+
+/// Feeds the rest of the snapshot into the staging consensus
+async fn import_staging(
+    staging: &ConsensusProxy,
+    mut reader: impl Read,
+    header: SnapshotHeader,
+    proof: PruningPointProof,
+    pruning_points: PruningPointsList,
+    trusted_set: Vec<TrustedBlock>,
+) -> UtxoSnapshotResult<()> {
+    let trusted_set = staging
+        .clone()
+        .spawn_blocking(move |c| {
+            c.apply_pruning_proof(proof, &trusted_set)?;
+            c.import_pruning_points(pruning_points);
+            UtxoSnapshotResult::Ok(trusted_set)
+        })
+        .await?;
+    info!("Processing {} trusted blocks from the snapshot", trusted_set.len());
+    for tb in trusted_set {
+        staging.validate_and_insert_trusted_block(tb).virtual_state_task.await?;
+    }
+
+    let mut headers_count = 0;
+    loop {
+        let headers: Vec<Arc<Header>> = read_record(&mut reader, MAX_HEADERS_CHUNK_LEN)?;
+        if headers.is_empty() {
+            break;
+        }
+        headers_count += headers.len();
+        let jobs = headers
+            .into_iter()
+            .map(|h| staging.validate_and_insert_block(Block::from_header_arc(finalized(h))).virtual_state_task)
+            .collect::<Vec<_>>();
+        try_join_all(jobs).await?;
+    }
+    info!("Processed {} headers from the snapshot", headers_count);
+    if staging.async_get_headers_selected_tip().await != header.headers_selected_tip {
+        return Err(UtxoSnapshotError::Invalid("the header chain does not lead to the claimed headers selected tip"));
+    }
+    // The proof was validated against the current consensus with the claimed blue work
+    if staging.async_get_header(header.headers_selected_tip).await?.blue_work != header.headers_selected_tip_blue_work {
+        return Err(UtxoSnapshotError::Invalid("the blue work of the headers selected tip is not the claimed one"));
+    }
+    staging.async_validate_pruning_points().await?;
+
+    let mut multiset = MuHash::new();
+    let mut utxos_count = 0;
+    loop {
+        let chunk: Vec<(TransactionOutpoint, UtxoEntry)> = read_record(&mut reader, MAX_UTXO_CHUNK_LEN)?;
+        if chunk.is_empty() {
+            break;
+        }
+        utxos_count += chunk.len();
+        multiset = staging
+            .clone()
+            .spawn_blocking(move |c| {
+                c.append_imported_pruning_point_utxos(&chunk, &mut multiset);
+                multiset
+            })
+            .await;
+    }
+    let expected_multiset_hash: Hash = read_record(&mut reader, MAX_MULTISET_HASH_LEN)?;
+    if multiset.clone().finalize() != expected_multiset_hash {
+        return Err(UtxoSnapshotError::Invalid("the UTXO set does not match the snapshot MuHash"));
+    }
+    info!("Importing {} UTXOs from the snapshot", utxos_count);
+    // Verifies the imported UTXO set against the UTXO commitment of the pruning point
+    staging.clone().spawn_blocking(move |c| c.import_pruning_point_utxo_set(header.pruning_point, multiset)).await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kaspa_consensus_core::tx::ScriptPublicKey;
+    use std::io::Cursor;
+
+    #[test]
+    fn test_snapshot_records() {
+        let header = Arc::new(Header::from_precomputed_hash(1.into(), vec![]));
+        let utxos = vec![(TransactionOutpoint::new(2.into(), 0), UtxoEntry::new(10, ScriptPublicKey::default(), 5, false))];
+        let mut multiset = MuHash::new();
+        utxos.iter().for_each(|(outpoint, entry)| multiset.add_utxo(outpoint, entry));
+
+        let mut buffer = Vec::new();
+        write_record(&mut buffer, &vec![header.clone()], MAX_HEADERS_CHUNK_LEN).unwrap();
+        write_record(&mut buffer, &utxos, MAX_UTXO_CHUNK_LEN).unwrap();
+        write_record(&mut buffer, &multiset.finalize(), MAX_MULTISET_HASH_LEN).unwrap();
+        assert!(matches!(write_record(&mut buffer, &utxos, MAX_MULTISET_HASH_LEN), Err(UtxoSnapshotError::RecordTooLarge(_, _))));
+
+        let mut reader = Cursor::new(buffer);
+        let headers: Vec<Arc<Header>> = read_record(&mut reader, MAX_HEADERS_CHUNK_LEN).unwrap();
+        assert_eq!(headers[0].hash, header.hash);
+        // The hash claimed by the snapshot is replaced by the one computed from the header fields
+        assert_ne!(finalized(headers[0].clone()).hash, header.hash);
+        assert_eq!(read_record::<Vec<(TransactionOutpoint, UtxoEntry)>>(&mut reader, MAX_UTXO_CHUNK_LEN).unwrap(), utxos);
+        assert_eq!(read_record::<Hash>(&mut reader, MAX_MULTISET_HASH_LEN).unwrap(), multiset.finalize());
+        assert!(matches!(read_record::<Hash>(&mut reader, MAX_MULTISET_HASH_LEN), Err(UtxoSnapshotError::Io(_))));
+    }
+
+    #[test]
+    fn test_snapshot_record_limits() {
+        // A length prefix over the limit is rejected before reading the record
+        let mut reader = Cursor::new(u64::MAX.to_le_bytes());
+        assert!(matches!(
+            read_record::<Vec<Arc<Header>>>(&mut reader, MAX_HEADERS_CHUNK_LEN),
+            Err(UtxoSnapshotError::RecordTooLarge(u64::MAX, MAX_HEADERS_CHUNK_LEN))
+        ));
+
+        // A length prefix within the limit but past the end of the file is an I/O error
+        let mut buffer = MAX_UTXO_CHUNK_LEN.to_le_bytes().to_vec();
+        buffer.extend([0u8; 16]);
+        let mut reader = Cursor::new(buffer);
+        assert!(matches!(
+            read_record::<Vec<(TransactionOutpoint, UtxoEntry)>>(&mut reader, MAX_UTXO_CHUNK_LEN),
+            Err(UtxoSnapshotError::Io(_))
+        ));
+    }
+}
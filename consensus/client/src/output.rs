@@ -19,6 +19,8 @@ export interface ITransactionOutput {
 
     /** Optional verbose data provided by RPC */
     verboseData?: ITransactionOutputVerboseData;
+    /** Disassembled script public key provided by RPC along with verbose data, for non-standard outputs which have no address */
+    scriptPublicKeyAsm?: string;
 }
 
 /**
@@ -20,6 +20,7 @@ use crate::{
     pruning::{PruningPointProof, PruningPointTrustedData, PruningPointsList, PruningProofMetadata},
     trusted::{ExternalGhostdagData, TrustedBlock},
     tx::{MutableTransaction, Transaction, TransactionOutpoint, UtxoEntry},
+    utxo::utxo_diff::UtxoDiff,
     BlockHashSet, BlueWorkType, ChainPath,
 };
 use kaspa_hashes::Hash;
@@ -299,6 +300,11 @@ pub trait ConsensusApi: Send + Sync {
         unimplemented!()
     }
 
+    /// Returns the UTXO diff of a UTXO-valid chain block from its selected parent, i.e. the diff of its mergeset
+    fn get_block_utxo_diff(&self, hash: Hash) -> ConsensusResult<Arc<UtxoDiff>> {
+        unimplemented!()
+    }
+
     /// Returns acceptance data for a set of blocks belonging to the selected parent chain.
     ///
     /// See `self::get_virtual_chain`
@@ -362,6 +368,19 @@ pub trait ConsensusApi: Send + Sync {
     fn finality_point(&self) -> Hash {
         unimplemented!()
     }
+
+    /// Returns the blocks in the future of the pruning point which agree with `finality_block_hash` in topological
+    /// order, i.e. its inclusive future and the past thereof. Fails if `finality_block_hash` does not conflict with
+    /// the current finality point.
+    fn get_finality_conflict_resolution_hashes(&self, finality_block_hash: Hash) -> ConsensusResult<Vec<Hash>> {
+        unimplemented!()
+    }
+
+    /// Notifies that a finality conflict was resolved in favor of `finality_block_hash`. Fails if the block is not
+    /// in the inclusive past of the sink.
+    fn notify_finality_conflict_resolved(&self, finality_block_hash: Hash) -> ConsensusResult<()> {
+        unimplemented!()
+    }
 }
 
 pub type DynConsensus = Arc<dyn ConsensusApi>;
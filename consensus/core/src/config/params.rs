@@ -133,6 +133,26 @@ pub struct Params {
 
     /// Activation rules for when to enable using the payload field in transactions
     pub payload_activation: ForkActivation,
+
+    /// Activation rules for the remaining transaction introspection opcodes which were reserved by KIP-10:
+    ///    - OpTxVersion (0xb2): Get transaction version
+    ///    - OpTxLockTime (0xb5): Get transaction lock time
+    ///    - OpTxSubnetId (0xb6): Get transaction subnetwork id
+    ///    - OpTxGas (0xb7): Get transaction gas
+    ///    - OpTxPayload (0xb8): Get transaction payload
+    ///    - OpTxInputSeq (0xbd): Get input sequence
+    ///    - OpTxInputBlockDaaScore (0xc0): Get the DAA score of the block which created the input UTXO
+    ///    - OpTxInputIsCoinbase (0xc1): Get whether the input UTXO is a coinbase output
+    pub tx_introspection_activation: ForkActivation,
+
+    /// Activation rule for the byte string opcodes, which are disabled before activation:
+    ///    - OpCat (0x7e): Concatenate the two top stack elements
+    ///    - OpSubStr (0x7f): Get a sub range of a stack element
+    ///    - OpLeft (0x80): Get a prefix of a stack element
+    ///    - OpRight (0x81): Get a suffix of a stack element
+    ///
+    /// Results larger than the max script element size fail the script.
+    pub byte_string_opcodes_activation: ForkActivation,
 }
 
 fn unix_now() -> u64 {
@@ -411,6 +431,8 @@ pub const MAINNET_PARAMS: Params = Params {
     pruning_proof_m: 1000,
 
     payload_activation: ForkActivation::never(),
+    tx_introspection_activation: ForkActivation::never(),
+    byte_string_opcodes_activation: ForkActivation::never(),
 };
 
 pub const TESTNET_PARAMS: Params = Params {
@@ -476,6 +498,8 @@ pub const TESTNET_PARAMS: Params = Params {
     pruning_proof_m: 1000,
 
     payload_activation: ForkActivation::never(),
+    tx_introspection_activation: ForkActivation::never(),
+    byte_string_opcodes_activation: ForkActivation::never(),
 };
 
 pub const TESTNET11_PARAMS: Params = Params {
@@ -536,6 +560,8 @@ pub const TESTNET11_PARAMS: Params = Params {
     // Roughly at Dec 3, 2024 1800 UTC
     kip10_activation: ForkActivation::new(287238000),
     payload_activation: ForkActivation::new(287238000),
+    tx_introspection_activation: ForkActivation::never(),
+    byte_string_opcodes_activation: ForkActivation::never(),
 
     skip_proof_of_work: false,
     max_block_level: 250,
@@ -595,6 +621,8 @@ pub const SIMNET_PARAMS: Params = Params {
     max_block_level: 250,
 
     payload_activation: ForkActivation::never(),
+    tx_introspection_activation: ForkActivation::never(),
+    byte_string_opcodes_activation: ForkActivation::never(),
 };
 
 pub const DEVNET_PARAMS: Params = Params {
@@ -654,4 +682,6 @@ pub const DEVNET_PARAMS: Params = Params {
     pruning_proof_m: 1000,
 
     payload_activation: ForkActivation::never(),
+    tx_introspection_activation: ForkActivation::never(),
+    byte_string_opcodes_activation: ForkActivation::never(),
 };
@@ -23,6 +23,9 @@ pub enum ConsensusError {
     #[error("pruning point is not at sufficient depth from virtual, cannot obtain its final anticone at this stage")]
     PruningPointInsufficientDepth,
 
+    #[error("block {0} does not conflict with the finality point {1}")]
+    NoFinalityConflict(Hash, Hash),
+
     #[error("sync manager error: {0}")]
     SyncManagerError(#[from] SyncManagerError),
 
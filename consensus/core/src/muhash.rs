@@ -8,6 +8,7 @@ use kaspa_muhash::MuHash;
 pub trait MuHashExtensions {
     fn add_transaction(&mut self, tx: &impl VerifiableTransaction, block_daa_score: u64);
     fn add_utxo(&mut self, outpoint: &TransactionOutpoint, entry: &UtxoEntry);
+    fn remove_utxo(&mut self, outpoint: &TransactionOutpoint, entry: &UtxoEntry);
     fn from_transaction(tx: &impl VerifiableTransaction, block_daa_score: u64) -> Self;
     fn from_utxo(outpoint: &TransactionOutpoint, entry: &UtxoEntry) -> Self;
 }
@@ -16,9 +17,7 @@ impl MuHashExtensions for MuHash {
     fn add_transaction(&mut self, tx: &impl VerifiableTransaction, block_daa_score: u64) {
         let tx_id = tx.id();
         for (input, entry) in tx.populated_inputs() {
-            let mut writer = self.remove_element_builder();
-            write_utxo(&mut writer, entry, &input.previous_outpoint);
-            writer.finalize();
+            self.remove_utxo(&input.previous_outpoint, entry);
         }
         for (i, output) in tx.outputs().iter().enumerate() {
             let outpoint = TransactionOutpoint::new(tx_id, i as u32);
@@ -33,6 +32,12 @@ impl MuHashExtensions for MuHash {
         writer.finalize();
     }
 
+    fn remove_utxo(&mut self, outpoint: &TransactionOutpoint, entry: &UtxoEntry) {
+        let mut writer = self.remove_element_builder();
+        write_utxo(&mut writer, entry, outpoint);
+        writer.finalize();
+    }
+
     fn from_transaction(tx: &impl VerifiableTransaction, block_daa_score: u64) -> Self {
         let mut mh = Self::new();
         mh.add_transaction(tx, block_daa_score);
@@ -1,4 +1,4 @@
-use crate::{block::Block, header::Header, BlockHashMap, BlueWorkType, KType};
+use crate::{block::Block, blockhash::ORIGIN, header::Header, BlockHashMap, BlockHashSet, BlueWorkType, HashMapCustomHasher, KType};
 use kaspa_hashes::Hash;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
@@ -53,3 +53,55 @@ impl TrustedGhostdagData {
         Self { hash, ghostdag }
     }
 }
+
+/// Builds the trusted set -- a sub-DAG in the anti-future of the pruning point which contains all the blocks
+/// and ghostdag data needed in order to validate the headers in the future of the pruning point. The blocks are
+/// expected to start with the pruning point followed by its anticone.
+///
+/// Returns `None` if the ghostdag data of some block is missing.
+pub fn build_trusted_subdag(
+    blocks: impl IntoIterator<Item = Block>,
+    daa_window: &[TrustedHeader],
+    ghostdag_window: &[TrustedGhostdagData],
+) -> Option<Vec<TrustedBlock>> {
+    let mut trusted_blocks = Vec::new();
+    let mut set = BlockHashSet::new();
+    let mut map = BlockHashMap::new();
+
+    for th in ghostdag_window.iter() {
+        map.insert(th.hash, &th.ghostdag);
+    }
+
+    for th in daa_window.iter() {
+        map.insert(th.header.hash, &th.ghostdag);
+    }
+
+    for block in blocks {
+        if set.insert(block.hash()) {
+            let ghostdag = (*map.get(&block.hash())?).clone();
+            trusted_blocks.push(TrustedBlock::new(block, ghostdag));
+        }
+    }
+
+    for th in daa_window.iter() {
+        if set.insert(th.header.hash) {
+            trusted_blocks.push(TrustedBlock::new(Block::from_header_arc(th.header.clone()), th.ghostdag.clone()));
+        }
+    }
+
+    // Prune all missing ghostdag mergeset blocks. If due to this prune data becomes insufficient, future
+    // blocks will not validate correctly which will lead to a rule error
+    for tb in trusted_blocks.iter_mut() {
+        tb.ghostdag.mergeset_blues.retain(|h| set.contains(h));
+        tb.ghostdag.mergeset_reds.retain(|h| set.contains(h));
+        tb.ghostdag.blues_anticone_sizes.retain(|k, _| set.contains(k));
+        if !set.contains(&tb.ghostdag.selected_parent) {
+            tb.ghostdag.selected_parent = ORIGIN;
+        }
+    }
+
+    // Topological sort
+    trusted_blocks.sort_by(|a, b| a.block.header.blue_work.cmp(&b.block.header.blue_work));
+
+    Some(trusted_blocks)
+}
@@ -1,5 +1,6 @@
 use derive_more::Display;
-use kaspa_consensus_core::{acceptance_data::AcceptanceData, block::Block, utxo::utxo_diff::UtxoDiff};
+use kaspa_addresses::Address;
+use kaspa_consensus_core::{acceptance_data::AcceptanceData, block::Block, tx::TransactionId, utxo::utxo_diff::UtxoDiff};
 use kaspa_hashes::Hash;
 use kaspa_notify::{
     events::EventType,
@@ -7,7 +8,7 @@ use kaspa_notify::{
     notification::Notification as NotificationTrait,
     subscription::{
         context::SubscriptionContext,
-        single::{OverallSubscription, UtxosChangedSubscription, VirtualChainChangedSubscription},
+        single::{MempoolChangedSubscription, OverallSubscription, UtxosChangedSubscription, VirtualChainChangedSubscription},
         Subscription,
     },
 };
@@ -42,6 +43,9 @@ pub enum Notification {
 
     #[display(fmt = "NewBlockTemplate notification")]
     NewBlockTemplate(NewBlockTemplateNotification),
+
+    #[display(fmt = "MempoolChanged notification: transaction {} {}", "_0.transaction_id", "_0.kind")]
+    MempoolChanged(MempoolChangedNotification),
 }
 }
 
@@ -87,6 +91,18 @@ impl NotificationTrait for Notification {
         Some(self.clone())
     }
 
+    fn apply_mempool_changed_subscription(
+        &self,
+        subscription: &MempoolChangedSubscription,
+        _context: &SubscriptionContext,
+    ) -> Option<Self> {
+        // Same as above, the subscription addresses are applied farther along the notification backbone.
+        match subscription.active() {
+            true => Some(self.clone()),
+            false => None,
+        }
+    }
+
     fn event_type(&self) -> EventType {
         self.into()
     }
@@ -181,3 +197,46 @@ pub struct PruningPointUtxoSetOverrideNotification {}
 
 #[derive(Debug, Clone)]
 pub struct NewBlockTemplateNotification {}
+
+/// Kind of change a transaction went through in the mempool
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display)]
+pub enum MempoolChangeKind {
+    /// The transaction entered the mempool transaction pool
+    #[display(fmt = "added")]
+    Added,
+
+    /// The transaction was included in a block
+    #[display(fmt = "accepted")]
+    Accepted,
+
+    /// The transaction was replaced by a transaction paying a higher fee rate (RBF)
+    #[display(fmt = "replaced")]
+    Replaced,
+
+    /// The low priority transaction was not included in a block in due time
+    #[display(fmt = "expired")]
+    Expired,
+
+    /// The transaction was evicted to make room for a transaction paying a higher fee rate,
+    /// or because it no longer satisfies an updated mempool policy
+    #[display(fmt = "evicted")]
+    Evicted,
+
+    /// The transaction was removed for any other reason, like a double spend or being invalid
+    #[display(fmt = "removed")]
+    Removed,
+}
+
+#[derive(Debug, Clone)]
+pub struct MempoolChangedNotification {
+    pub transaction_id: TransactionId,
+    pub kind: MempoolChangeKind,
+    /// Addresses of the transaction inputs and outputs
+    pub addresses: Arc<Vec<Address>>,
+}
+
+impl MempoolChangedNotification {
+    pub fn new(transaction_id: TransactionId, kind: MempoolChangeKind, addresses: Arc<Vec<Address>>) -> Self {
+        Self { transaction_id, kind, addresses }
+    }
+}
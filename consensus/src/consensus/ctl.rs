@@ -3,7 +3,8 @@ use kaspa_consensusmanager::ConsensusCtl;
 use kaspa_database::prelude::DB;
 use parking_lot::RwLock;
 use std::{
-    path::PathBuf,
+    io,
+    path::{Path, PathBuf},
     sync::{Arc, Weak},
     thread::JoinHandle,
 };
@@ -40,6 +41,12 @@ impl ConsensusCtl for Ctl {
         // TODO: pass a value to make sure the correct consensus is committed
         self.management_store.write().commit_staging_consensus().unwrap();
     }
+
+    fn create_checkpoint(&self, consensus_db_dir: &Path) -> io::Result<()> {
+        let db = self.consensus_db_ref.upgrade().ok_or_else(|| io::Error::other("the consensus database is closed"))?;
+        let dir_name = self.consensus_db_path.file_name().expect("the consensus database path ends with its directory name");
+        db.create_checkpoint(consensus_db_dir.join(dir_name)).map_err(io::Error::other)
+    }
 }
 
 /// Impl for test purposes
@@ -55,4 +62,8 @@ impl ConsensusCtl for Consensus {
     fn make_active(&self) {
         unimplemented!()
     }
+
+    fn create_checkpoint(&self, _consensus_db_dir: &Path) -> io::Result<()> {
+        unimplemented!()
+    }
 }
@@ -19,7 +19,13 @@ use kaspa_utils::mem_size::MemSizeEstimator;
 use parking_lot::RwLock;
 use rocksdb::WriteBatch;
 use serde::{Deserialize, Serialize};
-use std::{collections::HashMap, error::Error, fs, path::PathBuf, sync::Arc};
+use std::{
+    collections::HashMap,
+    error::Error,
+    fs, io,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 
 #[derive(Serialize, Deserialize, Clone)]
 pub struct ConsensusEntry {
@@ -236,6 +242,11 @@ impl MultiConsensusManagementStore {
         Ok(())
     }
 
+    /// Creates a checkpoint of the management database at `path`
+    pub fn create_checkpoint(&self, path: &Path) -> StoreResult<()> {
+        Ok(self.db.create_checkpoint(path)?)
+    }
+
     pub fn should_upgrade(&self) -> StoreResult<bool> {
         match self.metadata.read() {
             Ok(data) => Ok(data.version != LATEST_DB_VERSION),
@@ -338,7 +349,7 @@ impl ConsensusFactory for Factory {
         (ConsensusInstance::new(session_lock, consensus.clone()), Arc::new(Ctl::new(self.management_store.clone(), db, consensus)))
     }
 
-    fn new_staging_consensus(&self) -> (ConsensusInstance, DynConsensusCtl) {
+    fn new_staging_consensus(&self, add_genesis: bool) -> (ConsensusInstance, DynConsensusCtl) {
         assert!(!self.notification_root.is_closed());
 
         let entry = self.management_store.write().new_staging_consensus_entry().unwrap();
@@ -350,10 +361,12 @@ impl ConsensusFactory for Factory {
             .build()
             .unwrap();
 
+        let config =
+            if add_genesis { self.config.to_builder().build() } else { self.config.to_builder().skip_adding_genesis().build() };
         let session_lock = SessionLock::new();
         let consensus = Arc::new(Consensus::new(
             db.clone(),
-            Arc::new(self.config.to_builder().skip_adding_genesis().build()),
+            Arc::new(config),
             session_lock.clone(),
             self.notification_root.clone(),
             self.counters.clone(),
@@ -417,4 +430,8 @@ impl ConsensusFactory for Factory {
             write_guard.cancel_staging_consensus().unwrap();
         }
     }
+
+    fn create_management_checkpoint(&self, meta_db_dir: &Path) -> io::Result<()> {
+        self.management_store.read().create_checkpoint(meta_db_dir).map_err(io::Error::other)
+    }
 }
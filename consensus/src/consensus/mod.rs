@@ -24,6 +24,7 @@ use crate::{
             relations::RelationsStoreReader,
             statuses::StatusesStoreReader,
             tips::TipsStoreReader,
+            utxo_diffs::UtxoDiffsStoreReader,
             utxo_set::{UtxoSetStore, UtxoSetStoreReader},
             DB,
         },
@@ -67,9 +68,13 @@ use kaspa_consensus_core::{
     pruning::{PruningPointProof, PruningPointTrustedData, PruningPointsList, PruningProofMetadata},
     trusted::{ExternalGhostdagData, TrustedBlock},
     tx::{MutableTransaction, Transaction, TransactionOutpoint, UtxoEntry},
+    utxo::utxo_diff::UtxoDiff,
     BlockHashSet, BlueWorkType, ChainPath, HashMapCustomHasher,
 };
-use kaspa_consensus_notify::root::ConsensusNotificationRoot;
+use kaspa_consensus_notify::{
+    notification::{FinalityConflictResolvedNotification, Notification},
+    root::ConsensusNotificationRoot,
+};
 
 use crossbeam_channel::{
     bounded as bounded_crossbeam, unbounded as unbounded_crossbeam, Receiver as CrossbeamReceiver, Sender as CrossbeamSender,
@@ -80,12 +85,13 @@ use kaspa_consensusmanager::{SessionLock, SessionReadGuard};
 use kaspa_database::prelude::StoreResultExtensions;
 use kaspa_hashes::Hash;
 use kaspa_muhash::MuHash;
+use kaspa_notify::notifier::Notify;
 use kaspa_txscript::caches::TxScriptCacheCounters;
 use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
 
 use std::{
     cmp::Reverse,
-    collections::BinaryHeap,
+    collections::{BinaryHeap, VecDeque},
     future::Future,
     iter::once,
     ops::Deref,
@@ -928,6 +934,10 @@ impl ConsensusApi for Consensus {
         self.acceptance_data_store.get(hash).unwrap_option().ok_or(ConsensusError::MissingData(hash))
     }
 
+    fn get_block_utxo_diff(&self, hash: Hash) -> ConsensusResult<Arc<UtxoDiff>> {
+        self.utxo_diffs_store.get(hash).unwrap_option().ok_or(ConsensusError::MissingData(hash))
+    }
+
     fn get_blocks_acceptance_data(
         &self,
         hashes: &[Hash],
@@ -1044,4 +1054,65 @@ impl ConsensusApi for Consensus {
     fn finality_point(&self) -> Hash {
         self.virtual_processor.virtual_finality_point(&self.lkg_virtual_state.load().ghostdag_data, self.pruning_point())
     }
+
+    fn get_finality_conflict_resolution_hashes(&self, finality_block_hash: Hash) -> ConsensusResult<Vec<Hash>> {
+        let _guard = self.pruning_lock.blocking_read();
+        match self.statuses_store.read().get(finality_block_hash).unwrap_option() {
+            None => return Err(ConsensusError::HeaderNotFound(finality_block_hash)),
+            Some(status) if status.is_header_only() => return Err(ConsensusError::BlockNotFound(finality_block_hash)),
+            Some(status) if !status.is_utxo_valid_or_pending() => return Err(ConsensusError::InvalidBlock(finality_block_hash)),
+            Some(_) => {}
+        }
+
+        let reachability = &self.services.reachability_service;
+        let pruning_point = self.pruning_point();
+        if !reachability.is_dag_ancestor_of(pruning_point, finality_block_hash) {
+            return Err(ConsensusError::General("the finality block is not in the future of the pruning point"));
+        }
+        let finality_point = self.finality_point();
+        if reachability.is_dag_ancestor_of(finality_point, finality_block_hash)
+            || reachability.is_dag_ancestor_of(finality_block_hash, finality_point)
+        {
+            return Err(ConsensusError::NoFinalityConflict(finality_block_hash, finality_point));
+        }
+
+        // Collect the inclusive future of the finality block, and then the past thereof down to the pruning point
+        let mut visited = BlockHashSet::from_iter([finality_block_hash]);
+        let mut queue = VecDeque::from([finality_block_hash]);
+        while let Some(hash) = queue.pop_front() {
+            for &child in self.services.relations_service.get_children(hash).unwrap().read().iter() {
+                if visited.insert(child) {
+                    queue.push_back(child);
+                }
+            }
+        }
+        let mut queue = VecDeque::from_iter(visited.iter().copied());
+        while let Some(hash) = queue.pop_front() {
+            for &parent in self.services.relations_service.get_parents(hash).unwrap().iter() {
+                if parent != pruning_point && reachability.is_dag_ancestor_of(pruning_point, parent) && visited.insert(parent) {
+                    queue.push_back(parent);
+                }
+            }
+        }
+
+        // Blue work is monotonic along parent relations, so sorting by it yields a topological order
+        Ok(visited
+            .into_iter()
+            .map(|hash| SortableBlock::new(hash, self.ghostdag_store.get_blue_work(hash).unwrap()))
+            .sorted()
+            .map(|block| block.hash)
+            .collect())
+    }
+
+    fn notify_finality_conflict_resolved(&self, finality_block_hash: Hash) -> ConsensusResult<()> {
+        let _guard = self.pruning_lock.blocking_read();
+        self.validate_block_exists(finality_block_hash)?;
+        if !self.services.reachability_service.is_dag_ancestor_of(finality_block_hash, self.get_sink()) {
+            return Err(ConsensusError::General("the finality block is not in the past of the sink"));
+        }
+        self.notification_root
+            .notify(Notification::FinalityConflictResolved(FinalityConflictResolvedNotification::new(finality_block_hash)))
+            .expect("expecting an open unbounded channel");
+        Ok(())
+    }
 }
@@ -148,6 +148,8 @@ impl ConsensusServices {
             params.storage_mass_activation,
             params.kip10_activation,
             params.payload_activation,
+            params.tx_introspection_activation,
+            params.byte_string_opcodes_activation,
         );
 
         let pruning_point_manager = PruningPointManager::new(
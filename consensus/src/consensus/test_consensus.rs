@@ -35,7 +35,7 @@ use crate::{
 use kaspa_database::create_temp_db;
 use kaspa_database::prelude::ConnBuilder;
 use std::future::Future;
-use std::{sync::Arc, thread::JoinHandle};
+use std::{io, path::Path, sync::Arc, thread::JoinHandle};
 
 pub struct TestConsensus {
     params: Params,
@@ -286,7 +286,7 @@ impl ConsensusFactory for TestConsensusFactory {
         (ci, self.tc.consensus_clone() as DynConsensusCtl)
     }
 
-    fn new_staging_consensus(&self) -> (ConsensusInstance, DynConsensusCtl) {
+    fn new_staging_consensus(&self, _add_genesis: bool) -> (ConsensusInstance, DynConsensusCtl) {
         unimplemented!()
     }
 
@@ -301,4 +301,8 @@ impl ConsensusFactory for TestConsensusFactory {
     fn delete_staging_entry(&self) {
         unimplemented!()
     }
+
+    fn create_management_checkpoint(&self, _meta_db_dir: &Path) -> io::Result<()> {
+        unimplemented!()
+    }
 }
@@ -23,7 +23,7 @@ use std::{
 };
 
 #[derive(Clone, Serialize, Deserialize)]
-pub(crate) struct ReachabilityData {
+pub struct ReachabilityData {
     pub parent: Hash,
     pub interval: Interval,
     pub height: u64,
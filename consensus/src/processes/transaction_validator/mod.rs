@@ -31,6 +31,10 @@ pub struct TransactionValidator {
     /// KIP-10 hardfork DAA score
     kip10_activation: ForkActivation,
     payload_activation: ForkActivation,
+    /// Transaction introspection opcodes hardfork DAA score
+    tx_introspection_activation: ForkActivation,
+    /// Byte string opcodes hardfork DAA score
+    byte_string_opcodes_activation: ForkActivation,
 }
 
 impl TransactionValidator {
@@ -48,6 +52,8 @@ impl TransactionValidator {
         storage_mass_activation: ForkActivation,
         kip10_activation: ForkActivation,
         payload_activation: ForkActivation,
+        tx_introspection_activation: ForkActivation,
+        byte_string_opcodes_activation: ForkActivation,
     ) -> Self {
         Self {
             max_tx_inputs,
@@ -62,6 +68,8 @@ impl TransactionValidator {
             storage_mass_activation,
             kip10_activation,
             payload_activation,
+            tx_introspection_activation,
+            byte_string_opcodes_activation,
         }
     }
 
@@ -88,6 +96,8 @@ impl TransactionValidator {
             storage_mass_activation: ForkActivation::never(),
             kip10_activation: ForkActivation::never(),
             payload_activation: ForkActivation::never(),
+            tx_introspection_activation: ForkActivation::never(),
+            byte_string_opcodes_activation: ForkActivation::never(),
         }
     }
 }
@@ -0,0 +1,144 @@
+use borsh::BorshDeserialize;
+use clap::{Arg, ArgAction, Command};
+use kaspa_consensus_core::{
+    hashing::sighash::SigHashReusedValuesUnsync,
+    tx::{PopulatedTransaction, Transaction, UtxoEntry},
+};
+use kaspa_txscript::{
+    asm::opcode_asm_name,
+    caches::Cache,
+    trace::{ScriptTracer, TraceStep},
+    EngineFlags, TxScriptEngine,
+};
+use kaspa_txscript_errors::TxScriptError;
+
+const SCRIPT_NAMES: [&str; 3] = ["signature", "spk", "redeem"];
+
+struct Args {
+    transaction: String,
+    utxos: Vec<String>,
+    input: usize,
+    flags: EngineFlags,
+}
+
+impl Args {
+    fn parse() -> Self {
+        let m = cli().get_matches();
+        Args {
+            transaction: m.get_one::<String>("transaction").cloned().unwrap(),
+            utxos: m.get_many::<String>("utxo").map(|utxos| utxos.cloned().collect()).unwrap_or_default(),
+            input: m.get_one::<usize>("input").cloned().unwrap(),
+            flags: EngineFlags {
+                kip10_enabled: m.get_flag("kip10"),
+                tx_introspection_enabled: m.get_flag("tx-introspection"),
+                byte_string_opcodes_enabled: m.get_flag("byte-string-opcodes"),
+            },
+        }
+    }
+}
+
+fn cli() -> Command {
+    Command::new("txscript-debug")
+        .about(format!("{} v{}", env!("CARGO_PKG_DESCRIPTION"), env!("CARGO_PKG_VERSION")))
+        .version(env!("CARGO_PKG_VERSION"))
+        .arg(Arg::new("transaction").long("tx").value_name("hex").required(true).help("Borsh serialized transaction in hex format"))
+        .arg(
+            Arg::new("utxo")
+                .long("utxo")
+                .value_name("hex")
+                .action(ArgAction::Append)
+                .help("Borsh serialized UTXO entry in hex format, given once per transaction input and in input order"),
+        )
+        .arg(
+            Arg::new("input")
+                .long("input")
+                .short('i')
+                .value_name("index")
+                .default_value("0")
+                .value_parser(clap::value_parser!(usize))
+                .help("Index of the input to trace"),
+        )
+        .arg(Arg::new("kip10").long("kip10").action(ArgAction::SetTrue).help("Enable KIP-10 introspection opcodes"))
+        .arg(
+            Arg::new("tx-introspection")
+                .long("tx-introspection")
+                .action(ArgAction::SetTrue)
+                .help("Enable the transaction level introspection opcodes"),
+        )
+        .arg(
+            Arg::new("byte-string-opcodes")
+                .long("byte-string-opcodes")
+                .action(ArgAction::SetTrue)
+                .help("Enable OpCat, OpSubStr, OpLeft and OpRight"),
+        )
+}
+
+/// Prints every executed step along with the resulting stacks
+struct PrintTracer;
+
+impl ScriptTracer for PrintTracer {
+    fn before_step(&mut self, _step: &TraceStep) {}
+
+    fn after_step(&mut self, step: &TraceStep, result: &Result<(), TxScriptError>) {
+        let data = if step.data.is_empty() { String::new() } else { format!(" <{}>", hex::encode(step.data)) };
+        let status = match result {
+            Ok(()) if step.executing => String::new(),
+            Ok(()) => " (skipped)".to_string(),
+            Err(err) => format!(" FAILED: {err}"),
+        };
+        println!("{:>9} {:04}  {}{data}{status}", SCRIPT_NAMES[step.script_index], step.pc, opcode_asm_name(step.opcode));
+        println!("{:>16}stack: {}", "", format_stack(step.dstack));
+        if !step.astack.is_empty() {
+            println!("{:>16}  alt: {}", "", format_stack(step.astack));
+        }
+    }
+}
+
+fn format_stack(stack: &[Vec<u8>]) -> String {
+    let items: Vec<String> = stack.iter().map(|item| if item.is_empty() { "<>".to_string() } else { hex::encode(item) }).collect();
+    format!("[{}]", items.join(", "))
+}
+
+fn decode<T: BorshDeserialize>(name: &str, value: &str) -> Result<T, String> {
+    let bytes = hex::decode(value).map_err(|err| format!("invalid {name} hex: {err}"))?;
+    T::try_from_slice(&bytes).map_err(|err| format!("invalid {name}: {err}"))
+}
+
+fn run(args: Args) -> Result<(), String> {
+    let tx: Transaction = decode("transaction", &args.transaction)?;
+    let entries = args.utxos.iter().map(|utxo| decode::<UtxoEntry>("UTXO entry", utxo)).collect::<Result<Vec<_>, _>>()?;
+    if entries.len() != tx.inputs.len() {
+        return Err(format!("expected {} UTXO entries, one per transaction input, got {}", tx.inputs.len(), entries.len()));
+    }
+    if args.input >= tx.inputs.len() {
+        return Err(format!("input index {} is out of bounds, the transaction has {} inputs", args.input, tx.inputs.len()));
+    }
+
+    let tx = PopulatedTransaction::new(&tx, entries);
+    let sig_cache = Cache::new(10_000);
+    let reused_values = SigHashReusedValuesUnsync::new();
+    let mut tracer = PrintTracer;
+    let mut vm = TxScriptEngine::from_transaction_input(
+        &tx,
+        &tx.tx.inputs[args.input],
+        args.input,
+        &tx.entries[args.input],
+        &reused_values,
+        &sig_cache,
+        args.flags,
+    )
+    .with_tracer(&mut tracer);
+
+    match vm.execute() {
+        Ok(()) => println!("input {} executed successfully", args.input),
+        Err(err) => println!("input {} failed: {err}", args.input),
+    }
+    Ok(())
+}
+
+fn main() {
+    if let Err(err) = run(Args::parse()) {
+        eprintln!("error: {err}");
+        std::process::exit(1);
+    }
+}
@@ -71,6 +71,8 @@ pub enum TxScriptError {
     ScriptSize(usize, usize),
     #[error("transaction output {0} is out of bounds, should be non-negative below {1}")]
     InvalidOutputIndex(i32, usize),
+    #[error("byte range [{0}, {1}) is out of bounds of an element of size {2}")]
+    InvalidByteRange(i64, i64, usize),
     #[error(transparent)]
     Serialization(#[from] SerializationError),
 }
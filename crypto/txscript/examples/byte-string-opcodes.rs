@@ -0,0 +1,273 @@
+use kaspa_addresses::{Address, Prefix, Version};
+use kaspa_consensus_core::{
+    hashing::{
+        sighash::{calc_schnorr_signature_hash, SigHashReusedValuesUnsync},
+        sighash_type::SIG_HASH_ALL,
+    },
+    tx::{
+        MutableTransaction, PopulatedTransaction, ScriptPublicKey, Transaction, TransactionId, TransactionInput, TransactionOutpoint,
+        TransactionOutput, UtxoEntry, VerifiableTransaction,
+    },
+};
+use kaspa_txscript::{
+    caches::Cache,
+    opcodes::codes::{
+        OpBlake2b, OpCat, OpCheckSig, OpData32, OpDup, OpElse, OpEndIf, OpEqual, OpEqualVerify, OpFalse, OpGreaterThanOrEqual, OpIf,
+        OpLeft, OpNot, OpRight, OpSize, OpSubStr, OpTrue, OpTxInputAmount, OpTxInputIndex, OpTxInputSpk, OpTxOutputAmount,
+        OpTxOutputSpk,
+    },
+    pay_to_address_script, pay_to_script_hash_script,
+    script_builder::{ScriptBuilder, ScriptBuilderResult},
+    EngineFlags, TxScriptEngine,
+};
+use kaspa_txscript_errors::TxScriptError::{EvalFalse, OpcodeDisabled, VerifyError};
+use rand::thread_rng;
+use secp256k1::Keypair;
+
+const BYTE_STRING_OPCODES_ENABLED: EngineFlags =
+    EngineFlags { kip10_enabled: true, tx_introspection_enabled: true, byte_string_opcodes_enabled: true };
+const BYTE_STRING_OPCODES_DISABLED: EngineFlags =
+    EngineFlags { kip10_enabled: true, tx_introspection_enabled: true, byte_string_opcodes_enabled: false };
+
+/// Size of a serialized P2SH script public key: version (2) + OpBlake2b (1) + OpData32 (1) + hash (32) + OpEqual (1)
+const P2SH_SPK_LEN: i64 = 37;
+
+/// Main function to execute all byte string opcode scenarios.
+///
+/// # Returns
+///
+/// * `ScriptBuilderResult<()>` - Result of script builder operations for all scenarios.
+fn main() -> ScriptBuilderResult<()> {
+    beneficiary_payout_scenario()?;
+    p2sh_migration_scenario()?;
+    Ok(())
+}
+
+/// # Beneficiary Payout Scenario
+///
+/// The covenant stores only the 32-byte public key of a beneficiary and reconstructs the full
+/// P2PK script public key on the stack with `OpCat`, then compares it with the introspected output.
+///
+/// 1. **Owner case:** The owner may spend the funds anywhere with a signature.
+/// 2. **Payout case:** Anyone may sweep the funds, as long as the output with the same index pays at
+///    least the input amount to the P2PK address of the beneficiary.
+///
+/// Byte string opcodes are disabled before activation, so the script cannot be spent at all until then.
+///
+/// # Returns
+///
+/// * `ScriptBuilderResult<()>` - Result of script builder operations for this scenario.
+fn beneficiary_payout_scenario() -> ScriptBuilderResult<()> {
+    println!("\n[PAYOUT] Running beneficiary payout scenario");
+    let owner = Keypair::new(secp256k1::SECP256K1, &mut thread_rng());
+    let beneficiary = Keypair::new(secp256k1::SECP256K1, &mut thread_rng());
+
+    let sig_cache = Cache::new(10_000);
+    let reused_values = SigHashReusedValuesUnsync::new();
+
+    // P2PK script public key layout: version (0x0000) || OpData32 || public key || OpCheckSig
+    let script = ScriptBuilder::new()
+        // Owner branch
+        .add_op(OpIf)?
+        .add_data(owner.x_only_public_key().0.serialize().as_slice())?
+        .add_op(OpCheckSig)?
+        // Payout branch
+        .add_op(OpElse)?
+        .add_data(&[0x00, 0x00, OpData32])?
+        .add_data(beneficiary.x_only_public_key().0.serialize().as_slice())?
+        .add_op(OpCat)?
+        .add_data(&[OpCheckSig])?
+        .add_op(OpCat)?
+        .add_ops(&[OpTxInputIndex, OpTxOutputSpk, OpEqualVerify])?
+        .add_ops(&[OpTxInputIndex, OpTxOutputAmount, OpTxInputIndex, OpTxInputAmount, OpGreaterThanOrEqual])?
+        .add_op(OpEndIf)?
+        .drain();
+
+    let input_value = 1000000000;
+    let utxo_entry = UtxoEntry::new(input_value, pay_to_script_hash_script(&script), 0, false);
+    let output = TransactionOutput { value: input_value, script_public_key: p2pk(&beneficiary) };
+    let mut tx = Transaction::new(1, vec![input_mock()], vec![output], 0, Default::default(), 0, vec![]);
+
+    // Check owner branch
+    {
+        println!("[PAYOUT] Checking owner branch");
+        let mut tx = MutableTransaction::with_entries(tx.clone(), vec![utxo_entry.clone()]);
+        let sig_hash = calc_schnorr_signature_hash(&tx.as_verifiable(), 0, SIG_HASH_ALL, &reused_values);
+        let msg = secp256k1::Message::from_digest_slice(sig_hash.as_bytes().as_slice()).unwrap();
+        let mut signature = owner.sign_schnorr(msg).as_ref().to_vec();
+        signature.push(SIG_HASH_ALL.to_u8());
+
+        tx.tx.inputs[0].signature_script = ScriptBuilder::new().add_data(&signature)?.add_op(OpTrue)?.add_data(&script)?.drain();
+        let tx = tx.as_verifiable();
+        let mut vm = TxScriptEngine::from_transaction_input(
+            &tx,
+            &tx.inputs()[0],
+            0,
+            &utxo_entry,
+            &reused_values,
+            &sig_cache,
+            BYTE_STRING_OPCODES_ENABLED,
+        );
+        assert_eq!(vm.execute(), Ok(()));
+        println!("[PAYOUT] Owner branch execution successful");
+    }
+
+    // Check payout branch
+    tx.inputs[0].signature_script = ScriptBuilder::new().add_op(OpFalse)?.add_data(&script)?.drain();
+    {
+        println!("[PAYOUT] Checking payout branch");
+        let tx = PopulatedTransaction::new(&tx, vec![utxo_entry.clone()]);
+        let mut vm = TxScriptEngine::from_transaction_input(
+            &tx,
+            &tx.tx.inputs[0],
+            0,
+            &utxo_entry,
+            &reused_values,
+            &sig_cache,
+            BYTE_STRING_OPCODES_ENABLED,
+        );
+        assert_eq!(vm.execute(), Ok(()));
+        println!("[PAYOUT] Payout branch execution successful");
+    }
+
+    // Check payout branch before activation
+    {
+        println!("[PAYOUT] Checking payout branch before byte string opcodes activation");
+        let tx = PopulatedTransaction::new(&tx, vec![utxo_entry.clone()]);
+        let mut vm = TxScriptEngine::from_transaction_input(
+            &tx,
+            &tx.tx.inputs[0],
+            0,
+            &utxo_entry,
+            &reused_values,
+            &sig_cache,
+            BYTE_STRING_OPCODES_DISABLED,
+        );
+        assert!(matches!(vm.execute(), Err(OpcodeDisabled(_))));
+        println!("[PAYOUT] Payout branch before activation failed as expected");
+    }
+
+    // Check payout branch with output going to the wrong address
+    {
+        println!("[PAYOUT] Checking payout branch with output going to wrong address");
+        let mut tx = tx.clone();
+        tx.outputs[0].script_public_key = p2pk(&owner);
+        let tx = PopulatedTransaction::new(&tx, vec![utxo_entry.clone()]);
+        let mut vm = TxScriptEngine::from_transaction_input(
+            &tx,
+            &tx.tx.inputs[0],
+            0,
+            &utxo_entry,
+            &reused_values,
+            &sig_cache,
+            BYTE_STRING_OPCODES_ENABLED,
+        );
+        assert_eq!(vm.execute(), Err(VerifyError));
+        println!("[PAYOUT] Payout branch with output going to wrong address failed as expected");
+    }
+
+    println!("[PAYOUT] Beneficiary payout scenario completed successfully");
+    Ok(())
+}
+
+/// # P2SH Migration Scenario
+///
+/// The covenant inspects the shape of the output script public key rather than its exact value:
+/// the funds may only move to a P2SH address other than the covenant itself.
+///
+/// 1. `OpSize` checks the output script public key has the P2SH length.
+/// 2. `OpLeft` checks the version and the `OpBlake2b OpData32` prefix.
+/// 3. `OpRight` checks the trailing `OpEqual`.
+/// 4. `OpSubStr` extracts the script hashes of the output and of the spent input, which must differ.
+///
+/// # Returns
+///
+/// * `ScriptBuilderResult<()>` - Result of script builder operations for this scenario.
+fn p2sh_migration_scenario() -> ScriptBuilderResult<()> {
+    println!("\n[MIGRATION] Running P2SH migration scenario");
+    let sig_cache = Cache::new(10_000);
+    let reused_values = SigHashReusedValuesUnsync::new();
+
+    let script = ScriptBuilder::new()
+        .add_ops(&[OpTxInputIndex, OpTxOutputSpk])?
+        // Check the length
+        .add_op(OpSize)?
+        .add_i64(P2SH_SPK_LEN)?
+        .add_op(OpEqualVerify)?
+        // Check the prefix
+        .add_op(OpDup)?
+        .add_i64(4)?
+        .add_op(OpLeft)?
+        .add_data(&[0x00, 0x00, OpBlake2b, OpData32])?
+        .add_op(OpEqualVerify)?
+        // Check the suffix
+        .add_op(OpDup)?
+        .add_i64(1)?
+        .add_op(OpRight)?
+        .add_data(&[OpEqual])?
+        .add_op(OpEqualVerify)?
+        // Compare the output script hash with the one of the spent input
+        .add_i64(4)?
+        .add_i64(32)?
+        .add_op(OpSubStr)?
+        .add_ops(&[OpTxInputIndex, OpTxInputSpk])?
+        .add_i64(4)?
+        .add_i64(32)?
+        .add_ops(&[OpSubStr, OpEqual, OpNot])?
+        .drain();
+
+    let input_value = 1000000000;
+    let utxo_entry = UtxoEntry::new(input_value, pay_to_script_hash_script(&script), 0, false);
+    let destination = pay_to_script_hash_script(&ScriptBuilder::new().add_op(OpTrue)?.drain());
+    let output = TransactionOutput { value: input_value, script_public_key: destination };
+    let mut input = input_mock();
+    input.signature_script = ScriptBuilder::new().add_data(&script)?.drain();
+    let tx = Transaction::new(1, vec![input], vec![output], 0, Default::default(), 0, vec![]);
+
+    let cases = [
+        ("another P2SH address", tx.outputs[0].script_public_key.clone(), Ok(())),
+        ("a P2PK address", p2pk(&Keypair::new(secp256k1::SECP256K1, &mut thread_rng())), Err(VerifyError)),
+        ("the covenant itself", utxo_entry.script_public_key.clone(), Err(EvalFalse)),
+    ];
+    for (name, script_public_key, expected) in cases {
+        println!("[MIGRATION] Checking migration to {name}");
+        let mut tx = tx.clone();
+        tx.outputs[0].script_public_key = script_public_key;
+        let tx = PopulatedTransaction::new(&tx, vec![utxo_entry.clone()]);
+        let mut vm = TxScriptEngine::from_transaction_input(
+            &tx,
+            &tx.tx.inputs[0],
+            0,
+            &utxo_entry,
+            &reused_values,
+            &sig_cache,
+            BYTE_STRING_OPCODES_ENABLED,
+        );
+        assert_eq!(vm.execute(), expected);
+        println!("[MIGRATION] Migration to {name} behaved as expected");
+    }
+
+    println!("[MIGRATION] P2SH migration scenario completed successfully");
+    Ok(())
+}
+
+// Helper function to create a transaction input spending a mock outpoint
+fn input_mock() -> TransactionInput {
+    TransactionInput {
+        previous_outpoint: TransactionOutpoint {
+            transaction_id: TransactionId::from_bytes([
+                0xc9, 0x97, 0xa5, 0xe5, 0x6e, 0x10, 0x42, 0x02, 0xfa, 0x20, 0x9c, 0x6a, 0x85, 0x2d, 0xd9, 0x06, 0x60, 0xa2, 0x0b,
+                0x2d, 0x9c, 0x35, 0x24, 0x23, 0xed, 0xce, 0x25, 0x85, 0x7f, 0xcd, 0x37, 0x04,
+            ]),
+            index: 0,
+        },
+        signature_script: vec![],
+        sequence: 4294967295,
+        sig_op_count: 1,
+    }
+}
+
+// Helper function to create a P2PK script public key
+fn p2pk(key: &Keypair) -> ScriptPublicKey {
+    pay_to_address_script(&Address::new(Prefix::Mainnet, Version::PubKey, key.x_only_public_key().0.serialize().as_slice()))
+}
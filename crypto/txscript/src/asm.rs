@@ -0,0 +1,171 @@
+//! Textual representation of scripts.
+//!
+//! Opcodes are written by name (`OP_DUP`, `OP_CHECKSIG`...) and data pushes as hex between angle brackets
+//! (`<aabb>`). A bare data push uses the shortest push opcode for its length, while any other encoding is
+//! kept explicit (`OP_PUSHDATA1 <aabb>`), so that [`disassemble`] and [`assemble`] round-trip exactly.
+
+use crate::opcodes::{codes, codes::*, opcode_from_name, opcode_name};
+use crate::parse_script;
+use kaspa_consensus_core::{hashing::sighash::SigHashReusedValuesUnsync, tx::PopulatedTransaction};
+use kaspa_utils::hex::{FromHex, ToHex};
+use thiserror::Error;
+
+#[derive(Error, PartialEq, Eq, Debug, Clone)]
+pub enum AsmError {
+    #[error("unknown opcode `{0}`")]
+    UnknownOpcode(String),
+
+    #[error("invalid data push `{0}`")]
+    InvalidData(String),
+
+    #[error("opcode {0} must be followed by a data push")]
+    MissingData(String),
+
+    #[error("opcode {0} cannot push {1} bytes")]
+    InvalidDataLength(String, usize),
+}
+
+pub type AsmResult<T> = std::result::Result<T, AsmError>;
+
+/// Returns the textual name of an opcode, e.g. `OP_CHECKSIG`
+pub fn opcode_asm_name(opcode: u8) -> String {
+    format!("OP_{}", opcode_name(opcode)[2..].to_uppercase())
+}
+
+/// Converts a script to its textual representation. A malformed script is disassembled up to the
+/// offending opcode, followed by an `[error: ...]` token.
+pub fn disassemble(script: &[u8]) -> String {
+    let mut tokens = Vec::new();
+    for opcode in parse_script::<PopulatedTransaction, SigHashReusedValuesUnsync>(script) {
+        match opcode {
+            Ok(opcode) if (OpData1..=OpPushData4).contains(&opcode.value()) => {
+                let data = format!("<{}>", opcode.get_data().to_hex());
+                if opcode.value() == shortest_push_opcode(opcode.get_data().len()) {
+                    tokens.push(data);
+                } else {
+                    tokens.push(format!("{} {data}", opcode_asm_name(opcode.value())));
+                }
+            }
+            Ok(opcode) => tokens.push(opcode_asm_name(opcode.value())),
+            Err(err) => {
+                tokens.push(format!("[error: {err}]"));
+                break;
+            }
+        }
+    }
+    tokens.join(" ")
+}
+
+/// Parses the textual representation of a script, as produced by [`disassemble`]
+pub fn assemble(asm: &str) -> AsmResult<Vec<u8>> {
+    let mut script = Vec::new();
+    let mut tokens = asm.split_whitespace();
+    while let Some(token) = tokens.next() {
+        if let Some(data) = parse_data(token)? {
+            push_data(&mut script, shortest_push_opcode(data.len()), &data);
+            continue;
+        }
+
+        let opcode = opcode_from_name(token).ok_or_else(|| AsmError::UnknownOpcode(token.to_string()))?;
+        if (OpData1..=OpPushData4).contains(&opcode) {
+            let data = tokens.next().map(parse_data).transpose()?.flatten().ok_or_else(|| AsmError::MissingData(token.to_string()))?;
+            let valid = match opcode {
+                codes::OpPushData1 => data.len() <= u8::MAX as usize,
+                codes::OpPushData2 => data.len() <= u16::MAX as usize,
+                codes::OpPushData4 => data.len() <= u32::MAX as usize,
+                _ => data.len() == opcode as usize,
+            };
+            if !valid {
+                return Err(AsmError::InvalidDataLength(token.to_string(), data.len()));
+            }
+            push_data(&mut script, opcode, &data);
+        } else {
+            script.push(opcode);
+        }
+    }
+    Ok(script)
+}
+
+/// Parses a `<hex>` token, returning `None` if the token is not a data push
+fn parse_data(token: &str) -> AsmResult<Option<Vec<u8>>> {
+    match token.strip_prefix('<').and_then(|token| token.strip_suffix('>')) {
+        Some(hex) => Vec::from_hex(hex).map(Some).map_err(|_| AsmError::InvalidData(token.to_string())),
+        None => Ok(None),
+    }
+}
+
+fn shortest_push_opcode(len: usize) -> u8 {
+    match len {
+        0 => OpFalse,
+        1..=75 => len as u8,
+        76..=0xff => OpPushData1,
+        0x100..=0xffff => OpPushData2,
+        _ => OpPushData4,
+    }
+}
+
+fn push_data(script: &mut Vec<u8>, opcode: u8, data: &[u8]) {
+    script.push(opcode);
+    match opcode {
+        codes::OpPushData1 => script.push(data.len() as u8),
+        codes::OpPushData2 => script.extend((data.len() as u16).to_le_bytes()),
+        codes::OpPushData4 => script.extend((data.len() as u32).to_le_bytes()),
+        _ => {}
+    }
+    script.extend_from_slice(data);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{pay_to_address_script, pay_to_script_hash_script, script_builder::ScriptBuilder};
+    use kaspa_addresses::{Address, Prefix, Version};
+
+    #[test]
+    fn test_disassemble() {
+        let key = [0x11u8; 32];
+        let p2pk = pay_to_address_script(&Address::new(Prefix::Mainnet, Version::PubKey, &key));
+        assert_eq!(disassemble(p2pk.script()), format!("<{}> OP_CHECKSIG", "11".repeat(32)));
+
+        let p2sh = pay_to_script_hash_script(&[OpTrue]);
+        assert!(disassemble(p2sh.script()).starts_with("OP_BLAKE2B <"));
+        assert!(disassemble(p2sh.script()).ends_with("> OP_EQUAL"));
+
+        let script = ScriptBuilder::new().add_op(OpDup).unwrap().add_i64(5).unwrap().add_data(&[0xaa; 80]).unwrap().drain();
+        assert_eq!(disassemble(&script), format!("OP_DUP OP_5 <{}>", "aa".repeat(80)));
+
+        // Non canonical pushes keep their opcode
+        assert_eq!(disassemble(&[OpPushData1, 0x02, 0xaa, 0xbb]), "OP_PUSHDATA1 <aabb>");
+
+        // Truncated push
+        assert_eq!(disassemble(&[OpTrue, OpData2, 0xaa]), "OP_TRUE [error: opcode requires 2 bytes, but script only has 1 remaining]");
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let scripts = [
+            vec![],
+            vec![OpFalse, OpTrue, Op16, Op1Negate],
+            vec![OpData1, 0x05, OpData2, 0xaa, 0xbb],
+            vec![OpPushData1, 0x01, 0xaa, OpPushData2, 0x01, 0x00, 0xbb, OpPushData4, 0x01, 0x00, 0x00, 0x00, 0xcc],
+            [vec![OpPushData1, 80], vec![0xdd; 80], vec![OpCat, OpTxInputIndex, OpTxOutputSpk, OpEqualVerify]].concat(),
+            (0..=255u8).filter(|opcode| !(OpData1..=OpPushData4).contains(opcode)).collect(),
+        ];
+        for script in scripts {
+            let asm = disassemble(&script);
+            assert_eq!(assemble(&asm), Ok(script.clone()), "failed to round-trip `{asm}`");
+        }
+    }
+
+    #[test]
+    fn test_assemble() {
+        assert_eq!(assemble("OP_DUP op_blake2b OpEqual OP_0 OP_1"), Ok(vec![OpDup, OpBlake2b, OpEqual, OpFalse, OpTrue]));
+        assert_eq!(assemble("<> <05> OP_DATA1 <05>"), Ok(vec![OpFalse, OpData1, 0x05, OpData1, 0x05]));
+        assert_eq!(assemble("OP_FOO"), Err(AsmError::UnknownOpcode("OP_FOO".to_string())));
+        assert_eq!(assemble("<0g>"), Err(AsmError::InvalidData("<0g>".to_string())));
+        assert_eq!(assemble("<abc>"), Err(AsmError::InvalidData("<abc>".to_string())));
+        assert_eq!(assemble("OP_PUSHDATA1"), Err(AsmError::MissingData("OP_PUSHDATA1".to_string())));
+        assert_eq!(assemble("OP_PUSHDATA1 OP_DUP"), Err(AsmError::MissingData("OP_PUSHDATA1".to_string())));
+        assert_eq!(assemble("OP_DATA2 <aa>"), Err(AsmError::InvalidDataLength("OP_DATA2".to_string(), 1)));
+    }
+}
@@ -1,4 +1,4 @@
-use crate::TxScriptError;
+use crate::{TxScriptError, MAX_SCRIPT_ELEMENT_SIZE};
 use core::fmt::Debug;
 use core::iter;
 use kaspa_txscript_errors::SerializationError;
@@ -70,6 +70,8 @@ pub(crate) trait DataStack {
     fn push_item<T: Debug>(&mut self, item: T) -> Result<(), TxScriptError>
     where
         Vec<u8>: OpcodeData<T>;
+    /// Pushes a raw element built by script execution, rejecting it if it exceeds [`MAX_SCRIPT_ELEMENT_SIZE`]
+    fn push_element(&mut self, element: Vec<u8>) -> Result<(), TxScriptError>;
     fn drop_items<const SIZE: usize>(&mut self) -> Result<(), TxScriptError>;
     fn dup_items<const SIZE: usize>(&mut self) -> Result<(), TxScriptError>;
     fn over_items<const SIZE: usize>(&mut self) -> Result<(), TxScriptError>;
@@ -283,6 +285,15 @@ impl DataStack for Stack {
         Ok(())
     }
 
+    #[inline]
+    fn push_element(&mut self, element: Vec<u8>) -> Result<(), TxScriptError> {
+        if element.len() > MAX_SCRIPT_ELEMENT_SIZE {
+            return Err(TxScriptError::ElementTooBig(element.len(), MAX_SCRIPT_ELEMENT_SIZE));
+        }
+        Vec::push(self, element);
+        Ok(())
+    }
+
     #[inline]
     fn drop_items<const SIZE: usize>(&mut self) -> Result<(), TxScriptError> {
         match self.len() >= SIZE {
@@ -1,6 +1,7 @@
 extern crate alloc;
 extern crate core;
 
+pub mod asm;
 pub mod caches;
 mod data_stack;
 pub mod error;
@@ -9,6 +10,7 @@ pub mod result;
 pub mod script_builder;
 pub mod script_class;
 pub mod standard;
+pub mod trace;
 #[cfg(feature = "wasm32-sdk")]
 pub mod wasm;
 
@@ -16,14 +18,17 @@ use crate::caches::Cache;
 use crate::data_stack::{DataStack, Stack};
 use crate::opcodes::{deserialize_next_opcode, OpCodeImplementation};
 use itertools::Itertools;
-use kaspa_consensus_core::hashing::sighash::{calc_ecdsa_signature_hash, calc_schnorr_signature_hash, SigHashReusedValues};
+use kaspa_consensus_core::hashing::sighash::{
+    calc_ecdsa_signature_hash, calc_schnorr_signature_hash, SigHashReusedValues, SigHashReusedValuesUnsync,
+};
 use kaspa_consensus_core::hashing::sighash_type::SigHashType;
-use kaspa_consensus_core::tx::{ScriptPublicKey, TransactionInput, UtxoEntry, VerifiableTransaction};
+use kaspa_consensus_core::tx::{PopulatedTransaction, ScriptPublicKey, TransactionInput, UtxoEntry, VerifiableTransaction};
 use kaspa_txscript_errors::TxScriptError;
 use log::trace;
 use opcodes::codes::OpReturn;
 use opcodes::{codes, to_small_int, OpCond};
 use script_class::ScriptClass;
+use trace::{ScriptTracer, TraceStep};
 
 pub mod prelude {
     pub use super::standard::*;
@@ -67,6 +72,18 @@ pub struct SigCacheKey {
     message: secp256k1::Message,
 }
 
+/// Consensus features which change the behavior of the script engine once activated
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct EngineFlags {
+    /// Whether KIP-10 transaction introspection opcodes and 8-byte arithmetic are enabled
+    pub kip10_enabled: bool,
+    /// Whether the transaction level (version, lock time, subnetwork id, gas, payload) and the input
+    /// sequence, block DAA score and coinbase introspection opcodes are enabled
+    pub tx_introspection_enabled: bool,
+    /// Whether the byte string opcodes (OpCat, OpSubStr, OpLeft, OpRight) are enabled
+    pub byte_string_opcodes_enabled: bool,
+}
+
 enum ScriptSource<'a, T: VerifiableTransaction> {
     TxInput { tx: &'a T, input: &'a TransactionInput, idx: usize, utxo_entry: &'a UtxoEntry, is_p2sh: bool },
     StandAloneScripts(Vec<&'a [u8]>),
@@ -85,7 +102,9 @@ pub struct TxScriptEngine<'a, T: VerifiableTransaction, Reused: SigHashReusedVal
     cond_stack: Vec<OpCond>, // Following if stacks, and whether it is running
 
     num_ops: i32,
-    kip10_enabled: bool,
+    flags: EngineFlags,
+
+    tracer: Option<&'a mut dyn ScriptTracer>,
 }
 
 fn parse_script<T: VerifiableTransaction, Reused: SigHashReusedValues>(
@@ -94,6 +113,17 @@ fn parse_script<T: VerifiableTransaction, Reused: SigHashReusedValues>(
     script.iter().batching(|it| deserialize_next_opcode(it))
 }
 
+/// Like [`parse_script`], but also yields the byte offset of each opcode within the script
+fn parse_script_with_offsets<T: VerifiableTransaction, Reused: SigHashReusedValues>(
+    script: &[u8],
+) -> impl Iterator<Item = (usize, Result<DynOpcodeImplementation<T, Reused>, TxScriptError>)> + '_ {
+    let mut it = script.iter();
+    std::iter::from_fn(move || {
+        let pc = script.len() - it.len();
+        deserialize_next_opcode(&mut it).map(|opcode| (pc, opcode))
+    })
+}
+
 #[must_use]
 pub fn get_sig_op_count<T: VerifiableTransaction, Reused: SigHashReusedValues>(
     signature_script: &[u8],
@@ -115,6 +145,17 @@ pub fn get_sig_op_count<T: VerifiableTransaction, Reused: SigHashReusedValues>(
     get_sig_op_count_by_opcodes(&p2sh_ops)
 }
 
+/// Returns the redeem script revealed by the signature script of a pay-to-script-hash spend, i.e. its last
+/// data push, or `None` if the signature script is empty or not push-only.
+pub fn extract_redeem_script(signature_script: &[u8]) -> Option<Vec<u8>> {
+    let signature_script_ops =
+        parse_script::<PopulatedTransaction, SigHashReusedValuesUnsync>(signature_script).collect::<Result<Vec<_>, _>>().ok()?;
+    if !signature_script_ops.iter().all(|op| op.is_push_opcode()) {
+        return None;
+    }
+    signature_script_ops.last().map(|op| op.get_data().to_vec())
+}
+
 fn get_sig_op_count_by_opcodes<T: VerifiableTransaction, Reused: SigHashReusedValues>(
     opcodes: &[Result<DynOpcodeImplementation<T, Reused>, TxScriptError>],
 ) -> u64 {
@@ -155,7 +196,7 @@ pub fn is_unspendable<T: VerifiableTransaction, Reused: SigHashReusedValues>(scr
 }
 
 impl<'a, T: VerifiableTransaction, Reused: SigHashReusedValues> TxScriptEngine<'a, T, Reused> {
-    pub fn new(reused_values: &'a Reused, sig_cache: &'a Cache<SigCacheKey, bool>, kip10_enabled: bool) -> Self {
+    pub fn new(reused_values: &'a Reused, sig_cache: &'a Cache<SigCacheKey, bool>, flags: EngineFlags) -> Self {
         Self {
             dstack: vec![],
             astack: vec![],
@@ -164,7 +205,8 @@ impl<'a, T: VerifiableTransaction, Reused: SigHashReusedValues> TxScriptEngine<'
             sig_cache,
             cond_stack: vec![],
             num_ops: 0,
-            kip10_enabled,
+            flags,
+            tracer: None,
         }
     }
 
@@ -177,7 +219,7 @@ impl<'a, T: VerifiableTransaction, Reused: SigHashReusedValues> TxScriptEngine<'
     /// * `utxo_entry` - UTXO entry being spent
     /// * `reused_values` - Reused values for signature hashing
     /// * `sig_cache` - Cache for signature verification
-    /// * `kip10_enabled` - Whether KIP-10 transaction introspection opcodes are enabled
+    /// * `flags` - Consensus features enabled for this validation
     ///
     /// # Panics
     /// * When input_idx >= number of inputs in transaction (malformed input)
@@ -191,7 +233,7 @@ impl<'a, T: VerifiableTransaction, Reused: SigHashReusedValues> TxScriptEngine<'
         utxo_entry: &'a UtxoEntry,
         reused_values: &'a Reused,
         sig_cache: &'a Cache<SigCacheKey, bool>,
-        kip10_enabled: bool,
+        flags: EngineFlags,
     ) -> Self {
         let script_public_key = utxo_entry.script_public_key.script();
         // The script_public_key in P2SH is just validating the hash on the OpMultiSig script
@@ -206,7 +248,8 @@ impl<'a, T: VerifiableTransaction, Reused: SigHashReusedValues> TxScriptEngine<'
             sig_cache,
             cond_stack: Default::default(),
             num_ops: 0,
-            kip10_enabled,
+            flags,
+            tracer: None,
         }
     }
 
@@ -214,7 +257,7 @@ impl<'a, T: VerifiableTransaction, Reused: SigHashReusedValues> TxScriptEngine<'
         script: &'a [u8],
         reused_values: &'a Reused,
         sig_cache: &'a Cache<SigCacheKey, bool>,
-        kip10_enabled: bool,
+        flags: EngineFlags,
     ) -> Self {
         Self {
             dstack: Default::default(),
@@ -224,16 +267,23 @@ impl<'a, T: VerifiableTransaction, Reused: SigHashReusedValues> TxScriptEngine<'
             sig_cache,
             cond_stack: Default::default(),
             num_ops: 0,
-            kip10_enabled,
+            flags,
+            tracer: None,
         }
     }
 
+    /// Attaches a tracer which is called before and after each opcode executed by the engine
+    pub fn with_tracer(mut self, tracer: &'a mut dyn ScriptTracer) -> Self {
+        self.tracer = Some(tracer);
+        self
+    }
+
     #[inline]
     pub fn is_executing(&self) -> bool {
         self.cond_stack.is_empty() || *self.cond_stack.last().expect("Checked not empty") == OpCond::True
     }
 
-    fn execute_opcode(&mut self, opcode: DynOpcodeImplementation<T, Reused>) -> Result<(), TxScriptError> {
+    fn execute_opcode(&mut self, opcode: &DynOpcodeImplementation<T, Reused>) -> Result<(), TxScriptError> {
         // Different from kaspad: Illegal and disabled opcode are checked on execute instead
         // Note that this includes OP_RESERVED which counts as a push operation.
         if !opcode.is_push_opcode() {
@@ -255,28 +305,15 @@ impl<'a, T: VerifiableTransaction, Reused: SigHashReusedValues> TxScriptEngine<'
         }
     }
 
-    fn execute_script(&mut self, script: &[u8], verify_only_push: bool) -> Result<(), TxScriptError> {
-        let script_result = parse_script(script).try_for_each(|opcode| {
+    fn execute_script(&mut self, script_index: usize, script: &[u8], verify_only_push: bool) -> Result<(), TxScriptError> {
+        let script_result = parse_script_with_offsets(script).try_for_each(|(pc, opcode)| {
             let opcode = opcode?;
-            if opcode.is_disabled() {
-                return Err(TxScriptError::OpcodeDisabled(format!("{:?}", opcode)));
-            }
+            let executing = self.is_executing() || opcode.is_conditional();
+            self.trace(script_index, pc, &opcode, executing, None);
 
-            if opcode.always_illegal() {
-                return Err(TxScriptError::OpcodeReserved(format!("{:?}", opcode)));
-            }
-
-            if verify_only_push && !opcode.is_push_opcode() {
-                return Err(TxScriptError::SignatureScriptNotPushOnly);
-            }
-
-            self.execute_opcode(opcode)?;
-
-            let combined_size = self.astack.len() + self.dstack.len();
-            if combined_size > MAX_STACK_SIZE {
-                return Err(TxScriptError::StackSizeExceeded(combined_size, MAX_STACK_SIZE));
-            }
-            Ok(())
+            let step_result = self.execute_step(&opcode, verify_only_push);
+            self.trace(script_index, pc, &opcode, executing, Some(&step_result));
+            step_result
         });
 
         // Moving between scripts - we can't be inside an if
@@ -291,6 +328,55 @@ impl<'a, T: VerifiableTransaction, Reused: SigHashReusedValues> TxScriptEngine<'
         script_result
     }
 
+    fn execute_step(&mut self, opcode: &DynOpcodeImplementation<T, Reused>, verify_only_push: bool) -> Result<(), TxScriptError> {
+        if opcode.is_disabled(self.flags) {
+            return Err(TxScriptError::OpcodeDisabled(format!("{:?}", opcode)));
+        }
+
+        if opcode.always_illegal() {
+            return Err(TxScriptError::OpcodeReserved(format!("{:?}", opcode)));
+        }
+
+        if verify_only_push && !opcode.is_push_opcode() {
+            return Err(TxScriptError::SignatureScriptNotPushOnly);
+        }
+
+        self.execute_opcode(opcode)?;
+
+        let combined_size = self.astack.len() + self.dstack.len();
+        if combined_size > MAX_STACK_SIZE {
+            return Err(TxScriptError::StackSizeExceeded(combined_size, MAX_STACK_SIZE));
+        }
+        Ok(())
+    }
+
+    /// Reports a step to the tracer, if any. A step without a result is reported before the opcode runs
+    #[inline]
+    fn trace(
+        &mut self,
+        script_index: usize,
+        pc: usize,
+        opcode: &DynOpcodeImplementation<T, Reused>,
+        executing: bool,
+        result: Option<&Result<(), TxScriptError>>,
+    ) {
+        if let Some(tracer) = self.tracer.as_deref_mut() {
+            let step = TraceStep {
+                script_index,
+                pc,
+                opcode: opcode.value(),
+                data: opcode.get_data(),
+                executing,
+                dstack: &self.dstack,
+                astack: &self.astack,
+            };
+            match result {
+                None => tracer.before_step(&step),
+                Some(result) => tracer.after_step(&step, result),
+            }
+        }
+    }
+
     pub fn execute(&mut self) -> Result<(), TxScriptError> {
         let (scripts, is_p2sh) = match &self.script_source {
             ScriptSource::TxInput { input, utxo_entry, is_p2sh, .. } => {
@@ -329,14 +415,14 @@ impl<'a, T: VerifiableTransaction, Reused: SigHashReusedValues> TxScriptEngine<'
             if is_p2sh && idx == 1 {
                 saved_stack = Some(self.dstack.clone());
             }
-            self.execute_script(s, verify_only_push)
+            self.execute_script(idx, s, verify_only_push)
         })?;
 
         if is_p2sh {
             self.check_error_condition(false)?;
             self.dstack = saved_stack.ok_or(TxScriptError::EmptyStack)?;
             let script = self.dstack.pop().ok_or(TxScriptError::EmptyStack)?;
-            self.execute_script(script.as_slice(), false)?
+            self.execute_script(2, script.as_slice(), false)?
         }
 
         self.check_error_condition(true)?;
@@ -553,6 +639,7 @@ mod tests {
     use crate::opcodes::codes::{OpBlake2b, OpCheckSig, OpData1, OpData2, OpData32, OpDup, OpEqual, OpPushData1, OpTrue};
 
     use super::*;
+    use crate::trace::TraceRecorder;
     use kaspa_consensus_core::hashing::sighash::SigHashReusedValuesUnsync;
     use kaspa_consensus_core::tx::{
         PopulatedTransaction, ScriptPublicKey, Transaction, TransactionId, TransactionOutpoint, TransactionOutput,
@@ -618,7 +705,7 @@ mod tests {
                     &utxo_entry,
                     &reused_values,
                     &sig_cache,
-                    kip10_enabled,
+                    EngineFlags { kip10_enabled, ..Default::default() },
                 );
                 assert_eq!(vm.execute(), test.expected_result);
             });
@@ -973,6 +1060,62 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_tracer() {
+        let sig_cache = Cache::new(10_000);
+        let reused_values = SigHashReusedValuesUnsync::new();
+
+        // OpTrue, OpIf, Op2, OpElse, Op3, OpEndIf, OpToAltStack, OpFromAltStack, OpDrop, OpTrue
+        let script = b"\x51\x63\x52\x67\x53\x68\x6b\x6c\x75\x51";
+        let mut recorder = TraceRecorder::new();
+        let mut vm = TxScriptEngine::<VerifiableTransactionMock, SigHashReusedValuesUnsync>::from_script(
+            script,
+            &reused_values,
+            &sig_cache,
+            Default::default(),
+        )
+        .with_tracer(&mut recorder);
+        assert_eq!(vm.execute(), Ok(()));
+
+        let expected = [
+            (0, true, vec![vec![1u8]], vec![]),
+            (1, true, vec![], vec![]),
+            (2, true, vec![vec![2]], vec![]),
+            (3, true, vec![vec![2]], vec![]),
+            (4, false, vec![vec![2]], vec![]),
+            (5, true, vec![vec![2]], vec![]),
+            (6, true, vec![], vec![vec![2]]),
+            (7, true, vec![vec![2]], vec![]),
+            (8, true, vec![], vec![]),
+            (9, true, vec![vec![1]], vec![]),
+        ];
+        assert_eq!(recorder.records.len(), expected.len());
+        for (record, (pc, executing, dstack, astack)) in recorder.records.iter().zip(expected) {
+            assert_eq!(record.script_index, 0);
+            assert_eq!(record.pc, pc);
+            assert_eq!(record.opcode, script[pc]);
+            assert_eq!(record.executing, executing, "pc {pc}");
+            assert_eq!(record.dstack, dstack, "pc {pc}");
+            assert_eq!(record.astack, astack, "pc {pc}");
+            assert_eq!(record.result, Ok(()));
+        }
+
+        // Steps rejected by the engine are reported with their error
+        let script = b"\x51\x7e"; // OpTrue, OpCat
+        let mut recorder = TraceRecorder::new();
+        let mut vm = TxScriptEngine::<VerifiableTransactionMock, SigHashReusedValuesUnsync>::from_script(
+            script,
+            &reused_values,
+            &sig_cache,
+            Default::default(),
+        )
+        .with_tracer(&mut recorder);
+        assert!(matches!(vm.execute(), Err(TxScriptError::OpcodeDisabled(_))));
+        assert_eq!(recorder.records.len(), 2);
+        assert_eq!(recorder.records[1].pc, 1);
+        assert!(matches!(recorder.records[1].result, Err(TxScriptError::OpcodeDisabled(_))));
+    }
 }
 
 #[cfg(test)]
@@ -1085,7 +1228,7 @@ mod bitcoind_tests {
                 &populated_tx.entries[0],
                 &reused_values,
                 &sig_cache,
-                kip10_enabled,
+                EngineFlags { kip10_enabled, ..Default::default() },
             );
             vm.execute().map_err(UnifiedError::TxScriptError)
         }
@@ -122,6 +122,27 @@ macro_rules! opcode_list {
             }
         }
 
+        /// Returns the name of an opcode as declared in the opcode list, e.g. `OpCheckSig`
+        pub fn opcode_name(opcode: u8) -> &'static str {
+            match opcode {
+                $(
+                    $num => stringify!($name),
+                )*
+            }
+        }
+
+        /// Returns the value of the opcode with the given name or alias. The lookup ignores case and
+        /// underscores, so `OpCheckSig` and `OP_CHECKSIG` are both accepted.
+        pub fn opcode_from_name(name: &str) -> Option<u8> {
+            let name = name.replace('_', "").to_uppercase();
+            $(
+                if name == stringify!($name).to_uppercase() $(|| name == stringify!($alias).to_uppercase())? {
+                    return Some($num);
+                }
+            )*
+            None
+        }
+
         #[cfg(test)]
         use crate::script_builder::{ScriptBuilder, ScriptBuilderResult};
 
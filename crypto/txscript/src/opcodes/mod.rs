@@ -3,8 +3,8 @@ mod macros;
 
 use crate::{
     data_stack::{DataStack, Kip10I64, OpcodeData},
-    ScriptSource, SpkEncoding, TxScriptEngine, TxScriptError, LOCK_TIME_THRESHOLD, MAX_TX_IN_SEQUENCE_NUM, NO_COST_OPCODE,
-    SEQUENCE_LOCK_TIME_DISABLED, SEQUENCE_LOCK_TIME_MASK,
+    EngineFlags, ScriptSource, SpkEncoding, TxScriptEngine, TxScriptError, LOCK_TIME_THRESHOLD, MAX_TX_IN_SEQUENCE_NUM,
+    NO_COST_OPCODE, SEQUENCE_LOCK_TIME_DISABLED, SEQUENCE_LOCK_TIME_MASK,
 };
 use blake2b_simd::Params;
 use kaspa_consensus_core::hashing::sighash::SigHashReusedValues;
@@ -66,7 +66,7 @@ pub trait OpCodeMetadata: Debug {
     // For push data- check if we can use shorter encoding
     fn check_minimal_data_push(&self) -> Result<(), TxScriptError>;
 
-    fn is_disabled(&self) -> bool;
+    fn is_disabled(&self, flags: EngineFlags) -> bool;
     fn always_illegal(&self) -> bool;
     fn is_push_opcode(&self) -> bool;
     fn get_data(&self) -> &[u8];
@@ -107,14 +107,13 @@ impl<const CODE: u8> OpCodeMetadata for OpCode<CODE> {
         CODE
     }
 
-    fn is_disabled(&self) -> bool {
+    fn is_disabled(&self, flags: EngineFlags) -> bool {
+        if matches!(CODE, codes::OpCat | codes::OpSubStr | codes::OpLeft | codes::OpRight) {
+            return !flags.byte_string_opcodes_enabled;
+        }
         matches!(
             CODE,
-            codes::OpCat
-                | codes::OpSubStr
-                | codes::OpLeft
-                | codes::OpRight
-                | codes::OpInvert
+            codes::OpInvert
                 | codes::OpAnd
                 | codes::OpOr
                 | codes::OpXor
@@ -216,13 +215,27 @@ fn push_number<T: VerifiableTransaction, Reused: SigHashReusedValues>(
     Ok(())
 }
 
+/// Pushes the `[start, end)` byte range of `element`, failing if the range does not lie within it
+#[inline]
+fn push_byte_range<T: VerifiableTransaction, Reused: SigHashReusedValues>(
+    element: Vec<u8>,
+    start: i64,
+    end: i64,
+    vm: &mut TxScriptEngine<T, Reused>,
+) -> OpCodeResult {
+    match (usize::try_from(start), usize::try_from(end)) {
+        (Ok(s), Ok(e)) if s <= e && e <= element.len() => vm.dstack.push_element(element[s..e].to_vec()),
+        _ => Err(TxScriptError::InvalidByteRange(start, end, element.len())),
+    }
+}
+
 /// This macro helps to avoid code duplication in numeric opcodes where the only difference
 /// between KIP10_ENABLED and disabled states is the numeric type used (Kip10I64 vs i64).
 /// KIP10I64 deserializator supports 8-byte integers
 // TODO: Remove this macro after KIP-10 activation.
 macro_rules! numeric_op {
     ($vm: expr, $pattern: pat, $count: expr, $block: expr) => {
-        if $vm.kip10_enabled {
+        if $vm.flags.kip10_enabled {
             let $pattern: [Kip10I64; $count] = $vm.dstack.pop_items()?;
             let r = $block;
             $vm.dstack.push_item(r)?;
@@ -536,10 +549,46 @@ opcode_list! {
     }
 
     // Splice opcodes.
-    opcode OpCat<0x7e, 1>(self, vm) Err(TxScriptError::OpcodeDisabled(format!("{self:?}")))
-    opcode OpSubStr<0x7f, 1>(self, vm) Err(TxScriptError::OpcodeDisabled(format!("{self:?}")))
-    opcode OpLeft<0x80, 1>(self, vm) Err(TxScriptError::OpcodeDisabled(format!("{self:?}")))
-    opcode OpRight<0x81, 1>(self, vm) Err(TxScriptError::OpcodeDisabled(format!("{self:?}")))
+    opcode OpCat<0x7e, 1>(self, vm) {
+        if vm.flags.byte_string_opcodes_enabled {
+            let [mut first, second] = vm.dstack.pop_raw()?;
+            first.extend(second);
+            vm.dstack.push_element(first)
+        } else {
+            Err(TxScriptError::OpcodeDisabled(format!("{self:?}")))
+        }
+    }
+    // Stack: [element, start, size] -> [element[start..start + size]]
+    opcode OpSubStr<0x7f, 1>(self, vm) {
+        if vm.flags.byte_string_opcodes_enabled {
+            let [start, size]: [i32; 2] = vm.dstack.pop_items()?;
+            let [element] = vm.dstack.pop_raw()?;
+            push_byte_range(element, start as i64, start as i64 + size as i64, vm)
+        } else {
+            Err(TxScriptError::OpcodeDisabled(format!("{self:?}")))
+        }
+    }
+    // Stack: [element, size] -> [element[..size]]
+    opcode OpLeft<0x80, 1>(self, vm) {
+        if vm.flags.byte_string_opcodes_enabled {
+            let [size]: [i32; 1] = vm.dstack.pop_items()?;
+            let [element] = vm.dstack.pop_raw()?;
+            push_byte_range(element, 0, size as i64, vm)
+        } else {
+            Err(TxScriptError::OpcodeDisabled(format!("{self:?}")))
+        }
+    }
+    // Stack: [element, size] -> [element[element.len() - size..]]
+    opcode OpRight<0x81, 1>(self, vm) {
+        if vm.flags.byte_string_opcodes_enabled {
+            let [size]: [i32; 1] = vm.dstack.pop_items()?;
+            let [element] = vm.dstack.pop_raw()?;
+            let len = element.len() as i64;
+            push_byte_range(element, len - size as i64, len, vm)
+        } else {
+            Err(TxScriptError::OpcodeDisabled(format!("{self:?}")))
+        }
+    }
 
     opcode OpSize<0x82, 1>(self, vm) {
         match vm.dstack.last() {
@@ -642,7 +691,7 @@ opcode_list! {
     }
 
     opcode OpNumEqualVerify<0x9d, 1>(self, vm) {
-        if vm.kip10_enabled {
+        if vm.flags.kip10_enabled {
             let [a,b]: [Kip10I64; 2] = vm.dstack.pop_items()?;
             match a == b {
                 true => Ok(()),
@@ -879,9 +928,20 @@ opcode_list! {
 
     // Introspection opcodes
     // Transaction level opcodes (following Transaction struct field order)
-    opcode OpTxVersion<0xb2, 1>(self, vm) Err(TxScriptError::OpcodeReserved(format!("{self:?}")))
+    opcode OpTxVersion<0xb2, 1>(self, vm) {
+        if vm.flags.tx_introspection_enabled {
+            match vm.script_source {
+                ScriptSource::TxInput{tx, ..} => {
+                    push_number(tx.tx().version as i64, vm)
+                },
+                _ => Err(TxScriptError::InvalidSource("OpTxVersion only applies to transaction inputs".to_string()))
+            }
+        } else {
+            Err(TxScriptError::OpcodeReserved(format!("{self:?}")))
+        }
+    }
     opcode OpTxInputCount<0xb3, 1>(self, vm) {
-        if vm.kip10_enabled {
+        if vm.flags.kip10_enabled {
             match vm.script_source {
                 ScriptSource::TxInput{tx, ..} => {
                     push_number(tx.inputs().len() as i64, vm)
@@ -893,7 +953,7 @@ opcode_list! {
         }
     }
     opcode OpTxOutputCount<0xb4, 1>(self, vm) {
-        if vm.kip10_enabled {
+        if vm.flags.kip10_enabled {
             match vm.script_source {
                 ScriptSource::TxInput{tx, ..} => {
                     push_number(tx.outputs().len() as i64, vm)
@@ -904,13 +964,57 @@ opcode_list! {
             Err(TxScriptError::InvalidOpcode(format!("{self:?}")))
         }
     }
-    opcode OpTxLockTime<0xb5, 1>(self, vm) Err(TxScriptError::OpcodeReserved(format!("{self:?}")))
-    opcode OpTxSubnetId<0xb6, 1>(self, vm) Err(TxScriptError::OpcodeReserved(format!("{self:?}")))
-    opcode OpTxGas<0xb7, 1>(self, vm) Err(TxScriptError::OpcodeReserved(format!("{self:?}")))
-    opcode OpTxPayload<0xb8, 1>(self, vm) Err(TxScriptError::OpcodeReserved(format!("{self:?}")))
+    opcode OpTxLockTime<0xb5, 1>(self, vm) {
+        if vm.flags.tx_introspection_enabled {
+            match vm.script_source {
+                ScriptSource::TxInput{tx, ..} => {
+                    push_number(tx.tx().lock_time.try_into().map_err(|e: TryFromIntError| TxScriptError::NumberTooBig(e.to_string()))?, vm)
+                },
+                _ => Err(TxScriptError::InvalidSource("OpTxLockTime only applies to transaction inputs".to_string()))
+            }
+        } else {
+            Err(TxScriptError::OpcodeReserved(format!("{self:?}")))
+        }
+    }
+    opcode OpTxSubnetId<0xb6, 1>(self, vm) {
+        if vm.flags.tx_introspection_enabled {
+            match vm.script_source {
+                ScriptSource::TxInput{tx, ..} => {
+                    push_data(AsRef::<[u8]>::as_ref(&tx.tx().subnetwork_id).to_vec(), vm)
+                },
+                _ => Err(TxScriptError::InvalidSource("OpTxSubnetId only applies to transaction inputs".to_string()))
+            }
+        } else {
+            Err(TxScriptError::OpcodeReserved(format!("{self:?}")))
+        }
+    }
+    opcode OpTxGas<0xb7, 1>(self, vm) {
+        if vm.flags.tx_introspection_enabled {
+            match vm.script_source {
+                ScriptSource::TxInput{tx, ..} => {
+                    push_number(tx.tx().gas.try_into().map_err(|e: TryFromIntError| TxScriptError::NumberTooBig(e.to_string()))?, vm)
+                },
+                _ => Err(TxScriptError::InvalidSource("OpTxGas only applies to transaction inputs".to_string()))
+            }
+        } else {
+            Err(TxScriptError::OpcodeReserved(format!("{self:?}")))
+        }
+    }
+    opcode OpTxPayload<0xb8, 1>(self, vm) {
+        if vm.flags.tx_introspection_enabled {
+            match vm.script_source {
+                ScriptSource::TxInput{tx, ..} => {
+                    vm.dstack.push_element(tx.tx().payload.clone())
+                },
+                _ => Err(TxScriptError::InvalidSource("OpTxPayload only applies to transaction inputs".to_string()))
+            }
+        } else {
+            Err(TxScriptError::OpcodeReserved(format!("{self:?}")))
+        }
+    }
     // Input related opcodes (following TransactionInput struct field order)
     opcode OpTxInputIndex<0xb9, 1>(self, vm) {
-        if vm.kip10_enabled {
+        if vm.flags.kip10_enabled {
             match vm.script_source {
                 ScriptSource::TxInput{idx, ..} => {
                     push_number(idx as i64, vm)
@@ -924,10 +1028,25 @@ opcode_list! {
     opcode OpOutpointTxId<0xba, 1>(self, vm) Err(TxScriptError::OpcodeReserved(format!("{self:?}")))
     opcode OpOutpointIndex<0xbb, 1>(self, vm) Err(TxScriptError::OpcodeReserved(format!("{self:?}")))
     opcode OpTxInputScriptSig<0xbc, 1>(self, vm) Err(TxScriptError::OpcodeReserved(format!("{self:?}")))
-    opcode OpTxInputSeq<0xbd, 1>(self, vm) Err(TxScriptError::OpcodeReserved(format!("{self:?}")))
+    opcode OpTxInputSeq<0xbd, 1>(self, vm) {
+        if vm.flags.tx_introspection_enabled {
+            match vm.script_source {
+                ScriptSource::TxInput{tx, ..} => {
+                    let [idx]: [i32; 1] = vm.dstack.pop_items()?;
+                    let input = usize::try_from(idx).ok()
+                        .and_then(|idx| tx.inputs().get(idx))
+                        .ok_or_else(|| TxScriptError::InvalidInputIndex(idx, tx.inputs().len()))?;
+                    push_number(input.sequence.try_into().map_err(|e: TryFromIntError| TxScriptError::NumberTooBig(e.to_string()))?, vm)
+                },
+                _ => Err(TxScriptError::InvalidSource("OpTxInputSeq only applies to transaction inputs".to_string()))
+            }
+        } else {
+            Err(TxScriptError::OpcodeReserved(format!("{self:?}")))
+        }
+    }
     // UTXO related opcodes (following UtxoEntry struct field order)
     opcode OpTxInputAmount<0xbe, 1>(self, vm) {
-        if vm.kip10_enabled {
+        if vm.flags.kip10_enabled {
             match vm.script_source {
                 ScriptSource::TxInput{tx, ..} => {
                     let [idx]: [i32; 1] = vm.dstack.pop_items()?;
@@ -943,7 +1062,7 @@ opcode_list! {
         }
     }
     opcode OpTxInputSpk<0xbf, 1>(self, vm) {
-        if vm.kip10_enabled {
+        if vm.flags.kip10_enabled {
             match vm.script_source {
                 ScriptSource::TxInput{tx, ..} => {
                     let [idx]: [i32; 1] = vm.dstack.pop_items()?;
@@ -959,11 +1078,42 @@ opcode_list! {
             Err(TxScriptError::InvalidOpcode(format!("{self:?}")))
         }
     }
-    opcode OpTxInputBlockDaaScore<0xc0, 1>(self, vm) Err(TxScriptError::OpcodeReserved(format!("{self:?}")))
-    opcode OpTxInputIsCoinbase<0xc1, 1>(self, vm) Err(TxScriptError::OpcodeReserved(format!("{self:?}")))
+    opcode OpTxInputBlockDaaScore<0xc0, 1>(self, vm) {
+        if vm.flags.tx_introspection_enabled {
+            match vm.script_source {
+                ScriptSource::TxInput{tx, ..} => {
+                    let [idx]: [i32; 1] = vm.dstack.pop_items()?;
+                    let utxo = usize::try_from(idx).ok()
+                        .and_then(|idx| tx.utxo(idx))
+                        .ok_or_else(|| TxScriptError::InvalidInputIndex(idx, tx.inputs().len()))?;
+                    push_number(utxo.block_daa_score.try_into().map_err(|e: TryFromIntError| TxScriptError::NumberTooBig(e.to_string()))?, vm)
+                },
+                _ => Err(TxScriptError::InvalidSource("OpTxInputBlockDaaScore only applies to transaction inputs".to_string()))
+            }
+        } else {
+            Err(TxScriptError::OpcodeReserved(format!("{self:?}")))
+        }
+    }
+    opcode OpTxInputIsCoinbase<0xc1, 1>(self, vm) {
+        if vm.flags.tx_introspection_enabled {
+            match vm.script_source {
+                ScriptSource::TxInput{tx, ..} => {
+                    let [idx]: [i32; 1] = vm.dstack.pop_items()?;
+                    let utxo = usize::try_from(idx).ok()
+                        .and_then(|idx| tx.utxo(idx))
+                        .ok_or_else(|| TxScriptError::InvalidInputIndex(idx, tx.inputs().len()))?;
+                    vm.dstack.push_item(utxo.is_coinbase)?;
+                    Ok(())
+                },
+                _ => Err(TxScriptError::InvalidSource("OpTxInputIsCoinbase only applies to transaction inputs".to_string()))
+            }
+        } else {
+            Err(TxScriptError::OpcodeReserved(format!("{self:?}")))
+        }
+    }
     // Output related opcodes (following TransactionOutput struct field order)
     opcode OpTxOutputAmount<0xc2, 1>(self, vm) {
-        if vm.kip10_enabled {
+        if vm.flags.kip10_enabled {
             match vm.script_source {
                 ScriptSource::TxInput{tx, ..} => {
                     let [idx]: [i32; 1] = vm.dstack.pop_items()?;
@@ -979,7 +1129,7 @@ opcode_list! {
         }
     }
     opcode OpTxOutputSpk<0xc3, 1>(self, vm) {
-        if vm.kip10_enabled {
+        if vm.flags.kip10_enabled {
             match vm.script_source {
                 ScriptSource::TxInput{tx, ..} => {
                     let [idx]: [i32; 1] = vm.dstack.pop_items()?;
@@ -1076,7 +1226,7 @@ mod test {
     use crate::caches::Cache;
     use crate::data_stack::Stack;
     use crate::opcodes::{OpCodeExecution, OpCodeImplementation};
-    use crate::{opcodes, pay_to_address_script, TxScriptEngine, TxScriptError, LOCK_TIME_THRESHOLD};
+    use crate::{opcodes, pay_to_address_script, EngineFlags, TxScriptEngine, TxScriptError, LOCK_TIME_THRESHOLD};
     use kaspa_addresses::{Address, Prefix, Version};
     use kaspa_consensus_core::constants::{SOMPI_PER_KASPA, TX_VERSION};
     use kaspa_consensus_core::hashing::sighash::SigHashReusedValuesUnsync;
@@ -1103,7 +1253,7 @@ mod test {
         let reused_values = SigHashReusedValuesUnsync::new();
         for TestCase { init, code, dstack } in tests {
             [false, true].into_iter().for_each(|kip10_enabled| {
-                let mut vm = TxScriptEngine::new(&reused_values, &cache, kip10_enabled);
+                let mut vm = TxScriptEngine::new(&reused_values, &cache, EngineFlags { kip10_enabled, ..Default::default() });
                 vm.dstack = init.clone();
                 code.execute(&mut vm).unwrap_or_else(|_| panic!("Opcode {} should not fail", code.value()));
                 assert_eq!(*vm.dstack, dstack, "OpCode {} Pushed wrong value", code.value());
@@ -1116,7 +1266,7 @@ mod test {
         let reused_values = SigHashReusedValuesUnsync::new();
         for ErrorTestCase { init, code, error } in tests {
             [false, true].into_iter().for_each(|kip10_enabled| {
-                let mut vm = TxScriptEngine::new(&reused_values, &cache, kip10_enabled);
+                let mut vm = TxScriptEngine::new(&reused_values, &cache, EngineFlags { kip10_enabled, ..Default::default() });
                 vm.dstack.clone_from(&init);
                 assert_eq!(
                     code.execute(&mut vm)
@@ -1152,7 +1302,7 @@ mod test {
 
         let cache = Cache::new(10_000);
         let reused_values = SigHashReusedValuesUnsync::new();
-        let mut vm = TxScriptEngine::new(&reused_values, &cache, false);
+        let mut vm = TxScriptEngine::new(&reused_values, &cache, Default::default());
 
         for pop in tests {
             match pop.execute(&mut vm) {
@@ -1186,7 +1336,7 @@ mod test {
 
         let cache = Cache::new(10_000);
         let reused_values = SigHashReusedValuesUnsync::new();
-        let mut vm = TxScriptEngine::new(&reused_values, &cache, false);
+        let mut vm = TxScriptEngine::new(&reused_values, &cache, Default::default());
 
         for pop in tests {
             match pop.execute(&mut vm) {
@@ -1259,7 +1409,7 @@ mod test {
 
         let cache = Cache::new(10_000);
         let reused_values = SigHashReusedValuesUnsync::new();
-        let mut vm = TxScriptEngine::new(&reused_values, &cache, false);
+        let mut vm = TxScriptEngine::new(&reused_values, &cache, Default::default());
 
         for pop in tests {
             match pop.execute(&mut vm) {
@@ -2854,7 +3004,8 @@ mod test {
         ] {
             let mut tx = base_tx.clone();
             tx.0.lock_time = tx_lock_time;
-            let mut vm = TxScriptEngine::from_transaction_input(&tx, &input, 0, &utxo_entry, &reused_values, &sig_cache, false);
+            let mut vm =
+                TxScriptEngine::from_transaction_input(&tx, &input, 0, &utxo_entry, &reused_values, &sig_cache, Default::default());
             vm.dstack = vec![lock_time.clone()];
             match code.execute(&mut vm) {
                 // Message is based on the should_fail values
@@ -2896,7 +3047,8 @@ mod test {
         ] {
             let mut input = base_input.clone();
             input.sequence = tx_sequence;
-            let mut vm = TxScriptEngine::from_transaction_input(&tx, &input, 0, &utxo_entry, &reused_values, &sig_cache, false);
+            let mut vm =
+                TxScriptEngine::from_transaction_input(&tx, &input, 0, &utxo_entry, &reused_values, &sig_cache, Default::default());
             vm.dstack = vec![sequence.clone()];
             match code.execute(&mut vm) {
                 // Message is based on the should_fail values
@@ -3089,7 +3241,7 @@ mod test {
                     tx.utxo(current_idx).unwrap(),
                     &reused_values,
                     &sig_cache,
-                    group.kip10_enabled,
+                    EngineFlags { kip10_enabled: group.kip10_enabled, ..Default::default() },
                 );
 
                 // Check input index opcode first
@@ -3357,7 +3509,7 @@ mod test {
                         tx.utxo(0).unwrap(),
                         &reused_values,
                         &sig_cache,
-                        kip10_enabled,
+                        EngineFlags { kip10_enabled, ..Default::default() },
                     );
 
                     let op_input_count = opcodes::OpTxInputCount::empty().expect("Should accept empty");
@@ -3437,7 +3589,7 @@ mod test {
                     tx.utxo(0).unwrap(),
                     &reused_values,
                     &sig_cache,
-                    true,
+                    EngineFlags { kip10_enabled: true, ..Default::default() },
                 );
 
                 assert_eq!(vm.execute(), Ok(()));
@@ -3461,7 +3613,7 @@ mod test {
                     tx.utxo(0).unwrap(),
                     &reused_values,
                     &sig_cache,
-                    true,
+                    EngineFlags { kip10_enabled: true, ..Default::default() },
                 );
 
                 assert_eq!(vm.execute(), Err(TxScriptError::EvalFalse));
@@ -3501,7 +3653,7 @@ mod test {
                     tx.utxo(0).unwrap(),
                     &reused_values,
                     &sig_cache,
-                    true,
+                    EngineFlags { kip10_enabled: true, ..Default::default() },
                 );
 
                 assert_eq!(vm.execute(), Ok(()));
@@ -3527,7 +3679,7 @@ mod test {
                     tx.utxo(0).unwrap(),
                     &reused_values,
                     &sig_cache,
-                    true,
+                    EngineFlags { kip10_enabled: true, ..Default::default() },
                 );
 
                 assert_eq!(vm.execute(), Err(TxScriptError::EvalFalse));
@@ -3549,8 +3701,15 @@ mod test {
             tx.tx.inputs[0].signature_script = ScriptBuilder::new().add_data(&redeem_script).unwrap().drain();
 
             let tx = tx.as_verifiable();
-            let mut vm =
-                TxScriptEngine::from_transaction_input(&tx, &tx.inputs()[0], 0, tx.utxo(0).unwrap(), &reused_values, &sig_cache, true);
+            let mut vm = TxScriptEngine::from_transaction_input(
+                &tx,
+                &tx.inputs()[0],
+                0,
+                tx.utxo(0).unwrap(),
+                &reused_values,
+                &sig_cache,
+                EngineFlags { kip10_enabled: true, ..Default::default() },
+            );
 
             // OpInputSpk should push input's SPK onto stack, making it non-empty
             assert_eq!(vm.execute(), Ok(()));
@@ -3573,8 +3732,15 @@ mod test {
             tx.tx.inputs[0].signature_script = ScriptBuilder::new().add_data(&redeem_script).unwrap().drain();
 
             let tx = tx.as_verifiable();
-            let mut vm =
-                TxScriptEngine::from_transaction_input(&tx, &tx.inputs()[0], 0, tx.utxo(0).unwrap(), &reused_values, &sig_cache, true);
+            let mut vm = TxScriptEngine::from_transaction_input(
+                &tx,
+                &tx.inputs()[0],
+                0,
+                tx.utxo(0).unwrap(),
+                &reused_values,
+                &sig_cache,
+                EngineFlags { kip10_enabled: true, ..Default::default() },
+            );
 
             // Should succeed because the SPKs are different
             assert_eq!(vm.execute(), Ok(()));
@@ -3598,8 +3764,15 @@ mod test {
             tx.tx.inputs[0].signature_script = ScriptBuilder::new().add_data(&redeem_script).unwrap().drain();
 
             let tx = tx.as_verifiable();
-            let mut vm =
-                TxScriptEngine::from_transaction_input(&tx, &tx.inputs()[0], 0, tx.utxo(0).unwrap(), &reused_values, &sig_cache, true);
+            let mut vm = TxScriptEngine::from_transaction_input(
+                &tx,
+                &tx.inputs()[0],
+                0,
+                tx.utxo(0).unwrap(),
+                &reused_values,
+                &sig_cache,
+                EngineFlags { kip10_enabled: true, ..Default::default() },
+            );
 
             // Should succeed because both SPKs are identical
             assert_eq!(vm.execute(), Ok(()));
@@ -3643,7 +3816,7 @@ mod test {
                     tx.utxo(0).unwrap(),
                     &reused_values,
                     &sig_cache,
-                    true,
+                    EngineFlags { kip10_enabled: true, ..Default::default() },
                 );
 
                 assert_eq!(vm.execute(), Ok(()));
@@ -3669,7 +3842,7 @@ mod test {
                     tx.utxo(0).unwrap(),
                     &reused_values,
                     &sig_cache,
-                    true,
+                    EngineFlags { kip10_enabled: true, ..Default::default() },
                 );
 
                 assert_eq!(vm.execute(), Err(TxScriptError::EvalFalse));
@@ -3702,7 +3875,7 @@ mod test {
                     tx.utxo(0).unwrap(),
                     &reused_values,
                     &sig_cache,
-                    true,
+                    EngineFlags { kip10_enabled: true, ..Default::default() },
                 );
 
                 assert_eq!(vm.execute(), Ok(()));
@@ -3726,7 +3899,7 @@ mod test {
                     tx.utxo(1).unwrap(),
                     &reused_values,
                     &sig_cache,
-                    true,
+                    EngineFlags { kip10_enabled: true, ..Default::default() },
                 );
 
                 // Should fail because script expects index 0 but we're at index 1
@@ -3772,7 +3945,7 @@ mod test {
                     tx.utxo(0).unwrap(),
                     &reused_values,
                     &sig_cache,
-                    true,
+                    EngineFlags { kip10_enabled: true, ..Default::default() },
                 );
 
                 assert_eq!(vm.execute(), Ok(()));
@@ -3791,7 +3964,7 @@ mod test {
                     tx.utxo(1).unwrap(),
                     &reused_values,
                     &sig_cache,
-                    true,
+                    EngineFlags { kip10_enabled: true, ..Default::default() },
                 );
 
                 assert_eq!(vm.execute(), Ok(()));
@@ -3814,7 +3987,7 @@ mod test {
                     tx.utxo(0).unwrap(),
                     &reused_values,
                     &sig_cache,
-                    true,
+                    EngineFlags { kip10_enabled: true, ..Default::default() },
                 );
 
                 assert_eq!(vm.execute(), Err(TxScriptError::EvalFalse));
@@ -3836,11 +4009,296 @@ mod test {
                     tx.utxo(1).unwrap(),
                     &reused_values,
                     &sig_cache,
-                    true,
+                    EngineFlags { kip10_enabled: true, ..Default::default() },
                 );
 
                 assert_eq!(vm.execute(), Err(TxScriptError::EvalFalse));
             }
         }
     }
+
+    mod tx_introspection {
+        use super::*;
+        use crate::{
+            data_stack::DataStack,
+            opcodes::{codes::*, deserialize_next_opcode},
+            pay_to_script_hash_script,
+            script_builder::{ScriptBuilder, ScriptBuilderResult},
+            MAX_SCRIPT_ELEMENT_SIZE, MAX_TX_IN_SEQUENCE_NUM,
+        };
+        use kaspa_consensus_core::{subnets::SubnetworkId, tx::MutableTransaction};
+
+        const ENABLED: EngineFlags =
+            EngineFlags { kip10_enabled: true, tx_introspection_enabled: true, byte_string_opcodes_enabled: false };
+        const DISABLED: EngineFlags =
+            EngineFlags { kip10_enabled: true, tx_introspection_enabled: false, byte_string_opcodes_enabled: false };
+
+        const LOCK_TIME: u64 = 1_000;
+        const GAS: u64 = 777;
+        const SUBNETWORK_ID: SubnetworkId = SubnetworkId::from_byte(5);
+
+        const INTROSPECTION_OPCODES: [u8; 8] =
+            [OpTxVersion, OpTxLockTime, OpTxSubnetId, OpTxGas, OpTxPayload, OpTxInputSeq, OpTxInputBlockDaaScore, OpTxInputIsCoinbase];
+
+        /// Builds a transaction with two inputs: a non-coinbase UTXO created at DAA score 100 and spent with
+        /// sequence 10, and a coinbase UTXO created at DAA score 200 and spent with the max sequence
+        fn tx_mock(payload: Vec<u8>) -> (Transaction, Vec<UtxoEntry>) {
+            let spk = pay_to_address_script(&Address::new(Prefix::Testnet, Version::PubKey, &[1u8; 32]));
+            let prev_out = TransactionOutpoint::new(kaspa_hashes::Hash::from_u64_word(1), 1);
+            let inputs = vec![
+                TransactionInput::new(prev_out, vec![], 10, 0),
+                TransactionInput::new(prev_out, vec![], MAX_TX_IN_SEQUENCE_NUM, 0),
+            ];
+            let entries = vec![UtxoEntry::new(1000, spk.clone(), 100, false), UtxoEntry::new(2000, spk.clone(), 200, true)];
+            let outputs = vec![TransactionOutput::new(2500, spk)];
+            let tx = Transaction::new(TX_VERSION, inputs, outputs, LOCK_TIME, SUBNETWORK_ID, GAS, payload);
+            (tx, entries)
+        }
+
+        fn number(value: i64) -> Vec<u8> {
+            let mut stack: Stack = vec![];
+            stack.push_item(value).unwrap();
+            stack.pop().unwrap()
+        }
+
+        /// Executes `opcode` over an engine validating the first input, starting with `init` on the stack
+        fn execute(flags: EngineFlags, payload: Vec<u8>, init: Stack, opcode: u8) -> Result<Stack, TxScriptError> {
+            let (tx, entries) = tx_mock(payload);
+            let tx = PopulatedTransaction::new(&tx, entries);
+            let cache = Cache::new(10_000);
+            let reused_values = SigHashReusedValuesUnsync::new();
+            let mut vm =
+                TxScriptEngine::from_transaction_input(&tx, &tx.inputs()[0], 0, tx.utxo(0).unwrap(), &reused_values, &cache, flags);
+            vm.dstack = init;
+            let code = deserialize_next_opcode(&mut [opcode].iter()).unwrap()?;
+            code.execute(&mut vm)?;
+            Ok(vm.dstack)
+        }
+
+        #[test]
+        fn test_reserved_before_activation() {
+            for opcode in INTROSPECTION_OPCODES {
+                match execute(DISABLED, vec![], vec![number(0)], opcode) {
+                    Err(TxScriptError::OpcodeReserved(_)) => {}
+                    result => panic!("Opcode {opcode:#x} should be reserved before activation, got {result:?}"),
+                }
+            }
+        }
+
+        #[test]
+        fn test_transaction_level_opcodes() {
+            let payload = vec![0xde, 0xad, 0xbe, 0xef];
+            let tests = [
+                (OpTxVersion, number(TX_VERSION as i64)),
+                (OpTxLockTime, number(LOCK_TIME as i64)),
+                (OpTxSubnetId, AsRef::<[u8]>::as_ref(&SUBNETWORK_ID).to_vec()),
+                (OpTxGas, number(GAS as i64)),
+                (OpTxPayload, payload.clone()),
+            ];
+
+            for (opcode, expected) in tests {
+                let dstack =
+                    execute(ENABLED, payload.clone(), vec![], opcode).unwrap_or_else(|err| panic!("{opcode:#x} failed: {err}"));
+                assert_eq!(dstack, vec![expected], "Opcode {opcode:#x} pushed a wrong value");
+            }
+        }
+
+        #[test]
+        fn test_input_level_opcodes() {
+            let tests = [
+                (OpTxInputSeq, 0, Ok(number(10))),
+                (OpTxInputSeq, 1, Err(TxScriptError::NumberTooBig(i64::try_from(MAX_TX_IN_SEQUENCE_NUM).unwrap_err().to_string()))),
+                (OpTxInputBlockDaaScore, 0, Ok(number(100))),
+                (OpTxInputBlockDaaScore, 1, Ok(number(200))),
+                (OpTxInputIsCoinbase, 0, Ok(vec![])),
+                (OpTxInputIsCoinbase, 1, Ok(vec![1])),
+            ];
+            for (opcode, index, expected) in tests {
+                let result = execute(ENABLED, vec![], vec![number(index)], opcode);
+                assert_eq!(result, expected.map(|item| vec![item]), "Opcode {opcode:#x} with index {index} returned a wrong result");
+            }
+
+            // Out of range indexes
+            for index in [2, -1] {
+                for opcode in [OpTxInputSeq, OpTxInputBlockDaaScore, OpTxInputIsCoinbase] {
+                    let result = execute(ENABLED, vec![], vec![number(index)], opcode);
+                    assert_eq!(
+                        result,
+                        Err(TxScriptError::InvalidInputIndex(index as i32, 2)),
+                        "Opcode {opcode:#x} with index {index}"
+                    );
+                }
+            }
+
+            // Missing index
+            let result = execute(ENABLED, vec![], vec![], OpTxInputBlockDaaScore);
+            assert_eq!(result, Err(TxScriptError::InvalidStackOperation(1, 0)));
+        }
+
+        #[test]
+        fn test_payload_too_big() {
+            let payload = vec![0u8; MAX_SCRIPT_ELEMENT_SIZE + 1];
+            let result = execute(ENABLED, payload, vec![], OpTxPayload);
+            assert_eq!(result, Err(TxScriptError::ElementTooBig(MAX_SCRIPT_ELEMENT_SIZE + 1, MAX_SCRIPT_ELEMENT_SIZE)));
+        }
+
+        #[test]
+        fn test_standalone_script_source() {
+            let cache = Cache::new(10_000);
+            let reused_values = SigHashReusedValuesUnsync::new();
+            let mut vm = TxScriptEngine::<PopulatedTransaction, _>::new(&reused_values, &cache, ENABLED);
+            for opcode in INTROSPECTION_OPCODES {
+                vm.dstack = vec![number(0)];
+                let code = deserialize_next_opcode(&mut [opcode].iter()).unwrap().unwrap();
+                assert!(
+                    matches!(code.execute(&mut vm), Err(TxScriptError::InvalidSource(_))),
+                    "{opcode:#x} should require a tx input"
+                );
+            }
+        }
+
+        /// A vault which can only be spent once the spent UTXO is at least `min_age` DAA scores old. Consensus
+        /// guarantees the transaction is not accepted before its lock time, so the script checks that the lock
+        /// time is far enough from the UTXO creation
+        fn utxo_age_script(min_age: i64) -> ScriptBuilderResult<Vec<u8>> {
+            Ok(ScriptBuilder::new()
+                .add_op(OpTxLockTime)?
+                .add_op(OpTxInputIndex)?
+                .add_op(OpTxInputBlockDaaScore)?
+                .add_op(OpSub)?
+                .add_i64(min_age)?
+                .add_op(OpGreaterThanOrEqual)?
+                .drain())
+        }
+
+        #[test]
+        fn test_utxo_age_vault() {
+            let cache = Cache::new(10_000);
+            let reused_values = SigHashReusedValuesUnsync::new();
+
+            // The first UTXO is created at DAA score 100 and the lock time is 1000
+            for (min_age, flags, expect_success) in [(900, ENABLED, true), (901, ENABLED, false), (900, DISABLED, false)] {
+                let script = utxo_age_script(min_age).unwrap();
+                let (tx, mut entries) = tx_mock(vec![]);
+                entries[0].script_public_key = pay_to_script_hash_script(&script);
+                let mut tx = MutableTransaction::with_entries(tx, entries);
+                tx.tx.inputs[0].signature_script = ScriptBuilder::new().add_data(&script).unwrap().drain();
+
+                let tx = tx.as_verifiable();
+                let mut vm = TxScriptEngine::from_transaction_input(
+                    &tx,
+                    &tx.inputs()[0],
+                    0,
+                    tx.utxo(0).unwrap(),
+                    &reused_values,
+                    &cache,
+                    flags,
+                );
+                assert_eq!(vm.execute().is_ok(), expect_success, "min age {min_age} with {flags:?}");
+            }
+        }
+    }
+
+    mod byte_string {
+        use super::*;
+        use crate::{
+            data_stack::DataStack,
+            opcodes::{codes::*, deserialize_next_opcode},
+            script_builder::ScriptBuilder,
+            MAX_SCRIPT_ELEMENT_SIZE,
+        };
+
+        const ENABLED: EngineFlags =
+            EngineFlags { kip10_enabled: true, tx_introspection_enabled: true, byte_string_opcodes_enabled: true };
+
+        fn number(value: i64) -> Vec<u8> {
+            let mut stack: Stack = vec![];
+            stack.push_item(value).unwrap();
+            stack.pop().unwrap()
+        }
+
+        /// Executes `opcode` over a stand-alone engine, starting with `init` on the stack
+        fn execute(flags: EngineFlags, init: Stack, opcode: u8) -> Result<Stack, TxScriptError> {
+            let cache = Cache::new(10_000);
+            let reused_values = SigHashReusedValuesUnsync::new();
+            let mut vm: TxScriptEngine<PopulatedTransaction, _> = TxScriptEngine::new(&reused_values, &cache, flags);
+            vm.dstack = init;
+            let code = deserialize_next_opcode(&mut [opcode].iter()).unwrap()?;
+            code.execute(&mut vm)?;
+            Ok(vm.dstack)
+        }
+
+        #[test]
+        fn test_disabled_before_activation() {
+            let script = ScriptBuilder::new()
+                .add_data(b"ab")
+                .unwrap()
+                .add_data(b"cd")
+                .unwrap()
+                .add_op(OpCat)
+                .unwrap()
+                .add_data(b"abcd")
+                .unwrap()
+                .add_op(OpEqual)
+                .unwrap()
+                .drain();
+            let cache = Cache::new(10_000);
+            let reused_values = SigHashReusedValuesUnsync::new();
+
+            let mut vm: TxScriptEngine<PopulatedTransaction, _> =
+                TxScriptEngine::from_script(&script, &reused_values, &cache, Default::default());
+            assert!(matches!(vm.execute(), Err(TxScriptError::OpcodeDisabled(_))));
+
+            let mut vm: TxScriptEngine<PopulatedTransaction, _> =
+                TxScriptEngine::from_script(&script, &reused_values, &cache, ENABLED);
+            assert_eq!(vm.execute(), Ok(()));
+        }
+
+        #[test]
+        fn test_byte_string_opcodes() {
+            let tests = [
+                (OpCat, vec![b"ab".to_vec(), b"cd".to_vec()], Ok(b"abcd".to_vec())),
+                (OpCat, vec![vec![], b"cd".to_vec()], Ok(b"cd".to_vec())),
+                (OpCat, vec![vec![], vec![]], Ok(vec![])),
+                (OpSubStr, vec![b"abcdef".to_vec(), number(1), number(3)], Ok(b"bcd".to_vec())),
+                (OpSubStr, vec![b"abcdef".to_vec(), number(6), number(0)], Ok(vec![])),
+                (OpSubStr, vec![b"abcdef".to_vec(), number(4), number(3)], Err(TxScriptError::InvalidByteRange(4, 7, 6))),
+                (OpSubStr, vec![b"abcdef".to_vec(), number(-1), number(3)], Err(TxScriptError::InvalidByteRange(-1, 2, 6))),
+                (OpSubStr, vec![b"abcdef".to_vec(), number(2), number(-1)], Err(TxScriptError::InvalidByteRange(2, 1, 6))),
+                (OpLeft, vec![b"abcdef".to_vec(), number(2)], Ok(b"ab".to_vec())),
+                (OpLeft, vec![b"abcdef".to_vec(), number(6)], Ok(b"abcdef".to_vec())),
+                (OpLeft, vec![b"abcdef".to_vec(), number(7)], Err(TxScriptError::InvalidByteRange(0, 7, 6))),
+                (OpLeft, vec![b"abcdef".to_vec(), number(-1)], Err(TxScriptError::InvalidByteRange(0, -1, 6))),
+                (OpRight, vec![b"abcdef".to_vec(), number(2)], Ok(b"ef".to_vec())),
+                (OpRight, vec![b"abcdef".to_vec(), number(0)], Ok(vec![])),
+                (OpRight, vec![b"abcdef".to_vec(), number(7)], Err(TxScriptError::InvalidByteRange(-1, 6, 6))),
+            ];
+
+            for (opcode, init, expected) in tests {
+                let result = execute(ENABLED, init.clone(), opcode);
+                assert_eq!(result, expected.map(|element| vec![element]), "Opcode {opcode:#x} failed on {init:?}");
+            }
+        }
+
+        #[test]
+        fn test_element_size_limit() {
+            let half = vec![0u8; MAX_SCRIPT_ELEMENT_SIZE / 2];
+            assert_eq!(execute(ENABLED, vec![half.clone(), half.clone()], OpCat), Ok(vec![vec![0u8; MAX_SCRIPT_ELEMENT_SIZE]]));
+            assert_eq!(
+                execute(ENABLED, vec![half.clone(), [half, vec![0]].concat()], OpCat),
+                Err(TxScriptError::ElementTooBig(MAX_SCRIPT_ELEMENT_SIZE + 1, MAX_SCRIPT_ELEMENT_SIZE))
+            );
+        }
+
+        #[test]
+        fn test_insufficient_stack() {
+            for (opcode, required) in [(OpCat, 2), (OpSubStr, 2), (OpLeft, 1), (OpRight, 1)] {
+                assert_eq!(
+                    execute(ENABLED, vec![], opcode),
+                    Err(TxScriptError::InvalidStackOperation(required, 0)),
+                    "Opcode {opcode:#x} should require {required} items"
+                );
+            }
+        }
+    }
 }
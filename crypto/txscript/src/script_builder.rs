@@ -253,6 +253,23 @@ impl ScriptBuilder {
         self.add_u64(sequence)
     }
 
+    /// Pushes an input index followed by OpTxInputSeq, leaving the sequence of that input on the stack.
+    pub fn add_input_sequence(&mut self, input_index: i64) -> ScriptBuilderResult<&mut Self> {
+        self.add_i64(input_index)?.add_op(OpTxInputSeq)
+    }
+
+    /// Pushes an input index followed by OpTxInputBlockDaaScore, leaving the DAA score of the block
+    /// which created the UTXO spent by that input on the stack.
+    pub fn add_input_block_daa_score(&mut self, input_index: i64) -> ScriptBuilderResult<&mut Self> {
+        self.add_i64(input_index)?.add_op(OpTxInputBlockDaaScore)
+    }
+
+    /// Pushes an input index followed by OpTxInputIsCoinbase, leaving whether the UTXO spent by that
+    /// input is a coinbase output on the stack.
+    pub fn add_input_is_coinbase(&mut self, input_index: i64) -> ScriptBuilderResult<&mut Self> {
+        self.add_i64(input_index)?.add_op(OpTxInputIsCoinbase)
+    }
+
     /// Gets a u64 lock time or sequence, converts it to byte array in little-endian, and then used the add_data function.
     fn add_u64(&mut self, val: u64) -> ScriptBuilderResult<&mut Self> {
         let buffer: [u8; 8] = val.to_le_bytes();
@@ -562,6 +579,16 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_input_introspection() {
+        let result = ScriptBuilder::new().add_input_sequence(0).unwrap().drain();
+        assert_eq!(result, vec![Op0, OpTxInputSeq]);
+        let result = ScriptBuilder::new().add_input_block_daa_score(3).unwrap().drain();
+        assert_eq!(result, vec![Op3, OpTxInputBlockDaaScore]);
+        let result = ScriptBuilder::new().add_input_is_coinbase(17).unwrap().drain();
+        assert_eq!(result, vec![OpData1, 17, OpTxInputIsCoinbase]);
+    }
+
     /// Ensures that all of the functions that can be used to add data to a script don't allow
     /// the script to exceed the max allowed size.
     #[test]
@@ -1,4 +1,8 @@
-use crate::{opcodes, MAX_SCRIPT_PUBLIC_KEY_VERSION};
+use crate::{
+    extract_redeem_script, opcodes,
+    standard::{extract_hash_time_lock, extract_time_lock_vault},
+    MAX_SCRIPT_PUBLIC_KEY_VERSION,
+};
 use borsh::{BorshDeserialize, BorshSerialize};
 use kaspa_addresses::Version;
 use kaspa_consensus_core::tx::{ScriptPublicKey, ScriptPublicKeyVersion};
@@ -28,12 +32,18 @@ pub enum ScriptClass {
     PubKeyECDSA,
     /// Pay to script hash
     ScriptHash,
+    /// Hash time-locked contract
+    HashTimeLock,
+    /// Pay to pubkey after a lock time
+    TimeLockVault,
 }
 
 const NON_STANDARD: &str = "nonstandard";
 const PUB_KEY: &str = "pubkey";
 const PUB_KEY_ECDSA: &str = "pubkeyecdsa";
 const SCRIPT_HASH: &str = "scripthash";
+const HASH_TIME_LOCK: &str = "hashtimelock";
+const TIME_LOCK_VAULT: &str = "timelockvault";
 
 impl ScriptClass {
     pub fn from_script(script_public_key: &ScriptPublicKey) -> Self {
@@ -53,6 +63,29 @@ impl ScriptClass {
         }
     }
 
+    /// Returns the class of the redeem script revealed by the signature script spending a
+    /// pay-to-script-hash output, which is either one of the time-locked templates or
+    /// [`ScriptClass::NonStandard`].
+    ///
+    /// The time-locked templates are only recognized behind a script hash, bare outputs
+    /// paying to them are classified as [`ScriptClass::NonStandard`] by [`Self::from_script`].
+    pub fn from_signature_script(signature_script: &[u8]) -> Self {
+        extract_redeem_script(signature_script)
+            .map_or(ScriptClass::NonStandard, |redeem_script| Self::from_redeem_script(&redeem_script))
+    }
+
+    /// Returns the class of a script committed to by a pay-to-script-hash output, which is
+    /// either one of the time-locked templates or [`ScriptClass::NonStandard`].
+    pub fn from_redeem_script(redeem_script: &[u8]) -> Self {
+        if Self::is_hash_time_lock(redeem_script) {
+            ScriptClass::HashTimeLock
+        } else if Self::is_time_lock_vault(redeem_script) {
+            ScriptClass::TimeLockVault
+        } else {
+            ScriptClass::NonStandard
+        }
+    }
+
     // Returns true if the script passed is a pay-to-pubkey
     // transaction, false otherwise.
     #[inline(always)]
@@ -81,12 +114,28 @@ impl ScriptClass {
         (script_public_key[34] == opcodes::codes::OpEqual)
     }
 
+    /// Returns true if the script is a hash time-locked contract
+    /// as built by [`crate::standard::htlc_redeem_script`], false otherwise.
+    #[inline(always)]
+    pub fn is_hash_time_lock(script: &[u8]) -> bool {
+        extract_hash_time_lock(script).is_some()
+    }
+
+    /// Returns true if the script is a lock time vault
+    /// as built by [`crate::standard::time_lock_vault_redeem_script`], false otherwise.
+    #[inline(always)]
+    pub fn is_time_lock_vault(script: &[u8]) -> bool {
+        extract_time_lock_vault(script).is_some()
+    }
+
     fn as_str(&self) -> &'static str {
         match self {
             ScriptClass::NonStandard => NON_STANDARD,
             ScriptClass::PubKey => PUB_KEY,
             ScriptClass::PubKeyECDSA => PUB_KEY_ECDSA,
             ScriptClass::ScriptHash => SCRIPT_HASH,
+            ScriptClass::HashTimeLock => HASH_TIME_LOCK,
+            ScriptClass::TimeLockVault => TIME_LOCK_VAULT,
         }
     }
 
@@ -96,6 +145,8 @@ impl ScriptClass {
             ScriptClass::PubKey => MAX_SCRIPT_PUBLIC_KEY_VERSION,
             ScriptClass::PubKeyECDSA => MAX_SCRIPT_PUBLIC_KEY_VERSION,
             ScriptClass::ScriptHash => MAX_SCRIPT_PUBLIC_KEY_VERSION,
+            ScriptClass::HashTimeLock => MAX_SCRIPT_PUBLIC_KEY_VERSION,
+            ScriptClass::TimeLockVault => MAX_SCRIPT_PUBLIC_KEY_VERSION,
         }
     }
 }
@@ -115,6 +166,8 @@ impl FromStr for ScriptClass {
             PUB_KEY => Ok(ScriptClass::PubKey),
             PUB_KEY_ECDSA => Ok(ScriptClass::PubKeyECDSA),
             SCRIPT_HASH => Ok(ScriptClass::ScriptHash),
+            HASH_TIME_LOCK => Ok(ScriptClass::HashTimeLock),
+            TIME_LOCK_VAULT => Ok(ScriptClass::TimeLockVault),
             _ => Err(Error::InvalidScriptClass(script_class.to_string())),
         }
     }
@@ -140,6 +193,7 @@ impl From<Version> for ScriptClass {
 
 #[cfg(test)]
 mod tests {
+    use crate::opcodes::codes;
     use kaspa_consensus_core::tx::ScriptVec;
 
     use super::*;
@@ -173,6 +227,18 @@ mod tests {
                 version: 0,
                 class: ScriptClass::ScriptHash,
             },
+            Test {
+                name: "non standard script (bare hash time lock)",
+                script: crate::standard::htlc_redeem_script(&[1; 32], &[2; 32], &[3; 32], 1_000_000).unwrap(),
+                version: 0,
+                class: ScriptClass::NonStandard,
+            },
+            Test {
+                name: "non standard script (bare time lock vault)",
+                script: crate::standard::time_lock_vault_redeem_script(&[4; 32], 1_000_000).unwrap(),
+                version: 0,
+                class: ScriptClass::NonStandard,
+            },
             Test {
                 name: "non standard script (unexpected version)",
                 script: hex::decode("204a23f5eef4b2dead811c7efb4f1afbd8df845e804b6c36a4001fc096e13f8151ac").unwrap(),
@@ -199,4 +265,40 @@ mod tests {
             assert_eq!(test.class, ScriptClass::from_script(&script_public_key), "{} wrong script class", test.name);
         }
     }
+
+    #[test]
+    fn test_script_class_from_signature_script() {
+        let htlc = crate::standard::htlc_redeem_script(&[1; 32], &[2; 32], &[3; 32], 1_000_000).unwrap();
+        let vault = crate::standard::time_lock_vault_redeem_script(&[4; 32], 1_000_000).unwrap();
+        let signature = [5u8; 65];
+
+        let tests = [
+            (
+                "hash time lock claim",
+                crate::standard::htlc_claim_signature_script(&htlc, &signature, &[6; 32]).unwrap(),
+                ScriptClass::HashTimeLock,
+            ),
+            (
+                "hash time lock refund",
+                crate::standard::htlc_refund_signature_script(&htlc, &signature).unwrap(),
+                ScriptClass::HashTimeLock,
+            ),
+            (
+                "time lock vault",
+                crate::standard::time_lock_vault_signature_script(&vault, &signature).unwrap(),
+                ScriptClass::TimeLockVault,
+            ),
+            (
+                "other redeem script",
+                crate::standard::pay_to_script_hash_signature_script(vec![codes::OpTrue], signature.to_vec()).unwrap(),
+                ScriptClass::NonStandard,
+            ),
+            ("empty signature script", vec![], ScriptClass::NonStandard),
+            ("not push only", [htlc.as_slice(), &[codes::OpTrue, codes::OpDrop]].concat(), ScriptClass::NonStandard),
+        ];
+
+        for (name, signature_script, class) in tests {
+            assert_eq!(class, ScriptClass::from_signature_script(&signature_script), "{name} wrong script class");
+        }
+    }
 }
@@ -11,8 +11,13 @@ use smallvec::SmallVec;
 use std::iter::once;
 
 mod multisig;
+mod timelock;
 
 pub use multisig::{multisig_redeem_script, multisig_redeem_script_ecdsa, Error as MultisigCreateError};
+pub use timelock::{
+    extract_hash_time_lock, extract_time_lock_vault, htlc_claim_signature_script, htlc_redeem_script, htlc_refund_signature_script,
+    htlc_secret_hash, time_lock_vault_redeem_script, time_lock_vault_signature_script, HashTimeLock, TimeLockVault, HTLC_SECRET_SIZE,
+};
 
 /// Creates a new script to pay a transaction output to a 32-byte pubkey.
 fn pay_to_pub_key(address_payload: &[u8]) -> ScriptVec {
@@ -76,7 +81,7 @@ pub fn extract_script_pub_key_address(script_public_key: &ScriptPublicKey, prefi
     }
     let script = script_public_key.script();
     match class {
-        ScriptClass::NonStandard => Err(TxScriptError::PubKeyFormat),
+        ScriptClass::NonStandard | ScriptClass::HashTimeLock | ScriptClass::TimeLockVault => Err(TxScriptError::PubKeyFormat),
         ScriptClass::PubKey => Ok(Address::new(prefix, Version::PubKey, &script[1..33])),
         ScriptClass::PubKeyECDSA => Ok(Address::new(prefix, Version::PubKeyECDSA, &script[1..34])),
         ScriptClass::ScriptHash => Ok(Address::new(prefix, Version::ScriptHash, &script[2..34])),
@@ -0,0 +1,315 @@
+use crate::opcodes::codes::{
+    Op16, OpBlake2b, OpCheckLockTimeVerify, OpCheckSig, OpData1, OpData32, OpElse, OpEndIf, OpEqualVerify, OpFalse, OpIf, OpSize,
+    OpTrue,
+};
+use crate::script_builder::{ScriptBuilder, ScriptBuilderResult};
+use blake2b_simd::Params;
+
+/// Size of the secret revealed when claiming a hash time-locked contract
+pub const HTLC_SECRET_SIZE: usize = 32;
+
+// The fixed parts of a hash time-locked contract redeem script, surrounding the variable-length lock time push:
+//
+// OpIf
+//     OpSize <32> OpEqualVerify OpBlake2b <secret hash> OpEqualVerify <receiver pubkey>
+// OpElse
+//     <lock time> OpCheckLockTimeVerify <refunder pubkey>
+// OpEndIf
+// OpCheckSig
+const HTLC_PREFIX_LEN: usize = 74; // OpIf, OpSize, 2 bytes of size push, OpEqualVerify, OpBlake2b, 33 bytes of hash push, OpEqualVerify, 33 bytes of receiver push, OpElse
+const HTLC_SUFFIX_LEN: usize = 36; // OpCheckLockTimeVerify, 33 bytes of refunder push, OpEndIf, OpCheckSig
+
+// The fixed part of a lock time vault redeem script following the lock time push:
+//
+// <lock time> OpCheckLockTimeVerify <owner pubkey> OpCheckSig
+const VAULT_SUFFIX_LEN: usize = 35; // OpCheckLockTimeVerify, 33 bytes of owner push, OpCheckSig
+
+/// The parameters of a hash time-locked contract (HTLC).
+///
+/// The funds can be claimed at any time by `receiver`, given the preimage of `secret_hash`,
+/// or refunded to `refunder` once `lock_time` (a DAA score or a timestamp) has been reached.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HashTimeLock {
+    pub secret_hash: [u8; 32],
+    pub receiver: [u8; 32],
+    pub refunder: [u8; 32],
+    pub lock_time: u64,
+}
+
+/// The parameters of a lock time vault: funds spendable by `owner` only once `lock_time`
+/// (a DAA score or a timestamp) has been reached.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TimeLockVault {
+    pub owner: [u8; 32],
+    pub lock_time: u64,
+}
+
+/// Returns the hash a hash time-locked contract commits to for the given secret, as computed by `OpBlake2b`.
+pub fn htlc_secret_hash(secret: &[u8; HTLC_SECRET_SIZE]) -> [u8; 32] {
+    let hash = Params::new().hash_length(32).to_state().update(secret).finalize();
+    hash.as_bytes().try_into().expect("hash length is 32")
+}
+
+/// Creates the redeem script of a hash time-locked contract, to be paid to with
+/// [`pay_to_script_hash_script`](super::pay_to_script_hash_script).
+pub fn htlc_redeem_script(
+    secret_hash: &[u8; 32],
+    receiver: &[u8; 32],
+    refunder: &[u8; 32],
+    lock_time: u64,
+) -> ScriptBuilderResult<Vec<u8>> {
+    let mut builder = ScriptBuilder::new();
+    builder
+        .add_op(OpIf)?
+        .add_op(OpSize)?
+        .add_i64(HTLC_SECRET_SIZE as i64)?
+        .add_op(OpEqualVerify)?
+        .add_op(OpBlake2b)?
+        .add_data(secret_hash)?
+        .add_op(OpEqualVerify)?
+        .add_data(receiver)?
+        .add_op(OpElse)?
+        .add_lock_time(lock_time)?
+        .add_op(OpCheckLockTimeVerify)?
+        .add_data(refunder)?
+        .add_op(OpEndIf)?
+        .add_op(OpCheckSig)?;
+    Ok(builder.drain())
+}
+
+/// Generates a signature script claiming a P2SH hash time-locked contract by revealing its secret.
+///
+/// `signature` is the receiver's signature, including its sighash type byte.
+pub fn htlc_claim_signature_script(
+    redeem_script: &[u8],
+    signature: &[u8],
+    secret: &[u8; HTLC_SECRET_SIZE],
+) -> ScriptBuilderResult<Vec<u8>> {
+    let mut builder = ScriptBuilder::new();
+    builder.add_data(signature)?.add_data(secret)?.add_op(OpTrue)?.add_data(redeem_script)?;
+    Ok(builder.drain())
+}
+
+/// Generates a signature script refunding a P2SH hash time-locked contract.
+///
+/// `signature` is the refunder's signature, including its sighash type byte. The spending
+/// transaction must have a lock time of at least the contract lock time, and the input a
+/// sequence lower than [`MAX_TX_IN_SEQUENCE_NUM`](crate::MAX_TX_IN_SEQUENCE_NUM).
+pub fn htlc_refund_signature_script(redeem_script: &[u8], signature: &[u8]) -> ScriptBuilderResult<Vec<u8>> {
+    let mut builder = ScriptBuilder::new();
+    builder.add_data(signature)?.add_op(OpFalse)?.add_data(redeem_script)?;
+    Ok(builder.drain())
+}
+
+/// Creates the redeem script of a lock time vault, to be paid to with
+/// [`pay_to_script_hash_script`](super::pay_to_script_hash_script).
+pub fn time_lock_vault_redeem_script(owner: &[u8; 32], lock_time: u64) -> ScriptBuilderResult<Vec<u8>> {
+    let mut builder = ScriptBuilder::new();
+    builder.add_lock_time(lock_time)?.add_op(OpCheckLockTimeVerify)?.add_data(owner)?.add_op(OpCheckSig)?;
+    Ok(builder.drain())
+}
+
+/// Generates a signature script spending a P2SH lock time vault.
+///
+/// `signature` is the owner's signature, including its sighash type byte. The same lock time
+/// requirements as in [`htlc_refund_signature_script`] apply.
+pub fn time_lock_vault_signature_script(redeem_script: &[u8], signature: &[u8]) -> ScriptBuilderResult<Vec<u8>> {
+    let mut builder = ScriptBuilder::new();
+    builder.add_data(signature)?.add_data(redeem_script)?;
+    Ok(builder.drain())
+}
+
+/// Extracts the parameters of a hash time-locked contract from its redeem script, or returns
+/// `None` if the script is not in the exact form built by [`htlc_redeem_script`].
+pub fn extract_hash_time_lock(script: &[u8]) -> Option<HashTimeLock> {
+    if script.len() <= HTLC_PREFIX_LEN + HTLC_SUFFIX_LEN {
+        return None;
+    }
+    let (prefix, rest) = script.split_at(HTLC_PREFIX_LEN);
+    let (lock_time_push, suffix) = rest.split_at(rest.len() - HTLC_SUFFIX_LEN);
+    if prefix[..7] != [OpIf, OpSize, OpData1, HTLC_SECRET_SIZE as u8, OpEqualVerify, OpBlake2b, OpData32]
+        || prefix[39..41] != [OpEqualVerify, OpData32]
+        || prefix[73] != OpElse
+        || suffix[..2] != [OpCheckLockTimeVerify, OpData32]
+        || suffix[34..] != [OpEndIf, OpCheckSig]
+    {
+        return None;
+    }
+    Some(HashTimeLock {
+        secret_hash: prefix[7..39].try_into().expect("slice length is 32"),
+        receiver: prefix[41..73].try_into().expect("slice length is 32"),
+        refunder: suffix[2..34].try_into().expect("slice length is 32"),
+        lock_time: parse_lock_time(lock_time_push)?,
+    })
+}
+
+/// Extracts the parameters of a lock time vault from its redeem script, or returns
+/// `None` if the script is not in the exact form built by [`time_lock_vault_redeem_script`].
+pub fn extract_time_lock_vault(script: &[u8]) -> Option<TimeLockVault> {
+    if script.len() <= VAULT_SUFFIX_LEN {
+        return None;
+    }
+    let (lock_time_push, suffix) = script.split_at(script.len() - VAULT_SUFFIX_LEN);
+    if suffix[..2] != [OpCheckLockTimeVerify, OpData32] || suffix[34] != OpCheckSig {
+        return None;
+    }
+    Some(TimeLockVault { owner: suffix[2..34].try_into().expect("slice length is 32"), lock_time: parse_lock_time(lock_time_push)? })
+}
+
+/// Decodes a lock time push, accepting only the canonical encoding produced by [`ScriptBuilder::add_lock_time`]
+fn parse_lock_time(push: &[u8]) -> Option<u64> {
+    let lock_time = match push.split_first()? {
+        (&opcode, []) if (OpTrue..=Op16).contains(&opcode) => (opcode - OpTrue + 1) as u64,
+        (_, data) if data.len() <= 8 => {
+            let mut bytes = [0u8; 8];
+            bytes[..data.len()].copy_from_slice(data);
+            u64::from_le_bytes(bytes)
+        }
+        _ => return None,
+    };
+    (ScriptBuilder::new().add_lock_time(lock_time).ok()?.drain() == push).then_some(lock_time)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{caches::Cache, pay_to_script_hash_script, TxScriptEngine, TxScriptError, MAX_TX_IN_SEQUENCE_NUM};
+    use kaspa_consensus_core::{
+        hashing::{
+            sighash::{calc_schnorr_signature_hash, SigHashReusedValuesUnsync},
+            sighash_type::SIG_HASH_ALL,
+        },
+        subnets::SUBNETWORK_ID_NATIVE,
+        tx::*,
+    };
+    use secp256k1::Keypair;
+
+    fn kp(seed: u8) -> Keypair {
+        Keypair::from_seckey_slice(secp256k1::SECP256K1, &[seed; 32]).unwrap()
+    }
+
+    fn pk(kp: &Keypair) -> [u8; 32] {
+        kp.x_only_public_key().0.serialize()
+    }
+
+    /// Signs the single input of a transaction spending `redeem_script` with `kp`, fills its
+    /// signature script using `signature_script` and executes it.
+    fn spend(
+        redeem_script: &[u8],
+        kp: &Keypair,
+        tx_lock_time: u64,
+        sequence: u64,
+        signature_script: impl FnOnce(&[u8]) -> Vec<u8>,
+    ) -> Result<(), TxScriptError> {
+        let tx = Transaction::new(
+            0,
+            vec![TransactionInput {
+                previous_outpoint: TransactionOutpoint { transaction_id: TransactionId::from_bytes([1; 32]), index: 0 },
+                signature_script: vec![],
+                sequence,
+                sig_op_count: 1,
+            }],
+            vec![],
+            tx_lock_time,
+            SUBNETWORK_ID_NATIVE,
+            0,
+            vec![],
+        );
+        let entry = UtxoEntry::new(1000, pay_to_script_hash_script(redeem_script), 0, false);
+        let mut tx = MutableTransaction::with_entries(tx, vec![entry]);
+
+        let reused_values = SigHashReusedValuesUnsync::new();
+        let sig_hash = calc_schnorr_signature_hash(&tx.as_verifiable(), 0, SIG_HASH_ALL, &reused_values);
+        let msg = secp256k1::Message::from_digest_slice(sig_hash.as_bytes().as_slice()).unwrap();
+        let signature: Vec<u8> = kp.sign_schnorr(msg).as_ref().iter().copied().chain([SIG_HASH_ALL.to_u8()]).collect();
+        tx.tx.inputs[0].signature_script = signature_script(&signature);
+
+        let tx = tx.as_verifiable();
+        let (input, entry) = tx.populated_inputs().next().unwrap();
+        let cache = Cache::new(10_000);
+        TxScriptEngine::from_transaction_input(&tx, input, 0, entry, &reused_values, &cache, Default::default()).execute()
+    }
+
+    #[test]
+    fn test_htlc_spend_paths() {
+        let (receiver, refunder) = (kp(1), kp(2));
+        let secret = [7u8; HTLC_SECRET_SIZE];
+        let lock_time = 1_000_000;
+        let script = htlc_redeem_script(&htlc_secret_hash(&secret), &pk(&receiver), &pk(&refunder), lock_time).unwrap();
+
+        // Claim with the secret, regardless of lock time
+        assert!(spend(&script, &receiver, 0, MAX_TX_IN_SEQUENCE_NUM, |sig| htlc_claim_signature_script(&script, sig, &secret)
+            .unwrap())
+        .is_ok());
+        // Claim with a wrong secret or by the wrong key
+        assert!(
+            spend(&script, &receiver, 0, 0, |sig| htlc_claim_signature_script(&script, sig, &[8; HTLC_SECRET_SIZE]).unwrap()).is_err()
+        );
+        assert!(spend(&script, &refunder, 0, 0, |sig| htlc_claim_signature_script(&script, sig, &secret).unwrap()).is_err());
+
+        // Refund once the lock time was reached
+        assert!(spend(&script, &refunder, lock_time, 0, |sig| htlc_refund_signature_script(&script, sig).unwrap()).is_ok());
+        // Refund before the lock time, with a finalized input, or by the wrong key
+        assert!(matches!(
+            spend(&script, &refunder, lock_time - 1, 0, |sig| htlc_refund_signature_script(&script, sig).unwrap()),
+            Err(TxScriptError::UnsatisfiedLockTime(_))
+        ));
+        assert!(matches!(
+            spend(&script, &refunder, lock_time, MAX_TX_IN_SEQUENCE_NUM, |sig| htlc_refund_signature_script(&script, sig).unwrap()),
+            Err(TxScriptError::UnsatisfiedLockTime(_))
+        ));
+        assert!(spend(&script, &receiver, lock_time, 0, |sig| htlc_refund_signature_script(&script, sig).unwrap()).is_err());
+    }
+
+    #[test]
+    fn test_time_lock_vault_spend_paths() {
+        let (owner, other) = (kp(1), kp(2));
+        let lock_time = 5000;
+        let script = time_lock_vault_redeem_script(&pk(&owner), lock_time).unwrap();
+
+        assert!(spend(&script, &owner, lock_time, 0, |sig| time_lock_vault_signature_script(&script, sig).unwrap()).is_ok());
+        assert!(matches!(
+            spend(&script, &owner, lock_time - 1, 0, |sig| time_lock_vault_signature_script(&script, sig).unwrap()),
+            Err(TxScriptError::UnsatisfiedLockTime(_))
+        ));
+        assert!(spend(&script, &other, lock_time, 0, |sig| time_lock_vault_signature_script(&script, sig).unwrap()).is_err());
+    }
+
+    #[test]
+    fn test_extract_round_trip() {
+        for lock_time in [0, 1, 16, 17, 0x80, 0xffff, 1_700_000_000_000, u64::MAX] {
+            let htlc = HashTimeLock { secret_hash: [1; 32], receiver: [2; 32], refunder: [3; 32], lock_time };
+            let script = htlc_redeem_script(&htlc.secret_hash, &htlc.receiver, &htlc.refunder, lock_time).unwrap();
+            assert_eq!(extract_hash_time_lock(&script), Some(htlc), "lock time {lock_time}");
+            assert_eq!(extract_time_lock_vault(&script), None);
+
+            let vault = TimeLockVault { owner: [4; 32], lock_time };
+            let script = time_lock_vault_redeem_script(&vault.owner, lock_time).unwrap();
+            assert_eq!(extract_time_lock_vault(&script), Some(vault), "lock time {lock_time}");
+            assert_eq!(extract_hash_time_lock(&script), None);
+        }
+    }
+
+    #[test]
+    fn test_extract_rejects_non_canonical() {
+        let script = time_lock_vault_redeem_script(&[4; 32], 0x1234).unwrap();
+        assert_eq!(script[0], OpData1 + 1);
+
+        // Lock time pushed with trailing zero bytes
+        let mut padded = vec![OpData1 + 2, 0x34, 0x12, 0x00];
+        padded.extend_from_slice(&script[3..]);
+        assert_eq!(extract_time_lock_vault(&padded), None);
+
+        // Lock time pushed as more than 8 bytes
+        let mut oversized = vec![OpData1 + 8];
+        oversized.extend_from_slice(&[0xff; 9]);
+        oversized.extend_from_slice(&script[3..]);
+        assert_eq!(extract_time_lock_vault(&oversized), None);
+
+        // A single trailing opcode too many
+        let mut extended = script.clone();
+        extended.push(OpCheckSig);
+        assert_eq!(extract_time_lock_vault(&extended), None);
+        assert_eq!(extract_time_lock_vault(&script[1..]), None);
+    }
+}
@@ -0,0 +1,73 @@
+//! Hooks for observing the script engine while it executes, one opcode at a time.
+
+use kaspa_txscript_errors::TxScriptError;
+
+/// The engine state around the execution of a single opcode
+#[derive(Debug, Clone, Copy)]
+pub struct TraceStep<'a> {
+    /// Index of the running script: 0 for the signature script, 1 for the script public key
+    /// and 2 for the redeem script of a P2SH input
+    pub script_index: usize,
+    /// Byte offset of the opcode within the running script
+    pub pc: usize,
+    /// The opcode value
+    pub opcode: u8,
+    /// The data pushed by the opcode, empty for non-push opcodes
+    pub data: &'a [u8],
+    /// Whether the opcode runs, as opposed to being skipped by a false conditional branch
+    pub executing: bool,
+    /// The main stack, top element last
+    pub dstack: &'a [Vec<u8>],
+    /// The alt stack, top element last
+    pub astack: &'a [Vec<u8>],
+}
+
+/// Receives a callback from the engine before and after each opcode of the executed scripts
+pub trait ScriptTracer {
+    fn before_step(&mut self, step: &TraceStep);
+    /// Called with the result of the step, including the engine checks which may reject
+    /// the opcode before it runs (disabled, reserved, stack size etc.)
+    fn after_step(&mut self, step: &TraceStep, result: &Result<(), TxScriptError>);
+}
+
+/// An owned copy of a [`TraceStep`] taken after the opcode ran
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraceRecord {
+    pub script_index: usize,
+    pub pc: usize,
+    pub opcode: u8,
+    pub data: Vec<u8>,
+    pub executing: bool,
+    pub dstack: Vec<Vec<u8>>,
+    pub astack: Vec<Vec<u8>>,
+    pub result: Result<(), TxScriptError>,
+}
+
+/// A [`ScriptTracer`] which records the state of the engine after every step
+#[derive(Debug, Default)]
+pub struct TraceRecorder {
+    pub records: Vec<TraceRecord>,
+}
+
+impl TraceRecorder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl ScriptTracer for TraceRecorder {
+    fn before_step(&mut self, _step: &TraceStep) {}
+
+    fn after_step(&mut self, step: &TraceStep, result: &Result<(), TxScriptError>) {
+        self.records.push(TraceRecord {
+            script_index: step.script_index,
+            pc: step.pc,
+            opcode: step.opcode,
+            data: step.data.to_vec(),
+            executing: step.executing,
+            dstack: step.dstack.to_vec(),
+            astack: step.astack.to_vec(),
+            result: result.clone(),
+        });
+    }
+}
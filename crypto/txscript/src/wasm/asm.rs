@@ -0,0 +1,24 @@
+use crate::asm;
+use crate::result::Result;
+use kaspa_utils::hex::ToHex;
+use kaspa_wasm_core::types::{BinaryT, HexString};
+use wasm_bindgen::prelude::wasm_bindgen;
+use workflow_wasm::prelude::*;
+
+/// Converts a script to its textual representation, e.g. `OP_DUP OP_BLAKE2B <...> OP_EQUALVERIFY`.
+/// @param script - The script ({@link HexString} or Uint8Array).
+/// @see {@link assembleScript}
+/// @category Consensus
+#[wasm_bindgen(js_name = "disassembleScript")]
+pub fn disassemble_script(script: BinaryT) -> Result<String> {
+    let script = script.try_as_vec_u8()?;
+    Ok(asm::disassemble(&script))
+}
+
+/// Parses the textual representation of a script, as produced by {@link disassembleScript},
+/// returning the script bytes represented by a hex string.
+/// @category Consensus
+#[wasm_bindgen(js_name = "assembleScript")]
+pub fn assemble_script(asm: &str) -> Result<HexString> {
+    Ok(asm::assemble(asm)?.to_hex().into())
+}
@@ -168,6 +168,14 @@ impl ScriptBuilder {
         Ok(generated_script.to_hex().into())
     }
 
+    /// Get the textual representation of the script.
+    /// @see {@link disassembleScript}
+    pub fn disassemble(&self) -> String {
+        let inner = self.inner();
+
+        crate::asm::disassemble(inner.script())
+    }
+
     #[wasm_bindgen(js_name = "hexView")]
     pub fn hex_view(&self, args: Option<HexViewConfigT>) -> Result<String> {
         let inner = self.inner();
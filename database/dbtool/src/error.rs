@@ -0,0 +1,36 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum DbToolError {
+    #[error("the database path is missing, set it with --db")]
+    MissingDb,
+
+    #[error("no database found at {0}")]
+    DbNotFound(String),
+
+    #[error("unknown store {0}, run `kaspa-dbtool stores` to list the known stores")]
+    UnknownStore(String),
+
+    #[error("the {0} store requires a key")]
+    MissingKey(&'static str),
+
+    #[error("invalid key {0}: {1}")]
+    InvalidKey(String, String),
+
+    #[error("key {0} not found")]
+    KeyNotFound(String),
+
+    #[error("rocksdb error: {0}")]
+    RocksDb(#[from] rocksdb::Error),
+
+    #[error("bincode error: {0}")]
+    Bincode(#[from] bincode::Error),
+
+    #[error("json error: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("{0}")]
+    FdBudget(#[from] kaspa_utils::fd_budget::Error),
+}
+
+pub type DbToolResult<T> = std::result::Result<T, DbToolError>;
@@ -0,0 +1,179 @@
+use clap::{Arg, ArgMatches, Command};
+use error::{DbToolError, DbToolResult};
+use kaspa_consensus_core::BlockLevel;
+use kaspa_database::prelude::{ConnBuilder, DB};
+use rocksdb::{IteratorMode, ReadOptions};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::{json, Value};
+use std::{collections::BTreeMap, path::PathBuf, sync::Arc};
+use stores::{hex, store_name, Store, STORES};
+
+mod error;
+mod stores;
+mod verify;
+
+const DEFAULT_SCAN_LIMIT: &str = "100";
+
+fn cli() -> Command {
+    let store_arg = |name: &'static str, long: bool| {
+        let arg = Arg::new(name).value_name("store").required(true).help("Name of the store, see the `stores` command");
+        if long {
+            arg.long(name)
+        } else {
+            arg
+        }
+    };
+    let level_arg = Arg::new("level")
+        .long("level")
+        .value_name("level")
+        .default_value("0")
+        .value_parser(clap::value_parser!(BlockLevel))
+        .help("Block level of per-level stores such as ghostdag");
+
+    Command::new("kaspa-dbtool")
+        .about(format!("{} v{}", env!("CARGO_PKG_DESCRIPTION"), env!("CARGO_PKG_VERSION")))
+        .version(env!("CARGO_PKG_VERSION"))
+        .subcommand_required(true)
+        .arg(
+            Arg::new("db").long("db").value_name("path").global(true).help(
+                "Path of the database directory, e.g. <appdir>/kaspa-mainnet/datadir/consensus/consensus-001 or .../datadir/meta",
+            ),
+        )
+        .subcommand(Command::new("stores").about("List the stores decoded by the tool"))
+        .subcommand(
+            Command::new("get")
+                .about("Get and decode a single entry of a store")
+                .arg(store_arg("store", false))
+                .arg(Arg::new("key").value_name("key").help("Block hash, <transaction id>:<index> outpoint or index, by store"))
+                .arg(level_arg.clone()),
+        )
+        .subcommand(
+            Command::new("scan")
+                .about("Decode the entries of a store in key order")
+                .arg(store_arg("prefix", true))
+                .arg(
+                    Arg::new("limit")
+                        .long("limit")
+                        .value_name("count")
+                        .default_value(DEFAULT_SCAN_LIMIT)
+                        .value_parser(clap::value_parser!(usize))
+                        .help("Maximum number of entries to decode, 0 for all"),
+                )
+                .arg(level_arg),
+        )
+        .subcommand(Command::new("stats").about("Count the keys and the key and value sizes of every store prefix"))
+        .subcommand(Command::new("verify").about("Recompute the UTXO set commitments of a consensus database"))
+}
+
+/// Opens the DB read-only, so the tool can run against the database of a live node
+fn open_db(path: &str) -> DbToolResult<Arc<DB>> {
+    let path = PathBuf::from(path);
+    if !path.join("CURRENT").exists() {
+        return Err(DbToolError::DbNotFound(path.display().to_string()));
+    }
+    Ok(ConnBuilder::default().with_db_path(path).with_read_only(true).with_files_limit(128).build()?)
+}
+
+pub(crate) fn prefix_iterator(db: &DB, prefix: Vec<u8>) -> impl Iterator<Item = Result<(Box<[u8]>, Box<[u8]>), rocksdb::Error>> + '_ {
+    let mut read_opts = ReadOptions::default();
+    read_opts.set_iterate_range(rocksdb::PrefixRange(prefix));
+    db.iterator_opt(IteratorMode::Start, read_opts)
+}
+
+pub(crate) fn get_item<T: DeserializeOwned>(db: &DB, key: &[u8]) -> DbToolResult<T> {
+    let value = db.get_pinned(key)?.ok_or_else(|| DbToolError::KeyNotFound(hex(key)))?;
+    Ok(bincode::deserialize(&value)?)
+}
+
+/// Decodes an entry of `store`, falling back to the raw value if it cannot be decoded
+fn decode_entry(store: &Store, key: &[u8], value: &[u8]) -> Value {
+    match store.decode_value(value) {
+        Ok(decoded) => json!({ "key": store.decode_key(key), "value": decoded }),
+        Err(err) => json!({ "key": store.decode_key(key), "raw": hex(value), "error": err.to_string() }),
+    }
+}
+
+fn get(db: &DB, m: &ArgMatches) -> DbToolResult<Value> {
+    let store = Store::find(m.get_one::<String>("store").unwrap())?;
+    let key = store.parse_key(m.get_one::<String>("key").map(String::as_str))?;
+    let db_key = [store.prefix_bytes(*m.get_one::<BlockLevel>("level").unwrap()), key.clone()].concat();
+    let value = db.get_pinned(&db_key)?.ok_or_else(|| DbToolError::KeyNotFound(hex(&db_key)))?;
+    Ok(decode_entry(store, &key, &value))
+}
+
+fn scan(db: &DB, m: &ArgMatches) -> DbToolResult<Value> {
+    let store = Store::find(m.get_one::<String>("prefix").unwrap())?;
+    let limit = match *m.get_one::<usize>("limit").unwrap() {
+        0 => usize::MAX,
+        limit => limit,
+    };
+    let prefix = store.prefix_bytes(*m.get_one::<BlockLevel>("level").unwrap());
+    let entries = prefix_iterator(db, prefix.clone())
+        .take(limit)
+        .map(|entry| entry.map(|(key, value)| decode_entry(store, &key[prefix.len()..], &value)))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(json!({ "store": store.name, "count": entries.len(), "entries": entries }))
+}
+
+#[derive(Default, Serialize)]
+struct PrefixStats {
+    prefix: u8,
+    store: String,
+    keys: u64,
+    key_bytes: u64,
+    value_bytes: u64,
+}
+
+fn stats(db: &DB) -> DbToolResult<Value> {
+    let mut stats = BTreeMap::<u8, PrefixStats>::new();
+    for entry in db.iterator(IteratorMode::Start) {
+        let (key, value) = entry?;
+        let Some(&prefix) = key.first() else { continue };
+        let stats = stats.entry(prefix).or_insert_with(|| PrefixStats { prefix, store: store_name(prefix), ..Default::default() });
+        stats.keys += 1;
+        stats.key_bytes += key.len() as u64;
+        stats.value_bytes += value.len() as u64;
+    }
+    Ok(serde_json::to_value(stats.into_values().collect::<Vec<_>>())?)
+}
+
+fn run() -> DbToolResult<bool> {
+    let m = cli().get_matches();
+    let (command, m) = m.subcommand().unwrap();
+    let (output, passed) = match command {
+        "stores" => {
+            let stores = STORES
+                .iter()
+                .map(|store| json!({ "name": store.name, "prefix": store.prefix as u8, "key": format!("{:?}", store.key_kind) }))
+                .collect::<Vec<_>>();
+            (json!(stores), true)
+        }
+        command => {
+            let db = open_db(m.get_one::<String>("db").ok_or(DbToolError::MissingDb)?)?;
+            match command {
+                "get" => (get(&db, m)?, true),
+                "scan" => (scan(&db, m)?, true),
+                "stats" => (stats(&db)?, true),
+                "verify" => {
+                    let checks = verify::verify(&db)?;
+                    let passed = checks.iter().all(|check| check.passed());
+                    (json!({ "passed": passed, "checks": checks }), passed)
+                }
+                _ => unreachable!("subcommands are validated by clap"),
+            }
+        }
+    };
+    println!("{}", serde_json::to_string_pretty(&output)?);
+    Ok(passed)
+}
+
+fn main() {
+    match run() {
+        Ok(true) => {}
+        Ok(false) => std::process::exit(2),
+        Err(err) => {
+            eprintln!("error: {err}");
+            std::process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,260 @@
+//!
+//! The stores decoded by the tool, i.e. how their DB keys are formed and how their values are encoded.
+//! Keys are `{ store prefix || key }`, where the store prefix is a [`DatabaseStorePrefixes`] byte
+//! possibly followed by a bucket (e.g. the block level of ghostdag data).
+//!
+
+use crate::error::{DbToolError, DbToolResult};
+use kaspa_consensus::{
+    consensus::factory::{ConsensusEntry, MultiConsensusMetadata},
+    model::stores::{
+        ghostdag::GhostdagData,
+        headers::HeaderWithBlockLevel,
+        pruning::PruningPointInfo,
+        reachability::ReachabilityData,
+        utxo_set::{UtxoKey, UTXO_KEY_SIZE},
+        virtual_state::VirtualState,
+    },
+    processes::ghostdag::ordering::SortableBlock,
+};
+use kaspa_consensus_core::{
+    blockstatus::BlockStatus,
+    tx::{TransactionOutpoint, UtxoEntry},
+    BlockHashSet, BlockLevel,
+};
+use kaspa_database::registry::{DatabaseStorePrefixes, SEPARATOR};
+use kaspa_hashes::{Hash, HASH_SIZE};
+use kaspa_math::Uint3072;
+use kaspa_muhash::MuHash;
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::{json, Value};
+use std::str::FromStr;
+
+/// The structure of the keys of a store, following its store prefix
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum KeyKind {
+    /// A block hash
+    Hash,
+    /// A transaction outpoint, see [`UtxoKey`]
+    Outpoint,
+    /// A little-endian u64 index
+    Index,
+    /// A single item stored under the store prefix alone
+    Item,
+}
+
+pub struct Store {
+    pub name: &'static str,
+    pub prefix: DatabaseStorePrefixes,
+    pub key_kind: KeyKind,
+    decode_value: fn(&[u8]) -> DbToolResult<Value>,
+}
+
+pub const STORES: &[Store] = &[
+    Store {
+        name: "headers",
+        prefix: DatabaseStorePrefixes::Headers,
+        key_kind: KeyKind::Hash,
+        decode_value: decode::<HeaderWithBlockLevel>,
+    },
+    Store { name: "ghostdag", prefix: DatabaseStorePrefixes::Ghostdag, key_kind: KeyKind::Hash, decode_value: decode::<GhostdagData> },
+    Store { name: "statuses", prefix: DatabaseStorePrefixes::Statuses, key_kind: KeyKind::Hash, decode_value: decode::<BlockStatus> },
+    Store {
+        name: "reachability",
+        prefix: DatabaseStorePrefixes::Reachability,
+        key_kind: KeyKind::Hash,
+        decode_value: decode::<ReachabilityData>,
+    },
+    Store {
+        name: "virtual-utxoset",
+        prefix: DatabaseStorePrefixes::VirtualUtxoset,
+        key_kind: KeyKind::Outpoint,
+        decode_value: decode::<UtxoEntry>,
+    },
+    Store {
+        name: "pruning-utxoset",
+        prefix: DatabaseStorePrefixes::PruningUtxoset,
+        key_kind: KeyKind::Outpoint,
+        decode_value: decode::<UtxoEntry>,
+    },
+    Store {
+        name: "utxo-multisets",
+        prefix: DatabaseStorePrefixes::UtxoMultisets,
+        key_kind: KeyKind::Hash,
+        decode_value: decode_multiset,
+    },
+    Store {
+        name: "pruning-point",
+        prefix: DatabaseStorePrefixes::PruningPoint,
+        key_kind: KeyKind::Item,
+        decode_value: decode::<PruningPointInfo>,
+    },
+    Store {
+        name: "past-pruning-points",
+        prefix: DatabaseStorePrefixes::PastPruningPoints,
+        key_kind: KeyKind::Index,
+        decode_value: decode::<Hash>,
+    },
+    Store {
+        name: "headers-selected-tip",
+        prefix: DatabaseStorePrefixes::HeadersSelectedTip,
+        key_kind: KeyKind::Item,
+        decode_value: decode::<SortableBlock>,
+    },
+    Store {
+        name: "virtual-state",
+        prefix: DatabaseStorePrefixes::VirtualState,
+        key_kind: KeyKind::Item,
+        decode_value: decode_virtual_state,
+    },
+    Store {
+        name: "utxoindex-tips",
+        prefix: DatabaseStorePrefixes::UtxoIndexTips,
+        key_kind: KeyKind::Item,
+        decode_value: decode::<BlockHashSet>,
+    },
+    // ---- Meta ----
+    Store {
+        name: "consensus-entries",
+        prefix: DatabaseStorePrefixes::ConsensusEntries,
+        key_kind: KeyKind::Index,
+        decode_value: decode::<ConsensusEntry>,
+    },
+    Store {
+        name: "consensus-metadata",
+        prefix: DatabaseStorePrefixes::MultiConsensusMetadata,
+        key_kind: KeyKind::Item,
+        decode_value: decode::<MultiConsensusMetadata>,
+    },
+];
+
+impl Store {
+    pub fn find(name: &str) -> DbToolResult<&'static Store> {
+        STORES.iter().find(|store| store.name == name).ok_or_else(|| DbToolError::UnknownStore(name.to_string()))
+    }
+
+    /// Returns the DB key prefix of the store. `level` only applies to per-level stores
+    pub fn prefix_bytes(&self, level: BlockLevel) -> Vec<u8> {
+        let mut prefix = vec![self.prefix as u8];
+        match self.prefix {
+            DatabaseStorePrefixes::Ghostdag => prefix.push(level),
+            // The reachability store of the consensus is bucketed by the separator, see `DbReachabilityStore::new`
+            DatabaseStorePrefixes::Reachability => prefix.push(SEPARATOR),
+            _ => {}
+        }
+        prefix
+    }
+
+    /// Parses a key given on the command line into its DB encoding
+    pub fn parse_key(&self, key: Option<&str>) -> DbToolResult<Vec<u8>> {
+        let invalid = |reason: &str| DbToolError::InvalidKey(key.unwrap_or_default().to_string(), reason.to_string());
+        match (self.key_kind, key) {
+            (KeyKind::Item, None) => Ok(vec![]),
+            (KeyKind::Item, Some(_)) => Err(invalid(&format!("the {} store holds a single item and takes no key", self.name))),
+            (_, None) => Err(DbToolError::MissingKey(self.name)),
+            (KeyKind::Hash, Some(key)) => Ok(Hash::from_str(key).map_err(|err| invalid(&err.to_string()))?.as_bytes().to_vec()),
+            (KeyKind::Outpoint, Some(key)) => {
+                let (transaction_id, index) = key.split_once(':').ok_or_else(|| invalid("expected <transaction id>:<index>"))?;
+                let transaction_id = Hash::from_str(transaction_id).map_err(|err| invalid(&err.to_string()))?;
+                let index = index.parse().map_err(|_| invalid("invalid output index"))?;
+                Ok(UtxoKey::from(TransactionOutpoint::new(transaction_id, index)).as_ref().to_vec())
+            }
+            (KeyKind::Index, Some(key)) => {
+                Ok(key.parse::<u64>().map_err(|_| invalid("expected an integer index"))?.to_le_bytes().to_vec())
+            }
+        }
+    }
+
+    /// Decodes a DB key, stripped of the store prefix, into its JSON representation
+    pub fn decode_key(&self, key: &[u8]) -> Value {
+        match self.key_kind {
+            KeyKind::Hash if key.len() == HASH_SIZE => json!(Hash::from_slice(key)),
+            KeyKind::Outpoint if key.len() <= UTXO_KEY_SIZE => match UtxoKey::try_from(key) {
+                Ok(utxo_key) => json!(TransactionOutpoint::from(utxo_key)),
+                Err(_) => json!(hex(key)),
+            },
+            KeyKind::Index if key.len() == size_of::<u64>() => json!(u64::from_le_bytes(key.try_into().unwrap())),
+            KeyKind::Item if key.is_empty() => Value::Null,
+            _ => json!(hex(key)),
+        }
+    }
+
+    pub fn decode_value(&self, value: &[u8]) -> DbToolResult<Value> {
+        (self.decode_value)(value)
+    }
+}
+
+/// Returns the name of the store whose prefix is `prefix`, if it is one decoded by the tool
+pub fn store_name(prefix: u8) -> String {
+    match STORES.iter().find(|store| store.prefix as u8 == prefix) {
+        Some(store) => store.name.to_string(),
+        None => match DatabaseStorePrefixes::try_from(prefix) {
+            Ok(prefix) => format!("{prefix:?}"),
+            Err(_) => format!("unknown ({prefix})"),
+        },
+    }
+}
+
+pub fn hex(bytes: &[u8]) -> String {
+    faster_hex::hex_string(bytes)
+}
+
+fn decode<T: DeserializeOwned + Serialize>(value: &[u8]) -> DbToolResult<Value> {
+    let value: T = bincode::deserialize(value)?;
+    Ok(serde_json::to_value(value)?)
+}
+
+/// Multisets are stored as their raw field element, we present them by their finalized hash
+fn decode_multiset(value: &[u8]) -> DbToolResult<Value> {
+    let multiset: Uint3072 = bincode::deserialize(value)?;
+    Ok(json!({ "hash": MuHash::from(multiset).finalize() }))
+}
+
+/// The virtual state holds the full UTXO diff from the sink, which is summarized by its size
+fn decode_virtual_state(value: &[u8]) -> DbToolResult<Value> {
+    let mut state: VirtualState = bincode::deserialize(value)?;
+    Ok(json!({
+        "parents": state.parents,
+        "selected_parent": state.ghostdag_data.selected_parent,
+        "blue_score": state.ghostdag_data.blue_score,
+        "blue_work": state.ghostdag_data.blue_work,
+        "daa_score": state.daa_score,
+        "bits": state.bits,
+        "past_median_time": state.past_median_time,
+        "multiset": state.multiset.finalize(),
+        "utxo_diff": { "added": state.utxo_diff.add.len(), "removed": state.utxo_diff.remove.len() },
+        "accepted_transactions": state.accepted_tx_ids.len(),
+        "mergeset_blues": state.ghostdag_data.mergeset_blues.len(),
+        "mergeset_reds": state.ghostdag_data.mergeset_reds.len(),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_store_keys() {
+        let hash = Hash::from_u64_word(7);
+        let headers = Store::find("headers").unwrap();
+        let key = headers.parse_key(Some(&hash.to_string())).unwrap();
+        assert_eq!(headers.decode_key(&key), json!(hash));
+        assert!(headers.parse_key(None).is_err());
+
+        let utxoset = Store::find("virtual-utxoset").unwrap();
+        let key = utxoset.parse_key(Some(&format!("{hash}:3"))).unwrap();
+        assert_eq!(utxoset.decode_key(&key), json!(TransactionOutpoint::new(hash, 3)));
+        assert!(utxoset.parse_key(Some(&hash.to_string())).is_err());
+
+        let past_pruning_points = Store::find("past-pruning-points").unwrap();
+        assert_eq!(past_pruning_points.decode_key(&past_pruning_points.parse_key(Some("12")).unwrap()), json!(12));
+
+        let virtual_state = Store::find("virtual-state").unwrap();
+        assert_eq!(virtual_state.parse_key(None).unwrap(), Vec::<u8>::new());
+        assert!(virtual_state.parse_key(Some("0")).is_err());
+
+        assert_eq!(Store::find("ghostdag").unwrap().prefix_bytes(2), vec![DatabaseStorePrefixes::Ghostdag as u8, 2]);
+        assert_eq!(store_name(DatabaseStorePrefixes::BlockTransactions as u8), "BlockTransactions");
+        assert!(Store::find("unknown").is_err());
+    }
+}
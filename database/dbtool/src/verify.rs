@@ -0,0 +1,159 @@
+//!
+//! Consistency checks of a consensus DB, recomputing the UTXO set commitments from the stored UTXO sets
+//!
+
+use crate::{
+    error::{DbToolError, DbToolResult},
+    get_item, prefix_iterator,
+    stores::hex,
+};
+use kaspa_consensus::model::stores::{
+    headers::HeaderWithBlockLevel, pruning::PruningPointInfo, utxo_set::UtxoKey, virtual_state::VirtualState,
+};
+use kaspa_consensus_core::{
+    muhash::MuHashExtensions,
+    tx::{TransactionOutpoint, UtxoEntry},
+};
+use kaspa_database::{prelude::DB, registry::DatabaseStorePrefixes};
+use kaspa_hashes::Hash;
+use kaspa_math::Uint3072;
+use kaspa_muhash::MuHash;
+use serde::Serialize;
+
+#[derive(Serialize)]
+pub struct Check {
+    pub name: &'static str,
+    pub description: String,
+    /// The commitment found in the DB
+    pub expected: Option<Hash>,
+    /// The commitment recomputed from the stored UTXO set
+    pub actual: Option<Hash>,
+    /// Set when the check could not run, e.g. since the DB is mid-update
+    pub skipped: Option<String>,
+    pub utxos: Option<u64>,
+}
+
+impl Check {
+    pub fn passed(&self) -> bool {
+        self.skipped.is_some() || self.expected == self.actual
+    }
+}
+
+/// Runs all checks over `db`, which is expected to be a consensus DB
+pub fn verify(db: &DB) -> DbToolResult<Vec<Check>> {
+    let mut state: VirtualState = get_item(db, &[DatabaseStorePrefixes::VirtualState as u8])?;
+    let (mut virtual_multiset, utxos) = utxo_set_multiset(db, DatabaseStorePrefixes::VirtualUtxoset)?;
+    let actual = virtual_multiset.finalize();
+    let mut checks = vec![Check {
+        name: "virtual-utxoset",
+        description: "the multiset of the virtual UTXO set against the virtual state".to_string(),
+        expected: Some(state.multiset.finalize()),
+        actual: Some(actual),
+        skipped: None,
+        utxos: Some(utxos),
+    }];
+
+    // The virtual UTXO set is the UTXO set of the sink, i.e. the selected parent of virtual, with the virtual diff applied
+    let sink = state.ghostdag_data.selected_parent;
+    let sink_multiset: Uint3072 = get_item(db, &hash_key(DatabaseStorePrefixes::UtxoMultisets, sink))?;
+    let mut expected = MuHash::from(sink_multiset);
+    state.utxo_diff.add.iter().for_each(|(outpoint, entry)| expected.add_utxo(outpoint, entry));
+    state.utxo_diff.remove.iter().for_each(|(outpoint, entry)| expected.remove_utxo(outpoint, entry));
+    checks.push(Check {
+        name: "utxo-multisets",
+        description: format!(
+            "the multiset of the virtual UTXO set against the multiset of the sink {sink} with the virtual UTXO diff applied"
+        ),
+        expected: Some(expected.finalize()),
+        actual: Some(actual),
+        skipped: None,
+        utxos: None,
+    });
+
+    checks.push(verify_pruning_utxoset(db)?);
+    Ok(checks)
+}
+
+/// Recomputes the pruning point UTXO commitment, unless the pruning point UTXO set is being moved to a new pruning point
+fn verify_pruning_utxoset(db: &DB) -> DbToolResult<Check> {
+    let pruning_point = get_item::<PruningPointInfo>(db, &[DatabaseStorePrefixes::PruningPoint as u8])?.pruning_point;
+    let position: Hash = get_item(db, &[DatabaseStorePrefixes::PruningUtxosetPosition as u8])?;
+    let description =
+        format!("the multiset of the pruning point UTXO set against the UTXO commitment of the pruning point {pruning_point}");
+    if position != pruning_point {
+        let skipped = Some(format!("the pruning point UTXO set is at {position} and is being moved to the pruning point"));
+        return Ok(Check { name: "pruning-utxoset", description, expected: None, actual: None, skipped, utxos: None });
+    }
+
+    let header: HeaderWithBlockLevel = get_item(db, &hash_key(DatabaseStorePrefixes::Headers, pruning_point))?;
+    let (mut multiset, utxos) = utxo_set_multiset(db, DatabaseStorePrefixes::PruningUtxoset)?;
+    Ok(Check {
+        name: "pruning-utxoset",
+        description,
+        expected: Some(header.header.utxo_commitment),
+        actual: Some(multiset.finalize()),
+        skipped: None,
+        utxos: Some(utxos),
+    })
+}
+
+fn hash_key(prefix: DatabaseStorePrefixes, hash: Hash) -> Vec<u8> {
+    prefix.into_iter().chain(hash.as_bytes()).collect()
+}
+
+/// Computes the multiset of the UTXO set stored under `prefix`, along with its size
+fn utxo_set_multiset(db: &DB, prefix: DatabaseStorePrefixes) -> DbToolResult<(MuHash, u64)> {
+    let mut multiset = MuHash::new();
+    let mut count = 0;
+    for entry in prefix_iterator(db, vec![prefix as u8]) {
+        let (key, value) = entry?;
+        let outpoint: TransactionOutpoint =
+            UtxoKey::try_from(&key[1..]).map_err(|err| DbToolError::InvalidKey(hex(&key), err.to_string()))?.into();
+        let entry: UtxoEntry = bincode::deserialize(&value)?;
+        multiset.add_utxo(&outpoint, &entry);
+        count += 1;
+    }
+    Ok((multiset, count))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kaspa_consensus::{config::ConfigBuilder, consensus::test_consensus::TestConsensus, params::DEVNET_PARAMS};
+    use kaspa_database::{create_temp_db, prelude::ConnBuilder};
+
+    #[tokio::test]
+    async fn test_verify() {
+        let (_lifetime, db) = create_temp_db!(ConnBuilder::default().with_files_limit(10));
+        let config = ConfigBuilder::new(DEVNET_PARAMS).skip_proof_of_work().build();
+        let (notification_sender, _) = async_channel::unbounded();
+        let tc = TestConsensus::with_db(db.clone(), &config, notification_sender);
+        let wait_handles = tc.init();
+        let mut parent = config.genesis.hash;
+        for i in 1..=5 {
+            let hash = Hash::from_u64_word(i);
+            tc.add_utxo_valid_block_with_parents(hash, vec![parent], vec![]).await.unwrap();
+            parent = hash;
+        }
+        tc.shutdown(wait_handles);
+
+        let checks = verify(&db).unwrap();
+        assert_eq!(
+            checks.iter().map(|check| check.name).collect::<Vec<_>>(),
+            vec!["virtual-utxoset", "utxo-multisets", "pruning-utxoset"]
+        );
+        assert!(checks.iter().all(|check| check.skipped.is_none() && check.passed()));
+        assert!(checks[0].utxos.unwrap() > 0);
+
+        // Corrupt the amount of a single virtual UTXO entry
+        let (key, value) = prefix_iterator(&db, vec![DatabaseStorePrefixes::VirtualUtxoset as u8]).next().unwrap().unwrap();
+        let mut entry: UtxoEntry = bincode::deserialize(&value).unwrap();
+        entry.amount += 1;
+        db.put(key, bincode::serialize(&entry).unwrap()).unwrap();
+
+        let checks = verify(&db).unwrap();
+        let virtual_utxoset = checks.iter().find(|check| check.name == "virtual-utxoset").unwrap();
+        assert!(!virtual_utxoset.passed());
+        assert!(checks.iter().find(|check| check.name == "pruning-utxoset").unwrap().passed());
+    }
+}
@@ -1,6 +1,6 @@
-use rocksdb::{DBWithThreadMode, MultiThreaded};
+use rocksdb::{checkpoint::Checkpoint, DBWithThreadMode, MultiThreaded};
 use std::ops::{Deref, DerefMut};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 pub use conn_builder::ConnBuilder;
 use kaspa_utils::fd_budget::FDGuard;
@@ -17,6 +17,12 @@ impl DB {
     pub fn new(inner: DBWithThreadMode<MultiThreaded>, fd_guard: FDGuard) -> Self {
         Self { inner, _fd_guard: fd_guard }
     }
+
+    /// Creates a consistent point-in-time copy of the DB at `path`, which must not exist. Files are hard-linked when
+    /// `path` is on the same filesystem as the DB, so the checkpoint is cheap and does not block writers.
+    pub fn create_checkpoint(&self, path: impl AsRef<Path>) -> Result<(), rocksdb::Error> {
+        Checkpoint::new(&self.inner)?.create_checkpoint(path)
+    }
 }
 
 impl DerefMut for DB {
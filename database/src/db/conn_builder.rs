@@ -9,6 +9,7 @@ pub struct Unspecified;
 pub struct ConnBuilder<Path, const STATS_ENABLED: bool, StatsPeriod, FDLimit> {
     db_path: Path,
     create_if_missing: bool,
+    read_only: bool,
     parallelism: usize,
     files_limit: FDLimit,
     mem_budget: usize,
@@ -20,6 +21,7 @@ impl Default for ConnBuilder<Unspecified, false, Unspecified, Unspecified> {
         ConnBuilder {
             db_path: Unspecified,
             create_if_missing: true,
+            read_only: false,
             parallelism: 1,
             mem_budget: 64 * 1024 * 1024,
             stats_period: Unspecified,
@@ -34,6 +36,7 @@ impl<Path, const STATS_ENABLED: bool, StatsPeriod, FDLimit> ConnBuilder<Path, ST
             db_path,
             files_limit: self.files_limit,
             create_if_missing: self.create_if_missing,
+            read_only: self.read_only,
             parallelism: self.parallelism,
             mem_budget: self.mem_budget,
             stats_period: self.stats_period,
@@ -42,6 +45,11 @@ impl<Path, const STATS_ENABLED: bool, StatsPeriod, FDLimit> ConnBuilder<Path, ST
     pub fn with_create_if_missing(self, create_if_missing: bool) -> ConnBuilder<Path, STATS_ENABLED, StatsPeriod, FDLimit> {
         ConnBuilder { create_if_missing, ..self }
     }
+    /// Opens the DB in read-only mode, which is allowed even while another process holds it open for writing.
+    /// Note that such a connection sees the DB as of the time it was opened
+    pub fn with_read_only(self, read_only: bool) -> ConnBuilder<Path, STATS_ENABLED, StatsPeriod, FDLimit> {
+        ConnBuilder { read_only, create_if_missing: self.create_if_missing && !read_only, ..self }
+    }
     pub fn with_parallelism(self, parallelism: impl Into<usize>) -> ConnBuilder<Path, STATS_ENABLED, StatsPeriod, FDLimit> {
         ConnBuilder { parallelism: parallelism.into(), ..self }
     }
@@ -53,6 +61,7 @@ impl<Path, const STATS_ENABLED: bool, StatsPeriod, FDLimit> ConnBuilder<Path, ST
             db_path: self.db_path,
             files_limit: files_limit.into(),
             create_if_missing: self.create_if_missing,
+            read_only: self.read_only,
             parallelism: self.parallelism,
             mem_budget: self.mem_budget,
             stats_period: self.stats_period,
@@ -65,6 +74,7 @@ impl<Path, FDLimit> ConnBuilder<Path, false, Unspecified, FDLimit> {
         ConnBuilder {
             db_path: self.db_path,
             create_if_missing: self.create_if_missing,
+            read_only: self.read_only,
             parallelism: self.parallelism,
             files_limit: self.files_limit,
             mem_budget: self.mem_budget,
@@ -78,6 +88,7 @@ impl<Path, StatsPeriod, FDLimit> ConnBuilder<Path, true, StatsPeriod, FDLimit> {
         ConnBuilder {
             db_path: self.db_path,
             create_if_missing: self.create_if_missing,
+            read_only: self.read_only,
             parallelism: self.parallelism,
             files_limit: self.files_limit,
             mem_budget: self.mem_budget,
@@ -88,6 +99,7 @@ impl<Path, StatsPeriod, FDLimit> ConnBuilder<Path, true, StatsPeriod, FDLimit> {
         ConnBuilder {
             db_path: self.db_path,
             create_if_missing: self.create_if_missing,
+            read_only: self.read_only,
             parallelism: self.parallelism,
             files_limit: self.files_limit,
             mem_budget: self.mem_budget,
@@ -111,10 +123,21 @@ macro_rules! default_opts {
     }};
 }
 
+macro_rules! open_db {
+    ($self: expr, $opts: expr) => {{
+        let path = $self.db_path.to_str().unwrap();
+        if $self.read_only {
+            <DBWithThreadMode<MultiThreaded>>::open_for_read_only(&$opts, path, false).unwrap()
+        } else {
+            <DBWithThreadMode<MultiThreaded>>::open(&$opts, path).unwrap()
+        }
+    }};
+}
+
 impl ConnBuilder<PathBuf, false, Unspecified, i32> {
     pub fn build(self) -> Result<Arc<DB>, kaspa_utils::fd_budget::Error> {
         let (opts, guard) = default_opts!(self)?;
-        let db = Arc::new(DB::new(<DBWithThreadMode<MultiThreaded>>::open(&opts, self.db_path.to_str().unwrap()).unwrap(), guard));
+        let db = Arc::new(DB::new(open_db!(self, opts), guard));
         Ok(db)
     }
 }
@@ -123,7 +146,7 @@ impl ConnBuilder<PathBuf, true, Unspecified, i32> {
     pub fn build(self) -> Result<Arc<DB>, kaspa_utils::fd_budget::Error> {
         let (mut opts, guard) = default_opts!(self)?;
         opts.enable_statistics();
-        let db = Arc::new(DB::new(<DBWithThreadMode<MultiThreaded>>::open(&opts, self.db_path.to_str().unwrap()).unwrap(), guard));
+        let db = Arc::new(DB::new(open_db!(self, opts), guard));
         Ok(db)
     }
 }
@@ -134,7 +157,7 @@ impl ConnBuilder<PathBuf, true, u32, i32> {
         opts.enable_statistics();
         opts.set_report_bg_io_stats(true);
         opts.set_stats_dump_period_sec(self.stats_period);
-        let db = Arc::new(DB::new(<DBWithThreadMode<MultiThreaded>>::open(&opts, self.db_path.to_str().unwrap()).unwrap(), guard));
+        let db = Arc::new(DB::new(open_db!(self, opts), guard));
         Ok(db)
     }
 }
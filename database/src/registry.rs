@@ -52,11 +52,23 @@ pub enum DatabaseStorePrefixes {
     // ---- Components ----
     Addresses = 128,
     BannedAddresses = 129,
+    MempoolSnapshot = 130,
+    NamedAddresses = 131,
+    AddressBucketingKey = 132,
+    BannedAddressExpiries = 133,
 
     // ---- Indexes ----
     UtxoIndex = 192,
     UtxoIndexTips = 193,
     CirculatingSupply = 194,
+    TxIndexEntries = 195,
+    TxIndexAcceptingBlocks = 196,
+    TxIndexSink = 197,
+    AddressHistoryEntries = 198,
+    AddressHistoryChainBlocks = 199,
+    AddressHistorySink = 200,
+    AddressHistoryState = 201,
+    TxIndexChainBlocks = 202,
 
     // ---- Separator ----
     /// Reserved as a separator
@@ -0,0 +1,83 @@
+use kaspa_consensus_core::{acceptance_data::AcceptanceData, tx::ScriptPublicKey};
+use kaspa_consensusmanager::spawn_blocking;
+use kaspa_database::prelude::StoreResult;
+use kaspa_hashes::Hash;
+use parking_lot::RwLock;
+use std::{fmt::Debug, sync::Arc};
+
+use crate::{
+    errors::AddressHistoryIndexResult,
+    model::{AddressHistoryPage, AddressHistoryState},
+};
+
+///Address history index API targeted at retrieval calls.
+pub trait AddressHistoryIndexApi: Send + Sync + Debug {
+    /// Retrieve a page of the history of a script public key, starting at `from_daa_score`.
+    ///
+    /// A page holds at least `limit` entries when available and is only cut between distinct
+    /// DAA scores, so resuming from `next_daa_score` never skips or repeats an entry.
+    ///
+    /// Note: Use a read lock when accessing this method
+    fn get_history(&self, script_public_key: &ScriptPublicKey, from_daa_score: u64, limit: usize) -> StoreResult<AddressHistoryPage>;
+
+    /// Retrieve the bookkeeping state of the index (used for testing purposes).
+    ///
+    /// Note: Use a read lock when accessing this method
+    fn get_state(&self) -> StoreResult<AddressHistoryState>;
+
+    /// Checks if the address history index's db is synced with consensus.
+    ///
+    /// Note:
+    /// 1) Use a read lock when accessing this method
+    /// 2) due to potential sync-gaps is_synced is unreliable while consensus is actively resolving virtual states.
+    fn is_synced(&self) -> AddressHistoryIndexResult<bool>;
+
+    /// Update the address history index with the given virtual chain changes, and the acceptance data of the added chain blocks.
+    ///
+    /// Note: Use a write lock when accessing this method
+    fn update(
+        &mut self,
+        added_chain_block_hashes: Arc<Vec<Hash>>,
+        removed_chain_block_hashes: Arc<Vec<Hash>>,
+        added_chain_blocks_acceptance_data: Arc<Vec<Arc<AcceptanceData>>>,
+    ) -> AddressHistoryIndexResult<()>;
+
+    /// Resync the address history index from the consensus db
+    ///
+    /// Note: Use a write lock when accessing this method
+    fn resync(&mut self) -> AddressHistoryIndexResult<()>;
+}
+
+/// Async proxy for the address history index
+#[derive(Debug, Clone)]
+pub struct AddressHistoryIndexProxy {
+    inner: Arc<RwLock<dyn AddressHistoryIndexApi>>,
+}
+
+impl AddressHistoryIndexProxy {
+    pub fn new(inner: Arc<RwLock<dyn AddressHistoryIndexApi>>) -> Self {
+        Self { inner }
+    }
+
+    pub async fn get_history(
+        self,
+        script_public_key: ScriptPublicKey,
+        from_daa_score: u64,
+        limit: usize,
+    ) -> StoreResult<AddressHistoryPage> {
+        spawn_blocking(move || self.inner.read().get_history(&script_public_key, from_daa_score, limit)).await.unwrap()
+    }
+
+    pub async fn update(
+        self,
+        added_chain_block_hashes: Arc<Vec<Hash>>,
+        removed_chain_block_hashes: Arc<Vec<Hash>>,
+        added_chain_blocks_acceptance_data: Arc<Vec<Arc<AcceptanceData>>>,
+    ) -> AddressHistoryIndexResult<()> {
+        spawn_blocking(move || {
+            self.inner.write().update(added_chain_block_hashes, removed_chain_block_hashes, added_chain_blocks_acceptance_data)
+        })
+        .await
+        .unwrap()
+    }
+}
@@ -0,0 +1,29 @@
+use std::io;
+use thiserror::Error;
+
+use crate::IDENT;
+use kaspa_consensus_core::{errors::consensus::ConsensusError, tx::TransactionOutpoint};
+use kaspa_database::prelude::StoreError;
+use kaspa_hashes::Hash;
+
+/// Errors originating from the [`AddressHistoryIndex`](crate::AddressHistoryIndex).
+#[derive(Error, Debug)]
+pub enum AddressHistoryIndexError {
+    #[error("[{IDENT}]: {0}")]
+    StoreAccessError(#[from] StoreError),
+
+    #[error("[{IDENT}]: {0}")]
+    ConsensusQueryError(#[from] ConsensusError),
+
+    #[error("[{IDENT}]: {0}")]
+    DBResetError(#[from] io::Error),
+
+    #[error("[{IDENT}]: expected acceptance data for {0} added chain blocks, got {1}")]
+    MissingAcceptanceData(usize, usize),
+
+    #[error("[{IDENT}]: the UTXO spent from outpoint {0} is missing from the UTXO diff of chain block {1}")]
+    MissingSpentUtxo(TransactionOutpoint, Hash),
+}
+
+/// Results originating from the [`AddressHistoryIndex`](crate::AddressHistoryIndex).
+pub type AddressHistoryIndexResult<T> = Result<T, AddressHistoryIndexError>;
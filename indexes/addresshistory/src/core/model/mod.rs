@@ -0,0 +1,88 @@
+use kaspa_consensus_core::tx::{ScriptPublicKey, TransactionId, TransactionOutpoint};
+use kaspa_hashes::Hash;
+use kaspa_utils::mem_size::MemSizeEstimator;
+use serde::{Deserialize, Serialize};
+
+/// The direction of an [`AddressHistoryEntry`], seen from the script public key it belongs to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[repr(u8)]
+pub enum AddressHistoryDirection {
+    /// An output paying to the script public key entered the UTXO set
+    Received = 0,
+    /// An output paying to the script public key left the UTXO set
+    Spent = 1,
+}
+
+/// A single event in the history of a script public key.
+///
+/// Both directions reference the outpoint that entered or left the UTXO set, and are dated at
+/// the DAA score of the chain block accepting the transaction creating or spending it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AddressHistoryEntry {
+    pub daa_score: u64,
+    pub outpoint: TransactionOutpoint,
+    pub direction: AddressHistoryDirection,
+    pub amount: u64,
+    /// The transaction spending the outpoint, only set for `Spent` entries
+    pub spending_transaction_id: Option<TransactionId>,
+}
+
+impl AddressHistoryEntry {
+    pub fn new_received(daa_score: u64, outpoint: TransactionOutpoint, amount: u64) -> Self {
+        Self { daa_score, outpoint, direction: AddressHistoryDirection::Received, amount, spending_transaction_id: None }
+    }
+
+    pub fn new_spent(daa_score: u64, outpoint: TransactionOutpoint, amount: u64, spending_transaction_id: TransactionId) -> Self {
+        Self {
+            daa_score,
+            outpoint,
+            direction: AddressHistoryDirection::Spent,
+            amount,
+            spending_transaction_id: Some(spending_transaction_id),
+        }
+    }
+}
+
+impl MemSizeEstimator for AddressHistoryEntry {}
+
+/// The history entries recorded for a chain block, kept so they can be reverted if the block
+/// is removed from the selected chain.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AddressHistoryChainBlock {
+    pub daa_score: u64,
+    pub entries: Vec<(ScriptPublicKey, AddressHistoryEntry)>,
+}
+
+impl AddressHistoryChainBlock {
+    pub fn new(daa_score: u64, entries: Vec<(ScriptPublicKey, AddressHistoryEntry)>) -> Self {
+        Self { daa_score, entries }
+    }
+}
+
+impl MemSizeEstimator for AddressHistoryChainBlock {}
+
+/// A page of the history of a script public key, ordered by DAA score.
+#[derive(Clone, Debug, Default)]
+pub struct AddressHistoryPage {
+    pub entries: Vec<AddressHistoryEntry>,
+    /// The DAA score to resume from, if more entries are available
+    pub next_daa_score: Option<u64>,
+    /// History below this DAA score was pruned before the index was (re)built and is only
+    /// represented by the outputs still unspent at that time
+    pub history_start_daa_score: u64,
+}
+
+/// The bookkeeping state of the address history index.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AddressHistoryState {
+    /// The DAA score of the pruning point the index was built from
+    pub history_start_daa_score: u64,
+    /// The last pruning point the reorg bookkeeping was pruned by
+    pub pruning_point: Hash,
+}
+
+impl AddressHistoryState {
+    pub fn new(history_start_daa_score: u64, pruning_point: Hash) -> Self {
+        Self { history_start_daa_score, pruning_point }
+    }
+}
@@ -0,0 +1,415 @@
+use crate::{
+    api::AddressHistoryIndexApi,
+    errors::{AddressHistoryIndexError, AddressHistoryIndexResult},
+    model::{AddressHistoryChainBlock, AddressHistoryEntry, AddressHistoryPage, AddressHistoryState},
+    stores::store_manager::Store,
+    IDENT,
+};
+use kaspa_consensus_core::{
+    acceptance_data::AcceptanceData,
+    api::ConsensusApi,
+    tx::{ScriptPublicKey, Transaction, TransactionOutpoint, UtxoEntry},
+    utxo::{utxo_collection::UtxoCollection, utxo_diff::UtxoDiff},
+};
+use kaspa_consensusmanager::{ConsensusManager, ConsensusResetHandler};
+use kaspa_core::{info, trace};
+use kaspa_database::prelude::{StoreError, StoreResult, DB};
+use kaspa_hashes::Hash;
+use parking_lot::RwLock;
+use std::{
+    fmt::Debug,
+    sync::{Arc, Weak},
+};
+
+const RESYNC_CHUNK_SIZE: usize = 2048;
+
+/// AddressHistoryIndex records every UTXO received by or spent from a [`ScriptPublicKey`], ordered by DAA score,
+/// by following the selected chain through virtual chain changes. Entries are dated at the DAA score of the chain
+/// block accepting the transaction, and spends carry the id of the spending transaction.
+///
+/// History is only kept from the point the index was built: a resync (e.g. following a pruning point UTXO set override)
+/// seeds it with the UTXO set of the sink and records the DAA score of the pruning point as the history start.
+/// Note: The AddressHistoryIndex struct by itself is not thread safe, only correct usage of the supplied RwLock via `new` makes it so.
+/// please follow guidelines found in the comments under `addresshistory::core::api::AddressHistoryIndexApi` for proper thread safety.
+pub struct AddressHistoryIndex {
+    consensus_manager: Arc<ConsensusManager>,
+    store: Store,
+}
+
+impl AddressHistoryIndex {
+    /// Creates a new [`AddressHistoryIndex`] within a [`RwLock`]
+    pub fn new(consensus_manager: Arc<ConsensusManager>, db: Arc<DB>) -> AddressHistoryIndexResult<Arc<RwLock<Self>>> {
+        let mut index = Self { consensus_manager: consensus_manager.clone(), store: Store::new(db) };
+        if !index.is_synced()? {
+            index.resync()?;
+        }
+        let index = Arc::new(RwLock::new(index));
+        consensus_manager
+            .register_consensus_reset_handler(Arc::new(AddressHistoryIndexConsensusResetHandler::new(Arc::downgrade(&index))));
+        Ok(index)
+    }
+
+    /// Collects the entries recorded for an added chain block from the transactions of its mergeset it accepted
+    fn chain_block_entries(
+        consensus: &dyn ConsensusApi,
+        chain_block: Hash,
+        acceptance_data: &AcceptanceData,
+    ) -> AddressHistoryIndexResult<AddressHistoryChainBlock> {
+        let daa_score = consensus.get_header(chain_block)?.daa_score;
+        let utxo_diff = consensus.get_block_utxo_diff(chain_block)?;
+        let mut accepted_transactions = Vec::new();
+        for mergeset_block in acceptance_data.iter() {
+            let transactions = consensus.get_block(mergeset_block.block_hash)?.transactions;
+            accepted_transactions.extend(
+                mergeset_block.accepted_transactions.iter().map(|entry| transactions[entry.index_within_block as usize].clone()),
+            );
+        }
+        collect_chain_block_entries(chain_block, daa_score, &utxo_diff, &accepted_transactions)
+    }
+}
+
+/// Collects the history entries of a chain block with DAA score `daa_score`, whose mergeset UTXO diff is `utxo_diff`,
+/// from the transactions it accepted in acceptance order.
+///
+/// Every output is recorded as received and every input as spent by its transaction. Spent UTXOs are resolved from the
+/// removed side of the UTXO diff or, when created and spent within the same mergeset (in which case they are absent from
+/// the diff), from the outputs of the preceding accepted transactions.
+fn collect_chain_block_entries(
+    chain_block: Hash,
+    daa_score: u64,
+    utxo_diff: &UtxoDiff,
+    accepted_transactions: &[Transaction],
+) -> AddressHistoryIndexResult<AddressHistoryChainBlock> {
+    let mut created = UtxoCollection::new();
+    let mut entries = Vec::new();
+    for transaction in accepted_transactions.iter() {
+        let transaction_id = transaction.id();
+        for input in transaction.inputs.iter() {
+            let outpoint = input.previous_outpoint;
+            let utxo_entry = created
+                .remove(&outpoint)
+                .or_else(|| utxo_diff.remove.get(&outpoint).cloned())
+                .ok_or(AddressHistoryIndexError::MissingSpentUtxo(outpoint, chain_block))?;
+            entries.push((
+                utxo_entry.script_public_key,
+                AddressHistoryEntry::new_spent(daa_score, outpoint, utxo_entry.amount, transaction_id),
+            ));
+        }
+        for (index, output) in transaction.outputs.iter().enumerate() {
+            let outpoint = TransactionOutpoint::new(transaction_id, index as u32);
+            entries.push((output.script_public_key.clone(), AddressHistoryEntry::new_received(daa_score, outpoint, output.value)));
+            created.insert(
+                outpoint,
+                UtxoEntry::new(output.value, output.script_public_key.clone(), daa_score, transaction.is_coinbase()),
+            );
+        }
+    }
+    Ok(AddressHistoryChainBlock::new(daa_score, entries))
+}
+
+impl AddressHistoryIndexApi for AddressHistoryIndex {
+    fn get_history(&self, script_public_key: &ScriptPublicKey, from_daa_score: u64, limit: usize) -> StoreResult<AddressHistoryPage> {
+        trace!("[{0}] retrieving up to {1} history entries from DAA score {2}", IDENT, limit, from_daa_score);
+
+        self.store.get_history(script_public_key, from_daa_score, limit)
+    }
+
+    fn get_state(&self) -> StoreResult<AddressHistoryState> {
+        trace!("[{0}] retrieving state", IDENT);
+
+        self.store.get_state()
+    }
+
+    /// Updates the [AddressHistoryIndex] via the virtual chain changes supplied:
+    /// 1) Reverts the entries recorded by the removed chain blocks.
+    /// 2) Records the entries of the transactions accepted by the added chain blocks and commits the new sink.
+    /// 3) Prunes the reorg bookkeeping if the pruning point moved.
+    fn update(
+        &mut self,
+        added_chain_block_hashes: Arc<Vec<Hash>>,
+        removed_chain_block_hashes: Arc<Vec<Hash>>,
+        added_chain_blocks_acceptance_data: Arc<Vec<Arc<AcceptanceData>>>,
+    ) -> AddressHistoryIndexResult<()> {
+        trace!("[{0}] updating...", IDENT);
+        trace!("[{0}] adding {1} chain blocks", IDENT, added_chain_block_hashes.len());
+        trace!("[{0}] removing {1} chain blocks", IDENT, removed_chain_block_hashes.len());
+
+        if added_chain_blocks_acceptance_data.len() != added_chain_block_hashes.len() {
+            return Err(AddressHistoryIndexError::MissingAcceptanceData(
+                added_chain_block_hashes.len(),
+                added_chain_blocks_acceptance_data.len(),
+            ));
+        }
+
+        // The update is driven by a consensus notification, so we avoid waiting on the consensus session lock
+        let session = self.consensus_manager.consensus().unguarded_session_blocking();
+
+        // A chain change ends with the new sink. When it only removes blocks, i.e. the sink moved back to one of its
+        // chain ancestors, the new sink is the selected parent of the lowest removed block.
+        let sink = match (added_chain_block_hashes.last(), removed_chain_block_hashes.last()) {
+            (Some(&sink), _) => sink,
+            (None, Some(&lowest_removed)) => session.get_ghostdag_data(lowest_removed)?.selected_parent,
+            (None, None) => return Ok(()),
+        };
+
+        let added_chain_blocks = added_chain_block_hashes
+            .iter()
+            .copied()
+            .zip(added_chain_blocks_acceptance_data.iter())
+            .map(|(hash, acceptance_data)| Ok((hash, Self::chain_block_entries(&*session, hash, acceptance_data)?)))
+            .collect::<AddressHistoryIndexResult<Vec<_>>>()?;
+        self.store.apply_chain_changes(&removed_chain_block_hashes, added_chain_blocks.into_iter(), sink)?;
+
+        let pruning_point = session.pruning_point();
+        if self.store.get_state()?.pruning_point != pruning_point {
+            let pruning_point_daa_score = session.get_header(pruning_point)?.daa_score;
+            self.store.prune(pruning_point, pruning_point_daa_score)?;
+        }
+
+        Ok(())
+    }
+
+    /// Checks to see if the [AddressHistoryIndex] is sync'd. This is done via comparing the committed sink with the one of the consensus database.
+    ///
+    /// **Note:** Due to sync gaps between the index and consensus, this function is only reliable while consensus is not processing new blocks.
+    fn is_synced(&self) -> AddressHistoryIndexResult<bool> {
+        trace!("[{0}] checking sync status...", IDENT);
+
+        let consensus = self.consensus_manager.consensus();
+        let session = futures::executor::block_on(consensus.session_blocking());
+
+        match self.store.get_sink() {
+            Ok(sink) => {
+                let res = sink == session.get_sink();
+                trace!("[{0}] sync status is {1}", IDENT, res);
+                Ok(res)
+            }
+            Err(StoreError::KeyNotFound(_)) => {
+                //Means address history sink database is empty i.e. not sync'd.
+                trace!("[{0}] sync status is {1}", IDENT, false);
+                Ok(false)
+            }
+            Err(err) => Err(AddressHistoryIndexError::StoreAccessError(err)),
+        }
+    }
+
+    /// Deletes and reinstates the address history database, seeding it from the UTXO set of the sink in the consensus database.
+    ///
+    /// **Notes:**
+    /// 1) History preceding the resync is lost, only the UTXOs still unspent are recorded as received.
+    /// 2) The UTXO set of the sink is approximated by the virtual UTXO set less the UTXOs created by transactions accepted
+    ///    by virtual alone, which carry a DAA score above the sink. UTXOs spent by such transactions are not seeded, their
+    ///    spend is still recorded once a chain block accepts it.
+    /// 3) resyncing while consensus notifies of virtual chain changes, may result in a corrupted db.
+    fn resync(&mut self) -> AddressHistoryIndexResult<()> {
+        info!("Resyncing the address history index...");
+
+        self.store.delete_all()?;
+        let consensus = self.consensus_manager.consensus();
+        let session = futures::executor::block_on(consensus.session_blocking());
+
+        let sink = session.get_sink();
+        let sink_daa_score = session.get_header(sink)?.daa_score;
+        let pruning_point = session.pruning_point();
+        let pruning_point_daa_score = session.get_header(pruning_point)?.daa_score;
+
+        //Initial batch is without specified seek and none-skipping.
+        let mut virtual_utxo_batch = session.get_virtual_utxos(None, RESYNC_CHUNK_SIZE, false);
+        while !virtual_utxo_batch.is_empty() {
+            trace!("[{0}] resyncing with batch of {1} utxos from consensus db", IDENT, virtual_utxo_batch.len());
+            let next_outpoint_from = (virtual_utxo_batch.len() == RESYNC_CHUNK_SIZE)
+                .then(|| virtual_utxo_batch.last().expect("expected a last outpoint").0);
+            virtual_utxo_batch.retain(|(_, utxo_entry)| utxo_entry.block_daa_score <= sink_daa_score);
+            self.store.insert_received_utxos(&virtual_utxo_batch)?;
+
+            let Some(next_outpoint_from) = next_outpoint_from else {
+                break;
+            };
+            virtual_utxo_batch = session.get_virtual_utxos(Some(next_outpoint_from), RESYNC_CHUNK_SIZE, true);
+        }
+
+        trace!("[{0}] committing history start at DAA score {1}", IDENT, pruning_point_daa_score);
+        self.store.set_resync_state(AddressHistoryState::new(pruning_point_daa_score, pruning_point), sink)?;
+
+        Ok(())
+    }
+}
+
+impl Debug for AddressHistoryIndex {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AddressHistoryIndex").finish()
+    }
+}
+
+struct AddressHistoryIndexConsensusResetHandler {
+    index: Weak<RwLock<AddressHistoryIndex>>,
+}
+
+impl AddressHistoryIndexConsensusResetHandler {
+    fn new(index: Weak<RwLock<AddressHistoryIndex>>) -> Self {
+        Self { index }
+    }
+}
+
+impl ConsensusResetHandler for AddressHistoryIndexConsensusResetHandler {
+    fn handle_consensus_reset(&self) {
+        if let Some(index) = self.index.upgrade() {
+            index.write().resync().unwrap();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::collect_chain_block_entries;
+    use crate::{
+        api::AddressHistoryIndexApi,
+        errors::AddressHistoryIndexError,
+        model::{AddressHistoryDirection, AddressHistoryEntry},
+        AddressHistoryIndex,
+    };
+    use futures::executor::block_on;
+    use kaspa_consensus::{config::Config, consensus::test_consensus::TestConsensus, params::DEVNET_PARAMS};
+    use kaspa_consensus_core::{
+        api::ConsensusApi,
+        constants::TX_VERSION,
+        subnets::SUBNETWORK_ID_NATIVE,
+        tx::{ScriptPublicKey, ScriptVec, Transaction, TransactionInput, TransactionOutpoint, TransactionOutput, UtxoEntry},
+        utxo::{utxo_collection::UtxoCollection, utxo_diff::UtxoDiff},
+    };
+    use kaspa_consensusmanager::ConsensusManager;
+    use kaspa_database::create_temp_db;
+    use kaspa_database::prelude::ConnBuilder;
+    use kaspa_hashes::Hash;
+    use std::sync::Arc;
+
+    fn utxo(script_public_key: &ScriptPublicKey, amount: u64, block_daa_score: u64) -> UtxoEntry {
+        UtxoEntry::new(amount, script_public_key.clone(), block_daa_score, false)
+    }
+
+    fn transaction(inputs: &[TransactionOutpoint], outputs: &[(&ScriptPublicKey, u64)]) -> Transaction {
+        Transaction::new(
+            TX_VERSION,
+            inputs.iter().map(|&outpoint| TransactionInput::new(outpoint, vec![], 0, 0)).collect(),
+            outputs.iter().map(|&(script_public_key, value)| TransactionOutput::new(value, script_public_key.clone())).collect(),
+            0,
+            SUBNETWORK_ID_NATIVE,
+            0,
+            vec![],
+        )
+    }
+
+    #[test]
+    fn test_collect_chain_block_entries() {
+        let chain_block = Hash::from_u64_word(1);
+        let daa_score = 50;
+        let spk_a = ScriptPublicKey::new(0, ScriptVec::from_slice(&[1; 34]));
+        let spk_b = ScriptPublicKey::new(0, ScriptVec::from_slice(&[2; 34]));
+        let outpoint = TransactionOutpoint::new(Hash::from_u64_word(2), 0);
+
+        // `tx_b` spends the output of `tx_a` within the same mergeset, so this output is absent from the UTXO diff
+        let tx_a = transaction(&[outpoint], &[(&spk_b, 60)]);
+        let outpoint_a = TransactionOutpoint::new(tx_a.id(), 0);
+        let tx_b = transaction(&[outpoint_a], &[(&spk_a, 50)]);
+        let outpoint_b = TransactionOutpoint::new(tx_b.id(), 0);
+        let utxo_diff = UtxoDiff::new(
+            UtxoCollection::from_iter([(outpoint_b, utxo(&spk_a, 50, daa_score))]),
+            UtxoCollection::from_iter([(outpoint, utxo(&spk_a, 100, 10))]),
+        );
+
+        let chain_block_entries =
+            collect_chain_block_entries(chain_block, daa_score, &utxo_diff, &[tx_a.clone(), tx_b.clone()]).unwrap();
+        assert_eq!(chain_block_entries.daa_score, daa_score);
+        assert_eq!(
+            chain_block_entries.entries,
+            vec![
+                (spk_a.clone(), AddressHistoryEntry::new_spent(daa_score, outpoint, 100, tx_a.id())),
+                (spk_b.clone(), AddressHistoryEntry::new_received(daa_score, outpoint_a, 60)),
+                (spk_b.clone(), AddressHistoryEntry::new_spent(daa_score, outpoint_a, 60, tx_b.id())),
+                (spk_a.clone(), AddressHistoryEntry::new_received(daa_score, outpoint_b, 50)),
+            ]
+        );
+
+        // A spent UTXO which cannot be resolved means the acceptance data does not match the UTXO diff
+        assert!(matches!(
+            collect_chain_block_entries(chain_block, daa_score, &utxo_diff, &[tx_b]),
+            Err(AddressHistoryIndexError::MissingSpentUtxo(missing, _)) if missing == outpoint_a
+        ));
+    }
+
+    #[test]
+    fn test_address_history_index() {
+        kaspa_core::log::try_init_logger("INFO");
+
+        let (_db_lifetime, db) = create_temp_db!(ConnBuilder::default().with_files_limit(10));
+        let config = Config::new(DEVNET_PARAMS);
+        let tc = Arc::new(TestConsensus::new(&config));
+        tc.init();
+        let consensus_manager = Arc::new(ConsensusManager::from_consensus(tc.consensus_clone()));
+        let index = AddressHistoryIndex::new(consensus_manager, db).unwrap();
+
+        // A fresh index is synced and starts its history at the pruning point.
+        assert!(index.read().is_synced().expect("expected bool"));
+        assert_eq!(index.read().store.get_sink().unwrap(), tc.get_sink());
+        let state = index.read().get_state().unwrap();
+        assert_eq!(state.pruning_point, tc.pruning_point());
+        assert_eq!(state.history_start_daa_score, tc.get_header(tc.pruning_point()).unwrap().daa_score);
+
+        // Pages are never cut between entries sharing a DAA score.
+        let spk_b = ScriptPublicKey::new(0, ScriptVec::from_slice(&[2; 34]));
+        let daa_scores = [1u64, 2, 3, 3, 4, 5];
+        let utxos = daa_scores
+            .iter()
+            .enumerate()
+            .map(|(i, daa_score)| (TransactionOutpoint::new(Hash::from_u64_word(10 + i as u64), 0), utxo(&spk_b, 1, *daa_score)))
+            .collect::<Vec<_>>();
+        index.write().store.insert_received_utxos(&utxos).unwrap();
+
+        let page = index.read().get_history(&spk_b, 0, 3).unwrap();
+        assert_eq!(page.entries.iter().map(|entry| entry.daa_score).collect::<Vec<_>>(), vec![1, 2, 3, 3]);
+        assert_eq!(page.next_daa_score, Some(4));
+        let page = index.read().get_history(&spk_b, page.next_daa_score.unwrap(), 3).unwrap();
+        assert_eq!(page.entries.iter().map(|entry| entry.daa_score).collect::<Vec<_>>(), vec![4, 5]);
+        assert_eq!(page.next_daa_score, None);
+
+        // Follow a chain of blocks, whose coinbase transactions pay the empty script public key of the test miner.
+        let miner_spk = ScriptPublicKey::from_vec(0, vec![]);
+        let blocks = (1..=4).map(Hash::from_u64_word).collect::<Vec<_>>();
+        let mut parent = config.genesis.hash;
+        for &block in blocks.iter() {
+            block_on(tc.add_utxo_valid_block_with_parents(block, vec![parent], vec![])).unwrap();
+            parent = block;
+        }
+        let acceptance_data = blocks.iter().map(|&block| tc.get_block_acceptance_data(block).unwrap()).collect::<Vec<_>>();
+        index.write().update(Arc::new(blocks.clone()), Arc::new(vec![]), Arc::new(acceptance_data)).unwrap();
+        assert!(index.read().is_synced().expect("expected bool"));
+
+        let sink_daa_score = tc.get_header(blocks[3]).unwrap().daa_score;
+        let entries = index.read().get_history(&miner_spk, 0, usize::MAX).unwrap().entries;
+        assert!(entries.iter().all(|entry| entry.direction == AddressHistoryDirection::Received));
+        assert!(entries.iter().any(|entry| entry.daa_score == sink_daa_score));
+
+        // Removing the sink from the chain deletes the outputs it accepted, rather than recording them as spent.
+        index.write().update(Arc::new(vec![]), Arc::new(vec![blocks[3]]), Arc::new(vec![])).unwrap();
+        assert_eq!(index.read().store.get_sink().unwrap(), blocks[2]);
+        assert_eq!(
+            index.read().get_history(&miner_spk, 0, usize::MAX).unwrap().entries,
+            entries.into_iter().filter(|entry| entry.daa_score != sink_daa_score).collect::<Vec<_>>()
+        );
+
+        // Chain changes must come with the acceptance data of the added chain blocks.
+        assert!(matches!(
+            index.write().update(Arc::new(vec![blocks[3]]), Arc::new(vec![]), Arc::new(vec![])),
+            Err(AddressHistoryIndexError::MissingAcceptanceData(1, 0))
+        ));
+
+        // Resync clears the recorded history and seeds it from the UTXO set of the sink.
+        index.write().resync().expect("expected resync");
+        assert!(index.read().get_history(&spk_b, 0, 10).unwrap().entries.is_empty());
+        assert!(index.read().is_synced().expect("expected bool"));
+
+        // Deconstruct
+        drop(index);
+        drop(tc);
+    }
+}
@@ -0,0 +1,74 @@
+use std::sync::Arc;
+
+use kaspa_database::{
+    prelude::{CachePolicy, CachedDbAccess, DbWriter, DirectDbWriter, StoreError, StoreResult, DB},
+    registry::DatabaseStorePrefixes,
+};
+use kaspa_hashes::Hash;
+
+use crate::model::AddressHistoryChainBlock;
+
+/// Reader API for `AddressHistoryChainBlocksStore`.
+pub trait AddressHistoryChainBlocksStoreReader {
+    fn get(&self, hash: Hash) -> StoreResult<Option<AddressHistoryChainBlock>>;
+    fn get_below(&self, daa_score: u64) -> StoreResult<Vec<Hash>>;
+}
+
+pub trait AddressHistoryChainBlocksStore: AddressHistoryChainBlocksStoreReader {
+    fn insert(&mut self, writer: impl DbWriter, hash: Hash, chain_block: AddressHistoryChainBlock) -> StoreResult<()>;
+    fn remove_many(&mut self, writer: impl DbWriter, hashes: &[Hash]) -> StoreResult<()>;
+    fn delete_all(&mut self) -> StoreResult<()>;
+}
+
+/// A DB + cache implementation of `AddressHistoryChainBlocksStore` trait
+#[derive(Clone)]
+pub struct DbAddressHistoryChainBlocksStore {
+    db: Arc<DB>,
+    access: CachedDbAccess<Hash, AddressHistoryChainBlock>,
+}
+
+impl DbAddressHistoryChainBlocksStore {
+    pub fn new(db: Arc<DB>, cache_policy: CachePolicy) -> Self {
+        Self {
+            db: Arc::clone(&db),
+            access: CachedDbAccess::new(db, cache_policy, DatabaseStorePrefixes::AddressHistoryChainBlocks.into()),
+        }
+    }
+}
+
+impl AddressHistoryChainBlocksStoreReader for DbAddressHistoryChainBlocksStore {
+    fn get(&self, hash: Hash) -> StoreResult<Option<AddressHistoryChainBlock>> {
+        match self.access.read(hash) {
+            Ok(chain_block) => Ok(Some(chain_block)),
+            Err(StoreError::KeyNotFound(_)) => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+
+    fn get_below(&self, daa_score: u64) -> StoreResult<Vec<Hash>> {
+        let mut hashes = Vec::new();
+        for item in self.access.iterator() {
+            let (key, chain_block) = item.map_err(|err| StoreError::DataInconsistency(err.to_string()))?;
+            if chain_block.daa_score < daa_score {
+                hashes.push(Hash::try_from_slice(&key).map_err(|err| StoreError::DataInconsistency(err.to_string()))?);
+            }
+        }
+        Ok(hashes)
+    }
+}
+
+impl AddressHistoryChainBlocksStore for DbAddressHistoryChainBlocksStore {
+    fn insert(&mut self, writer: impl DbWriter, hash: Hash, chain_block: AddressHistoryChainBlock) -> StoreResult<()> {
+        self.access.write(writer, hash, chain_block)
+    }
+
+    fn remove_many(&mut self, writer: impl DbWriter, hashes: &[Hash]) -> StoreResult<()> {
+        let mut keys = hashes.iter().copied();
+        self.access.delete_many(writer, &mut keys)
+    }
+
+    /// Removes all entries in the cache and db, besides prefixes themselves.
+    fn delete_all(&mut self) -> StoreResult<()> {
+        self.access.delete_all(DirectDbWriter::new(&self.db))
+    }
+}
@@ -0,0 +1,133 @@
+use std::{fmt::Display, sync::Arc};
+
+use kaspa_consensus_core::tx::{ScriptPublicKey, ScriptPublicKeyVersion, TransactionIndexType};
+use kaspa_database::{
+    prelude::{CachePolicy, CachedDbAccess, DbWriter, DirectDbWriter, StoreError, StoreResult, DB},
+    registry::DatabaseStorePrefixes,
+};
+
+use crate::model::{AddressHistoryEntry, AddressHistoryPage};
+
+pub const VERSION_TYPE_SIZE: usize = size_of::<ScriptPublicKeyVersion>();
+
+/// [`ScriptPublicKeyBucket`].
+/// Consists of 2 bytes of little endian [ScriptPublicKeyVersion] bytes, 8 bytes of little endian script length, followed by the script.
+#[derive(Eq, Hash, PartialEq, Debug, Clone)]
+struct ScriptPublicKeyBucket(Vec<u8>);
+
+impl From<&ScriptPublicKey> for ScriptPublicKeyBucket {
+    fn from(script_public_key: &ScriptPublicKey) -> Self {
+        let mut bytes: Vec<u8> = Vec::with_capacity(VERSION_TYPE_SIZE + size_of::<u64>() + script_public_key.script().len());
+        bytes.extend_from_slice(&script_public_key.version().to_le_bytes());
+        bytes.extend_from_slice(&(script_public_key.script().len() as u64).to_le_bytes());
+        bytes.extend_from_slice(script_public_key.script());
+        Self(bytes)
+    }
+}
+
+impl AsRef<[u8]> for ScriptPublicKeyBucket {
+    fn as_ref(&self) -> &[u8] {
+        self.0.as_slice()
+    }
+}
+
+/// Full [AddressHistoryEntry] access key.
+/// Consists of a [ScriptPublicKeyBucket], 8 bytes of big endian DAA score (so entries iterate in DAA score order),
+/// 32 bytes of transaction id, 4 bytes of little endian output index and a direction byte.
+#[derive(Eq, Hash, PartialEq, Debug, Clone)]
+struct AddressHistoryKey(Arc<Vec<u8>>);
+
+impl AddressHistoryKey {
+    fn new(bucket: &ScriptPublicKeyBucket, entry: &AddressHistoryEntry) -> Self {
+        let mut bytes = Vec::with_capacity(
+            bucket.as_ref().len() + size_of::<u64>() + kaspa_hashes::HASH_SIZE + size_of::<TransactionIndexType>() + 1,
+        );
+        bytes.extend_from_slice(bucket.as_ref());
+        bytes.extend_from_slice(&entry.daa_score.to_be_bytes());
+        bytes.extend_from_slice(&entry.outpoint.transaction_id.as_bytes());
+        bytes.extend_from_slice(&entry.outpoint.index.to_le_bytes());
+        bytes.push(entry.direction as u8);
+        Self(Arc::new(bytes))
+    }
+
+    /// A partial key pointing at the first entry of `bucket` with a DAA score of at least `daa_score`.
+    fn seek(bucket: &ScriptPublicKeyBucket, daa_score: u64) -> Self {
+        let mut bytes = Vec::with_capacity(bucket.as_ref().len() + size_of::<u64>());
+        bytes.extend_from_slice(bucket.as_ref());
+        bytes.extend_from_slice(&daa_score.to_be_bytes());
+        Self(Arc::new(bytes))
+    }
+}
+
+impl Display for AddressHistoryKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl AsRef<[u8]> for AddressHistoryKey {
+    fn as_ref(&self) -> &[u8] {
+        self.0.as_slice()
+    }
+}
+
+/// Reader API for `AddressHistoryEntriesStore`.
+pub trait AddressHistoryEntriesStoreReader {
+    fn get_page(&self, script_public_key: &ScriptPublicKey, from_daa_score: u64, limit: usize) -> StoreResult<AddressHistoryPage>;
+}
+
+pub trait AddressHistoryEntriesStore: AddressHistoryEntriesStoreReader {
+    fn insert(&mut self, writer: impl DbWriter, script_public_key: &ScriptPublicKey, entry: AddressHistoryEntry) -> StoreResult<()>;
+    fn remove(&mut self, writer: impl DbWriter, script_public_key: &ScriptPublicKey, entry: &AddressHistoryEntry) -> StoreResult<()>;
+    fn delete_all(&mut self) -> StoreResult<()>;
+}
+
+/// A DB + cache implementation of `AddressHistoryEntriesStore` trait
+#[derive(Clone)]
+pub struct DbAddressHistoryEntriesStore {
+    db: Arc<DB>,
+    access: CachedDbAccess<AddressHistoryKey, AddressHistoryEntry>,
+}
+
+impl DbAddressHistoryEntriesStore {
+    pub fn new(db: Arc<DB>, cache_policy: CachePolicy) -> Self {
+        Self {
+            db: Arc::clone(&db),
+            access: CachedDbAccess::new(db, cache_policy, DatabaseStorePrefixes::AddressHistoryEntries.into()),
+        }
+    }
+}
+
+impl AddressHistoryEntriesStoreReader for DbAddressHistoryEntriesStore {
+    fn get_page(&self, script_public_key: &ScriptPublicKey, from_daa_score: u64, limit: usize) -> StoreResult<AddressHistoryPage> {
+        let bucket = ScriptPublicKeyBucket::from(script_public_key);
+        let mut page = AddressHistoryPage::default();
+        for item in
+            self.access.seek_iterator(Some(bucket.as_ref()), Some(AddressHistoryKey::seek(&bucket, from_daa_score)), usize::MAX, false)
+        {
+            let (_, entry) = item.map_err(|err| StoreError::DataInconsistency(err.to_string()))?;
+            // Only cut the page between distinct DAA scores, so the next page can safely resume from a DAA score
+            if page.entries.len() >= limit && page.entries.last().is_some_and(|last| last.daa_score != entry.daa_score) {
+                page.next_daa_score = Some(entry.daa_score);
+                break;
+            }
+            page.entries.push(entry);
+        }
+        Ok(page)
+    }
+}
+
+impl AddressHistoryEntriesStore for DbAddressHistoryEntriesStore {
+    fn insert(&mut self, writer: impl DbWriter, script_public_key: &ScriptPublicKey, entry: AddressHistoryEntry) -> StoreResult<()> {
+        self.access.write(writer, AddressHistoryKey::new(&ScriptPublicKeyBucket::from(script_public_key), &entry), entry)
+    }
+
+    fn remove(&mut self, writer: impl DbWriter, script_public_key: &ScriptPublicKey, entry: &AddressHistoryEntry) -> StoreResult<()> {
+        self.access.delete(writer, AddressHistoryKey::new(&ScriptPublicKeyBucket::from(script_public_key), entry))
+    }
+
+    /// Removes all entries in the cache and db, besides prefixes themselves.
+    fn delete_all(&mut self) -> StoreResult<()> {
+        self.access.delete_all(DirectDbWriter::new(&self.db))
+    }
+}
@@ -0,0 +1,5 @@
+mod chain_blocks;
+mod entries;
+mod sink;
+mod state;
+pub mod store_manager;
@@ -0,0 +1,47 @@
+use std::sync::Arc;
+
+use kaspa_database::{
+    prelude::{CachedDbItem, DbWriter, DirectDbWriter, StoreResult, DB},
+    registry::DatabaseStorePrefixes,
+};
+
+use crate::model::AddressHistoryState;
+
+/// Reader API for `AddressHistoryStateStore`.
+pub trait AddressHistoryStateStoreReader {
+    fn get(&self) -> StoreResult<AddressHistoryState>;
+}
+
+pub trait AddressHistoryStateStore: AddressHistoryStateStoreReader {
+    fn set(&mut self, writer: impl DbWriter, state: AddressHistoryState) -> StoreResult<()>;
+    fn remove(&mut self) -> StoreResult<()>;
+}
+
+/// A DB + cache implementation of `AddressHistoryStateStore` trait
+#[derive(Clone)]
+pub struct DbAddressHistoryStateStore {
+    db: Arc<DB>,
+    access: CachedDbItem<AddressHistoryState>,
+}
+
+impl DbAddressHistoryStateStore {
+    pub fn new(db: Arc<DB>) -> Self {
+        Self { db: Arc::clone(&db), access: CachedDbItem::new(db, DatabaseStorePrefixes::AddressHistoryState.into()) }
+    }
+}
+
+impl AddressHistoryStateStoreReader for DbAddressHistoryStateStore {
+    fn get(&self) -> StoreResult<AddressHistoryState> {
+        self.access.read()
+    }
+}
+
+impl AddressHistoryStateStore for DbAddressHistoryStateStore {
+    fn set(&mut self, writer: impl DbWriter, state: AddressHistoryState) -> StoreResult<()> {
+        self.access.write(writer, &state)
+    }
+
+    fn remove(&mut self) -> StoreResult<()> {
+        self.access.remove(DirectDbWriter::new(&self.db))
+    }
+}
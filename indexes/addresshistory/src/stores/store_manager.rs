@@ -0,0 +1,161 @@
+use std::sync::Arc;
+
+use kaspa_consensus_core::tx::{ScriptPublicKey, TransactionOutpoint, UtxoEntry};
+use kaspa_core::trace;
+use kaspa_database::prelude::{BatchDbWriter, CachePolicy, StoreResult, DB};
+use kaspa_hashes::Hash;
+use rocksdb::WriteBatch;
+
+use crate::{
+    model::{AddressHistoryChainBlock, AddressHistoryEntry, AddressHistoryPage, AddressHistoryState},
+    stores::{
+        chain_blocks::{AddressHistoryChainBlocksStore, AddressHistoryChainBlocksStoreReader, DbAddressHistoryChainBlocksStore},
+        entries::{AddressHistoryEntriesStore, AddressHistoryEntriesStoreReader, DbAddressHistoryEntriesStore},
+        sink::{AddressHistorySinkStore, AddressHistorySinkStoreReader, DbAddressHistorySinkStore},
+        state::{AddressHistoryStateStore, AddressHistoryStateStoreReader, DbAddressHistoryStateStore},
+    },
+    IDENT,
+};
+
+const CHAIN_BLOCKS_CACHE_SIZE: usize = 1_000;
+
+#[derive(Clone)]
+pub struct Store {
+    db: Arc<DB>,
+    entries_store: DbAddressHistoryEntriesStore,
+    chain_blocks_store: DbAddressHistoryChainBlocksStore,
+    sink_store: DbAddressHistorySinkStore,
+    state_store: DbAddressHistoryStateStore,
+}
+
+impl Store {
+    pub fn new(db: Arc<DB>) -> Self {
+        Self {
+            db: db.clone(),
+            entries_store: DbAddressHistoryEntriesStore::new(db.clone(), CachePolicy::Empty),
+            chain_blocks_store: DbAddressHistoryChainBlocksStore::new(db.clone(), CachePolicy::Count(CHAIN_BLOCKS_CACHE_SIZE)),
+            sink_store: DbAddressHistorySinkStore::new(db.clone()),
+            state_store: DbAddressHistoryStateStore::new(db),
+        }
+    }
+
+    pub fn get_history(
+        &self,
+        script_public_key: &ScriptPublicKey,
+        from_daa_score: u64,
+        limit: usize,
+    ) -> StoreResult<AddressHistoryPage> {
+        let mut page = self.entries_store.get_page(script_public_key, from_daa_score, limit)?;
+        page.history_start_daa_score = self.state_store.get()?.history_start_daa_score;
+        Ok(page)
+    }
+
+    pub fn get_sink(&self) -> StoreResult<Hash> {
+        self.sink_store.get()
+    }
+
+    pub fn get_state(&self) -> StoreResult<AddressHistoryState> {
+        self.state_store.get()
+    }
+
+    /// Atomically applies virtual chain changes to the address history:
+    /// 1) deletes the entries recorded by the removed chain blocks, so that outputs created by transactions which
+    ///    are no longer accepted leave no trace and reverted spends are no longer reported,
+    /// 2) records the entries of the added chain blocks,
+    /// 3) sets the new sink.
+    ///
+    /// Removed chain blocks with no recorded entries precede the index (e.g. they were accepted before a resync)
+    /// and are skipped.
+    pub fn apply_chain_changes(
+        &mut self,
+        removed_chain_block_hashes: &[Hash],
+        added_chain_blocks: impl Iterator<Item = (Hash, AddressHistoryChainBlock)>,
+        sink: Hash,
+    ) -> StoreResult<()> {
+        let mut batch = WriteBatch::default();
+        let mut writer = BatchDbWriter::new(&mut batch);
+
+        for &removed in removed_chain_block_hashes.iter() {
+            if let Some(chain_block) = self.chain_blocks_store.get(removed)? {
+                trace!("[{0}] reverting {1} entries of chain block {2}", IDENT, chain_block.entries.len(), removed);
+                for (script_public_key, entry) in chain_block.entries.iter() {
+                    self.entries_store.remove(&mut writer, script_public_key, entry)?;
+                }
+            }
+        }
+        self.chain_blocks_store.remove_many(&mut writer, removed_chain_block_hashes)?;
+
+        for (hash, chain_block) in added_chain_blocks {
+            for (script_public_key, entry) in chain_block.entries.iter() {
+                self.entries_store.insert(&mut writer, script_public_key, *entry)?;
+            }
+            self.chain_blocks_store.insert(&mut writer, hash, chain_block)?;
+        }
+
+        self.sink_store.set(&mut writer, sink)?;
+
+        self.db.write(batch)?;
+        Ok(())
+    }
+
+    /// Records a batch of UTXOs as received, used to seed the history from the virtual UTXO set.
+    pub fn insert_received_utxos(&mut self, utxos: &[(TransactionOutpoint, UtxoEntry)]) -> StoreResult<()> {
+        let mut batch = WriteBatch::default();
+        let mut writer = BatchDbWriter::new(&mut batch);
+        for (outpoint, utxo_entry) in utxos.iter() {
+            self.insert_received(&mut writer, outpoint, utxo_entry)?;
+        }
+        self.db.write(batch)?;
+        Ok(())
+    }
+
+    fn insert_received(
+        &mut self,
+        writer: &mut BatchDbWriter<'_>,
+        outpoint: &TransactionOutpoint,
+        utxo_entry: &UtxoEntry,
+    ) -> StoreResult<()> {
+        let entry = AddressHistoryEntry::new_received(utxo_entry.block_daa_score, *outpoint, utxo_entry.amount);
+        self.entries_store.insert(writer, &utxo_entry.script_public_key, entry)
+    }
+
+    /// Drops the reorg bookkeeping of the chain blocks below the pruning point, since they can no longer be removed
+    /// from the selected chain. The history entries themselves are kept.
+    pub fn prune(&mut self, pruning_point: Hash, pruning_point_daa_score: u64) -> StoreResult<()> {
+        let pruned = self.chain_blocks_store.get_below(pruning_point_daa_score)?;
+        trace!("[{0}] pruning the bookkeeping of {1} chain blocks below pruning point {2}", IDENT, pruned.len(), pruning_point);
+
+        let mut batch = WriteBatch::default();
+        let mut writer = BatchDbWriter::new(&mut batch);
+        self.chain_blocks_store.remove_many(&mut writer, &pruned)?;
+        let state = self.state_store.get()?;
+        self.state_store.set(&mut writer, AddressHistoryState::new(state.history_start_daa_score, pruning_point))?;
+        self.db.write(batch)?;
+        Ok(())
+    }
+
+    /// Commits the state and sink of a freshly resynced index.
+    pub fn set_resync_state(&mut self, state: AddressHistoryState, sink: Hash) -> StoreResult<()> {
+        let mut batch = WriteBatch::default();
+        let mut writer = BatchDbWriter::new(&mut batch);
+        self.state_store.set(&mut writer, state)?;
+        self.sink_store.set(&mut writer, sink)?;
+        self.db.write(batch)?;
+        Ok(())
+    }
+
+    /// Resets the address history database:
+    pub fn delete_all(&mut self) -> StoreResult<()> {
+        trace!("[{0}] attempting to clear address history database...", IDENT);
+
+        // Clear all
+        self.sink_store.remove()?;
+        self.state_store.remove()?;
+        self.chain_blocks_store.delete_all()?;
+        self.entries_store.delete_all()?;
+
+        trace!("[{0}] clearing address history database - success!", IDENT);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,45 @@
+use kaspa_consensus_core::tx::TransactionId;
+use kaspa_hashes::Hash;
+use kaspa_utils::mem_size::MemSizeEstimator;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+/// An entry of the transaction index, locating an accepted transaction in the DAG.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TxIndexEntry {
+    /// The merged block whose body contains the transaction
+    pub containing_block_hash: Hash,
+    /// The chain block which accepted the transaction
+    pub accepting_block_hash: Hash,
+    /// The position of the transaction in the body of the containing block
+    pub index_within_block: u32,
+}
+
+impl TxIndexEntry {
+    pub fn new(containing_block_hash: Hash, accepting_block_hash: Hash, index_within_block: u32) -> Self {
+        Self { containing_block_hash, accepting_block_hash, index_within_block }
+    }
+}
+
+impl MemSizeEstimator for TxIndexEntry {}
+
+/// A chain block indexed by the txindex, along with the transactions it accepted so that they can be
+/// reverted if the block is removed from the selected chain.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TxIndexAcceptingBlock {
+    /// The position of the block in the selected chain followed by the txindex
+    pub chain_index: u64,
+    pub transaction_ids: Arc<Vec<TransactionId>>,
+}
+
+impl TxIndexAcceptingBlock {
+    pub fn new(chain_index: u64, transaction_ids: Arc<Vec<TransactionId>>) -> Self {
+        Self { chain_index, transaction_ids }
+    }
+}
+
+impl MemSizeEstimator for TxIndexAcceptingBlock {
+    fn estimate_mem_bytes(&self) -> usize {
+        size_of::<Self>() + self.transaction_ids.len() * size_of::<TransactionId>()
+    }
+}
@@ -0,0 +1,285 @@
+use crate::{
+    api::TxIndexApi,
+    errors::{TxIndexError, TxIndexResult},
+    model::TxIndexEntry,
+    stores::store_manager::Store,
+    IDENT,
+};
+use kaspa_consensus_core::{acceptance_data::AcceptanceData, tx::TransactionId};
+use kaspa_consensusmanager::{ConsensusManager, ConsensusResetHandler};
+use kaspa_core::{info, trace};
+use kaspa_database::prelude::{StoreError, StoreResult, DB};
+use kaspa_hashes::Hash;
+use parking_lot::RwLock;
+use std::{
+    fmt::Debug,
+    iter::empty,
+    sync::{Arc, Weak},
+};
+
+const RESYNC_CHUNK_SIZE: usize = 1024; // Chain blocks per chunk, each carrying the acceptance data of its full mergeset.
+
+/// TxIndex maps accepted [`TransactionId`]s to the block containing them and the chain block accepting them,
+/// commits them to its own store, and follows the selected chain through virtual chain changes.
+/// Note: The TxIndex struct by itself is not thread safe, only correct usage of the supplied RwLock via `new` makes it so.
+/// please follow guidelines found in the comments under `txindex::core::api::TxIndexApi` for proper thread safety.
+pub struct TxIndex {
+    consensus_manager: Arc<ConsensusManager>,
+    store: Store,
+}
+
+impl TxIndex {
+    /// Creates a new [`TxIndex`] within a [`RwLock`]
+    pub fn new(consensus_manager: Arc<ConsensusManager>, db: Arc<DB>) -> TxIndexResult<Arc<RwLock<Self>>> {
+        let mut txindex = Self { consensus_manager: consensus_manager.clone(), store: Store::new(db) };
+        if !txindex.is_synced()? {
+            txindex.resync()?;
+        }
+        let txindex = Arc::new(RwLock::new(txindex));
+        consensus_manager.register_consensus_reset_handler(Arc::new(TxIndexConsensusResetHandler::new(Arc::downgrade(&txindex))));
+        Ok(txindex)
+    }
+}
+
+impl TxIndexApi for TxIndex {
+    fn get_transaction_entry(&self, transaction_id: TransactionId) -> StoreResult<Option<TxIndexEntry>> {
+        trace!("[{0}] retrieving entry of transaction {1}", IDENT, transaction_id);
+
+        self.store.get_entry(transaction_id)
+    }
+
+    fn get_transaction_entries(&self, transaction_ids: &[TransactionId]) -> StoreResult<Vec<Option<TxIndexEntry>>> {
+        trace!("[{0}] retrieving entries of {1} transactions", IDENT, transaction_ids.len());
+
+        self.store.get_entries(transaction_ids)
+    }
+
+    fn get_txindex_sink(&self) -> StoreResult<Hash> {
+        trace!("[{0}] retrieving sink", IDENT);
+
+        self.store.get_sink()
+    }
+
+    /// Updates the [TxIndex] via the virtual chain changes supplied:
+    /// 1) Removes the entries accepted by the removed chain blocks.
+    /// 2) Adds the entries accepted by the added chain blocks and commits the new sink.
+    /// 3) Prunes the reversal data of the chain blocks below the pruning point.
+    fn update(
+        &mut self,
+        added_chain_block_hashes: Arc<Vec<Hash>>,
+        removed_chain_block_hashes: Arc<Vec<Hash>>,
+        added_chain_blocks_acceptance_data: Arc<Vec<Arc<AcceptanceData>>>,
+    ) -> TxIndexResult<()> {
+        trace!("[{0}] updating...", IDENT);
+        trace!("[{0}] adding {1} chain blocks", IDENT, added_chain_block_hashes.len());
+        trace!("[{0}] removing {1} chain blocks", IDENT, removed_chain_block_hashes.len());
+
+        // Acceptance data is expected to be aligned with the added chain blocks. If the notification was stripped
+        // of it (e.g. by a subscription excluding accepted transaction ids) the index cannot be trusted anymore.
+        if added_chain_blocks_acceptance_data.len() != added_chain_block_hashes.len() {
+            return Err(TxIndexError::MissingAcceptanceData(added_chain_block_hashes.len(), added_chain_blocks_acceptance_data.len()));
+        }
+
+        // The update is driven by a consensus notification, so we avoid waiting on the consensus session lock
+        let session = self.consensus_manager.consensus().unguarded_session_blocking();
+
+        // A chain change ends with the new sink. When it only removes blocks, i.e. the sink moved back to one of its
+        // chain ancestors, the new sink is the selected parent of the lowest removed block.
+        let sink = match (added_chain_block_hashes.last(), removed_chain_block_hashes.last()) {
+            (Some(&sink), _) => sink,
+            (None, Some(&lowest_removed)) => session.get_ghostdag_data(lowest_removed)?.selected_parent,
+            (None, None) => return Ok(()),
+        };
+
+        self.store.apply_chain_changes(
+            &removed_chain_block_hashes,
+            added_chain_block_hashes.iter().copied().zip(added_chain_blocks_acceptance_data.iter().cloned()),
+            sink,
+        )?;
+        self.store.prune(session.pruning_point())?;
+
+        Ok(())
+    }
+
+    /// Checks to see if the [TxIndex] is sync'd. This is done via comparing the txindex committed sink with the one of the consensus database.
+    ///
+    /// **Note:** Due to sync gaps between the txindex and consensus, this function is only reliable while consensus is not processing new blocks.
+    fn is_synced(&self) -> TxIndexResult<bool> {
+        trace!("[{0}] checking sync status...", IDENT);
+
+        let consensus = self.consensus_manager.consensus();
+        let session = futures::executor::block_on(consensus.session_blocking());
+
+        match self.store.get_sink() {
+            Ok(txindex_sink) => {
+                let res = txindex_sink == session.get_sink();
+                trace!("[{0}] sync status is {1}", IDENT, res);
+                Ok(res)
+            }
+            Err(StoreError::KeyNotFound(_)) => {
+                //Means txindex sink database is empty i.e. not sync'd.
+                trace!("[{0}] sync status is {1}", IDENT, false);
+                Ok(false)
+            }
+            Err(err) => Err(TxIndexError::StoreAccessError(err)),
+        }
+    }
+
+    /// Deletes and reinstates the txindex database, syncing it from scratch via the consensus database.
+    ///
+    /// **Notes:**
+    /// 1) Only transactions accepted by chain blocks above the current pruning point can be indexed, older data is pruned by consensus.
+    /// 2) resyncing while consensus notifies of virtual chain changes, may result in a corrupted db.
+    fn resync(&mut self) -> TxIndexResult<()> {
+        info!("Resyncing the txindex...");
+
+        self.store.delete_all()?;
+        let consensus = self.consensus_manager.consensus();
+        let session = futures::executor::block_on(consensus.session_blocking());
+
+        let mut low = session.pruning_point();
+        // Make sure the sink is committed even if the chain from the pruning point is empty.
+        self.store.apply_chain_changes(&[], empty(), low)?;
+
+        loop {
+            let chain_path = session.get_virtual_chain_from_block(low, Some(RESYNC_CHUNK_SIZE))?;
+            let Some(chunk_last) = chain_path.added.last().copied() else {
+                break;
+            };
+            trace!("[{0}] resyncing with a batch of {1} chain blocks from consensus db", IDENT, chain_path.added.len());
+
+            let acceptance_data = session.get_blocks_acceptance_data(&chain_path.added, None)?;
+            self.store.apply_chain_changes(&[], chain_path.added.iter().copied().zip(acceptance_data), chunk_last)?;
+
+            if chain_path.added.len() < RESYNC_CHUNK_SIZE {
+                break;
+            }
+            low = chunk_last;
+        }
+
+        Ok(())
+    }
+}
+
+impl Debug for TxIndex {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TxIndex").finish()
+    }
+}
+
+struct TxIndexConsensusResetHandler {
+    txindex: Weak<RwLock<TxIndex>>,
+}
+
+impl TxIndexConsensusResetHandler {
+    fn new(txindex: Weak<RwLock<TxIndex>>) -> Self {
+        Self { txindex }
+    }
+}
+
+impl ConsensusResetHandler for TxIndexConsensusResetHandler {
+    fn handle_consensus_reset(&self) {
+        if let Some(txindex) = self.txindex.upgrade() {
+            txindex.write().resync().unwrap();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{api::TxIndexApi, model::TxIndexEntry, TxIndex};
+    use futures::executor::block_on;
+    use kaspa_consensus::{config::Config, consensus::test_consensus::TestConsensus, params::DEVNET_PARAMS};
+    use kaspa_consensus_core::{
+        acceptance_data::{AcceptedTxEntry, MergesetBlockAcceptanceData},
+        api::ConsensusApi,
+        tx::TransactionId,
+    };
+    use kaspa_consensusmanager::ConsensusManager;
+    use kaspa_database::create_temp_db;
+    use kaspa_database::prelude::ConnBuilder;
+    use kaspa_hashes::Hash;
+    use std::sync::Arc;
+
+    fn acceptance_data(merged_block: Hash, transaction_ids: &[TransactionId]) -> Arc<Vec<MergesetBlockAcceptanceData>> {
+        Arc::new(vec![MergesetBlockAcceptanceData {
+            block_hash: merged_block,
+            accepted_transactions: transaction_ids
+                .iter()
+                .enumerate()
+                .map(|(i, transaction_id)| AcceptedTxEntry { transaction_id: *transaction_id, index_within_block: i as u32 })
+                .collect(),
+        }])
+    }
+
+    #[test]
+    fn test_txindex() {
+        kaspa_core::log::try_init_logger("INFO");
+
+        let (_txindex_db_lifetime, txindex_db) = create_temp_db!(ConnBuilder::default().with_files_limit(10));
+        let config = Config::new(DEVNET_PARAMS);
+        let tc = Arc::new(TestConsensus::new(&config));
+        tc.init();
+        let consensus_manager = Arc::new(ConsensusManager::from_consensus(tc.consensus_clone()));
+        let txindex = TxIndex::new(consensus_manager, txindex_db).unwrap();
+
+        // A fresh index is synced with the genesis sink.
+        assert!(txindex.read().is_synced().expect("expected bool"));
+        assert_eq!(txindex.read().get_txindex_sink().unwrap(), tc.get_sink());
+
+        let (chain_a, chain_b, chain_c) = (Hash::from_u64_word(1), Hash::from_u64_word(2), Hash::from_u64_word(3));
+        let (merged_a, merged_c) = (Hash::from_u64_word(11), Hash::from_u64_word(13));
+        let (tx_1, tx_2, tx_3) =
+            (TransactionId::from_u64_word(101), TransactionId::from_u64_word(102), TransactionId::from_u64_word(103));
+
+        // Add two chain blocks, the first accepting two transactions from a merged block.
+        txindex
+            .write()
+            .update(
+                Arc::new(vec![chain_a, chain_b]),
+                Arc::new(vec![]),
+                Arc::new(vec![acceptance_data(merged_a, &[tx_1, tx_2]), acceptance_data(chain_b, &[])]),
+            )
+            .expect("expected update");
+        assert_eq!(txindex.read().get_transaction_entry(tx_1).unwrap(), Some(TxIndexEntry::new(merged_a, chain_a, 0)));
+        assert_eq!(txindex.read().get_transaction_entry(tx_2).unwrap(), Some(TxIndexEntry::new(merged_a, chain_a, 1)));
+        assert_eq!(txindex.read().get_transaction_entry(tx_3).unwrap(), None);
+        assert_eq!(txindex.read().get_txindex_sink().unwrap(), chain_b);
+
+        // Reorg: remove `chain_a` and `chain_b`, then re-accept `tx_2` alongside `tx_3` in `chain_c`.
+        txindex
+            .write()
+            .update(
+                Arc::new(vec![chain_c]),
+                Arc::new(vec![chain_b, chain_a]),
+                Arc::new(vec![acceptance_data(merged_c, &[tx_3, tx_2])]),
+            )
+            .expect("expected update");
+        assert_eq!(
+            txindex.read().get_transaction_entries(&[tx_1, tx_2, tx_3]).unwrap(),
+            vec![None, Some(TxIndexEntry::new(merged_c, chain_c, 1)), Some(TxIndexEntry::new(merged_c, chain_c, 0))]
+        );
+        assert_eq!(txindex.read().get_txindex_sink().unwrap(), chain_c);
+
+        // Missing acceptance data is rejected.
+        assert!(txindex.write().update(Arc::new(vec![chain_a]), Arc::new(vec![]), Arc::new(vec![])).is_err());
+
+        // Resync from consensus, which only knows about genesis, clears all entries.
+        txindex.write().resync().expect("expected resync");
+        assert_eq!(txindex.read().get_transaction_entries(&[tx_1, tx_2, tx_3]).unwrap(), vec![None, None, None]);
+        assert!(txindex.read().is_synced().expect("expected bool"));
+
+        // A chain change only removing blocks moves the sink back to the selected parent of the lowest removed block.
+        let (block_1, block_2) = (Hash::from_u64_word(21), Hash::from_u64_word(22));
+        block_on(tc.add_block_with_parents(block_1, vec![config.genesis.hash])).unwrap();
+        block_on(tc.add_block_with_parents(block_2, vec![block_1])).unwrap();
+        txindex.write().resync().expect("expected resync");
+        assert_eq!(txindex.read().get_txindex_sink().unwrap(), block_2);
+        txindex.write().update(Arc::new(vec![]), Arc::new(vec![block_2]), Arc::new(vec![])).expect("expected update");
+        assert_eq!(txindex.read().get_txindex_sink().unwrap(), block_1);
+
+        // Deconstruct
+        drop(txindex);
+        drop(tc);
+    }
+}
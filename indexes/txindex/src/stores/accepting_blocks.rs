@@ -0,0 +1,70 @@
+use std::sync::Arc;
+
+use kaspa_database::{
+    prelude::{CachePolicy, CachedDbAccess, DbWriter, DirectDbWriter, StoreError, StoreResult, DB},
+    registry::DatabaseStorePrefixes,
+};
+use kaspa_hashes::Hash;
+
+use crate::model::TxIndexAcceptingBlock;
+
+/// Reader API for `TxIndexAcceptingBlocksStore`.
+///
+/// The store maps every indexed chain block to the ids of the transactions it accepted,
+/// so that entries can be reverted when the block is removed from the selected chain.
+pub trait TxIndexAcceptingBlocksStoreReader {
+    fn get(&self, accepting_block_hash: Hash) -> StoreResult<Option<TxIndexAcceptingBlock>>;
+}
+
+pub trait TxIndexAcceptingBlocksStore: TxIndexAcceptingBlocksStoreReader {
+    fn insert(&mut self, writer: impl DbWriter, accepting_block_hash: Hash, accepting_block: TxIndexAcceptingBlock)
+        -> StoreResult<()>;
+    fn remove(&mut self, writer: impl DbWriter, accepting_block_hash: Hash) -> StoreResult<()>;
+    fn delete_all(&mut self) -> StoreResult<()>;
+}
+
+/// A DB + cache implementation of `TxIndexAcceptingBlocksStore` trait
+#[derive(Clone)]
+pub struct DbTxIndexAcceptingBlocksStore {
+    db: Arc<DB>,
+    access: CachedDbAccess<Hash, TxIndexAcceptingBlock>,
+}
+
+impl DbTxIndexAcceptingBlocksStore {
+    pub fn new(db: Arc<DB>, cache_policy: CachePolicy) -> Self {
+        Self {
+            db: Arc::clone(&db),
+            access: CachedDbAccess::new(db, cache_policy, DatabaseStorePrefixes::TxIndexAcceptingBlocks.into()),
+        }
+    }
+}
+
+impl TxIndexAcceptingBlocksStoreReader for DbTxIndexAcceptingBlocksStore {
+    fn get(&self, accepting_block_hash: Hash) -> StoreResult<Option<TxIndexAcceptingBlock>> {
+        match self.access.read(accepting_block_hash) {
+            Ok(accepting_block) => Ok(Some(accepting_block)),
+            Err(StoreError::KeyNotFound(_)) => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+}
+
+impl TxIndexAcceptingBlocksStore for DbTxIndexAcceptingBlocksStore {
+    fn insert(
+        &mut self,
+        writer: impl DbWriter,
+        accepting_block_hash: Hash,
+        accepting_block: TxIndexAcceptingBlock,
+    ) -> StoreResult<()> {
+        self.access.write(writer, accepting_block_hash, accepting_block)
+    }
+
+    fn remove(&mut self, writer: impl DbWriter, accepting_block_hash: Hash) -> StoreResult<()> {
+        self.access.delete(writer, accepting_block_hash)
+    }
+
+    /// Removes all entries in the cache and db, besides prefixes themselves.
+    fn delete_all(&mut self) -> StoreResult<()> {
+        self.access.delete_all(DirectDbWriter::new(&self.db))
+    }
+}
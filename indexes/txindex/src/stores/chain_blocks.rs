@@ -0,0 +1,81 @@
+use std::sync::Arc;
+
+use kaspa_database::{
+    prelude::{CachePolicy, CachedDbAccess, DbWriter, DirectDbWriter, StoreError, StoreResult, DB},
+    registry::DatabaseStorePrefixes,
+};
+use kaspa_hashes::Hash;
+
+/// A chain index encoded in big-endian, so that the store iterates in chain order
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+struct ChainIndexKey([u8; size_of::<u64>()]);
+
+impl From<u64> for ChainIndexKey {
+    fn from(chain_index: u64) -> Self {
+        Self(chain_index.to_be_bytes())
+    }
+}
+
+impl AsRef<[u8]> for ChainIndexKey {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+/// Reader API for `TxIndexChainBlocksStore`.
+///
+/// The store maps the chain index of every indexed chain block to its hash, allowing to drop the
+/// reversal data of chain blocks which can no longer be removed from the selected chain.
+pub trait TxIndexChainBlocksStoreReader {
+    /// Returns the chain blocks with an index lower than `chain_index`, in chain order
+    fn get_below(&self, chain_index: u64) -> StoreResult<Vec<(u64, Hash)>>;
+}
+
+pub trait TxIndexChainBlocksStore: TxIndexChainBlocksStoreReader {
+    fn insert(&mut self, writer: impl DbWriter, chain_index: u64, hash: Hash) -> StoreResult<()>;
+    fn remove(&mut self, writer: impl DbWriter, chain_index: u64) -> StoreResult<()>;
+    fn delete_all(&mut self) -> StoreResult<()>;
+}
+
+/// A DB + cache implementation of `TxIndexChainBlocksStore` trait
+#[derive(Clone)]
+pub struct DbTxIndexChainBlocksStore {
+    db: Arc<DB>,
+    access: CachedDbAccess<ChainIndexKey, Hash>,
+}
+
+impl DbTxIndexChainBlocksStore {
+    pub fn new(db: Arc<DB>, cache_policy: CachePolicy) -> Self {
+        Self { db: Arc::clone(&db), access: CachedDbAccess::new(db, cache_policy, DatabaseStorePrefixes::TxIndexChainBlocks.into()) }
+    }
+}
+
+impl TxIndexChainBlocksStoreReader for DbTxIndexChainBlocksStore {
+    fn get_below(&self, chain_index: u64) -> StoreResult<Vec<(u64, Hash)>> {
+        let mut chain_blocks = Vec::new();
+        for item in self.access.iterator() {
+            let (key, hash) = item.map_err(|err| StoreError::DataInconsistency(err.to_string()))?;
+            let index = u64::from_be_bytes(key.as_ref().try_into().expect("chain index keys are u64"));
+            if index >= chain_index {
+                break;
+            }
+            chain_blocks.push((index, hash));
+        }
+        Ok(chain_blocks)
+    }
+}
+
+impl TxIndexChainBlocksStore for DbTxIndexChainBlocksStore {
+    fn insert(&mut self, writer: impl DbWriter, chain_index: u64, hash: Hash) -> StoreResult<()> {
+        self.access.write(writer, chain_index.into(), hash)
+    }
+
+    fn remove(&mut self, writer: impl DbWriter, chain_index: u64) -> StoreResult<()> {
+        self.access.delete(writer, chain_index.into())
+    }
+
+    /// Removes all entries in the cache and db, besides prefixes themselves.
+    fn delete_all(&mut self) -> StoreResult<()> {
+        self.access.delete_all(DirectDbWriter::new(&self.db))
+    }
+}
@@ -0,0 +1,74 @@
+use std::sync::Arc;
+
+use kaspa_consensus_core::tx::TransactionId;
+use kaspa_database::{
+    prelude::{CachePolicy, CachedDbAccess, DbWriter, DirectDbWriter, StoreError, StoreResult, DB},
+    registry::DatabaseStorePrefixes,
+};
+
+use crate::model::TxIndexEntry;
+
+/// Reader API for `TxIndexEntriesStore`.
+pub trait TxIndexEntriesStoreReader {
+    fn get(&self, transaction_id: TransactionId) -> StoreResult<Option<TxIndexEntry>>;
+}
+
+pub trait TxIndexEntriesStore: TxIndexEntriesStoreReader {
+    fn insert_many(
+        &mut self,
+        writer: impl DbWriter,
+        entries: &mut (impl Iterator<Item = (TransactionId, TxIndexEntry)> + Clone),
+    ) -> StoreResult<()>;
+    fn remove_many(
+        &mut self,
+        writer: impl DbWriter,
+        transaction_ids: &mut (impl Iterator<Item = TransactionId> + Clone),
+    ) -> StoreResult<()>;
+    fn delete_all(&mut self) -> StoreResult<()>;
+}
+
+/// A DB + cache implementation of `TxIndexEntriesStore` trait
+#[derive(Clone)]
+pub struct DbTxIndexEntriesStore {
+    db: Arc<DB>,
+    access: CachedDbAccess<TransactionId, TxIndexEntry>,
+}
+
+impl DbTxIndexEntriesStore {
+    pub fn new(db: Arc<DB>, cache_policy: CachePolicy) -> Self {
+        Self { db: Arc::clone(&db), access: CachedDbAccess::new(db, cache_policy, DatabaseStorePrefixes::TxIndexEntries.into()) }
+    }
+}
+
+impl TxIndexEntriesStoreReader for DbTxIndexEntriesStore {
+    fn get(&self, transaction_id: TransactionId) -> StoreResult<Option<TxIndexEntry>> {
+        match self.access.read(transaction_id) {
+            Ok(entry) => Ok(Some(entry)),
+            Err(StoreError::KeyNotFound(_)) => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+}
+
+impl TxIndexEntriesStore for DbTxIndexEntriesStore {
+    fn insert_many(
+        &mut self,
+        writer: impl DbWriter,
+        entries: &mut (impl Iterator<Item = (TransactionId, TxIndexEntry)> + Clone),
+    ) -> StoreResult<()> {
+        self.access.write_many(writer, entries)
+    }
+
+    fn remove_many(
+        &mut self,
+        writer: impl DbWriter,
+        transaction_ids: &mut (impl Iterator<Item = TransactionId> + Clone),
+    ) -> StoreResult<()> {
+        self.access.delete_many(writer, transaction_ids)
+    }
+
+    /// Removes all entries in the cache and db, besides prefixes themselves.
+    fn delete_all(&mut self) -> StoreResult<()> {
+        self.access.delete_all(DirectDbWriter::new(&self.db))
+    }
+}
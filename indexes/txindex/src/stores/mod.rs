@@ -0,0 +1,5 @@
+mod accepting_blocks;
+mod chain_blocks;
+mod entries;
+mod sink;
+pub mod store_manager;
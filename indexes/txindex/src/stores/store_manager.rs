@@ -0,0 +1,197 @@
+use std::sync::Arc;
+
+use kaspa_consensus_core::{acceptance_data::AcceptanceData, tx::TransactionId};
+use kaspa_core::trace;
+use kaspa_database::prelude::{BatchDbWriter, CachePolicy, StoreError, StoreResult, DB};
+use kaspa_hashes::Hash;
+use rocksdb::WriteBatch;
+
+use crate::{
+    model::{TxIndexAcceptingBlock, TxIndexEntry},
+    stores::{
+        accepting_blocks::{DbTxIndexAcceptingBlocksStore, TxIndexAcceptingBlocksStore, TxIndexAcceptingBlocksStoreReader},
+        chain_blocks::{DbTxIndexChainBlocksStore, TxIndexChainBlocksStore, TxIndexChainBlocksStoreReader},
+        entries::{DbTxIndexEntriesStore, TxIndexEntriesStore, TxIndexEntriesStoreReader},
+        sink::{DbTxIndexSinkStore, TxIndexSinkStore, TxIndexSinkStoreReader},
+    },
+    IDENT,
+};
+
+const ENTRIES_CACHE_SIZE: usize = 10_000;
+const ACCEPTING_BLOCKS_CACHE_SIZE: usize = 1_000;
+const CHAIN_BLOCKS_CACHE_SIZE: usize = 1_000;
+
+#[derive(Clone)]
+pub struct Store {
+    db: Arc<DB>,
+    entries_store: DbTxIndexEntriesStore,
+    accepting_blocks_store: DbTxIndexAcceptingBlocksStore,
+    chain_blocks_store: DbTxIndexChainBlocksStore,
+    sink_store: DbTxIndexSinkStore,
+}
+
+impl Store {
+    pub fn new(db: Arc<DB>) -> Self {
+        Self {
+            db: db.clone(),
+            entries_store: DbTxIndexEntriesStore::new(db.clone(), CachePolicy::Count(ENTRIES_CACHE_SIZE)),
+            accepting_blocks_store: DbTxIndexAcceptingBlocksStore::new(db.clone(), CachePolicy::Count(ACCEPTING_BLOCKS_CACHE_SIZE)),
+            chain_blocks_store: DbTxIndexChainBlocksStore::new(db.clone(), CachePolicy::Count(CHAIN_BLOCKS_CACHE_SIZE)),
+            sink_store: DbTxIndexSinkStore::new(db),
+        }
+    }
+
+    pub fn get_entry(&self, transaction_id: TransactionId) -> StoreResult<Option<TxIndexEntry>> {
+        self.entries_store.get(transaction_id)
+    }
+
+    pub fn get_entries(&self, transaction_ids: &[TransactionId]) -> StoreResult<Vec<Option<TxIndexEntry>>> {
+        transaction_ids.iter().map(|transaction_id| self.entries_store.get(*transaction_id)).collect()
+    }
+
+    pub fn get_sink(&self) -> StoreResult<Hash> {
+        self.sink_store.get()
+    }
+
+    /// Atomically applies a selected chain change to the txindex:
+    /// 1) reverts the entries accepted by `removed_chain_blocks`, ordered from the previous sink down,
+    /// 2) inserts the entries accepted by `added_chain_blocks`, ordered up to the new sink,
+    /// 3) sets the new txindex sink.
+    pub fn apply_chain_changes(
+        &mut self,
+        removed_chain_blocks: &[Hash],
+        added_chain_blocks: impl Iterator<Item = (Hash, Arc<AcceptanceData>)>,
+        sink: Hash,
+    ) -> StoreResult<()> {
+        // Added chain blocks take the positions of the removed ones, or follow the current sink
+        let mut chain_index = match removed_chain_blocks.last() {
+            Some(&lowest_removed) => self.accepting_blocks_store.get(lowest_removed)?.map(|block| block.chain_index),
+            None => match self.sink_store.get() {
+                Ok(current_sink) => self.accepting_blocks_store.get(current_sink)?.map(|block| block.chain_index + 1),
+                Err(StoreError::KeyNotFound(_)) => None,
+                Err(err) => return Err(err),
+            },
+        }
+        .unwrap_or_default();
+
+        let mut batch = WriteBatch::default();
+        let mut writer = BatchDbWriter::new(&mut batch);
+
+        for removed_chain_block in removed_chain_blocks.iter().copied() {
+            if let Some(accepting_block) = self.accepting_blocks_store.get(removed_chain_block)? {
+                let transaction_ids = &accepting_block.transaction_ids;
+                trace!("[{0}] reverting {1} transactions accepted by {2}", IDENT, transaction_ids.len(), removed_chain_block);
+                self.entries_store.remove_many(&mut writer, &mut transaction_ids.iter().copied())?;
+                self.accepting_blocks_store.remove(&mut writer, removed_chain_block)?;
+                self.chain_blocks_store.remove(&mut writer, accepting_block.chain_index)?;
+            }
+        }
+
+        for (accepting_block_hash, acceptance_data) in added_chain_blocks {
+            let mut to_add = acceptance_data.iter().flat_map(|mergeset_block_data| {
+                mergeset_block_data.accepted_transactions.iter().map(move |accepted_tx| {
+                    (
+                        accepted_tx.transaction_id,
+                        TxIndexEntry::new(mergeset_block_data.block_hash, accepting_block_hash, accepted_tx.index_within_block),
+                    )
+                })
+            });
+            let transaction_ids = Arc::new(to_add.clone().map(|(transaction_id, _)| transaction_id).collect::<Vec<_>>());
+            trace!("[{0}] indexing {1} transactions accepted by {2}", IDENT, transaction_ids.len(), accepting_block_hash);
+            self.entries_store.insert_many(&mut writer, &mut to_add)?;
+            self.accepting_blocks_store.insert(
+                &mut writer,
+                accepting_block_hash,
+                TxIndexAcceptingBlock::new(chain_index, transaction_ids),
+            )?;
+            self.chain_blocks_store.insert(&mut writer, chain_index, accepting_block_hash)?;
+            chain_index += 1;
+        }
+
+        self.sink_store.set(&mut writer, sink)?;
+
+        self.db.write(batch)?;
+        Ok(())
+    }
+
+    /// Drops the reversal data of the chain blocks below `pruning_point`, which can no longer be removed
+    /// from the selected chain. The entries they accepted remain indexed.
+    pub fn prune(&mut self, pruning_point: Hash) -> StoreResult<()> {
+        let Some(pruning_point_block) = self.accepting_blocks_store.get(pruning_point)? else {
+            // The pruning point is at or below the block the txindex was synced from
+            return Ok(());
+        };
+
+        let pruned_chain_blocks = self.chain_blocks_store.get_below(pruning_point_block.chain_index)?;
+        if pruned_chain_blocks.is_empty() {
+            return Ok(());
+        }
+        trace!("[{0}] pruning the reversal data of {1} chain blocks below {2}", IDENT, pruned_chain_blocks.len(), pruning_point);
+
+        let mut batch = WriteBatch::default();
+        let mut writer = BatchDbWriter::new(&mut batch);
+        for (chain_index, hash) in pruned_chain_blocks {
+            self.accepting_blocks_store.remove(&mut writer, hash)?;
+            self.chain_blocks_store.remove(&mut writer, chain_index)?;
+        }
+        self.db.write(batch)?;
+        Ok(())
+    }
+
+    /// Resets the txindex database:
+    pub fn delete_all(&mut self) -> StoreResult<()> {
+        trace!("[{0}] attempting to clear txindex database...", IDENT);
+
+        // Clear all
+        self.sink_store.remove()?;
+        self.accepting_blocks_store.delete_all()?;
+        self.chain_blocks_store.delete_all()?;
+        self.entries_store.delete_all()?;
+
+        trace!("[{0}] clearing txindex database - success!", IDENT);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kaspa_consensus_core::acceptance_data::{AcceptedTxEntry, MergesetBlockAcceptanceData};
+    use kaspa_database::{create_temp_db, prelude::ConnBuilder};
+
+    fn acceptance_data(chain_block: Hash, transaction_id: TransactionId) -> Arc<AcceptanceData> {
+        Arc::new(vec![MergesetBlockAcceptanceData {
+            block_hash: chain_block,
+            accepted_transactions: vec![AcceptedTxEntry { transaction_id, index_within_block: 0 }],
+        }])
+    }
+
+    #[test]
+    fn test_prune_reversal_data() {
+        let (_db_lifetime, db) = create_temp_db!(ConnBuilder::default().with_files_limit(10));
+        let mut store = Store::new(db);
+        let blocks = [Hash::from_u64_word(1), Hash::from_u64_word(2), Hash::from_u64_word(3)];
+        let transactions = [TransactionId::from_u64_word(11), TransactionId::from_u64_word(12), TransactionId::from_u64_word(13)];
+
+        let chain = blocks.iter().zip(transactions.iter()).map(|(&block, &tx)| (block, acceptance_data(block, tx)));
+        store.apply_chain_changes(&[], chain, blocks[2]).unwrap();
+        let chain_indexes =
+            blocks.iter().map(|&block| store.accepting_blocks_store.get(block).unwrap().unwrap().chain_index).collect::<Vec<_>>();
+        assert_eq!(chain_indexes, vec![0, 1, 2]);
+
+        // Pruning drops the reversal data below the pruning point, yet keeps the entries themselves
+        store.prune(blocks[1]).unwrap();
+        assert!(store.accepting_blocks_store.get(blocks[0]).unwrap().is_none());
+        assert!(store.accepting_blocks_store.get(blocks[1]).unwrap().is_some());
+        assert_eq!(store.chain_blocks_store.get_below(u64::MAX).unwrap(), vec![(1, blocks[1]), (2, blocks[2])]);
+        assert!(store.get_entry(transactions[0]).unwrap().is_some());
+
+        // A reorg above the pruning point reuses the chain indexes of the removed blocks
+        let block_4 = Hash::from_u64_word(4);
+        store.apply_chain_changes(&[blocks[2]], [(block_4, acceptance_data(block_4, transactions[2]))].into_iter(), block_4).unwrap();
+        assert_eq!(store.accepting_blocks_store.get(block_4).unwrap().unwrap().chain_index, 2);
+        assert_eq!(store.chain_blocks_store.get_below(u64::MAX).unwrap(), vec![(1, blocks[1]), (2, block_4)]);
+        assert_eq!(store.get_sink().unwrap(), block_4);
+    }
+}
@@ -8,7 +8,12 @@ use kaspa_database::prelude::StoreResult;
 use kaspa_hashes::Hash;
 use kaspa_index_core::indexed_utxos::BalanceByScriptPublicKey;
 use parking_lot::RwLock;
-use std::{collections::HashSet, fmt::Debug, sync::Arc};
+use std::{
+    collections::HashSet,
+    fmt::Debug,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 
 use crate::{
     errors::UtxoIndexResult,
@@ -53,6 +58,11 @@ pub trait UtxoIndexApi: Send + Sync + Debug {
     ///
     /// Note: Use a write lock when accessing this method
     fn resync(&mut self) -> UtxoIndexResult<()>;
+
+    /// Creates a checkpoint of the utxoindex db at `path`.
+    ///
+    /// Note: Use a read lock when accessing this method, so that the checkpoint does not split an update
+    fn create_checkpoint(&self, path: &Path) -> StoreResult<()>;
 }
 
 /// Async proxy for the UTXO index
@@ -81,6 +91,10 @@ impl UtxoIndexProxy {
         spawn_blocking(move || self.inner.read().get_balance_by_script_public_keys(script_public_keys)).await.unwrap()
     }
 
+    pub async fn create_checkpoint(self, path: PathBuf) -> StoreResult<()> {
+        spawn_blocking(move || self.inner.read().create_checkpoint(&path)).await.unwrap()
+    }
+
     pub async fn update(self, utxo_diff: Arc<UtxoDiff>, tips: Arc<Vec<Hash>>) -> UtxoIndexResult<UtxoChanges> {
         spawn_blocking(move || self.inner.write().update(utxo_diff, tips)).await.unwrap()
     }
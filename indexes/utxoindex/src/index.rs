@@ -16,6 +16,7 @@ use kaspa_utils::arc::ArcExtensions;
 use parking_lot::RwLock;
 use std::{
     fmt::Debug,
+    path::Path,
     sync::{Arc, Weak},
 };
 
@@ -186,6 +187,11 @@ impl UtxoIndexApi for UtxoIndex {
     fn get_all_outpoints(&self) -> StoreResult<std::collections::HashSet<kaspa_consensus_core::tx::TransactionOutpoint>> {
         self.store.get_all_outpoints()
     }
+
+    fn create_checkpoint(&self, path: &Path) -> StoreResult<()> {
+        trace!("[{0}] creating a checkpoint at {1}", IDENT, path.display());
+        self.store.create_checkpoint(path)
+    }
 }
 
 impl Debug for UtxoIndex {
@@ -1,4 +1,4 @@
-use std::{collections::HashSet, sync::Arc};
+use std::{collections::HashSet, path::Path, sync::Arc};
 
 use kaspa_consensus_core::{
     tx::{ScriptPublicKeys, TransactionOutpoint},
@@ -20,6 +20,7 @@ use crate::{
 
 #[derive(Clone)]
 pub struct Store {
+    db: Arc<DB>,
     utxoindex_tips_store: DbUtxoIndexTipsStore,
     circulating_supply_store: DbCirculatingSupplyStore,
     utxos_by_script_public_key_store: DbUtxoSetByScriptPublicKeyStore,
@@ -28,6 +29,7 @@ pub struct Store {
 impl Store {
     pub fn new(db: Arc<DB>) -> Self {
         Self {
+            db: db.clone(),
             utxoindex_tips_store: DbUtxoIndexTipsStore::new(db.clone()),
             circulating_supply_store: DbCirculatingSupplyStore::new(db.clone()),
             utxos_by_script_public_key_store: DbUtxoSetByScriptPublicKeyStore::new(db, CachePolicy::Empty),
@@ -103,6 +105,11 @@ impl Store {
         res
     }
 
+    /// Creates a checkpoint of the utxoindex database at `path`
+    pub fn create_checkpoint(&self, path: &Path) -> StoreResult<()> {
+        Ok(self.db.create_checkpoint(path)?)
+    }
+
     /// Resets the utxoindex database:
     pub fn delete_all(&mut self) -> StoreResult<()> {
         // TODO: explore possibility of deleting and replacing whole db, currently there is an issue because of file lock and db being in an arc.
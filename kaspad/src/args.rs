@@ -1,10 +1,13 @@
+use crate::prometheus::DEFAULT_PROMETHEUS_PORT;
 use clap::{arg, Arg, ArgAction, Command};
 use kaspa_consensus_core::{
     config::Config,
     network::{NetworkId, NetworkType},
 };
 use kaspa_core::kaspad_env::version;
+use kaspa_mining::mempool::config::MempoolPolicyUpdate;
 use kaspa_notify::address::tracker::Tracker;
+use kaspa_rpc_core::api::{auth::RpcAuthConfig, limits::RpcRateLimitConfig};
 use kaspa_utils::networking::ContextualNetAddress;
 use kaspa_wrpc_server::address::WrpcNetAddress;
 use serde::Deserialize;
@@ -38,6 +41,11 @@ pub struct Args {
     pub rpclisten_json: Option<WrpcNetAddress>,
     #[serde(rename = "unsaferpc")]
     pub unsafe_rpc: bool,
+    /// PEM certificate chain enabling TLS on all RPC listeners, along with `rpckey`
+    pub rpccert: Option<String>,
+    pub rpckey: Option<String>,
+    /// PEM CA certificates RPC clients must present a certificate from (mutual TLS)
+    pub rpcclientca: Option<String>,
     pub wrpc_verbose: bool,
     #[serde(rename = "loglevel")]
     pub log_level: String,
@@ -53,6 +61,9 @@ pub struct Args {
     #[serde(rename = "uacomment")]
     pub user_agent_comments: Vec<String>,
     pub utxoindex: bool,
+    pub txindex: bool,
+    pub addresshistoryindex: bool,
+    pub persist_mempool: bool,
     pub reset_db: bool,
     #[serde(rename = "outpeers")]
     pub outbound_target: usize,
@@ -73,9 +84,18 @@ pub struct Args {
     pub yes: bool,
     #[serde_as(as = "Option<DisplayFromStr>")]
     pub externalip: Option<ContextualNetAddress>,
+    /// SOCKS5 proxy outbound P2P connections are dialed through
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub proxy: Option<ContextualNetAddress>,
     pub perf_metrics: bool,
     pub perf_metrics_interval_sec: u64,
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub prometheus_listen: Option<ContextualNetAddress>,
     pub block_template_cache_lifetime: Option<u64>,
+    /// Snapshot file the pruning point UTXO set is exported to, after which the node exits
+    pub export_utxo_snapshot: Option<String>,
+    /// Snapshot file the node is bootstrapped from, after which the node exits
+    pub import_utxo_snapshot: Option<String>,
 
     #[cfg(feature = "devnet-prealloc")]
     pub num_prealloc_utxos: Option<u64>,
@@ -90,6 +110,14 @@ pub struct Args {
     #[serde(rename = "nogrpc")]
     pub disable_grpc: bool,
     pub ram_scale: f64,
+    /// Mempool relay policy overrides, only settable through the `[mempool]` section of the config file
+    pub mempool: MempoolPolicyUpdate,
+    /// RPC client credentials and roles, only settable through the `[rpc-auth]` section of the config file.
+    /// When unset, any RPC client may call any method.
+    pub rpc_auth: Option<RpcAuthConfig>,
+    /// Per-connection and per-IP RPC request budgets, only settable through the `[rpc-limits]` section of the config file.
+    /// When unset, RPC requests are not limited.
+    pub rpc_limits: Option<RpcRateLimitConfig>,
 }
 
 impl Default for Args {
@@ -100,8 +128,14 @@ impl Default for Args {
             rpclisten_borsh: None,
             rpclisten_json: None,
             unsafe_rpc: false,
+            rpccert: None,
+            rpckey: None,
+            rpcclientca: None,
             async_threads: num_cpus::get(),
             utxoindex: false,
+            txindex: false,
+            addresshistoryindex: false,
+            persist_mempool: false,
             reset_db: false,
             outbound_target: 8,
             inbound_limit: 128,
@@ -126,8 +160,12 @@ impl Default for Args {
             yes: false,
             perf_metrics: false,
             perf_metrics_interval_sec: 10,
+            prometheus_listen: None,
             externalip: None,
+            proxy: None,
             block_template_cache_lifetime: None,
+            export_utxo_snapshot: None,
+            import_utxo_snapshot: None,
 
             #[cfg(feature = "devnet-prealloc")]
             num_prealloc_utxos: None,
@@ -140,6 +178,9 @@ impl Default for Args {
             disable_dns_seeding: false,
             disable_grpc: false,
             ram_scale: 1.0,
+            mempool: Default::default(),
+            rpc_auth: None,
+            rpc_limits: None,
         }
     }
 }
@@ -147,6 +188,8 @@ impl Default for Args {
 impl Args {
     pub fn apply_to_config(&self, config: &mut Config) {
         config.utxoindex = self.utxoindex;
+        config.txindex = self.txindex;
+        config.addresshistoryindex = self.addresshistoryindex;
         config.disable_upnp = self.disable_upnp;
         config.unsafe_rpc = self.unsafe_rpc;
         config.enable_unsynced_mining = self.enable_unsynced_mining;
@@ -251,6 +294,30 @@ pub fn cli() -> Command {
                 .help("Interface:port to listen for wRPC JSON connections (default port: 18110, testnet: 18210)."),
         )
         .arg(arg!(--unsaferpc "Enable RPC commands which affect the state of the node"))
+        .arg(
+            Arg::new("rpccert")
+                .long("rpccert")
+                .value_name("FILE")
+                .require_equals(true)
+                .requires("rpckey")
+                .help("PEM certificate chain enabling TLS on the gRPC and wRPC listeners (requires --rpckey)."),
+        )
+        .arg(
+            Arg::new("rpckey")
+                .long("rpckey")
+                .value_name("FILE")
+                .require_equals(true)
+                .requires("rpccert")
+                .help("PEM private key of the certificate given by --rpccert."),
+        )
+        .arg(
+            Arg::new("rpcclientca")
+                .long("rpcclientca")
+                .value_name("FILE")
+                .require_equals(true)
+                .requires("rpccert")
+                .help("PEM CA certificates RPC clients must present a certificate signed by (mutual TLS)."),
+        )
         .arg(
             Arg::new("connect-peers")
                 .long("connect")
@@ -311,6 +378,9 @@ pub fn cli() -> Command {
                 .help("Allow mainnet mining (currently enabled by default while the flag is kept for backwards compatibility)"),
         )
         .arg(arg!(--utxoindex "Enable the UTXO index"))
+        .arg(arg!(--txindex "Enable the transaction index, mapping accepted transactions to their containing and accepting blocks"))
+        .arg(arg!(--addresshistoryindex "Enable the address history index, recording every UTXO received by or spent from an address"))
+        .arg(arg!(--"persist-mempool" "Persist the mempool across node restarts: a snapshot is written periodically and on shutdown, and revalidated on startup"))
         .arg(
             Arg::new("max-tracked-addresses")
                 .long("max-tracked-addresses")
@@ -350,6 +420,14 @@ Setting to 0 prevents the preallocation and sets the maximum to {}, leading to 0
                 .value_parser(clap::value_parser!(ContextualNetAddress))
                 .help("Add a socket address(ip:port) to the list of local addresses we claim to listen on to peers"),
         )
+        .arg(
+            Arg::new("proxy")
+                .long("proxy")
+                .value_name("proxy")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(ContextualNetAddress))
+                .help("Connect to peers via a SOCKS5 proxy, enabling connections to onion services (eg. 127.0.0.1:9050). DNS seeding is disabled while a proxy is set"),
+        )
         .arg(arg!(--"perf-metrics" "Enable performance metrics: cpu, memory, disk io usage"))
         .arg(
             Arg::new("perf-metrics-interval-sec")
@@ -358,6 +436,29 @@ Setting to 0 prevents the preallocation and sets the maximum to {}, leading to 0
                 .value_parser(clap::value_parser!(u64))
                 .help("Interval in seconds for performance metrics collection."),
         )
+        .arg(
+            Arg::new("prometheus-listen")
+                .long("prometheus-listen")
+                .value_name("IP[:PORT]")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(ContextualNetAddress))
+                .help(format!("Interface:port serving node metrics to Prometheus at /metrics (default port: {DEFAULT_PROMETHEUS_PORT})")),
+        )
+        .arg(
+            Arg::new("export-utxo-snapshot")
+                .long("export-utxo-snapshot")
+                .value_name("FILE")
+                .require_equals(true)
+                .help("Export the pruning point UTXO set along with the data required for validating it to a snapshot file and exit."),
+        )
+        .arg(
+            Arg::new("import-utxo-snapshot")
+                .long("import-utxo-snapshot")
+                .value_name("FILE")
+                .require_equals(true)
+                .conflicts_with("export-utxo-snapshot")
+                .help("Bootstrap the node from a snapshot file written by --export-utxo-snapshot and exit. The snapshot is fully validated against its header chain and the UTXO commitment of its pruning point."),
+        )
         .arg(arg!(--"disable-upnp" "Disable upnp"))
         .arg(arg!(--"nodnsseed" "Disable DNS seeding for peers"))
         .arg(arg!(--"nogrpc" "Disable gRPC server"))
@@ -417,6 +518,9 @@ impl Args {
             rpclisten_borsh: m.get_one::<WrpcNetAddress>("rpclisten-borsh").cloned().or(defaults.rpclisten_borsh),
             rpclisten_json: m.get_one::<WrpcNetAddress>("rpclisten-json").cloned().or(defaults.rpclisten_json),
             unsafe_rpc: arg_match_unwrap_or::<bool>(&m, "unsaferpc", defaults.unsafe_rpc),
+            rpccert: m.get_one::<String>("rpccert").cloned().or(defaults.rpccert),
+            rpckey: m.get_one::<String>("rpckey").cloned().or(defaults.rpckey),
+            rpcclientca: m.get_one::<String>("rpcclientca").cloned().or(defaults.rpcclientca),
             wrpc_verbose: false,
             log_level: arg_match_unwrap_or::<String>(&m, "log_level", defaults.log_level),
             async_threads: arg_match_unwrap_or::<usize>(&m, "async_threads", defaults.async_threads),
@@ -431,6 +535,9 @@ impl Args {
             enable_unsynced_mining: arg_match_unwrap_or::<bool>(&m, "enable-unsynced-mining", defaults.enable_unsynced_mining),
             enable_mainnet_mining: arg_match_unwrap_or::<bool>(&m, "enable-mainnet-mining", defaults.enable_mainnet_mining),
             utxoindex: arg_match_unwrap_or::<bool>(&m, "utxoindex", defaults.utxoindex),
+            txindex: arg_match_unwrap_or::<bool>(&m, "txindex", defaults.txindex),
+            addresshistoryindex: arg_match_unwrap_or::<bool>(&m, "addresshistoryindex", defaults.addresshistoryindex),
+            persist_mempool: arg_match_unwrap_or::<bool>(&m, "persist-mempool", defaults.persist_mempool),
             testnet: arg_match_unwrap_or::<bool>(&m, "testnet", defaults.testnet),
             testnet_suffix: arg_match_unwrap_or::<u32>(&m, "netsuffix", defaults.testnet_suffix),
             devnet: arg_match_unwrap_or::<bool>(&m, "devnet", defaults.devnet),
@@ -440,14 +547,21 @@ impl Args {
             yes: arg_match_unwrap_or::<bool>(&m, "yes", defaults.yes),
             user_agent_comments: arg_match_many_unwrap_or::<String>(&m, "user_agent_comments", defaults.user_agent_comments),
             externalip: m.get_one::<ContextualNetAddress>("externalip").cloned(),
+            proxy: m.get_one::<ContextualNetAddress>("proxy").cloned().or(defaults.proxy),
             perf_metrics: arg_match_unwrap_or::<bool>(&m, "perf-metrics", defaults.perf_metrics),
             perf_metrics_interval_sec: arg_match_unwrap_or::<u64>(&m, "perf-metrics-interval-sec", defaults.perf_metrics_interval_sec),
+            prometheus_listen: m.get_one::<ContextualNetAddress>("prometheus-listen").cloned().or(defaults.prometheus_listen),
             // Note: currently used programmatically by benchmarks and not exposed to CLI users
             block_template_cache_lifetime: defaults.block_template_cache_lifetime,
+            export_utxo_snapshot: m.get_one::<String>("export-utxo-snapshot").cloned().or(defaults.export_utxo_snapshot),
+            import_utxo_snapshot: m.get_one::<String>("import-utxo-snapshot").cloned().or(defaults.import_utxo_snapshot),
             disable_upnp: arg_match_unwrap_or::<bool>(&m, "disable-upnp", defaults.disable_upnp),
             disable_dns_seeding: arg_match_unwrap_or::<bool>(&m, "nodnsseed", defaults.disable_dns_seeding),
             disable_grpc: arg_match_unwrap_or::<bool>(&m, "nogrpc", defaults.disable_grpc),
             ram_scale: arg_match_unwrap_or::<f64>(&m, "ram-scale", defaults.ram_scale),
+            mempool: defaults.mempool,
+            rpc_auth: defaults.rpc_auth,
+            rpc_limits: defaults.rpc_limits,
 
             #[cfg(feature = "devnet-prealloc")]
             num_prealloc_utxos: m.get_one::<u64>("num-prealloc-utxos").cloned(),
@@ -549,6 +663,8 @@ fn arg_match_many_unwrap_or<T: Clone + Send + Sync + 'static>(m: &clap::ArgMatch
       --maxutxocachesize=                   Max size of loaded UTXO into ram from the disk in bytes (default:
                                             5000000000)
       --utxoindex                           Enable the UTXO index
+      --txindex                             Enable the transaction index
+      --addresshistoryindex                 Enable the address history index
       --archival                            Run as an archival node: don't delete old block data when moving the
                                             pruning point (Warning: heavy disk usage)'
       --protocol-version=                   Use non default p2p protocol version (default: 5)
@@ -14,13 +14,15 @@ use kaspa_database::{
 };
 use kaspa_grpc_server::service::GrpcService;
 use kaspa_notify::{address::tracker::Tracker, subscription::context::SubscriptionContext};
-use kaspa_rpc_service::service::RpcCoreService;
+use kaspa_rpc_core::api::{auth::RpcAuthenticator, limits::RpcRateLimiter, tls::RpcServerTlsConfig};
+use kaspa_rpc_service::{backup::DatabaseLayout, service::RpcCoreService};
 use kaspa_txscript::caches::TxScriptCacheCounters;
 use kaspa_utils::git;
 use kaspa_utils::networking::ContextualNetAddress;
 use kaspa_utils::sysinfo::SystemInfo;
 use kaspa_utils_tower::counters::TowerConnectionCounters;
 
+use kaspa_addresshistory::{api::AddressHistoryIndexProxy, AddressHistoryIndex};
 use kaspa_addressmanager::AddressManager;
 use kaspa_consensus::{consensus::factory::Factory as ConsensusFactory, pipeline::ProcessingCounters};
 use kaspa_consensus::{
@@ -31,13 +33,16 @@ use kaspa_core::task::runtime::AsyncRuntime;
 use kaspa_index_processor::service::IndexService;
 use kaspa_mining::{
     manager::{MiningManager, MiningManagerProxy},
+    mempool::notifier::MempoolNotifier,
     monitor::MiningMonitor,
+    persistence::{DbMempoolSnapshotStore, MempoolPersistence, DEFAULT_SNAPSHOT_INTERVAL},
     MiningCounters,
 };
 use kaspa_p2p_flows::{flow_context::FlowContext, service::P2pService};
 
 use itertools::Itertools;
 use kaspa_perf_monitor::{builder::Builder as PerfMonitorBuilder, counters::CountersSnapshot};
+use kaspa_txindex::{api::TxIndexProxy, TxIndex};
 use kaspa_utxoindex::{api::UtxoIndexProxy, UtxoIndex};
 use kaspa_wrpc_server::service::{Options as WrpcServerOptions, WebSocketCounters as WrpcServerCounters, WrpcEncoding, WrpcService};
 
@@ -50,14 +55,22 @@ pub const DESIRED_DAEMON_SOFT_FD_LIMIT: u64 = 8 * 1024;
 /// this value may impact the database performance).
 pub const MINIMUM_DAEMON_SOFT_FD_LIMIT: u64 = 4 * 1024;
 
-use crate::args::Args;
+use crate::{
+    args::Args,
+    prometheus::{PrometheusService, DEFAULT_PROMETHEUS_PORT},
+    utxo_snapshot::{UtxoSnapshotCommand, UtxoSnapshotService},
+};
 
 const DEFAULT_DATA_DIR: &str = "datadir";
 const CONSENSUS_DB: &str = "consensus";
 const UTXOINDEX_DB: &str = "utxoindex";
+const TXINDEX_DB: &str = "txindex";
+const ADDRESSHISTORY_DB: &str = "addresshistory";
 const META_DB: &str = "meta";
 const META_DB_FILE_LIMIT: i32 = 5;
 const DEFAULT_LOG_DIR: &str = "logs";
+/// The default port of a SOCKS5 proxy, as used by Tor
+const DEFAULT_PROXY_PORT: u16 = 9050;
 
 fn get_home_dir() -> PathBuf {
     #[cfg(target_os = "windows")]
@@ -101,6 +114,9 @@ pub fn validate_args(args: &Args) -> ConfigResult<()> {
     if args.max_tracked_addresses > Tracker::MAX_ADDRESS_UPPER_BOUND {
         return Err(ConfigError::MaxTrackedAddressesTooHigh(Tracker::MAX_ADDRESS_UPPER_BOUND));
     }
+    if args.export_utxo_snapshot.is_some() && args.import_utxo_snapshot.is_some() {
+        return Err(ConfigError::MixedUtxoSnapshotExportAndImport);
+    }
     Ok(())
 }
 
@@ -220,6 +236,20 @@ pub fn create_core_with_runtime(runtime: &Runtime, args: &Args, fd_total_budget:
     } else {
         0
     };
+    let tx_files_limit = if args.txindex {
+        let tx_files_limit = fd_remaining * 10 / 100;
+        fd_remaining -= tx_files_limit;
+        tx_files_limit
+    } else {
+        0
+    };
+    let address_history_files_limit = if args.addresshistoryindex {
+        let address_history_files_limit = fd_remaining * 10 / 100;
+        fd_remaining -= address_history_files_limit;
+        address_history_files_limit
+    } else {
+        0
+    };
     // Make sure args forms a valid set of properties
     if let Err(err) = validate_args(args) {
         println!("{}", err);
@@ -255,6 +285,8 @@ pub fn create_core_with_runtime(runtime: &Runtime, args: &Args, fd_total_budget:
 
     let consensus_db_dir = db_dir.join(CONSENSUS_DB);
     let utxoindex_db_dir = db_dir.join(UTXOINDEX_DB);
+    let txindex_db_dir = db_dir.join(TXINDEX_DB);
+    let addresshistory_db_dir = db_dir.join(ADDRESSHISTORY_DB);
     let meta_db_dir = db_dir.join(META_DB);
 
     let mut is_db_reset_needed = args.reset_db;
@@ -274,6 +306,14 @@ do you confirm? (answer y/n or pass --yes to the Kaspad command line to confirm
         info!("Utxoindex Data directory {}", utxoindex_db_dir.display());
         fs::create_dir_all(utxoindex_db_dir.as_path()).unwrap();
     }
+    if args.txindex {
+        info!("Txindex Data directory {}", txindex_db_dir.display());
+        fs::create_dir_all(txindex_db_dir.as_path()).unwrap();
+    }
+    if args.addresshistoryindex {
+        info!("Address history index Data directory {}", addresshistory_db_dir.display());
+        fs::create_dir_all(addresshistory_db_dir.as_path()).unwrap();
+    }
 
     // DB used for addresses store and for multi-consensus management
     let mut meta_db = kaspa_database::prelude::ConnBuilder::default()
@@ -438,6 +478,12 @@ do you confirm? (answer y/n or pass --yes to the Kaspad command line to confirm
         if args.utxoindex {
             fs::create_dir_all(utxoindex_db_dir.as_path()).unwrap();
         }
+        if args.txindex {
+            fs::create_dir_all(txindex_db_dir.as_path()).unwrap();
+        }
+        if args.addresshistoryindex {
+            fs::create_dir_all(addresshistory_db_dir.as_path()).unwrap();
+        }
 
         // Reopen the DB
         meta_db = kaspa_database::prelude::ConnBuilder::default()
@@ -454,6 +500,7 @@ do you confirm? (answer y/n or pass --yes to the Kaspad command line to confirm
     let connect_peers = args.connect_peers.iter().map(|x| x.normalize(config.default_p2p_port())).collect::<Vec<_>>();
     let add_peers = args.add_peers.iter().map(|x| x.normalize(config.default_p2p_port())).collect();
     let p2p_server_addr = args.listen.unwrap_or(ContextualNetAddress::unspecified()).normalize(config.default_p2p_port());
+    let p2p_proxy = args.proxy.map(|proxy| proxy.normalize(DEFAULT_PROXY_PORT).into());
     // connect_peers means no DNS seeding and no outbound peers
     let outbound_target = if connect_peers.is_empty() { args.outbound_target } else { 0 };
     let dns_seeders = if connect_peers.is_empty() && !args.disable_dns_seeding { config.dns_seeders } else { &[] };
@@ -510,32 +557,72 @@ do you confirm? (answer y/n or pass --yes to the Kaspad command line to confirm
     let system_info = SystemInfo::default();
 
     let notify_service = Arc::new(NotifyService::new(notification_root.clone(), notification_recv, subscription_context.clone()));
-    let index_service: Option<Arc<IndexService>> = if args.utxoindex {
+    let index_service: Option<Arc<IndexService>> = if args.utxoindex || args.txindex || args.addresshistoryindex {
         // Use only a single thread for none-consensus databases
-        let utxoindex_db = kaspa_database::prelude::ConnBuilder::default()
-            .with_db_path(utxoindex_db_dir)
-            .with_files_limit(utxo_files_limit)
-            .build()
-            .unwrap();
-        let utxoindex = UtxoIndexProxy::new(UtxoIndex::new(consensus_manager.clone(), utxoindex_db).unwrap());
-        let index_service = Arc::new(IndexService::new(&notify_service.notifier(), subscription_context.clone(), Some(utxoindex)));
+        let utxoindex = args.utxoindex.then(|| {
+            let utxoindex_db = kaspa_database::prelude::ConnBuilder::default()
+                .with_db_path(utxoindex_db_dir)
+                .with_files_limit(utxo_files_limit)
+                .build()
+                .unwrap();
+            UtxoIndexProxy::new(UtxoIndex::new(consensus_manager.clone(), utxoindex_db).unwrap())
+        });
+        let txindex = args.txindex.then(|| {
+            let txindex_db = kaspa_database::prelude::ConnBuilder::default()
+                .with_db_path(txindex_db_dir)
+                .with_files_limit(tx_files_limit)
+                .build()
+                .unwrap();
+            TxIndexProxy::new(TxIndex::new(consensus_manager.clone(), txindex_db).unwrap())
+        });
+        let addresshistory = args.addresshistoryindex.then(|| {
+            let addresshistory_db = kaspa_database::prelude::ConnBuilder::default()
+                .with_db_path(addresshistory_db_dir)
+                .with_files_limit(address_history_files_limit)
+                .build()
+                .unwrap();
+            AddressHistoryIndexProxy::new(AddressHistoryIndex::new(consensus_manager.clone(), addresshistory_db).unwrap())
+        });
+        let index_service =
+            Arc::new(IndexService::new(&notify_service.notifier(), subscription_context.clone(), utxoindex, txindex, addresshistory));
         Some(index_service)
     } else {
         None
     };
 
+    let mempool_snapshot_store = args.persist_mempool.then(|| DbMempoolSnapshotStore::new(meta_db.clone()));
     let (address_manager, port_mapping_extender_svc) = AddressManager::new(config.clone(), meta_db, tick_service.clone());
 
-    let mining_manager = MiningManagerProxy::new(Arc::new(MiningManager::new_with_extended_config(
+    let mining_manager = Arc::new(MiningManager::new_with_extended_config(
         config.target_time_per_block,
         false,
         config.max_block_mass,
         config.ram_scale,
         config.block_template_cache_lifetime,
         mining_counters.clone(),
-    )));
-    let mining_monitor =
-        Arc::new(MiningMonitor::new(mining_manager.clone(), mining_counters, tx_script_cache_counters.clone(), tick_service.clone()));
+        Some(MempoolNotifier::new(notification_root.clone(), config.prefix())),
+    ));
+    // Apply the [mempool] section of the config file, if any
+    if let Err(err) = mining_manager.update_policy(&args.mempool) {
+        println!("{}", err);
+        exit(1);
+    }
+    let mining_manager = MiningManagerProxy::new(mining_manager);
+    let mining_monitor = Arc::new(MiningMonitor::new(
+        mining_manager.clone(),
+        mining_counters.clone(),
+        tx_script_cache_counters.clone(),
+        tick_service.clone(),
+    ));
+    let mempool_persistence = mempool_snapshot_store.map(|store| {
+        Arc::new(MempoolPersistence::new(
+            mining_manager.clone(),
+            consensus_manager.clone(),
+            store,
+            DEFAULT_SNAPSHOT_INTERVAL,
+            tick_service.clone(),
+        ))
+    });
 
     let flow_context = Arc::new(FlowContext::new(
         consensus_manager.clone(),
@@ -555,26 +642,66 @@ do you confirm? (answer y/n or pass --yes to the Kaspad command line to confirm
         dns_seeders,
         config.default_p2p_port(),
         p2p_tower_counters.clone(),
+        p2p_proxy,
     ));
 
+    // Backups mirror the location of the databases within the application directory
+    let data_dir = PathBuf::from(network.to_prefixed()).join(DEFAULT_DATA_DIR);
+    let database_layout = DatabaseLayout {
+        consensus_db_dir: data_dir.join(CONSENSUS_DB),
+        meta_db_dir: data_dir.join(META_DB),
+        utxoindex_db_dir: data_dir.join(UTXOINDEX_DB),
+    };
     let rpc_core_service = Arc::new(RpcCoreService::new(
         consensus_manager.clone(),
         notify_service.notifier(),
-        index_service.as_ref().map(|x| x.notifier()),
+        // UtxosChanged notifications are only produced by the index processor when the utxoindex is enabled
+        index_service.as_ref().filter(|x| x.utxoindex().is_some()).map(|x| x.notifier()),
         mining_manager,
         flow_context,
         subscription_context,
-        index_service.as_ref().map(|x| x.utxoindex().unwrap()),
+        index_service.as_ref().and_then(|x| x.utxoindex()),
+        index_service.as_ref().and_then(|x| x.txindex()),
+        index_service.as_ref().and_then(|x| x.addresshistory()),
         config.clone(),
         core.clone(),
-        processing_counters,
+        processing_counters.clone(),
         wrpc_borsh_counters.clone(),
         wrpc_json_counters.clone(),
         perf_monitor.clone(),
         p2p_tower_counters.clone(),
         grpc_tower_counters.clone(),
         system_info,
+        args.rpc_limits.clone().filter(|limits| !limits.is_empty()),
+        database_layout,
     ));
+    // Apply the [rpc-auth] section of the config file, if any
+    let rpc_authenticator = match args.rpc_auth.as_ref().map(RpcAuthenticator::new).transpose() {
+        Ok(authenticator) => authenticator.map(Arc::new),
+        Err(err) => {
+            println!("Invalid [rpc-auth] config: {}", err);
+            exit(1);
+        }
+    };
+    // Apply the [rpc-limits] section of the config file, if any. All the RPC servers share the per-IP budgets.
+    let rpc_rate_limiter = match args.rpc_limits.clone().filter(|limits| !limits.is_empty()).map(RpcRateLimiter::new).transpose() {
+        Ok(rate_limiter) => rate_limiter.map(Arc::new),
+        Err(err) => {
+            println!("Invalid [rpc-limits] config: {}", err);
+            exit(1);
+        }
+    };
+    // Load the TLS material of the RPC listeners, if any
+    let rpc_tls = match args.rpccert.as_ref().zip(args.rpckey.as_ref()) {
+        Some((cert, key)) => match RpcServerTlsConfig::load(cert, key, args.rpcclientca.as_ref()) {
+            Ok(tls) => Some(tls),
+            Err(err) => {
+                println!("Invalid RPC TLS config: {}", err);
+                exit(1);
+            }
+        },
+        None => None,
+    };
     let grpc_service_broadcasters: usize = 3; // TODO: add a command line argument or derive from other arg/config/host-related fields
     let grpc_service = if !args.disable_grpc {
         Some(Arc::new(GrpcService::new(
@@ -584,11 +711,30 @@ do you confirm? (answer y/n or pass --yes to the Kaspad command line to confirm
             args.rpc_max_clients,
             grpc_service_broadcasters,
             grpc_tower_counters,
+            rpc_authenticator.clone(),
+            rpc_rate_limiter.clone(),
+            rpc_tls.clone(),
         )))
     } else {
         None
     };
 
+    // A UTXO snapshot export or import runs with consensus only and shuts the node down once done
+    let utxo_snapshot_command = match (&args.export_utxo_snapshot, &args.import_utxo_snapshot) {
+        (Some(path), _) => Some(UtxoSnapshotCommand::Export(PathBuf::from(path))),
+        (_, Some(path)) => Some(UtxoSnapshotCommand::Import(PathBuf::from(path))),
+        _ => None,
+    };
+    if let Some(command) = utxo_snapshot_command {
+        let async_runtime = Arc::new(AsyncRuntime::new(args.async_threads));
+        async_runtime.register(tick_service);
+        async_runtime.register(notify_service);
+        async_runtime.register(Arc::new(UtxoSnapshotService::new(command, consensus_manager.clone(), core.clone())));
+        core.bind(consensus_manager);
+        core.bind(async_runtime);
+        return (core, rpc_core_service);
+    }
+
     // Create an async runtime and register the top-level async services
     let async_runtime = Arc::new(AsyncRuntime::new(args.async_threads));
     async_runtime.register(tick_service);
@@ -606,7 +752,19 @@ do you confirm? (answer y/n or pass --yes to the Kaspad command line to confirm
     async_runtime.register(p2p_service);
     async_runtime.register(consensus_monitor);
     async_runtime.register(mining_monitor);
+    if let Some(mempool_persistence) = mempool_persistence {
+        async_runtime.register(mempool_persistence)
+    }
     async_runtime.register(perf_monitor);
+    if let Some(prometheus_listen) = args.prometheus_listen {
+        async_runtime.register(Arc::new(PrometheusService::new(
+            prometheus_listen.normalize(DEFAULT_PROMETHEUS_PORT),
+            rpc_core_service.clone(),
+            processing_counters,
+            mining_counters,
+            tx_script_cache_counters,
+        )));
+    }
     let wrpc_service_tasks: usize = 2; // num_cpus::get() / 2;
                                        // Register wRPC servers based on command line arguments
     [
@@ -624,6 +782,9 @@ do you confirm? (answer y/n or pass --yes to the Kaspad command line to confirm
                 WrpcServerOptions {
                     listen_address: listen_address.to_address(&network.network_type, &encoding).to_string(), // TODO: use a normalized ContextualNetAddress instead of a String
                     verbose: args.wrpc_verbose,
+                    authenticator: rpc_authenticator.clone(),
+                    rate_limiter: rpc_rate_limiter.clone(),
+                    tls: rpc_tls.clone(),
                     ..WrpcServerOptions::default()
                 },
             ))
@@ -1,2 +1,4 @@
 pub mod args;
 pub mod daemon;
+pub mod prometheus;
+pub mod utxo_snapshot;
@@ -0,0 +1,235 @@
+//!
+//! An HTTP endpoint exposing the node metrics to Prometheus scrapers at `/metrics`.
+//!
+//! Each scrape samples the [`RpcApi::get_metrics`] data of the node, along with the consensus
+//! processing, mempool and transaction script cache counters. Counters are exposed raw, leaving
+//! rates to PromQL `rate()`, so that concurrent scrapers do not interfere with each other.
+//!
+
+use kaspa_consensus_core::api::counters::ProcessingCounters;
+use kaspa_core::{
+    debug, info,
+    task::service::{AsyncService, AsyncServiceError, AsyncServiceFuture},
+    trace, warn,
+};
+use kaspa_metrics_core::{
+    prometheus::{PrometheusEncoder, PrometheusMetricType::*, PROMETHEUS_CONTENT_TYPE},
+    MetricsData,
+};
+use kaspa_mining::MiningCounters;
+use kaspa_rpc_core::api::rpc::RpcApi;
+use kaspa_rpc_service::service::RpcCoreService;
+use kaspa_txscript::caches::TxScriptCacheCounters;
+use kaspa_utils::{networking::NetAddress, triggers::SingleTrigger};
+use std::{sync::Arc, time::Duration};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{TcpListener, TcpStream},
+    sync::Semaphore,
+    time::timeout,
+};
+
+const PROMETHEUS: &str = "prometheus-service";
+
+/// Default port of the endpoint when `--prometheus-listen` only specifies an IP
+pub const DEFAULT_PROMETHEUS_PORT: u16 = 9110;
+
+const MAX_REQUEST_HEADER_SIZE: usize = 8 * 1024;
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The maximal number of scrape connections served at once, further connections are closed on accept
+const MAX_CONCURRENT_REQUESTS: usize = 16;
+
+/// Delay before accepting again after an accept error, such as running out of file descriptors
+const ACCEPT_ERROR_BACKOFF: Duration = Duration::from_millis(500);
+
+pub struct PrometheusService {
+    listen_address: NetAddress,
+    rpc_core_service: Arc<RpcCoreService>,
+    processing_counters: Arc<ProcessingCounters>,
+    mining_counters: Arc<MiningCounters>,
+    tx_script_cache_counters: Arc<TxScriptCacheCounters>,
+    shutdown: SingleTrigger,
+}
+
+impl PrometheusService {
+    pub fn new(
+        listen_address: NetAddress,
+        rpc_core_service: Arc<RpcCoreService>,
+        processing_counters: Arc<ProcessingCounters>,
+        mining_counters: Arc<MiningCounters>,
+        tx_script_cache_counters: Arc<TxScriptCacheCounters>,
+    ) -> Self {
+        Self {
+            listen_address,
+            rpc_core_service,
+            processing_counters,
+            mining_counters,
+            tx_script_cache_counters,
+            shutdown: Default::default(),
+        }
+    }
+
+    async fn render(&self) -> String {
+        let mut encoder = PrometheusEncoder::new();
+
+        match self.rpc_core_service.get_metrics(true, true, true, true, true, false).await {
+            Ok(response) => match MetricsData::try_from(response) {
+                Ok(data) => {
+                    encoder.data(&data);
+                }
+                Err(err) => warn!("{} unable to convert node metrics: {}", PROMETHEUS, err),
+            },
+            Err(err) => warn!("{} unable to sample node metrics: {}", PROMETHEUS, err),
+        }
+
+        let consensus = self.processing_counters.snapshot();
+        encoder
+            .metric("consensus_blocks_submitted", Counter, "Blocks submitted to consensus", consensus.blocks_submitted as f64)
+            .metric("consensus_headers_processed", Counter, "Headers processed by consensus", consensus.header_counts as f64)
+            .metric("consensus_dependencies_processed", Counter, "Header parents processed by consensus", consensus.dep_counts as f64)
+            .metric(
+                "consensus_mergeset_processed",
+                Counter,
+                "Mergeset blocks processed by consensus",
+                consensus.mergeset_counts as f64,
+            )
+            .metric("consensus_bodies_processed", Counter, "Block bodies processed by consensus", consensus.body_counts as f64)
+            .metric("consensus_transactions_processed", Counter, "Transactions processed by consensus", consensus.txs_counts as f64)
+            .metric(
+                "consensus_chain_blocks_processed",
+                Counter,
+                "Chain blocks processed by consensus",
+                consensus.chain_block_counts as f64,
+            )
+            .metric(
+                "consensus_chain_blocks_disqualified",
+                Counter,
+                "Chain blocks disqualified by consensus",
+                consensus.chain_disqualified_counts as f64,
+            )
+            .metric("consensus_mass_processed", Counter, "Block mass processed by consensus", consensus.mass_counts as f64);
+
+        let mempool = self.mining_counters.snapshot();
+        encoder
+            .metric(
+                "mempool_high_priority_txs",
+                Counter,
+                "High priority transactions submitted",
+                mempool.high_priority_tx_counts as f64,
+            )
+            .metric("mempool_low_priority_txs", Counter, "Low priority transactions relayed", mempool.low_priority_tx_counts as f64)
+            .metric("mempool_block_txs", Counter, "Transactions included in block templates", mempool.block_tx_counts as f64)
+            .metric("mempool_txs_accepted", Counter, "Transactions accepted by the DAG", mempool.tx_accepted_counts as f64)
+            .metric("mempool_txs_evicted", Counter, "Transactions evicted from the mempool", mempool.tx_evicted_counts as f64)
+            .metric("mempool_inputs", Counter, "Inputs of the transactions entering the mempool", mempool.input_counts as f64)
+            .metric("mempool_outputs", Counter, "Outputs of the transactions entering the mempool", mempool.output_counts as f64)
+            .metric("mempool_ready_txs", Gauge, "Ready transactions in the mempool", mempool.ready_txs_sample as f64)
+            .metric("mempool_txs", Gauge, "Transactions in the mempool", mempool.txs_sample as f64)
+            .metric("mempool_orphans", Gauge, "Orphan transactions in the mempool", mempool.orphans_sample as f64)
+            .metric("mempool_accepted", Gauge, "Accepted transactions tracked by the mempool", mempool.accepted_sample as f64);
+
+        let tx_script_cache = self.tx_script_cache_counters.snapshot();
+        encoder
+            .metric("txscript_cache_inserts", Counter, "Transaction script cache inserts", tx_script_cache.insert_counts as f64)
+            .metric("txscript_cache_hits", Counter, "Transaction script cache hits", tx_script_cache.get_counts as f64)
+            .metric("txscript_cache_hit_ratio", Gauge, "Transaction script cache hits per insert", tx_script_cache.hit_ratio());
+
+        encoder.finish()
+    }
+
+    async fn handle(self: Arc<Self>, mut stream: TcpStream) -> std::io::Result<()> {
+        // Read the request header, only the request line matters
+        let mut request = Vec::with_capacity(1024);
+        let mut buffer = [0u8; 1024];
+        while !request.windows(4).any(|window| window == b"\r\n\r\n") {
+            if request.len() >= MAX_REQUEST_HEADER_SIZE {
+                return Self::respond(&mut stream, "431 Request Header Fields Too Large", "text/plain", "").await;
+            }
+            match stream.read(&mut buffer).await? {
+                0 => return Ok(()),
+                n => request.extend_from_slice(&buffer[..n]),
+            }
+        }
+        let request = String::from_utf8_lossy(&request);
+        let mut request_line = request.lines().next().unwrap_or_default().split_whitespace();
+        match (request_line.next(), request_line.next()) {
+            (Some("GET"), Some("/metrics")) => {
+                let body = self.render().await;
+                Self::respond(&mut stream, "200 OK", PROMETHEUS_CONTENT_TYPE, &body).await
+            }
+            (Some("GET"), _) => Self::respond(&mut stream, "404 Not Found", "text/plain", "").await,
+            _ => Self::respond(&mut stream, "405 Method Not Allowed", "text/plain", "").await,
+        }
+    }
+
+    async fn respond(stream: &mut TcpStream, status: &str, content_type: &str, body: &str) -> std::io::Result<()> {
+        let header = format!(
+            "HTTP/1.1 {status}\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+            body.len()
+        );
+        stream.write_all(header.as_bytes()).await?;
+        stream.write_all(body.as_bytes()).await?;
+        stream.shutdown().await
+    }
+}
+
+impl AsyncService for PrometheusService {
+    fn ident(self: Arc<Self>) -> &'static str {
+        PROMETHEUS
+    }
+
+    fn start(self: Arc<Self>) -> AsyncServiceFuture {
+        trace!("{} starting", PROMETHEUS);
+        let shutdown_signal = self.shutdown.listener.clone();
+
+        Box::pin(async move {
+            let listener = TcpListener::bind(self.listen_address.to_string())
+                .await
+                .map_err(|err| AsyncServiceError::Service(format!("Prometheus bind error on {}: {}", self.listen_address, err)))?;
+            info!("Prometheus metrics served on: http://{}/metrics", self.listen_address);
+
+            let requests = Arc::new(Semaphore::new(MAX_CONCURRENT_REQUESTS));
+            tokio::pin!(shutdown_signal);
+            loop {
+                tokio::select! {
+                    _ = &mut shutdown_signal => break,
+                    accepted = listener.accept() => match accepted {
+                        Ok((stream, peer)) => {
+                            let Ok(permit) = requests.clone().try_acquire_owned() else {
+                                debug!("{} request from {} dropped, too many concurrent requests", PROMETHEUS, peer);
+                                continue;
+                            };
+                            let service = self.clone();
+                            tokio::spawn(async move {
+                                match timeout(REQUEST_TIMEOUT, service.handle(stream)).await {
+                                    Ok(Ok(())) => {}
+                                    Ok(Err(err)) => debug!("{} request from {} failed: {}", PROMETHEUS, peer, err),
+                                    Err(_) => debug!("{} request from {} timed out", PROMETHEUS, peer),
+                                }
+                                drop(permit);
+                            });
+                        }
+                        Err(err) => {
+                            warn!("{} accept error: {}", PROMETHEUS, err);
+                            tokio::time::sleep(ACCEPT_ERROR_BACKOFF).await;
+                        }
+                    }
+                }
+            }
+            Ok(())
+        })
+    }
+
+    fn signal_exit(self: Arc<Self>) {
+        trace!("sending an exit signal to {}", PROMETHEUS);
+        self.shutdown.trigger.trigger();
+    }
+
+    fn stop(self: Arc<Self>) -> AsyncServiceFuture {
+        Box::pin(async move {
+            trace!("{} stopped", PROMETHEUS);
+            Ok(())
+        })
+    }
+}
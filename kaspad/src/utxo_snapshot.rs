@@ -0,0 +1,81 @@
+//!
+//! A one-shot service exporting the pruning point UTXO set to a snapshot file, or bootstrapping
+//! the node from one, after which the node is shut down.
+//!
+//! The service runs once consensus is up and the node is otherwise offline, i.e. neither P2P nor
+//! RPC services are started alongside it.
+//!
+
+use kaspa_consensusmanager::ConsensusManager;
+use kaspa_core::{
+    core::Core,
+    info,
+    signals::Shutdown,
+    task::service::{AsyncService, AsyncServiceError, AsyncServiceFuture},
+    trace,
+};
+use std::{path::PathBuf, sync::Arc};
+
+const UTXO_SNAPSHOT: &str = "utxo-snapshot-service";
+
+pub enum UtxoSnapshotCommand {
+    Export(PathBuf),
+    Import(PathBuf),
+}
+
+pub struct UtxoSnapshotService {
+    command: UtxoSnapshotCommand,
+    consensus_manager: Arc<ConsensusManager>,
+    core: Arc<Core>,
+}
+
+impl UtxoSnapshotService {
+    pub fn new(command: UtxoSnapshotCommand, consensus_manager: Arc<ConsensusManager>, core: Arc<Core>) -> Self {
+        Self { command, consensus_manager, core }
+    }
+}
+
+impl AsyncService for UtxoSnapshotService {
+    fn ident(self: Arc<Self>) -> &'static str {
+        UTXO_SNAPSHOT
+    }
+
+    fn start(self: Arc<Self>) -> AsyncServiceFuture {
+        trace!("{} starting", UTXO_SNAPSHOT);
+
+        Box::pin(async move {
+            match &self.command {
+                UtxoSnapshotCommand::Export(path) => {
+                    info!("Exporting the pruning point UTXO set to {}", path.display());
+                    self.consensus_manager
+                        .export_utxo_snapshot(path)
+                        .await
+                        .map_err(|err| AsyncServiceError::Service(format!("UTXO snapshot export failed: {err}")))?;
+                    info!("UTXO snapshot exported to {}", path.display());
+                }
+                UtxoSnapshotCommand::Import(path) => {
+                    info!("Importing the UTXO snapshot {}", path.display());
+                    self.consensus_manager
+                        .import_utxo_snapshot(path)
+                        .await
+                        .map_err(|err| AsyncServiceError::Service(format!("UTXO snapshot import failed: {err}")))?;
+                    info!("UTXO snapshot imported from {}", path.display());
+                }
+            }
+            // A failure is returned as a service error, which shuts the core down as well
+            self.core.shutdown();
+            Ok(())
+        })
+    }
+
+    fn signal_exit(self: Arc<Self>) {
+        trace!("sending an exit signal to {}", UTXO_SNAPSHOT);
+    }
+
+    fn stop(self: Arc<Self>) -> AsyncServiceFuture {
+        Box::pin(async move {
+            trace!("{} stopped", UTXO_SNAPSHOT);
+            Ok(())
+        })
+    }
+}
@@ -72,6 +72,8 @@ impl MetricGroup {
                 Metric::NodeP2pBytesTxPerSecond,
                 Metric::NodeP2pBytesRx,
                 Metric::NodeP2pBytesRxPerSecond,
+                Metric::NodeP2pTxAnnouncementBytesTx,
+                Metric::NodeP2pTxFloodBytesTx,
                 Metric::NodeGrpcUserBytesTx,
                 Metric::NodeGrpcUserBytesTxPerSecond,
                 Metric::NodeGrpcUserBytesRx,
@@ -144,6 +146,8 @@ impl From<Metric> for MetricGroup {
             | Metric::NodeJsonBytesRx
             | Metric::NodeP2pBytesTx
             | Metric::NodeP2pBytesRx
+            | Metric::NodeP2pTxAnnouncementBytesTx
+            | Metric::NodeP2pTxFloodBytesTx
             | Metric::NodeGrpcUserBytesTx
             | Metric::NodeGrpcUserBytesRx
             | Metric::NodeTotalBytesRx
@@ -214,6 +218,9 @@ pub enum Metric {
     NodeP2pBytesRx,
     NodeP2pBytesTxPerSecond,
     NodeP2pBytesRxPerSecond,
+    // Bytes of the transaction announcements, versus what flooding them to all peers would have cost
+    NodeP2pTxAnnouncementBytesTx,
+    NodeP2pTxFloodBytesTx,
 
     NodeBorshBytesTx,
     NodeBorshBytesRx,
@@ -321,6 +328,8 @@ impl Metric {
             Metric::NodeJsonBytesRx => as_data_size(f, si),
             Metric::NodeP2pBytesTx => as_data_size(f, si),
             Metric::NodeP2pBytesRx => as_data_size(f, si),
+            Metric::NodeP2pTxAnnouncementBytesTx => as_data_size(f, si),
+            Metric::NodeP2pTxFloodBytesTx => as_data_size(f, si),
             Metric::NodeGrpcUserBytesTx => as_data_size(f, si),
             Metric::NodeGrpcUserBytesRx => as_data_size(f, si),
             Metric::NodeTotalBytesTx => as_data_size(f, si),
@@ -385,6 +394,8 @@ impl Metric {
             Metric::NodeJsonBytesRx => ("wRPC JSON Rx", "Json Rx"),
             Metric::NodeP2pBytesTx => ("p2p Tx", "p2p Tx"),
             Metric::NodeP2pBytesRx => ("p2p Rx", "p2p Rx"),
+            Metric::NodeP2pTxAnnouncementBytesTx => ("p2p Tx Announcements", "p2p Tx Ann"),
+            Metric::NodeP2pTxFloodBytesTx => ("p2p Tx Announcements (flooded)", "p2p Tx Flood"),
             Metric::NodeGrpcUserBytesTx => ("gRPC Tx", "gRPC Tx"),
             Metric::NodeGrpcUserBytesRx => ("gRPC Rx", "gRPC Rx"),
             Metric::NodeTotalBytesTx => ("Total Tx", "Total Tx"),
@@ -454,6 +465,8 @@ pub struct MetricsData {
     pub node_json_bytes_rx: u64,
     pub node_p2p_bytes_tx: u64,
     pub node_p2p_bytes_rx: u64,
+    pub node_p2p_tx_announcement_bytes_tx: u64,
+    pub node_p2p_tx_flood_bytes_tx: u64,
     pub node_grpc_user_bytes_tx: u64,
     pub node_grpc_user_bytes_rx: u64,
     pub node_total_bytes_tx: u64,
@@ -537,6 +550,8 @@ impl TryFrom<GetMetricsResponse> for MetricsData {
             node_json_bytes_rx: bandwidth_metrics.json_bytes_rx,
             node_p2p_bytes_tx: bandwidth_metrics.p2p_bytes_tx,
             node_p2p_bytes_rx: bandwidth_metrics.p2p_bytes_rx,
+            node_p2p_tx_announcement_bytes_tx: bandwidth_metrics.p2p_tx_announcement_bytes_tx,
+            node_p2p_tx_flood_bytes_tx: bandwidth_metrics.p2p_tx_flood_bytes_tx,
             node_grpc_user_bytes_tx: bandwidth_metrics.grpc_bytes_tx,
             node_grpc_user_bytes_rx: bandwidth_metrics.grpc_bytes_rx,
 
@@ -597,6 +612,8 @@ pub struct MetricsSnapshot {
     pub node_json_bytes_rx: f64,
     pub node_p2p_bytes_tx: f64,
     pub node_p2p_bytes_rx: f64,
+    pub node_p2p_tx_announcement_bytes_tx: f64,
+    pub node_p2p_tx_flood_bytes_tx: f64,
     pub node_grpc_user_bytes_tx: f64,
     pub node_grpc_user_bytes_rx: f64,
     pub node_total_bytes_tx: f64,
@@ -663,6 +680,8 @@ impl MetricsSnapshot {
             Metric::NodeJsonBytesRx => self.node_json_bytes_rx,
             Metric::NodeP2pBytesTx => self.node_p2p_bytes_tx,
             Metric::NodeP2pBytesRx => self.node_p2p_bytes_rx,
+            Metric::NodeP2pTxAnnouncementBytesTx => self.node_p2p_tx_announcement_bytes_tx,
+            Metric::NodeP2pTxFloodBytesTx => self.node_p2p_tx_flood_bytes_tx,
             Metric::NodeGrpcUserBytesTx => self.node_grpc_user_bytes_tx,
             Metric::NodeGrpcUserBytesRx => self.node_grpc_user_bytes_rx,
             Metric::NodeTotalBytesTx => self.node_total_bytes_tx,
@@ -760,6 +779,8 @@ impl From<(&MetricsData, &MetricsData)> for MetricsSnapshot {
             node_json_bytes_rx: b.node_json_bytes_rx as f64,
             node_p2p_bytes_tx: b.node_p2p_bytes_tx as f64,
             node_p2p_bytes_rx: b.node_p2p_bytes_rx as f64,
+            node_p2p_tx_announcement_bytes_tx: b.node_p2p_tx_announcement_bytes_tx as f64,
+            node_p2p_tx_flood_bytes_tx: b.node_p2p_tx_flood_bytes_tx as f64,
             node_grpc_user_bytes_tx: b.node_grpc_user_bytes_tx as f64,
             node_grpc_user_bytes_rx: b.node_grpc_user_bytes_rx as f64,
             node_total_bytes_tx: b.node_total_bytes_tx as f64,
@@ -0,0 +1,182 @@
+//!
+//! Rendering of node metrics in the Prometheus text exposition format.
+//!
+//! All names are prefixed with `kaspa_`. Monotonic values are exposed as counters
+//! (suffixed with `_total`), everything else as gauges. Rates are not exposed, being
+//! computed by the scraper with PromQL `rate()` over the matching counters.
+//!
+
+use crate::data::{Metric, MetricGroup, MetricsData, MetricsSnapshot};
+use std::fmt::Write;
+
+/// Content type of the rendered exposition
+pub const PROMETHEUS_CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+const PREFIX: &str = "kaspa";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PrometheusMetricType {
+    Counter,
+    Gauge,
+}
+
+impl PrometheusMetricType {
+    fn as_str(&self) -> &'static str {
+        match self {
+            PrometheusMetricType::Counter => "counter",
+            PrometheusMetricType::Gauge => "gauge",
+        }
+    }
+}
+
+impl Metric {
+    pub fn prometheus_type(&self) -> PrometheusMetricType {
+        match self {
+            Metric::NodeDiskIoReadBytes
+            | Metric::NodeDiskIoWriteBytes
+            | Metric::NodeBorshConnectionAttempts
+            | Metric::NodeBorshHandshakeFailures
+            | Metric::NodeJsonConnectionAttempts
+            | Metric::NodeJsonHandshakeFailures
+            | Metric::NodeTotalBytesTx
+            | Metric::NodeTotalBytesRx
+            | Metric::NodeP2pBytesTx
+            | Metric::NodeP2pBytesRx
+            | Metric::NodeP2pTxAnnouncementBytesTx
+            | Metric::NodeP2pTxFloodBytesTx
+            | Metric::NodeBorshBytesTx
+            | Metric::NodeBorshBytesRx
+            | Metric::NodeGrpcUserBytesTx
+            | Metric::NodeGrpcUserBytesRx
+            | Metric::NodeJsonBytesTx
+            | Metric::NodeJsonBytesRx
+            | Metric::NodeBlocksSubmittedCount
+            | Metric::NodeHeadersProcessedCount
+            | Metric::NodeDependenciesProcessedCount
+            | Metric::NodeBodiesProcessedCount
+            | Metric::NodeTransactionsProcessedCount
+            | Metric::NodeChainBlocksProcessedCount
+            | Metric::NodeMassProcessedCount => PrometheusMetricType::Counter,
+            _ => PrometheusMetricType::Gauge,
+        }
+    }
+
+    /// Whether the metric is a rate the [`MetricsSnapshot`] computes between two samples
+    pub fn is_sampled_rate(&self) -> bool {
+        matches!(
+            self,
+            Metric::NodeTotalBytesTxPerSecond
+                | Metric::NodeTotalBytesRxPerSecond
+                | Metric::NodeP2pBytesTxPerSecond
+                | Metric::NodeP2pBytesRxPerSecond
+                | Metric::NodeBorshBytesTxPerSecond
+                | Metric::NodeBorshBytesRxPerSecond
+                | Metric::NodeGrpcUserBytesTxPerSecond
+                | Metric::NodeGrpcUserBytesRxPerSecond
+                | Metric::NodeJsonBytesTxPerSecond
+                | Metric::NodeJsonBytesRxPerSecond
+                | Metric::NetworkTransactionsPerSecond
+        )
+    }
+
+    /// The metric name, e.g. `kaspa_node_cpu_usage` for [`Metric::NodeCpuUsage`]
+    pub fn prometheus_name(&self) -> String {
+        let mut name = String::from(PREFIX);
+        for c in self.as_str().chars() {
+            if c.is_ascii_uppercase() {
+                name.push('_');
+            }
+            name.push(c.to_ascii_lowercase());
+        }
+        name
+    }
+}
+
+/// Accumulates metric families into a Prometheus text exposition
+#[derive(Default)]
+pub struct PrometheusEncoder {
+    buffer: String,
+}
+
+impl PrometheusEncoder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends a single sample family. `name` gets prefixed with `kaspa_`, and suffixed with `_total` for counters.
+    pub fn metric(&mut self, name: &str, kind: PrometheusMetricType, help: &str, value: f64) -> &mut Self {
+        let name = match kind {
+            PrometheusMetricType::Counter => format!("{PREFIX}_{name}_total"),
+            PrometheusMetricType::Gauge => format!("{PREFIX}_{name}"),
+        };
+        self.write(&name, kind, help, value);
+        self
+    }
+
+    /// Appends every [`Metric`] sampled in `data`, except for the rates computed between two samples
+    pub fn data(&mut self, data: &MetricsData) -> &mut Self {
+        // Only the raw values of a single sample snapshot are meaningful
+        let snapshot = MetricsSnapshot::from((data, data));
+        for group in MetricGroup::iter() {
+            for metric in group.metrics().filter(|metric| !metric.is_sampled_rate()) {
+                let kind = metric.prometheus_type();
+                let name = match kind {
+                    PrometheusMetricType::Counter => format!("{}_total", metric.prometheus_name()),
+                    PrometheusMetricType::Gauge => metric.prometheus_name(),
+                };
+                self.write(&name, kind, metric.title().0, snapshot.get(metric));
+            }
+        }
+        self
+    }
+
+    pub fn finish(self) -> String {
+        self.buffer
+    }
+
+    fn write(&mut self, name: &str, kind: PrometheusMetricType, help: &str, value: f64) {
+        // Writing to a String never fails
+        let _ = writeln!(self.buffer, "# HELP {name} {help}");
+        let _ = writeln!(self.buffer, "# TYPE {name} {}", kind.as_str());
+        let _ = match value {
+            v if v.is_nan() => writeln!(self.buffer, "{name} NaN"),
+            v if v == f64::INFINITY => writeln!(self.buffer, "{name} +Inf"),
+            v if v == f64::NEG_INFINITY => writeln!(self.buffer, "{name} -Inf"),
+            v => writeln!(self.buffer, "{name} {v}"),
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_metric_names() {
+        assert_eq!(Metric::NodeCpuUsage.prometheus_name(), "kaspa_node_cpu_usage");
+        assert_eq!(Metric::NodeP2pBytesTx.prometheus_name(), "kaspa_node_p2p_bytes_tx");
+        assert_eq!(Metric::NetworkVirtualDaaScore.prometheus_name(), "kaspa_network_virtual_daa_score");
+    }
+
+    #[test]
+    fn test_encoder() {
+        let mut encoder = PrometheusEncoder::new();
+        encoder.metric("txscript_cache_hit_ratio", PrometheusMetricType::Gauge, "Script cache hit ratio", 0.5);
+        encoder.metric("mempool_tx_accepted", PrometheusMetricType::Counter, "Accepted transactions", f64::NAN);
+        encoder.data(&MetricsData { node_active_peers: 8, node_p2p_bytes_rx: 1024, node_cpu_cores: 4, ..Default::default() });
+        let text = encoder.finish();
+
+        assert!(text.starts_with(
+            "# HELP kaspa_txscript_cache_hit_ratio Script cache hit ratio\n\
+             # TYPE kaspa_txscript_cache_hit_ratio gauge\n\
+             kaspa_txscript_cache_hit_ratio 0.5\n"
+        ));
+        assert!(text.contains("# TYPE kaspa_mempool_tx_accepted_total counter\nkaspa_mempool_tx_accepted_total NaN\n"));
+        assert!(text.contains("\nkaspa_node_active_peers 8\n"));
+        assert!(text.contains("# TYPE kaspa_node_p2p_bytes_rx_total counter\nkaspa_node_p2p_bytes_rx_total 1024\n"));
+        assert!(!text.contains("per_second"));
+        let families =
+            MetricGroup::iter().map(|group| group.metrics().filter(|metric| !metric.is_sampled_rate()).count()).sum::<usize>() + 2;
+        assert_eq!(text.lines().filter(|line| line.starts_with("# TYPE")).count(), families);
+    }
+}
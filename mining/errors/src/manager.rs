@@ -10,6 +10,9 @@ pub enum MiningManagerError {
     /// A mempool rule error
     #[error(transparent)]
     MempoolError(#[from] RuleError),
+
+    #[error("invalid mempool policy: {0}")]
+    InvalidMempoolPolicy(String),
 }
 
 pub type MiningManagerResult<T> = std::result::Result<T, MiningManagerError>;
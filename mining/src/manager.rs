@@ -4,8 +4,9 @@ use crate::{
     errors::MiningManagerResult,
     feerate::{FeeEstimateVerbose, FeerateEstimations, FeerateEstimatorArgs},
     mempool::{
-        config::Config,
+        config::{Config, MempoolPolicy, MempoolPolicyUpdate},
         model::tx::{MempoolTransaction, TransactionPostValidation, TransactionPreValidation, TxRemovalReason},
+        notifier::MempoolNotifier,
         populate_entries_and_try_validate::{
             populate_mempool_transactions_in_parallel, validate_mempool_transaction, validate_mempool_transactions_in_parallel,
         },
@@ -14,6 +15,7 @@ use crate::{
     },
     model::{
         owner_txs::{GroupedOwnerTransactions, ScriptPublicKeySet},
+        persisted_tx::PersistedTransaction,
         topological_sort::IntoIterTopologically,
         tx_insert::TransactionInsertion,
         tx_query::TransactionQuery,
@@ -35,10 +37,15 @@ use kaspa_consensusmanager::{spawn_blocking, ConsensusProxy};
 use kaspa_core::{debug, error, info, time::Stopwatch, warn};
 use kaspa_mining_errors::{manager::MiningManagerError, mempool::RuleError};
 use parking_lot::RwLock;
-use std::sync::Arc;
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
 use tokio::sync::mpsc::UnboundedSender;
 
 pub struct MiningManager {
+    /// The config the manager was built with. Its relay policy fields may be outdated,
+    /// the current policy is held by the mempool config.
     config: Arc<Config>,
     block_template_cache: BlockTemplateCache,
     mempool: RwLock<Mempool>,
@@ -64,15 +71,25 @@ impl MiningManager {
         ram_scale: f64,
         cache_lifetime: Option<u64>,
         counters: Arc<MiningCounters>,
+        notifier: Option<MempoolNotifier>,
     ) -> Self {
         let config =
             Config::build_default(target_time_per_block, relay_non_std_transactions, max_block_mass).apply_ram_scale(ram_scale);
-        Self::with_config(config, cache_lifetime, counters)
+        Self::with_config_and_notifier(config, cache_lifetime, counters, notifier)
     }
 
     pub(crate) fn with_config(config: Config, cache_lifetime: Option<u64>, counters: Arc<MiningCounters>) -> Self {
+        Self::with_config_and_notifier(config, cache_lifetime, counters, None)
+    }
+
+    pub(crate) fn with_config_and_notifier(
+        config: Config,
+        cache_lifetime: Option<u64>,
+        counters: Arc<MiningCounters>,
+        notifier: Option<MempoolNotifier>,
+    ) -> Self {
         let config = Arc::new(config);
-        let mempool = RwLock::new(Mempool::new(config.clone(), counters.clone()));
+        let mempool = RwLock::new(Mempool::new(config.clone(), counters.clone(), notifier));
         let block_template_cache = BlockTemplateCache::new(cache_lifetime);
         Self { config, block_template_cache, mempool, counters }
     }
@@ -205,8 +222,11 @@ impl MiningManager {
     /// Returns realtime feerate estimations based on internal mempool state
     pub(crate) fn get_realtime_feerate_estimations(&self) -> FeerateEstimations {
         let args = FeerateEstimatorArgs::new(self.config.network_blocks_per_second, self.config.maximum_mass_per_block);
-        let estimator = self.mempool.read().build_feerate_estimator(args);
-        estimator.calc_estimations(self.config.minimum_feerate())
+        let mempool_read = self.mempool.read();
+        let estimator = mempool_read.build_feerate_estimator(args);
+        let minimum_feerate = mempool_read.config().minimum_feerate();
+        drop(mempool_read);
+        estimator.calc_estimations(minimum_feerate)
     }
 
     /// Returns realtime feerate estimations based on internal mempool state with additional verbose data
@@ -221,9 +241,10 @@ impl MiningManager {
         let estimator = mempool_read.build_feerate_estimator(args);
         let ready_transactions_count = mempool_read.ready_transaction_count();
         let ready_transaction_total_mass = mempool_read.ready_transaction_total_mass();
+        let minimum_feerate = mempool_read.config().minimum_feerate();
         drop(mempool_read);
         let mut resp = FeeEstimateVerbose {
-            estimations: estimator.calc_estimations(self.config.minimum_feerate()),
+            estimations: estimator.calc_estimations(minimum_feerate),
             network_mass_per_second,
             mempool_ready_transactions_count: ready_transactions_count as u64,
             mempool_ready_transactions_total_mass: ready_transaction_total_mass,
@@ -561,6 +582,83 @@ impl MiningManager {
         (transactions, orphans)
     }
 
+    /// Returns the mempool transactions, orphans included, whose short id as computed by `short_id`
+    /// belongs to `short_ids`, keyed by short id.
+    ///
+    /// Short ids matched by several transactions are ambiguous and left out of the result.
+    pub fn get_transactions_by_short_ids(
+        &self,
+        short_ids: &HashSet<u64>,
+        short_id: impl Fn(&TransactionId) -> u64,
+    ) -> HashMap<u64, Arc<Transaction>> {
+        // read lock on mempool, short ids get computed out of it
+        let (transaction_ids, orphan_ids) = self.mempool.read().get_all_transaction_ids(TransactionQuery::All);
+        let mut matches = HashMap::with_capacity(short_ids.len());
+        let mut ambiguous = HashSet::new();
+        for transaction_id in transaction_ids.into_iter().chain(orphan_ids) {
+            let id = short_id(&transaction_id);
+            if short_ids.contains(&id) && !ambiguous.contains(&id) && matches.insert(id, transaction_id).is_some() {
+                matches.remove(&id);
+                ambiguous.insert(id);
+            }
+        }
+        // read lock on mempool
+        let mempool = self.mempool.read();
+        matches
+            .into_iter()
+            .filter_map(|(id, transaction_id)| mempool.get_transaction(&transaction_id, TransactionQuery::All).map(|x| (id, x.tx)))
+            .collect()
+    }
+
+    /// Returns all the transactions of the mempool, orphans included, in a form suitable for
+    /// persisting them across node restarts.
+    pub fn get_all_persisted_transactions(&self) -> Vec<PersistedTransaction> {
+        const TRANSACTION_CHUNK_SIZE: usize = 1000;
+        // read lock on mempool by transaction chunks
+        let (transaction_ids, orphan_ids) = self.mempool.read().get_all_transaction_ids(TransactionQuery::All);
+        let mut transactions = Vec::with_capacity(transaction_ids.len() + orphan_ids.len());
+        for chunk in transaction_ids.into_iter().chain(orphan_ids).chunks(TRANSACTION_CHUNK_SIZE).into_iter() {
+            let mempool = self.mempool.read();
+            transactions.extend(chunk.filter_map(|x| mempool.get_persisted_transaction(&x)));
+        }
+        transactions
+    }
+
+    /// Restores transactions persisted by a previous run of the node.
+    ///
+    /// The transactions go through [`Self::validate_and_insert_transaction_batch`] so stale or
+    /// now-invalid entries are rejected normally. A transaction which was not an orphan when
+    /// persisted but lands in the orphan pool has its inputs spent by now and is removed too.
+    ///
+    /// Returns the number of transactions restored in the mempool.
+    pub fn restore_persisted_transactions(&self, consensus: &dyn ConsensusApi, transactions: Vec<PersistedTransaction>) -> usize {
+        let transaction_ids = transactions.iter().map(|x| x.id()).collect_vec();
+        let non_orphan_ids = transactions.iter().filter(|x| !x.is_orphan).map(|x| x.id()).collect_vec();
+
+        // High priority transactions go first so they are not evicted in favor of low priority ones
+        let (high_priority, low_priority): (Vec<_>, Vec<_>) = transactions.into_iter().partition(|x| x.priority == Priority::High);
+        for (priority, transactions) in [(Priority::High, high_priority), (Priority::Low, low_priority)] {
+            if transactions.is_empty() {
+                continue;
+            }
+            let transactions = transactions.into_iter().map(|x| x.transaction).collect();
+            self.validate_and_insert_transaction_batch(consensus, transactions, priority, Orphan::Allowed, RbfPolicy::Forbidden);
+        }
+
+        // write lock on mempool
+        let mut mempool = self.mempool.write();
+        for transaction_id in non_orphan_ids.iter() {
+            if mempool.has_transaction(transaction_id, TransactionQuery::OrphansOnly) {
+                if let Err(err) =
+                    mempool.remove_transaction(transaction_id, true, TxRemovalReason::RevalidationWithMissingOutpoints, "")
+                {
+                    debug!("Failed to remove stale persisted transaction {0}: {1}", transaction_id, err);
+                }
+            }
+        }
+        transaction_ids.iter().filter(|x| mempool.has_transaction(x, TransactionQuery::All)).count()
+    }
+
     /// get_transactions_by_addresses returns the sending and receiving transactions for
     /// a set of addresses.
     ///
@@ -614,7 +712,7 @@ impl MiningManager {
         for chunk in &expired_low_priority_transactions.iter().chunks(24) {
             let mut mempool = self.mempool.write();
             chunk.into_iter().for_each(|tx| {
-                if let Err(err) = mempool.remove_transaction(tx, true, TxRemovalReason::Muted, "") {
+                if let Err(err) = mempool.remove_transaction(tx, true, TxRemovalReason::Expired, "") {
                     warn!("Failed to remove transaction {} from mempool: {}", tx, err);
                 }
             });
@@ -820,6 +918,28 @@ impl MiningManager {
         self.mempool.read().is_transaction_output_dust(transaction_output)
     }
 
+    /// Returns the current mempool relay policy
+    pub fn get_policy(&self) -> MempoolPolicy {
+        self.mempool.read().config().policy()
+    }
+
+    /// Applies `update` to the mempool relay policy and evicts the transactions which no longer satisfy it,
+    /// all under a single mempool write lock.
+    ///
+    /// Returns the new policy along with the number of evicted transactions.
+    pub fn update_policy(&self, update: &MempoolPolicyUpdate) -> MiningManagerResult<(MempoolPolicy, usize)> {
+        let mut mempool = self.mempool.write();
+        let policy = mempool.config().policy().apply(update);
+        policy.validate()?;
+        let evicted = mempool.update_policy(&policy)?;
+        drop(mempool);
+        // Evicted transactions may be part of the cached template
+        if evicted > 0 {
+            self.block_template_cache.clear();
+        }
+        Ok((policy, evicted))
+    }
+
     pub fn has_accepted_transaction(&self, transaction_id: &TransactionId) -> bool {
         self.mempool.read().has_accepted_transaction(transaction_id)
     }
@@ -954,6 +1074,31 @@ impl MiningManagerProxy {
         spawn_blocking(move || self.inner.get_all_transactions(query)).await.unwrap()
     }
 
+    /// Returns the mempool transactions matching the short ids computed by `short_id`.
+    ///
+    /// See [`MiningManager::get_transactions_by_short_ids`].
+    pub async fn get_transactions_by_short_ids(
+        self,
+        short_ids: HashSet<u64>,
+        short_id: impl Fn(&TransactionId) -> u64 + Send + 'static,
+    ) -> HashMap<u64, Arc<Transaction>> {
+        spawn_blocking(move || self.inner.get_transactions_by_short_ids(&short_ids, short_id)).await.unwrap()
+    }
+
+    /// Returns all the transactions of the mempool, orphans included, in a form suitable for
+    /// persisting them across node restarts.
+    pub async fn get_all_persisted_transactions(self) -> Vec<PersistedTransaction> {
+        spawn_blocking(move || self.inner.get_all_persisted_transactions()).await.unwrap()
+    }
+
+    /// Restores transactions persisted by a previous run of the node, returning the number of
+    /// transactions restored in the mempool.
+    ///
+    /// See [`MiningManager::restore_persisted_transactions`].
+    pub async fn restore_persisted_transactions(self, consensus: &ConsensusProxy, transactions: Vec<PersistedTransaction>) -> usize {
+        consensus.clone().spawn_blocking(move |c| self.inner.restore_persisted_transactions(c, transactions)).await
+    }
+
     /// get_transactions_by_addresses returns the sending and receiving transactions for
     /// a set of addresses.
     ///
@@ -966,6 +1111,19 @@ impl MiningManagerProxy {
         spawn_blocking(move || self.inner.get_transactions_by_addresses(&script_public_keys, query)).await.unwrap()
     }
 
+    /// Returns the current mempool relay policy
+    pub async fn get_policy(self) -> MempoolPolicy {
+        spawn_blocking(move || self.inner.get_policy()).await.unwrap()
+    }
+
+    /// Applies `update` to the mempool relay policy, returning the new policy along with the number of
+    /// evicted transactions.
+    ///
+    /// See [`MiningManager::update_policy`].
+    pub async fn update_policy(self, update: MempoolPolicyUpdate) -> MiningManagerResult<(MempoolPolicy, usize)> {
+        spawn_blocking(move || self.inner.update_policy(&update)).await.unwrap()
+    }
+
     /// Returns whether a transaction id was registered as accepted in the mempool, meaning
     /// that the consensus accepted a block containing it and said block was handled by the
     /// mempool.
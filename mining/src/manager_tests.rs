@@ -5,9 +5,10 @@ mod tests {
         errors::{MiningManagerError, MiningManagerResult},
         manager::MiningManager,
         mempool::{
-            config::{Config, DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE},
+            config::{Config, MempoolPolicyUpdate, DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE},
             errors::RuleError,
             model::frontier::selectors::TakeAllSelector,
+            notifier::MempoolNotifier,
             tx::{Orphan, Priority, RbfPolicy},
         },
         model::{tx_insert::TransactionInsertion, tx_query::TransactionQuery},
@@ -29,10 +30,15 @@ mod tests {
             TransactionOutput, UtxoEntry,
         },
     };
+    use kaspa_consensus_notify::{
+        notification::{MempoolChangeKind, Notification},
+        root::ConsensusNotificationRoot,
+    };
     use kaspa_hashes::Hash;
     use kaspa_mining_errors::mempool::RuleResult;
+    use kaspa_notify::{scope::MempoolChangedScope, subscriber::SubscriptionManager};
     use kaspa_txscript::{
-        pay_to_address_script, pay_to_script_hash_signature_script,
+        extract_script_pub_key_address, pay_to_address_script, pay_to_script_hash_signature_script,
         test_helpers::{create_transaction, create_transaction_with_change, op_true_script},
     };
     use kaspa_utils::mem_size::MemSizeEstimator;
@@ -1174,6 +1180,180 @@ mod tests {
         assert!(validate_and_insert_mutable_transaction(&mining_manager, consensus.as_ref(), too_big_tx.clone()).is_err());
     }
 
+    // test_update_policy verifies that changing the mempool policy at runtime evicts the transactions
+    // no longer satisfying it, lowest fee rates first.
+    #[test]
+    fn test_update_policy() {
+        const TX_COUNT: usize = 10;
+        let txs = (0..TX_COUNT)
+            .map(|i| {
+                let mut tx = create_transaction_with_utxo_entry(i as u32, 0);
+                tx.calculated_fee = Some(1000 * (i as u64 + 1));
+                tx
+            })
+            .collect_vec();
+        let mass = txs[0].calculated_compute_mass.unwrap();
+
+        let consensus = Arc::new(ConsensusMock::new());
+        let counters = Arc::new(MiningCounters::default());
+        let config = Config::build_default(TARGET_TIME_PER_BLOCK, false, MAX_BLOCK_MASS);
+        let mining_manager = MiningManager::with_config(config, None, counters);
+        for tx in txs.iter() {
+            validate_and_insert_mutable_transaction(&mining_manager, consensus.as_ref(), tx.clone()).unwrap();
+        }
+        assert_transaction_count(&mining_manager, TX_COUNT, "all transactions should be in the mempool");
+
+        // Invalid policies are rejected and leave the mempool untouched
+        let update = MempoolPolicyUpdate { maximum_transaction_count: Some(0), ..Default::default() };
+        assert!(matches!(mining_manager.update_policy(&update), Err(MiningManagerError::InvalidMempoolPolicy(_))));
+        assert_eq!(mining_manager.get_policy(), Config::build_default(TARGET_TIME_PER_BLOCK, false, MAX_BLOCK_MASS).policy());
+        assert_transaction_count(&mining_manager, TX_COUNT, "no transaction should be evicted");
+
+        // Raise the minimum fee to 5500 sompi for the transaction mass, evicting the 5 lowest fee transactions
+        let update = MempoolPolicyUpdate { minimum_relay_transaction_fee: Some(5500 * 1000 / mass), ..Default::default() };
+        let (policy, evicted) = mining_manager.update_policy(&update).unwrap();
+        assert_eq!(policy.minimum_relay_transaction_fee, 5500 * 1000 / mass);
+        assert_eq!(mining_manager.get_policy(), policy);
+        assert_eq!(evicted, 5);
+        assert_transaction_count(&mining_manager, TX_COUNT - 5, "low fee transactions should be evicted");
+        for (i, tx) in txs.iter().enumerate() {
+            assert_eq!(mining_manager.has_transaction(&tx.id(), TransactionQuery::TransactionsOnly), i >= 5);
+        }
+
+        // A transaction below the new minimum fee is rejected
+        let mut low_fee_tx = create_transaction_with_utxo_entry(TX_COUNT as u32, 0);
+        low_fee_tx.calculated_fee = Some(1000);
+        assert!(validate_and_insert_mutable_transaction(&mining_manager, consensus.as_ref(), low_fee_tx).is_err());
+
+        // Lower the transaction count limit, keeping the highest fee transactions
+        let update = MempoolPolicyUpdate { maximum_transaction_count: Some(2), ..Default::default() };
+        let (policy, evicted) = mining_manager.update_policy(&update).unwrap();
+        assert_eq!(policy.maximum_transaction_count, 2);
+        assert_eq!(policy.minimum_relay_transaction_fee, 5500 * 1000 / mass, "unset fields should be left unchanged");
+        assert_eq!(evicted, 3);
+        for (i, tx) in txs.iter().enumerate() {
+            assert_eq!(mining_manager.has_transaction(&tx.id(), TransactionQuery::TransactionsOnly), i >= 8);
+        }
+    }
+
+    // test_restore_persisted_transactions verifies that persisted transactions are restored into a fresh mempool
+    // with their priority, and that transactions made stale in the meantime are rejected.
+    #[test]
+    fn test_restore_persisted_transactions() {
+        const TX_PAIRS_COUNT: usize = 3;
+        let consensus = Arc::new(ConsensusMock::new());
+        let mining_manager =
+            MiningManager::new(TARGET_TIME_PER_BLOCK, false, MAX_BLOCK_MASS, None, Arc::new(MiningCounters::default()));
+
+        // Parents and children chained in the mempool, plus a high priority orphan
+        let (parent_txs, child_txs) = create_arrays_of_parent_and_children_transactions(&consensus, TX_PAIRS_COUNT);
+        for (transaction, priority) in
+            parent_txs.iter().map(|x| (x, Priority::Low)).chain(child_txs.iter().map(|x| (x, Priority::High)))
+        {
+            let result = mining_manager.validate_and_insert_transaction(
+                consensus.as_ref(),
+                transaction.clone(),
+                priority,
+                Orphan::Forbidden,
+                RbfPolicy::Forbidden,
+            );
+            assert!(result.is_ok(), "inserting transaction {} failed", transaction.id());
+        }
+        let (_, orphan_tx) = create_parent_and_children_transactions(&consensus, vec![700 * SOMPI_PER_KASPA]);
+        let result = mining_manager.validate_and_insert_transaction(
+            consensus.as_ref(),
+            orphan_tx.clone(),
+            Priority::High,
+            Orphan::Allowed,
+            RbfPolicy::Forbidden,
+        );
+        assert!(result.is_ok(), "inserting the orphan transaction {} failed", orphan_tx.id());
+
+        let persisted = mining_manager.get_all_persisted_transactions();
+        assert_eq!(persisted.len(), 2 * TX_PAIRS_COUNT + 1);
+        assert_eq!(persisted.iter().filter(|x| x.is_orphan).map(|x| x.id()).collect_vec(), vec![orphan_tx.id()]);
+
+        // Simulate the first parent being accepted by consensus while the node was down
+        consensus.add_transaction(parent_txs[0].clone(), 2);
+
+        let restored_mining_manager =
+            MiningManager::new(TARGET_TIME_PER_BLOCK, false, MAX_BLOCK_MASS, None, Arc::new(MiningCounters::default()));
+        let restored_count = restored_mining_manager.restore_persisted_transactions(consensus.as_ref(), persisted);
+        assert_eq!(restored_count, 2 * TX_PAIRS_COUNT, "all but the accepted parent should be restored");
+
+        let (transactions, orphans) = restored_mining_manager.get_all_transactions(TransactionQuery::All);
+        assert!(!contained_by(parent_txs[0].id(), &transactions), "the accepted parent should not be restored");
+        assert!(!contained_by(parent_txs[0].id(), &orphans), "the accepted parent should not be restored as an orphan");
+        for transaction in parent_txs.iter().skip(1).chain(child_txs.iter()) {
+            assert!(contained_by(transaction.id(), &transactions), "transaction {} should be restored", transaction.id());
+        }
+        assert_eq!(orphans.len(), 1);
+        assert!(contained_by(orphan_tx.id(), &orphans), "the orphan transaction should be restored as an orphan");
+
+        // Priorities are restored as well
+        let persisted = restored_mining_manager.get_all_persisted_transactions();
+        for transaction in persisted.iter() {
+            let expected_priority = if contained_by(transaction.id(), &parent_txs) { Priority::Low } else { Priority::High };
+            assert_eq!(expected_priority, transaction.priority, "wrong priority for transaction {}", transaction.id());
+        }
+    }
+
+    // test_mempool_changed_notifications verifies that transactions entering and leaving the transaction pool
+    // are reported to the notification root along with the addresses they involve.
+    #[tokio::test]
+    async fn test_mempool_changed_notifications() {
+        let consensus = Arc::new(ConsensusMock::new());
+        let (sender, receiver) = async_channel::unbounded();
+        let root = Arc::new(ConsensusNotificationRoot::new(sender));
+        let notifier = MempoolNotifier::new(root.clone(), Prefix::Testnet);
+        let config = Config::build_default(TARGET_TIME_PER_BLOCK, false, MAX_BLOCK_MASS);
+        let mining_manager =
+            MiningManager::with_config_and_notifier(config, None, Arc::new(MiningCounters::default()), Some(notifier));
+
+        // Nothing is sent until someone subscribes
+        let (parent_tx, child_tx) = create_parent_and_children_transactions(&consensus, vec![500 * SOMPI_PER_KASPA]);
+        let result = mining_manager.validate_and_insert_transaction(
+            consensus.as_ref(),
+            parent_tx.clone(),
+            Priority::Low,
+            Orphan::Forbidden,
+            RbfPolicy::Forbidden,
+        );
+        assert!(result.is_ok(), "inserting the parent transaction failed");
+        assert!(receiver.is_empty(), "no notification should be sent without a subscription");
+
+        root.start_notify(0, MempoolChangedScope::default().into()).await.unwrap();
+        let result = mining_manager.validate_and_insert_transaction(
+            consensus.as_ref(),
+            child_tx.clone(),
+            Priority::Low,
+            Orphan::Forbidden,
+            RbfPolicy::Forbidden,
+        );
+        assert!(result.is_ok(), "inserting the child transaction failed");
+
+        // Accepting the parent in a block only removes the parent from the mempool
+        let result = mining_manager.handle_new_block_transactions(consensus.as_ref(), 2, &build_block_transactions(once(&parent_tx)));
+        assert!(result.is_ok(), "handling the block transactions failed");
+
+        let expected = [(child_tx.id(), MempoolChangeKind::Added), (parent_tx.id(), MempoolChangeKind::Accepted)];
+        for (transaction_id, kind) in expected {
+            let Ok(Notification::MempoolChanged(notification)) = receiver.try_recv() else {
+                panic!("a MempoolChanged notification was expected for transaction {}", transaction_id);
+            };
+            assert_eq!(transaction_id, notification.transaction_id);
+            assert_eq!(kind, notification.kind);
+            let expected_address = extract_script_pub_key_address(&op_true_script().0, Prefix::Testnet).unwrap();
+            assert_eq!(
+                vec![expected_address],
+                *notification.addresses,
+                "transaction {} should only involve its op-true address",
+                transaction_id
+            );
+        }
+        assert!(receiver.is_empty(), "no other notification was expected");
+    }
+
     fn validate_and_insert_mutable_transaction(
         mining_manager: &MiningManager,
         consensus: &dyn ConsensusApi,
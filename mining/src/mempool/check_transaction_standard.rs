@@ -182,7 +182,8 @@ impl Mempool {
             // function.
             let entry = transaction.entries[i].as_ref().unwrap();
             match ScriptClass::from_script(&entry.script_public_key) {
-                ScriptClass::NonStandard => {
+                // Time-locked templates are only recognized as P2SH redeem scripts, never as bare public key scripts
+                ScriptClass::NonStandard | ScriptClass::HashTimeLock | ScriptClass::TimeLockVault => {
                     return Err(NonStandardError::RejectInputScriptClass(transaction_id, i));
                 }
                 ScriptClass::PubKey => {}
@@ -294,7 +295,7 @@ mod tests {
                 let mut config = Config::build_default(params.target_time_per_block, false, params.max_block_mass);
                 config.minimum_relay_transaction_fee = test.minimum_relay_transaction_fee;
                 let counters = Arc::new(MiningCounters::default());
-                let mempool = Mempool::new(Arc::new(config), counters);
+                let mempool = Mempool::new(Arc::new(config), counters, None);
 
                 let got = mempool.minimum_required_transaction_relay_fee(test.size);
                 if got != test.want {
@@ -379,7 +380,7 @@ mod tests {
                 let mut config = Config::build_default(params.target_time_per_block, false, params.max_block_mass);
                 config.minimum_relay_transaction_fee = test.minimum_relay_transaction_fee;
                 let counters = Arc::new(MiningCounters::default());
-                let mempool = Mempool::new(Arc::new(config), counters);
+                let mempool = Mempool::new(Arc::new(config), counters, None);
 
                 println!("test_is_transaction_output_dust test '{}' ", test.name);
                 let res = mempool.is_transaction_output_dust(&test.tx_out);
@@ -513,6 +514,49 @@ mod tests {
                 ),
                 is_standard: false,
             },
+            Test {
+                name: "Bare hash time-locked contract public key script",
+                mtx: new_mtx(
+                    Transaction::new(
+                        TX_VERSION,
+                        vec![dummy_tx_input.clone()],
+                        vec![TransactionOutput::new(
+                            SOMPI_PER_KASPA,
+                            ScriptPublicKey::new(
+                                MAX_SCRIPT_PUBLIC_KEY_VERSION,
+                                kaspa_txscript::htlc_redeem_script(&[1; 32], &[2; 32], &[3; 32], 1_000_000).unwrap().into(),
+                            ),
+                        )],
+                        0,
+                        SUBNETWORK_ID_NATIVE,
+                        0,
+                        vec![],
+                    ),
+                    1000,
+                ),
+                is_standard: false,
+            },
+            Test {
+                name: "Pay to script hash of a hash time-locked contract",
+                mtx: new_mtx(
+                    Transaction::new(
+                        TX_VERSION,
+                        vec![dummy_tx_input.clone()],
+                        vec![TransactionOutput::new(
+                            SOMPI_PER_KASPA,
+                            kaspa_txscript::pay_to_script_hash_script(
+                                &kaspa_txscript::htlc_redeem_script(&[1; 32], &[2; 32], &[3; 32], 1_000_000).unwrap(),
+                            ),
+                        )],
+                        0,
+                        SUBNETWORK_ID_NATIVE,
+                        0,
+                        vec![],
+                    ),
+                    1000,
+                ),
+                is_standard: true,
+            },
             Test {
                 name: "Dust output",
                 mtx: new_mtx(
@@ -558,7 +602,7 @@ mod tests {
                 let params: Params = net.into();
                 let config = Config::build_default(params.target_time_per_block, false, params.max_block_mass);
                 let counters = Arc::new(MiningCounters::default());
-                let mempool = Mempool::new(Arc::new(config), counters);
+                let mempool = Mempool::new(Arc::new(config), counters, None);
 
                 // Ensure standard-ness is as expected.
                 println!("test_check_transaction_standard_in_isolation test '{}' ", test.name);
@@ -1,4 +1,6 @@
-use kaspa_consensus_core::constants::TX_VERSION;
+use crate::errors::{MiningManagerError, MiningManagerResult};
+use kaspa_consensus_core::constants::{MAX_SOMPI, TX_VERSION};
+use serde::Deserialize;
 
 pub(crate) const DEFAULT_MAXIMUM_TRANSACTION_COUNT: usize = 1_000_000;
 pub(crate) const DEFAULT_MEMPOOL_SIZE_LIMIT: usize = 1_000_000_000;
@@ -130,9 +132,91 @@ impl Config {
         self
     }
 
+    /// Returns the relay policy part of the config
+    pub fn policy(&self) -> MempoolPolicy {
+        MempoolPolicy {
+            maximum_transaction_count: self.maximum_transaction_count,
+            mempool_size_limit: self.mempool_size_limit,
+            maximum_orphan_transaction_mass: self.maximum_orphan_transaction_mass,
+            maximum_orphan_transaction_count: self.maximum_orphan_transaction_count,
+            accept_non_standard: self.accept_non_standard,
+            minimum_relay_transaction_fee: self.minimum_relay_transaction_fee,
+        }
+    }
+
+    /// Returns a copy of the config with its relay policy replaced by `policy`
+    pub fn with_policy(&self, policy: &MempoolPolicy) -> Self {
+        Self {
+            maximum_transaction_count: policy.maximum_transaction_count,
+            mempool_size_limit: policy.mempool_size_limit,
+            maximum_orphan_transaction_mass: policy.maximum_orphan_transaction_mass,
+            maximum_orphan_transaction_count: policy.maximum_orphan_transaction_count,
+            accept_non_standard: policy.accept_non_standard,
+            minimum_relay_transaction_fee: policy.minimum_relay_transaction_fee,
+            ..self.clone()
+        }
+    }
+
     /// Returns the minimum standard fee/mass ratio currently required by the mempool
     pub(crate) fn minimum_feerate(&self) -> f64 {
         // The parameter minimum_relay_transaction_fee is in sompi/kg units so divide by 1000 to get sompi/gram
         self.minimum_relay_transaction_fee as f64 / 1000.0
     }
 }
+
+/// The part of the mempool [`Config`] defining which transactions are accepted and relayed.
+///
+/// Unlike the rest of the config, the policy can be changed while the node is running.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MempoolPolicy {
+    pub maximum_transaction_count: usize,
+    pub mempool_size_limit: usize,
+    pub maximum_orphan_transaction_mass: u64,
+    pub maximum_orphan_transaction_count: u64,
+    pub accept_non_standard: bool,
+    /// In sompi per 1kg of transaction mass, see [`DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE`]
+    pub minimum_relay_transaction_fee: u64,
+}
+
+impl MempoolPolicy {
+    /// Returns a copy of the policy with the fields set in `update` replaced
+    pub fn apply(&self, update: &MempoolPolicyUpdate) -> Self {
+        Self {
+            maximum_transaction_count: update.maximum_transaction_count.unwrap_or(self.maximum_transaction_count),
+            mempool_size_limit: update.mempool_size_limit.unwrap_or(self.mempool_size_limit),
+            maximum_orphan_transaction_mass: update.maximum_orphan_transaction_mass.unwrap_or(self.maximum_orphan_transaction_mass),
+            maximum_orphan_transaction_count: update.maximum_orphan_transaction_count.unwrap_or(self.maximum_orphan_transaction_count),
+            accept_non_standard: update.accept_non_standard.unwrap_or(self.accept_non_standard),
+            minimum_relay_transaction_fee: update.minimum_relay_transaction_fee.unwrap_or(self.minimum_relay_transaction_fee),
+        }
+    }
+
+    pub fn validate(&self) -> MiningManagerResult<()> {
+        if self.maximum_transaction_count == 0 {
+            return Err(MiningManagerError::InvalidMempoolPolicy("maximum transaction count must be positive".to_string()));
+        }
+        if self.mempool_size_limit == 0 {
+            return Err(MiningManagerError::InvalidMempoolPolicy("mempool size limit must be positive".to_string()));
+        }
+        if self.minimum_relay_transaction_fee > MAX_SOMPI {
+            return Err(MiningManagerError::InvalidMempoolPolicy(format!(
+                "minimum relay transaction fee must not exceed {MAX_SOMPI} sompi/kg"
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// A partial [`MempoolPolicy`] where unset fields are left unchanged.
+///
+/// Deserializes from the `[mempool]` section of the kaspad config file.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+pub struct MempoolPolicyUpdate {
+    pub maximum_transaction_count: Option<usize>,
+    pub mempool_size_limit: Option<usize>,
+    pub maximum_orphan_transaction_mass: Option<u64>,
+    pub maximum_orphan_transaction_count: Option<u64>,
+    pub accept_non_standard: Option<bool>,
+    pub minimum_relay_transaction_fee: Option<u64>,
+}
@@ -2,6 +2,7 @@ use crate::{
     feerate::{FeerateEstimator, FeerateEstimatorArgs},
     model::{
         owner_txs::{GroupedOwnerTransactions, ScriptPublicKeySet},
+        persisted_tx::PersistedTransaction,
         tx_query::TransactionQuery,
     },
     MiningCounters,
@@ -10,12 +11,14 @@ use crate::{
 use self::{
     config::Config,
     model::{accepted_transactions::AcceptedTransactions, orphan_pool::OrphanPool, pool::Pool, transactions_pool::TransactionsPool},
+    notifier::MempoolNotifier,
     tx::Priority,
 };
 use kaspa_consensus_core::{
     block::TemplateTransactionSelector,
     tx::{MutableTransaction, TransactionId},
 };
+use kaspa_consensus_notify::notification::MempoolChangeKind;
 use kaspa_core::time::Stopwatch;
 use std::sync::Arc;
 
@@ -24,9 +27,11 @@ pub mod config;
 pub mod errors;
 pub(crate) mod handle_new_block_transactions;
 pub(crate) mod model;
+pub mod notifier;
 pub(crate) mod populate_entries_and_try_validate;
 pub(crate) mod remove_transaction;
 pub(crate) mod replace_by_fee;
+pub(crate) mod update_policy;
 pub(crate) mod validate_and_insert_transaction;
 
 /// Mempool contains transactions intended to be inserted into a block and mined.
@@ -51,14 +56,25 @@ pub(crate) struct Mempool {
     orphan_pool: OrphanPool,
     accepted_transactions: AcceptedTransactions,
     counters: Arc<MiningCounters>,
+    notifier: Option<MempoolNotifier>,
 }
 
 impl Mempool {
-    pub(crate) fn new(config: Arc<Config>, counters: Arc<MiningCounters>) -> Self {
+    pub(crate) fn new(config: Arc<Config>, counters: Arc<MiningCounters>, notifier: Option<MempoolNotifier>) -> Self {
         let transaction_pool = TransactionsPool::new(config.clone());
         let orphan_pool = OrphanPool::new(config.clone());
         let accepted_transactions = AcceptedTransactions::new(config.clone());
-        Self { config, transaction_pool, orphan_pool, accepted_transactions, counters }
+        Self { config, transaction_pool, orphan_pool, accepted_transactions, counters, notifier }
+    }
+
+    pub(crate) fn config(&self) -> &Config {
+        &self.config
+    }
+
+    fn notify_change(&self, transaction: &MutableTransaction, kind: MempoolChangeKind) {
+        if let Some(ref notifier) = self.notifier {
+            notifier.notify(transaction, kind);
+        }
     }
 
     pub(crate) fn get_transaction(&self, transaction_id: &TransactionId, query: TransactionQuery) -> Option<MutableTransaction> {
@@ -72,6 +88,13 @@ impl Mempool {
         transaction.map(|x| x.mtx.clone())
     }
 
+    pub(crate) fn get_persisted_transaction(&self, transaction_id: &TransactionId) -> Option<PersistedTransaction> {
+        if let Some(transaction) = self.transaction_pool.get(transaction_id) {
+            return Some(PersistedTransaction::new(transaction.mtx.tx.as_ref().clone(), transaction.priority, false));
+        }
+        self.orphan_pool.get(transaction_id).map(|x| PersistedTransaction::new(x.mtx.tx.as_ref().clone(), x.priority, true))
+    }
+
     pub(crate) fn has_transaction(&self, transaction_id: &TransactionId, query: TransactionQuery) -> bool {
         (query.include_transaction_pool() && self.transaction_pool.has(transaction_id))
             || (query.include_orphan_pool() && self.orphan_pool.has(transaction_id))
@@ -165,7 +188,9 @@ impl Mempool {
 }
 
 pub mod tx {
-    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
     pub enum Priority {
         Low,
         High,
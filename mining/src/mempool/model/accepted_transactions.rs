@@ -21,6 +21,10 @@ impl AcceptedTransactions {
         Self { config, transactions: Default::default(), last_expire_scan_daa_score: 0, last_expire_scan_time: unix_now() }
     }
 
+    pub(crate) fn set_config(&mut self, config: Arc<Config>) {
+        self.config = config;
+    }
+
     pub(crate) fn add(&mut self, transaction_id: TransactionId, daa_score: u64) -> bool {
         self.transactions.insert(transaction_id, daa_score).is_none()
     }
@@ -51,6 +51,25 @@ impl OrphanPool {
         }
     }
 
+    /// Replaces the config and evicts the orphans exceeding its mass and count limits
+    pub(crate) fn set_config(&mut self, config: Arc<Config>) -> RuleResult<()> {
+        self.config = config;
+        let heavy_orphans = self
+            .all_orphans
+            .values()
+            .filter(|x| x.mtx.calculated_compute_mass.unwrap() > self.config.maximum_orphan_transaction_mass)
+            .map(|x| x.id())
+            .collect::<Vec<_>>();
+        for id in heavy_orphans {
+            self.remove_orphan(&id, true, TxRemovalReason::PolicyChanged, "")?;
+        }
+        match self.limit_orphan_pool_size(0) {
+            // The remaining orphans are all high priority, they are kept
+            Err(RuleError::RejectOrphanPoolIsFull(..)) => Ok(()),
+            res => res,
+        }
+    }
+
     pub(crate) fn outpoint_orphan(&self, outpoint: &TransactionOutpoint) -> Option<&MempoolTransaction> {
         self.outpoint_owner_id.get(outpoint).and_then(|id| self.all_orphans.get(id))
     }
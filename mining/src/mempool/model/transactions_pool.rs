@@ -94,6 +94,10 @@ impl TransactionsPool {
         }
     }
 
+    pub(crate) fn set_config(&mut self, config: Arc<Config>) {
+        self.config = config;
+    }
+
     /// Add a mutable transaction to the pool
     pub(crate) fn add_transaction(
         &mut self,
@@ -277,6 +281,33 @@ impl TransactionsPool {
         Err(RuleError::RejectMempoolIsFull)
     }
 
+    /// Returns the low-priority ready transactions having the lowest fee rates which must be removed,
+    /// along with their redeemers, for the pool to fit within the configured count and size limits.
+    pub(crate) fn collect_exceeding_low_priority_transactions(&self) -> Vec<TransactionId> {
+        let mut len = self.len();
+        let mut estimated_size = self.estimated_size;
+        let mut removed = TransactionIdSet::default();
+        let mut txs_to_remove = vec![];
+        for tx in self
+            .ready_transactions
+            .ascending_iter()
+            .map(|tx| self.all_transactions.get(&tx.id()).unwrap())
+            .filter(|mtx| mtx.priority == Priority::Low)
+        {
+            if len <= self.config.maximum_transaction_count && estimated_size <= self.config.mempool_size_limit {
+                break;
+            }
+            for id in once(tx.id()).chain(self.get_redeemer_ids_in_pool(&tx.id())) {
+                if removed.insert(id) {
+                    len -= 1;
+                    estimated_size -= self.all_transactions.get(&id).unwrap().mtx.mempool_estimated_bytes();
+                }
+            }
+            txs_to_remove.push(tx.id());
+        }
+        txs_to_remove
+    }
+
     pub(crate) fn get_estimated_size(&self) -> usize {
         self.estimated_size
     }
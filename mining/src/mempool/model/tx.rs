@@ -1,5 +1,6 @@
 use crate::mempool::tx::{Priority, RbfPolicy};
 use kaspa_consensus_core::tx::{MutableTransaction, Transaction, TransactionId, TransactionOutpoint};
+use kaspa_consensus_notify::notification::MempoolChangeKind;
 use kaspa_mining_errors::mempool::RuleError;
 use std::{
     fmt::{Display, Formatter},
@@ -85,6 +86,7 @@ pub(crate) enum TxRemovalReason {
     InvalidInBlockTemplate,
     RevalidationWithMissingOutpoints,
     ReplacedByFee,
+    PolicyChanged,
 }
 
 impl TxRemovalReason {
@@ -99,12 +101,30 @@ impl TxRemovalReason {
             TxRemovalReason::InvalidInBlockTemplate => "invalid in block template",
             TxRemovalReason::RevalidationWithMissingOutpoints => "revalidation with missing outpoints",
             TxRemovalReason::ReplacedByFee => "replaced by fee",
+            TxRemovalReason::PolicyChanged => "policy changed",
         }
     }
 
     pub(crate) fn verbose(&self) -> bool {
         !matches!(self, TxRemovalReason::Muted)
     }
+
+    /// Returns the kind of mempool change reported to listeners when a transaction is removed from the
+    /// transaction pool for this reason, if any.
+    pub(crate) fn change_kind(&self) -> Option<MempoolChangeKind> {
+        match self {
+            TxRemovalReason::Accepted => Some(MempoolChangeKind::Accepted),
+            TxRemovalReason::ReplacedByFee => Some(MempoolChangeKind::Replaced),
+            TxRemovalReason::Expired => Some(MempoolChangeKind::Expired),
+            TxRemovalReason::MakingRoom | TxRemovalReason::PolicyChanged => Some(MempoolChangeKind::Evicted),
+            TxRemovalReason::Muted
+            | TxRemovalReason::DoubleSpend
+            | TxRemovalReason::InvalidInBlockTemplate
+            | TxRemovalReason::RevalidationWithMissingOutpoints => Some(MempoolChangeKind::Removed),
+            // Unorphaned transactions leave the orphan pool, not the transaction pool
+            TxRemovalReason::Unorphaned => None,
+        }
+    }
 }
 
 impl Display for TxRemovalReason {
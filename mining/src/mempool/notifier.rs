@@ -0,0 +1,41 @@
+use kaspa_addresses::Prefix;
+use kaspa_consensus_core::tx::MutableTransaction;
+use kaspa_consensus_notify::{
+    notification::{MempoolChangeKind, MempoolChangedNotification, Notification},
+    root::ConsensusNotificationRoot,
+};
+use kaspa_notify::{events::EventType, notifier::Notify};
+use kaspa_txscript::extract_script_pub_key_address;
+use std::{collections::BTreeSet, sync::Arc};
+
+/// Emits MempoolChanged notifications through the consensus notification root.
+///
+/// Only the transaction pool is observed: orphans are reported when they get unorphaned.
+#[derive(Clone)]
+pub struct MempoolNotifier {
+    root: Arc<ConsensusNotificationRoot>,
+    prefix: Prefix,
+}
+
+impl MempoolNotifier {
+    pub fn new(root: Arc<ConsensusNotificationRoot>, prefix: Prefix) -> Self {
+        Self { root, prefix }
+    }
+
+    pub(crate) fn notify(&self, transaction: &MutableTransaction, kind: MempoolChangeKind) {
+        // Avoid extracting the addresses when nobody is listening
+        if !self.root.has_subscription(EventType::MempoolChanged) {
+            return;
+        }
+        let addresses = transaction
+            .entries
+            .iter()
+            .flatten()
+            .map(|entry| &entry.script_public_key)
+            .chain(transaction.tx.outputs.iter().map(|output| &output.script_public_key))
+            .filter_map(|script_public_key| extract_script_pub_key_address(script_public_key, self.prefix).ok())
+            .collect::<BTreeSet<_>>();
+        let notification = MempoolChangedNotification::new(transaction.id(), kind, Arc::new(addresses.into_iter().collect()));
+        let _ = self.root.notify(Notification::MempoolChanged(notification));
+    }
+}
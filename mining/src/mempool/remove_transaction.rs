@@ -36,11 +36,16 @@ impl Mempool {
             // Update/remove descendent orphan txs (depending on `remove_redeemers`)
             let txs = self.orphan_pool.update_orphans_after_transaction_removed(&tx, remove_redeemers)?;
             removed_orphans.extend(txs.into_iter().map(|x| x.id()));
+            // Redeemers removed along with the transaction share its removal reason
+            if let Some(kind) = reason.change_kind() {
+                self.notify_change(&tx.mtx, kind);
+            }
         }
         removed_transactions.extend(removed_orphans);
 
         match reason {
-            TxRemovalReason::Muted => {}
+            // Expired transactions are logged in bulk by the caller
+            TxRemovalReason::Muted | TxRemovalReason::Expired => {}
             TxRemovalReason::DoubleSpend => match removed_transactions.len() {
                 0 => {}
                 1 => debug!("Removed transaction ({}) {}{}", reason, removed_transactions[0], extra_info),
@@ -0,0 +1,50 @@
+use crate::mempool::{
+    config::MempoolPolicy,
+    errors::RuleResult,
+    model::{pool::Pool, tx::TxRemovalReason},
+    Mempool,
+};
+use kaspa_core::info;
+use std::sync::Arc;
+
+impl Mempool {
+    /// Replaces the relay policy and evicts the transactions which no longer satisfy it.
+    ///
+    /// Standardness and fee requirements apply to all transactions, while only low priority
+    /// transactions are evicted to fit the count and size limits, the same way they are when
+    /// making room for a new transaction.
+    ///
+    /// Returns the number of transactions evicted from the transaction pool.
+    pub(crate) fn update_policy(&mut self, policy: &MempoolPolicy) -> RuleResult<usize> {
+        let config = Arc::new(self.config.with_policy(policy));
+        self.config = config.clone();
+        self.transaction_pool.set_config(config.clone());
+        self.accepted_transactions.set_config(config.clone());
+        self.orphan_pool.set_config(config)?;
+
+        let initial_count = self.transaction_pool.len();
+        if !self.config.accept_non_standard {
+            let non_standard = self
+                .transaction_pool
+                .all()
+                .values()
+                .filter(|tx| {
+                    self.check_transaction_standard_in_isolation(&tx.mtx).is_err()
+                        || self.check_transaction_standard_in_context(&tx.mtx).is_err()
+                })
+                .map(|tx| tx.id())
+                .collect::<Vec<_>>();
+            for transaction_id in non_standard.iter() {
+                // Redeemers are removed along with their parent, so the transaction may already be gone
+                self.remove_transaction(transaction_id, true, TxRemovalReason::PolicyChanged, "")?;
+            }
+        }
+        for transaction_id in self.transaction_pool.collect_exceeding_low_priority_transactions() {
+            self.remove_transaction(&transaction_id, true, TxRemovalReason::PolicyChanged, "")?;
+        }
+
+        let evicted = initial_count - self.transaction_pool.len();
+        info!("Mempool policy updated: {:?}, {} transactions evicted", policy, evicted);
+        Ok(evicted)
+    }
+}
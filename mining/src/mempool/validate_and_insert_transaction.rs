@@ -14,6 +14,7 @@ use kaspa_consensus_core::{
     constants::UNACCEPTED_DAA_SCORE,
     tx::{MutableTransaction, Transaction, TransactionId, TransactionOutpoint, UtxoEntry},
 };
+use kaspa_consensus_notify::notification::MempoolChangeKind;
 use kaspa_core::{debug, info};
 
 impl Mempool {
@@ -120,12 +121,12 @@ impl Mempool {
         );
 
         // Add the transaction to the mempool as a MempoolTransaction and return a clone of the embedded Arc<Transaction>
-        let accepted_transaction = self
-            .transaction_pool
-            .add_transaction(transaction, consensus.get_virtual_daa_score(), priority, transaction_size)?
-            .mtx
-            .tx
-            .clone();
+        let accepted_transaction =
+            self.transaction_pool.add_transaction(transaction, consensus.get_virtual_daa_score(), priority, transaction_size)?;
+        if let Some(ref notifier) = self.notifier {
+            notifier.notify(&accepted_transaction.mtx, MempoolChangeKind::Added);
+        }
+        let accepted_transaction = accepted_transaction.mtx.tx.clone();
         Ok(TransactionPostValidation { removed: removed_transaction, accepted: Some(accepted_transaction) })
     }
 
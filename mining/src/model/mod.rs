@@ -3,6 +3,7 @@ use std::collections::HashSet;
 
 pub mod candidate_tx;
 pub mod owner_txs;
+pub mod persisted_tx;
 pub mod topological_index;
 pub mod topological_sort;
 pub mod tx_insert;
@@ -0,0 +1,28 @@
+use crate::mempool::tx::Priority;
+use kaspa_consensus_core::tx::{Transaction, TransactionId};
+use kaspa_utils::mem_size::MemSizeEstimator;
+use serde::{Deserialize, Serialize};
+
+/// A mempool transaction as persisted across node restarts.
+///
+/// Only the transaction itself is kept. UTXO entries, fee and mass are computed again
+/// when the transaction is revalidated on restore.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PersistedTransaction {
+    pub transaction: Transaction,
+    pub priority: Priority,
+    /// Whether the transaction was held in the orphan pool when persisted
+    pub is_orphan: bool,
+}
+
+impl PersistedTransaction {
+    pub fn new(transaction: Transaction, priority: Priority, is_orphan: bool) -> Self {
+        Self { transaction, priority, is_orphan }
+    }
+
+    pub fn id(&self) -> TransactionId {
+        self.transaction.id()
+    }
+}
+
+impl MemSizeEstimator for PersistedTransaction {}
@@ -0,0 +1,112 @@
+use crate::manager::MiningManagerProxy;
+use kaspa_consensusmanager::{spawn_blocking, ConsensusManager};
+use kaspa_core::{
+    info,
+    task::{
+        service::{AsyncService, AsyncServiceFuture},
+        tick::{TickReason, TickService},
+    },
+    trace, warn,
+};
+use parking_lot::RwLock;
+use std::{sync::Arc, time::Duration};
+use store::{MempoolSnapshotStore, MempoolSnapshotStoreReader};
+
+pub mod store;
+
+pub use store::DbMempoolSnapshotStore;
+
+const SERVICE: &str = "mempool-persistence";
+
+/// Default interval between two periodic mempool snapshots
+pub const DEFAULT_SNAPSHOT_INTERVAL: Duration = Duration::from_secs(300);
+
+/// Persists the mempool across node restarts.
+///
+/// The mempool is restored from the latest snapshot on startup and is then written
+/// to the store periodically and once more on shutdown.
+pub struct MempoolPersistence {
+    mining_manager: MiningManagerProxy,
+    consensus_manager: Arc<ConsensusManager>,
+    store: Arc<RwLock<DbMempoolSnapshotStore>>,
+    snapshot_interval: Duration,
+    tick_service: Arc<TickService>,
+}
+
+impl MempoolPersistence {
+    pub fn new(
+        mining_manager: MiningManagerProxy,
+        consensus_manager: Arc<ConsensusManager>,
+        store: DbMempoolSnapshotStore,
+        snapshot_interval: Duration,
+        tick_service: Arc<TickService>,
+    ) -> Self {
+        Self { mining_manager, consensus_manager, store: Arc::new(RwLock::new(store)), snapshot_interval, tick_service }
+    }
+
+    async fn restore(&self) {
+        let store = self.store.clone();
+        let transactions = match spawn_blocking(move || store.read().get_all()).await.unwrap() {
+            Ok(transactions) => transactions,
+            Err(err) => {
+                warn!("Failed to read the mempool snapshot, starting with an empty mempool: {}", err);
+                return;
+            }
+        };
+        if transactions.is_empty() {
+            return;
+        }
+        let count = transactions.len();
+        let session = self.consensus_manager.consensus().session().await;
+        let restored = self.mining_manager.clone().restore_persisted_transactions(&session, transactions).await;
+        info!("Restored {} out of {} transactions from the mempool snapshot", restored, count);
+    }
+
+    async fn snapshot(&self) {
+        let transactions = self.mining_manager.clone().get_all_persisted_transactions().await;
+        let count = transactions.len();
+        let store = self.store.clone();
+        match spawn_blocking(move || store.write().set_all(transactions)).await.unwrap() {
+            Ok(()) => trace!("[{}] persisted {} mempool transactions", SERVICE, count),
+            Err(err) => warn!("Failed to persist the mempool snapshot: {}", err),
+        }
+    }
+
+    pub async fn worker(self: &Arc<MempoolPersistence>) {
+        self.restore().await;
+        loop {
+            let reason = self.tick_service.tick(self.snapshot_interval).await;
+            self.snapshot().await;
+            if let TickReason::Shutdown = reason {
+                info!("Persisted the mempool snapshot");
+                break;
+            }
+        }
+        trace!("{} worker exiting", SERVICE);
+    }
+}
+
+// service trait implementation for MempoolPersistence
+impl AsyncService for MempoolPersistence {
+    fn ident(self: Arc<Self>) -> &'static str {
+        SERVICE
+    }
+
+    fn start(self: Arc<Self>) -> AsyncServiceFuture {
+        Box::pin(async move {
+            self.worker().await;
+            Ok(())
+        })
+    }
+
+    fn signal_exit(self: Arc<Self>) {
+        trace!("sending an exit signal to {}", SERVICE);
+    }
+
+    fn stop(self: Arc<Self>) -> AsyncServiceFuture {
+        Box::pin(async move {
+            trace!("{} stopped", SERVICE);
+            Ok(())
+        })
+    }
+}
@@ -0,0 +1,57 @@
+use crate::model::persisted_tx::PersistedTransaction;
+use kaspa_consensus_core::tx::TransactionId;
+use kaspa_database::{
+    prelude::{BatchDbWriter, CachePolicy, CachedDbAccess, StoreError, StoreResult, DB},
+    registry::DatabaseStorePrefixes,
+};
+use rocksdb::WriteBatch;
+use std::sync::Arc;
+
+pub trait MempoolSnapshotStoreReader {
+    fn get_all(&self) -> StoreResult<Vec<PersistedTransaction>>;
+}
+
+pub trait MempoolSnapshotStore: MempoolSnapshotStoreReader {
+    /// Replaces the stored snapshot with `transactions` in a single atomic write
+    fn set_all(&mut self, transactions: Vec<PersistedTransaction>) -> StoreResult<()>;
+}
+
+/// A DB store holding the latest snapshot of the mempool
+#[derive(Clone)]
+pub struct DbMempoolSnapshotStore {
+    db: Arc<DB>,
+    access: CachedDbAccess<TransactionId, PersistedTransaction>,
+}
+
+impl DbMempoolSnapshotStore {
+    pub fn new(db: Arc<DB>) -> Self {
+        // The snapshot is only read once on startup so there is no point in caching it
+        Self {
+            db: Arc::clone(&db),
+            access: CachedDbAccess::new(db, CachePolicy::Empty, DatabaseStorePrefixes::MempoolSnapshot.into()),
+        }
+    }
+}
+
+impl MempoolSnapshotStoreReader for DbMempoolSnapshotStore {
+    fn get_all(&self) -> StoreResult<Vec<PersistedTransaction>> {
+        self.access
+            .iterator()
+            .map(|res| res.map(|(_, transaction)| transaction))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|err| StoreError::DataInconsistency(err.to_string()))
+    }
+}
+
+impl MempoolSnapshotStore for DbMempoolSnapshotStore {
+    fn set_all(&mut self, transactions: Vec<PersistedTransaction>) -> StoreResult<()> {
+        let mut batch = WriteBatch::default();
+        self.access.delete_all(BatchDbWriter::new(&mut batch))?;
+        self.access.write_many_without_cache(
+            BatchDbWriter::new(&mut batch),
+            &mut transactions.into_iter().map(|transaction| (transaction.id(), transaction)),
+        )?;
+        self.db.write(batch)?;
+        Ok(())
+    }
+}
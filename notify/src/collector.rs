@@ -128,7 +128,7 @@ mod tests {
         notifier::test_helpers::NotifyMock,
         subscription::{
             context::SubscriptionContext,
-            single::{OverallSubscription, UtxosChangedSubscription, VirtualChainChangedSubscription},
+            single::{MempoolChangedSubscription, OverallSubscription, UtxosChangedSubscription, VirtualChainChangedSubscription},
         },
     };
     use derive_more::Display;
@@ -171,6 +171,10 @@ mod tests {
             unimplemented!()
         }
 
+        fn apply_mempool_changed_subscription(&self, _: &MempoolChangedSubscription, _: &SubscriptionContext) -> Option<Self> {
+            unimplemented!()
+        }
+
         fn event_type(&self) -> EventType {
             unimplemented!()
         }
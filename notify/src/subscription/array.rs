@@ -13,6 +13,7 @@ impl ArrayBuilder {
             let event_type = EventType::try_from(i).unwrap();
             let subscription: DynSubscription = match event_type {
                 EventType::VirtualChainChanged => Arc::<single::VirtualChainChangedSubscription>::default(),
+                EventType::MempoolChanged => Arc::<single::MempoolChangedSubscription>::default(),
                 EventType::UtxosChanged => Arc::new(single::UtxosChangedSubscription::with_capacity(
                     single::UtxosChangedState::None,
                     listener_id,
@@ -3,7 +3,7 @@ use crate::{
     error::Result,
     events::EventType,
     listener::ListenerId,
-    scope::{Scope, UtxosChangedScope, VirtualChainChangedScope},
+    scope::{MempoolChangedScope, Scope, UtxosChangedScope, VirtualChainChangedScope},
     subscription::{
         context::SubscriptionContext, BroadcastingSingle, Command, DynSubscription, Mutation, MutationOutcome, MutationPolicies,
         Single, Subscription, UtxosChangedMutationPolicy,
@@ -15,7 +15,7 @@ use kaspa_consensus_core::tx::ScriptPublicKey;
 use kaspa_core::trace;
 use parking_lot::{RwLock, RwLockReadGuard, RwLockWriteGuard};
 use std::{
-    collections::hash_set,
+    collections::{hash_set, BTreeSet},
     fmt::{Debug, Display},
     hash::{Hash, Hasher},
     sync::{
@@ -172,6 +172,101 @@ impl Subscription for VirtualChainChangedSubscription {
     }
 }
 
+/// Subscription to MempoolChanged notifications
+///
+/// An active subscription with an empty address set covers all addresses.
+///
+/// Mutations propagated upwards are always wildcards since the address filtering
+/// is fully achieved at the listener level.
+#[derive(Eq, PartialEq, Hash, Clone, Debug, Default)]
+pub struct MempoolChangedSubscription {
+    active: bool,
+    addresses: Arc<BTreeSet<Address>>,
+}
+
+impl MempoolChangedSubscription {
+    pub fn new(active: bool, addresses: BTreeSet<Address>) -> Self {
+        Self { active, addresses: Arc::new(addresses) }
+    }
+
+    pub fn to_all(&self) -> bool {
+        self.active && self.addresses.is_empty()
+    }
+
+    pub fn contains_address(&self, address: &Address) -> bool {
+        self.addresses.contains(address)
+    }
+
+    pub fn addresses(&self) -> &BTreeSet<Address> {
+        &self.addresses
+    }
+}
+
+impl Single for MempoolChangedSubscription {
+    fn apply_mutation(
+        &self,
+        _: &Arc<dyn Single>,
+        mutation: Mutation,
+        _: MutationPolicies,
+        _: &SubscriptionContext,
+    ) -> Result<MutationOutcome> {
+        assert_eq!(self.event_type(), mutation.event_type());
+        let mutated = if let Scope::MempoolChanged(scope) = mutation.scope {
+            match (mutation.command, scope.addresses.is_empty()) {
+                // Mutation All => new state All
+                (Command::Start, true) => Some(Self::new(true, BTreeSet::new())),
+                // Mutation Add(A) => new state Selected(A) if current state is All, Selected(A ∪ S) otherwise
+                (Command::Start, false) => {
+                    let mut addresses = if self.to_all() { BTreeSet::new() } else { (*self.addresses).clone() };
+                    addresses.extend(scope.addresses);
+                    Some(Self::new(true, addresses))
+                }
+                // Mutation None => new state None
+                (Command::Stop, true) => Some(Self::default()),
+                // Mutation Remove(R) => no change if current state is All, Selected(S – R) or None otherwise
+                (Command::Stop, false) if self.to_all() => None,
+                (Command::Stop, false) => {
+                    let mut addresses = (*self.addresses).clone();
+                    scope.addresses.iter().for_each(|x| {
+                        addresses.remove(x);
+                    });
+                    Some(Self::new(!addresses.is_empty(), addresses))
+                }
+            }
+        } else {
+            None
+        };
+        let outcome = match mutated.filter(|x| x != self) {
+            Some(mutated) => {
+                let mutations = match (self.active, mutated.active) {
+                    (false, true) => vec![Mutation::new(Command::Start, MempoolChangedScope::default().into())],
+                    (true, false) => vec![Mutation::new(Command::Stop, MempoolChangedScope::default().into())],
+                    _ => vec![],
+                };
+                MutationOutcome::with_mutated(Arc::new(mutated), mutations)
+            }
+            None => MutationOutcome::new(),
+        };
+        Ok(outcome)
+    }
+}
+
+impl Subscription for MempoolChangedSubscription {
+    #[inline(always)]
+    fn event_type(&self) -> EventType {
+        EventType::MempoolChanged
+    }
+
+    #[inline(always)]
+    fn active(&self) -> bool {
+        self.active
+    }
+
+    fn scope(&self, _context: &SubscriptionContext) -> Scope {
+        MempoolChangedScope::new(self.addresses.iter().cloned().collect()).into()
+    }
+}
+
 static UTXOS_CHANGED_SUBSCRIPTIONS: AtomicUsize = AtomicUsize::new(0);
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -924,6 +1019,116 @@ mod tests {
         tests.run(&context)
     }
 
+    #[test]
+    fn test_mempool_changed_mutation() {
+        let context = SubscriptionContext::new();
+        let a_stock = get_3_addresses(true);
+
+        let av = |indexes: &[usize]| indexes.iter().map(|idx| (a_stock[*idx]).clone()).collect::<Vec<_>>();
+        let s = |active: bool, indexes: &[usize]| -> DynSubscription {
+            Arc::new(MempoolChangedSubscription::new(active, av(indexes).into_iter().collect()))
+        };
+        let m = |command: Command, indexes: &[usize]| -> Mutation {
+            Mutation { command, scope: Scope::MempoolChanged(MempoolChangedScope::new(av(indexes))) }
+        };
+
+        // Subscriptions
+        let none = || s(false, &[]);
+        let selected_0 = || s(true, &[0]);
+        let selected_01 = || s(true, &[0, 1]);
+        let all = || s(true, &[]);
+
+        // Mutations
+        let start_all = || m(Command::Start, &[]);
+        let stop_all = || m(Command::Stop, &[]);
+        let start_0 = || m(Command::Start, &[0]);
+        let start_1 = || m(Command::Start, &[1]);
+        let stop_0 = || m(Command::Stop, &[0]);
+        let stop_1 = || m(Command::Stop, &[1]);
+
+        // Tests
+        let tests = MutationTests::new(vec![
+            MutationTest {
+                name: "MempoolChangedSubscription None to All",
+                state: none(),
+                mutation: start_all(),
+                new_state: all(),
+                outcome: MutationOutcome::with_mutated(all(), vec![start_all()]),
+            },
+            MutationTest {
+                name: "MempoolChangedSubscription None to Selected 0",
+                state: none(),
+                mutation: start_0(),
+                new_state: selected_0(),
+                outcome: MutationOutcome::with_mutated(selected_0(), vec![start_all()]),
+            },
+            MutationTest {
+                name: "MempoolChangedSubscription None to None (stop 0)",
+                state: none(),
+                mutation: stop_0(),
+                new_state: none(),
+                outcome: MutationOutcome::new(),
+            },
+            MutationTest {
+                name: "MempoolChangedSubscription Selected 0 to Selected 01",
+                state: selected_0(),
+                mutation: start_1(),
+                new_state: selected_01(),
+                outcome: MutationOutcome::with_mutated(selected_01(), vec![]),
+            },
+            MutationTest {
+                name: "MempoolChangedSubscription Selected 01 to Selected 0",
+                state: selected_01(),
+                mutation: stop_1(),
+                new_state: selected_0(),
+                outcome: MutationOutcome::with_mutated(selected_0(), vec![]),
+            },
+            MutationTest {
+                name: "MempoolChangedSubscription Selected 0 to Selected 0 (stop 1)",
+                state: selected_0(),
+                mutation: stop_1(),
+                new_state: selected_0(),
+                outcome: MutationOutcome::new(),
+            },
+            MutationTest {
+                name: "MempoolChangedSubscription Selected 0 to None (stop 0)",
+                state: selected_0(),
+                mutation: stop_0(),
+                new_state: none(),
+                outcome: MutationOutcome::with_mutated(none(), vec![stop_all()]),
+            },
+            MutationTest {
+                name: "MempoolChangedSubscription Selected 01 to All",
+                state: selected_01(),
+                mutation: start_all(),
+                new_state: all(),
+                outcome: MutationOutcome::with_mutated(all(), vec![]),
+            },
+            MutationTest {
+                name: "MempoolChangedSubscription All to Selected 0",
+                state: all(),
+                mutation: start_0(),
+                new_state: selected_0(),
+                outcome: MutationOutcome::with_mutated(selected_0(), vec![]),
+            },
+            MutationTest {
+                name: "MempoolChangedSubscription All to All (stop 0)",
+                state: all(),
+                mutation: stop_0(),
+                new_state: all(),
+                outcome: MutationOutcome::new(),
+            },
+            MutationTest {
+                name: "MempoolChangedSubscription All to None",
+                state: all(),
+                mutation: stop_all(),
+                new_state: none(),
+                outcome: MutationOutcome::with_mutated(none(), vec![stop_all()]),
+            },
+        ]);
+        tests.run(&context)
+    }
+
     #[test]
     fn test_utxos_changed_mutation() {
         let context = SubscriptionContext::new();
@@ -1,9 +1,9 @@
 use crate::flowcontext::{
     orphans::{OrphanBlocksPool, OrphanOutput},
     process_queue::ProcessQueue,
-    transactions::TransactionsSpread,
+    transactions::{ReconciliationSet, TransactionsSpread, TxRelayCounters},
 };
-use crate::{v5, v6};
+use crate::{v5, v6, v7};
 use async_trait::async_trait;
 use futures::future::join_all;
 use kaspa_addressmanager::AddressManager;
@@ -58,7 +58,7 @@ use tokio_stream::{wrappers::UnboundedReceiverStream, StreamExt};
 use uuid::Uuid;
 
 /// The P2P protocol version. Currently the only one supported.
-const PROTOCOL_VERSION: u32 = 6;
+const PROTOCOL_VERSION: u32 = 7;
 
 /// See `check_orphan_resolution_range`
 const BASELINE_ORPHAN_RESOLUTION_RANGE: u32 = 5;
@@ -216,6 +216,7 @@ pub struct FlowContextInner {
     orphans_pool: AsyncRwLock<OrphanBlocksPool>,
     shared_block_requests: Arc<Mutex<HashMap<Hash, RequestScopeMetadata>>>,
     transactions_spread: AsyncRwLock<TransactionsSpread>,
+    tx_relay_counters: Arc<TxRelayCounters>,
     shared_transaction_requests: Arc<Mutex<HashMap<TransactionId, RequestScopeMetadata>>>,
     is_ibd_running: Arc<AtomicBool>,
     ibd_metadata: Arc<RwLock<Option<IbdMetadata>>>,
@@ -305,6 +306,7 @@ impl FlowContext {
         notification_root: Arc<ConsensusNotificationRoot>,
     ) -> Self {
         let hub = Hub::new();
+        let tx_relay_counters = Arc::new(TxRelayCounters::default());
 
         let orphan_resolution_range = BASELINE_ORPHAN_RESOLUTION_RANGE + (config.bps() as f64).log2().ceil() as u32;
 
@@ -317,7 +319,8 @@ impl FlowContext {
                 consensus_manager,
                 orphans_pool: AsyncRwLock::new(OrphanBlocksPool::new(max_orphans)),
                 shared_block_requests: Arc::new(Mutex::new(HashMap::new())),
-                transactions_spread: AsyncRwLock::new(TransactionsSpread::new(hub.clone())),
+                transactions_spread: AsyncRwLock::new(TransactionsSpread::new(hub.clone(), tx_relay_counters.clone())),
+                tx_relay_counters,
                 shared_transaction_requests: Arc::new(Mutex::new(HashMap::new())),
                 is_ibd_running: Default::default(),
                 ibd_metadata: Default::default(),
@@ -365,6 +368,13 @@ impl FlowContext {
         self.connection_manager.read().clone()
     }
 
+    /// Reports the error a flow of the peer failed with, adding to the misbehavior score of the peer
+    pub async fn report_misbehavior(&self, router: &Router, err: &ProtocolError) {
+        if let Some(connection_manager) = self.connection_manager() {
+            connection_manager.report_misbehavior(&router.into(), err).await;
+        }
+    }
+
     pub fn consensus(&self) -> ConsensusInstance {
         self.consensus_manager.consensus()
     }
@@ -373,6 +383,10 @@ impl FlowContext {
         &self.hub
     }
 
+    pub fn tx_relay_counters(&self) -> &Arc<TxRelayCounters> {
+        &self.tx_relay_counters
+    }
+
     pub fn mining_manager(&self) -> &MiningManagerProxy {
         &self.mining_manager
     }
@@ -686,6 +700,11 @@ impl FlowContext {
     pub async fn broadcast_transactions<I: IntoIterator<Item = TransactionId>>(&self, transaction_ids: I, should_throttle: bool) {
         self.transactions_spread.write().await.broadcast_transactions(transaction_ids, should_throttle).await
     }
+
+    /// Registers a peer reconciling its transaction set with us, see [`TransactionsSpread::register_reconciliation_peer`]
+    pub async fn register_reconciliation_peer(&self, peer_key: PeerKey, is_outbound: bool) -> ReconciliationSet {
+        self.transactions_spread.write().await.register_reconciliation_peer(peer_key, is_outbound)
+    }
 }
 
 #[async_trait]
@@ -697,6 +716,15 @@ impl ConnectionInitializer for FlowContext {
         // We start the router receive loop only after we registered to handshake routes
         router.start();
 
+        // Peers dialed by host name share the ip of the proxy, so they are not subject to ip bans
+        if router.named_address().is_none() {
+            if let Some(connection_manager) = self.connection_manager() {
+                if connection_manager.is_banned(&router.net_address()).await {
+                    return Err(ProtocolError::Other("peer is banned"));
+                }
+            }
+        }
+
         let network_name = self.config.network_name();
 
         let local_address = self.address_manager.lock().best_local_address();
@@ -705,6 +733,9 @@ impl ConnectionInitializer for FlowContext {
         // Subnets are not currently supported
         let mut self_version_message = Version::new(local_address, self.node_id, network_name.clone(), None, PROTOCOL_VERSION);
         self_version_message.add_user_agent(name(), version(), &self.config.user_agent_comments);
+        // A fresh nonzero salt per connection signals support of transaction set reconciliation
+        let reconciliation_salt = rand::random::<u64>().max(1);
+        self_version_message.tx_reconciliation_salt = Some(reconciliation_salt);
         // TODO: get number of live services
         // TODO: disable_relay_tx from config/cmd
 
@@ -732,7 +763,11 @@ impl ConnectionInitializer for FlowContext {
 
         // Register all flows according to version
         let (flows, applied_protocol_version) = match peer_version.protocol_version {
-            v if v >= PROTOCOL_VERSION => (v6::register(self.clone(), router.clone()), PROTOCOL_VERSION),
+            v if v >= PROTOCOL_VERSION => {
+                let reconciliation_salts = peer_version.tx_reconciliation_salt.map(|remote_salt| (reconciliation_salt, remote_salt));
+                (v7::register(self.clone(), router.clone(), reconciliation_salts), PROTOCOL_VERSION)
+            }
+            6 => (v6::register(self.clone(), router.clone()), 6),
             5 => (v5::register(self.clone(), router.clone()), 5),
             v => return Err(ProtocolError::VersionMismatch(PROTOCOL_VERSION, v)),
         };
@@ -755,18 +790,22 @@ impl ConnectionInitializer for FlowContext {
 
         // Launch all flows. Note we launch only after the ready signal was exchanged
         for flow in flows {
-            flow.launch();
+            flow.launch(self.clone());
         }
 
         if router.is_outbound() || peer_version.address.is_some() {
             let mut address_manager = self.address_manager.lock();
+            let source = router.net_address().ip().into();
 
             if router.is_outbound() {
-                address_manager.add_address(router.net_address().into());
+                match router.named_address() {
+                    Some(named_address) => address_manager.add_named_address(named_address.clone()),
+                    None => address_manager.add_address(router.net_address().into(), source),
+                }
             }
 
             if let Some(peer_ip_address) = peer_version.address {
-                address_manager.add_address(peer_ip_address);
+                address_manager.add_address(peer_ip_address, source);
             }
         }
 
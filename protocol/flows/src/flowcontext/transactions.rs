@@ -5,9 +5,18 @@ use kaspa_core::debug;
 use kaspa_p2p_lib::{
     make_message,
     pb::{kaspad_message::Payload, InvTransactionsMessage, KaspadMessage},
-    Hub,
+    Hub, PeerKey,
+};
+use parking_lot::Mutex;
+use prost::Message;
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Weak,
+    },
+    time::{Duration, Instant},
 };
-use std::time::{Duration, Instant};
 
 /// Interval between mempool scanning tasks (in seconds)
 const SCANNING_TASK_INTERVAL: u64 = 10;
@@ -15,6 +24,37 @@ const REBROADCAST_FREQUENCY: u64 = 3;
 const BROADCAST_INTERVAL: Duration = Duration::from_millis(500);
 pub(crate) const MAX_INV_PER_TX_INV_MSG: usize = 131_072;
 
+/// Maximum number of outbound reconciliation peers still receiving flooded transaction invs
+const MAX_FLOOD_OUTBOUND_PEERS: usize = 4;
+
+/// Maximum number of transactions pending reconciliation with a peer. Transactions beyond are flooded.
+pub(crate) const MAX_RECONCILIATION_SET_SIZE: usize = 32_768;
+
+/// Transaction ids pending reconciliation with a peer
+pub type ReconciliationSet = Arc<Mutex<HashSet<TransactionId>>>;
+
+/// Bytes sent by the node for announcing transactions to its peers
+#[derive(Default, Debug)]
+pub struct TxRelayCounters {
+    /// Bytes of the transaction invs and reconciliation messages sent
+    pub announcement_bytes: AtomicU64,
+    /// Bytes the transaction invs would have cost if flooded to all peers, as done without reconciliation
+    pub flood_bytes: AtomicU64,
+}
+
+impl TxRelayCounters {
+    pub fn add_announcement(&self, msg: &KaspadMessage) {
+        self.announcement_bytes.fetch_add(msg.encoded_len() as u64, Ordering::Relaxed);
+    }
+}
+
+struct ReconciliationPeer {
+    /// Owned by the reconciliation flow of the peer, hence dropped when it disconnects
+    set: Weak<Mutex<HashSet<TransactionId>>>,
+    /// Whether invs are still flooded to this peer
+    flood: bool,
+}
+
 pub struct TransactionsSpread {
     hub: Hub,
     last_scanning_time: Instant,
@@ -22,10 +62,12 @@ pub struct TransactionsSpread {
     scanning_job_count: u64,
     transaction_ids: ProcessQueue<TransactionId>,
     last_broadcast_time: Instant,
+    reconciliation_peers: HashMap<PeerKey, ReconciliationPeer>,
+    counters: Arc<TxRelayCounters>,
 }
 
 impl TransactionsSpread {
-    pub fn new(hub: Hub) -> Self {
+    pub fn new(hub: Hub, counters: Arc<TxRelayCounters>) -> Self {
         Self {
             hub,
             last_scanning_time: Instant::now(),
@@ -33,9 +75,23 @@ impl TransactionsSpread {
             scanning_job_count: 0,
             transaction_ids: ProcessQueue::new(),
             last_broadcast_time: Instant::now(),
+            reconciliation_peers: HashMap::new(),
+            counters,
         }
     }
 
+    /// Registers a peer reconciling its transaction set with us and returns the set of transactions to reconcile.
+    ///
+    /// Invs are no longer flooded to the peer unless it is one of the first [`MAX_FLOOD_OUTBOUND_PEERS`] outbound
+    /// reconciliation peers, which keep the network propagation fast. The peer is unregistered once the set is dropped.
+    pub fn register_reconciliation_peer(&mut self, peer_key: PeerKey, is_outbound: bool) -> ReconciliationSet {
+        self.reconciliation_peers.retain(|_, peer| peer.set.strong_count() > 0);
+        let flood = is_outbound && self.reconciliation_peers.values().filter(|peer| peer.flood).count() < MAX_FLOOD_OUTBOUND_PEERS;
+        let set = ReconciliationSet::default();
+        self.reconciliation_peers.insert(peer_key, ReconciliationPeer { set: Arc::downgrade(&set), flood });
+        set
+    }
+
     /// Returns true if the time has come for running the task of scanning mempool transactions
     /// and if so, mark the task as running.
     pub fn should_run_mempool_scanning_task(&mut self) -> bool {
@@ -84,22 +140,50 @@ impl TransactionsSpread {
             return;
         }
 
+        self.reconciliation_peers.retain(|_, peer| peer.set.strong_count() > 0);
         while !self.transaction_ids.is_empty() {
-            let ids = self.transaction_ids.dequeue_chunk(MAX_INV_PER_TX_INV_MSG).map(|x| x.into()).collect_vec();
-            debug!("Transaction propagation: broadcasting {} transactions", ids.len());
-            let msg = make_message!(Payload::InvTransactions, InvTransactionsMessage { ids });
-            self.broadcast(msg, should_throttle).await;
+            let ids = self.transaction_ids.dequeue_chunk(MAX_INV_PER_TX_INV_MSG).collect_vec();
+            let reconciled = self.enqueue_for_reconciliation(&ids);
+            debug!("Transaction propagation: broadcasting {} transactions, {} peers reconciling them", ids.len(), reconciled.len());
+            let msg =
+                make_message!(Payload::InvTransactions, InvTransactionsMessage { ids: ids.into_iter().map(|x| x.into()).collect() });
+            self.broadcast(msg, should_throttle, &reconciled).await;
         }
 
         self.last_broadcast_time = Instant::now();
     }
 
-    async fn broadcast(&self, msg: KaspadMessage, should_throttle: bool) {
-        if should_throttle {
-            // TODO: Figure out a better number
-            self.hub.broadcast_to_some_peers(msg, 8).await
+    /// Adds the ids to the sets of the reconciliation peers not flooded and returns these peers
+    fn enqueue_for_reconciliation(&self, ids: &[TransactionId]) -> HashSet<PeerKey> {
+        self.reconciliation_peers
+            .iter()
+            .filter(|(_, peer)| !peer.flood)
+            .filter_map(|(key, peer)| {
+                let set = peer.set.upgrade()?;
+                let mut set = set.lock();
+                // A peer falling that much behind gets the invs flooded instead
+                if set.len() + ids.len() > MAX_RECONCILIATION_SET_SIZE {
+                    return None;
+                }
+                set.extend(ids.iter().copied());
+                Some(*key)
+            })
+            .collect()
+    }
+
+    async fn broadcast(&self, msg: KaspadMessage, should_throttle: bool, reconciled: &HashSet<PeerKey>) {
+        // TODO: Figure out a better number
+        const THROTTLED_PEERS: usize = 8;
+        let msg_size = msg.encoded_len() as u64;
+        let (peers, flood_peers) = if should_throttle {
+            (
+                self.hub.broadcast_to_some_peers_except(msg, THROTTLED_PEERS, reconciled).await,
+                self.hub.active_peers_len().min(THROTTLED_PEERS),
+            )
         } else {
-            self.hub.broadcast(msg).await
-        }
+            (self.hub.broadcast_except(msg, reconciled).await, self.hub.active_peers_len())
+        };
+        self.counters.announcement_bytes.fetch_add(msg_size * peers as u64, Ordering::Relaxed);
+        self.counters.flood_bytes.fetch_add(msg_size * flood_peers as u64, Ordering::Relaxed);
     }
 }
@@ -1,4 +1,4 @@
-use std::sync::Arc;
+use std::{net::SocketAddr, sync::Arc};
 
 use kaspa_addressmanager::NetAddress;
 use kaspa_connectionmanager::ConnectionManager;
@@ -25,6 +25,7 @@ pub struct P2pService {
     default_port: u16,
     shutdown: SingleTrigger,
     counters: Arc<TowerConnectionCounters>,
+    proxy: Option<SocketAddr>,
 }
 
 impl P2pService {
@@ -38,6 +39,7 @@ impl P2pService {
         dns_seeders: &'static [&'static str],
         default_port: u16,
         counters: Arc<TowerConnectionCounters>,
+        proxy: Option<SocketAddr>,
     ) -> Self {
         Self {
             flow_context,
@@ -50,6 +52,7 @@ impl P2pService {
             dns_seeders,
             default_port,
             counters,
+            proxy,
         }
     }
 }
@@ -65,9 +68,14 @@ impl AsyncService for P2pService {
         // Prepare a shutdown signal receiver
         let shutdown_signal = self.shutdown.listener.clone();
 
-        let p2p_adaptor =
-            Adaptor::bidirectional(self.listen, self.flow_context.hub().clone(), self.flow_context.clone(), self.counters.clone())
-                .unwrap();
+        let p2p_adaptor = Adaptor::bidirectional(
+            self.listen,
+            self.flow_context.hub().clone(),
+            self.flow_context.clone(),
+            self.counters.clone(),
+            self.proxy,
+        )
+        .unwrap();
         let connection_manager = ConnectionManager::new(
             p2p_adaptor.clone(),
             self.outbound_target,
@@ -1,10 +1,10 @@
 use crate::{flow_context::FlowContext, flow_trait::Flow};
 use itertools::Itertools;
-use kaspa_addressmanager::NetAddress;
+use kaspa_addressmanager::{NamedNetAddress, NetAddress};
 use kaspa_p2p_lib::{
     common::ProtocolError,
     dequeue, dequeue_with_timeout, make_message,
-    pb::{kaspad_message::Payload, AddressesMessage, RequestAddressesMessage},
+    pb::{self, kaspad_message::Payload, AddressesMessage, RequestAddressesMessage},
     IncomingRoute, Router,
 };
 use kaspa_utils::networking::IpAddress;
@@ -14,6 +14,9 @@ use std::sync::Arc;
 /// The maximum number of addresses that are sent in a single kaspa Addresses message.
 const MAX_ADDRESSES_SEND: usize = 1000;
 
+/// The maximum number of addresses designated by host name among the addresses of a single kaspa Addresses message.
+const MAX_NAMED_ADDRESSES_SEND: usize = MAX_ADDRESSES_SEND / 4;
+
 /// The maximum number of addresses that can be received in a single kaspa Addresses response.
 /// If a peer exceeds this value we consider it a protocol error.
 const MAX_ADDRESSES_RECEIVE: usize = 2500;
@@ -22,6 +25,8 @@ pub struct ReceiveAddressesFlow {
     ctx: FlowContext,
     router: Arc<Router>,
     incoming_route: IncomingRoute,
+    /// Whether the peer may share addresses designated by host name (protocol version 7 and above)
+    named_addresses: bool,
 }
 
 #[async_trait::async_trait]
@@ -36,8 +41,8 @@ impl Flow for ReceiveAddressesFlow {
 }
 
 impl ReceiveAddressesFlow {
-    pub fn new(ctx: FlowContext, router: Arc<Router>, incoming_route: IncomingRoute) -> Self {
-        Self { ctx, router, incoming_route }
+    pub fn new(ctx: FlowContext, router: Arc<Router>, incoming_route: IncomingRoute, named_addresses: bool) -> Self {
+        Self { ctx, router, incoming_route, named_addresses }
     }
 
     async fn start_impl(&mut self) -> Result<(), ProtocolError> {
@@ -49,13 +54,21 @@ impl ReceiveAddressesFlow {
             .await?;
 
         let msg = dequeue_with_timeout!(self.incoming_route, Payload::Addresses)?;
-        let address_list: Vec<(IpAddress, u16)> = msg.try_into()?;
-        if address_list.len() > MAX_ADDRESSES_RECEIVE {
-            return Err(ProtocolError::OtherOwned(format!("address count {} exceeded {}", address_list.len(), MAX_ADDRESSES_RECEIVE)));
+        let (address_list, named_address_list): (Vec<(IpAddress, u16)>, Vec<NamedNetAddress>) = msg.try_into()?;
+        let address_count = address_list.len() + named_address_list.len();
+        if address_count > MAX_ADDRESSES_RECEIVE {
+            return Err(ProtocolError::LimitExceeded("address", address_count, MAX_ADDRESSES_RECEIVE));
+        }
+        if !self.named_addresses && !named_address_list.is_empty() {
+            return Err(ProtocolError::Other("peer sent host name addresses prior to protocol version 7"));
         }
+        let source = self.router.net_address().ip().into();
         let mut amgr_lock = self.ctx.address_manager.lock();
         for (ip, port) in address_list {
-            amgr_lock.add_address(NetAddress::new(ip, port))
+            amgr_lock.add_address(NetAddress::new(ip, port), source)
+        }
+        for address in named_address_list {
+            amgr_lock.add_named_address(address)
         }
 
         Ok(())
@@ -66,6 +79,9 @@ pub struct SendAddressesFlow {
     ctx: FlowContext,
     router: Arc<Router>,
     incoming_route: IncomingRoute,
+    /// Whether addresses designated by host name may be shared with the peer. Peers prior to protocol
+    /// version 7 fail to parse them, hence they are only sent to peers of version 7 and above
+    named_addresses: bool,
 }
 
 #[async_trait::async_trait]
@@ -80,18 +96,28 @@ impl Flow for SendAddressesFlow {
 }
 
 impl SendAddressesFlow {
-    pub fn new(ctx: FlowContext, router: Arc<Router>, incoming_route: IncomingRoute) -> Self {
-        Self { ctx, router, incoming_route }
+    pub fn new(ctx: FlowContext, router: Arc<Router>, incoming_route: IncomingRoute, named_addresses: bool) -> Self {
+        Self { ctx, router, incoming_route, named_addresses }
     }
 
     async fn start_impl(&mut self) -> Result<(), ProtocolError> {
         loop {
             dequeue!(self.incoming_route, Payload::RequestAddresses)?;
-            let addresses = self.ctx.address_manager.lock().iterate_addresses().collect_vec();
-            let address_list = addresses
-                .choose_multiple(&mut rand::thread_rng(), MAX_ADDRESSES_SEND)
-                .map(|addr| (addr.ip, addr.port).into())
+            let (addresses, named_addresses) = {
+                let amgr_lock = self.ctx.address_manager.lock();
+                let named_addresses =
+                    if self.named_addresses { amgr_lock.iterate_named_addresses().cloned().collect_vec() } else { vec![] };
+                (amgr_lock.iterate_addresses().collect_vec(), named_addresses)
+            };
+            let mut address_list: Vec<pb::NetAddress> = named_addresses
+                .choose_multiple(&mut rand::thread_rng(), MAX_NAMED_ADDRESSES_SEND)
+                .map(|addr| addr.clone().into())
                 .collect();
+            address_list.extend(
+                addresses
+                    .choose_multiple(&mut rand::thread_rng(), MAX_ADDRESSES_SEND - address_list.len())
+                    .map(|addr| pb::NetAddress::from((addr.ip, addr.port))),
+            );
             self.router.enqueue(make_message!(Payload::Addresses, AddressesMessage { address_list })).await?;
         }
     }
@@ -0,0 +1,95 @@
+use crate::{flow_context::FlowContext, flow_trait::Flow};
+use kaspa_consensus_core::block::Block;
+use kaspa_core::debug;
+use kaspa_hashes::Hash;
+use kaspa_p2p_lib::{
+    common::ProtocolError,
+    convert::model::compact::CompactBlock,
+    make_response,
+    pb::{kaspad_message::Payload, BlockTransactionsMessage},
+    IncomingRoute, Router,
+};
+use std::sync::Arc;
+
+/// Serves compact blocks, and the transactions of a compact block the peer could not rebuild from its mempool
+pub struct HandleCompactBlockRequests {
+    ctx: FlowContext,
+    router: Arc<Router>,
+    incoming_route: IncomingRoute,
+    /// The last block sent in compact form, which is expectedly the target of the next transactions request
+    last_block: Option<Block>,
+}
+
+#[async_trait::async_trait]
+impl Flow for HandleCompactBlockRequests {
+    fn router(&self) -> Option<Arc<Router>> {
+        Some(self.router.clone())
+    }
+
+    async fn start(&mut self) -> Result<(), ProtocolError> {
+        self.start_impl().await
+    }
+}
+
+impl HandleCompactBlockRequests {
+    pub fn new(ctx: FlowContext, router: Arc<Router>, incoming_route: IncomingRoute) -> Self {
+        Self { ctx, router, incoming_route, last_block: None }
+    }
+
+    async fn start_impl(&mut self) -> Result<(), ProtocolError> {
+        loop {
+            let Some(msg) = self.incoming_route.recv().await else {
+                return Err(ProtocolError::ConnectionClosed);
+            };
+            match msg.payload {
+                Some(Payload::RequestCompactBlocks(payload)) => {
+                    let hashes: Vec<Hash> = payload.try_into()?;
+                    for hash in hashes {
+                        let block = self.get_block(hash).await?;
+                        // A fresh nonce per block keeps short id collisions from being reproducible
+                        let compact = CompactBlock::new(&block, rand::random());
+                        self.router.enqueue(make_response!(Payload::CompactBlock, (&compact).into(), msg.request_id)).await?;
+                        debug!("relayed compact block with hash {} to peer {}", hash, self.router);
+                        self.last_block = Some(block);
+                    }
+                }
+                Some(Payload::RequestBlockTransactions(payload)) => {
+                    let (hash, indexes): (Hash, Vec<u32>) = payload.try_into()?;
+                    let block = self.get_block(hash).await?;
+                    let transactions = indexes
+                        .iter()
+                        .map(|&index| {
+                            block.transactions.get(index as usize).map(|tx| tx.into()).ok_or_else(|| {
+                                ProtocolError::OtherOwned(format!(
+                                    "requested transaction index {} out of range of block {}",
+                                    index, hash
+                                ))
+                            })
+                        })
+                        .collect::<Result<Vec<_>, _>>()?;
+                    self.router
+                        .enqueue(make_response!(
+                            Payload::BlockTransactions,
+                            BlockTransactionsMessage { block_hash: Some(hash.into()), transactions },
+                            msg.request_id
+                        ))
+                        .await?;
+                    debug!("relayed {} transactions of block {} to peer {}", indexes.len(), hash, self.router);
+                }
+                _ => {
+                    return Err(ProtocolError::UnexpectedMessage(
+                        stringify!(Payload::RequestCompactBlocks | Payload::RequestBlockTransactions),
+                        msg.payload.as_ref().map(|v| v.into()),
+                    ))
+                }
+            }
+        }
+    }
+
+    async fn get_block(&self, hash: Hash) -> Result<Block, ProtocolError> {
+        match self.last_block {
+            Some(ref block) if block.hash() == hash => Ok(block.clone()),
+            _ => Ok(self.ctx.consensus().unguarded_session().async_get_block(hash).await?),
+        }
+    }
+}
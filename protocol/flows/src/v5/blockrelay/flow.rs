@@ -3,18 +3,27 @@ use crate::{
     flow_trait::Flow,
     flowcontext::orphans::OrphanOutput,
 };
-use kaspa_consensus_core::{api::BlockValidationFutures, block::Block, blockstatus::BlockStatus, errors::block::RuleError};
+use kaspa_consensus_core::{
+    api::BlockValidationFutures, block::Block, blockstatus::BlockStatus, errors::block::RuleError, tx::Transaction,
+};
 use kaspa_consensusmanager::{BlockProcessingBatch, ConsensusProxy};
 use kaspa_core::debug;
 use kaspa_hashes::Hash;
 use kaspa_p2p_lib::{
     common::ProtocolError,
+    convert::model::compact::CompactBlock,
     dequeue, dequeue_with_timeout, make_message, make_request,
-    pb::{kaspad_message::Payload, InvRelayBlockMessage, RequestBlockLocatorMessage, RequestRelayBlocksMessage},
+    pb::{
+        kaspad_message::Payload, InvRelayBlockMessage, RequestBlockLocatorMessage, RequestBlockTransactionsMessage,
+        RequestCompactBlocksMessage, RequestRelayBlocksMessage,
+    },
     IncomingRoute, Router, SharedIncomingRoute,
 };
 use kaspa_utils::channel::{JobSender, JobTrySendError as TrySendError};
-use std::{collections::VecDeque, sync::Arc};
+use std::{
+    collections::{HashSet, VecDeque},
+    sync::Arc,
+};
 
 pub struct RelayInvMessage {
     hash: Hash,
@@ -63,6 +72,8 @@ pub struct HandleRelayInvsFlow {
     msg_route: IncomingRoute,
     /// A channel sender for sending blocks to be handled by the IBD flow (of this peer)
     ibd_sender: JobSender<Block>,
+    /// Indicates whether relay blocks are requested in compact form, which the peer supports from protocol version 7
+    compact_blocks: bool,
 }
 
 #[async_trait::async_trait]
@@ -83,8 +94,9 @@ impl HandleRelayInvsFlow {
         invs_route: SharedIncomingRoute,
         msg_route: IncomingRoute,
         ibd_sender: JobSender<Block>,
+        compact_blocks: bool,
     ) -> Self {
-        Self { ctx, router, invs_route: TwoWayIncomingRoute::new(invs_route), msg_route, ibd_sender }
+        Self { ctx, router, invs_route: TwoWayIncomingRoute::new(invs_route), msg_route, ibd_sender, compact_blocks }
     }
 
     async fn start_impl(&mut self) -> Result<(), ProtocolError> {
@@ -228,6 +240,12 @@ impl HandleRelayInvsFlow {
         let Some(request_scope) = self.ctx.try_adding_block_request(requested_hash) else {
             return Ok(None);
         };
+        if self.compact_blocks {
+            if let Some(block) = self.request_compact_block(requested_hash, request_id).await? {
+                return Ok(Some((block, request_scope)));
+            }
+            debug!("Compact block {} could not be reconstructed, requesting it in full", requested_hash);
+        }
         self.router
             .enqueue(make_request!(
                 Payload::RequestRelayBlocks,
@@ -244,6 +262,59 @@ impl HandleRelayInvsFlow {
         }
     }
 
+    /// Requests the block in compact form and rebuilds it from the mempool, requesting the transactions
+    /// missing from it. Returns `None` if the rebuilt block does not match the header merkle root, in which
+    /// case the block should be requested in full.
+    async fn request_compact_block(&mut self, requested_hash: Hash, request_id: u32) -> Result<Option<Block>, ProtocolError> {
+        self.router
+            .enqueue(make_request!(
+                Payload::RequestCompactBlocks,
+                RequestCompactBlocksMessage { hashes: vec![requested_hash.into()] },
+                request_id
+            ))
+            .await?;
+        let msg = dequeue_with_timeout!(self.msg_route, Payload::CompactBlock)?;
+        let compact: CompactBlock = msg.try_into()?;
+        if compact.hash() != requested_hash {
+            return Err(ProtocolError::OtherOwned(format!(
+                "requested block hash {} but got compact block {}",
+                requested_hash,
+                compact.hash()
+            )));
+        }
+
+        let hasher = compact.short_id_hasher();
+        let short_ids: HashSet<u64> = compact.short_ids.iter().copied().collect();
+        let known_transactions =
+            self.ctx.mining_manager().clone().get_transactions_by_short_ids(short_ids, move |id| hasher.short_id(id)).await;
+        let mut partial = compact.into_partial_block(&known_transactions)?;
+
+        if !partial.missing_indexes().is_empty() {
+            self.router
+                .enqueue(make_request!(
+                    Payload::RequestBlockTransactions,
+                    RequestBlockTransactionsMessage {
+                        block_hash: Some(requested_hash.into()),
+                        indexes: partial.missing_indexes().to_vec()
+                    },
+                    request_id
+                ))
+                .await?;
+            let msg = dequeue_with_timeout!(self.msg_route, Payload::BlockTransactions)?;
+            let (hash, transactions): (Hash, Vec<Transaction>) = msg.try_into()?;
+            if hash != requested_hash {
+                return Err(ProtocolError::OtherOwned(format!(
+                    "requested transactions of block {} but got transactions of {}",
+                    requested_hash, hash
+                )));
+            }
+            partial.fill(transactions)?;
+        }
+
+        let storage_mass_activated = self.ctx.config.storage_mass_activation.is_active(partial.header.daa_score);
+        Ok(partial.try_into_block(storage_mass_activated))
+    }
+
     /// Process the orphan block. Returns `Some(BlockProcessingBatch)` if the block has no missing roots, where
     /// the batch includes ancestor blocks and their consensus processing batch. This indicates a retry is recommended.
     async fn process_orphan(
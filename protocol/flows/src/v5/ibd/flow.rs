@@ -114,7 +114,12 @@ impl IbdFlow {
             }
             IbdType::DownloadHeadersProof => {
                 drop(session); // Avoid holding the previous consensus throughout the staging IBD
-                let staging = self.ctx.consensus_manager.new_staging_consensus();
+                let Some(reservation) = self.ctx.consensus_manager.try_reserve_staging() else {
+                    return Err(ProtocolError::Other(
+                        "staging consensus is in use by a snapshot import or a finality conflict resolution",
+                    ));
+                };
+                let staging = self.ctx.consensus_manager.new_staging_consensus(&reservation);
                 match self.ibd_with_headers_proof(&staging, negotiation_output.syncer_virtual_selected_parent, &relay_block).await {
                     Ok(()) => {
                         spawn_blocking(|| staging.commit()).await.unwrap();
@@ -110,7 +110,7 @@ impl RelayTransactionsFlow {
             // trace!("Receive an inv message from {} with {} transaction ids", self.router.identity(), inv.len());
 
             if inv.len() > MAX_INV_PER_TX_INV_MSG {
-                return Err(ProtocolError::Other("Number of invs in tx inv message is over the limit"));
+                return Err(ProtocolError::LimitExceeded("transaction inv", inv.len(), MAX_INV_PER_TX_INV_MSG));
             }
 
             let session = self.ctx.consensus().unguarded_session();
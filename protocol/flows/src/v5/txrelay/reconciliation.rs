@@ -0,0 +1,157 @@
+use crate::{
+    flow_context::FlowContext,
+    flow_trait::Flow,
+    flowcontext::transactions::{ReconciliationSet, MAX_INV_PER_TX_INV_MSG},
+};
+use itertools::Itertools;
+use kaspa_consensus_core::tx::TransactionId;
+use kaspa_core::debug;
+use kaspa_p2p_lib::{
+    common::ProtocolError,
+    convert::model::{
+        compact::{ShortIdHasher, ShortTransactionId},
+        reconciliation::{reconciliation_short_id_hasher, TxSketch},
+    },
+    dequeue, dequeue_with_timeout, make_message,
+    pb::{
+        kaspad_message::Payload, InvTransactionsMessage, KaspadMessage, RequestTxReconciliationMessage,
+        TxReconciliationDifferenceMessage, TxReconciliationSketchMessage,
+    },
+    IncomingRoute, Router,
+};
+use std::{collections::HashMap, sync::Arc, time::Duration};
+
+/// Interval between two reconciliations initiated with a peer
+const RECONCILIATION_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Initial estimation of the set difference beyond the set sizes difference, relatively to the smaller set
+const DEFAULT_DIFFERENCE_RATIO: f64 = 0.25;
+
+/// Flow reconciling the transactions announced to and by a peer, in place of flooding invs to it.
+///
+/// The outbound side of the connection periodically requests a sketch of the set of transactions the peer
+/// would have announced since the previous round, decodes the set difference with its own set and announces
+/// the transactions the peer misses, while telling it which ones it misses itself. When the difference cannot
+/// be decoded, both sides announce their whole set.
+pub struct TxReconciliationFlow {
+    ctx: FlowContext,
+    router: Arc<Router>,
+    incoming_route: IncomingRoute,
+    hasher: ShortIdHasher,
+    /// Ratio of the set difference beyond the set sizes difference to the smaller set, as observed in the
+    /// last successful reconciliation (`q` in the Erlay paper)
+    difference_ratio: f64,
+}
+
+#[async_trait::async_trait]
+impl Flow for TxReconciliationFlow {
+    fn router(&self) -> Option<Arc<Router>> {
+        Some(self.router.clone())
+    }
+
+    async fn start(&mut self) -> Result<(), ProtocolError> {
+        self.start_impl().await
+    }
+}
+
+impl TxReconciliationFlow {
+    pub fn new(ctx: FlowContext, router: Arc<Router>, incoming_route: IncomingRoute, local_salt: u64, remote_salt: u64) -> Self {
+        let hasher = reconciliation_short_id_hasher(local_salt, remote_salt);
+        Self { ctx, router, incoming_route, hasher, difference_ratio: DEFAULT_DIFFERENCE_RATIO }
+    }
+
+    async fn start_impl(&mut self) -> Result<(), ProtocolError> {
+        // The set is dropped along with the flow, unregistering the peer
+        let set = self.ctx.register_reconciliation_peer(self.router.key(), self.router.is_outbound()).await;
+        if self.router.is_outbound() {
+            loop {
+                tokio::time::sleep(RECONCILIATION_INTERVAL).await;
+                self.initiate_reconciliation(&set).await?;
+            }
+        } else {
+            loop {
+                self.respond_to_reconciliation(&set).await?;
+            }
+        }
+    }
+
+    async fn initiate_reconciliation(&mut self, set: &ReconciliationSet) -> Result<(), ProtocolError> {
+        let local = self.take_short_ids(set);
+        self.send(make_message!(Payload::RequestTxReconciliation, RequestTxReconciliationMessage { set_size: local.len() as u32 }))
+            .await?;
+        let remote: TxSketch = dequeue_with_timeout!(self.incoming_route, Payload::TxReconciliationSketch)?.try_into()?;
+
+        let mut sketch = TxSketch::matching(&remote);
+        local.keys().for_each(|&id| sketch.insert(id));
+        match sketch.decode_difference(&remote) {
+            Some((local_only, remote_only)) => {
+                debug!(
+                    "Reconciled {} transactions with peer {}, announcing {} and missing {}",
+                    local.len(),
+                    self.router,
+                    local_only.len(),
+                    remote_only.len()
+                );
+                self.send(make_message!(
+                    Payload::TxReconciliationDifference,
+                    TxReconciliationDifferenceMessage { success: true, missing_short_ids: remote_only }
+                ))
+                .await?;
+                self.announce(local_only.iter().filter_map(|id| local.get(id).copied())).await
+            }
+            None => {
+                debug!("Reconciliation with peer {} failed, announcing all {} transactions", self.router, local.len());
+                self.send(make_message!(
+                    Payload::TxReconciliationDifference,
+                    TxReconciliationDifferenceMessage { success: false, missing_short_ids: vec![] }
+                ))
+                .await?;
+                self.announce(local.into_values()).await
+            }
+        }
+    }
+
+    async fn respond_to_reconciliation(&mut self, set: &ReconciliationSet) -> Result<(), ProtocolError> {
+        let remote_size = dequeue!(self.incoming_route, Payload::RequestTxReconciliation)?.set_size as usize;
+        let local = self.take_short_ids(set);
+
+        let (min_size, max_size) = (local.len().min(remote_size), local.len().max(remote_size));
+        let capacity = max_size - min_size + (self.difference_ratio * min_size as f64).ceil() as usize;
+        let mut sketch = TxSketch::with_capacity(capacity);
+        local.keys().for_each(|&id| sketch.insert(id));
+        self.send(make_message!(Payload::TxReconciliationSketch, TxReconciliationSketchMessage { sketch: sketch.to_bytes() })).await?;
+
+        let difference: Option<Vec<ShortTransactionId>> =
+            dequeue_with_timeout!(self.incoming_route, Payload::TxReconciliationDifference)?.try_into()?;
+        match difference {
+            Some(missing) => {
+                let missing = missing.into_iter().filter_map(|id| local.get(&id).copied()).collect_vec();
+                // The transactions the peer announces are those of its set missing from ours
+                let shared = local.len() - missing.len();
+                let difference = missing.len() + remote_size.saturating_sub(shared);
+                if min_size > 0 {
+                    self.difference_ratio = ((difference - (max_size - min_size).min(difference)) as f64 / min_size as f64).min(2.0);
+                }
+                self.announce(missing.into_iter()).await
+            }
+            None => self.announce(local.into_values()).await,
+        }
+    }
+
+    fn take_short_ids(&self, set: &ReconciliationSet) -> HashMap<ShortTransactionId, TransactionId> {
+        std::mem::take(&mut *set.lock()).into_iter().map(|id| (self.hasher.short_id(&id), id)).collect()
+    }
+
+    async fn announce(&self, transaction_ids: impl Iterator<Item = TransactionId>) -> Result<(), ProtocolError> {
+        for chunk in transaction_ids.chunks(MAX_INV_PER_TX_INV_MSG).into_iter() {
+            let ids = chunk.map(|id| id.into()).collect_vec();
+            self.send(make_message!(Payload::InvTransactions, InvTransactionsMessage { ids })).await?;
+        }
+        Ok(())
+    }
+
+    async fn send(&self, msg: KaspadMessage) -> Result<(), ProtocolError> {
+        self.ctx.tx_relay_counters().add_announcement(&msg);
+        self.router.enqueue(msg).await
+    }
+}
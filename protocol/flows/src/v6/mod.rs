@@ -112,11 +112,17 @@ pub fn register(ctx: FlowContext, router: Arc<Router>) -> Vec<Box<dyn Flow>> {
             router.clone(),
             router.subscribe(vec![KaspadMessagePayloadType::RequestTransactions]),
         )),
-        Box::new(ReceiveAddressesFlow::new(ctx.clone(), router.clone(), router.subscribe(vec![KaspadMessagePayloadType::Addresses]))),
+        Box::new(ReceiveAddressesFlow::new(
+            ctx.clone(),
+            router.clone(),
+            router.subscribe(vec![KaspadMessagePayloadType::Addresses]),
+            false,
+        )),
         Box::new(SendAddressesFlow::new(
             ctx.clone(),
             router.clone(),
             router.subscribe(vec![KaspadMessagePayloadType::RequestAddresses]),
+            false,
         )),
         Box::new(RequestBlockLocatorFlow::new(
             ctx.clone(),
@@ -136,6 +142,7 @@ pub fn register(ctx: FlowContext, router: Arc<Router>) -> Vec<Box<dyn Flow>> {
             shared_invs_route.clone(),
             router.subscribe(vec![]),
             ibd_sender.clone(),
+            false,
         )) as Box<dyn Flow>
     }));
 
@@ -49,6 +49,9 @@ pub enum ProtocolError {
     #[error("misbehaving peer: {0}")]
     MisbehavingPeer(String),
 
+    #[error("{0} count {1} exceeded the limit of {2}")]
+    LimitExceeded(&'static str, usize, usize),
+
     #[error("peer connection is closed")]
     ConnectionClosed,
 
@@ -74,6 +77,9 @@ pub enum ProtocolError {
     IgnorableReject(String),
 }
 
+/// Misbehavior score at which a peer gets banned
+pub const MISBEHAVIOR_BAN_THRESHOLD: u32 = 100;
+
 /// String used as a P2P convention to signal connection is rejected because we are connecting to ourselves
 const LOOPBACK_CONNECTION_MESSAGE: &str = "LOOPBACK_CONNECTION";
 
@@ -89,6 +95,18 @@ impl ProtocolError {
         !matches!(self, Self::ConnectionClosed | Self::OutgoingRouteCapacityReached(_))
     }
 
+    /// Penalty points added to the misbehavior score of a peer whose flow failed with this error. Errors
+    /// which are not attributable to the peer, such as closed connections or local failures, carry no penalty.
+    pub fn misbehavior_penalty(&self) -> u32 {
+        match self {
+            Self::MisbehavingPeer(_) => MISBEHAVIOR_BAN_THRESHOLD,
+            Self::RuleError(_) | Self::PruningImportError(_) | Self::ConversionError(_) | Self::LimitExceeded(..) => 50,
+            Self::UnexpectedMessage(..) | Self::IncomingRouteCapacityReached(..) => 20,
+            Self::Timeout(_) => 10,
+            _ => 0,
+        }
+    }
+
     pub fn to_reject_message(&self) -> String {
         match self {
             Self::LoopbackConnection(_) => LOOPBACK_CONNECTION_MESSAGE.to_owned(),
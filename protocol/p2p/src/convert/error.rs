@@ -12,6 +12,12 @@ pub enum ConversionError {
     #[error("IP has illegal length {0}")]
     IllegalIPLength(usize),
 
+    #[error(transparent)]
+    IllegalHostName(#[from] kaspa_utils::networking::NamedNetAddressError),
+
+    #[error("Set reconciliation sketch has illegal length {0}")]
+    IllegalSketchLength(usize),
+
     #[error("Bytes size mismatch error {0}")]
     ArrayBytesSizeError(#[from] std::array::TryFromSliceError),
 
@@ -1,6 +1,8 @@
 use super::{
     error::ConversionError,
     model::{
+        compact::ShortTransactionId,
+        reconciliation::TxSketch,
         trusted::{TrustedDataEntry, TrustedDataPackage},
         version::Version,
     },
@@ -10,10 +12,10 @@ use crate::pb as protowire;
 use kaspa_consensus_core::{
     header::Header,
     pruning::{PruningPointProof, PruningPointsList},
-    tx::{TransactionId, TransactionOutpoint, UtxoEntry},
+    tx::{Transaction, TransactionId, TransactionOutpoint, UtxoEntry},
 };
 use kaspa_hashes::Hash;
-use kaspa_utils::networking::{IpAddress, PeerId};
+use kaspa_utils::networking::{IpAddress, NamedNetAddress, PeerId};
 
 use std::sync::Arc;
 
@@ -33,6 +35,9 @@ impl From<Version> for protowire::VersionMessage {
             disable_relay_tx: item.disable_relay_tx,
             subnetwork_id: item.subnetwork_id.map(|x| x.into()),
             network: item.network.clone(),
+            tx_reconciliation_salt: item.tx_reconciliation_salt.unwrap_or_default(),
+            // Set by the handshake
+            transport_public_key: vec![],
         }
     }
 }
@@ -54,6 +59,7 @@ impl TryFrom<protowire::VersionMessage> for Version {
             disable_relay_tx: msg.disable_relay_tx,
             subnetwork_id: if msg.subnetwork_id.is_none() { None } else { Some(msg.subnetwork_id.unwrap().try_into()?) },
             network: msg.network.clone(),
+            tx_reconciliation_salt: (msg.tx_reconciliation_salt != 0).then_some(msg.tx_reconciliation_salt),
         })
     }
 }
@@ -159,6 +165,46 @@ impl TryFrom<protowire::RequestRelayBlocksMessage> for Vec<Hash> {
     }
 }
 
+impl TryFrom<protowire::RequestCompactBlocksMessage> for Vec<Hash> {
+    type Error = ConversionError;
+
+    fn try_from(msg: protowire::RequestCompactBlocksMessage) -> Result<Self, Self::Error> {
+        msg.hashes.into_iter().map(|v| v.try_into()).collect()
+    }
+}
+
+impl TryFrom<protowire::RequestBlockTransactionsMessage> for (Hash, Vec<u32>) {
+    type Error = ConversionError;
+
+    fn try_from(msg: protowire::RequestBlockTransactionsMessage) -> Result<Self, Self::Error> {
+        Ok((msg.block_hash.try_into_ex()?, msg.indexes))
+    }
+}
+
+impl TryFrom<protowire::BlockTransactionsMessage> for (Hash, Vec<Transaction>) {
+    type Error = ConversionError;
+
+    fn try_from(msg: protowire::BlockTransactionsMessage) -> Result<Self, Self::Error> {
+        Ok((msg.block_hash.try_into_ex()?, msg.transactions.into_iter().map(|v| v.try_into()).collect::<Result<_, _>>()?))
+    }
+}
+
+impl TryFrom<protowire::TxReconciliationSketchMessage> for TxSketch {
+    type Error = ConversionError;
+
+    fn try_from(msg: protowire::TxReconciliationSketchMessage) -> Result<Self, Self::Error> {
+        msg.sketch.as_slice().try_into()
+    }
+}
+
+impl TryFrom<protowire::TxReconciliationDifferenceMessage> for Option<Vec<ShortTransactionId>> {
+    type Error = ConversionError;
+
+    fn try_from(msg: protowire::TxReconciliationDifferenceMessage) -> Result<Self, Self::Error> {
+        Ok(msg.success.then_some(msg.missing_short_ids))
+    }
+}
+
 impl TryFrom<protowire::RequestIbdBlocksMessage> for Vec<Hash> {
     type Error = ConversionError;
 
@@ -175,11 +221,15 @@ impl TryFrom<protowire::BlockLocatorMessage> for Vec<Hash> {
     }
 }
 
-impl TryFrom<protowire::AddressesMessage> for Vec<(IpAddress, u16)> {
+impl TryFrom<protowire::AddressesMessage> for (Vec<(IpAddress, u16)>, Vec<NamedNetAddress>) {
     type Error = ConversionError;
 
     fn try_from(msg: protowire::AddressesMessage) -> Result<Self, Self::Error> {
-        msg.address_list.into_iter().map(|addr| addr.try_into()).collect::<Result<_, _>>()
+        let (named, ip): (Vec<_>, Vec<_>) = msg.address_list.into_iter().partition(|addr| !addr.host.is_empty());
+        Ok((
+            ip.into_iter().map(|addr| addr.try_into()).collect::<Result<_, _>>()?,
+            named.into_iter().map(|addr| addr.try_into()).collect::<Result<_, _>>()?,
+        ))
     }
 }
 
@@ -0,0 +1,225 @@
+//!
+//! Model structures of the compact block relay protocol. A compact block carries the header of a block,
+//! short ids of its transactions, and the transactions the receiver is not expected to hold (the coinbase).
+//! The receiver rebuilds the block from its mempool and requests the transactions it misses.
+//!
+
+use crate::common::ProtocolError;
+use kaspa_consensus_core::{
+    block::Block,
+    header::Header,
+    merkle::calc_hash_merkle_root,
+    tx::{Transaction, TransactionId},
+};
+use kaspa_hashes::{Hash, HasherBase, ShortTransactionIdHash};
+use std::{collections::HashMap, sync::Arc};
+
+pub type ShortTransactionId = u64;
+
+/// Computes the short ids of the transactions of a compact block.
+///
+/// Ids are keyed by the block hash and a nonce picked by the sender, so that colliding transactions
+/// cannot be crafted ahead of time.
+#[derive(Clone)]
+pub struct ShortIdHasher(ShortTransactionIdHash);
+
+impl ShortIdHasher {
+    pub fn new(block_hash: Hash, nonce: u64) -> Self {
+        let mut hasher = ShortTransactionIdHash::new();
+        hasher.update(block_hash).update(nonce.to_le_bytes());
+        Self(hasher)
+    }
+
+    pub fn short_id(&self, transaction_id: &TransactionId) -> ShortTransactionId {
+        let mut hasher = self.0.clone();
+        hasher.update(transaction_id);
+        hasher.finalize().to_le_u64()[0]
+    }
+}
+
+pub struct CompactBlock {
+    pub header: Arc<Header>,
+    pub nonce: u64,
+    /// Short ids of the transactions which are not prefilled, in block order
+    pub short_ids: Vec<ShortTransactionId>,
+    /// Transactions sent in full along with their index in the block, in ascending index order
+    pub prefilled_transactions: Vec<(u32, Transaction)>,
+}
+
+impl CompactBlock {
+    /// Builds the compact form of `block`, prefilling its coinbase transaction
+    pub fn new(block: &Block, nonce: u64) -> Self {
+        let hasher = ShortIdHasher::new(block.hash(), nonce);
+        let mut transactions = block.transactions.iter();
+        let prefilled_transactions = transactions.next().map(|coinbase| (0, coinbase.clone())).into_iter().collect();
+        let short_ids = transactions.map(|tx| hasher.short_id(&tx.id())).collect();
+        Self { header: block.header.clone(), nonce, short_ids, prefilled_transactions }
+    }
+
+    pub fn hash(&self) -> Hash {
+        self.header.hash
+    }
+
+    pub fn transaction_count(&self) -> usize {
+        self.short_ids.len() + self.prefilled_transactions.len()
+    }
+
+    pub fn short_id_hasher(&self) -> ShortIdHasher {
+        ShortIdHasher::new(self.hash(), self.nonce)
+    }
+
+    /// Lays out the block transactions, taking those which are not prefilled from `known_transactions`
+    /// when matching their short id
+    pub fn into_partial_block(
+        self,
+        known_transactions: &HashMap<ShortTransactionId, Arc<Transaction>>,
+    ) -> Result<PartialBlock, ProtocolError> {
+        let count = self.transaction_count();
+        let mut transactions: Vec<Option<Transaction>> = vec![None; count];
+        let mut previous_index = None;
+        for (index, tx) in self.prefilled_transactions {
+            if index as usize >= count || previous_index.is_some_and(|previous| index <= previous) {
+                return Err(ProtocolError::OtherOwned(format!(
+                    "compact block {} has an invalid prefilled index {}",
+                    self.header.hash, index
+                )));
+            }
+            transactions[index as usize] = Some(tx);
+            previous_index = Some(index);
+        }
+        let mut short_ids = self.short_ids.into_iter();
+        let mut missing = Vec::new();
+        for (index, slot) in transactions.iter_mut().enumerate() {
+            if slot.is_none() {
+                // Counts match so every empty slot has a short id
+                let short_id = short_ids.next().unwrap();
+                match known_transactions.get(&short_id) {
+                    Some(tx) => *slot = Some(tx.as_ref().clone()),
+                    None => missing.push(index as u32),
+                }
+            }
+        }
+        Ok(PartialBlock { header: self.header, transactions, missing })
+    }
+}
+
+/// A block being rebuilt from a compact block
+pub struct PartialBlock {
+    pub header: Arc<Header>,
+    transactions: Vec<Option<Transaction>>,
+    missing: Vec<u32>,
+}
+
+impl PartialBlock {
+    pub fn hash(&self) -> Hash {
+        self.header.hash
+    }
+
+    /// The indexes of the transactions unknown to the receiver, in ascending order
+    pub fn missing_indexes(&self) -> &[u32] {
+        &self.missing
+    }
+
+    /// Fills the missing transactions, in the order of [`Self::missing_indexes`]
+    pub fn fill(&mut self, transactions: Vec<Transaction>) -> Result<(), ProtocolError> {
+        if transactions.len() != self.missing.len() {
+            return Err(ProtocolError::OtherOwned(format!(
+                "requested {} transactions of block {} but got {}",
+                self.missing.len(),
+                self.header.hash,
+                transactions.len()
+            )));
+        }
+        for (index, tx) in self.missing.drain(..).zip(transactions) {
+            self.transactions[index as usize] = Some(tx);
+        }
+        Ok(())
+    }
+
+    /// Returns the block if all its transactions are known and match the header merkle root.
+    ///
+    /// A mismatch is not a protocol violation since mempool transactions may collide with the short
+    /// ids or differ from the block ones by their signatures, in which case the block should be
+    /// requested in full.
+    pub fn try_into_block(self, storage_mass_activated: bool) -> Option<Block> {
+        let transactions = self.transactions.into_iter().collect::<Option<Vec<_>>>()?;
+        if calc_hash_merkle_root(transactions.iter(), storage_mass_activated) != self.header.hash_merkle_root {
+            return None;
+        }
+        Some(Block::new(self.header.as_ref().clone(), transactions))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kaspa_consensus_core::{
+        subnets::{SUBNETWORK_ID_COINBASE, SUBNETWORK_ID_NATIVE},
+        tx::{ScriptPublicKey, TransactionOutput},
+    };
+
+    fn transaction(value: u64, subnetwork_id: kaspa_consensus_core::subnets::SubnetworkId) -> Transaction {
+        Transaction::new(0, vec![], vec![TransactionOutput::new(value, ScriptPublicKey::default())], 0, subnetwork_id, 0, vec![])
+    }
+
+    fn block() -> Block {
+        let transactions = vec![
+            transaction(1, SUBNETWORK_ID_COINBASE),
+            transaction(2, SUBNETWORK_ID_NATIVE),
+            transaction(3, SUBNETWORK_ID_NATIVE),
+            transaction(4, SUBNETWORK_ID_NATIVE),
+        ];
+        let mut header = Header::from_precomputed_hash(Hash::from_u64_word(7), vec![]);
+        header.hash_merkle_root = calc_hash_merkle_root(transactions.iter(), false);
+        Block::new(header, transactions)
+    }
+
+    #[test]
+    fn test_compact_block_round_trip() {
+        let block = block();
+        let compact = CompactBlock::new(&block, 42);
+        assert_eq!(compact.transaction_count(), 4);
+        assert_eq!(compact.prefilled_transactions.len(), 1);
+
+        // The receiver knows the second and fourth transactions
+        let hasher = compact.short_id_hasher();
+        let known = [1, 3]
+            .into_iter()
+            .map(|i| (hasher.short_id(&block.transactions[i].id()), Arc::new(block.transactions[i].clone())))
+            .collect();
+        let mut partial = compact.into_partial_block(&known).unwrap();
+        assert_eq!(partial.missing_indexes(), &[2]);
+        assert!(partial.fill(vec![]).is_err());
+        partial.fill(vec![block.transactions[2].clone()]).unwrap();
+        let rebuilt = partial.try_into_block(false).unwrap();
+        assert_eq!(rebuilt.hash(), block.hash());
+        assert_eq!(
+            rebuilt.transactions.iter().map(|tx| tx.id()).collect::<Vec<_>>(),
+            block.transactions.iter().map(|tx| tx.id()).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_compact_block_mismatch() {
+        let block = block();
+        let compact = CompactBlock::new(&block, 42);
+
+        // A colliding transaction yields a block failing the merkle root check
+        let hasher = compact.short_id_hasher();
+        let known =
+            (1..4).map(|i| (hasher.short_id(&block.transactions[i].id()), Arc::new(transaction(100, SUBNETWORK_ID_NATIVE)))).collect();
+        let partial = compact.into_partial_block(&known).unwrap();
+        assert!(partial.missing_indexes().is_empty());
+        assert!(partial.try_into_block(false).is_none());
+
+        // Short ids depend on the nonce
+        assert_ne!(
+            ShortIdHasher::new(block.hash(), 1).short_id(&block.transactions[1].id()),
+            hasher.short_id(&block.transactions[1].id())
+        );
+
+        let mut compact = CompactBlock::new(&block, 42);
+        compact.prefilled_transactions[0].0 = 4;
+        assert!(compact.into_partial_block(&HashMap::new()).is_err());
+    }
+}
@@ -1,2 +1,4 @@
+pub mod compact;
+pub mod reconciliation;
 pub mod trusted;
 pub mod version;
@@ -0,0 +1,247 @@
+//!
+//! Model structures of the transaction set reconciliation protocol. Instead of flooding transaction invs,
+//! two peers periodically exchange a sketch of the short ids they would have announced to each other, from
+//! which the set difference gets decoded and only the transactions missing on either side get announced.
+//!
+//! The sketch is an invertible Bloom lookup table, decodable as long as the set difference is small enough
+//! relatively to its size.
+//!
+
+use super::{compact::ShortIdHasher, compact::ShortTransactionId};
+use crate::convert::error::ConversionError;
+use kaspa_hashes::Hash;
+use std::collections::VecDeque;
+
+/// Number of cells each short id is mapped to, one per table partition
+const HASH_COUNT: usize = 3;
+
+/// Serialized size of a sketch cell: a signed count followed by the short id and check hash sums
+const CELL_SIZE: usize = 4 + 8 + 8;
+
+/// Upper bound on the number of cells of a sketch, limiting a sketch message to about 240 KB
+pub const MAX_SKETCH_CELLS: usize = HASH_COUNT * 4096;
+
+/// Minimal number of cells of a sketch
+const MIN_SKETCH_CELLS: usize = HASH_COUNT * 8;
+
+/// Builds the short id hasher shared by two peers, keyed by the salts both sent in their version messages.
+/// The salts are ordered so that both peers derive the same hasher.
+pub fn reconciliation_short_id_hasher(local_salt: u64, remote_salt: u64) -> ShortIdHasher {
+    let (low, high) = if local_salt < remote_salt { (local_salt, remote_salt) } else { (remote_salt, local_salt) };
+    ShortIdHasher::new(Hash::from_le_u64([low, high, 0, 0]), 0)
+}
+
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq)]
+struct SketchCell {
+    count: i32,
+    id_sum: ShortTransactionId,
+    hash_sum: u64,
+}
+
+impl SketchCell {
+    fn toggle(&mut self, id: ShortTransactionId, sign: i32) {
+        self.count = self.count.wrapping_add(sign);
+        self.id_sum ^= id;
+        self.hash_sum ^= check_hash(id);
+    }
+
+    fn is_pure(&self) -> bool {
+        (self.count == 1 || self.count == -1) && self.hash_sum == check_hash(self.id_sum)
+    }
+
+    fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// The splitmix64 finalizer. Short ids are already uniformly distributed so this only needs to decorrelate
+/// the cell positions and check hashes derived from them.
+fn mix(mut x: u64) -> u64 {
+    x = (x ^ (x >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94d049bb133111eb);
+    x ^ (x >> 31)
+}
+
+fn check_hash(id: ShortTransactionId) -> u64 {
+    mix(id ^ 0x9e3779b97f4a7c15)
+}
+
+/// A sketch of a set of short transaction ids
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TxSketch {
+    cells: Vec<SketchCell>,
+}
+
+impl TxSketch {
+    /// Creates an empty sketch able to decode a set difference of about `capacity` elements
+    pub fn with_capacity(capacity: usize) -> Self {
+        // A table 1.5 times larger than the difference decodes with high probability with 3 hash functions,
+        // small differences requiring some extra room
+        let cells = (capacity + capacity / 2 + 32).next_multiple_of(HASH_COUNT).clamp(MIN_SKETCH_CELLS, MAX_SKETCH_CELLS);
+        Self::with_cells(cells)
+    }
+
+    /// Creates an empty sketch of the same size as `other`, so that both can be subtracted
+    pub fn matching(other: &TxSketch) -> Self {
+        Self::with_cells(other.cells.len())
+    }
+
+    fn with_cells(cells: usize) -> Self {
+        Self { cells: vec![Default::default(); cells] }
+    }
+
+    pub fn cell_count(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn insert(&mut self, id: ShortTransactionId) {
+        self.toggle(id, 1)
+    }
+
+    fn toggle(&mut self, id: ShortTransactionId, sign: i32) {
+        let partition = self.cells.len() / HASH_COUNT;
+        let mut h = id;
+        for i in 0..HASH_COUNT {
+            h = mix(h);
+            self.cells[i * partition + (h % partition as u64) as usize].toggle(id, sign);
+        }
+    }
+
+    /// Decodes the difference between the set of this sketch and the one of `other`, returning the short ids
+    /// only present in this set and those only present in the other one, or `None` if the difference is too
+    /// large to be decoded or the sketches do not match in size.
+    pub fn decode_difference(&self, other: &TxSketch) -> Option<(Vec<ShortTransactionId>, Vec<ShortTransactionId>)> {
+        if self.cells.len() != other.cells.len() {
+            return None;
+        }
+        let mut difference = TxSketch {
+            cells: self
+                .cells
+                .iter()
+                .zip(other.cells.iter())
+                .map(|(a, b)| SketchCell {
+                    count: a.count.wrapping_sub(b.count),
+                    id_sum: a.id_sum ^ b.id_sum,
+                    hash_sum: a.hash_sum ^ b.hash_sum,
+                })
+                .collect(),
+        };
+
+        let (mut local, mut remote) = (Vec::new(), Vec::new());
+        let mut pure: VecDeque<usize> = (0..difference.cells.len()).filter(|&i| difference.cells[i].is_pure()).collect();
+        while let Some(index) = pure.pop_front() {
+            let cell = difference.cells[index];
+            // The cell may have been emptied since it was queued
+            if !cell.is_pure() {
+                continue;
+            }
+            let id = cell.id_sum;
+            if cell.count == 1 {
+                local.push(id);
+            } else {
+                remote.push(id);
+            }
+            difference.toggle(id, -cell.count);
+            let partition = difference.cells.len() / HASH_COUNT;
+            let mut h = id;
+            for i in 0..HASH_COUNT {
+                h = mix(h);
+                let position = i * partition + (h % partition as u64) as usize;
+                if difference.cells[position].is_pure() {
+                    pure.push_back(position);
+                }
+            }
+        }
+
+        difference.cells.iter().all(SketchCell::is_empty).then_some((local, remote))
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(self.cells.len() * CELL_SIZE);
+        for cell in self.cells.iter() {
+            bytes.extend_from_slice(&cell.count.to_le_bytes());
+            bytes.extend_from_slice(&cell.id_sum.to_le_bytes());
+            bytes.extend_from_slice(&cell.hash_sum.to_le_bytes());
+        }
+        bytes
+    }
+}
+
+impl TryFrom<&[u8]> for TxSketch {
+    type Error = ConversionError;
+
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        let cells = bytes.len() / CELL_SIZE;
+        if !bytes.len().is_multiple_of(CELL_SIZE)
+            || !cells.is_multiple_of(HASH_COUNT)
+            || !(MIN_SKETCH_CELLS..=MAX_SKETCH_CELLS).contains(&cells)
+        {
+            return Err(ConversionError::IllegalSketchLength(bytes.len()));
+        }
+        let cells = bytes
+            .chunks_exact(CELL_SIZE)
+            .map(|chunk| SketchCell {
+                count: i32::from_le_bytes(chunk[0..4].try_into().unwrap()),
+                id_sum: u64::from_le_bytes(chunk[4..12].try_into().unwrap()),
+                hash_sum: u64::from_le_bytes(chunk[12..20].try_into().unwrap()),
+            })
+            .collect();
+        Ok(Self { cells })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sketch(capacity: usize, ids: impl IntoIterator<Item = u64>) -> TxSketch {
+        let mut sketch = TxSketch::with_capacity(capacity);
+        ids.into_iter().for_each(|id| sketch.insert(mix(id)));
+        sketch
+    }
+
+    #[test]
+    fn test_sketch_difference() {
+        // 1000 shared ids, 30 only local and 20 only remote
+        let local = sketch(50, 0..1030);
+        let remote = sketch(50, (30..1030).chain(5000..5020));
+        let (mut only_local, mut only_remote) = local.decode_difference(&remote).unwrap();
+        only_local.sort();
+        only_remote.sort();
+        let mut expected_local = (0..30).map(mix).collect::<Vec<_>>();
+        let mut expected_remote = (5000..5020).map(mix).collect::<Vec<_>>();
+        expected_local.sort();
+        expected_remote.sort();
+        assert_eq!(only_local, expected_local);
+        assert_eq!(only_remote, expected_remote);
+
+        // Identical sets have an empty difference
+        assert_eq!(local.decode_difference(&local), Some((vec![], vec![])));
+    }
+
+    #[test]
+    fn test_sketch_overflow() {
+        // A difference far beyond the capacity cannot be decoded
+        let local = sketch(10, 0..500);
+        let remote = sketch(10, 250..750);
+        assert!(local.decode_difference(&remote).is_none());
+        // Neither can sketches of different sizes
+        assert!(sketch(10, 0..5).decode_difference(&sketch(100, 0..5)).is_none());
+    }
+
+    #[test]
+    fn test_sketch_serialization() {
+        let sketch = sketch(40, 0..100);
+        let bytes = sketch.to_bytes();
+        assert_eq!(TxSketch::try_from(bytes.as_slice()).unwrap(), sketch);
+        assert!(TxSketch::try_from(&bytes[1..]).is_err());
+        assert!(TxSketch::try_from(&[][..]).is_err());
+    }
+
+    #[test]
+    fn test_salted_hasher() {
+        let id = Hash::from_u64_word(1);
+        assert_eq!(reconciliation_short_id_hasher(1, 2).short_id(&id), reconciliation_short_id_hasher(2, 1).short_id(&id));
+        assert_ne!(reconciliation_short_id_hasher(1, 2).short_id(&id), reconciliation_short_id_hasher(1, 3).short_id(&id));
+    }
+}
@@ -18,6 +18,8 @@ pub struct Version {
     pub user_agent: String,
     pub disable_relay_tx: bool,
     pub subnetwork_id: Option<SubnetworkId>,
+    /// Salt of the transaction reconciliation short ids, if reconciliation is supported
+    pub tx_reconciliation_salt: Option<u64>,
 }
 
 impl Version {
@@ -38,6 +40,7 @@ impl Version {
             user_agent: format!("/{}:{}/", name(), version()),
             disable_relay_tx: false,
             subnetwork_id,
+            tx_reconciliation_salt: None,
         }
     }
 
@@ -4,7 +4,7 @@ use super::error::ConversionError;
 use crate::pb as protowire;
 
 use itertools::Itertools;
-use kaspa_utils::networking::{IpAddress, NetAddress};
+use kaspa_utils::networking::{IpAddress, NamedNetAddress, NetAddress};
 
 // ----------------------------------------------------------------------------
 // consensus_core to protowire
@@ -20,6 +20,7 @@ impl From<(IpAddress, u16)> for protowire::NetAddress {
                 IpAddr::V6(ip) => ip.octets().to_vec(),
             },
             port: port as u32,
+            host: String::new(),
         }
     }
 }
@@ -30,6 +31,12 @@ impl From<NetAddress> for protowire::NetAddress {
     }
 }
 
+impl From<NamedNetAddress> for protowire::NetAddress {
+    fn from(item: NamedNetAddress) -> Self {
+        Self { timestamp: 0, ip: vec![], port: item.port as u32, host: item.host }
+    }
+}
+
 // ----------------------------------------------------------------------------
 // protowire to consensus_core
 // ----------------------------------------------------------------------------
@@ -65,9 +72,18 @@ impl TryFrom<protowire::NetAddress> for NetAddress {
     }
 }
 
+impl TryFrom<protowire::NetAddress> for NamedNetAddress {
+    type Error = ConversionError;
+
+    fn try_from(item: protowire::NetAddress) -> Result<Self, Self::Error> {
+        NamedNetAddress::validate_host(&item.host)?;
+        Ok(NamedNetAddress::new(item.host.to_ascii_lowercase(), item.port.try_into()?))
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use kaspa_utils::networking::IpAddress;
+    use kaspa_utils::networking::{IpAddress, NamedNetAddress};
 
     use crate::pb;
     use std::{
@@ -77,14 +93,27 @@ mod tests {
 
     #[test]
     fn test_netaddress() {
-        let net_addr_ipv4 = pb::NetAddress { timestamp: 0, ip: hex::decode("6a0a8af0").unwrap(), port: 123 };
+        let net_addr_ipv4 = pb::NetAddress { timestamp: 0, ip: hex::decode("6a0a8af0").unwrap(), port: 123, host: String::new() };
         let ipv4 = Ipv4Addr::from_str("106.10.138.240").unwrap().into();
         assert_eq!(<(IpAddress, u16)>::try_from(net_addr_ipv4.clone()).unwrap(), (ipv4, 123u16));
         assert_eq!(pb::NetAddress::from((ipv4, 123u16)), net_addr_ipv4);
 
-        let net_addr_ipv6 = pb::NetAddress { timestamp: 0, ip: hex::decode("20010db885a3000000008a2e03707334").unwrap(), port: 456 };
+        let net_addr_ipv6 = pb::NetAddress {
+            timestamp: 0,
+            ip: hex::decode("20010db885a3000000008a2e03707334").unwrap(),
+            port: 456,
+            host: String::new(),
+        };
         let ipv6 = Ipv6Addr::from_str("2001:0db8:85a3:0000:0000:8a2e:0370:7334").unwrap().into();
         assert_eq!(<(IpAddress, u16)>::try_from(net_addr_ipv6.clone()).unwrap(), (ipv6, 456u16));
         assert_eq!(pb::NetAddress::from((ipv6, 456u16)), net_addr_ipv6);
+
+        let named = NamedNetAddress::from_str("kaspadxyz2ruzvtwoiqbuzhvrdjmcvg7tgxnc5rplwhsbd5e2fkaxyad.onion:16111").unwrap();
+        let net_addr_named = pb::NetAddress::from(named.clone());
+        assert!(net_addr_named.ip.is_empty());
+        assert_eq!(NamedNetAddress::try_from(net_addr_named.clone()).unwrap(), named);
+        // Peers unaware of host names fail to convert such addresses
+        assert!(<(IpAddress, u16)>::try_from(net_addr_named).is_err());
+        assert!(NamedNetAddress::try_from(net_addr_ipv6).is_err());
     }
 }
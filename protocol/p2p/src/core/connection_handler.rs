@@ -6,13 +6,15 @@ use crate::pb::{
 use crate::{ConnectionInitializer, Router};
 use futures::FutureExt;
 use kaspa_core::{debug, info};
-use kaspa_utils::networking::NetAddress;
+use kaspa_utils::networking::{NamedNetAddress, NamedNetAddressError, NetAddress};
 use kaspa_utils_tower::{
     counters::TowerConnectionCounters,
     middleware::{BodyExt, CountBytesBody, MapRequestBodyLayer, MapResponseBodyLayer, ServiceBuilder},
+    socks5::Socks5Connector,
 };
-use std::net::ToSocketAddrs;
+use std::net::{SocketAddr, ToSocketAddrs};
 use std::pin::Pin;
+use std::str::FromStr;
 use std::sync::Arc;
 use std::time::Duration;
 use thiserror::Error;
@@ -28,6 +30,9 @@ pub enum ConnectionError {
     #[error("missing socket address")]
     NoAddress,
 
+    #[error("{0}")]
+    NamedAddressError(#[from] NamedNetAddressError),
+
     #[error("{0}")]
     IoError(#[from] std::io::Error),
 
@@ -51,6 +56,8 @@ pub struct ConnectionHandler {
     hub_sender: MpscSender<HubEvent>,
     initializer: Arc<dyn ConnectionInitializer>,
     counters: Arc<TowerConnectionCounters>,
+    /// The SOCKS5 proxy outbound connections are dialed through, if any
+    proxy: Option<SocketAddr>,
 }
 
 impl ConnectionHandler {
@@ -58,8 +65,13 @@ impl ConnectionHandler {
         hub_sender: MpscSender<HubEvent>,
         initializer: Arc<dyn ConnectionInitializer>,
         counters: Arc<TowerConnectionCounters>,
+        proxy: Option<SocketAddr>,
     ) -> Self {
-        Self { hub_sender, initializer, counters }
+        Self { hub_sender, initializer, counters, proxy }
+    }
+
+    pub(crate) fn proxy(&self) -> Option<SocketAddr> {
+        self.proxy
     }
 
     /// Launches a P2P server listener loop
@@ -93,19 +105,31 @@ impl ConnectionHandler {
         Ok(termination_sender)
     }
 
-    /// Connect to a new peer
+    /// Connect to a new peer. When a proxy is configured, `peer_address` may designate the peer by host name,
+    /// in which case the name gets resolved by the proxy
     pub(crate) async fn connect(&self, peer_address: String) -> Result<Arc<Router>, ConnectionError> {
-        let Some(socket_address) = peer_address.to_socket_addrs()?.next() else {
-            return Err(ConnectionError::NoAddress);
+        let (socket_address, named_address) = match self.proxy {
+            Some(proxy) => match SocketAddr::from_str(&peer_address) {
+                Ok(socket_address) => (socket_address, None),
+                Err(_) => (proxy, Some(NamedNetAddress::from_str(&peer_address)?)),
+            },
+            None => {
+                let Some(socket_address) = peer_address.to_socket_addrs()?.next() else {
+                    return Err(ConnectionError::NoAddress);
+                };
+                (socket_address, None)
+            }
         };
         let peer_address = format!("http://{}", peer_address); // Add scheme prefix as required by Tonic
 
-        let channel = tonic::transport::Endpoint::new(peer_address)?
+        let endpoint = tonic::transport::Endpoint::new(peer_address)?
             .timeout(Duration::from_millis(Self::communication_timeout()))
-            .connect_timeout(Duration::from_millis(Self::connect_timeout()))
-            .tcp_keepalive(Some(Duration::from_millis(Self::keep_alive())))
-            .connect()
-            .await?;
+            .connect_timeout(Duration::from_millis(self.connect_timeout()))
+            .tcp_keepalive(Some(Duration::from_millis(Self::keep_alive())));
+        let channel = match self.proxy {
+            Some(proxy) => endpoint.connect_with_connector(Socks5Connector::new(proxy)).await?,
+            None => endpoint.connect().await?,
+        };
 
         let channel = ServiceBuilder::new()
             .layer(MapResponseBodyLayer::new(move |body| CountBytesBody::new(body, self.counters.bytes_rx.clone())))
@@ -120,7 +144,7 @@ impl ConnectionHandler {
         let (outgoing_route, outgoing_receiver) = mpsc_channel(Self::outgoing_network_channel_size());
         let incoming_stream = client.message_stream(ReceiverStream::new(outgoing_receiver)).await?.into_inner();
 
-        let router = Router::new(socket_address, true, self.hub_sender.clone(), incoming_stream, outgoing_route).await;
+        let router = Router::new(socket_address, named_address, true, self.hub_sender.clone(), incoming_stream, outgoing_route).await;
 
         // For outbound peers, we perform the initialization as part of the connect logic
         match self.initializer.initialize_connection(router.clone()).await {
@@ -189,8 +213,12 @@ impl ConnectionHandler {
         10_000
     }
 
-    fn connect_timeout() -> u64 {
-        1_000
+    fn connect_timeout(&self) -> u64 {
+        // Circuits through a proxy, and the Tor network in particular, take much longer to establish
+        match self.proxy {
+            Some(_) => 30_000,
+            None => 1_000,
+        }
     }
 }
 
@@ -212,7 +240,7 @@ impl ProtoP2p for ConnectionHandler {
         let incoming_stream = request.into_inner();
 
         // Build the router object
-        let router = Router::new(remote_address, false, self.hub_sender.clone(), incoming_stream, outgoing_route).await;
+        let router = Router::new(remote_address, None, false, self.hub_sender.clone(), incoming_stream, outgoing_route).await;
 
         // Notify the central Hub about the new peer
         self.hub_sender.send(HubEvent::NewPeer(router)).await.expect("hub receiver should never drop before senders");
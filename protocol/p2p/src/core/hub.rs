@@ -2,7 +2,7 @@ use crate::{common::ProtocolError, pb::KaspadMessage, ConnectionInitializer, Pee
 use kaspa_core::{debug, info, warn};
 use parking_lot::RwLock;
 use std::{
-    collections::{hash_map::Entry::Occupied, HashMap},
+    collections::{hash_map::Entry::Occupied, HashMap, HashSet},
     sync::Arc,
 };
 use tokio::sync::mpsc::Receiver as MpscReceiver;
@@ -86,9 +86,10 @@ impl Hub {
     }
 
     /// Selects a random subset of peers, trying to select at least half for outbound when possible
-    fn select_some_peers(&self, num_peers: usize) -> impl Iterator<Item = Arc<Router>> {
+    fn select_some_peers(&self, num_peers: usize, excluded: &HashSet<PeerKey>) -> impl Iterator<Item = Arc<Router>> {
         let peers = self.peers.read();
-        let total_outbound = peers.values().filter(|peer| peer.is_outbound()).count();
+        let peers = peers.iter().filter(|(key, _)| !excluded.contains(key)).map(|(_, peer)| peer).collect::<Vec<_>>();
+        let total_outbound = peers.iter().filter(|peer| peer.is_outbound()).count();
         let total_inbound = peers.len() - total_outbound;
 
         let mut outbound_count = ((num_peers + 1) / 2).min(total_outbound);
@@ -104,12 +105,12 @@ impl Hub {
         let thread_rng = &mut rand::thread_rng();
 
         peers
-            .values()
+            .iter()
             .filter(|peer| peer.is_outbound())
-            .cloned()
+            .map(|&peer| peer.clone())
             .choose_multiple(thread_rng, outbound_count) // Randomly select about half from outbound
             .into_iter() // Then select the rest from inbound
-            .chain(peers.values().filter(|peer| !peer.is_outbound()).cloned().choose_multiple(thread_rng, inbound_count))
+            .chain(peers.iter().filter(|peer| !peer.is_outbound()).map(|&peer| peer.clone()).choose_multiple(thread_rng, inbound_count))
     }
 
     /// Send a message to a specific peer
@@ -133,13 +134,29 @@ impl Hub {
 
     /// Broadcast a message to only some number of peers
     pub async fn broadcast_to_some_peers(&self, msg: KaspadMessage, num_peers: usize) {
+        self.broadcast_to_some_peers_except(msg, num_peers, &HashSet::new()).await;
+    }
+
+    /// Broadcast a message to all peers but the excluded ones, returning the number of peers it was sent to
+    pub async fn broadcast_except(&self, msg: KaspadMessage, excluded: &HashSet<PeerKey>) -> usize {
+        let peers = self.peers.read().iter().filter(|(key, _)| !excluded.contains(key)).map(|(_, r)| r.clone()).collect::<Vec<_>>();
+        for router in peers.iter() {
+            let _ = router.enqueue(msg.clone()).await;
+        }
+        peers.len()
+    }
+
+    /// Broadcast a message to only some number of peers, skipping the excluded ones, and returns the number
+    /// of peers it was sent to
+    pub async fn broadcast_to_some_peers_except(&self, msg: KaspadMessage, num_peers: usize, excluded: &HashSet<PeerKey>) -> usize {
         assert!(num_peers > 0);
 
-        let peers = self.select_some_peers(num_peers);
+        let peers = self.select_some_peers(num_peers, excluded).collect::<Vec<_>>();
 
-        for router in peers {
+        for router in peers.iter() {
             let _ = router.enqueue(msg.clone()).await;
         }
+        peers.len()
     }
 
     /// Broadcast a vector of messages to all peers
@@ -4,3 +4,4 @@ pub mod hub;
 pub mod payload_type;
 pub mod peer;
 pub mod router;
+pub mod transport;
@@ -46,6 +46,14 @@ pub enum KaspadMessagePayloadType {
     IbdChainBlockLocator,
     RequestAntipast,
     RequestNextPruningPointAndItsAnticoneBlocks,
+    RequestCompactBlocks,
+    CompactBlock,
+    RequestBlockTransactions,
+    BlockTransactions,
+    RequestTxReconciliation,
+    TxReconciliationSketch,
+    TxReconciliationDifference,
+    Encrypted,
 }
 
 impl From<&KaspadMessagePayload> for KaspadMessagePayloadType {
@@ -100,6 +108,14 @@ impl From<&KaspadMessagePayload> for KaspadMessagePayloadType {
             KaspadMessagePayload::RequestNextPruningPointAndItsAnticoneBlocks(_) => {
                 KaspadMessagePayloadType::RequestNextPruningPointAndItsAnticoneBlocks
             }
+            KaspadMessagePayload::RequestCompactBlocks(_) => KaspadMessagePayloadType::RequestCompactBlocks,
+            KaspadMessagePayload::CompactBlock(_) => KaspadMessagePayloadType::CompactBlock,
+            KaspadMessagePayload::RequestBlockTransactions(_) => KaspadMessagePayloadType::RequestBlockTransactions,
+            KaspadMessagePayload::BlockTransactions(_) => KaspadMessagePayloadType::BlockTransactions,
+            KaspadMessagePayload::RequestTxReconciliation(_) => KaspadMessagePayloadType::RequestTxReconciliation,
+            KaspadMessagePayload::TxReconciliationSketch(_) => KaspadMessagePayloadType::TxReconciliationSketch,
+            KaspadMessagePayload::TxReconciliationDifference(_) => KaspadMessagePayloadType::TxReconciliationDifference,
+            KaspadMessagePayload::Encrypted(_) => KaspadMessagePayloadType::Encrypted,
         }
     }
 }
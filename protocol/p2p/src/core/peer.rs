@@ -1,5 +1,5 @@
 use kaspa_consensus_core::subnets::SubnetworkId;
-use kaspa_utils::networking::{IpAddress, PeerId};
+use kaspa_utils::networking::{IpAddress, NamedNetAddress, PeerId};
 use std::{fmt::Display, net::SocketAddr, sync::Arc, time::Instant};
 
 #[derive(Debug, Clone, Default)]
@@ -17,22 +17,26 @@ pub struct PeerProperties {
 pub struct Peer {
     identity: PeerId,
     net_address: SocketAddr,
+    named_address: Option<NamedNetAddress>,
     is_outbound: bool,
     connection_started: Instant,
     properties: Arc<PeerProperties>,
     last_ping_duration: u64,
+    is_encrypted: bool,
 }
 
 impl Peer {
     pub fn new(
         identity: PeerId,
         net_address: SocketAddr,
+        named_address: Option<NamedNetAddress>,
         is_outbound: bool,
         connection_started: Instant,
         properties: Arc<PeerProperties>,
         last_ping_duration: u64,
+        is_encrypted: bool,
     ) -> Self {
-        Self { identity, net_address, is_outbound, connection_started, properties, last_ping_duration }
+        Self { identity, net_address, named_address, is_outbound, connection_started, properties, last_ping_duration, is_encrypted }
     }
 
     /// Internal identity of this peer
@@ -40,11 +44,16 @@ impl Peer {
         self.identity
     }
 
-    /// The socket address of this peer
+    /// The socket address of this peer, which is the address of the proxy for peers dialed by host name
     pub fn net_address(&self) -> SocketAddr {
         self.net_address
     }
 
+    /// The host name address this peer was dialed by, if any
+    pub fn named_address(&self) -> Option<&NamedNetAddress> {
+        self.named_address.as_ref()
+    }
+
     pub fn key(&self) -> PeerKey {
         self.into()
     }
@@ -65,6 +74,11 @@ impl Peer {
     pub fn last_ping_duration(&self) -> u64 {
         self.last_ping_duration
     }
+
+    /// Indicates whether the connection runs over an encrypted transport
+    pub fn is_encrypted(&self) -> bool {
+        self.is_encrypted
+    }
 }
 
 #[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
@@ -1,10 +1,11 @@
 use crate::core::hub::HubEvent;
-use crate::pb::RejectMessage;
+use crate::core::transport::{derive_ciphers, Decryptor, Encryptor, TransportKeyPair};
 use crate::pb::{kaspad_message::Payload as KaspadMessagePayload, KaspadMessage};
+use crate::pb::{RejectMessage, VersionMessage};
 use crate::{common::ProtocolError, KaspadMessagePayloadType};
 use crate::{make_message, Peer};
 use kaspa_core::{debug, error, info, trace, warn};
-use kaspa_utils::networking::PeerId;
+use kaspa_utils::networking::{NamedNetAddress, PeerId};
 use parking_lot::{Mutex, RwLock};
 use seqlock::SeqLock;
 use std::fmt::{Debug, Display};
@@ -102,6 +103,9 @@ struct RouterMutableState {
 
     /// Duration of the last ping to this peer
     last_ping_duration: u64,
+
+    /// The key pair announced in the local version message, consumed when the peer version message arrives
+    transport_key_pair: Option<TransportKeyPair>,
 }
 
 impl RouterMutableState {
@@ -117,9 +121,12 @@ pub struct Router {
     /// Internal identity of this peer
     identity: SeqLock<PeerId>,
 
-    /// The socket address of this peer
+    /// The socket address of this peer, which is the address of the proxy for peers dialed by host name
     net_address: SocketAddr,
 
+    /// The host name address this peer was dialed by, if any
+    named_address: Option<NamedNetAddress>,
+
     /// Indicates whether this connection is an outbound connection
     is_outbound: bool,
 
@@ -139,11 +146,20 @@ pub struct Router {
 
     /// Used for managing router mutable state
     mutable_state: Mutex<RouterMutableState>,
+
+    /// Encrypts outgoing messages once an encrypted transport is negotiated
+    encryptor: Mutex<Option<Encryptor>>,
+
+    /// Decrypts incoming messages once an encrypted transport is negotiated
+    decryptor: Mutex<Option<Decryptor>>,
 }
 
 impl Display for Router {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.net_address)
+        match self.named_address {
+            Some(ref named_address) => write!(f, "{}", named_address),
+            None => write!(f, "{}", self.net_address),
+        }
     }
 }
 
@@ -158,10 +174,12 @@ impl From<&Router> for Peer {
         Self::new(
             router.identity(),
             router.net_address,
+            router.named_address.clone(),
             router.is_outbound,
             router.connection_started,
             router.properties(),
             router.last_ping_duration(),
+            router.is_encrypted(),
         )
     }
 }
@@ -175,6 +193,7 @@ fn message_summary(msg: &KaspadMessage) -> impl Debug {
 impl Router {
     pub(crate) async fn new(
         net_address: SocketAddr,
+        named_address: Option<NamedNetAddress>,
         is_outbound: bool,
         hub_sender: MpscSender<HubEvent>,
         mut incoming_stream: Streaming<KaspadMessage>,
@@ -186,6 +205,7 @@ impl Router {
         let router = Arc::new(Router {
             identity: Default::default(),
             net_address,
+            named_address,
             is_outbound,
             connection_started: Instant::now(),
             routing_map_by_type: RwLock::new(HashMap::new()),
@@ -193,6 +213,8 @@ impl Router {
             outgoing_route,
             hub_sender,
             mutable_state: Mutex::new(RouterMutableState::new(Some(start_sender), Some(shutdown_sender))),
+            encryptor: Mutex::new(None),
+            decryptor: Mutex::new(None),
         });
 
         let router_clone = router.clone();
@@ -260,6 +282,11 @@ impl Router {
         self.net_address
     }
 
+    /// The host name address this peer was dialed by, if any
+    pub fn named_address(&self) -> Option<&NamedNetAddress> {
+        self.named_address.as_ref()
+    }
+
     pub fn key(&self) -> PeerKey {
         self.into()
     }
@@ -294,6 +321,17 @@ impl Router {
         self.mutable_state.lock().last_ping_duration
     }
 
+    /// Indicates whether messages to and from this peer are encrypted
+    pub fn is_encrypted(&self) -> bool {
+        self.encryptor.lock().is_some()
+    }
+
+    /// Sets the key pair announced in the local version message. Must be called before the router receive loop
+    /// starts, so that the transport gets negotiated as soon as the peer version message is received
+    pub(crate) fn set_transport_key_pair(&self, key_pair: TransportKeyPair) {
+        self.mutable_state.lock().transport_key_pair = Some(key_pair);
+    }
+
     pub fn incoming_flow_baseline_channel_size() -> usize {
         256
     }
@@ -363,6 +401,7 @@ impl Router {
 
     /// Routes a message coming from the network to the corresponding registered flow
     pub fn route_to_flow(&self, msg: KaspadMessage) -> Result<(), ProtocolError> {
+        let msg = self.decrypt(msg)?;
         if msg.payload.is_none() {
             debug!("P2P, Route to flow got empty payload, peer: {}", self);
             return Err(ProtocolError::Other("received kaspad p2p message with empty payload"));
@@ -373,6 +412,9 @@ impl Router {
             let Some(KaspadMessagePayload::Reject(reject)) = msg.payload else { unreachable!() };
             return Err(ProtocolError::from_reject_message(reject.reason));
         }
+        if let Some(KaspadMessagePayload::Version(version)) = &msg.payload {
+            self.negotiate_transport(version)?;
+        }
 
         let op = if msg.response_id != BLANK_ROUTE_ID {
             self.routing_map_by_id.read().get(&msg.response_id).cloned()
@@ -399,9 +441,51 @@ impl Router {
         }
     }
 
+    /// Unwraps a message received over an encrypted transport, and rejects messages not matching the transport
+    fn decrypt(&self, msg: KaspadMessage) -> Result<KaspadMessage, ProtocolError> {
+        let mut decryptor = self.decryptor.lock();
+        match (decryptor.as_mut(), msg.payload) {
+            (Some(decryptor), Some(KaspadMessagePayload::Encrypted(encrypted))) => decryptor.decrypt(encrypted),
+            (None, Some(KaspadMessagePayload::Encrypted(_))) => {
+                Err(ProtocolError::MisbehavingPeer("received an encrypted message over a plaintext transport".to_owned()))
+            }
+            (Some(_), _) => Err(ProtocolError::MisbehavingPeer("received a plaintext message over an encrypted transport".to_owned())),
+            (None, payload) => Ok(KaspadMessage { payload, ..msg }),
+        }
+    }
+
+    /// Switches to an encrypted transport if both the local and the peer version messages announced a key.
+    ///
+    /// The version message is sent in plaintext before any other message by each side, hence all messages
+    /// following it in both directions are encrypted. Since this runs within the receive loop, the decryptor
+    /// is in place before the next incoming message is routed, and the encryptor before any response to the
+    /// version message is sent.
+    fn negotiate_transport(&self, version: &VersionMessage) -> Result<(), ProtocolError> {
+        // Only the first version message negotiates the transport
+        let Some(key_pair) = self.mutable_state.lock().transport_key_pair.take() else {
+            return Ok(());
+        };
+        if version.transport_public_key.is_empty() {
+            debug!("P2P, peer {} does not support transport encryption", self);
+            return Ok(());
+        }
+        let (encryptor, decryptor) = derive_ciphers(key_pair, &version.transport_public_key, self.is_outbound)?;
+        *self.encryptor.lock() = Some(encryptor);
+        *self.decryptor.lock() = Some(decryptor);
+        debug!("P2P, negotiated an encrypted transport with peer {}", self);
+        Ok(())
+    }
+
     /// Enqueues a locally-originated message to be sent to the network peer
     pub async fn enqueue(&self, msg: KaspadMessage) -> Result<(), ProtocolError> {
         assert!(msg.payload.is_some(), "Kaspad P2P message should always have a value");
+        // Encrypting under the lock keeps the nonce sequence aligned with the outgoing message order
+        let mut encryptor = self.encryptor.lock();
+        let msg = match encryptor.as_mut() {
+            // The peer negotiates the transport upon receiving our version message, which is hence never encrypted
+            Some(encryptor) if !matches!(msg.payload, Some(KaspadMessagePayload::Version(_))) => encryptor.encrypt(&msg),
+            _ => msg,
+        };
         match self.outgoing_route.try_send(msg) {
             Ok(_) => Ok(()),
             Err(TrySendError::Closed(_)) => Err(ProtocolError::ConnectionClosed),
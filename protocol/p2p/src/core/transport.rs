@@ -0,0 +1,179 @@
+//!
+//! Opportunistic encryption of the p2p transport, in the spirit of BIP324. Each side announces an ephemeral
+//! secp256k1 public key in its version message, and when both do, every following message is sent as the
+//! ChaCha20-Poly1305 encryption of the serialized message, with one key per direction derived from the ECDH
+//! shared secret. Legacy peers ignore the announced key, in which case the connection remains in plaintext.
+//!
+//! Peers are not authenticated, hence this protects from passive observers and from tampering with an
+//! established session, but not from an active attacker intercepting the key exchange.
+//!
+
+use crate::{
+    common::ProtocolError,
+    make_message,
+    pb::{kaspad_message::Payload, EncryptedMessage, KaspadMessage},
+};
+use chacha20poly1305::{
+    aead::{Aead, KeyInit},
+    ChaCha20Poly1305, Key, Nonce,
+};
+use prost::Message;
+use secp256k1::{ecdh::SharedSecret, PublicKey, SecretKey};
+use sha2::{Digest, Sha256};
+use std::fmt::Debug;
+
+/// Domain separation tag of the transport key derivation
+const KEY_DERIVATION_TAG: &[u8] = b"kaspa-p2p-transport-v2";
+
+/// The ephemeral key pair announced in the version message of a connection
+#[derive(Debug)]
+pub struct TransportKeyPair {
+    secret_key: SecretKey,
+    public_key: PublicKey,
+}
+
+impl TransportKeyPair {
+    pub fn generate() -> Self {
+        let secret_key = SecretKey::new(&mut secp256k1::rand::thread_rng());
+        let public_key = secret_key.public_key(secp256k1::SECP256K1);
+        Self { secret_key, public_key }
+    }
+
+    /// The compressed public key
+    pub fn public_key(&self) -> Vec<u8> {
+        self.public_key.serialize().to_vec()
+    }
+}
+
+/// One direction of an encrypted transport. Messages are delivered in order, so a counter provides a unique
+/// nonce per message and makes replayed, dropped or reordered messages fail authentication.
+struct CipherState {
+    cipher: ChaCha20Poly1305,
+    counter: u64,
+}
+
+impl CipherState {
+    fn new(key: [u8; 32]) -> Self {
+        Self { cipher: ChaCha20Poly1305::new(Key::from_slice(&key)), counter: 0 }
+    }
+
+    fn next_nonce(&mut self) -> Nonce {
+        let mut nonce = Nonce::default();
+        nonce[4..].copy_from_slice(&self.counter.to_le_bytes());
+        self.counter += 1;
+        nonce
+    }
+}
+
+impl Debug for CipherState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CipherState").field("counter", &self.counter).finish_non_exhaustive()
+    }
+}
+
+/// Encrypts the messages sent to the peer
+#[derive(Debug)]
+pub struct Encryptor(CipherState);
+
+impl Encryptor {
+    pub fn encrypt(&mut self, msg: &KaspadMessage) -> KaspadMessage {
+        let nonce = self.0.next_nonce();
+        let ciphertext =
+            self.0.cipher.encrypt(&nonce, msg.encode_to_vec().as_slice()).expect("encryption of in-memory buffers never fails");
+        make_message!(Payload::Encrypted, EncryptedMessage { ciphertext })
+    }
+}
+
+/// Decrypts the messages received from the peer
+#[derive(Debug)]
+pub struct Decryptor(CipherState);
+
+impl Decryptor {
+    pub fn decrypt(&mut self, msg: EncryptedMessage) -> Result<KaspadMessage, ProtocolError> {
+        let nonce = self.0.next_nonce();
+        let plaintext = self
+            .0
+            .cipher
+            .decrypt(&nonce, msg.ciphertext.as_slice())
+            .map_err(|_| ProtocolError::MisbehavingPeer("encrypted message failed authentication".to_owned()))?;
+        let msg = KaspadMessage::decode(plaintext.as_slice())
+            .map_err(|err| ProtocolError::MisbehavingPeer(format!("encrypted message is malformed: {err}")))?;
+        if let Some(Payload::Encrypted(_)) = msg.payload {
+            return Err(ProtocolError::MisbehavingPeer("encrypted message wraps another encrypted message".to_owned()));
+        }
+        Ok(msg)
+    }
+}
+
+/// Derives the ciphers of both directions from the local key pair and the public key announced by the peer.
+/// The connection initiator and responder derive the same keys, in swapped roles.
+pub fn derive_ciphers(
+    local: TransportKeyPair,
+    remote_public_key: &[u8],
+    is_initiator: bool,
+) -> Result<(Encryptor, Decryptor), ProtocolError> {
+    let remote_public_key = PublicKey::from_slice(remote_public_key)
+        .map_err(|err| ProtocolError::MisbehavingPeer(format!("invalid transport public key: {err}")))?;
+    let shared_secret = SharedSecret::new(&remote_public_key, &local.secret_key);
+    let (initiator_key, responder_key) =
+        if is_initiator { (local.public_key, remote_public_key) } else { (remote_public_key, local.public_key) };
+    let derive_key = |direction: &[u8]| -> [u8; 32] {
+        Sha256::new()
+            .chain_update(KEY_DERIVATION_TAG)
+            .chain_update(shared_secret.secret_bytes())
+            .chain_update(initiator_key.serialize())
+            .chain_update(responder_key.serialize())
+            .chain_update(direction)
+            .finalize()
+            .into()
+    };
+    let (initiator_to_responder, responder_to_initiator) = (derive_key(b"initiator"), derive_key(b"responder"));
+    let (send_key, recv_key) =
+        if is_initiator { (initiator_to_responder, responder_to_initiator) } else { (responder_to_initiator, initiator_to_responder) };
+    Ok((Encryptor(CipherState::new(send_key)), Decryptor(CipherState::new(recv_key))))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pb::{PingMessage, VerackMessage};
+
+    fn unwrap_encrypted(msg: KaspadMessage) -> EncryptedMessage {
+        match msg.payload {
+            Some(Payload::Encrypted(encrypted)) => encrypted,
+            _ => panic!("expected an encrypted message"),
+        }
+    }
+
+    #[test]
+    fn test_encrypted_transport() {
+        let (initiator, responder) = (TransportKeyPair::generate(), TransportKeyPair::generate());
+        let (initiator_public_key, responder_public_key) = (initiator.public_key(), responder.public_key());
+        let (mut initiator_encryptor, mut initiator_decryptor) = derive_ciphers(initiator, &responder_public_key, true).unwrap();
+        let (mut responder_encryptor, mut responder_decryptor) = derive_ciphers(responder, &initiator_public_key, false).unwrap();
+
+        let ping = make_message!(Payload::Ping, PingMessage { nonce: 7 });
+        let verack = make_message!(Payload::Verack, VerackMessage {});
+        for _ in 0..3 {
+            let encrypted = unwrap_encrypted(initiator_encryptor.encrypt(&ping));
+            assert_eq!(responder_decryptor.decrypt(encrypted).unwrap(), ping);
+            let encrypted = unwrap_encrypted(responder_encryptor.encrypt(&verack));
+            assert_eq!(initiator_decryptor.decrypt(encrypted).unwrap(), verack);
+        }
+
+        // Tampered, replayed and reordered messages fail authentication
+        let mut tampered = unwrap_encrypted(initiator_encryptor.encrypt(&ping));
+        tampered.ciphertext[0] ^= 1;
+        assert!(responder_decryptor.decrypt(tampered).is_err());
+        let first = unwrap_encrypted(responder_encryptor.encrypt(&verack));
+        let second = unwrap_encrypted(responder_encryptor.encrypt(&verack));
+        assert!(initiator_decryptor.decrypt(second).is_err());
+        assert!(initiator_decryptor.decrypt(first).is_err());
+    }
+
+    #[test]
+    fn test_invalid_transport_key() {
+        assert!(derive_ciphers(TransportKeyPair::generate(), &[0; 33], true).is_err());
+        assert!(derive_ciphers(TransportKeyPair::generate(), &[], true).is_err());
+    }
+}
@@ -1,5 +1,6 @@
 use std::time::Duration;
 
+use crate::core::transport::TransportKeyPair;
 use crate::pb::{kaspad_message::Payload, ReadyMessage, VerackMessage, VersionMessage};
 use crate::{common::ProtocolError, dequeue_with_timeout, make_message};
 use crate::{IncomingRoute, KaspadMessagePayloadType, Router};
@@ -11,16 +12,23 @@ pub struct KaspadHandshake<'a> {
     version_receiver: IncomingRoute,
     verack_receiver: IncomingRoute,
     ready_receiver: IncomingRoute,
+    /// The public key announced for negotiating an encrypted transport
+    transport_public_key: Vec<u8>,
 }
 
 impl<'a> KaspadHandshake<'a> {
-    /// Builds the handshake object and subscribes to handshake messages
+    /// Builds the handshake object, subscribes to handshake messages and hands an ephemeral transport key
+    /// pair to the router. Must be called before the router is started.
     pub fn new(router: &'a Router) -> Self {
+        let transport_key_pair = TransportKeyPair::generate();
+        let transport_public_key = transport_key_pair.public_key();
+        router.set_transport_key_pair(transport_key_pair);
         Self {
             router,
             version_receiver: router.subscribe(vec![KaspadMessagePayloadType::Version]),
             verack_receiver: router.subscribe(vec![KaspadMessagePayloadType::Verack]),
             ready_receiver: router.subscribe(vec![KaspadMessagePayloadType::Ready]),
+            transport_public_key,
         }
     }
 
@@ -36,16 +44,14 @@ impl<'a> KaspadHandshake<'a> {
         Ok(version_message)
     }
 
-    async fn send_version_flow(
-        router: &Router,
-        verack_receiver: &mut IncomingRoute,
-        version_message: VersionMessage,
-    ) -> Result<(), ProtocolError> {
-        debug!("starting send version flow");
-
+    async fn send_version(router: &Router, version_message: VersionMessage) -> Result<(), ProtocolError> {
         debug!("sending version message: {version_message:?}");
         let version_message = make_message!(Payload::Version, version_message);
-        router.enqueue(version_message).await?;
+        router.enqueue(version_message).await
+    }
+
+    async fn receive_verack_flow(verack_receiver: &mut IncomingRoute) -> Result<(), ProtocolError> {
+        debug!("starting receive verack flow");
 
         let verack_message = dequeue_with_timeout!(verack_receiver, Payload::Verack, Duration::from_secs(4))?;
         debug!("accepted verack_message: {verack_message:?}");
@@ -67,14 +73,19 @@ impl<'a> KaspadHandshake<'a> {
         Ok(())
     }
 
-    /// Performs the handshake with the peer, essentially exchanging version messages
-    pub async fn handshake(&mut self, self_version_message: VersionMessage) -> Result<VersionMessage, ProtocolError> {
-        // Run both send and receive flows concurrently -- this is critical in order to avoid a handshake deadlock
-        let (send_res, recv_res) = tokio::join!(
-            Self::send_version_flow(self.router, &mut self.verack_receiver, self_version_message),
+    /// Performs the handshake with the peer, essentially exchanging version messages, and negotiates an encrypted
+    /// transport if the peer supports it
+    pub async fn handshake(&mut self, mut self_version_message: VersionMessage) -> Result<VersionMessage, ProtocolError> {
+        self_version_message.transport_public_key = self.transport_public_key.clone();
+        // The version message must precede any other message, since the peer expects all messages following it to
+        // be encrypted once the transport is negotiated
+        Self::send_version(self.router, self_version_message).await?;
+        // Run both receive flows concurrently -- this is critical in order to avoid a handshake deadlock
+        let (verack_res, recv_res) = tokio::join!(
+            Self::receive_verack_flow(&mut self.verack_receiver),
             Self::receive_version_flow(self.router, &mut self.version_receiver)
         );
-        send_res?;
+        verack_res?;
         recv_res
     }
 }
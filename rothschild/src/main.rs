@@ -1,4 +1,4 @@
-use std::{collections::HashMap, sync::Arc, time::Duration};
+use std::{collections::HashMap, net::SocketAddr, sync::Arc, time::Duration};
 
 use clap::{Arg, ArgAction, Command};
 use itertools::Itertools;
@@ -41,6 +41,7 @@ pub struct Args {
     pub private_key: Option<String>,
     pub tps: u64,
     pub rpc_server: String,
+    pub proxy: Option<SocketAddr>,
     pub threads: u8,
     pub unleashed: bool,
     pub addr: Option<String>,
@@ -55,6 +56,7 @@ impl Args {
             private_key: m.get_one::<String>("private-key").cloned(),
             tps: m.get_one::<u64>("tps").cloned().unwrap(),
             rpc_server: m.get_one::<String>("rpcserver").cloned().unwrap_or("localhost:16210".to_owned()),
+            proxy: m.get_one::<SocketAddr>("proxy").cloned(),
             threads: m.get_one::<u8>("threads").cloned().unwrap(),
             unleashed: m.get_one::<bool>("unleashed").cloned().unwrap_or(false),
             addr: m.get_one::<String>("addr").cloned(),
@@ -86,6 +88,13 @@ pub fn cli() -> Command {
                 .default_value("localhost:16210")
                 .help("RPC server"),
         )
+        .arg(
+            Arg::new("proxy")
+                .long("proxy")
+                .value_name("proxy")
+                .value_parser(clap::value_parser!(SocketAddr))
+                .help("SOCKS5 proxy to connect to the RPC server through (eg. 127.0.0.1:9050)"),
+        )
         .arg(
             Arg::new("threads")
                 .long("threads")
@@ -115,7 +124,7 @@ pub fn cli() -> Command {
         )
 }
 
-async fn new_rpc_client(subscription_context: &SubscriptionContext, address: &str) -> GrpcClient {
+async fn new_rpc_client(subscription_context: &SubscriptionContext, address: &str, proxy: Option<SocketAddr>) -> GrpcClient {
     GrpcClient::connect_with_args(
         NotificationMode::Direct,
         format!("grpc://{}", address),
@@ -125,6 +134,8 @@ async fn new_rpc_client(subscription_context: &SubscriptionContext, address: &st
         false,
         Some(500_000),
         Default::default(),
+        None,
+        proxy,
     )
     .await
     .unwrap()
@@ -159,6 +170,8 @@ async fn main() {
         false,
         Some(500_000),
         Default::default(),
+        None,
+        args.proxy,
     )
     .await
     .unwrap();
@@ -232,7 +245,7 @@ async fn main() {
     const CLIENT_POOL_SIZE: usize = 8;
     let mut rpc_clients = Vec::with_capacity(CLIENT_POOL_SIZE);
     for _ in 0..CLIENT_POOL_SIZE {
-        rpc_clients.push(Arc::new(new_rpc_client(&subscription_context, &args.rpc_server).await));
+        rpc_clients.push(Arc::new(new_rpc_client(&subscription_context, &args.rpc_server, args.proxy).await));
     }
 
     let submit_tx_pool = ClientPool::new(rpc_clients, 1000);
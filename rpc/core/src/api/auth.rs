@@ -0,0 +1,386 @@
+//!
+//! Authentication and per-method authorization of RPC clients.
+//!
+//! Clients present their credentials as an `authorization` value, either `Bearer <token>`
+//! or `Basic <base64(username:password)>`. The gRPC server reads it from the request metadata
+//! of the message stream, the wRPC server from an `Authenticate` request sent on the connection.
+//!
+//! Credentials resolve to a role, granting access to a set of [`RpcApiOps`].
+//!
+
+use crate::{api::ops::RpcApiOps, RpcError, RpcResult};
+use base64::{engine::general_purpose, Engine as _};
+use borsh::{BorshDeserialize, BorshSerialize};
+use kaspa_notify::events::EventType;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
+use subtle::ConstantTimeEq;
+use thiserror::Error;
+
+/// Key of the gRPC request metadata entry carrying the client credentials
+pub const AUTHORIZATION_METADATA_KEY: &str = "authorization";
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum RpcAuthConfigError {
+    #[error("role `{0}` is not defined")]
+    UnknownRole(String),
+
+    #[error("role `{0}`: pattern `{1}` matches no RPC method")]
+    UnknownMethod(String, String),
+
+    #[error("user `{0}` is defined more than once")]
+    DuplicateUser(String),
+
+    #[error("a token is defined more than once")]
+    DuplicateToken,
+
+    #[error("usernames, passwords and tokens must not be empty")]
+    EmptyCredentials,
+}
+
+/// A role granting access to the RPC methods matching a pattern of `allow` but none of `deny`.
+///
+/// A pattern is either a method name as found in [`RpcApiOps`] (e.g. `GetBlock`) or a name prefix
+/// followed by `*` (e.g. `Get*`, or `*` for all methods).
+///
+/// Subscriptions are authorized by the matching `Notify*` method (e.g. `NotifyBlockAdded`).
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+pub struct RpcRoleConfig {
+    pub allow: Vec<String>,
+    pub deny: Vec<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct RpcUserConfig {
+    pub username: String,
+    pub password: String,
+    pub role: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct RpcTokenConfig {
+    pub token: String,
+    pub role: String,
+}
+
+/// RPC authentication settings, deserialized from the `[rpc-auth]` section of the kaspad config file.
+///
+/// ```toml
+/// [rpc-auth]
+/// anonymous-role = "read-only"
+///
+/// [rpc-auth.roles.read-only]
+/// allow = ["Get*", "Notify*", "Ping"]
+/// deny = ["GetMempoolPolicy"]
+///
+/// [rpc-auth.roles.admin]
+/// allow = ["*"]
+///
+/// [[rpc-auth.users]]
+/// username = "operator"
+/// password = "secret"
+/// role = "admin"
+///
+/// [[rpc-auth.tokens]]
+/// token = "0123456789abcdef"
+/// role = "read-only"
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+pub struct RpcAuthConfig {
+    /// Role granted to clients presenting no credentials. If unset, such clients are refused.
+    pub anonymous_role: Option<String>,
+    pub roles: HashMap<String, RpcRoleConfig>,
+    pub users: Vec<RpcUserConfig>,
+    pub tokens: Vec<RpcTokenConfig>,
+}
+
+/// Client credentials
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RpcCredentials {
+    Token(String),
+    Basic { username: String, password: String },
+}
+
+impl RpcCredentials {
+    /// Parses an `authorization` value
+    pub fn parse(authorization: &str) -> RpcResult<Self> {
+        let (scheme, value) = authorization.trim().split_once(' ').ok_or(RpcError::AuthenticationFailed)?;
+        let value = value.trim();
+        if scheme.eq_ignore_ascii_case("bearer") {
+            Ok(Self::Token(value.to_string()))
+        } else if scheme.eq_ignore_ascii_case("basic") {
+            let decoded = general_purpose::STANDARD.decode(value).map_err(|_| RpcError::AuthenticationFailed)?;
+            let decoded = String::from_utf8(decoded).map_err(|_| RpcError::AuthenticationFailed)?;
+            let (username, password) = decoded.split_once(':').ok_or(RpcError::AuthenticationFailed)?;
+            Ok(Self::Basic { username: username.to_string(), password: password.to_string() })
+        } else {
+            Err(RpcError::AuthenticationFailed)
+        }
+    }
+
+    /// Returns the `authorization` value carrying these credentials
+    pub fn to_authorization(&self) -> String {
+        match self {
+            Self::Token(token) => format!("Bearer {token}"),
+            Self::Basic { username, password } => {
+                format!("Basic {}", general_purpose::STANDARD.encode(format!("{username}:{password}")))
+            }
+        }
+    }
+}
+
+/// The RPC methods granted to an authenticated client
+#[derive(Debug, PartialEq, Eq)]
+pub struct RpcPermissions {
+    role: String,
+    allowed: HashSet<RpcApiOps>,
+}
+
+pub type RpcPermissionsRef = Arc<RpcPermissions>;
+
+impl RpcPermissions {
+    fn from_config(name: &str, config: &RpcRoleConfig) -> Result<Self, RpcAuthConfigError> {
+        let resolve = |patterns: &[String]| -> Result<HashSet<RpcApiOps>, RpcAuthConfigError> {
+            let mut ops = HashSet::new();
+            for pattern in patterns.iter() {
+                let matched = RpcApiOps::into_iter().filter(|op| matches_pattern(pattern, op.as_str())).collect::<Vec<_>>();
+                if matched.is_empty() {
+                    return Err(RpcAuthConfigError::UnknownMethod(name.to_string(), pattern.clone()));
+                }
+                ops.extend(matched);
+            }
+            Ok(ops)
+        };
+        let denied = resolve(&config.deny)?;
+        let allowed = resolve(&config.allow)?.into_iter().filter(|op| !denied.contains(op)).collect();
+        Ok(Self { role: name.to_string(), allowed })
+    }
+
+    pub fn role(&self) -> &str {
+        &self.role
+    }
+
+    pub fn allows(&self, op: RpcApiOps) -> bool {
+        self.allowed.contains(&op)
+    }
+
+    /// Returns [`RpcError::MethodNotAllowed`] if `op` is not granted
+    pub fn authorize(&self, op: RpcApiOps) -> RpcResult<()> {
+        match self.allows(op) {
+            true => Ok(()),
+            false => Err(RpcError::MethodNotAllowed(op.as_str().to_string(), self.role.clone())),
+        }
+    }
+
+    /// Authorizes a subscription to `event` against the matching `Notify*` method
+    pub fn authorize_subscription(&self, event: EventType) -> RpcResult<()> {
+        self.authorize(match event {
+            EventType::BlockAdded => RpcApiOps::NotifyBlockAdded,
+            EventType::VirtualChainChanged => RpcApiOps::NotifyVirtualChainChanged,
+            EventType::FinalityConflict => RpcApiOps::NotifyFinalityConflict,
+            EventType::FinalityConflictResolved => RpcApiOps::NotifyFinalityConflictResolved,
+            EventType::UtxosChanged => RpcApiOps::NotifyUtxosChanged,
+            EventType::SinkBlueScoreChanged => RpcApiOps::NotifySinkBlueScoreChanged,
+            EventType::VirtualDaaScoreChanged => RpcApiOps::NotifyVirtualDaaScoreChanged,
+            EventType::PruningPointUtxoSetOverride => RpcApiOps::NotifyPruningPointUtxoSetOverride,
+            EventType::NewBlockTemplate => RpcApiOps::NotifyNewBlockTemplate,
+            EventType::MempoolChanged => RpcApiOps::NotifyMempoolChanged,
+        })
+    }
+}
+
+fn matches_pattern(pattern: &str, name: &str) -> bool {
+    match pattern.strip_suffix('*') {
+        Some(prefix) => name.starts_with(prefix),
+        None => name == pattern,
+    }
+}
+
+fn constant_time_eq(a: &str, b: &str) -> bool {
+    a.as_bytes().ct_eq(b.as_bytes()).into()
+}
+
+#[derive(Debug)]
+struct RpcUser {
+    username: String,
+    password: String,
+    permissions: RpcPermissionsRef,
+}
+
+/// Resolves client credentials into [`RpcPermissions`]
+#[derive(Debug)]
+pub struct RpcAuthenticator {
+    anonymous: Option<RpcPermissionsRef>,
+    users: Vec<RpcUser>,
+    tokens: Vec<(String, RpcPermissionsRef)>,
+}
+
+impl RpcAuthenticator {
+    pub fn new(config: &RpcAuthConfig) -> Result<Self, RpcAuthConfigError> {
+        let roles = config
+            .roles
+            .iter()
+            .map(|(name, role)| Ok((name.clone(), Arc::new(RpcPermissions::from_config(name, role)?))))
+            .collect::<Result<HashMap<_, _>, RpcAuthConfigError>>()?;
+        let role = |name: &String| roles.get(name).cloned().ok_or_else(|| RpcAuthConfigError::UnknownRole(name.clone()));
+
+        let anonymous = config.anonymous_role.as_ref().map(role).transpose()?;
+        let mut users: Vec<RpcUser> = Vec::with_capacity(config.users.len());
+        for user in config.users.iter() {
+            if user.username.is_empty() || user.password.is_empty() {
+                return Err(RpcAuthConfigError::EmptyCredentials);
+            }
+            if users.iter().any(|x| x.username == user.username) {
+                return Err(RpcAuthConfigError::DuplicateUser(user.username.clone()));
+            }
+            users.push(RpcUser { username: user.username.clone(), password: user.password.clone(), permissions: role(&user.role)? });
+        }
+        let mut tokens: Vec<(String, RpcPermissionsRef)> = Vec::with_capacity(config.tokens.len());
+        for token in config.tokens.iter() {
+            if token.token.is_empty() {
+                return Err(RpcAuthConfigError::EmptyCredentials);
+            }
+            if tokens.iter().any(|(x, _)| *x == token.token) {
+                return Err(RpcAuthConfigError::DuplicateToken);
+            }
+            tokens.push((token.token.clone(), role(&token.role)?));
+        }
+        Ok(Self { anonymous, users, tokens })
+    }
+
+    /// Resolves the permissions of a client presenting the `authorization` value, if any
+    pub fn authenticate(&self, authorization: Option<&str>) -> RpcResult<RpcPermissionsRef> {
+        let Some(authorization) = authorization else {
+            return self.anonymous.clone().ok_or(RpcError::AuthenticationFailed);
+        };
+        match RpcCredentials::parse(authorization)? {
+            RpcCredentials::Token(token) => {
+                // Compare against every token so that timing does not reveal which one matched
+                self.tokens.iter().fold(
+                    None,
+                    |found, (x, permissions)| {
+                        if constant_time_eq(x, &token) {
+                            Some(permissions.clone())
+                        } else {
+                            found
+                        }
+                    },
+                )
+            }
+            RpcCredentials::Basic { username, password } => {
+                // Compare against every user, on both the username and the password, so that timing
+                // reveals neither whether the username exists nor which user matched
+                self.users.iter().fold(None, |found, user| {
+                    let matched =
+                        user.username.as_bytes().ct_eq(username.as_bytes()) & user.password.as_bytes().ct_eq(password.as_bytes());
+                    if matched.into() {
+                        Some(user.permissions.clone())
+                    } else {
+                        found
+                    }
+                })
+            }
+        }
+        .ok_or(RpcError::AuthenticationFailed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> RpcAuthConfig {
+        RpcAuthConfig {
+            anonymous_role: None,
+            roles: HashMap::from([
+                (
+                    "read-only".to_string(),
+                    RpcRoleConfig { allow: vec!["Get*".into(), "Notify*".into()], deny: vec!["GetMetrics".into()] },
+                ),
+                ("admin".to_string(), RpcRoleConfig { allow: vec!["*".into()], deny: vec![] }),
+            ]),
+            users: vec![RpcUserConfig { username: "alice".into(), password: "pa:ss".into(), role: "admin".into() }],
+            tokens: vec![RpcTokenConfig { token: "t0ken".into(), role: "read-only".into() }],
+        }
+    }
+
+    #[test]
+    fn test_credentials_round_trip() {
+        for credentials in [
+            RpcCredentials::Token("t0ken".to_string()),
+            RpcCredentials::Basic { username: "alice".to_string(), password: "pa:ss".to_string() },
+        ] {
+            assert_eq!(RpcCredentials::parse(&credentials.to_authorization()).unwrap(), credentials);
+        }
+        for invalid in ["", "t0ken", "Digest abc", "Basic !!!", "Basic YWxpY2U="] {
+            assert!(RpcCredentials::parse(invalid).is_err(), "{invalid} should not parse");
+        }
+    }
+
+    #[test]
+    fn test_authenticate() {
+        let authenticator = RpcAuthenticator::new(&config()).unwrap();
+
+        let read_only = authenticator.authenticate(Some("Bearer t0ken")).unwrap();
+        assert_eq!(read_only.role(), "read-only");
+        assert!(read_only.authorize(RpcApiOps::GetBlock).is_ok());
+        assert!(read_only.authorize_subscription(EventType::BlockAdded).is_ok());
+        assert!(read_only.authorize(RpcApiOps::GetMetrics).is_err());
+        assert!(read_only.authorize(RpcApiOps::SubmitTransaction).is_err());
+        assert!(read_only.authorize(RpcApiOps::Shutdown).is_err());
+
+        let credentials = RpcCredentials::Basic { username: "alice".to_string(), password: "pa:ss".to_string() };
+        let admin = authenticator.authenticate(Some(&credentials.to_authorization())).unwrap();
+        assert_eq!(admin.role(), "admin");
+        assert!(admin.authorize(RpcApiOps::Shutdown).is_ok());
+
+        let credentials = RpcCredentials::Basic { username: "alice".to_string(), password: "wrong".to_string() };
+        assert!(authenticator.authenticate(Some(&credentials.to_authorization())).is_err());
+        let credentials = RpcCredentials::Basic { username: "bob".to_string(), password: "pa:ss".to_string() };
+        assert!(authenticator.authenticate(Some(&credentials.to_authorization())).is_err());
+        assert!(authenticator.authenticate(Some("Bearer wrong")).is_err());
+        assert!(authenticator.authenticate(None).is_err());
+
+        let authenticator = RpcAuthenticator::new(&RpcAuthConfig { anonymous_role: Some("read-only".into()), ..config() }).unwrap();
+        assert_eq!(authenticator.authenticate(None).unwrap().role(), "read-only");
+    }
+
+    #[test]
+    fn test_invalid_config() {
+        let tests = [
+            (RpcAuthConfig { anonymous_role: Some("guest".into()), ..config() }, RpcAuthConfigError::UnknownRole("guest".into())),
+            (
+                RpcAuthConfig {
+                    roles: HashMap::from([("typo".to_string(), RpcRoleConfig { allow: vec!["GetBlok".into()], deny: vec![] })]),
+                    users: vec![],
+                    tokens: vec![],
+                    ..config()
+                },
+                RpcAuthConfigError::UnknownMethod("typo".into(), "GetBlok".into()),
+            ),
+            (
+                RpcAuthConfig { users: [config().users.clone(), config().users.clone()].concat(), ..config() },
+                RpcAuthConfigError::DuplicateUser("alice".into()),
+            ),
+            (
+                RpcAuthConfig { tokens: [config().tokens.clone(), config().tokens.clone()].concat(), ..config() },
+                RpcAuthConfigError::DuplicateToken,
+            ),
+            (
+                RpcAuthConfig { tokens: vec![RpcTokenConfig { token: "".into(), role: "admin".into() }], ..config() },
+                RpcAuthConfigError::EmptyCredentials,
+            ),
+        ];
+        for (config, expected) in tests {
+            assert_eq!(RpcAuthenticator::new(&config).unwrap_err(), expected);
+        }
+    }
+}
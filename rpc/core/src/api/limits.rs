@@ -0,0 +1,517 @@
+//!
+//! Token-bucket rate limiting of RPC requests.
+//!
+//! Every RPC method belongs to a [`RpcCostClass`], each class being granted its own request budget.
+//! Budgets apply both to every single connection and to all the connections sharing an IP address.
+//! A request exceeding any of them is refused with [`RpcError::RateLimitExceeded`].
+//!
+//! Requests querying a list of addresses account for one request per address (see [`RpcRequestCost`]).
+//! Such a request is admitted as long as a budget has a token left, the budget then being in debt
+//! for the requests that follow.
+//!
+//! The limits in effect are reported by `GetServerInfo` so that clients can throttle themselves.
+//!
+
+use crate::{api::ops::RpcApiOps, model::message::*, RpcError, RpcResult};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    net::IpAddr,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+use thiserror::Error;
+use workflow_serializer::prelude::*;
+
+/// Interval at which the buckets of idle IP addresses get dropped
+const IP_PRUNING_INTERVAL: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum RpcRateLimitConfigError {
+    #[error("{0} limit of {1} methods: rate must be finite and positive, burst at least 1")]
+    InvalidLimit(&'static str, &'static str),
+}
+
+/// Relative cost of serving an RPC method
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum RpcCostClass {
+    /// Constant time queries of the node state
+    Light,
+    Standard,
+    /// Methods iterating the DAG, the UTXO set, the mempool or an index
+    Heavy,
+}
+
+impl RpcCostClass {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RpcCostClass::Light => "light",
+            RpcCostClass::Standard => "standard",
+            RpcCostClass::Heavy => "heavy",
+        }
+    }
+}
+
+impl RpcApiOps {
+    pub fn cost_class(&self) -> RpcCostClass {
+        match self {
+            RpcApiOps::NoOp
+            | RpcApiOps::Connect
+            | RpcApiOps::Disconnect
+            | RpcApiOps::Subscribe
+            | RpcApiOps::Unsubscribe
+            | RpcApiOps::Authenticate
+            | RpcApiOps::Ping
+            | RpcApiOps::GetInfo
+            | RpcApiOps::GetServerInfo
+            | RpcApiOps::GetSyncStatus
+            | RpcApiOps::GetCurrentNetwork
+            | RpcApiOps::GetSink
+            | RpcApiOps::GetSinkBlueScore
+            | RpcApiOps::GetBlockCount
+            | RpcApiOps::GetFeeEstimate => RpcCostClass::Light,
+            op if op.is_subscription() => RpcCostClass::Light,
+            RpcApiOps::GetBlocks
+            | RpcApiOps::GetHeaders
+            | RpcApiOps::GetVirtualChainFromBlock
+            | RpcApiOps::GetUtxosByAddresses
+            | RpcApiOps::GetBalancesByAddresses
+            | RpcApiOps::GetMempoolEntries
+            | RpcApiOps::GetMempoolEntriesByAddresses
+            | RpcApiOps::GetAddressHistory
+            | RpcApiOps::GetCoinSupply
+            | RpcApiOps::GetCurrentBlockColor
+            | RpcApiOps::GetDaaScoreTimestampEstimate
+            | RpcApiOps::EstimateNetworkHashesPerSecond
+            | RpcApiOps::GetFeeEstimateExperimental => RpcCostClass::Heavy,
+            _ => RpcCostClass::Standard,
+        }
+    }
+}
+
+/// Number of requests a call accounts for against the budget of its cost class
+pub trait RpcRequestCost {
+    fn cost(&self) -> u32 {
+        1
+    }
+}
+
+/// Cost of a request querying `addresses`, each address being looked up separately
+pub fn addresses_cost(addresses: usize) -> u32 {
+    addresses.clamp(1, u32::MAX as usize) as u32
+}
+
+macro_rules! unit_cost {
+    ($($request:ty),* $(,)?) => {
+        $(impl RpcRequestCost for $request {})*
+    };
+}
+
+unit_cost!(
+    SubmitBlockRequest,
+    GetBlockTemplateRequest,
+    GetBlockRequest,
+    GetInfoRequest,
+    GetCurrentNetworkRequest,
+    GetPeerAddressesRequest,
+    GetSinkRequest,
+    GetMempoolEntryRequest,
+    GetMempoolEntriesRequest,
+    GetConnectedPeerInfoRequest,
+    AddPeerRequest,
+    SubmitTransactionRequest,
+    SubmitTransactionReplacementRequest,
+    GetSubnetworkRequest,
+    GetVirtualChainFromBlockRequest,
+    GetBlocksRequest,
+    GetBlockCountRequest,
+    GetBlockDagInfoRequest,
+    ResolveFinalityConflictRequest,
+    ShutdownRequest,
+    GetHeadersRequest,
+    GetBalanceByAddressRequest,
+    GetSinkBlueScoreRequest,
+    BanRequest,
+    UnbanRequest,
+    EstimateNetworkHashesPerSecondRequest,
+    GetCoinSupplyRequest,
+    PingRequest,
+    GetConnectionsRequest,
+    GetSystemInfoRequest,
+    GetMetricsRequest,
+    GetServerInfoRequest,
+    GetSyncStatusRequest,
+    GetDaaScoreTimestampEstimateRequest,
+    GetFeeEstimateRequest,
+    GetFeeEstimateExperimentalRequest,
+    GetCurrentBlockColorRequest,
+    GetTransactionRequest,
+    GetTransactionAcceptanceRequest,
+    GetAddressHistoryRequest,
+    GetMempoolPolicyRequest,
+    SetMempoolPolicyRequest,
+    BackupRequest,
+    AuthenticateRequest,
+);
+
+impl RpcRequestCost for GetUtxosByAddressesRequest {
+    fn cost(&self) -> u32 {
+        addresses_cost(self.addresses.len())
+    }
+}
+
+impl RpcRequestCost for GetBalancesByAddressesRequest {
+    fn cost(&self) -> u32 {
+        addresses_cost(self.addresses.len())
+    }
+}
+
+impl RpcRequestCost for GetMempoolEntriesByAddressesRequest {
+    fn cost(&self) -> u32 {
+        addresses_cost(self.addresses.len())
+    }
+}
+
+/// A budget of `burst` requests, refilled at `rate` requests per second
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct RpcRateLimit {
+    pub rate: f64,
+    pub burst: u32,
+}
+
+impl Serializer for RpcRateLimit {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u8, &1, writer)?; // version
+        store!(f64, &self.rate, writer)?;
+        store!(u32, &self.burst, writer)
+    }
+}
+
+impl Deserializer for RpcRateLimit {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version: u8 = load!(u8, reader)?;
+        let rate = load!(f64, reader)?;
+        let burst = load!(u32, reader)?;
+        Ok(Self { rate, burst })
+    }
+}
+
+/// The budget of each cost class, unset classes being unlimited
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase", deny_unknown_fields)]
+pub struct RpcRateLimits {
+    pub light: Option<RpcRateLimit>,
+    pub standard: Option<RpcRateLimit>,
+    pub heavy: Option<RpcRateLimit>,
+}
+
+impl RpcRateLimits {
+    const CLASSES: [RpcCostClass; 3] = [RpcCostClass::Light, RpcCostClass::Standard, RpcCostClass::Heavy];
+
+    pub fn get(&self, class: RpcCostClass) -> Option<&RpcRateLimit> {
+        match class {
+            RpcCostClass::Light => self.light.as_ref(),
+            RpcCostClass::Standard => self.standard.as_ref(),
+            RpcCostClass::Heavy => self.heavy.as_ref(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.light.is_none() && self.standard.is_none() && self.heavy.is_none()
+    }
+}
+
+impl Serializer for RpcRateLimits {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u8, &1, writer)?; // version
+        serialize!(Option<RpcRateLimit>, &self.light, writer)?;
+        serialize!(Option<RpcRateLimit>, &self.standard, writer)?;
+        serialize!(Option<RpcRateLimit>, &self.heavy, writer)
+    }
+}
+
+impl Deserializer for RpcRateLimits {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version: u8 = load!(u8, reader)?;
+        let light = deserialize!(Option<RpcRateLimit>, reader)?;
+        let standard = deserialize!(Option<RpcRateLimit>, reader)?;
+        let heavy = deserialize!(Option<RpcRateLimit>, reader)?;
+        Ok(Self { light, standard, heavy })
+    }
+}
+
+/// RPC rate limits, deserialized from the `[rpc-limits]` section of the kaspad config file
+/// and reported to clients by `GetServerInfo`.
+///
+/// ```toml
+/// [rpc-limits.connection]
+/// light = { rate = 50.0, burst = 100 }
+/// heavy = { rate = 1.0, burst = 5 }
+///
+/// [rpc-limits.ip]
+/// standard = { rate = 100.0, burst = 200 }
+/// heavy = { rate = 5.0, burst = 20 }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase", deny_unknown_fields)]
+pub struct RpcRateLimitConfig {
+    /// Budget of each connection
+    pub connection: RpcRateLimits,
+    /// Budget shared by all the connections of an IP address
+    pub ip: RpcRateLimits,
+}
+
+impl RpcRateLimitConfig {
+    pub fn is_empty(&self) -> bool {
+        self.connection.is_empty() && self.ip.is_empty()
+    }
+
+    fn validate(&self) -> Result<(), RpcRateLimitConfigError> {
+        for (scope, limits) in [("connection", &self.connection), ("ip", &self.ip)] {
+            for class in RpcRateLimits::CLASSES {
+                if let Some(limit) = limits.get(class) {
+                    if !limit.rate.is_finite() || limit.rate <= 0.0 || limit.burst == 0 {
+                        return Err(RpcRateLimitConfigError::InvalidLimit(scope, class.as_str()));
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Serializer for RpcRateLimitConfig {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u8, &1, writer)?; // version
+        serialize!(RpcRateLimits, &self.connection, writer)?;
+        serialize!(RpcRateLimits, &self.ip, writer)
+    }
+}
+
+impl Deserializer for RpcRateLimitConfig {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version: u8 = load!(u8, reader)?;
+        let connection = deserialize!(RpcRateLimits, reader)?;
+        let ip = deserialize!(RpcRateLimits, reader)?;
+        Ok(Self { connection, ip })
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+struct TokenBucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+impl TokenBucket {
+    fn new(limit: &RpcRateLimit, now: Instant) -> Self {
+        Self { tokens: limit.burst as f64, updated: now }
+    }
+
+    fn refill(&mut self, limit: &RpcRateLimit, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.updated).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * limit.rate).min(limit.burst as f64);
+        self.updated = now;
+    }
+
+    /// Time to wait until a token is available, if none is
+    fn wait_time(&self, limit: &RpcRateLimit) -> Option<Duration> {
+        (self.tokens < 1.0).then(|| Duration::from_secs_f64((1.0 - self.tokens) / limit.rate))
+    }
+
+    fn is_full(&self, limit: &RpcRateLimit) -> bool {
+        self.tokens >= limit.burst as f64
+    }
+}
+
+/// A token bucket per limited cost class
+#[derive(Debug, Default)]
+struct TokenBuckets(HashMap<RpcCostClass, TokenBucket>);
+
+impl TokenBuckets {
+    /// Refills the bucket of `class`, returning the time to wait until it holds a token, if it is empty
+    fn check(&mut self, limits: &RpcRateLimits, class: RpcCostClass, now: Instant) -> Option<Duration> {
+        let limit = limits.get(class)?;
+        let bucket = self.0.entry(class).or_insert_with(|| TokenBucket::new(limit, now));
+        bucket.refill(limit, now);
+        bucket.wait_time(limit)
+    }
+
+    fn take(&mut self, class: RpcCostClass, cost: u32) {
+        if let Some(bucket) = self.0.get_mut(&class) {
+            bucket.tokens -= cost as f64;
+        }
+    }
+
+    fn is_full(&self, limits: &RpcRateLimits, now: Instant) -> bool {
+        self.0.iter().all(|(class, bucket)| {
+            let limit = limits.get(*class).unwrap();
+            let mut bucket = *bucket;
+            bucket.refill(limit, now);
+            bucket.is_full(limit)
+        })
+    }
+}
+
+#[derive(Debug)]
+struct IpBuckets {
+    buckets: HashMap<IpAddr, TokenBuckets>,
+    last_pruning: Instant,
+}
+
+/// Rate limiter shared by all the connections of an RPC server
+#[derive(Debug)]
+pub struct RpcRateLimiter {
+    config: RpcRateLimitConfig,
+    ips: Mutex<IpBuckets>,
+}
+
+impl RpcRateLimiter {
+    pub fn new(config: RpcRateLimitConfig) -> Result<Self, RpcRateLimitConfigError> {
+        config.validate()?;
+        Ok(Self { config, ips: Mutex::new(IpBuckets { buckets: HashMap::new(), last_pruning: Instant::now() }) })
+    }
+
+    pub fn config(&self) -> &RpcRateLimitConfig {
+        &self.config
+    }
+
+    /// Creates the rate limiter of a new connection opened from `ip`
+    pub fn connection(self: &Arc<Self>, ip: IpAddr) -> RpcConnectionRateLimiter {
+        RpcConnectionRateLimiter { limiter: self.clone(), ip, buckets: Default::default() }
+    }
+
+    fn check(&self, ip: IpAddr, connection: &mut TokenBuckets, op: RpcApiOps, cost: u32, now: Instant) -> RpcResult<()> {
+        let class = op.cost_class();
+        let mut ips = self.ips.lock().unwrap();
+        if now.saturating_duration_since(ips.last_pruning) >= IP_PRUNING_INTERVAL {
+            // Buckets refilled to their burst are equivalent to new ones
+            ips.buckets.retain(|_, buckets| !buckets.is_full(&self.config.ip, now));
+            ips.last_pruning = now;
+        }
+        let ip_buckets = ips.buckets.entry(ip).or_default();
+
+        // Both budgets must have a token before any gets consumed
+        let wait_time = connection.check(&self.config.connection, class, now).max(ip_buckets.check(&self.config.ip, class, now));
+        if let Some(wait_time) = wait_time {
+            let retry_after = wait_time.as_millis().try_into().unwrap_or(u64::MAX);
+            return Err(RpcError::RateLimitExceeded(op.as_str().to_string(), retry_after));
+        }
+        connection.take(class, cost);
+        ip_buckets.take(class, cost);
+        Ok(())
+    }
+}
+
+/// Rate limiter of a single connection
+#[derive(Debug)]
+pub struct RpcConnectionRateLimiter {
+    limiter: Arc<RpcRateLimiter>,
+    ip: IpAddr,
+    buckets: Mutex<TokenBuckets>,
+}
+
+impl RpcConnectionRateLimiter {
+    /// Consumes `cost` requests of the budgets `op` falls into, returning [`RpcError::RateLimitExceeded`] if any is exhausted
+    pub fn check(&self, op: RpcApiOps, cost: u32) -> RpcResult<()> {
+        self.limiter.check(self.ip, &mut self.buckets.lock().unwrap(), op, cost, Instant::now())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> RpcRateLimitConfig {
+        RpcRateLimitConfig {
+            connection: RpcRateLimits { heavy: Some(RpcRateLimit { rate: 1.0, burst: 2 }), ..Default::default() },
+            ip: RpcRateLimits { heavy: Some(RpcRateLimit { rate: 1.0, burst: 3 }), ..Default::default() },
+        }
+    }
+
+    #[test]
+    fn test_cost_classes() {
+        assert_eq!(RpcApiOps::Ping.cost_class(), RpcCostClass::Light);
+        assert_eq!(RpcApiOps::NotifyBlockAdded.cost_class(), RpcCostClass::Light);
+        assert_eq!(RpcApiOps::SubmitTransaction.cost_class(), RpcCostClass::Standard);
+        assert_eq!(RpcApiOps::GetUtxosByAddresses.cost_class(), RpcCostClass::Heavy);
+    }
+
+    #[test]
+    fn test_rate_limiter() {
+        let limiter = Arc::new(RpcRateLimiter::new(config()).unwrap());
+        let ip: IpAddr = [192, 168, 0, 1].into();
+        let now = Instant::now();
+        let (mut first, mut second) = (TokenBuckets::default(), TokenBuckets::default());
+
+        // Unlimited classes are never refused
+        for _ in 0..100 {
+            limiter.check(ip, &mut first, RpcApiOps::GetBlock, 1, now).unwrap();
+        }
+
+        // The connection budget is exhausted first, then the IP one
+        limiter.check(ip, &mut first, RpcApiOps::GetBlocks, 1, now).unwrap();
+        limiter.check(ip, &mut first, RpcApiOps::GetBlocks, 1, now).unwrap();
+        assert!(matches!(
+            limiter.check(ip, &mut first, RpcApiOps::GetBlocks, 1, now),
+            Err(RpcError::RateLimitExceeded(op, 1000)) if op == "GetBlocks"
+        ));
+        limiter.check(ip, &mut second, RpcApiOps::GetHeaders, 1, now).unwrap();
+        assert!(limiter.check(ip, &mut second, RpcApiOps::GetHeaders, 1, now).is_err());
+
+        // Other IP addresses have their own budget
+        limiter.check([192, 168, 0, 2].into(), &mut second, RpcApiOps::GetHeaders, 1, now).unwrap();
+
+        // Buckets get refilled over time
+        let later = now + Duration::from_millis(1500);
+        limiter.check(ip, &mut first, RpcApiOps::GetBlocks, 1, later).unwrap();
+        assert!(limiter.check(ip, &mut second, RpcApiOps::GetBlocks, 1, later).is_err());
+    }
+
+    #[test]
+    fn test_ip_pruning() {
+        let limiter = Arc::new(RpcRateLimiter::new(config()).unwrap());
+        let now = Instant::now();
+        let mut buckets = TokenBuckets::default();
+        limiter.check([10, 0, 0, 1].into(), &mut buckets, RpcApiOps::GetBlocks, 1, now).unwrap();
+        limiter.check([10, 0, 0, 2].into(), &mut buckets, RpcApiOps::GetBlocks, 1, now).unwrap();
+        assert_eq!(limiter.ips.lock().unwrap().buckets.len(), 2);
+
+        limiter.check([10, 0, 0, 3].into(), &mut TokenBuckets::default(), RpcApiOps::Ping, 1, now + IP_PRUNING_INTERVAL).unwrap();
+        assert_eq!(limiter.ips.lock().unwrap().buckets.len(), 1);
+    }
+
+    #[test]
+    fn test_addresses_cost() {
+        let limiter = Arc::new(RpcRateLimiter::new(config()).unwrap());
+        let ip: IpAddr = [192, 168, 0, 1].into();
+        let now = Instant::now();
+        let mut buckets = TokenBuckets::default();
+        let request = GetUtxosByAddressesRequest::new(vec![]);
+        assert_eq!(request.cost(), 1);
+        assert_eq!(GetBlocksRequest::new(None, false, false).cost(), 1);
+        assert_eq!(addresses_cost(10), 10);
+
+        // A request is admitted with a single token left, leaving the budget in debt for its whole cost
+        limiter.check(ip, &mut buckets, RpcApiOps::GetUtxosByAddresses, addresses_cost(10), now).unwrap();
+        assert!(matches!(
+            limiter.check(ip, &mut buckets, RpcApiOps::GetBlocks, 1, now),
+            Err(RpcError::RateLimitExceeded(op, 9000)) if op == "GetBlocks"
+        ));
+        let later = now + Duration::from_secs(9);
+        limiter.check(ip, &mut buckets, RpcApiOps::GetBlocks, 1, later).unwrap();
+    }
+
+    #[test]
+    fn test_invalid_config() {
+        for (limit, valid) in [((1.0, 1), true), ((0.0, 1), false), ((-1.0, 1), false), ((f64::NAN, 1), false), ((1.0, 0), false)] {
+            let limit = Some(RpcRateLimit { rate: limit.0, burst: limit.1 });
+            let config = RpcRateLimitConfig { ip: RpcRateLimits { standard: limit, ..Default::default() }, ..Default::default() };
+            match valid {
+                true => assert!(RpcRateLimiter::new(config).is_ok()),
+                false => assert_eq!(RpcRateLimiter::new(config).unwrap_err(), RpcRateLimitConfigError::InvalidLimit("ip", "standard")),
+            }
+        }
+    }
+}
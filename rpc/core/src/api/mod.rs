@@ -2,8 +2,11 @@
 //!  API module for the RPC server. Implements core RPC primitives.
 //!
 
+pub mod auth;
 pub mod connection;
 pub mod ctl;
+pub mod limits;
 pub mod notifications;
 pub mod ops;
 pub mod rpc;
+pub mod tls;
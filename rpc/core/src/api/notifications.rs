@@ -9,7 +9,7 @@ use kaspa_notify::{
     notification::{full_featured, Notification as NotificationTrait},
     subscription::{
         context::SubscriptionContext,
-        single::{OverallSubscription, UtxosChangedSubscription, VirtualChainChangedSubscription},
+        single::{MempoolChangedSubscription, OverallSubscription, UtxosChangedSubscription, VirtualChainChangedSubscription},
         Subscription,
     },
 };
@@ -48,6 +48,9 @@ pub enum Notification {
 
     #[display(fmt = "NewBlockTemplate notification")]
     NewBlockTemplate(NewBlockTemplateNotification),
+
+    #[display(fmt = "MempoolChanged notification: transaction {} {:?}", "_0.transaction_id", "_0.kind")]
+    MempoolChanged(MempoolChangedNotification),
 }
 }
 
@@ -64,6 +67,7 @@ impl Notification {
             Notification::VirtualDaaScoreChanged(v) => to_value(&v),
             Notification::SinkBlueScoreChanged(v) => to_value(&v),
             Notification::VirtualChainChanged(v) => to_value(&v),
+            Notification::MempoolChanged(v) => to_value(&v),
         }
     }
 }
@@ -112,6 +116,20 @@ impl NotificationTrait for Notification {
         }
     }
 
+    fn apply_mempool_changed_subscription(
+        &self,
+        subscription: &MempoolChangedSubscription,
+        _context: &SubscriptionContext,
+    ) -> Option<Self> {
+        match subscription.active() {
+            true => {
+                let Self::MempoolChanged(notification) = self else { return None };
+                notification.apply_mempool_changed_subscription(subscription).map(Self::MempoolChanged)
+            }
+            false => None,
+        }
+    }
+
     fn event_type(&self) -> EventType {
         self.into()
     }
@@ -157,6 +175,10 @@ impl Serializer for Notification {
                 store!(u16, &8, writer)?;
                 serialize!(NewBlockTemplateNotification, notification, writer)?;
             }
+            Notification::MempoolChanged(notification) => {
+                store!(u16, &9, writer)?;
+                serialize!(MempoolChangedNotification, notification, writer)?;
+            }
         }
         Ok(())
     }
@@ -202,6 +224,10 @@ impl Deserializer for Notification {
                 let notification = deserialize!(NewBlockTemplateNotification, reader)?;
                 Ok(Notification::NewBlockTemplate(notification))
             }
+            9 => {
+                let notification = deserialize!(MempoolChangedNotification, reader)?;
+                Ok(Notification::MempoolChanged(notification))
+            }
             _ => Err(std::io::Error::new(std::io::ErrorKind::InvalidData, "Invalid variant")),
         }
     }
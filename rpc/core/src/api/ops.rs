@@ -28,6 +28,9 @@ pub enum RpcApiOps {
     Subscribe = 3,
     Unsubscribe = 4,
 
+    // wRPC connection authentication
+    Authenticate = 5,
+
     // ~~~
 
     // Subscription commands for starting/stopping notifications
@@ -40,6 +43,7 @@ pub enum RpcApiOps {
     NotifyVirtualDaaScoreChanged = 16,
     NotifyVirtualChainChanged = 17,
     NotifySinkBlueScoreChanged = 18,
+    NotifyMempoolChanged = 19,
 
     // Notification ops required by wRPC
 
@@ -54,6 +58,7 @@ pub enum RpcApiOps {
     VirtualDaaScoreChangedNotification = 66,
     PruningPointUtxoSetOverrideNotification = 67,
     NewBlockTemplateNotification = 68,
+    MempoolChangedNotification = 69,
 
     // RPC methods
     /// Ping the node to check if connection is alive
@@ -136,6 +141,18 @@ pub enum RpcApiOps {
     GetFeeEstimateExperimental = 148,
     /// Block color determination by iterating DAG.
     GetCurrentBlockColor = 149,
+    /// Get a transaction from the transaction index
+    GetTransaction = 150,
+    /// Get the containing and accepting blocks of transactions from the transaction index
+    GetTransactionAcceptance = 151,
+    /// Get a page of the history of an address from the address history index
+    GetAddressHistory = 152,
+    /// Get the mempool relay policy
+    GetMempoolPolicy = 153,
+    /// Update the mempool relay policy
+    SetMempoolPolicy = 154,
+    /// Create a checkpoint of the node databases
+    Backup = 155,
 }
 
 impl RpcApiOps {
@@ -151,6 +168,7 @@ impl RpcApiOps {
                 | RpcApiOps::NotifyFinalityConflictResolved
                 | RpcApiOps::NotifySinkBlueScoreChanged
                 | RpcApiOps::NotifyVirtualDaaScoreChanged
+                | RpcApiOps::NotifyMempoolChanged
                 | RpcApiOps::Subscribe
                 | RpcApiOps::Unsubscribe
         )
@@ -177,6 +195,7 @@ impl From<EventType> for RpcApiOps {
             EventType::VirtualDaaScoreChanged => RpcApiOps::VirtualDaaScoreChangedNotification,
             EventType::PruningPointUtxoSetOverride => RpcApiOps::PruningPointUtxoSetOverrideNotification,
             EventType::NewBlockTemplate => RpcApiOps::NewBlockTemplateNotification,
+            EventType::MempoolChanged => RpcApiOps::MempoolChangedNotification,
         }
     }
 }
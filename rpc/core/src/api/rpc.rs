@@ -15,6 +15,12 @@ use std::sync::Arc;
 
 pub const MAX_SAFE_WINDOW_SIZE: u32 = 10_000;
 
+/// Maximum number of entries requested from the address history index in a single `GetAddressHistory` call
+pub const MAX_ADDRESS_HISTORY_PAGE_SIZE: u32 = 1_000;
+
+/// Maximum number of headers returned by a single `GetHeaders` call
+pub const MAX_HEADERS_PAGE_SIZE: u64 = 1_000;
+
 /// Client RPC Api
 ///
 /// The [`RpcApi`] trait defines RPC calls taking a request message as unique parameter.
@@ -311,9 +317,9 @@ pub trait RpcApi: Sync + Send + AnySync {
     }
     async fn shutdown_call(&self, connection: Option<&DynRpcConnection>, request: ShutdownRequest) -> RpcResult<ShutdownResponse>;
 
-    /// Requests headers between the given `start_hash` and the current virtual, up to the given limit.
+    /// Requests up to `limit` headers following the given `start_hash`, either towards the sink or towards the pruning point.
     async fn get_headers(&self, start_hash: RpcHash, limit: u64, is_ascending: bool) -> RpcResult<Vec<RpcHeader>> {
-        Ok(self.get_headers_call(None, GetHeadersRequest::new(start_hash, limit, is_ascending)).await?.headers)
+        Ok(self.get_headers_call(None, GetHeadersRequest::new(start_hash, limit, is_ascending, false)).await?.headers)
     }
     async fn get_headers_call(
         &self,
@@ -469,6 +475,78 @@ pub trait RpcApi: Sync + Send + AnySync {
         request: GetCurrentBlockColorRequest,
     ) -> RpcResult<GetCurrentBlockColorResponse>;
 
+    // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    // Transaction index API
+
+    /// Requests an accepted transaction by id. Requires the node to run with `--txindex`.
+    async fn get_transaction(
+        &self,
+        transaction_id: RpcTransactionId,
+        include_verbose_data: bool,
+    ) -> RpcResult<GetTransactionResponse> {
+        self.get_transaction_call(None, GetTransactionRequest { transaction_id, include_verbose_data }).await
+    }
+    async fn get_transaction_call(
+        &self,
+        connection: Option<&DynRpcConnection>,
+        request: GetTransactionRequest,
+    ) -> RpcResult<GetTransactionResponse>;
+
+    /// Requests the containing and accepting blocks of the given transactions. Requires the node to run with `--txindex`.
+    async fn get_transaction_acceptance(&self, transaction_ids: Vec<RpcTransactionId>) -> RpcResult<Vec<RpcTransactionAcceptance>> {
+        Ok(self.get_transaction_acceptance_call(None, GetTransactionAcceptanceRequest { transaction_ids }).await?.acceptances)
+    }
+    async fn get_transaction_acceptance_call(
+        &self,
+        connection: Option<&DynRpcConnection>,
+        request: GetTransactionAcceptanceRequest,
+    ) -> RpcResult<GetTransactionAcceptanceResponse>;
+
+    // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    // Address history index API
+
+    /// Requests a page of the history of `address`, starting at `start_daa_score` (inclusive) and
+    /// holding at most `limit` entries. Requires the node to run with `--addresshistoryindex`.
+    async fn get_address_history(
+        &self,
+        address: RpcAddress,
+        start_daa_score: u64,
+        limit: u32,
+    ) -> RpcResult<GetAddressHistoryResponse> {
+        self.get_address_history_call(None, GetAddressHistoryRequest { address, start_daa_score, limit }).await
+    }
+    async fn get_address_history_call(
+        &self,
+        connection: Option<&DynRpcConnection>,
+        request: GetAddressHistoryRequest,
+    ) -> RpcResult<GetAddressHistoryResponse>;
+
+    /// Requests the mempool relay policy currently in effect.
+    async fn get_mempool_policy(&self) -> RpcResult<GetMempoolPolicyResponse> {
+        self.get_mempool_policy_call(None, GetMempoolPolicyRequest {}).await
+    }
+    async fn get_mempool_policy_call(
+        &self,
+        connection: Option<&DynRpcConnection>,
+        request: GetMempoolPolicyRequest,
+    ) -> RpcResult<GetMempoolPolicyResponse>;
+
+    /// Updates the mempool relay policy, evicting the transactions which no longer satisfy it.
+    async fn set_mempool_policy(&self, request: SetMempoolPolicyRequest) -> RpcResult<SetMempoolPolicyResponse> {
+        self.set_mempool_policy_call(None, request).await
+    }
+    async fn set_mempool_policy_call(
+        &self,
+        connection: Option<&DynRpcConnection>,
+        request: SetMempoolPolicyRequest,
+    ) -> RpcResult<SetMempoolPolicyResponse>;
+
+    /// Creates a consistent checkpoint of the node databases in `target_dir`, a path on the node host.
+    async fn backup(&self, target_dir: String) -> RpcResult<Vec<RpcDatabaseBackup>> {
+        Ok(self.backup_call(None, BackupRequest::new(target_dir)).await?.databases)
+    }
+    async fn backup_call(&self, connection: Option<&DynRpcConnection>, request: BackupRequest) -> RpcResult<BackupResponse>;
+
     // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
     // Notification API
 
@@ -0,0 +1,78 @@
+//!
+//! TLS material of RPC servers and clients.
+//!
+//! Certificates and keys are kept PEM encoded, as read from disk, and are handed over
+//! to the TLS implementation of each transport (tonic for gRPC, rustls for wRPC).
+//!
+
+use std::{fmt, fs, path::Path};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum RpcTlsConfigError {
+    #[error("unable to read `{0}`: {1}")]
+    Read(String, std::io::Error),
+}
+
+fn read_pem(path: &Path) -> Result<Vec<u8>, RpcTlsConfigError> {
+    fs::read(path).map_err(|err| RpcTlsConfigError::Read(path.display().to_string(), err))
+}
+
+/// A PEM encoded certificate chain and its private key
+#[derive(Clone)]
+pub struct RpcTlsIdentity {
+    pub certificate: Vec<u8>,
+    pub private_key: Vec<u8>,
+}
+
+impl RpcTlsIdentity {
+    pub fn load(certificate: impl AsRef<Path>, private_key: impl AsRef<Path>) -> Result<Self, RpcTlsConfigError> {
+        Ok(Self { certificate: read_pem(certificate.as_ref())?, private_key: read_pem(private_key.as_ref())? })
+    }
+}
+
+impl fmt::Debug for RpcTlsIdentity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RpcTlsIdentity").finish_non_exhaustive()
+    }
+}
+
+/// TLS settings of an RPC server listener
+#[derive(Clone, Debug)]
+pub struct RpcServerTlsConfig {
+    /// The certificate presented to clients
+    pub identity: RpcTlsIdentity,
+    /// PEM encoded CA certificates the clients must present a certificate from, enabling mutual TLS
+    pub client_ca: Option<Vec<u8>>,
+}
+
+impl RpcServerTlsConfig {
+    pub fn load(
+        certificate: impl AsRef<Path>,
+        private_key: impl AsRef<Path>,
+        client_ca: Option<impl AsRef<Path>>,
+    ) -> Result<Self, RpcTlsConfigError> {
+        Ok(Self {
+            identity: RpcTlsIdentity::load(certificate, private_key)?,
+            client_ca: client_ca.map(|path| read_pem(path.as_ref())).transpose()?,
+        })
+    }
+}
+
+/// TLS settings of an RPC client.
+///
+/// Only applies to the gRPC client. The WebSocket transport of the wRPC client does not accept a custom
+/// TLS connector, so its `wss://` connections trust the web PKI roots and present no client certificate.
+#[derive(Clone, Debug, Default)]
+pub struct RpcClientTlsConfig {
+    /// PEM encoded CA certificates trusted in place of the built-in web PKI roots
+    pub ca_certificate: Option<Vec<u8>>,
+    /// The certificate presented to servers requiring mutual TLS
+    pub identity: Option<RpcTlsIdentity>,
+}
+
+impl RpcClientTlsConfig {
+    pub fn load(ca_certificate: Option<impl AsRef<Path>>, identity: Option<RpcTlsIdentity>) -> Result<Self, RpcTlsConfigError> {
+        Ok(Self { ca_certificate: ca_certificate.map(|path| read_pem(path.as_ref())).transpose()?, identity })
+    }
+}
@@ -2,8 +2,9 @@
 
 use crate::{
     convert::utxo::utxo_set_into_rpc, BlockAddedNotification, FinalityConflictNotification, FinalityConflictResolvedNotification,
-    NewBlockTemplateNotification, Notification, PruningPointUtxoSetOverrideNotification, RpcAcceptedTransactionIds,
-    SinkBlueScoreChangedNotification, UtxosChangedNotification, VirtualChainChangedNotification, VirtualDaaScoreChangedNotification,
+    MempoolChangedNotification, NewBlockTemplateNotification, Notification, PruningPointUtxoSetOverrideNotification,
+    RpcAcceptedTransactionIds, RpcMempoolChangeKind, SinkBlueScoreChangedNotification, UtxosChangedNotification,
+    VirtualChainChangedNotification, VirtualDaaScoreChangedNotification,
 };
 use kaspa_consensus_notify::notification as consensus_notify;
 use kaspa_index_core::notification as index_notify;
@@ -31,6 +32,7 @@ impl From<&consensus_notify::Notification> for Notification {
             consensus_notify::Notification::VirtualDaaScoreChanged(msg) => Notification::VirtualDaaScoreChanged(msg.into()),
             consensus_notify::Notification::PruningPointUtxoSetOverride(msg) => Notification::PruningPointUtxoSetOverride(msg.into()),
             consensus_notify::Notification::NewBlockTemplate(msg) => Notification::NewBlockTemplate(msg.into()),
+            consensus_notify::Notification::MempoolChanged(msg) => Notification::MempoolChanged(msg.into()),
         }
     }
 }
@@ -112,6 +114,25 @@ impl From<&consensus_notify::NewBlockTemplateNotification> for NewBlockTemplateN
     }
 }
 
+impl From<consensus_notify::MempoolChangeKind> for RpcMempoolChangeKind {
+    fn from(item: consensus_notify::MempoolChangeKind) -> Self {
+        match item {
+            consensus_notify::MempoolChangeKind::Added => RpcMempoolChangeKind::Added,
+            consensus_notify::MempoolChangeKind::Accepted => RpcMempoolChangeKind::Accepted,
+            consensus_notify::MempoolChangeKind::Replaced => RpcMempoolChangeKind::Replaced,
+            consensus_notify::MempoolChangeKind::Expired => RpcMempoolChangeKind::Expired,
+            consensus_notify::MempoolChangeKind::Evicted => RpcMempoolChangeKind::Evicted,
+            consensus_notify::MempoolChangeKind::Removed => RpcMempoolChangeKind::Removed,
+        }
+    }
+}
+
+impl From<&consensus_notify::MempoolChangedNotification> for MempoolChangedNotification {
+    fn from(item: &consensus_notify::MempoolChangedNotification) -> Self {
+        Self { transaction_id: item.transaction_id, kind: item.kind.into(), addresses: item.addresses.clone() }
+    }
+}
+
 // ----------------------------------------------------------------------------
 // index to rpc_core
 // ----------------------------------------------------------------------------
@@ -31,6 +31,7 @@ impl From<&TransactionOutput> for RpcTransactionOutput {
             script_public_key: item.script_public_key.clone(),
             // TODO: Implement a populating process inspired from kaspad\app\rpc\rpccontext\verbosedata.go
             verbose_data: None,
+            script_public_key_asm: None,
         }
     }
 }
@@ -63,6 +63,12 @@ pub enum RpcError {
     #[error("Method unavailable. Run the node with the --utxoindex argument.")]
     NoUtxoIndex,
 
+    #[error("Method unavailable. Run the node with the --txindex argument.")]
+    NoTxIndex,
+
+    #[error("Method unavailable. Run the node with the --addresshistoryindex argument.")]
+    NoAddressHistoryIndex,
+
     #[error("Method unavailable. No connection manager is currently available.")]
     NoConnectionManager,
 
@@ -75,6 +81,15 @@ pub enum RpcError {
     #[error("Method unavailable in safe mode. Run the node with --unsaferpc argument.")]
     UnavailableInSafeMode,
 
+    #[error("Authentication failed")]
+    AuthenticationFailed,
+
+    #[error("Method {0} is not allowed for role `{1}`")]
+    MethodNotAllowed(String, String),
+
+    #[error("Rate limit of method {0} exceeded, retry in {1} ms")]
+    RateLimitExceeded(String, u64),
+
     #[error("Cannot ban IP {0} because it has some permanent connection.")]
     IpHasPermanentConnection(IpAddress),
 
@@ -1,4 +1,5 @@
-use crate::{RpcTransactionOutpoint, RpcUtxoEntry};
+use crate::{RpcTransactionId, RpcTransactionOutpoint, RpcUtxoEntry};
+use borsh::{BorshDeserialize, BorshSerialize};
 use serde::{Deserialize, Serialize};
 use workflow_serializer::prelude::*;
 
@@ -58,3 +59,53 @@ impl Deserializer for RpcBalancesByAddressesEntry {
         Ok(Self { address, balance })
     }
 }
+
+/// Direction of an address history entry returned by the `GetAddressHistory` RPC.
+#[derive(Clone, Copy, Eq, PartialEq, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+#[borsh(use_discriminant = true)]
+pub enum RpcAddressHistoryDirection {
+    /// The outpoint was created, paying to the address
+    Received = 0,
+    /// The outpoint paying to the address was spent
+    Spent = 1,
+}
+
+/// Represents an entry of an address history returned by the `GetAddressHistory` RPC.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcAddressHistoryEntry {
+    /// The outpoint which was received or spent
+    pub outpoint: RpcTransactionOutpoint,
+
+    /// DAA score of the chain block accepting the transaction which created or spent the outpoint
+    pub daa_score: u64,
+    pub direction: RpcAddressHistoryDirection,
+    pub amount: u64,
+
+    /// The transaction spending the outpoint, set for `Spent` entries
+    pub spending_transaction_id: Option<RpcTransactionId>,
+}
+
+impl Serializer for RpcAddressHistoryEntry {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u8, &1, writer)?; // version
+        serialize!(RpcTransactionOutpoint, &self.outpoint, writer)?;
+        store!(u64, &self.daa_score, writer)?;
+        store!(RpcAddressHistoryDirection, &self.direction, writer)?;
+        store!(u64, &self.amount, writer)?;
+        store!(Option<RpcTransactionId>, &self.spending_transaction_id, writer)
+    }
+}
+
+impl Deserializer for RpcAddressHistoryEntry {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version: u8 = load!(u8, reader)?;
+        let outpoint = deserialize!(RpcTransactionOutpoint, reader)?;
+        let daa_score = load!(u64, reader)?;
+        let direction = load!(RpcAddressHistoryDirection, reader)?;
+        let amount = load!(u64, reader)?;
+        let spending_transaction_id = load!(Option<RpcTransactionId>, reader)?;
+        Ok(Self { outpoint, daa_score, direction, amount, spending_transaction_id })
+    }
+}
@@ -1,5 +1,6 @@
 use super::RpcAddress;
 use super::RpcTransaction;
+use borsh::{BorshDeserialize, BorshSerialize};
 use serde::{Deserialize, Serialize};
 use workflow_serializer::prelude::*;
 
@@ -63,6 +64,72 @@ impl Deserializer for RpcMempoolEntryByAddress {
     }
 }
 
+/// Kind of change reported by a `MempoolChanged` notification.
+#[derive(Clone, Copy, Eq, PartialEq, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+#[borsh(use_discriminant = true)]
+pub enum RpcMempoolChangeKind {
+    /// The transaction entered the mempool
+    Added = 0,
+    /// The transaction was included in a block
+    Accepted = 1,
+    /// The transaction was replaced by a transaction paying a higher fee rate (RBF)
+    Replaced = 2,
+    /// The low priority transaction was not included in a block in due time
+    Expired = 3,
+    /// The transaction was evicted to make room for a transaction paying a higher fee rate,
+    /// or because it no longer satisfies an updated mempool policy
+    Evicted = 4,
+    /// The transaction was removed for any other reason, like a double spend or being invalid
+    Removed = 5,
+}
+
+/// Mempool relay policy, as returned by the `GetMempoolPolicy` and `SetMempoolPolicy` RPCs.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcMempoolPolicy {
+    pub maximum_transaction_count: u64,
+    /// Estimated size limit of the mempool, in bytes
+    pub mempool_size_limit: u64,
+    pub maximum_orphan_transaction_mass: u64,
+    pub maximum_orphan_transaction_count: u64,
+    pub accept_non_standard: bool,
+    /// In sompi per 1kg of transaction mass
+    pub minimum_relay_transaction_fee: u64,
+}
+
+impl Serializer for RpcMempoolPolicy {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u8, &1, writer)?; // version
+        store!(u64, &self.maximum_transaction_count, writer)?;
+        store!(u64, &self.mempool_size_limit, writer)?;
+        store!(u64, &self.maximum_orphan_transaction_mass, writer)?;
+        store!(u64, &self.maximum_orphan_transaction_count, writer)?;
+        store!(bool, &self.accept_non_standard, writer)?;
+        store!(u64, &self.minimum_relay_transaction_fee, writer)
+    }
+}
+
+impl Deserializer for RpcMempoolPolicy {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version: u8 = load!(u8, reader)?;
+        let maximum_transaction_count = load!(u64, reader)?;
+        let mempool_size_limit = load!(u64, reader)?;
+        let maximum_orphan_transaction_mass = load!(u64, reader)?;
+        let maximum_orphan_transaction_count = load!(u64, reader)?;
+        let accept_non_standard = load!(bool, reader)?;
+        let minimum_relay_transaction_fee = load!(u64, reader)?;
+        Ok(Self {
+            maximum_transaction_count,
+            mempool_size_limit,
+            maximum_orphan_transaction_mass,
+            maximum_orphan_transaction_count,
+            accept_non_standard,
+            minimum_relay_transaction_fee,
+        })
+    }
+}
+
 cfg_if::cfg_if! {
     if #[cfg(feature = "wasm32-sdk")] {
         use wasm_bindgen::prelude::*;
@@ -1,8 +1,12 @@
-use crate::model::*;
+use crate::{api::limits::RpcRateLimitConfig, model::*};
 use borsh::{BorshDeserialize, BorshSerialize};
 use kaspa_consensus_core::api::stats::BlockCount;
 use kaspa_core::debug;
-use kaspa_notify::subscription::{context::SubscriptionContext, single::UtxosChangedSubscription, Command};
+use kaspa_notify::subscription::{
+    context::SubscriptionContext,
+    single::{MempoolChangedSubscription, UtxosChangedSubscription},
+    Command,
+};
 use kaspa_utils::hex::ToHex;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -603,16 +607,26 @@ impl GetConnectedPeerInfoResponse {
 
 impl Serializer for GetConnectedPeerInfoResponse {
     fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
-        store!(u16, &1, writer)?;
+        store!(u16, &3, writer)?;
         store!(Vec<RpcPeerInfo>, &self.peer_info, writer)?;
+        store!(Vec<bool>, &self.peer_info.iter().map(|peer| peer.is_encrypted).collect::<Vec<_>>(), writer)?;
+        store!(Vec<u32>, &self.peer_info.iter().map(|peer| peer.misbehavior_score).collect::<Vec<_>>(), writer)?;
         Ok(())
     }
 }
 
 impl Deserializer for GetConnectedPeerInfoResponse {
     fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
-        let _version = load!(u16, reader)?;
-        let peer_info = load!(Vec<RpcPeerInfo>, reader)?;
+        let payload_version = load!(u16, reader)?;
+        let mut peer_info = load!(Vec<RpcPeerInfo>, reader)?;
+        if payload_version > 1 {
+            let is_encrypted = load!(Vec<bool>, reader)?;
+            peer_info.iter_mut().zip(is_encrypted).for_each(|(peer, is_encrypted)| peer.is_encrypted = is_encrypted);
+        }
+        if payload_version > 2 {
+            let misbehavior_scores = load!(Vec<u32>, reader)?;
+            peer_info.iter_mut().zip(misbehavior_scores).for_each(|(peer, score)| peer.misbehavior_score = score);
+        }
         Ok(Self { peer_info })
     }
 }
@@ -1208,26 +1222,33 @@ impl Deserializer for ShutdownResponse {
     }
 }
 
+/// Requests up to `limit` headers following `start_hash` (exclusive), in ascending consensus order towards the
+/// sink or in descending order towards the pruning point. Pages hold whole mergesets, so they may exceed a `limit`
+/// lower than the size of a mergeset, and always end on a chain block. A following page is requested by passing the
+/// hash of the last returned header as `start_hash`.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct GetHeadersRequest {
     pub start_hash: RpcHash,
     pub limit: u64,
     pub is_ascending: bool,
+    #[serde(default)]
+    pub include_verbose_data: bool,
 }
 
 impl GetHeadersRequest {
-    pub fn new(start_hash: RpcHash, limit: u64, is_ascending: bool) -> Self {
-        Self { start_hash, limit, is_ascending }
+    pub fn new(start_hash: RpcHash, limit: u64, is_ascending: bool, include_verbose_data: bool) -> Self {
+        Self { start_hash, limit, is_ascending, include_verbose_data }
     }
 }
 
 impl Serializer for GetHeadersRequest {
     fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
-        store!(u16, &1, writer)?;
+        store!(u16, &2, writer)?;
         store!(RpcHash, &self.start_hash, writer)?;
         store!(u64, &self.limit, writer)?;
         store!(bool, &self.is_ascending, writer)?;
+        store!(bool, &self.include_verbose_data, writer)?;
 
         Ok(())
     }
@@ -1235,31 +1256,37 @@ impl Serializer for GetHeadersRequest {
 
 impl Deserializer for GetHeadersRequest {
     fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
-        let _version = load!(u16, reader)?;
+        let version = load!(u16, reader)?;
         let start_hash = load!(RpcHash, reader)?;
         let limit = load!(u64, reader)?;
         let is_ascending = load!(bool, reader)?;
+        let include_verbose_data = if version > 1 { load!(bool, reader)? } else { false };
 
-        Ok(Self { start_hash, limit, is_ascending })
+        Ok(Self { start_hash, limit, is_ascending, include_verbose_data })
     }
 }
 
+/// Headers in the requested order. When verbose data was requested, `verbose_data` holds the GHOSTDAG data of
+/// each header at the same index, otherwise it is empty.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct GetHeadersResponse {
     pub headers: Vec<RpcHeader>,
+    #[serde(default)]
+    pub verbose_data: Vec<RpcBlockVerboseData>,
 }
 
 impl GetHeadersResponse {
-    pub fn new(headers: Vec<RpcHeader>) -> Self {
-        Self { headers }
+    pub fn new(headers: Vec<RpcHeader>, verbose_data: Vec<RpcBlockVerboseData>) -> Self {
+        Self { headers, verbose_data }
     }
 }
 
 impl Serializer for GetHeadersResponse {
     fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
-        store!(u16, &1, writer)?;
+        store!(u16, &2, writer)?;
         store!(Vec<RpcHeader>, &self.headers, writer)?;
+        serialize!(Vec<RpcBlockVerboseData>, &self.verbose_data, writer)?;
 
         Ok(())
     }
@@ -1267,10 +1294,11 @@ impl Serializer for GetHeadersResponse {
 
 impl Deserializer for GetHeadersResponse {
     fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
-        let _version = load!(u16, reader)?;
+        let version = load!(u16, reader)?;
         let headers = load!(Vec<RpcHeader>, reader)?;
+        let verbose_data = if version > 1 { deserialize!(Vec<RpcBlockVerboseData>, reader)? } else { vec![] };
 
-        Ok(Self { headers })
+        Ok(Self { headers, verbose_data })
     }
 }
 
@@ -2106,11 +2134,15 @@ pub struct BandwidthMetrics {
     pub p2p_bytes_rx: u64,
     pub grpc_bytes_tx: u64,
     pub grpc_bytes_rx: u64,
+    /// Bytes of the p2p messages announcing transactions, including set reconciliation messages
+    pub p2p_tx_announcement_bytes_tx: u64,
+    /// Bytes the transaction announcements would have cost if flooded to all peers
+    pub p2p_tx_flood_bytes_tx: u64,
 }
 
 impl Serializer for BandwidthMetrics {
     fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
-        store!(u16, &1, writer)?;
+        store!(u16, &2, writer)?;
         store!(u64, &self.borsh_bytes_tx, writer)?;
         store!(u64, &self.borsh_bytes_rx, writer)?;
         store!(u64, &self.json_bytes_tx, writer)?;
@@ -2119,6 +2151,8 @@ impl Serializer for BandwidthMetrics {
         store!(u64, &self.p2p_bytes_rx, writer)?;
         store!(u64, &self.grpc_bytes_tx, writer)?;
         store!(u64, &self.grpc_bytes_rx, writer)?;
+        store!(u64, &self.p2p_tx_announcement_bytes_tx, writer)?;
+        store!(u64, &self.p2p_tx_flood_bytes_tx, writer)?;
 
         Ok(())
     }
@@ -2126,7 +2160,7 @@ impl Serializer for BandwidthMetrics {
 
 impl Deserializer for BandwidthMetrics {
     fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
-        let _version = load!(u16, reader)?;
+        let payload_version = load!(u16, reader)?;
         let borsh_bytes_tx = load!(u64, reader)?;
         let borsh_bytes_rx = load!(u64, reader)?;
         let json_bytes_tx = load!(u64, reader)?;
@@ -2135,6 +2169,8 @@ impl Deserializer for BandwidthMetrics {
         let p2p_bytes_rx = load!(u64, reader)?;
         let grpc_bytes_tx = load!(u64, reader)?;
         let grpc_bytes_rx = load!(u64, reader)?;
+        let (p2p_tx_announcement_bytes_tx, p2p_tx_flood_bytes_tx) =
+            if payload_version > 1 { (load!(u64, reader)?, load!(u64, reader)?) } else { (0, 0) };
 
         Ok(Self {
             borsh_bytes_tx,
@@ -2145,6 +2181,8 @@ impl Deserializer for BandwidthMetrics {
             p2p_bytes_rx,
             grpc_bytes_tx,
             grpc_bytes_rx,
+            p2p_tx_announcement_bytes_tx,
+            p2p_tx_flood_bytes_tx,
         })
     }
 }
@@ -2393,11 +2431,13 @@ pub struct GetServerInfoResponse {
     pub has_utxo_index: bool,
     pub is_synced: bool,
     pub virtual_daa_score: u64,
+    /// Rate limits of the RPC servers, if any
+    pub rate_limits: Option<RpcRateLimitConfig>,
 }
 
 impl Serializer for GetServerInfoResponse {
     fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
-        store!(u16, &1, writer)?;
+        store!(u16, &2, writer)?;
 
         store!(u16, &self.rpc_api_version, writer)?;
         store!(u16, &self.rpc_api_revision, writer)?;
@@ -2407,6 +2447,7 @@ impl Serializer for GetServerInfoResponse {
         store!(bool, &self.has_utxo_index, writer)?;
         store!(bool, &self.is_synced, writer)?;
         store!(u64, &self.virtual_daa_score, writer)?;
+        serialize!(Option<RpcRateLimitConfig>, &self.rate_limits, writer)?;
 
         Ok(())
     }
@@ -2414,7 +2455,7 @@ impl Serializer for GetServerInfoResponse {
 
 impl Deserializer for GetServerInfoResponse {
     fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
-        let _version = load!(u16, reader)?;
+        let payload_version = load!(u16, reader)?;
 
         let rpc_api_version = load!(u16, reader)?;
         let rpc_api_revision = load!(u16, reader)?;
@@ -2424,8 +2465,18 @@ impl Deserializer for GetServerInfoResponse {
         let has_utxo_index = load!(bool, reader)?;
         let is_synced = load!(bool, reader)?;
         let virtual_daa_score = load!(u64, reader)?;
+        let rate_limits = if payload_version > 1 { deserialize!(Option<RpcRateLimitConfig>, reader)? } else { None };
 
-        Ok(Self { rpc_api_version, rpc_api_revision, server_version, network_id, has_utxo_index, is_synced, virtual_daa_score })
+        Ok(Self {
+            rpc_api_version,
+            rpc_api_revision,
+            server_version,
+            network_id,
+            has_utxo_index,
+            is_synced,
+            virtual_daa_score,
+            rate_limits,
+        })
     }
 }
 
@@ -2666,6 +2717,454 @@ impl Deserializer for GetCurrentBlockColorResponse {
     }
 }
 
+/// GetTransactionRequest requests a transaction by id from the transaction index.
+///
+/// Requires the node to run with the `--txindex` argument.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetTransactionRequest {
+    pub transaction_id: RpcTransactionId,
+    pub include_verbose_data: bool,
+}
+
+impl GetTransactionRequest {
+    pub fn new(transaction_id: RpcTransactionId, include_verbose_data: bool) -> Self {
+        Self { transaction_id, include_verbose_data }
+    }
+}
+
+impl Serializer for GetTransactionRequest {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(RpcTransactionId, &self.transaction_id, writer)?;
+        store!(bool, &self.include_verbose_data, writer)?;
+
+        Ok(())
+    }
+}
+
+impl Deserializer for GetTransactionRequest {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let transaction_id = load!(RpcTransactionId, reader)?;
+        let include_verbose_data = load!(bool, reader)?;
+
+        Ok(Self { transaction_id, include_verbose_data })
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetTransactionResponse {
+    pub transaction: RpcTransaction,
+    pub containing_block_hash: RpcHash,
+    pub accepting_block_hash: RpcHash,
+}
+
+impl GetTransactionResponse {
+    pub fn new(transaction: RpcTransaction, containing_block_hash: RpcHash, accepting_block_hash: RpcHash) -> Self {
+        Self { transaction, containing_block_hash, accepting_block_hash }
+    }
+}
+
+impl Serializer for GetTransactionResponse {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        serialize!(RpcTransaction, &self.transaction, writer)?;
+        store!(RpcHash, &self.containing_block_hash, writer)?;
+        store!(RpcHash, &self.accepting_block_hash, writer)?;
+
+        Ok(())
+    }
+}
+
+impl Deserializer for GetTransactionResponse {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let transaction = deserialize!(RpcTransaction, reader)?;
+        let containing_block_hash = load!(RpcHash, reader)?;
+        let accepting_block_hash = load!(RpcHash, reader)?;
+
+        Ok(Self { transaction, containing_block_hash, accepting_block_hash })
+    }
+}
+
+/// GetTransactionAcceptanceRequest requests the containing and accepting blocks
+/// of a set of transactions from the transaction index.
+///
+/// Transactions unknown to the index are omitted from the response.
+/// Requires the node to run with the `--txindex` argument.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetTransactionAcceptanceRequest {
+    pub transaction_ids: Vec<RpcTransactionId>,
+}
+
+impl GetTransactionAcceptanceRequest {
+    pub fn new(transaction_ids: Vec<RpcTransactionId>) -> Self {
+        Self { transaction_ids }
+    }
+}
+
+impl Serializer for GetTransactionAcceptanceRequest {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(Vec<RpcTransactionId>, &self.transaction_ids, writer)?;
+
+        Ok(())
+    }
+}
+
+impl Deserializer for GetTransactionAcceptanceRequest {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let transaction_ids = load!(Vec<RpcTransactionId>, reader)?;
+
+        Ok(Self { transaction_ids })
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetTransactionAcceptanceResponse {
+    pub acceptances: Vec<RpcTransactionAcceptance>,
+}
+
+impl GetTransactionAcceptanceResponse {
+    pub fn new(acceptances: Vec<RpcTransactionAcceptance>) -> Self {
+        Self { acceptances }
+    }
+}
+
+impl Serializer for GetTransactionAcceptanceResponse {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        serialize!(Vec<RpcTransactionAcceptance>, &self.acceptances, writer)?;
+
+        Ok(())
+    }
+}
+
+impl Deserializer for GetTransactionAcceptanceResponse {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let acceptances = deserialize!(Vec<RpcTransactionAcceptance>, reader)?;
+
+        Ok(Self { acceptances })
+    }
+}
+
+/// GetAddressHistoryRequest requests a page of the history of an address from the
+/// address history index, ordered by ascending DAA score.
+///
+/// A page never splits the entries of a single DAA score, so it may hold more than `limit`
+/// entries. Requires the node to run with the `--addresshistoryindex` argument.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetAddressHistoryRequest {
+    pub address: RpcAddress,
+    /// First DAA score (inclusive) of the requested page
+    pub start_daa_score: u64,
+    /// Maximum number of entries in the page, capped by the node
+    pub limit: u32,
+}
+
+impl GetAddressHistoryRequest {
+    pub fn new(address: RpcAddress, start_daa_score: u64, limit: u32) -> Self {
+        Self { address, start_daa_score, limit }
+    }
+}
+
+impl Serializer for GetAddressHistoryRequest {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(RpcAddress, &self.address, writer)?;
+        store!(u64, &self.start_daa_score, writer)?;
+        store!(u32, &self.limit, writer)?;
+
+        Ok(())
+    }
+}
+
+impl Deserializer for GetAddressHistoryRequest {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let address = load!(RpcAddress, reader)?;
+        let start_daa_score = load!(u64, reader)?;
+        let limit = load!(u32, reader)?;
+
+        Ok(Self { address, start_daa_score, limit })
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetAddressHistoryResponse {
+    pub entries: Vec<RpcAddressHistoryEntry>,
+    /// `start_daa_score` of the next page, if any
+    pub next_daa_score: Option<u64>,
+    /// DAA score from which the index holds a complete history.
+    /// Entries prior to it were either pruned or predate the index.
+    pub history_start_daa_score: u64,
+}
+
+impl GetAddressHistoryResponse {
+    pub fn new(entries: Vec<RpcAddressHistoryEntry>, next_daa_score: Option<u64>, history_start_daa_score: u64) -> Self {
+        Self { entries, next_daa_score, history_start_daa_score }
+    }
+}
+
+impl Serializer for GetAddressHistoryResponse {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        serialize!(Vec<RpcAddressHistoryEntry>, &self.entries, writer)?;
+        store!(Option<u64>, &self.next_daa_score, writer)?;
+        store!(u64, &self.history_start_daa_score, writer)?;
+
+        Ok(())
+    }
+}
+
+impl Deserializer for GetAddressHistoryResponse {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let entries = deserialize!(Vec<RpcAddressHistoryEntry>, reader)?;
+        let next_daa_score = load!(Option<u64>, reader)?;
+        let history_start_daa_score = load!(u64, reader)?;
+
+        Ok(Self { entries, next_daa_score, history_start_daa_score })
+    }
+}
+
+/// GetMempoolPolicyRequest requests the current mempool relay policy.
+///
+/// This call is only available when the node runs with `--unsaferpc`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetMempoolPolicyRequest {}
+
+impl Serializer for GetMempoolPolicyRequest {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for GetMempoolPolicyRequest {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        Ok(Self {})
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetMempoolPolicyResponse {
+    pub policy: RpcMempoolPolicy,
+}
+
+impl GetMempoolPolicyResponse {
+    pub fn new(policy: RpcMempoolPolicy) -> Self {
+        Self { policy }
+    }
+}
+
+impl Serializer for GetMempoolPolicyResponse {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        serialize!(RpcMempoolPolicy, &self.policy, writer)?;
+
+        Ok(())
+    }
+}
+
+impl Deserializer for GetMempoolPolicyResponse {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let policy = deserialize!(RpcMempoolPolicy, reader)?;
+
+        Ok(Self { policy })
+    }
+}
+
+/// SetMempoolPolicyRequest updates the mempool relay policy. Unset fields are left unchanged.
+///
+/// The mempool is updated atomically, evicting the transactions which no longer satisfy the
+/// new policy. The change is not persisted across node restarts.
+///
+/// This call is only available when the node runs with `--unsaferpc`.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetMempoolPolicyRequest {
+    pub maximum_transaction_count: Option<u64>,
+    pub mempool_size_limit: Option<u64>,
+    pub maximum_orphan_transaction_mass: Option<u64>,
+    pub maximum_orphan_transaction_count: Option<u64>,
+    pub accept_non_standard: Option<bool>,
+    pub minimum_relay_transaction_fee: Option<u64>,
+}
+
+impl Serializer for SetMempoolPolicyRequest {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(Option<u64>, &self.maximum_transaction_count, writer)?;
+        store!(Option<u64>, &self.mempool_size_limit, writer)?;
+        store!(Option<u64>, &self.maximum_orphan_transaction_mass, writer)?;
+        store!(Option<u64>, &self.maximum_orphan_transaction_count, writer)?;
+        store!(Option<bool>, &self.accept_non_standard, writer)?;
+        store!(Option<u64>, &self.minimum_relay_transaction_fee, writer)?;
+
+        Ok(())
+    }
+}
+
+impl Deserializer for SetMempoolPolicyRequest {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let maximum_transaction_count = load!(Option<u64>, reader)?;
+        let mempool_size_limit = load!(Option<u64>, reader)?;
+        let maximum_orphan_transaction_mass = load!(Option<u64>, reader)?;
+        let maximum_orphan_transaction_count = load!(Option<u64>, reader)?;
+        let accept_non_standard = load!(Option<bool>, reader)?;
+        let minimum_relay_transaction_fee = load!(Option<u64>, reader)?;
+
+        Ok(Self {
+            maximum_transaction_count,
+            mempool_size_limit,
+            maximum_orphan_transaction_mass,
+            maximum_orphan_transaction_count,
+            accept_non_standard,
+            minimum_relay_transaction_fee,
+        })
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetMempoolPolicyResponse {
+    /// The policy in effect after the update
+    pub policy: RpcMempoolPolicy,
+    /// Number of transactions evicted from the mempool for not satisfying the new policy
+    pub evicted_transaction_count: u64,
+}
+
+impl SetMempoolPolicyResponse {
+    pub fn new(policy: RpcMempoolPolicy, evicted_transaction_count: u64) -> Self {
+        Self { policy, evicted_transaction_count }
+    }
+}
+
+impl Serializer for SetMempoolPolicyResponse {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        serialize!(RpcMempoolPolicy, &self.policy, writer)?;
+        store!(u64, &self.evicted_transaction_count, writer)?;
+
+        Ok(())
+    }
+}
+
+impl Deserializer for SetMempoolPolicyResponse {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let policy = deserialize!(RpcMempoolPolicy, reader)?;
+        let evicted_transaction_count = load!(u64, reader)?;
+
+        Ok(Self { policy, evicted_transaction_count })
+    }
+}
+
+/// BackupRequest creates a consistent checkpoint of the node databases (the active consensus, the
+/// consensus metadata and the UTXO index if enabled) in `target_dir`, a path on the node host which
+/// must not exist or be empty.
+///
+/// The checkpoint is laid out as an application directory, so that a node can be started from it
+/// with `--appdir=<target_dir>`. Database files are hard-linked rather than copied when `target_dir`
+/// is on the filesystem of the node data directory.
+///
+/// The UTXO index is checkpointed after the consensus while blocks keep being processed, so a node
+/// started from the backup may resync the UTXO index on startup.
+///
+/// This call is only available when the node runs with `--unsaferpc`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupRequest {
+    pub target_dir: String,
+}
+
+impl BackupRequest {
+    pub fn new(target_dir: String) -> Self {
+        Self { target_dir }
+    }
+}
+
+impl Serializer for BackupRequest {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(String, &self.target_dir, writer)?;
+
+        Ok(())
+    }
+}
+
+impl Deserializer for BackupRequest {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let target_dir = load!(String, reader)?;
+
+        Ok(Self { target_dir })
+    }
+}
+
+/// A database checkpoint created by the `Backup` call
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcDatabaseBackup {
+    /// The database name, e.g. `consensus`, `meta` or `utxoindex`
+    pub name: String,
+    /// The checkpoint directory on the node host
+    pub path: String,
+    /// The time taken by the backup stage which created the checkpoint, in milliseconds
+    pub duration_millis: u64,
+}
+
+impl RpcDatabaseBackup {
+    pub fn new(name: String, path: String, duration_millis: u64) -> Self {
+        Self { name, path, duration_millis }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupResponse {
+    /// The checkpoints created, one per database in the order they were created, along with the duration of each stage
+    pub databases: Vec<RpcDatabaseBackup>,
+}
+
+impl BackupResponse {
+    pub fn new(databases: Vec<RpcDatabaseBackup>) -> Self {
+        Self { databases }
+    }
+}
+
+impl Serializer for BackupResponse {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(Vec<RpcDatabaseBackup>, &self.databases, writer)?;
+
+        Ok(())
+    }
+}
+
+impl Deserializer for BackupResponse {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let databases = load!(Vec<RpcDatabaseBackup>, reader)?;
+
+        Ok(Self { databases })
+    }
+}
+
 // ----------------------------------------------------------------------------
 // Subscriptions & notifications
 // ----------------------------------------------------------------------------
@@ -3398,6 +3897,108 @@ impl Deserializer for NewBlockTemplateNotification {
     }
 }
 
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~
+// MempoolChangedNotification
+
+/// NotifyMempoolChangedRequest registers this connection for mempoolChanged notifications
+/// for the given addresses. Depending on the provided `command`, notifications will
+/// start or stop for the provided `addresses`.
+///
+/// If `addresses` is empty, the notifications will start or stop for all addresses.
+///
+/// See: MempoolChangedNotification
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotifyMempoolChangedRequest {
+    pub addresses: Vec<RpcAddress>,
+    pub command: Command,
+}
+
+impl NotifyMempoolChangedRequest {
+    pub fn new(addresses: Vec<RpcAddress>, command: Command) -> Self {
+        Self { addresses, command }
+    }
+}
+
+impl Serializer for NotifyMempoolChangedRequest {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(Vec<RpcAddress>, &self.addresses, writer)?;
+        store!(Command, &self.command, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for NotifyMempoolChangedRequest {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let addresses = load!(Vec<RpcAddress>, reader)?;
+        let command = load!(Command, reader)?;
+        Ok(Self { addresses, command })
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotifyMempoolChangedResponse {}
+
+impl Serializer for NotifyMempoolChangedResponse {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for NotifyMempoolChangedResponse {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        Ok(Self {})
+    }
+}
+
+/// MempoolChangedNotification is sent whenever a transaction enters or leaves the mempool.
+///
+/// Orphan transactions are only reported once they get unorphaned.
+///
+/// See: NotifyMempoolChangedRequest
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MempoolChangedNotification {
+    pub transaction_id: RpcTransactionId,
+    pub kind: RpcMempoolChangeKind,
+    /// Addresses of the transaction inputs and outputs
+    pub addresses: Arc<Vec<RpcAddress>>,
+}
+
+impl MempoolChangedNotification {
+    pub(crate) fn apply_mempool_changed_subscription(&self, subscription: &MempoolChangedSubscription) -> Option<Self> {
+        match subscription.to_all() || self.addresses.iter().any(|x| subscription.contains_address(x)) {
+            true => Some(self.clone()),
+            false => None,
+        }
+    }
+}
+
+impl Serializer for MempoolChangedNotification {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(RpcTransactionId, &self.transaction_id, writer)?;
+        store!(RpcMempoolChangeKind, &self.kind, writer)?;
+        store!(Vec<RpcAddress>, &self.addresses, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for MempoolChangedNotification {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let transaction_id = load!(RpcTransactionId, reader)?;
+        let kind = load!(RpcMempoolChangeKind, reader)?;
+        let addresses = load!(Vec<RpcAddress>, reader)?;
+        Ok(Self { transaction_id, kind, addresses: addresses.into() })
+    }
+}
+
 ///
 ///  wRPC response for RpcApiOps::Subscribe request
 ///
@@ -3448,3 +4049,66 @@ impl Deserializer for UnsubscribeResponse {
         Ok(Self {})
     }
 }
+
+///
+///  wRPC request for RpcApiOps::Authenticate, granting the connection the role of the given credentials
+///
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuthenticateRequest {
+    /// Either `Bearer <token>` or `Basic <base64(username:password)>`
+    pub authorization: String,
+}
+
+impl AuthenticateRequest {
+    pub fn new(authorization: String) -> Self {
+        Self { authorization }
+    }
+}
+
+impl Serializer for AuthenticateRequest {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(String, &self.authorization, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for AuthenticateRequest {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let authorization = load!(String, reader)?;
+        Ok(Self { authorization })
+    }
+}
+
+///
+///  wRPC response for RpcApiOps::Authenticate request
+///
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuthenticateResponse {
+    pub role: String,
+}
+
+impl AuthenticateResponse {
+    pub fn new(role: String) -> Self {
+        Self { role }
+    }
+}
+
+impl Serializer for AuthenticateResponse {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(String, &self.role, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for AuthenticateResponse {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let role = load!(String, reader)?;
+        Ok(Self { role })
+    }
+}
@@ -20,4 +20,14 @@ pub struct RpcPeerInfo {
     pub advertised_protocol_version: u32,
     pub time_connected: u64, // NOTE: i64 in gRPC protowire
     pub is_ibd_peer: bool,
+    /// Whether the connection runs over an encrypted transport. Serialized separately by
+    /// `GetConnectedPeerInfoResponse` for compatibility with the original borsh layout.
+    #[borsh(skip)]
+    #[serde(default)]
+    pub is_encrypted: bool,
+    /// The misbehavior score of the peer IP, the peer gets banned when it reaches 100. Serialized separately by
+    /// `GetConnectedPeerInfoResponse` for compatibility with the original borsh layout.
+    #[borsh(skip)]
+    #[serde(default)]
+    pub misbehavior_score: u32,
 }
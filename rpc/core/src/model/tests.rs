@@ -1,7 +1,7 @@
 #[cfg(test)]
 mod mockery {
 
-    use crate::{model::*, RpcScriptClass};
+    use crate::{api::limits::*, model::*, RpcScriptClass};
     use kaspa_addresses::{Prefix, Version};
     use kaspa_consensus_core::api::BlockCount;
     use kaspa_consensus_core::network::NetworkType;
@@ -229,7 +229,12 @@ mod mockery {
 
     impl Mock for RpcTransactionOutput {
         fn mock() -> Self {
-            RpcTransactionOutput { value: mock(), script_public_key: mock(), verbose_data: mock() }
+            RpcTransactionOutput {
+                value: mock(),
+                script_public_key: mock(),
+                verbose_data: mock(),
+                script_public_key_asm: Some("OP_TRUE".to_string()),
+            }
         }
     }
 
@@ -303,6 +308,8 @@ mod mockery {
                 advertised_protocol_version: mock(),
                 time_connected: mock(),
                 is_ibd_peer: mock(),
+                is_encrypted: mock(),
+                misbehavior_score: mock(),
             }
         }
     }
@@ -386,6 +393,8 @@ mod mockery {
                 p2p_bytes_rx: mock(),
                 grpc_bytes_tx: mock(),
                 grpc_bytes_rx: mock(),
+                p2p_tx_announcement_bytes_tx: mock(),
+                p2p_tx_flood_bytes_tx: mock(),
             }
         }
     }
@@ -757,7 +766,7 @@ mod mockery {
 
     impl Mock for GetHeadersRequest {
         fn mock() -> Self {
-            GetHeadersRequest { start_hash: mock(), limit: mock(), is_ascending: mock() }
+            GetHeadersRequest { start_hash: mock(), limit: mock(), is_ascending: mock(), include_verbose_data: mock() }
         }
     }
 
@@ -765,7 +774,7 @@ mod mockery {
 
     impl Mock for GetHeadersResponse {
         fn mock() -> Self {
-            GetHeadersResponse { headers: mock() }
+            GetHeadersResponse { headers: mock(), verbose_data: mock() }
         }
     }
 
@@ -1026,6 +1035,10 @@ mod mockery {
                 has_utxo_index: true,
                 is_synced: false,
                 virtual_daa_score: mock(),
+                rate_limits: Some(RpcRateLimitConfig {
+                    connection: RpcRateLimits { heavy: Some(RpcRateLimit { rate: 0.5, burst: 4 }), ..Default::default() },
+                    ip: RpcRateLimits { light: Some(RpcRateLimit { rate: 100.0, burst: 200 }), ..Default::default() },
+                }),
             }
         }
     }
@@ -1064,6 +1077,150 @@ mod mockery {
 
     test!(GetDaaScoreTimestampEstimateResponse);
 
+    impl Mock for GetTransactionRequest {
+        fn mock() -> Self {
+            GetTransactionRequest { transaction_id: mock(), include_verbose_data: true }
+        }
+    }
+
+    test!(GetTransactionRequest);
+
+    impl Mock for GetTransactionResponse {
+        fn mock() -> Self {
+            GetTransactionResponse { transaction: mock(), containing_block_hash: mock(), accepting_block_hash: mock() }
+        }
+    }
+
+    test!(GetTransactionResponse);
+
+    impl Mock for GetTransactionAcceptanceRequest {
+        fn mock() -> Self {
+            GetTransactionAcceptanceRequest { transaction_ids: mock() }
+        }
+    }
+
+    test!(GetTransactionAcceptanceRequest);
+
+    impl Mock for RpcTransactionAcceptance {
+        fn mock() -> Self {
+            RpcTransactionAcceptance { transaction_id: mock(), containing_block_hash: mock(), accepting_block_hash: mock() }
+        }
+    }
+
+    impl Mock for GetTransactionAcceptanceResponse {
+        fn mock() -> Self {
+            GetTransactionAcceptanceResponse { acceptances: mock() }
+        }
+    }
+
+    test!(GetTransactionAcceptanceResponse);
+
+    impl Mock for GetAddressHistoryRequest {
+        fn mock() -> Self {
+            GetAddressHistoryRequest { address: mock(), start_daa_score: mock(), limit: 100 }
+        }
+    }
+
+    test!(GetAddressHistoryRequest);
+
+    impl Mock for RpcAddressHistoryEntry {
+        fn mock() -> Self {
+            RpcAddressHistoryEntry {
+                outpoint: mock(),
+                daa_score: mock(),
+                direction: RpcAddressHistoryDirection::Spent,
+                amount: mock(),
+                spending_transaction_id: mock(),
+            }
+        }
+    }
+
+    impl Mock for GetAddressHistoryResponse {
+        fn mock() -> Self {
+            GetAddressHistoryResponse { entries: mock(), next_daa_score: mock(), history_start_daa_score: mock() }
+        }
+    }
+
+    test!(GetAddressHistoryResponse);
+
+    impl Mock for RpcMempoolPolicy {
+        fn mock() -> Self {
+            RpcMempoolPolicy {
+                maximum_transaction_count: mock(),
+                mempool_size_limit: mock(),
+                maximum_orphan_transaction_mass: mock(),
+                maximum_orphan_transaction_count: mock(),
+                accept_non_standard: mock(),
+                minimum_relay_transaction_fee: mock(),
+            }
+        }
+    }
+
+    impl Mock for GetMempoolPolicyRequest {
+        fn mock() -> Self {
+            GetMempoolPolicyRequest {}
+        }
+    }
+
+    test!(GetMempoolPolicyRequest);
+
+    impl Mock for GetMempoolPolicyResponse {
+        fn mock() -> Self {
+            GetMempoolPolicyResponse { policy: mock() }
+        }
+    }
+
+    test!(GetMempoolPolicyResponse);
+
+    impl Mock for SetMempoolPolicyRequest {
+        fn mock() -> Self {
+            SetMempoolPolicyRequest {
+                maximum_transaction_count: mock(),
+                mempool_size_limit: None,
+                maximum_orphan_transaction_mass: mock(),
+                maximum_orphan_transaction_count: None,
+                accept_non_standard: mock(),
+                minimum_relay_transaction_fee: mock(),
+            }
+        }
+    }
+
+    test!(SetMempoolPolicyRequest);
+
+    impl Mock for SetMempoolPolicyResponse {
+        fn mock() -> Self {
+            SetMempoolPolicyResponse { policy: mock(), evicted_transaction_count: mock() }
+        }
+    }
+
+    test!(SetMempoolPolicyResponse);
+
+    impl Mock for BackupRequest {
+        fn mock() -> Self {
+            BackupRequest { target_dir: "/var/backups/kaspad".to_string() }
+        }
+    }
+
+    test!(BackupRequest);
+
+    impl Mock for RpcDatabaseBackup {
+        fn mock() -> Self {
+            RpcDatabaseBackup {
+                name: "consensus".to_string(),
+                path: "/var/backups/kaspad/kaspa-mainnet/datadir/meta".to_string(),
+                duration_millis: mock(),
+            }
+        }
+    }
+
+    impl Mock for BackupResponse {
+        fn mock() -> Self {
+            BackupResponse { databases: mock() }
+        }
+    }
+
+    test!(BackupResponse);
+
     impl Mock for NotifyBlockAddedRequest {
         fn mock() -> Self {
             NotifyBlockAddedRequest { command: Command::Start }
@@ -1284,6 +1441,30 @@ mod mockery {
 
     test!(NewBlockTemplateNotification);
 
+    impl Mock for NotifyMempoolChangedRequest {
+        fn mock() -> Self {
+            NotifyMempoolChangedRequest { addresses: mock(), command: Command::Start }
+        }
+    }
+
+    test!(NotifyMempoolChangedRequest);
+
+    impl Mock for NotifyMempoolChangedResponse {
+        fn mock() -> Self {
+            NotifyMempoolChangedResponse {}
+        }
+    }
+
+    test!(NotifyMempoolChangedResponse);
+
+    impl Mock for MempoolChangedNotification {
+        fn mock() -> Self {
+            MempoolChangedNotification { transaction_id: mock(), kind: RpcMempoolChangeKind::Replaced, addresses: mock() }
+        }
+    }
+
+    test!(MempoolChangedNotification);
+
     impl Mock for SubscribeResponse {
         fn mock() -> Self {
             SubscribeResponse::new(mock())
@@ -1300,6 +1481,22 @@ mod mockery {
 
     test!(UnsubscribeResponse);
 
+    impl Mock for AuthenticateRequest {
+        fn mock() -> Self {
+            AuthenticateRequest::new("Bearer 0123456789abcdef".to_string())
+        }
+    }
+
+    test!(AuthenticateRequest);
+
+    impl Mock for AuthenticateResponse {
+        fn mock() -> Self {
+            AuthenticateResponse::new("read-only".to_string())
+        }
+    }
+
+    test!(AuthenticateResponse);
+
     struct Misalign;
 
     impl Mock for Misalign {
@@ -224,6 +224,9 @@ pub struct RpcTransactionOutput {
     pub value: u64,
     pub script_public_key: RpcScriptPublicKey,
     pub verbose_data: Option<RpcTransactionOutputVerboseData>,
+    /// The disassembled script public key of a non-standard output, which has no address.
+    /// Set instead of verbose data for outputs without an address.
+    pub script_public_key_asm: Option<String>,
 }
 
 impl RpcTransactionOutput {
@@ -234,16 +237,17 @@ impl RpcTransactionOutput {
 
 impl From<TransactionOutput> for RpcTransactionOutput {
     fn from(output: TransactionOutput) -> Self {
-        Self { value: output.value, script_public_key: output.script_public_key, verbose_data: None }
+        Self { value: output.value, script_public_key: output.script_public_key, verbose_data: None, script_public_key_asm: None }
     }
 }
 
 impl Serializer for RpcTransactionOutput {
     fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
-        store!(u8, &1, writer)?;
+        store!(u8, &2, writer)?;
         store!(u64, &self.value, writer)?;
         store!(RpcScriptPublicKey, &self.script_public_key, writer)?;
         serialize!(Option<RpcTransactionOutputVerboseData>, &self.verbose_data, writer)?;
+        store!(Option<String>, &self.script_public_key_asm, writer)?;
 
         Ok(())
     }
@@ -251,12 +255,13 @@ impl Serializer for RpcTransactionOutput {
 
 impl Deserializer for RpcTransactionOutput {
     fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
-        let _version = load!(u8, reader)?;
+        let version = load!(u8, reader)?;
         let value = load!(u64, reader)?;
         let script_public_key = load!(RpcScriptPublicKey, reader)?;
         let verbose_data = deserialize!(Option<RpcTransactionOutputVerboseData>, reader)?;
+        let script_public_key_asm = if version > 1 { load!(Option<String>, reader)? } else { None };
 
-        Ok(Self { value, script_public_key, verbose_data })
+        Ok(Self { value, script_public_key, verbose_data, script_public_key_asm })
     }
 }
 
@@ -398,3 +403,36 @@ pub struct RpcAcceptedTransactionIds {
     pub accepting_block_hash: RpcHash,
     pub accepted_transaction_ids: Vec<RpcTransactionId>,
 }
+
+/// Represents the acceptance status of a transaction as recorded by the transaction index
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcTransactionAcceptance {
+    pub transaction_id: RpcTransactionId,
+    /// Hash of the block the transaction was included in
+    pub containing_block_hash: RpcHash,
+    /// Hash of the chain block whose mergeset accepted the transaction
+    pub accepting_block_hash: RpcHash,
+}
+
+impl Serializer for RpcTransactionAcceptance {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u8, &1, writer)?;
+        store!(RpcTransactionId, &self.transaction_id, writer)?;
+        store!(RpcHash, &self.containing_block_hash, writer)?;
+        store!(RpcHash, &self.accepting_block_hash, writer)?;
+
+        Ok(())
+    }
+}
+
+impl Deserializer for RpcTransactionAcceptance {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u8, reader)?;
+        let transaction_id = load!(RpcTransactionId, reader)?;
+        let containing_block_hash = load!(RpcHash, reader)?;
+        let accepting_block_hash = load!(RpcHash, reader)?;
+
+        Ok(Self { transaction_id, containing_block_hash, accepting_block_hash })
+    }
+}
@@ -45,7 +45,12 @@ cfg_if::cfg_if! {
         impl From<TransactionOutput> for RpcTransactionOutput {
             fn from(output: TransactionOutput) -> Self {
                 let inner = output.inner();
-                RpcTransactionOutput { value: inner.value, script_public_key: inner.script_public_key.clone(), verbose_data: None }
+                RpcTransactionOutput {
+                    value: inner.value,
+                    script_public_key: inner.script_public_key.clone(),
+                    verbose_data: None,
+                    script_public_key_asm: None,
+                }
             }
         }
 
@@ -3,6 +3,7 @@
 //!
 
 #![allow(non_snake_case)]
+use crate::api::auth::RpcCredentials;
 use crate::error::RpcError as Error;
 use crate::error::RpcResult as Result;
 use crate::model::*;
@@ -474,6 +475,24 @@ declare! {
         hasUtxoIndex : boolean;
         isSynced : boolean;
         virtualDaaScore : bigint;
+        /**
+         * Rate limits of the RPC servers, if any. Each cost class
+         * (`light`, `standard`, `heavy`) grants a budget of `burst`
+         * requests, refilled at `rate` requests per second.
+         */
+        rateLimits? : {
+            connection : IRpcRateLimits;
+            ip : IRpcRateLimits;
+        };
+    }
+
+    /**
+     * @category Node RPC
+     */
+    export interface IRpcRateLimits {
+        light? : { rate : number; burst : number };
+        standard? : { rate : number; burst : number };
+        heavy? : { rate : number; burst : number };
     }
     "#,
 }
@@ -514,6 +533,45 @@ try_from! ( args: GetSyncStatusResponse, IGetSyncStatusResponse, {
     Ok(to_value(&args)?.into())
 });
 
+declare! {
+    IGetMempoolPolicyRequest,
+    r#"
+    /**
+     * Requires the node to run with `--unsaferpc`.
+     *
+     * @category Node RPC
+     */
+    export interface IGetMempoolPolicyRequest { }
+    "#,
+}
+
+try_from! ( args: IGetMempoolPolicyRequest, GetMempoolPolicyRequest, {
+    Ok(from_value(args.into())?)
+});
+
+declare! {
+    IGetMempoolPolicyResponse,
+    r#"
+    /**
+     * @category Node RPC
+     */
+    export interface IGetMempoolPolicyResponse {
+        policy : {
+            maximumTransactionCount : bigint;
+            mempoolSizeLimit : bigint;
+            maximumOrphanTransactionMass : bigint;
+            maximumOrphanTransactionCount : bigint;
+            acceptNonStandard : boolean;
+            minimumRelayTransactionFee : bigint;
+        };
+    }
+    "#,
+}
+
+try_from! ( args: GetMempoolPolicyResponse, IGetMempoolPolicyResponse, {
+    Ok(to_value(&args)?.into())
+});
+
 /*
     Interfaces for methods with arguments
 */
@@ -898,6 +956,235 @@ try_from! ( args: GetCurrentBlockColorResponse, IGetCurrentBlockColorResponse, {
 
 // ---
 
+declare! {
+    IGetTransactionRequest,
+    r#"
+    /**
+     * Requests an accepted transaction by id from the transaction index.
+     * Requires the node to run with `--txindex`.
+     *
+     * @category Node RPC
+     */
+    export interface IGetTransactionRequest {
+        transactionId : HexString;
+        includeVerboseData : boolean;
+    }
+    "#,
+}
+
+try_from! ( args: IGetTransactionRequest, GetTransactionRequest, {
+    Ok(from_value(args.into())?)
+});
+
+declare! {
+    IGetTransactionResponse,
+    r#"
+    /**
+     *
+     *
+     * @category Node RPC
+     */
+    export interface IGetTransactionResponse {
+        transaction : ITransaction;
+        containingBlockHash : HexString;
+        acceptingBlockHash : HexString;
+    }
+    "#,
+}
+
+try_from! ( args: GetTransactionResponse, IGetTransactionResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+// ---
+
+declare! {
+    IGetTransactionAcceptanceRequest,
+    r#"
+    /**
+     * Requests the containing and accepting blocks of transactions from the transaction index.
+     * Requires the node to run with `--txindex`.
+     *
+     * @category Node RPC
+     */
+    export interface IGetTransactionAcceptanceRequest {
+        transactionIds : HexString[];
+    }
+    "#,
+}
+
+try_from! ( args: IGetTransactionAcceptanceRequest, GetTransactionAcceptanceRequest, {
+    Ok(from_value(args.into())?)
+});
+
+declare! {
+    IGetTransactionAcceptanceResponse,
+    r#"
+    /**
+     * Transactions unknown to the transaction index are omitted.
+     *
+     * @category Node RPC
+     */
+    export interface IGetTransactionAcceptanceResponse {
+        acceptances : {
+            transactionId : HexString;
+            containingBlockHash : HexString;
+            acceptingBlockHash : HexString;
+        }[];
+    }
+    "#,
+}
+
+try_from! ( args: GetTransactionAcceptanceResponse, IGetTransactionAcceptanceResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+// ---
+
+declare! {
+    IGetAddressHistoryRequest,
+    r#"
+    /**
+     * Requests a page of the history of an address from the address history index.
+     * Requires the node to run with `--addresshistoryindex`.
+     *
+     * @category Node RPC
+     */
+    export interface IGetAddressHistoryRequest {
+        address : Address | string;
+        startDaaScore : bigint;
+        limit : number;
+    }
+    "#,
+}
+
+try_from! ( args: IGetAddressHistoryRequest, GetAddressHistoryRequest, {
+    Ok(from_value(args.into())?)
+});
+
+declare! {
+    IGetAddressHistoryResponse,
+    r#"
+    /**
+     * Entries are ordered by ascending DAA score. `nextDaaScore` is the
+     * `startDaaScore` of the next page, if any.
+     *
+     * @category Node RPC
+     */
+    export interface IGetAddressHistoryResponse {
+        entries : {
+            outpoint : ITransactionOutpoint;
+            daaScore : bigint;
+            direction : "received" | "spent";
+            amount : bigint;
+            spendingTransactionId? : HexString;
+        }[];
+        nextDaaScore? : bigint;
+        historyStartDaaScore : bigint;
+    }
+    "#,
+}
+
+try_from! ( args: GetAddressHistoryResponse, IGetAddressHistoryResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+// ---
+
+declare! {
+    ISetMempoolPolicyRequest,
+    r#"
+    /**
+     * Updates the mempool relay policy. Omitted fields are left unchanged.
+     * Requires the node to run with `--unsaferpc`.
+     *
+     * @category Node RPC
+     */
+    export interface ISetMempoolPolicyRequest {
+        maximumTransactionCount? : bigint;
+        mempoolSizeLimit? : bigint;
+        maximumOrphanTransactionMass? : bigint;
+        maximumOrphanTransactionCount? : bigint;
+        acceptNonStandard? : boolean;
+        minimumRelayTransactionFee? : bigint;
+    }
+    "#,
+}
+
+try_from! ( args: ISetMempoolPolicyRequest, SetMempoolPolicyRequest, {
+    Ok(from_value(args.into())?)
+});
+
+declare! {
+    ISetMempoolPolicyResponse,
+    r#"
+    /**
+     * @category Node RPC
+     */
+    export interface ISetMempoolPolicyResponse {
+        policy : {
+            maximumTransactionCount : bigint;
+            mempoolSizeLimit : bigint;
+            maximumOrphanTransactionMass : bigint;
+            maximumOrphanTransactionCount : bigint;
+            acceptNonStandard : boolean;
+            minimumRelayTransactionFee : bigint;
+        };
+        evictedTransactionCount : bigint;
+    }
+    "#,
+}
+
+try_from! ( args: SetMempoolPolicyResponse, ISetMempoolPolicyResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+// ---
+
+declare! {
+    IBackupRequest,
+    r#"
+    /**
+     * Requires the node to run with `--unsaferpc`.
+     *
+     * @category Node RPC
+     */
+    export interface IBackupRequest {
+        /**
+         * Directory on the node host, which must not exist or be empty.
+         * A node can be started from it with `--appdir=<targetDir>`.
+         */
+        targetDir : string;
+    }
+    "#,
+}
+
+try_from! ( args: IBackupRequest, BackupRequest, {
+    Ok(from_value(args.into())?)
+});
+
+declare! {
+    IBackupResponse,
+    r#"
+    /**
+     * @category Node RPC
+     */
+    export interface IBackupResponse {
+        databases : {
+            name : string;
+            path : string;
+            durationMillis : bigint;
+        }[];
+    }
+    "#,
+}
+
+try_from! ( args: BackupResponse, IBackupResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+// ---
+
 declare! {
     IGetDaaScoreTimestampEstimateRequest,
     r#"
@@ -984,6 +1271,7 @@ declare! {
         startHash : HexString;
         limit : bigint;
         isAscending : boolean;
+        includeVerboseData? : boolean;
     }
     "#,
 }
@@ -1002,6 +1290,7 @@ declare! {
      */
     export interface IGetHeadersResponse {
         headers : IHeader[];
+        verboseData : IBlockVerboseData[];
     }
     "#,
 }
@@ -1728,3 +2017,30 @@ try_from!( args: GetFeeEstimateExperimentalResponse, IGetFeeEstimateExperimental
 });
 
 // ---
+
+declare! {
+    IRpcCredentials,
+    r#"
+    /**
+     * Credentials authenticating a wRPC connection, either a `token`
+     * or a `username` along with a `password`.
+     *
+     * @category Node RPC
+     */
+    export interface IRpcCredentials {
+        token? : string;
+        username? : string;
+        password? : string;
+    }
+    "#,
+}
+
+try_from! ( args: IRpcCredentials, RpcCredentials, {
+    match (args.try_get_string("token")?, args.try_get_string("username")?, args.try_get_string("password")?) {
+        (Some(token), None, None) => Ok(RpcCredentials::Token(token)),
+        (None, Some(username), Some(password)) => Ok(RpcCredentials::Basic { username, password }),
+        _ => Err(Error::General("credentials require either a `token` or a `username` and a `password`".to_string())),
+    }
+});
+
+// ---
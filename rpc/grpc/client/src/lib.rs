@@ -28,7 +28,11 @@ use kaspa_notify::{
     },
 };
 use kaspa_rpc_core::{
-    api::rpc::RpcApi,
+    api::{
+        auth::{RpcCredentials, AUTHORIZATION_METADATA_KEY},
+        rpc::RpcApi,
+        tls::RpcClientTlsConfig,
+    },
     error::RpcError,
     error::RpcResult,
     model::message::*,
@@ -39,9 +43,11 @@ use kaspa_utils::{channel::Channel, triggers::DuplexTrigger};
 use kaspa_utils_tower::{
     counters::TowerConnectionCounters,
     middleware::{BodyExt, CountBytesBody, MapRequestBodyLayer, MapResponseBodyLayer, ServiceBuilder},
+    socks5::Socks5Connector,
 };
 use regex::Regex;
 use std::{
+    net::SocketAddr,
     sync::{
         atomic::{AtomicBool, Ordering},
         Arc,
@@ -50,6 +56,7 @@ use std::{
 };
 use tokio::sync::Mutex;
 use tonic::codec::CompressionEncoding;
+use tonic::transport::{Certificate, ClientTlsConfig, Identity};
 use tonic::Streaming;
 
 mod connection_event;
@@ -85,7 +92,7 @@ impl GrpcClient {
     pub const DIRECT_MODE_LISTENER_ID: ListenerId = 0;
 
     pub async fn connect(url: String) -> Result<GrpcClient> {
-        Self::connect_with_args(NotificationMode::Direct, url, None, false, None, false, None, Default::default()).await
+        Self::connect_with_args(NotificationMode::Direct, url, None, false, None, false, None, Default::default(), None, None).await
     }
 
     /// Connects to a gRPC server.
@@ -98,7 +105,8 @@ impl GrpcClient {
     ///               Registering a listener is pointless and ignored.
     ///               Subscribing to notifications ignores the listener ID.
     ///
-    /// `url`: the server to connect to
+    /// `url`: the server to connect to, optionally carrying credentials as `grpc://<token>@host:port`
+    /// or `grpc://<username>:<password>@host:port`. The `https://` scheme connects over TLS.
     ///
     /// `subscription_context`: it is advised to provide a clone of the same instance if multiple clients dealing with
     /// `UtxosChangedNotifications` are connected concurrently in order to optimize the memory footprint.
@@ -112,6 +120,11 @@ impl GrpcClient {
     /// `timeout_duration`: request timeout duration
     ///
     /// `counters`: collects some bandwidth metrics
+    ///
+    /// `tls`: for `https://` urls, the CA certificates to trust in place of the web PKI roots and
+    /// the client certificate to present to servers requiring mutual TLS
+    ///
+    /// `proxy`: a SOCKS5 proxy to dial the server through, resolving its host name on the proxy side
     pub async fn connect_with_args(
         notification_mode: NotificationMode,
         url: String,
@@ -121,8 +134,10 @@ impl GrpcClient {
         override_handle_stop_notify: bool,
         timeout_duration: Option<u64>,
         counters: Arc<TowerConnectionCounters>,
+        tls: Option<RpcClientTlsConfig>,
+        proxy: Option<SocketAddr>,
     ) -> Result<GrpcClient> {
-        let schema = Regex::new(r"^grpc://").unwrap();
+        let schema = Regex::new(r"^(grpc|https)://").unwrap();
         if !schema.is_match(&url) {
             return Err(Error::GrpcAddressSchema(url));
         }
@@ -132,6 +147,8 @@ impl GrpcClient {
             override_handle_stop_notify,
             timeout_duration.unwrap_or(REQUEST_TIMEOUT_DURATION),
             counters,
+            tls,
+            proxy,
         )
         .await?;
         let converter = Arc::new(RpcCoreConverter::new());
@@ -276,6 +293,12 @@ impl RpcApi for GrpcClient {
     route!(get_fee_estimate_call, GetFeeEstimate);
     route!(get_fee_estimate_experimental_call, GetFeeEstimateExperimental);
     route!(get_current_block_color_call, GetCurrentBlockColor);
+    route!(get_transaction_call, GetTransaction);
+    route!(get_transaction_acceptance_call, GetTransactionAcceptance);
+    route!(get_address_history_call, GetAddressHistory);
+    route!(get_mempool_policy_call, GetMempoolPolicy);
+    route!(set_mempool_policy_call, SetMempoolPolicy);
+    route!(backup_call, Backup);
 
     // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
     // Notification API
@@ -403,6 +426,15 @@ struct ServerFeatures {
 struct Inner {
     url: String,
 
+    /// The `authorization` value sent to the server when opening the message stream
+    authorization: Option<String>,
+
+    /// TLS settings applied to `https://` urls
+    tls: Option<ClientTlsConfig>,
+
+    /// SOCKS5 proxy the server is dialed through
+    proxy: Option<SocketAddr>,
+
     server_features: ServerFeatures,
 
     // Pushing incoming notifications forward
@@ -443,6 +475,9 @@ struct Inner {
 impl Inner {
     fn new(
         url: String,
+        authorization: Option<String>,
+        tls: Option<ClientTlsConfig>,
+        proxy: Option<SocketAddr>,
         server_features: ServerFeatures,
         request_sender: KaspadRequestSender,
         request_receiver: KaspadRequestReceiver,
@@ -458,6 +493,9 @@ impl Inner {
         let notification_channel = Channel::default();
         Self {
             url,
+            authorization,
+            tls,
+            proxy,
             server_features,
             notification_channel,
             request_sender,
@@ -485,18 +523,35 @@ impl Inner {
         override_handle_stop_notify: bool,
         timeout_duration: u64,
         counters: Arc<TowerConnectionCounters>,
+        tls: Option<RpcClientTlsConfig>,
+        proxy: Option<SocketAddr>,
     ) -> Result<Arc<Self>> {
         // Request channel
         let (request_sender, request_receiver) = async_channel::unbounded();
 
+        // Keep the credentials out of the url
+        let (url, authorization) = Self::split_credentials(&url);
+        let tls = tls.map(Self::tls_config);
+
         // Try to connect to the server
-        let (stream, server_features) =
-            Inner::try_connect(url.clone(), request_sender.clone(), request_receiver.clone(), timeout_duration, counters.clone())
-                .await?;
+        let (stream, server_features) = Inner::try_connect(
+            url.clone(),
+            authorization.clone(),
+            tls.clone(),
+            proxy,
+            request_sender.clone(),
+            request_receiver.clone(),
+            timeout_duration,
+            counters.clone(),
+        )
+        .await?;
 
         // create the inner object
         let inner = Arc::new(Inner::new(
             url,
+            authorization,
+            tls,
+            proxy,
             server_features,
             request_sender,
             request_receiver,
@@ -516,9 +571,49 @@ impl Inner {
         Ok(inner)
     }
 
+    /// Splits the credentials out of the userinfo of `url`, returning the url without them
+    /// along with the matching `authorization` value
+    fn split_credentials(url: &str) -> (String, Option<String>) {
+        let Some((scheme, rest)) = url.split_once("://") else {
+            return (url.to_string(), None);
+        };
+        let authority = &rest[..rest.find('/').unwrap_or(rest.len())];
+        let Some((userinfo, host)) = authority.rsplit_once('@') else {
+            return (url.to_string(), None);
+        };
+        let credentials = match userinfo.split_once(':') {
+            Some((username, password)) => RpcCredentials::Basic { username: username.to_string(), password: password.to_string() },
+            None => RpcCredentials::Token(userinfo.to_string()),
+        };
+        (format!("{scheme}://{host}{}", &rest[authority.len()..]), Some(credentials.to_authorization()))
+    }
+
+    fn tls_config(tls: RpcClientTlsConfig) -> ClientTlsConfig {
+        let config = match tls.ca_certificate {
+            Some(ca_certificate) => ClientTlsConfig::new().ca_certificate(Certificate::from_pem(ca_certificate)),
+            None => ClientTlsConfig::new().with_enabled_roots(),
+        };
+        match tls.identity {
+            Some(identity) => config.identity(Identity::from_pem(identity.certificate, identity.private_key)),
+            None => config,
+        }
+    }
+
+    fn endpoint(url: &str, tls: Option<ClientTlsConfig>) -> Result<tonic::transport::Endpoint> {
+        let endpoint =
+            tonic::transport::Channel::builder(url.parse::<tonic::transport::Uri>().map_err(|e| Error::String(e.to_string()))?);
+        match tls {
+            Some(tls) if url.starts_with("https://") => Ok(endpoint.tls_config(tls)?),
+            _ => Ok(endpoint),
+        }
+    }
+
     #[allow(unused_variables)]
     async fn try_connect(
         url: String,
+        authorization: Option<String>,
+        tls: Option<ClientTlsConfig>,
+        proxy: Option<SocketAddr>,
         request_sender: KaspadRequestSender,
         request_receiver: KaspadRequestReceiver,
         request_timeout: u64,
@@ -526,18 +621,17 @@ impl Inner {
     ) -> Result<(Streaming<KaspadResponse>, ServerFeatures)> {
         // gRPC endpoint
         #[cfg(not(feature = "heap"))]
-        let channel =
-            tonic::transport::Channel::builder(url.parse::<tonic::transport::Uri>().map_err(|e| Error::String(e.to_string()))?)
-                .timeout(tokio::time::Duration::from_millis(request_timeout))
-                .connect_timeout(tokio::time::Duration::from_millis(CONNECT_TIMEOUT_DURATION))
-                .connect()
-                .await?;
+        let endpoint = Self::endpoint(&url, tls)?
+            .timeout(tokio::time::Duration::from_millis(request_timeout))
+            .connect_timeout(tokio::time::Duration::from_millis(CONNECT_TIMEOUT_DURATION));
 
         #[cfg(feature = "heap")]
-        let channel =
-            tonic::transport::Channel::builder(url.parse::<tonic::transport::Uri>().map_err(|e| Error::String(e.to_string()))?)
-                .connect()
-                .await?;
+        let endpoint = Self::endpoint(&url, tls)?;
+
+        let channel = match proxy {
+            Some(proxy) => endpoint.connect_with_connector(Socks5Connector::new(proxy)).await?,
+            None => endpoint.connect().await?,
+        };
 
         let bytes_rx = &counters.bytes_rx;
         let bytes_tx = &counters.bytes_tx;
@@ -571,8 +665,15 @@ impl Inner {
             }
         };
 
+        // Attach the credentials, if any, to the stream opening request
+        let mut request = tonic::Request::new(request_stream);
+        if let Some(authorization) = authorization {
+            let authorization = authorization.parse().map_err(|_| Error::String("invalid gRPC credentials".to_string()))?;
+            request.metadata_mut().insert(AUTHORIZATION_METADATA_KEY, authorization);
+        }
+
         // Actual KaspadRequest to KaspadResponse stream
-        let mut stream: Streaming<KaspadResponse> = client.message_stream(request_stream).await?.into_inner();
+        let mut stream: Streaming<KaspadResponse> = client.message_stream(request).await?.into_inner();
 
         // Collect server capabilities as stated in GetInfoResponse
         let mut server_features = ServerFeatures::default();
@@ -611,6 +712,9 @@ impl Inner {
         // Try to connect to the server
         let (stream, _) = Inner::try_connect(
             self.url.clone(),
+            self.authorization.clone(),
+            self.tls.clone(),
+            self.proxy,
             self.request_sender.clone(),
             self.request_receiver.clone(),
             self.timeout_duration,
@@ -1,6 +1,7 @@
 use crate::protowire;
 use crate::{from, try_from};
-use kaspa_rpc_core::RpcError;
+use kaspa_rpc_core::{RpcError, RpcTransactionId};
+use std::str::FromStr;
 
 // ----------------------------------------------------------------------------
 // rpc_core to protowire
@@ -10,6 +11,23 @@ from!(item: &kaspa_rpc_core::RpcBalancesByAddressesEntry, protowire::RpcBalances
     Self { address: (&item.address).into(), balance: item.balance.unwrap_or_default(), error: None }
 });
 
+from!(item: kaspa_rpc_core::RpcAddressHistoryDirection, protowire::RpcAddressHistoryDirection, {
+    match item {
+        kaspa_rpc_core::RpcAddressHistoryDirection::Received => Self::Received,
+        kaspa_rpc_core::RpcAddressHistoryDirection::Spent => Self::Spent,
+    }
+});
+
+from!(item: &kaspa_rpc_core::RpcAddressHistoryEntry, protowire::RpcAddressHistoryEntry, {
+    Self {
+        outpoint: Some((&item.outpoint).into()),
+        daa_score: item.daa_score,
+        direction: protowire::RpcAddressHistoryDirection::from(item.direction) as i32,
+        amount: item.amount,
+        spending_transaction_id: item.spending_transaction_id.map(|id| id.to_string()).unwrap_or_default(),
+    }
+});
+
 // ----------------------------------------------------------------------------
 // protowire to rpc_core
 // ----------------------------------------------------------------------------
@@ -18,3 +36,30 @@ try_from!(item: &protowire::RpcBalancesByAddressesEntry, kaspa_rpc_core::RpcBala
     let balance = if item.error.is_some() { None } else { Some(item.balance) };
     Self { address: item.address.as_str().try_into()?, balance }
 });
+
+from!(item: protowire::RpcAddressHistoryDirection, kaspa_rpc_core::RpcAddressHistoryDirection, {
+    match item {
+        protowire::RpcAddressHistoryDirection::Received => Self::Received,
+        protowire::RpcAddressHistoryDirection::Spent => Self::Spent,
+    }
+});
+
+try_from!(item: &protowire::RpcAddressHistoryEntry, kaspa_rpc_core::RpcAddressHistoryEntry, {
+    Self {
+        outpoint: item
+            .outpoint
+            .as_ref()
+            .ok_or_else(|| RpcError::MissingRpcFieldError("RpcAddressHistoryEntry".to_string(), "outpoint".to_string()))?
+            .try_into()?,
+        daa_score: item.daa_score,
+        direction: protowire::RpcAddressHistoryDirection::try_from(item.direction)
+            .map_err(|_| RpcError::PrimitiveToEnumConversionError)?
+            .into(),
+        amount: item.amount,
+        spending_transaction_id: if item.spending_transaction_id.is_empty() {
+            None
+        } else {
+            Some(RpcTransactionId::from_str(&item.spending_transaction_id)?)
+        },
+    }
+});
@@ -63,6 +63,12 @@ pub mod kaspad_request_convert {
     impl_into_kaspad_request!(GetFeeEstimate);
     impl_into_kaspad_request!(GetFeeEstimateExperimental);
     impl_into_kaspad_request!(GetCurrentBlockColor);
+    impl_into_kaspad_request!(GetTransaction);
+    impl_into_kaspad_request!(GetTransactionAcceptance);
+    impl_into_kaspad_request!(GetAddressHistory);
+    impl_into_kaspad_request!(GetMempoolPolicy);
+    impl_into_kaspad_request!(SetMempoolPolicy);
+    impl_into_kaspad_request!(Backup);
 
     impl_into_kaspad_request!(NotifyBlockAdded);
     impl_into_kaspad_request!(NotifyNewBlockTemplate);
@@ -72,6 +78,7 @@ pub mod kaspad_request_convert {
     impl_into_kaspad_request!(NotifyVirtualDaaScoreChanged);
     impl_into_kaspad_request!(NotifyVirtualChainChanged);
     impl_into_kaspad_request!(NotifySinkBlueScoreChanged);
+    impl_into_kaspad_request!(NotifyMempoolChanged);
 
     macro_rules! impl_into_kaspad_request {
         ($name:tt) => {
@@ -200,6 +207,12 @@ pub mod kaspad_response_convert {
     impl_into_kaspad_response!(GetFeeEstimate);
     impl_into_kaspad_response!(GetFeeEstimateExperimental);
     impl_into_kaspad_response!(GetCurrentBlockColor);
+    impl_into_kaspad_response!(GetTransaction);
+    impl_into_kaspad_response!(GetTransactionAcceptance);
+    impl_into_kaspad_response!(GetAddressHistory);
+    impl_into_kaspad_response!(GetMempoolPolicy);
+    impl_into_kaspad_response!(SetMempoolPolicy);
+    impl_into_kaspad_response!(Backup);
 
     impl_into_kaspad_notify_response!(NotifyBlockAdded);
     impl_into_kaspad_notify_response!(NotifyNewBlockTemplate);
@@ -209,6 +222,7 @@ pub mod kaspad_response_convert {
     impl_into_kaspad_notify_response!(NotifyVirtualDaaScoreChanged);
     impl_into_kaspad_notify_response!(NotifyVirtualChainChanged);
     impl_into_kaspad_notify_response!(NotifySinkBlueScoreChanged);
+    impl_into_kaspad_notify_response!(NotifyMempoolChanged);
 
     impl_into_kaspad_notify_response!(NotifyUtxosChanged, StopNotifyingUtxosChanged);
     impl_into_kaspad_notify_response!(NotifyPruningPointUtxoSetOverride, StopNotifyingPruningPointUtxoSetOverride);
@@ -0,0 +1,50 @@
+use crate::protowire;
+use crate::{from, try_from};
+use kaspa_rpc_core::{api::limits::*, RpcError};
+
+// ----------------------------------------------------------------------------
+// rpc_core to protowire
+// ----------------------------------------------------------------------------
+
+from!(item: &RpcRateLimit, protowire::RpcRateLimit, { Self { rate: item.rate, burst: item.burst } });
+
+from!(item: &RpcRateLimits, protowire::RpcRateLimits, {
+    Self {
+        light: item.light.as_ref().map(|x| x.into()),
+        standard: item.standard.as_ref().map(|x| x.into()),
+        heavy: item.heavy.as_ref().map(|x| x.into()),
+    }
+});
+
+from!(item: &RpcRateLimitConfig, protowire::RpcRateLimitConfig, {
+    Self { connection: Some((&item.connection).into()), ip: Some((&item.ip).into()) }
+});
+
+// ----------------------------------------------------------------------------
+// protowire to rpc_core
+// ----------------------------------------------------------------------------
+
+try_from!(item: &protowire::RpcRateLimit, RpcRateLimit, { Self { rate: item.rate, burst: item.burst } });
+
+try_from!(item: &protowire::RpcRateLimits, RpcRateLimits, {
+    Self {
+        light: item.light.as_ref().map(|x| x.try_into()).transpose()?,
+        standard: item.standard.as_ref().map(|x| x.try_into()).transpose()?,
+        heavy: item.heavy.as_ref().map(|x| x.try_into()).transpose()?,
+    }
+});
+
+try_from!(item: &protowire::RpcRateLimitConfig, RpcRateLimitConfig, {
+    Self {
+        connection: item
+            .connection
+            .as_ref()
+            .ok_or_else(|| RpcError::MissingRpcFieldError("RpcRateLimitConfig".to_string(), "connection".to_string()))?
+            .try_into()?,
+        ip: item
+            .ip
+            .as_ref()
+            .ok_or_else(|| RpcError::MissingRpcFieldError("RpcRateLimitConfig".to_string(), "ip".to_string()))?
+            .try_into()?,
+    }
+});
@@ -18,6 +18,17 @@ from!(item: &kaspa_rpc_core::RpcMempoolEntryByAddress, protowire::RpcMempoolEntr
     }
 });
 
+from!(item: &kaspa_rpc_core::RpcMempoolPolicy, protowire::RpcMempoolPolicy, {
+    Self {
+        maximum_transaction_count: item.maximum_transaction_count,
+        mempool_size_limit: item.mempool_size_limit,
+        maximum_orphan_transaction_mass: item.maximum_orphan_transaction_mass,
+        maximum_orphan_transaction_count: item.maximum_orphan_transaction_count,
+        accept_non_standard: item.accept_non_standard,
+        minimum_relay_transaction_fee: item.minimum_relay_transaction_fee,
+    }
+});
+
 // ----------------------------------------------------------------------------
 // protowire to rpc_core
 // ----------------------------------------------------------------------------
@@ -40,3 +51,14 @@ try_from!(item: &protowire::RpcMempoolEntryByAddress, kaspa_rpc_core::RpcMempool
         item.receiving.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, _>>()?,
     )
 });
+
+try_from!(item: &protowire::RpcMempoolPolicy, kaspa_rpc_core::RpcMempoolPolicy, {
+    Self {
+        maximum_transaction_count: item.maximum_transaction_count,
+        mempool_size_limit: item.mempool_size_limit,
+        maximum_orphan_transaction_mass: item.maximum_orphan_transaction_mass,
+        maximum_orphan_transaction_count: item.maximum_orphan_transaction_count,
+        accept_non_standard: item.accept_non_standard,
+        minimum_relay_transaction_fee: item.minimum_relay_transaction_fee,
+    }
+});
@@ -325,10 +325,19 @@ from!(&kaspa_rpc_core::ShutdownRequest, protowire::ShutdownRequestMessage);
 from!(RpcResult<&kaspa_rpc_core::ShutdownResponse>, protowire::ShutdownResponseMessage);
 
 from!(item: &kaspa_rpc_core::GetHeadersRequest, protowire::GetHeadersRequestMessage, {
-    Self { start_hash: item.start_hash.to_string(), limit: item.limit, is_ascending: item.is_ascending }
+    Self {
+        start_hash: item.start_hash.to_string(),
+        limit: item.limit,
+        is_ascending: item.is_ascending,
+        include_verbose_data: item.include_verbose_data,
+    }
 });
 from!(item: RpcResult<&kaspa_rpc_core::GetHeadersResponse>, protowire::GetHeadersResponseMessage, {
-    Self { headers: item.headers.iter().map(|x| x.hash.to_string()).collect(), error: None }
+    Self {
+        headers: item.headers.iter().map(|x| x.into()).collect(),
+        verbose_data: item.verbose_data.iter().map(|x| x.into()).collect(),
+        error: None,
+    }
 });
 
 from!(item: &kaspa_rpc_core::GetUtxosByAddressesRequest, protowire::GetUtxosByAddressesRequestMessage, {
@@ -430,6 +439,71 @@ from!(item: RpcResult<&kaspa_rpc_core::GetCurrentBlockColorResponse>, protowire:
     Self { blue: item.blue, error: None }
 });
 
+from!(item: &kaspa_rpc_core::GetTransactionRequest, protowire::GetTransactionRequestMessage, {
+    Self { transaction_id: item.transaction_id.to_string(), include_verbose_data: item.include_verbose_data }
+});
+from!(item: RpcResult<&kaspa_rpc_core::GetTransactionResponse>, protowire::GetTransactionResponseMessage, {
+    Self {
+        transaction: Some((&item.transaction).into()),
+        containing_block_hash: item.containing_block_hash.to_string(),
+        accepting_block_hash: item.accepting_block_hash.to_string(),
+        error: None,
+    }
+});
+
+from!(item: &kaspa_rpc_core::GetTransactionAcceptanceRequest, protowire::GetTransactionAcceptanceRequestMessage, {
+    Self { transaction_ids: item.transaction_ids.iter().map(|x| x.to_string()).collect() }
+});
+from!(item: RpcResult<&kaspa_rpc_core::GetTransactionAcceptanceResponse>, protowire::GetTransactionAcceptanceResponseMessage, {
+    Self { acceptances: item.acceptances.iter().map(|x| x.into()).collect(), error: None }
+});
+
+from!(item: &kaspa_rpc_core::GetAddressHistoryRequest, protowire::GetAddressHistoryRequestMessage, {
+    Self { address: (&item.address).into(), start_daa_score: item.start_daa_score, limit: item.limit }
+});
+from!(item: RpcResult<&kaspa_rpc_core::GetAddressHistoryResponse>, protowire::GetAddressHistoryResponseMessage, {
+    Self {
+        entries: item.entries.iter().map(|x| x.into()).collect(),
+        next_daa_score: item.next_daa_score.unwrap_or_default(),
+        has_next_page: item.next_daa_score.is_some(),
+        history_start_daa_score: item.history_start_daa_score,
+        error: None,
+    }
+});
+
+from!(&kaspa_rpc_core::GetMempoolPolicyRequest, protowire::GetMempoolPolicyRequestMessage);
+from!(item: RpcResult<&kaspa_rpc_core::GetMempoolPolicyResponse>, protowire::GetMempoolPolicyResponseMessage, {
+    Self { policy: Some((&item.policy).into()), error: None }
+});
+
+from!(item: &kaspa_rpc_core::SetMempoolPolicyRequest, protowire::SetMempoolPolicyRequestMessage, {
+    Self {
+        maximum_transaction_count: item.maximum_transaction_count.unwrap_or_default(),
+        has_maximum_transaction_count: item.maximum_transaction_count.is_some(),
+        mempool_size_limit: item.mempool_size_limit.unwrap_or_default(),
+        has_mempool_size_limit: item.mempool_size_limit.is_some(),
+        maximum_orphan_transaction_mass: item.maximum_orphan_transaction_mass.unwrap_or_default(),
+        has_maximum_orphan_transaction_mass: item.maximum_orphan_transaction_mass.is_some(),
+        maximum_orphan_transaction_count: item.maximum_orphan_transaction_count.unwrap_or_default(),
+        has_maximum_orphan_transaction_count: item.maximum_orphan_transaction_count.is_some(),
+        accept_non_standard: item.accept_non_standard.unwrap_or_default(),
+        has_accept_non_standard: item.accept_non_standard.is_some(),
+        minimum_relay_transaction_fee: item.minimum_relay_transaction_fee.unwrap_or_default(),
+        has_minimum_relay_transaction_fee: item.minimum_relay_transaction_fee.is_some(),
+    }
+});
+from!(item: RpcResult<&kaspa_rpc_core::SetMempoolPolicyResponse>, protowire::SetMempoolPolicyResponseMessage, {
+    Self { policy: Some((&item.policy).into()), evicted_transaction_count: item.evicted_transaction_count, error: None }
+});
+
+from!(item: &kaspa_rpc_core::RpcDatabaseBackup, protowire::RpcDatabaseBackup, {
+    Self { name: item.name.clone(), path: item.path.clone(), duration_millis: item.duration_millis }
+});
+from!(item: &kaspa_rpc_core::BackupRequest, protowire::BackupRequestMessage, { Self { target_dir: item.target_dir.clone() } });
+from!(item: RpcResult<&kaspa_rpc_core::BackupResponse>, protowire::BackupResponseMessage, {
+    Self { databases: item.databases.iter().map(|x| x.into()).collect(), error: None }
+});
+
 from!(&kaspa_rpc_core::PingRequest, protowire::PingRequestMessage);
 from!(RpcResult<&kaspa_rpc_core::PingResponse>, protowire::PingResponseMessage);
 
@@ -495,6 +569,7 @@ from!(item: RpcResult<&kaspa_rpc_core::GetServerInfoResponse>, protowire::GetSer
         has_utxo_index: item.has_utxo_index,
         is_synced: item.is_synced,
         virtual_daa_score: item.virtual_daa_score,
+        rate_limits: item.rate_limits.as_ref().map(|x| x.into()),
         error: None,
     }
 });
@@ -549,6 +624,11 @@ from!(item: &kaspa_rpc_core::NotifySinkBlueScoreChangedRequest, protowire::Notif
 });
 from!(RpcResult<&kaspa_rpc_core::NotifySinkBlueScoreChangedResponse>, protowire::NotifySinkBlueScoreChangedResponseMessage);
 
+from!(item: &kaspa_rpc_core::NotifyMempoolChangedRequest, protowire::NotifyMempoolChangedRequestMessage, {
+    Self { addresses: item.addresses.iter().map(|x| x.into()).collect(), command: item.command.into() }
+});
+from!(RpcResult<&kaspa_rpc_core::NotifyMempoolChangedResponse>, protowire::NotifyMempoolChangedResponseMessage);
+
 // ----------------------------------------------------------------------------
 // protowire to rpc_core
 // ----------------------------------------------------------------------------
@@ -804,11 +884,18 @@ try_from!(&protowire::ShutdownRequestMessage, kaspa_rpc_core::ShutdownRequest);
 try_from!(&protowire::ShutdownResponseMessage, RpcResult<kaspa_rpc_core::ShutdownResponse>);
 
 try_from!(item: &protowire::GetHeadersRequestMessage, kaspa_rpc_core::GetHeadersRequest, {
-    Self { start_hash: RpcHash::from_str(&item.start_hash)?, limit: item.limit, is_ascending: item.is_ascending }
+    Self {
+        start_hash: RpcHash::from_str(&item.start_hash)?,
+        limit: item.limit,
+        is_ascending: item.is_ascending,
+        include_verbose_data: item.include_verbose_data,
+    }
 });
 try_from!(item: &protowire::GetHeadersResponseMessage, RpcResult<kaspa_rpc_core::GetHeadersResponse>, {
-    // TODO
-    Self { headers: vec![] }
+    Self {
+        headers: item.headers.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, _>>()?,
+        verbose_data: item.verbose_data.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, _>>()?,
+    }
 });
 
 try_from!(item: &protowire::GetUtxosByAddressesRequestMessage, kaspa_rpc_core::GetUtxosByAddressesRequest, {
@@ -917,6 +1004,79 @@ try_from!(item: &protowire::GetCurrentBlockColorResponseMessage, RpcResult<kaspa
     }
 });
 
+try_from!(item: &protowire::GetTransactionRequestMessage, kaspa_rpc_core::GetTransactionRequest, {
+    Self { transaction_id: RpcHash::from_str(&item.transaction_id)?, include_verbose_data: item.include_verbose_data }
+});
+try_from!(item: &protowire::GetTransactionResponseMessage, RpcResult<kaspa_rpc_core::GetTransactionResponse>, {
+    Self {
+        transaction: item
+            .transaction
+            .as_ref()
+            .ok_or_else(|| RpcError::MissingRpcFieldError("GetTransactionResponseMessage".to_string(), "transaction".to_string()))?
+            .try_into()?,
+        containing_block_hash: RpcHash::from_str(&item.containing_block_hash)?,
+        accepting_block_hash: RpcHash::from_str(&item.accepting_block_hash)?,
+    }
+});
+
+try_from!(item: &protowire::GetTransactionAcceptanceRequestMessage, kaspa_rpc_core::GetTransactionAcceptanceRequest, {
+    Self { transaction_ids: item.transaction_ids.iter().map(|x| RpcHash::from_str(x)).collect::<Result<Vec<_>, _>>()? }
+});
+try_from!(item: &protowire::GetTransactionAcceptanceResponseMessage, RpcResult<kaspa_rpc_core::GetTransactionAcceptanceResponse>, {
+    Self { acceptances: item.acceptances.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, _>>()? }
+});
+
+try_from!(item: &protowire::GetAddressHistoryRequestMessage, kaspa_rpc_core::GetAddressHistoryRequest, {
+    Self { address: item.address.as_str().try_into()?, start_daa_score: item.start_daa_score, limit: item.limit }
+});
+try_from!(item: &protowire::GetAddressHistoryResponseMessage, RpcResult<kaspa_rpc_core::GetAddressHistoryResponse>, {
+    Self {
+        entries: item.entries.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, _>>()?,
+        next_daa_score: if item.has_next_page { Some(item.next_daa_score) } else { None },
+        history_start_daa_score: item.history_start_daa_score,
+    }
+});
+
+try_from!(&protowire::GetMempoolPolicyRequestMessage, kaspa_rpc_core::GetMempoolPolicyRequest);
+try_from!(item: &protowire::GetMempoolPolicyResponseMessage, RpcResult<kaspa_rpc_core::GetMempoolPolicyResponse>, {
+    Self {
+        policy: item
+            .policy
+            .as_ref()
+            .ok_or_else(|| RpcError::MissingRpcFieldError("GetMempoolPolicyResponseMessage".to_string(), "policy".to_string()))?
+            .try_into()?,
+    }
+});
+
+try_from!(item: &protowire::SetMempoolPolicyRequestMessage, kaspa_rpc_core::SetMempoolPolicyRequest, {
+    Self {
+        maximum_transaction_count: item.has_maximum_transaction_count.then_some(item.maximum_transaction_count),
+        mempool_size_limit: item.has_mempool_size_limit.then_some(item.mempool_size_limit),
+        maximum_orphan_transaction_mass: item.has_maximum_orphan_transaction_mass.then_some(item.maximum_orphan_transaction_mass),
+        maximum_orphan_transaction_count: item.has_maximum_orphan_transaction_count.then_some(item.maximum_orphan_transaction_count),
+        accept_non_standard: item.has_accept_non_standard.then_some(item.accept_non_standard),
+        minimum_relay_transaction_fee: item.has_minimum_relay_transaction_fee.then_some(item.minimum_relay_transaction_fee),
+    }
+});
+try_from!(item: &protowire::SetMempoolPolicyResponseMessage, RpcResult<kaspa_rpc_core::SetMempoolPolicyResponse>, {
+    Self {
+        policy: item
+            .policy
+            .as_ref()
+            .ok_or_else(|| RpcError::MissingRpcFieldError("SetMempoolPolicyResponseMessage".to_string(), "policy".to_string()))?
+            .try_into()?,
+        evicted_transaction_count: item.evicted_transaction_count,
+    }
+});
+
+try_from!(item: &protowire::RpcDatabaseBackup, kaspa_rpc_core::RpcDatabaseBackup, {
+    Self { name: item.name.clone(), path: item.path.clone(), duration_millis: item.duration_millis }
+});
+try_from!(item: &protowire::BackupRequestMessage, kaspa_rpc_core::BackupRequest, { Self { target_dir: item.target_dir.clone() } });
+try_from!(item: &protowire::BackupResponseMessage, RpcResult<kaspa_rpc_core::BackupResponse>, {
+    Self { databases: item.databases.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, _>>()? }
+});
+
 try_from!(&protowire::PingRequestMessage, kaspa_rpc_core::PingRequest);
 try_from!(&protowire::PingResponseMessage, RpcResult<kaspa_rpc_core::PingResponse>);
 
@@ -977,6 +1137,7 @@ try_from!(item: &protowire::GetServerInfoResponseMessage, RpcResult<kaspa_rpc_co
         has_utxo_index: item.has_utxo_index,
         is_synced: item.is_synced,
         virtual_daa_score: item.virtual_daa_score,
+        rate_limits: item.rate_limits.as_ref().map(|x| x.try_into()).transpose()?,
     }
 });
 
@@ -1041,6 +1202,14 @@ try_from!(item: &protowire::NotifySinkBlueScoreChangedRequestMessage, kaspa_rpc_
 });
 try_from!(&protowire::NotifySinkBlueScoreChangedResponseMessage, RpcResult<kaspa_rpc_core::NotifySinkBlueScoreChangedResponse>);
 
+try_from!(item: &protowire::NotifyMempoolChangedRequestMessage, kaspa_rpc_core::NotifyMempoolChangedRequest, {
+    Self {
+        addresses: item.addresses.iter().map(|x| x.as_str().try_into()).collect::<Result<Vec<_>, _>>()?,
+        command: item.command.into(),
+    }
+});
+try_from!(&protowire::NotifyMempoolChangedResponseMessage, RpcResult<kaspa_rpc_core::NotifyMempoolChangedResponse>);
+
 // ----------------------------------------------------------------------------
 // Unit tests
 // ----------------------------------------------------------------------------
@@ -50,6 +50,8 @@ from!(item: &kaspa_rpc_core::BandwidthMetrics, protowire::BandwidthMetrics, {
         grpc_p2p_bytes_rx: item.p2p_bytes_rx,
         grpc_user_bytes_tx: item.grpc_bytes_tx,
         grpc_user_bytes_rx: item.grpc_bytes_rx,
+        p2p_tx_announcement_bytes_tx: item.p2p_tx_announcement_bytes_tx,
+        p2p_tx_flood_bytes_tx: item.p2p_tx_flood_bytes_tx,
     }
 });
 
@@ -124,6 +126,8 @@ try_from!(item: &protowire::BandwidthMetrics, kaspa_rpc_core::BandwidthMetrics,
         p2p_bytes_rx: item.grpc_p2p_bytes_rx,
         grpc_bytes_tx: item.grpc_user_bytes_tx,
         grpc_bytes_rx: item.grpc_user_bytes_rx,
+        p2p_tx_announcement_bytes_tx: item.p2p_tx_announcement_bytes_tx,
+        p2p_tx_flood_bytes_tx: item.p2p_tx_flood_bytes_tx,
     }
 });
 
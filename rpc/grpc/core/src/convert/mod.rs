@@ -4,6 +4,7 @@ pub mod error;
 pub mod feerate_estimate;
 pub mod header;
 pub mod kaspad;
+pub mod limits;
 pub mod mempool;
 pub mod message;
 pub mod metrics;
@@ -1,13 +1,15 @@
 use crate::protowire::{
-    kaspad_response::Payload, BlockAddedNotificationMessage, KaspadResponse, NewBlockTemplateNotificationMessage, RpcNotifyCommand,
+    kaspad_response::Payload, BlockAddedNotificationMessage, KaspadResponse, NewBlockTemplateNotificationMessage,
+    RpcMempoolChangeKind, RpcNotifyCommand,
 };
 use crate::protowire::{
-    FinalityConflictNotificationMessage, FinalityConflictResolvedNotificationMessage, NotifyPruningPointUtxoSetOverrideRequestMessage,
-    NotifyPruningPointUtxoSetOverrideResponseMessage, NotifyUtxosChangedRequestMessage, NotifyUtxosChangedResponseMessage,
-    PruningPointUtxoSetOverrideNotificationMessage, SinkBlueScoreChangedNotificationMessage,
-    StopNotifyingPruningPointUtxoSetOverrideRequestMessage, StopNotifyingPruningPointUtxoSetOverrideResponseMessage,
-    StopNotifyingUtxosChangedRequestMessage, StopNotifyingUtxosChangedResponseMessage, UtxosChangedNotificationMessage,
-    VirtualChainChangedNotificationMessage, VirtualDaaScoreChangedNotificationMessage,
+    FinalityConflictNotificationMessage, FinalityConflictResolvedNotificationMessage, MempoolChangedNotificationMessage,
+    NotifyPruningPointUtxoSetOverrideRequestMessage, NotifyPruningPointUtxoSetOverrideResponseMessage,
+    NotifyUtxosChangedRequestMessage, NotifyUtxosChangedResponseMessage, PruningPointUtxoSetOverrideNotificationMessage,
+    SinkBlueScoreChangedNotificationMessage, StopNotifyingPruningPointUtxoSetOverrideRequestMessage,
+    StopNotifyingPruningPointUtxoSetOverrideResponseMessage, StopNotifyingUtxosChangedRequestMessage,
+    StopNotifyingUtxosChangedResponseMessage, UtxosChangedNotificationMessage, VirtualChainChangedNotificationMessage,
+    VirtualDaaScoreChangedNotificationMessage,
 };
 use crate::{from, try_from};
 use kaspa_notify::subscription::Command;
@@ -34,6 +36,7 @@ from!(item: &kaspa_rpc_core::Notification, Payload, {
         Notification::PruningPointUtxoSetOverride(ref notification) => {
             Payload::PruningPointUtxoSetOverrideNotification(notification.into())
         }
+        Notification::MempoolChanged(ref notification) => Payload::MempoolChangedNotification(notification.into()),
     }
 });
 
@@ -74,6 +77,25 @@ from!(item: &kaspa_rpc_core::VirtualDaaScoreChangedNotification, VirtualDaaScore
 
 from!(&kaspa_rpc_core::PruningPointUtxoSetOverrideNotification, PruningPointUtxoSetOverrideNotificationMessage);
 
+from!(item: kaspa_rpc_core::RpcMempoolChangeKind, RpcMempoolChangeKind, {
+    match item {
+        kaspa_rpc_core::RpcMempoolChangeKind::Added => Self::Added,
+        kaspa_rpc_core::RpcMempoolChangeKind::Accepted => Self::Accepted,
+        kaspa_rpc_core::RpcMempoolChangeKind::Replaced => Self::Replaced,
+        kaspa_rpc_core::RpcMempoolChangeKind::Expired => Self::Expired,
+        kaspa_rpc_core::RpcMempoolChangeKind::Evicted => Self::Evicted,
+        kaspa_rpc_core::RpcMempoolChangeKind::Removed => Self::Removed,
+    }
+});
+
+from!(item: &kaspa_rpc_core::MempoolChangedNotification, MempoolChangedNotificationMessage, {
+    Self {
+        transaction_id: item.transaction_id.to_string(),
+        kind: RpcMempoolChangeKind::from(item.kind) as i32,
+        addresses: item.addresses.iter().map(|x| x.into()).collect(),
+    }
+});
+
 from!(item: Command, RpcNotifyCommand, {
     match item {
         Command::Start => RpcNotifyCommand::NotifyStart,
@@ -117,6 +139,7 @@ try_from!(item: &Payload, kaspa_rpc_core::Notification, {
         Payload::PruningPointUtxoSetOverrideNotification(ref notification) => {
             Notification::PruningPointUtxoSetOverride(notification.try_into()?)
         }
+        Payload::MempoolChangedNotification(ref notification) => Notification::MempoolChanged(notification.try_into()?),
         _ => Err(RpcError::UnsupportedFeature)?,
     }
 });
@@ -171,6 +194,25 @@ try_from!(item: &VirtualDaaScoreChangedNotificationMessage, kaspa_rpc_core::Virt
 
 try_from!(&PruningPointUtxoSetOverrideNotificationMessage, kaspa_rpc_core::PruningPointUtxoSetOverrideNotification);
 
+from!(item: RpcMempoolChangeKind, kaspa_rpc_core::RpcMempoolChangeKind, {
+    match item {
+        RpcMempoolChangeKind::Added => Self::Added,
+        RpcMempoolChangeKind::Accepted => Self::Accepted,
+        RpcMempoolChangeKind::Replaced => Self::Replaced,
+        RpcMempoolChangeKind::Expired => Self::Expired,
+        RpcMempoolChangeKind::Evicted => Self::Evicted,
+        RpcMempoolChangeKind::Removed => Self::Removed,
+    }
+});
+
+try_from!(item: &MempoolChangedNotificationMessage, kaspa_rpc_core::MempoolChangedNotification, {
+    Self {
+        transaction_id: RpcHash::from_str(&item.transaction_id)?,
+        kind: RpcMempoolChangeKind::try_from(item.kind).map_err(|_| RpcError::PrimitiveToEnumConversionError)?.into(),
+        addresses: Arc::new(item.addresses.iter().map(|x| x.as_str().try_into()).collect::<Result<Vec<_>, _>>()?),
+    }
+});
+
 from!(item: RpcNotifyCommand, Command, {
     match item {
         RpcNotifyCommand::NotifyStart => Command::Start,
@@ -36,6 +36,7 @@ from!(item: &kaspa_rpc_core::RpcTransactionOutput, protowire::RpcTransactionOutp
         amount: item.value,
         script_public_key: Some((&item.script_public_key).into()),
         verbose_data: item.verbose_data.as_ref().map(|x| x.into()),
+        script_public_key_asm: item.script_public_key_asm.clone().unwrap_or_default(),
     }
 });
 
@@ -82,6 +83,14 @@ from!(item: &kaspa_rpc_core::RpcAcceptedTransactionIds, protowire::RpcAcceptedTr
     }
 });
 
+from!(item: &kaspa_rpc_core::RpcTransactionAcceptance, protowire::RpcTransactionAcceptance, {
+    Self {
+        transaction_id: item.transaction_id.to_string(),
+        containing_block_hash: item.containing_block_hash.to_string(),
+        accepting_block_hash: item.accepting_block_hash.to_string(),
+    }
+});
+
 from!(item: &kaspa_rpc_core::RpcUtxosByAddressesEntry, protowire::RpcUtxosByAddressesEntry, {
     Self {
         address: item.address.as_ref().map_or("".to_string(), |x| x.into()),
@@ -139,6 +148,7 @@ try_from!(item: &protowire::RpcTransactionOutput, kaspa_rpc_core::RpcTransaction
             .ok_or_else(|| RpcError::MissingRpcFieldError("RpcTransactionOutput".to_string(), "script_public_key".to_string()))?
             .try_into()?,
         verbose_data: item.verbose_data.as_ref().map(kaspa_rpc_core::RpcTransactionOutputVerboseData::try_from).transpose()?,
+        script_public_key_asm: (!item.script_public_key_asm.is_empty()).then(|| item.script_public_key_asm.clone()),
     }
 });
 
@@ -189,6 +199,14 @@ try_from!(item: &protowire::RpcAcceptedTransactionIds, kaspa_rpc_core::RpcAccept
     }
 });
 
+try_from!(item: &protowire::RpcTransactionAcceptance, kaspa_rpc_core::RpcTransactionAcceptance, {
+    Self {
+        transaction_id: RpcHash::from_str(&item.transaction_id)?,
+        containing_block_hash: RpcHash::from_str(&item.containing_block_hash)?,
+        accepting_block_hash: RpcHash::from_str(&item.accepting_block_hash)?,
+    }
+});
+
 try_from!(item: &protowire::RpcUtxosByAddressesEntry, kaspa_rpc_core::RpcUtxosByAddressesEntry, {
     let address = if item.address.is_empty() { None } else { Some(item.address.as_str().try_into()?) };
     Self {
@@ -1,10 +1,11 @@
 use kaspa_notify::{scope::Scope, subscription::Command};
+use kaspa_rpc_core::api::limits::{addresses_cost, RpcRequestCost};
 
 use crate::protowire::{
     kaspad_request, kaspad_response, KaspadRequest, KaspadResponse, NotifyBlockAddedRequestMessage,
-    NotifyFinalityConflictRequestMessage, NotifyNewBlockTemplateRequestMessage, NotifyPruningPointUtxoSetOverrideRequestMessage,
-    NotifySinkBlueScoreChangedRequestMessage, NotifyUtxosChangedRequestMessage, NotifyVirtualChainChangedRequestMessage,
-    NotifyVirtualDaaScoreChangedRequestMessage,
+    NotifyFinalityConflictRequestMessage, NotifyMempoolChangedRequestMessage, NotifyNewBlockTemplateRequestMessage,
+    NotifyPruningPointUtxoSetOverrideRequestMessage, NotifySinkBlueScoreChangedRequestMessage, NotifyUtxosChangedRequestMessage,
+    NotifyVirtualChainChangedRequestMessage, NotifyVirtualDaaScoreChangedRequestMessage,
 };
 
 impl KaspadRequest {
@@ -17,6 +18,17 @@ impl KaspadRequest {
     }
 }
 
+impl RpcRequestCost for KaspadRequest {
+    fn cost(&self) -> u32 {
+        match self.payload {
+            Some(kaspad_request::Payload::GetUtxosByAddressesRequest(ref request)) => addresses_cost(request.addresses.len()),
+            Some(kaspad_request::Payload::GetBalancesByAddressesRequest(ref request)) => addresses_cost(request.addresses.len()),
+            Some(kaspad_request::Payload::GetMempoolEntriesByAddressesRequest(ref request)) => addresses_cost(request.addresses.len()),
+            _ => 1,
+        }
+    }
+}
+
 impl kaspad_request::Payload {
     pub fn from_notification_type(scope: &Scope, command: Command) -> Self {
         match scope {
@@ -64,6 +76,12 @@ impl kaspad_request::Payload {
                     command: command.into(),
                 })
             }
+            Scope::MempoolChanged(ref scope) => {
+                kaspad_request::Payload::NotifyMempoolChangedRequest(NotifyMempoolChangedRequestMessage {
+                    addresses: scope.addresses.iter().map(|x| x.into()).collect::<Vec<String>>(),
+                    command: command.into(),
+                })
+            }
         }
     }
 
@@ -79,6 +97,7 @@ impl kaspad_request::Payload {
                 | Payload::NotifyVirtualDaaScoreChangedRequest(_)
                 | Payload::NotifyPruningPointUtxoSetOverrideRequest(_)
                 | Payload::NotifyNewBlockTemplateRequest(_)
+                | Payload::NotifyMempoolChangedRequest(_)
                 | Payload::StopNotifyingUtxosChangedRequest(_)
                 | Payload::StopNotifyingPruningPointUtxoSetOverrideRequest(_)
         )
@@ -108,6 +127,7 @@ impl kaspad_response::Payload {
             Payload::VirtualDaaScoreChangedNotification(_) => true,
             Payload::PruningPointUtxoSetOverrideNotification(_) => true,
             Payload::NewBlockTemplateNotification(_) => true,
+            Payload::MempoolChangedNotification(_) => true,
             _ => false,
         }
     }
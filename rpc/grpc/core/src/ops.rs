@@ -1,5 +1,5 @@
 use crate::protowire::{kaspad_request::Payload as RequestPayload, kaspad_response::Payload as ResponsePayload, *};
-use kaspa_rpc_core::RpcError;
+use kaspa_rpc_core::{api::ops::RpcApiOps, RpcError};
 use workflow_core::enums::Describe;
 
 macro_rules! payload_type_enum {
@@ -87,6 +87,12 @@ pub enum KaspadPayloadOps {
     GetFeeEstimate,
     GetFeeEstimateExperimental,
     GetCurrentBlockColor,
+    GetTransaction,
+    GetTransactionAcceptance,
+    GetAddressHistory,
+    GetMempoolPolicy,
+    SetMempoolPolicy,
+    Backup,
 
     // Subscription commands for starting/stopping notifications
     NotifyBlockAdded,
@@ -97,6 +103,7 @@ pub enum KaspadPayloadOps {
     NotifyPruningPointUtxoSetOverride,
     NotifyVirtualDaaScoreChanged,
     NotifyVirtualChainChanged,
+    NotifyMempoolChanged,
 
     // Legacy stop subscription commands
     StopNotifyingUtxosChanged,
@@ -107,3 +114,14 @@ pub enum KaspadPayloadOps {
     // The conversion from a notification ResponsePayload into KaspadPayloadOps fails.
 }
 }
+
+impl From<KaspadPayloadOps> for RpcApiOps {
+    fn from(item: KaspadPayloadOps) -> Self {
+        match item {
+            // Legacy stop commands map to their subscription command
+            KaspadPayloadOps::StopNotifyingUtxosChanged => RpcApiOps::NotifyUtxosChanged,
+            KaspadPayloadOps::StopNotifyingPruningPointUtxoSetOverride => RpcApiOps::NotifyPruningPointUtxoSetOverride,
+            _ => RpcApiOps::from_str(item.as_str()).expect("every gRPC op has a matching RPC op"),
+        }
+    }
+}
@@ -1,7 +1,11 @@
 use crate::{connection_handler::ConnectionHandler, manager::Manager};
 use kaspa_core::debug;
 use kaspa_notify::{notifier::Notifier, subscription::context::SubscriptionContext};
-use kaspa_rpc_core::{api::rpc::DynRpcService, notify::connection::ChannelConnection, Notification, RpcResult};
+use kaspa_rpc_core::{
+    api::{auth::RpcAuthenticator, limits::RpcRateLimiter, rpc::DynRpcService, tls::RpcServerTlsConfig},
+    notify::connection::ChannelConnection,
+    Notification, RpcResult,
+};
 use kaspa_utils::networking::NetAddress;
 use kaspa_utils_tower::counters::TowerConnectionCounters;
 use std::{ops::Deref, sync::Arc};
@@ -31,6 +35,7 @@ impl Adaptor {
         Self { _server_termination: server_termination, connection_handler, manager, serve_address }
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn server(
         serve_address: NetAddress,
         network_bps: u64,
@@ -40,6 +45,9 @@ impl Adaptor {
         subscription_context: SubscriptionContext,
         broadcasters: usize,
         counters: Arc<TowerConnectionCounters>,
+        authenticator: Option<Arc<RpcAuthenticator>>,
+        rate_limiter: Option<Arc<RpcRateLimiter>>,
+        tls: Option<RpcServerTlsConfig>,
     ) -> Arc<Self> {
         let (manager_sender, manager_receiver) = mpsc_channel(Self::manager_channel_size());
         let connection_handler = ConnectionHandler::new(
@@ -50,8 +58,10 @@ impl Adaptor {
             subscription_context,
             broadcasters,
             counters,
+            authenticator,
+            rate_limiter,
         );
-        let server_termination = connection_handler.serve(serve_address);
+        let server_termination = connection_handler.serve(serve_address, tls);
         let adaptor = Arc::new(Adaptor::new(Some(server_termination), connection_handler, manager, serve_address));
         adaptor.manager.clone().start_event_loop(manager_receiver);
         adaptor.start();
@@ -21,7 +21,13 @@ use kaspa_notify::{
     listener::{ListenerId, ListenerLifespan},
     notifier::Notifier,
 };
-use kaspa_rpc_core::Notification;
+use kaspa_rpc_core::{
+    api::{
+        auth::RpcPermissionsRef,
+        limits::{RpcConnectionRateLimiter, RpcRequestCost},
+    },
+    Notification,
+};
 use parking_lot::Mutex;
 use std::{
     collections::{hash_map::Entry, HashMap},
@@ -79,6 +85,12 @@ struct Inner {
     /// The server RPC core service and notifier
     server_context: ServerContext,
 
+    /// The methods this client is allowed to call, or `None` if authentication is disabled
+    permissions: Option<RpcPermissionsRef>,
+
+    /// The request budgets of this client, or `None` if requests are not limited
+    rate_limiter: Option<RpcConnectionRateLimiter>,
+
     /// Used for managing connection mutable state
     mutable_state: Mutex<InnerMutableState>,
 
@@ -175,7 +187,20 @@ impl Router {
             debug!("GRPC, Route to handler got empty payload, client: {}", connection);
             return Err(GrpcServerError::InvalidRequestPayload);
         }
-        let rpc_op = request.payload.as_ref().unwrap().into();
+        let rpc_op: KaspadPayloadOps = request.payload.as_ref().unwrap().into();
+        let authorized = match connection.permissions() {
+            Some(permissions) => permissions.authorize(rpc_op.into()),
+            None => Ok(()),
+        };
+        let throttled = authorized.and_then(|_| match connection.rate_limiter() {
+            Some(rate_limiter) => rate_limiter.check(rpc_op.into(), request.cost()),
+            None => Ok(()),
+        });
+        if let Err(err) = throttled {
+            debug!("GRPC, Route to handler refused {:?}: {}, client: {}", rpc_op, err, connection);
+            connection.enqueue(KaspadResponse { id: request.id, payload: Some(rpc_op.to_error_response(err)) }).await?;
+            return Ok(());
+        }
         let route = self.get_or_subscribe(connection, rpc_op);
         match route.policy {
             RoutingPolicy::Enqueue => match route.send(request).await {
@@ -220,6 +245,8 @@ impl Connection {
         manager_sender: MpscSender<ManagerEvent>,
         mut incoming_stream: Streaming<KaspadRequest>,
         outgoing_route: GrpcSender,
+        permissions: Option<RpcPermissionsRef>,
+        rate_limiter: Option<RpcConnectionRateLimiter>,
     ) -> Self {
         let (shutdown_sender, mut shutdown_receiver) = oneshot_channel();
         let mut router = Router::new(server_context.clone(), interface.clone());
@@ -230,6 +257,8 @@ impl Connection {
                 outgoing_route,
                 manager_sender,
                 server_context,
+                permissions,
+                rate_limiter,
                 mutable_state: Mutex::new(InnerMutableState::new(Some(shutdown_sender))),
                 is_closed: AtomicBool::new(false),
             }),
@@ -314,6 +343,14 @@ impl Connection {
         self.inner.connection_id
     }
 
+    pub fn permissions(&self) -> Option<&RpcPermissionsRef> {
+        self.inner.permissions.as_ref()
+    }
+
+    pub fn rate_limiter(&self) -> Option<&RpcConnectionRateLimiter> {
+        self.inner.rate_limiter.as_ref()
+    }
+
     pub fn notifier(&self) -> Arc<GrpcNotifier> {
         self.inner.server_context.notifier.clone()
     }
@@ -22,7 +22,12 @@ use kaspa_notify::{
     subscription::{context::SubscriptionContext, MutationPolicies, UtxosChangedMutationPolicy},
 };
 use kaspa_rpc_core::{
-    api::rpc::DynRpcService,
+    api::{
+        auth::{RpcAuthenticator, RpcPermissionsRef, AUTHORIZATION_METADATA_KEY},
+        limits::RpcRateLimiter,
+        rpc::DynRpcService,
+        tls::RpcServerTlsConfig,
+    },
     notify::{channel::NotificationChannel, connection::ChannelConnection},
     Notification, RpcResult,
 };
@@ -46,7 +51,11 @@ use tokio::{
     time::timeout,
 };
 use tokio_stream::{wrappers::ReceiverStream, StreamExt};
-use tonic::{codec::CompressionEncoding, transport::Server as TonicServer, Request, Response};
+use tonic::{
+    codec::CompressionEncoding,
+    transport::{Certificate, Identity, Server as TonicServer, ServerTlsConfig},
+    Request, Response,
+};
 
 #[derive(Clone)]
 pub struct ServerContext {
@@ -76,6 +85,8 @@ pub struct ConnectionHandler {
     interface: Arc<Interface>,
     running: Arc<AtomicBool>,
     counters: Arc<TowerConnectionCounters>,
+    authenticator: Option<Arc<RpcAuthenticator>>,
+    rate_limiter: Option<Arc<RpcRateLimiter>>,
 }
 
 const GRPC_SERVER: &str = "grpc-server";
@@ -89,6 +100,8 @@ impl ConnectionHandler {
         subscription_context: SubscriptionContext,
         broadcasters: usize,
         counters: Arc<TowerConnectionCounters>,
+        authenticator: Option<Arc<RpcAuthenticator>>,
+        rate_limiter: Option<Arc<RpcRateLimiter>>,
     ) -> Self {
         // This notifier UTXOs subscription granularity to rpc-core notifier
         let policies = MutationPolicies::new(UtxosChangedMutationPolicy::AddressSet);
@@ -118,11 +131,11 @@ impl ConnectionHandler {
         let interface = Arc::new(Factory::new_interface(server_context.clone(), network_bps));
         let running = Default::default();
 
-        Self { manager_sender, server_context, interface, running, counters }
+        Self { manager_sender, server_context, interface, running, counters, authenticator, rate_limiter }
     }
 
     /// Launches a gRPC server listener loop
-    pub(crate) fn serve(&self, serve_address: NetAddress) -> OneshotSender<()> {
+    pub(crate) fn serve(&self, serve_address: NetAddress, tls: Option<RpcServerTlsConfig>) -> OneshotSender<()> {
         let (termination_sender, termination_receiver) = oneshot_channel::<()>();
         let (signal_sender, signal_receiver) = oneshot_channel::<()>();
         let connection_handler = self.clone();
@@ -141,7 +154,15 @@ impl ConnectionHandler {
             // TODO: check whether we should set tcp_keepalive
             // const GRPC_KEEP_ALIVE_PING_INTERVAL: Duration = Duration::from_secs(5);
             // const GRPC_KEEP_ALIVE_PING_TIMEOUT: Duration = Duration::from_secs(120);
-            let serve_result = TonicServer::builder()
+            let mut builder = TonicServer::builder();
+            if let Some(tls) = tls {
+                info!("GRPC Server serving TLS on: {}{}", serve_address, if tls.client_ca.is_some() { " (mutual)" } else { "" });
+                builder = match builder.tls_config(Self::tls_config(tls)) {
+                    Ok(builder) => builder,
+                    Err(err) => panic!("GRPC Server {serve_address} TLS configuration error: {err:?}"),
+                };
+            }
+            let serve_result = builder
                 // .http2_keepalive_interval(Some(GRPC_KEEP_ALIVE_PING_INTERVAL))
                 // .http2_keepalive_timeout(Some(GRPC_KEEP_ALIVE_PING_TIMEOUT))
                 .layer(MapRequestBodyLayer::new(move |body| CountBytesBody::new(body, bytes_rx.clone()).boxed_unsync()))
@@ -172,6 +193,14 @@ impl ConnectionHandler {
         termination_sender
     }
 
+    fn tls_config(tls: RpcServerTlsConfig) -> ServerTlsConfig {
+        let config = ServerTlsConfig::new().identity(Identity::from_pem(tls.identity.certificate, tls.identity.private_key));
+        match tls.client_ca {
+            Some(client_ca) => config.client_ca_root(Certificate::from_pem(client_ca)),
+            None => config,
+        }
+    }
+
     #[inline(always)]
     fn server_context(&self) -> ServerContext {
         self.server_context.clone()
@@ -192,6 +221,25 @@ impl ConnectionHandler {
         self.server_context.notifier.clone()
     }
 
+    /// Resolves the permissions of the client opening `request` from its `authorization` metadata.
+    ///
+    /// Returns `None` if authentication is disabled.
+    fn authenticate<T>(&self, request: &Request<T>) -> Result<Option<RpcPermissionsRef>, tonic::Status> {
+        let Some(ref authenticator) = self.authenticator else {
+            return Ok(None);
+        };
+        let authorization = request
+            .metadata()
+            .get(AUTHORIZATION_METADATA_KEY)
+            .map(|value| value.to_str())
+            .transpose()
+            .map_err(|_| tonic::Status::new(tonic::Code::Unauthenticated, "Malformed authorization metadata"))?;
+        authenticator
+            .authenticate(authorization)
+            .map(Some)
+            .map_err(|err| tonic::Status::new(tonic::Code::Unauthenticated, err.to_string()))
+    }
+
     pub fn start(&self) {
         debug!("GRPC, Starting the connection handler");
 
@@ -254,6 +302,10 @@ impl Rpc for ConnectionHandler {
 
         debug!("GRPC, Incoming message stream from {:?}", remote_address);
 
+        let permissions = self.authenticate(&request).inspect_err(|status| {
+            warn!("GRPC, refusing incoming message stream from {:?} - {}", remote_address, status.message());
+        })?;
+
         // Build the in/out pipes
         let (outgoing_route, outgoing_receiver) = mpsc_channel(Self::outgoing_route_channel_size());
         let incoming_stream = request.into_inner();
@@ -266,6 +318,8 @@ impl Rpc for ConnectionHandler {
             self.manager_sender(),
             incoming_stream,
             outgoing_route,
+            permissions,
+            self.rate_limiter.as_ref().map(|limiter| limiter.connection(remote_address.ip())),
         );
 
         // Try to get the connection registered into the central Manager
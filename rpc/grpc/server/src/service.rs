@@ -5,6 +5,7 @@ use kaspa_core::{
     task::service::{AsyncService, AsyncServiceFuture},
     trace, warn,
 };
+use kaspa_rpc_core::api::{auth::RpcAuthenticator, limits::RpcRateLimiter, tls::RpcServerTlsConfig};
 use kaspa_rpc_service::service::RpcCoreService;
 use kaspa_utils::{networking::NetAddress, triggers::SingleTrigger};
 use kaspa_utils_tower::counters::TowerConnectionCounters;
@@ -20,6 +21,12 @@ pub struct GrpcService {
     started: SingleTrigger,
     shutdown: SingleTrigger,
     counters: Arc<TowerConnectionCounters>,
+    /// Authenticates clients, or `None` if any client may call any method
+    authenticator: Option<Arc<RpcAuthenticator>>,
+    /// Limits the request rate of clients, or `None` if unlimited
+    rate_limiter: Option<Arc<RpcRateLimiter>>,
+    /// Serves the listener over TLS, or in plain text if `None`
+    tls: Option<RpcServerTlsConfig>,
 }
 
 impl GrpcService {
@@ -32,6 +39,9 @@ impl GrpcService {
         rpc_max_clients: usize,
         broadcasters: usize,
         counters: Arc<TowerConnectionCounters>,
+        authenticator: Option<Arc<RpcAuthenticator>>,
+        rate_limiter: Option<Arc<RpcRateLimiter>>,
+        tls: Option<RpcServerTlsConfig>,
     ) -> Self {
         Self {
             net_address: address,
@@ -42,6 +52,9 @@ impl GrpcService {
             started: Default::default(),
             shutdown: Default::default(),
             counters,
+            authenticator,
+            rate_limiter,
+            tls,
         }
     }
 
@@ -71,6 +84,9 @@ impl AsyncService for GrpcService {
             self.core_service.subscription_context(),
             self.broadcasters,
             self.counters.clone(),
+            self.authenticator.clone(),
+            self.rate_limiter.clone(),
+            self.tls.clone(),
         );
 
         // Signal the server was started
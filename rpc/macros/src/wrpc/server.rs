@@ -53,6 +53,7 @@ impl ToTokens for RpcTable {
                     interface.method(#rpc_api_ops::#handler, method!(|server_ctx: #server_ctx_type, connection_ctx: #connection_ctx_type, request: Serializable<#request_type>| async move {
                         let verbose = server_ctx.verbose();
                         if verbose { workflow_log::log_info!("request: {:?}",request); }
+                        server_ctx.authorize(&connection_ctx, #rpc_api_ops::#handler, kaspa_rpc_core::api::limits::RpcRequestCost::cost(&request.0)).map_err(|e|ServerError::Text(e.to_string()))?;
                         // TODO: RPC-CONNECT
                         let response: #response_type = server_ctx.rpc_service(&connection_ctx).#fn_call(None, request.into_inner()).await
                             .map_err(|e|ServerError::Text(e.to_string()))?;
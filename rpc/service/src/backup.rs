@@ -0,0 +1,119 @@
+//!
+//! Online checkpoints of the node databases, created by the `Backup` RPC.
+//!
+//! A checkpoint is a consistent point-in-time copy of a RocksDB database, whose files are hard-linked
+//! when the target is on the filesystem of the live database. Checkpoints mirror the layout of the
+//! application directory, so a node can be started from a backup with `--appdir=<target_dir>`.
+//! Indexes other than the utxoindex are not backed up, the node rebuilds them on startup.
+//!
+//! Blocks keep being processed while the backup is created, so the utxoindex checkpoint, which is
+//! created after the consensus one, may reflect a later virtual state. A node started from such a
+//! backup finds the utxoindex tips differing from the consensus virtual parents and resyncs the
+//! utxoindex from the consensus UTXO set on startup, as it does after any unclean shutdown.
+//!
+
+use kaspa_consensusmanager::{spawn_blocking, ConsensusManager};
+use kaspa_core::{info, warn};
+use kaspa_rpc_core::{RpcDatabaseBackup, RpcError, RpcResult};
+use kaspa_utxoindex::api::UtxoIndexProxy;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Instant,
+};
+
+/// The locations of the node databases relative to the application directory
+#[derive(Clone, Debug)]
+pub struct DatabaseLayout {
+    /// The directory holding the consensus databases
+    pub consensus_db_dir: PathBuf,
+    pub meta_db_dir: PathBuf,
+    pub utxoindex_db_dir: PathBuf,
+}
+
+/// Creates a backup of the node databases in `target_dir`, removing it again if any checkpoint fails
+pub(crate) async fn create_backup(
+    layout: &DatabaseLayout,
+    consensus_manager: Arc<ConsensusManager>,
+    utxoindex: Option<UtxoIndexProxy>,
+    target_dir: PathBuf,
+) -> RpcResult<Vec<RpcDatabaseBackup>> {
+    if target_dir.as_os_str().is_empty() {
+        return Err(RpcError::General("the backup target directory is empty".to_string()));
+    }
+    if fs::read_dir(&target_dir).is_ok_and(|mut entries| entries.next().is_some()) {
+        return Err(RpcError::General(format!("the backup target directory {} is not empty", target_dir.display())));
+    }
+
+    info!("Creating a backup of the node databases in {}", target_dir.display());
+    match create_checkpoints(layout, consensus_manager, utxoindex, &target_dir).await {
+        Ok(databases) => {
+            info!("Backup of the node databases created in {}", target_dir.display());
+            Ok(databases)
+        }
+        Err(err) => {
+            warn!("Creating a backup in {} failed: {}", target_dir.display(), err);
+            // The directory did not exist or was empty, so it holds nothing but the partial backup
+            if let Err(err) = fs::remove_dir_all(&target_dir) {
+                warn!("Removing the partial backup in {} failed: {}", target_dir.display(), err);
+            }
+            Err(err)
+        }
+    }
+}
+
+async fn create_checkpoints(
+    layout: &DatabaseLayout,
+    consensus_manager: Arc<ConsensusManager>,
+    utxoindex: Option<UtxoIndexProxy>,
+    target_dir: &Path,
+) -> RpcResult<Vec<RpcDatabaseBackup>> {
+    let mut databases = Vec::with_capacity(3);
+    let stages = if utxoindex.is_some() { 2 } else { 1 };
+
+    info!("Backup (1/{stages}): checkpointing the consensus databases");
+    let start = Instant::now();
+    let consensus_db_dir = target_dir.join(&layout.consensus_db_dir);
+    let meta_db_dir = target_dir.join(&layout.meta_db_dir);
+    create_parent_dir("meta", &meta_db_dir)?;
+    fs::create_dir_all(&consensus_db_dir).map_err(|err| backup_error("consensus", err))?;
+    {
+        let (consensus_db_dir, meta_db_dir) = (consensus_db_dir.clone(), meta_db_dir.clone());
+        spawn_blocking(move || consensus_manager.create_checkpoint(&consensus_db_dir, &meta_db_dir))
+            .await
+            .unwrap()
+            .map_err(|err| backup_error("consensus", err))?;
+    }
+    // Both databases are checkpointed in a single stage, see `ConsensusManager::create_checkpoint`
+    let duration_millis = start.elapsed().as_millis() as u64;
+    info!("Backup (1/{stages}): checkpointed the consensus databases in {duration_millis} ms");
+    databases.push(RpcDatabaseBackup::new("consensus".to_string(), consensus_db_dir.display().to_string(), duration_millis));
+    databases.push(RpcDatabaseBackup::new("meta".to_string(), meta_db_dir.display().to_string(), duration_millis));
+
+    if let Some(utxoindex) = utxoindex {
+        // Not consistent with the consensus checkpoint, the node resyncs the utxoindex on startup when they differ
+        info!("Backup (2/{stages}): checkpointing the utxoindex database");
+        let start = Instant::now();
+        let utxoindex_db_dir = target_dir.join(&layout.utxoindex_db_dir);
+        create_parent_dir("utxoindex", &utxoindex_db_dir)?;
+        utxoindex.create_checkpoint(utxoindex_db_dir.clone()).await.map_err(|err| backup_error("utxoindex", err))?;
+        let duration_millis = start.elapsed().as_millis() as u64;
+        info!("Backup (2/{stages}): checkpointed the utxoindex database in {duration_millis} ms");
+        databases.push(RpcDatabaseBackup::new("utxoindex".to_string(), utxoindex_db_dir.display().to_string(), duration_millis));
+    }
+
+    Ok(databases)
+}
+
+/// Creates the parent directory of a checkpoint, the checkpoint directory itself must not exist
+fn create_parent_dir(name: &str, checkpoint_dir: &Path) -> RpcResult<()> {
+    match checkpoint_dir.parent() {
+        Some(parent) => fs::create_dir_all(parent).map_err(|err| backup_error(name, err)),
+        None => Ok(()),
+    }
+}
+
+fn backup_error(name: &str, err: impl ToString) -> RpcError {
+    RpcError::General(format!("backup of the {name} database failed: {}", err.to_string()))
+}
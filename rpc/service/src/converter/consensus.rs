@@ -18,7 +18,7 @@ use kaspa_rpc_core::{
     RpcMempoolEntryByAddress, RpcResult, RpcTransaction, RpcTransactionInput, RpcTransactionOutput, RpcTransactionOutputVerboseData,
     RpcTransactionVerboseData,
 };
-use kaspa_txscript::{extract_script_pub_key_address, script_class::ScriptClass};
+use kaspa_txscript::{asm::disassemble, extract_script_pub_key_address, script_class::ScriptClass};
 use std::{collections::HashMap, fmt::Debug, sync::Arc};
 
 /// Conversion of consensus_core to rpc_core structures
@@ -53,23 +53,8 @@ impl ConsensusConverter {
         include_transactions: bool,
         include_transaction_verbose_data: bool,
     ) -> RpcResult<RpcBlock> {
-        let hash = block.hash();
-        let ghostdag_data = consensus.async_get_ghostdag_data(hash).await?;
-        let block_status = consensus.async_get_block_status(hash).await.unwrap();
-        let children = consensus.async_get_block_children(hash).await.unwrap_or_default();
-        let is_chain_block = consensus.async_is_chain_block(hash).await?;
-        let verbose_data = Some(RpcBlockVerboseData {
-            hash,
-            difficulty: self.get_difficulty_ratio(block.header.bits),
-            selected_parent_hash: ghostdag_data.selected_parent,
-            transaction_ids: block.transactions.iter().map(|x| x.id()).collect(),
-            is_header_only: block_status.is_header_only(),
-            blue_score: ghostdag_data.blue_score,
-            children_hashes: children,
-            merge_set_blues_hashes: ghostdag_data.mergeset_blues,
-            merge_set_reds_hashes: ghostdag_data.mergeset_reds,
-            is_chain_block,
-        });
+        let verbose_data =
+            Some(self.get_block_verbose_data(consensus, &block.header, block.transactions.iter().map(|x| x.id()).collect()).await?);
 
         let transactions = if include_transactions {
             block
@@ -84,6 +69,33 @@ impl ConsensusConverter {
         Ok(RpcBlock { header: block.header.as_ref().into(), transactions, verbose_data })
     }
 
+    /// Builds the [`RpcBlockVerboseData`] of the block with the given header. The transaction ids are left empty
+    /// when only the header is being returned.
+    pub async fn get_block_verbose_data(
+        &self,
+        consensus: &ConsensusProxy,
+        header: &Header,
+        transaction_ids: Vec<TransactionId>,
+    ) -> RpcResult<RpcBlockVerboseData> {
+        let hash = header.hash;
+        let ghostdag_data = consensus.async_get_ghostdag_data(hash).await?;
+        let block_status = consensus.async_get_block_status(hash).await.unwrap();
+        let children = consensus.async_get_block_children(hash).await.unwrap_or_default();
+        let is_chain_block = consensus.async_is_chain_block(hash).await?;
+        Ok(RpcBlockVerboseData {
+            hash,
+            difficulty: self.get_difficulty_ratio(header.bits),
+            selected_parent_hash: ghostdag_data.selected_parent,
+            transaction_ids,
+            is_header_only: block_status.is_header_only(),
+            blue_score: ghostdag_data.blue_score,
+            children_hashes: children,
+            merge_set_blues_hashes: ghostdag_data.mergeset_blues,
+            merge_set_reds_hashes: ghostdag_data.mergeset_reds,
+            is_chain_block,
+        })
+    }
+
     pub fn get_mempool_entry(&self, consensus: &ConsensusProxy, transaction: &MutableTransaction) -> RpcMempoolEntry {
         let is_orphan = !transaction.is_fully_populated();
         let rpc_transaction = self.get_transaction(consensus, &transaction.tx, None, true);
@@ -152,10 +164,20 @@ impl ConsensusConverter {
 
     fn get_transaction_output(&self, output: &TransactionOutput) -> RpcTransactionOutput {
         let script_public_key_type = ScriptClass::from_script(&output.script_public_key);
-        let address = extract_script_pub_key_address(&output.script_public_key, self.config.prefix()).ok();
-        let verbose_data =
-            address.map(|address| RpcTransactionOutputVerboseData { script_public_key_type, script_public_key_address: address });
-        RpcTransactionOutput { value: output.value, script_public_key: output.script_public_key.clone(), verbose_data }
+        let (verbose_data, script_public_key_asm) =
+            match extract_script_pub_key_address(&output.script_public_key, self.config.prefix()) {
+                Ok(script_public_key_address) => {
+                    (Some(RpcTransactionOutputVerboseData { script_public_key_type, script_public_key_address }), None)
+                }
+                // Non-standard scripts have no address, so their disassembly is reported instead
+                Err(_) => (None, Some(disassemble(output.script_public_key.script()))),
+            };
+        RpcTransactionOutput {
+            value: output.value,
+            script_public_key: output.script_public_key.clone(),
+            verbose_data,
+            script_public_key_asm,
+        }
     }
 
     pub async fn get_virtual_chain_accepted_transaction_ids(
@@ -203,3 +225,36 @@ impl Debug for ConsensusConverter {
         f.debug_struct("ConsensusConverter").field("consensus_manager", &"").field("config", &self.config).finish()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kaspa_addresses::{Prefix, Version};
+    use kaspa_consensus::consensus::test_consensus::TestConsensus;
+    use kaspa_consensus_core::{
+        config::{params::DEVNET_PARAMS, ConfigBuilder},
+        tx::ScriptPublicKey,
+    };
+    use kaspa_txscript::{opcodes::codes::OpTrue, pay_to_address_script};
+
+    #[test]
+    fn test_transaction_output() {
+        let config = Arc::new(ConfigBuilder::new(DEVNET_PARAMS).skip_proof_of_work().build());
+        let tc = TestConsensus::new(&config);
+        let converter = ConsensusConverter::new(Arc::new(ConsensusManager::from_consensus(tc.consensus_clone())), config);
+
+        // A standard output has an address, which is reported in the verbose data
+        let address = Address::new(Prefix::Devnet, Version::PubKey, &[0x11; 32]);
+        let output = converter.get_transaction_output(&TransactionOutput::new(1, pay_to_address_script(&address)));
+        let verbose_data = output.verbose_data.unwrap();
+        assert_eq!(verbose_data.script_public_key_type, ScriptClass::PubKey);
+        assert_eq!(verbose_data.script_public_key_address, address);
+        assert!(output.script_public_key_asm.is_none());
+
+        // A non-standard output has no address, so the disassembled script is reported instead
+        let script_public_key = ScriptPublicKey::from_vec(0, vec![OpTrue]);
+        let output = converter.get_transaction_output(&TransactionOutput::new(1, script_public_key));
+        assert!(output.verbose_data.is_none());
+        assert_eq!(output.script_public_key_asm.as_deref(), Some("OP_TRUE"));
+    }
+}
@@ -0,0 +1,36 @@
+use kaspa_mining::mempool::config::{MempoolPolicy, MempoolPolicyUpdate};
+use kaspa_rpc_core::{RpcMempoolPolicy, SetMempoolPolicyRequest};
+
+pub trait MempoolPolicyConverter {
+    fn into_rpc(self) -> RpcMempoolPolicy;
+}
+
+impl MempoolPolicyConverter for MempoolPolicy {
+    fn into_rpc(self) -> RpcMempoolPolicy {
+        RpcMempoolPolicy {
+            maximum_transaction_count: self.maximum_transaction_count as u64,
+            mempool_size_limit: self.mempool_size_limit as u64,
+            maximum_orphan_transaction_mass: self.maximum_orphan_transaction_mass,
+            maximum_orphan_transaction_count: self.maximum_orphan_transaction_count,
+            accept_non_standard: self.accept_non_standard,
+            minimum_relay_transaction_fee: self.minimum_relay_transaction_fee,
+        }
+    }
+}
+
+pub trait MempoolPolicyUpdateConverter {
+    fn into_policy_update(self) -> MempoolPolicyUpdate;
+}
+
+impl MempoolPolicyUpdateConverter for SetMempoolPolicyRequest {
+    fn into_policy_update(self) -> MempoolPolicyUpdate {
+        MempoolPolicyUpdate {
+            maximum_transaction_count: self.maximum_transaction_count.map(|x| x as usize),
+            mempool_size_limit: self.mempool_size_limit.map(|x| x as usize),
+            maximum_orphan_transaction_mass: self.maximum_orphan_transaction_mass,
+            maximum_orphan_transaction_count: self.maximum_orphan_transaction_count,
+            accept_non_standard: self.accept_non_standard,
+            minimum_relay_transaction_fee: self.minimum_relay_transaction_fee,
+        }
+    }
+}
@@ -1,5 +1,6 @@
 use std::sync::Arc;
 
+use kaspa_connectionmanager::ConnectionManager;
 use kaspa_p2p_flows::flow_context::FlowContext;
 use kaspa_p2p_lib::{Peer, PeerKey};
 use kaspa_rpc_core::RpcPeerInfo;
@@ -13,8 +14,20 @@ impl ProtocolConverter {
         Self { flow_context }
     }
 
-    fn get_peer_info(&self, peer: &Peer, ibd_peer_key: &Option<PeerKey>) -> RpcPeerInfo {
+    fn get_peer_info(
+        &self,
+        peer: &Peer,
+        ibd_peer_key: &Option<PeerKey>,
+        connection_manager: Option<&ConnectionManager>,
+    ) -> RpcPeerInfo {
         let properties = peer.properties();
+        // Peers dialed by host name share the ip of the proxy, which is not scored
+        let misbehavior_score = match connection_manager {
+            Some(connection_manager) if peer.named_address().is_none() => {
+                connection_manager.misbehavior_score(peer.net_address().ip())
+            }
+            _ => 0,
+        };
         RpcPeerInfo {
             id: peer.identity(),
             address: peer.net_address().into(),
@@ -25,11 +38,14 @@ impl ProtocolConverter {
             user_agent: properties.user_agent.clone(),
             advertised_protocol_version: properties.advertised_protocol_version,
             time_connected: peer.time_connected(),
+            is_encrypted: peer.is_encrypted(),
+            misbehavior_score,
         }
     }
 
     pub fn get_peers_info(&self, peers: &[Peer]) -> Vec<RpcPeerInfo> {
         let ibd_peer_key = self.flow_context.ibd_peer_key();
-        peers.iter().map(|x| self.get_peer_info(x, &ibd_peer_key)).collect()
+        let connection_manager = self.flow_context.connection_manager();
+        peers.iter().map(|x| self.get_peer_info(x, &ibd_peer_key, connection_manager.as_deref())).collect()
     }
 }
@@ -0,0 +1,172 @@
+//!
+//! Paging of the headers served by `GetHeaders`.
+//!
+//! Pages hold whole mergesets, so that they always end on a chain block from which the following page is
+//! requested. Since the start hash is excluded from its page, consecutive pages never share a header.
+//!
+
+use kaspa_consensus_core::{blockhash::BlockHashExtensions, header::Header};
+use kaspa_consensusmanager::ConsensusProxy;
+use kaspa_hashes::Hash;
+use kaspa_rpc_core::RpcResult;
+use std::{iter::once, sync::Arc};
+
+/// Collects up to `limit` headers following `start_hash` towards the sink, in consensus order. That is, up the
+/// selected chain, each chain block is preceded by its mergeset sorted by ascending blue work, less its selected
+/// parent which precedes them. A mergeset larger than `limit` is returned whole if it comes first, so that paging
+/// always progresses.
+pub async fn ascending_headers(
+    session: &ConsensusProxy,
+    start_hash: Hash,
+    limit: usize,
+    mergeset_size_limit: u64,
+) -> RpcResult<Vec<Arc<Header>>> {
+    let sink_hash = session.async_get_sink().await;
+    let mut hashes = Vec::new();
+    let mut low_hash = start_hash;
+    while hashes.len() < limit && low_hash != sink_hash {
+        // max_blocks MUST be >= mergeset_size_limit + 1
+        let remaining = limit - hashes.len();
+        let max_blocks = remaining.max(mergeset_size_limit as usize + 1);
+        // The returned hashes are whole mergesets, each ending with its chain block, up to `high_hash`
+        let (mut block_hashes, high_hash) = session.async_get_hashes_between(low_hash, sink_hash, max_blocks).await?;
+        if high_hash == low_hash {
+            break;
+        }
+        if block_hashes.len() > remaining {
+            // Cut after the last chain block fitting the page, or after the first one if none does and the page is empty
+            let mut end = 0;
+            for (i, &hash) in block_hashes.iter().enumerate() {
+                if i >= remaining && (end > 0 || !hashes.is_empty()) {
+                    break;
+                }
+                if session.async_is_chain_ancestor_of(hash, high_hash).await? {
+                    end = i + 1;
+                }
+            }
+            block_hashes.truncate(end);
+            hashes.extend(block_hashes);
+            break;
+        }
+        hashes.extend(block_hashes);
+        low_hash = high_hash;
+    }
+
+    let mut headers = Vec::with_capacity(hashes.len());
+    for hash in hashes {
+        headers.push(session.async_get_header(hash).await?);
+    }
+    Ok(headers)
+}
+
+/// Collects up to `limit` headers preceding `start_hash` towards the pruning point, in reverse consensus order. That is,
+/// down the selected chain of `start_hash`, the mergeset of each chain block sorted by descending blue work and ending
+/// with its selected parent. A mergeset larger than `limit` is returned whole if it comes first.
+pub async fn descending_headers(session: &ConsensusProxy, start_hash: Hash, limit: usize) -> RpcResult<Vec<Arc<Header>>> {
+    let mut headers = Vec::new();
+    let mut chain_hash = start_hash;
+    'chain: while headers.len() < limit {
+        // The data of blocks below the pruning point is pruned, in which case the traversal ends there
+        let Ok(ghostdag_data) = session.async_get_ghostdag_data(chain_hash).await else { break };
+        if ghostdag_data.selected_parent.is_origin() {
+            break;
+        }
+        let mergeset_size = ghostdag_data.mergeset_blues.len() + ghostdag_data.mergeset_reds.len();
+        if !headers.is_empty() && headers.len() + mergeset_size > limit {
+            break;
+        }
+        let mut mergeset = Vec::with_capacity(mergeset_size);
+        // The selected parent is first in mergeset blues
+        for hash in ghostdag_data.mergeset_blues.iter().skip(1).chain(ghostdag_data.mergeset_reds.iter()).copied() {
+            let Ok(header) = session.async_get_header(hash).await else { break 'chain };
+            mergeset.push(header);
+        }
+        mergeset.sort_by(|a, b| (b.blue_work, b.hash).cmp(&(a.blue_work, a.hash)));
+        let Ok(selected_parent) = session.async_get_header(ghostdag_data.selected_parent).await else { break };
+        headers.extend(mergeset.into_iter().chain(once(selected_parent)));
+        chain_hash = ghostdag_data.selected_parent;
+    }
+    Ok(headers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kaspa_consensus::consensus::test_consensus::TestConsensus;
+    use kaspa_consensus_core::{
+        api::ConsensusApi,
+        config::{params::DEVNET_PARAMS, ConfigBuilder},
+    };
+    use kaspa_consensusmanager::ConsensusManager;
+
+    fn hashes(headers: &[Arc<Header>]) -> Vec<Hash> {
+        headers.iter().map(|header| header.hash).collect()
+    }
+
+    #[tokio::test]
+    async fn test_headers_paging() {
+        let config = ConfigBuilder::new(DEVNET_PARAMS).skip_proof_of_work().build();
+        let tc = Arc::new(TestConsensus::new(&config));
+        let wait_handles = tc.init();
+
+        // Rows of blocks merging the whole previous row, so that all the blocks of a row but one are off the chain.
+        // The wider row makes for a mergeset larger than the smaller limits.
+        let genesis = config.genesis.hash;
+        let mut row = vec![genesis];
+        let mut next_hash = (1..).map(Hash::from_u64_word);
+        for width in [2, 2, 2, 2, 4, 2, 2, 2, 1] {
+            let mut next_row = Vec::with_capacity(width);
+            for _ in 0..width {
+                let hash = next_hash.next().unwrap();
+                tc.add_utxo_valid_block_with_parents(hash, row.clone(), vec![]).await.unwrap();
+                next_row.push(hash);
+            }
+            row = next_row;
+        }
+        let sink = row[0];
+        assert_eq!(tc.get_sink(), sink);
+
+        let consensus_manager = ConsensusManager::from_consensus(tc.consensus_clone());
+        let session = consensus_manager.consensus().session().await;
+        let mergeset_size_limit = config.mergeset_size_limit;
+
+        // All the blocks but genesis, ending with the sink
+        let ascending = hashes(&ascending_headers(&session, genesis, 1000, mergeset_size_limit).await.unwrap());
+        assert_eq!(ascending.len(), 19);
+        assert_eq!(ascending.last(), Some(&sink));
+        // All the blocks but the sink, ending with genesis
+        let descending = hashes(&descending_headers(&session, sink, 1000).await.unwrap());
+        assert_eq!(descending, once(genesis).chain(ascending[..ascending.len() - 1].iter().copied()).rev().collect::<Vec<_>>());
+
+        for limit in 1..=5 {
+            // Following pages are requested from the last header of the previous one, always a chain block
+            let mut pages = vec![];
+            let mut start_hash = genesis;
+            loop {
+                let page = hashes(&ascending_headers(&session, start_hash, limit, mergeset_size_limit).await.unwrap());
+                let Some(&last) = page.last() else { break };
+                assert!(session.async_is_chain_ancestor_of(last, sink).await.unwrap());
+                // Only a mergeset larger than the limit exceeds it
+                assert!(page.len() <= limit.max(4));
+                pages.extend(page);
+                start_hash = last;
+            }
+            assert_eq!(pages, ascending);
+
+            let mut pages = vec![];
+            let mut start_hash = sink;
+            loop {
+                let page = hashes(&descending_headers(&session, start_hash, limit).await.unwrap());
+                let Some(&last) = page.last() else { break };
+                assert!(session.async_is_chain_ancestor_of(last, sink).await.unwrap());
+                assert!(page.len() <= limit.max(4));
+                pages.extend(page);
+                start_hash = last;
+            }
+            assert_eq!(pages, descending);
+        }
+
+        drop(session);
+        tc.shutdown(wait_handles);
+    }
+}
@@ -1,3 +1,5 @@
+pub mod backup;
 pub mod collector;
 pub mod converter;
+pub mod headers;
 pub mod service;
@@ -1,10 +1,14 @@
 //! Core server implementation for ClientAPI
 
 use super::collector::{CollectorFromConsensus, CollectorFromIndex};
+use crate::backup::{create_backup, DatabaseLayout};
 use crate::converter::feerate_estimate::{FeeEstimateConverter, FeeEstimateVerboseConverter};
+use crate::converter::mempool::{MempoolPolicyConverter, MempoolPolicyUpdateConverter};
 use crate::converter::{consensus::ConsensusConverter, index::IndexConverter, protocol::ProtocolConverter};
+use crate::headers::{ascending_headers, descending_headers};
 use crate::service::NetworkType::{Mainnet, Testnet};
 use async_trait::async_trait;
+use kaspa_addresshistory::{api::AddressHistoryIndexProxy, model::AddressHistoryDirection};
 use kaspa_consensus_core::api::counters::ProcessingCounters;
 use kaspa_consensus_core::errors::block::RuleError;
 use kaspa_consensus_core::{
@@ -56,13 +60,15 @@ use kaspa_perf_monitor::{counters::CountersSnapshot, Monitor as PerfMonitor};
 use kaspa_rpc_core::{
     api::{
         connection::DynRpcConnection,
+        limits::RpcRateLimitConfig,
         ops::{RPC_API_REVISION, RPC_API_VERSION},
-        rpc::{RpcApi, MAX_SAFE_WINDOW_SIZE},
+        rpc::{RpcApi, MAX_ADDRESS_HISTORY_PAGE_SIZE, MAX_HEADERS_PAGE_SIZE, MAX_SAFE_WINDOW_SIZE},
     },
     model::*,
     notify::connection::ChannelConnection,
     Notification, RpcError, RpcResult,
 };
+use kaspa_txindex::api::TxIndexProxy;
 use kaspa_txscript::{extract_script_pub_key_address, pay_to_address_script};
 use kaspa_utils::expiring_cache::ExpiringCache;
 use kaspa_utils::sysinfo::SystemInfo;
@@ -73,6 +79,7 @@ use std::time::Duration;
 use std::{
     collections::HashMap,
     iter::once,
+    path::PathBuf,
     sync::{atomic::Ordering, Arc},
     vec,
 };
@@ -102,6 +109,8 @@ pub struct RpcCoreService {
     mining_manager: MiningManagerProxy,
     flow_context: Arc<FlowContext>,
     utxoindex: Option<UtxoIndexProxy>,
+    txindex: Option<TxIndexProxy>,
+    addresshistory: Option<AddressHistoryIndexProxy>,
     config: Arc<Config>,
     consensus_converter: Arc<ConsensusConverter>,
     index_converter: Arc<IndexConverter>,
@@ -116,6 +125,8 @@ pub struct RpcCoreService {
     p2p_tower_counters: Arc<TowerConnectionCounters>,
     grpc_tower_counters: Arc<TowerConnectionCounters>,
     system_info: SystemInfo,
+    rate_limits: Option<RpcRateLimitConfig>,
+    database_layout: DatabaseLayout,
     fee_estimate_cache: ExpiringCache<RpcFeeEstimate>,
     fee_estimate_verbose_cache: ExpiringCache<kaspa_mining::errors::MiningManagerResult<GetFeeEstimateExperimentalResponse>>,
 }
@@ -134,6 +145,8 @@ impl RpcCoreService {
         flow_context: Arc<FlowContext>,
         subscription_context: SubscriptionContext,
         utxoindex: Option<UtxoIndexProxy>,
+        txindex: Option<TxIndexProxy>,
+        addresshistory: Option<AddressHistoryIndexProxy>,
         config: Arc<Config>,
         core: Arc<Core>,
         processing_counters: Arc<ProcessingCounters>,
@@ -143,6 +156,8 @@ impl RpcCoreService {
         p2p_tower_counters: Arc<TowerConnectionCounters>,
         grpc_tower_counters: Arc<TowerConnectionCounters>,
         system_info: SystemInfo,
+        rate_limits: Option<RpcRateLimitConfig>,
+        database_layout: DatabaseLayout,
     ) -> Self {
         // This notifier UTXOs subscription granularity to index-processor or consensus notifier
         let policies = match index_notifier {
@@ -205,6 +220,8 @@ impl RpcCoreService {
             mining_manager,
             flow_context,
             utxoindex,
+            txindex,
+            addresshistory,
             config,
             consensus_converter,
             index_converter,
@@ -219,6 +236,8 @@ impl RpcCoreService {
             p2p_tower_counters,
             grpc_tower_counters,
             system_info,
+            rate_limits,
+            database_layout,
             fee_estimate_cache: ExpiringCache::new(Duration::from_millis(500), Duration::from_millis(1000)),
             fee_estimate_verbose_cache: ExpiringCache::new(Duration::from_millis(500), Duration::from_millis(1000)),
         }
@@ -700,6 +719,126 @@ NOTE: This error usually indicates an RPC conversion error between the node and
         Ok(GetCoinSupplyResponse::new(MAX_SOMPI, circulating_sompi))
     }
 
+    async fn get_transaction_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        request: GetTransactionRequest,
+    ) -> RpcResult<GetTransactionResponse> {
+        if !self.config.txindex {
+            return Err(RpcError::NoTxIndex);
+        }
+        let Some(entry) = self
+            .txindex
+            .clone()
+            .unwrap()
+            .get_transaction_entry(request.transaction_id)
+            .await
+            .map_err(|e| RpcError::General(e.to_string()))?
+        else {
+            return Err(RpcError::TransactionNotFound(request.transaction_id));
+        };
+        let session = self.consensus_manager.consensus().session().await;
+        let block = session.async_get_block(entry.containing_block_hash).await?;
+        let Some(transaction) = block.transactions.get(entry.index_within_block as usize) else {
+            return Err(RpcError::General(format!(
+                "transaction {} is missing from its indexed containing block {}",
+                request.transaction_id, entry.containing_block_hash
+            )));
+        };
+        let transaction =
+            self.consensus_converter.get_transaction(&session, transaction, Some(&block.header), request.include_verbose_data);
+        Ok(GetTransactionResponse::new(transaction, entry.containing_block_hash, entry.accepting_block_hash))
+    }
+
+    async fn get_transaction_acceptance_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        request: GetTransactionAcceptanceRequest,
+    ) -> RpcResult<GetTransactionAcceptanceResponse> {
+        if !self.config.txindex {
+            return Err(RpcError::NoTxIndex);
+        }
+        let entries = self
+            .txindex
+            .clone()
+            .unwrap()
+            .get_transaction_entries(request.transaction_ids.clone())
+            .await
+            .map_err(|e| RpcError::General(e.to_string()))?;
+        let acceptances = request
+            .transaction_ids
+            .into_iter()
+            .zip(entries)
+            .filter_map(|(transaction_id, entry)| {
+                entry.map(|entry| RpcTransactionAcceptance {
+                    transaction_id,
+                    containing_block_hash: entry.containing_block_hash,
+                    accepting_block_hash: entry.accepting_block_hash,
+                })
+            })
+            .collect();
+        Ok(GetTransactionAcceptanceResponse::new(acceptances))
+    }
+
+    async fn get_address_history_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        request: GetAddressHistoryRequest,
+    ) -> RpcResult<GetAddressHistoryResponse> {
+        if !self.config.addresshistoryindex {
+            return Err(RpcError::NoAddressHistoryIndex);
+        }
+        let limit = request.limit.clamp(1, MAX_ADDRESS_HISTORY_PAGE_SIZE) as usize;
+        let page = self
+            .addresshistory
+            .clone()
+            .unwrap()
+            .get_history(pay_to_address_script(&request.address), request.start_daa_score, limit)
+            .await
+            .map_err(|e| RpcError::General(e.to_string()))?;
+        let entries = page
+            .entries
+            .into_iter()
+            .map(|entry| RpcAddressHistoryEntry {
+                outpoint: entry.outpoint.into(),
+                daa_score: entry.daa_score,
+                direction: match entry.direction {
+                    AddressHistoryDirection::Received => RpcAddressHistoryDirection::Received,
+                    AddressHistoryDirection::Spent => RpcAddressHistoryDirection::Spent,
+                },
+                amount: entry.amount,
+                spending_transaction_id: entry.spending_transaction_id,
+            })
+            .collect();
+        Ok(GetAddressHistoryResponse::new(entries, page.next_daa_score, page.history_start_daa_score))
+    }
+
+    async fn get_mempool_policy_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        _request: GetMempoolPolicyRequest,
+    ) -> RpcResult<GetMempoolPolicyResponse> {
+        if !self.config.unsafe_rpc {
+            warn!("GetMempoolPolicy RPC command called while node in safe RPC mode -- ignoring.");
+            return Err(RpcError::UnavailableInSafeMode);
+        }
+        let policy = self.mining_manager.clone().get_policy().await;
+        Ok(GetMempoolPolicyResponse::new(policy.into_rpc()))
+    }
+
+    async fn set_mempool_policy_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        request: SetMempoolPolicyRequest,
+    ) -> RpcResult<SetMempoolPolicyResponse> {
+        if !self.config.unsafe_rpc {
+            warn!("SetMempoolPolicy RPC command called while node in safe RPC mode -- ignoring.");
+            return Err(RpcError::UnavailableInSafeMode);
+        }
+        let (policy, evicted) = self.mining_manager.clone().update_policy(request.into_policy_update()).await?;
+        Ok(SetMempoolPolicyResponse::new(policy.into_rpc(), evicted as u64))
+    }
+
     async fn get_daa_score_timestamp_estimate_call(
         &self,
         _connection: Option<&DynRpcConnection>,
@@ -801,9 +940,29 @@ NOTE: This error usually indicates an RPC conversion error between the node and
     async fn get_headers_call(
         &self,
         _connection: Option<&DynRpcConnection>,
-        _request: GetHeadersRequest,
+        request: GetHeadersRequest,
     ) -> RpcResult<GetHeadersResponse> {
-        Err(RpcError::NotImplemented)
+        let limit = request.limit.clamp(1, MAX_HEADERS_PAGE_SIZE) as usize;
+        let session = self.consensus_manager.consensus().session().await;
+
+        // Make sure start_hash points to an existing and valid block
+        session.async_get_ghostdag_data(request.start_hash).await?;
+
+        let headers = if request.is_ascending {
+            ascending_headers(&session, request.start_hash, limit, self.config.mergeset_size_limit).await?
+        } else {
+            descending_headers(&session, request.start_hash, limit).await?
+        };
+        let verbose_data = if request.include_verbose_data {
+            let mut verbose_data = Vec::with_capacity(headers.len());
+            for header in headers.iter() {
+                verbose_data.push(self.consensus_converter.get_block_verbose_data(&session, header, vec![]).await?);
+            }
+            verbose_data
+        } else {
+            vec![]
+        };
+        Ok(GetHeadersResponse { headers: headers.iter().map(|header| header.as_ref().into()).collect(), verbose_data })
     }
 
     async fn get_block_dag_info_call(
@@ -948,13 +1107,32 @@ NOTE: This error usually indicates an RPC conversion error between the node and
     async fn resolve_finality_conflict_call(
         &self,
         _connection: Option<&DynRpcConnection>,
-        _request: ResolveFinalityConflictRequest,
+        request: ResolveFinalityConflictRequest,
     ) -> RpcResult<ResolveFinalityConflictResponse> {
         if !self.config.unsafe_rpc {
             warn!("ResolveFinalityConflict RPC command called while node in safe RPC mode -- ignoring.");
             return Err(RpcError::UnavailableInSafeMode);
         }
-        Err(RpcError::NotImplemented)
+        self.consensus_manager
+            .resolve_finality_conflict(request.finality_block_hash)
+            .await
+            .map_err(|err| RpcError::General(err.to_string()))?;
+        Ok(ResolveFinalityConflictResponse {})
+    }
+
+    async fn backup_call(&self, _connection: Option<&DynRpcConnection>, request: BackupRequest) -> RpcResult<BackupResponse> {
+        if !self.config.unsafe_rpc {
+            warn!("Backup RPC command called while node in safe RPC mode -- ignoring.");
+            return Err(RpcError::UnavailableInSafeMode);
+        }
+        let databases = create_backup(
+            &self.database_layout,
+            self.consensus_manager.clone(),
+            self.utxoindex.clone(),
+            PathBuf::from(request.target_dir),
+        )
+        .await?;
+        Ok(BackupResponse::new(databases))
     }
 
     async fn get_connections_call(
@@ -1020,6 +1198,8 @@ NOTE: This error usually indicates an RPC conversion error between the node and
             p2p_bytes_rx: self.p2p_tower_counters.bytes_rx.load(Ordering::Relaxed) as u64,
             grpc_bytes_tx: self.grpc_tower_counters.bytes_tx.load(Ordering::Relaxed) as u64,
             grpc_bytes_rx: self.grpc_tower_counters.bytes_rx.load(Ordering::Relaxed) as u64,
+            p2p_tx_announcement_bytes_tx: self.flow_context.tx_relay_counters().announcement_bytes.load(Ordering::Relaxed),
+            p2p_tx_flood_bytes_tx: self.flow_context.tx_relay_counters().flood_bytes.load(Ordering::Relaxed),
         });
 
         let consensus_metrics = if req.consensus_metrics {
@@ -1103,6 +1283,7 @@ NOTE: This error usually indicates an RPC conversion error between the node and
             has_utxo_index: self.config.utxoindex,
             is_synced,
             virtual_daa_score,
+            rate_limits: self.rate_limits.clone(),
         })
     }
 
@@ -2,6 +2,8 @@
 
 use crate::imports::*;
 use crate::parse::parse_host;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::proxy::ProxyRelay;
 use crate::{error::Error, node::NodeDescriptor};
 use kaspa_consensus_core::network::NetworkType;
 use kaspa_notify::{
@@ -9,11 +11,13 @@ use kaspa_notify::{
     subscription::{context::SubscriptionContext, MutationPolicies, UtxosChangedMutationPolicy},
 };
 use kaspa_rpc_core::{
-    api::ctl::RpcCtl,
+    api::{auth::RpcCredentials, ctl::RpcCtl},
     notify::collector::{RpcCoreCollector, RpcCoreConverter},
 };
 pub use kaspa_rpc_macros::build_wrpc_client_interface;
 use std::fmt::Debug;
+#[cfg(not(target_arch = "wasm32"))]
+use std::net::SocketAddr;
 use workflow_core::{channel::Multiplexer, runtime as application_runtime};
 use workflow_dom::utils::window;
 use workflow_rpc::client::Ctl as WrpcCtl;
@@ -48,6 +52,16 @@ struct Inner {
     resolver: Mutex<Option<Resolver>>,
     network_id: Mutex<Option<NetworkId>>,
     node_descriptor: Mutex<Option<Arc<NodeDescriptor>>>,
+    // Credentials every connection gets authenticated with
+    credentials: Mutex<Option<RpcCredentials>>,
+    // Whether the current connection went through authentication
+    authenticated: AsyncMutex<bool>,
+    // SOCKS5 proxy `ws://` urls are dialed through
+    #[cfg(not(target_arch = "wasm32"))]
+    proxy: Mutex<Option<SocketAddr>>,
+    // The relay serving the current url when a proxy is in use
+    #[cfg(not(target_arch = "wasm32"))]
+    proxy_relay: Mutex<Option<ProxyRelay>>,
 }
 
 impl Inner {
@@ -76,6 +90,7 @@ impl Inner {
             RpcApiOps::VirtualDaaScoreChangedNotification,
             RpcApiOps::PruningPointUtxoSetOverrideNotification,
             RpcApiOps::NewBlockTemplateNotification,
+            RpcApiOps::MempoolChangedNotification,
         ]
         .into_iter()
         .for_each(|notification_op| {
@@ -119,6 +134,12 @@ impl Inner {
             resolver: Mutex::new(resolver),
             network_id: Mutex::new(network_id),
             node_descriptor: Mutex::new(None),
+            credentials: Mutex::new(None),
+            authenticated: AsyncMutex::new(false),
+            #[cfg(not(target_arch = "wasm32"))]
+            proxy: Mutex::new(None),
+            #[cfg(not(target_arch = "wasm32"))]
+            proxy_relay: Mutex::new(None),
         };
         Ok(client)
     }
@@ -143,6 +164,27 @@ impl Inner {
         Ok(())
     }
 
+    /// Authenticates the connection with `credentials`, returning the granted role.
+    async fn authenticate(&self, credentials: &RpcCredentials) -> RpcResult<String> {
+        let request = AuthenticateRequest::new(credentials.to_authorization());
+        let response: Serializable<AuthenticateResponse> =
+            self.rpc_client.call(RpcApiOps::Authenticate, Serializable(request)).await.map_err(|err| err.to_string())?;
+        Ok(response.into_inner().role)
+    }
+
+    /// Authenticates the current connection with the configured credentials, if any, unless already done.
+    async fn authenticate_connection(&self) -> RpcResult<()> {
+        let mut authenticated = self.authenticated.lock().await;
+        if !*authenticated {
+            let credentials = self.credentials.lock().unwrap().clone();
+            if let Some(credentials) = credentials {
+                self.authenticate(&credentials).await?;
+            }
+            *authenticated = true;
+        }
+        Ok(())
+    }
+
     fn ctor_url(&self) -> Option<String> {
         self.ctor_url.lock().unwrap().clone()
     }
@@ -242,6 +284,19 @@ impl RpcResolver for Inner {
 
         self.rpc_ctl.set_descriptor(Some(url.clone()));
         self.set_current_url(Some(&url));
+
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let proxy = *self.proxy.lock().unwrap();
+            if let Some(proxy) = proxy {
+                let relay = ProxyRelay::bind(&url, proxy).await.map_err(WebSocketError::custom)?;
+                let local_url = relay.local_url().to_string();
+                // Replacing the relay of a previous connection stops it
+                self.proxy_relay.lock().unwrap().replace(relay);
+                return Ok(local_url);
+            }
+        }
+
         Ok(url)
     }
 }
@@ -377,6 +432,38 @@ impl KaspaRpcClient {
         Ok(())
     }
 
+    /// Sets the credentials each connection gets authenticated with, once established
+    /// and before being signaled as open, applied on the next connection.
+    pub fn set_credentials(&self, credentials: Option<RpcCredentials>) -> Result<()> {
+        *self.inner.credentials.lock().unwrap() = credentials;
+        Ok(())
+    }
+
+    /// Authenticates the current connection with `credentials`, returning the role granted by the server.
+    /// The credentials are retained to authenticate subsequent connections, including automatic reconnections.
+    pub async fn authenticate(&self, credentials: RpcCredentials) -> Result<String> {
+        let mut authenticated = self.inner.authenticated.lock().await;
+        let role = self.inner.authenticate(&credentials).await?;
+        self.inner.credentials.lock().unwrap().replace(credentials);
+        *authenticated = true;
+        Ok(role)
+    }
+
+    /// Authenticates the current connection with the configured credentials unless already done.
+    /// Handlers of the raw [`WrpcCtl::Connect`] event (see [`Self::ctl_multiplexer`]) must await
+    /// this before issuing calls, since the event can be delivered before authentication completes.
+    pub async fn authenticate_connection(&self) -> Result<()> {
+        Ok(self.inner.authenticate_connection().await?)
+    }
+
+    /// Sets the SOCKS5 proxy `ws://` urls get dialed through, the host name being resolved by the proxy,
+    /// applied on the next connection. `wss://` urls are refused while a proxy is set.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn set_proxy(&self, proxy: Option<SocketAddr>) -> Result<()> {
+        *self.inner.proxy.lock().unwrap() = proxy;
+        Ok(())
+    }
+
     pub fn node_descriptor(&self) -> Option<Arc<NodeDescriptor>> {
         self.inner.node_descriptor.lock().unwrap().clone()
     }
@@ -434,6 +521,7 @@ impl KaspaRpcClient {
 
         let options = options.unwrap_or_default();
         let strategy = options.strategy;
+        let block_async_connect = options.block_async_connect;
 
         self.inner.set_default_url(options.url.as_deref());
         self.inner.rpc_ctl.set_descriptor(options.url.clone());
@@ -450,7 +538,13 @@ impl KaspaRpcClient {
         self.start().await?;
         self.inner.rpc_client.configure(ws_config);
         match self.inner.rpc_client.connect(options).await {
-            Ok(v) => Ok(v),
+            Ok(v) => {
+                // A blocking connect returns an established connection, make it usable right away
+                if block_async_connect {
+                    self.inner.authenticate_connection().await?;
+                }
+                Ok(v)
+            }
             Err(err) => {
                 if strategy == ConnectStrategy::Fallback {
                     let _guard = self.inner.disconnect_guard.lock().await;
@@ -469,6 +563,7 @@ impl KaspaRpcClient {
 
         self.inner.rpc_client.shutdown().await?;
         self.stop().await?;
+        *self.inner.authenticated.lock().await = false;
         Ok(())
     }
 
@@ -567,9 +662,14 @@ impl KaspaRpcClient {
                         if let Ok(msg) = msg {
                             match msg {
                                 WrpcCtl::Connect => {
+                                    // Authenticate (re)connections before signaling them as open
+                                    if let Err(err) = inner.authenticate_connection().await {
+                                        log_error!("wRPC authentication failed: {err}");
+                                    }
                                     inner.rpc_ctl.signal_open().await.expect("(KaspaRpcClient) rpc_ctl.signal_open() error");
                                 }
                                 WrpcCtl::Disconnect => {
+                                    *inner.authenticated.lock().await = false;
                                     inner.rpc_ctl.signal_close().await.expect("(KaspaRpcClient) rpc_ctl.signal_close() error");
                                 }
                             }
@@ -624,6 +724,12 @@ impl RpcApi for KaspaRpcClient {
             GetBlocks,
             GetBlockTemplate,
             GetCurrentBlockColor,
+            GetTransaction,
+            GetTransactionAcceptance,
+            GetAddressHistory,
+            GetMempoolPolicy,
+            SetMempoolPolicy,
+            Backup,
             GetCoinSupply,
             GetConnectedPeerInfo,
             GetConnections,
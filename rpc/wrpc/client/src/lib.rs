@@ -22,4 +22,6 @@ pub use imports::{KaspaRpcClient, Resolver, WrpcEncoding};
 pub mod node;
 pub mod parse;
 pub mod prelude;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod proxy;
 pub mod resolver;
@@ -0,0 +1,77 @@
+//!
+//! Dialing of `ws://` urls through a SOCKS5 proxy on native platforms.
+//!
+//! The WebSocket transport dials the url it is given directly. When a proxy is configured, the url
+//! is served by a [`ProxyRelay`] listening on loopback and tunneling each accepted connection through
+//! the proxy, the host name being resolved on the proxy side. The relay only reaches the single target
+//! it was bound for, so it grants local processes nothing the proxy itself does not.
+//!
+//! `wss://` urls are refused, their TLS session being established by the WebSocket transport which
+//! would then only reach the relay.
+//!
+
+use crate::{
+    error::Error,
+    parse::{parse_host, Host},
+    result::Result,
+};
+use kaspa_utils_tower::socks5;
+use std::net::SocketAddr;
+use tokio::{io::copy_bidirectional, net::TcpListener, task::JoinHandle};
+use workflow_log::log_warn;
+
+const DEFAULT_WS_PORT: u16 = 80;
+
+/// A loopback listener relaying each accepted connection to a `ws://` server through a SOCKS5 proxy.
+/// The relay stops when dropped.
+pub struct ProxyRelay {
+    local_url: String,
+    task: JoinHandle<()>,
+}
+
+impl ProxyRelay {
+    pub async fn bind(url: &str, proxy: SocketAddr) -> Result<Self> {
+        let parsed = parse_host(url).map_err(|err| Error::UrlError(err.to_string()))?;
+        match parsed.scheme {
+            Some(scheme) if scheme.eq_ignore_ascii_case("ws") => {}
+            _ => return Err(Error::UrlError(format!("only ws:// urls can be dialed through a proxy, got {url}"))),
+        }
+        let host = match parsed.host {
+            Host::Ipv6(ip) => ip.to_string(),
+            ref host => host.to_string(),
+        };
+        let port = parsed.port.unwrap_or(DEFAULT_WS_PORT);
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.map_err(|err| Error::Custom(err.to_string()))?;
+        let local_address = listener.local_addr().map_err(|err| Error::Custom(err.to_string()))?;
+        let local_url = format!("ws://{}{}", local_address, parsed.path);
+
+        let task = tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                let host = host.clone();
+                tokio::spawn(async move {
+                    let relayed = async {
+                        let mut upstream = socks5::connect(proxy, &host, port).await?;
+                        copy_bidirectional(&mut stream, &mut upstream).await
+                    };
+                    if let Err(err) = relayed.await {
+                        log_warn!("wRPC relay to {host}:{port} through proxy {proxy} failed: {err}");
+                    }
+                });
+            }
+        });
+
+        Ok(Self { local_url, task })
+    }
+
+    /// The loopback url the WebSocket connects to in place of the proxied url
+    pub fn local_url(&self) -> &str {
+        &self.local_url
+    }
+}
+
+impl Drop for ProxyRelay {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
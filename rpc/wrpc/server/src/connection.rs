@@ -6,7 +6,11 @@ use kaspa_notify::{
     notification::Notification as NotificationT,
     notifier::Notify,
 };
-use kaspa_rpc_core::{api::ops::RpcApiOps, notify::mode::NotificationMode, Notification};
+use kaspa_rpc_core::{
+    api::{auth::RpcPermissionsRef, limits::RpcConnectionRateLimiter, ops::RpcApiOps},
+    notify::mode::NotificationMode,
+    Notification,
+};
 use std::{
     fmt::{Debug, Display},
     sync::{Arc, Mutex},
@@ -51,6 +55,10 @@ struct ConnectionInner {
     pub grpc_client: Option<Arc<GrpcClient>>,
     // not using an atomic in case an Id will change type in the future...
     pub listener_id: Mutex<Option<ListenerId>>,
+    /// The methods this client is allowed to call, or `None` if it is not authenticated
+    pub permissions: Mutex<Option<RpcPermissionsRef>>,
+    /// The request budgets of this client, or `None` if requests are not limited
+    pub rate_limiter: Option<RpcConnectionRateLimiter>,
 }
 
 impl ConnectionInner {
@@ -83,12 +91,22 @@ pub struct Connection {
 }
 
 impl Connection {
-    pub fn new(id: u64, peer: &SocketAddr, messenger: Arc<Messenger>, grpc_client: Option<Arc<GrpcClient>>) -> Connection {
+    pub fn new(
+        id: u64,
+        peer: &SocketAddr,
+        messenger: Arc<Messenger>,
+        grpc_client: Option<Arc<GrpcClient>>,
+        permissions: Option<RpcPermissionsRef>,
+        rate_limiter: Option<RpcConnectionRateLimiter>,
+    ) -> Connection {
         // If a GrpcClient is provided, it has to come configured in direct mode
         assert!(grpc_client.is_none() || grpc_client.as_ref().unwrap().notification_mode() == NotificationMode::Direct);
         // Should a gRPC client be provided, no listener_id is required for subscriptions so the listener id is set to default
         let listener_id = Mutex::new(grpc_client.clone().map(|_| ListenerId::default()));
-        Connection { inner: Arc::new(ConnectionInner { id, peer: *peer, messenger, grpc_client, listener_id }) }
+        let permissions = Mutex::new(permissions);
+        Connection {
+            inner: Arc::new(ConnectionInner { id, peer: *peer, messenger, grpc_client, listener_id, permissions, rate_limiter }),
+        }
     }
 
     /// Obtain the connection id
@@ -121,6 +139,18 @@ impl Connection {
         self.inner.listener_id.lock().unwrap().replace(listener_id);
     }
 
+    pub fn permissions(&self) -> Option<RpcPermissionsRef> {
+        self.inner.permissions.lock().unwrap().clone()
+    }
+
+    pub fn set_permissions(&self, permissions: RpcPermissionsRef) {
+        self.inner.permissions.lock().unwrap().replace(permissions);
+    }
+
+    pub fn rate_limiter(&self) -> Option<&RpcConnectionRateLimiter> {
+        self.inner.rate_limiter.as_ref()
+    }
+
     pub fn peer(&self) -> &SocketAddr {
         &self.inner.peer
     }
@@ -20,6 +20,9 @@ pub enum Error {
 
     #[error("Notify error: {0}")]
     NotifyError(#[from] NotifyError),
+
+    #[error("TLS error: {0}")]
+    Tls(String),
 }
 
 impl<T> From<PoisonError<T>> for Error {
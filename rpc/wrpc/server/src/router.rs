@@ -45,6 +45,12 @@ impl Router {
                 GetBlocks,
                 GetBlockTemplate,
                 GetCurrentBlockColor,
+                GetTransaction,
+                GetTransactionAcceptance,
+                GetAddressHistory,
+                GetMempoolPolicy,
+                SetMempoolPolicy,
+                Backup,
                 GetCoinSupply,
                 GetConnectedPeerInfo,
                 GetCurrentNetwork,
@@ -81,7 +87,9 @@ impl Router {
             RpcApiOps::Subscribe,
             workflow_rpc::server::Method::new(move |manager: Server, connection: Connection, scope: Serializable<Scope>| {
                 Box::pin(async move {
-                    manager.start_notify(&connection, scope.into_inner()).await.map_err(|err| err.to_string())?;
+                    let scope = scope.into_inner();
+                    manager.authorize_subscription(&connection, &scope).map_err(|err| err.to_string())?;
+                    manager.start_notify(&connection, scope).await.map_err(|err| err.to_string())?;
                     Ok(Serializable(SubscribeResponse::new(connection.id())))
                 })
             }),
@@ -99,6 +107,20 @@ impl Router {
             }),
         );
 
+        interface.method(
+            RpcApiOps::Authenticate,
+            workflow_rpc::server::Method::new(
+                move |manager: Server, connection: Connection, request: Serializable<AuthenticateRequest>| {
+                    Box::pin(async move {
+                        manager.throttle(&connection, RpcApiOps::Authenticate, 1).map_err(|err| err.to_string())?;
+                        let role =
+                            manager.authenticate(&connection, &request.into_inner().authorization).map_err(|err| err.to_string())?;
+                        Ok(Serializable(AuthenticateResponse::new(role)))
+                    })
+                },
+            ),
+        );
+
         Router { interface: Arc::new(interface), server_context }
     }
 }
@@ -15,9 +15,12 @@ use kaspa_notify::{
     subscription::{MutationPolicies, UtxosChangedMutationPolicy},
 };
 use kaspa_rpc_core::{
-    api::rpc::{DynRpcService, RpcApi},
+    api::{
+        ops::RpcApiOps,
+        rpc::{DynRpcService, RpcApi},
+    },
     notify::{channel::NotificationChannel, connection::ChannelConnection, mode::NotificationMode},
-    Notification, RpcResult,
+    Notification, RpcError, RpcResult,
 };
 use kaspa_rpc_service::service::RpcCoreService;
 use std::{
@@ -126,6 +129,8 @@ impl Server {
                 true,
                 None,
                 Default::default(),
+                None,
+                None,
             )
             .await
             .map_err(|e| WebSocketError::Other(e.to_string()))?;
@@ -134,7 +139,10 @@ impl Server {
         } else {
             None
         };
-        let connection = Connection::new(id, peer, messenger, grpc_client);
+        // Clients start with the anonymous role, if any, until they authenticate
+        let permissions = self.inner.options.authenticator.as_ref().and_then(|authenticator| authenticator.authenticate(None).ok());
+        let rate_limiter = self.inner.options.rate_limiter.as_ref().map(|limiter| limiter.connection(peer.ip()));
+        let connection = Connection::new(id, peer, messenger, grpc_client, permissions, rate_limiter);
         if self.inner.options.grpc_proxy_address.is_some() {
             // log_trace!("starting gRPC");
             connection.grpc_client().start(Some(connection.grpc_client_notify_target())).await;
@@ -212,6 +220,43 @@ impl Server {
         Ok(())
     }
 
+    /// Grants `connection` the role of the credentials carried by `authorization`, returning the role name
+    pub fn authenticate(&self, connection: &Connection, authorization: &str) -> RpcResult<String> {
+        let Some(authenticator) = &self.inner.options.authenticator else {
+            return Err(RpcError::UnsupportedFeature);
+        };
+        let permissions = authenticator.authenticate(Some(authorization)).inspect_err(|_| {
+            log_warn!("WebSocket {} failed to authenticate", connection.peer());
+        })?;
+        let role = permissions.role().to_string();
+        connection.set_permissions(permissions);
+        Ok(role)
+    }
+
+    /// Checks whether `connection` is allowed to call `op` with a request of `cost`, within its rate limits
+    pub fn authorize(&self, connection: &Connection, op: RpcApiOps, cost: u32) -> RpcResult<()> {
+        if self.inner.options.authenticator.is_some() {
+            connection.permissions().ok_or(RpcError::AuthenticationFailed)?.authorize(op)?;
+        }
+        self.throttle(connection, op, cost)
+    }
+
+    /// Checks whether `connection` is allowed to subscribe to the notifications of `scope`, within its rate limits
+    pub fn authorize_subscription(&self, connection: &Connection, scope: &Scope) -> RpcResult<()> {
+        if self.inner.options.authenticator.is_some() {
+            connection.permissions().ok_or(RpcError::AuthenticationFailed)?.authorize_subscription(scope.event_type())?;
+        }
+        self.throttle(connection, RpcApiOps::Subscribe, 1)
+    }
+
+    /// Consumes `cost` requests of the budgets of `connection`, failing if calling `op` exceeds its rate limits
+    pub fn throttle(&self, connection: &Connection, op: RpcApiOps, cost: u32) -> RpcResult<()> {
+        match connection.rate_limiter() {
+            Some(rate_limiter) => rate_limiter.check(op, cost),
+            None => Ok(()),
+        }
+    }
+
     pub fn verbose(&self) -> bool {
         self.inner.options.verbose
     }
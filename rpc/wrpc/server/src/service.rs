@@ -1,15 +1,23 @@
-use crate::{connection::*, router::*, server::*};
+use crate::{
+    connection::*,
+    router::*,
+    server::*,
+    tls::{self, TlsServer},
+};
 use async_trait::async_trait;
 use kaspa_core::{
     info,
     task::service::{AsyncService, AsyncServiceError, AsyncServiceFuture},
     trace, warn,
 };
-use kaspa_rpc_core::api::ops::RpcApiOps;
+use kaspa_rpc_core::api::{auth::RpcAuthenticator, limits::RpcRateLimiter, ops::RpcApiOps, tls::RpcServerTlsConfig};
 use kaspa_rpc_service::service::RpcCoreService;
 use kaspa_utils::triggers::SingleTrigger;
 use std::sync::Arc;
-use tokio::sync::oneshot::{channel as oneshot_channel, Sender as OneshotSender};
+use tokio::{
+    net::TcpListener,
+    sync::oneshot::{channel as oneshot_channel, Sender as OneshotSender},
+};
 use workflow_rpc::server::prelude::*;
 pub use workflow_rpc::server::{Encoding as WrpcEncoding, WebSocketConfig, WebSocketCounters};
 
@@ -20,11 +28,24 @@ pub struct Options {
     pub listen_address: String,
     pub grpc_proxy_address: Option<String>,
     pub verbose: bool,
+    /// Authenticates clients, or `None` if any client may call any method
+    pub authenticator: Option<Arc<RpcAuthenticator>>,
+    /// Limits the request rate of clients, or `None` if unlimited
+    pub rate_limiter: Option<Arc<RpcRateLimiter>>,
+    /// Serves `wss://` on `listen_address`, or plain `ws://` if `None`
+    pub tls: Option<RpcServerTlsConfig>,
 }
 
 impl Default for Options {
     fn default() -> Self {
-        Options { listen_address: "127.0.0.1:17110".to_owned(), verbose: false, grpc_proxy_address: None }
+        Options {
+            listen_address: "127.0.0.1:17110".to_owned(),
+            verbose: false,
+            grpc_proxy_address: None,
+            authenticator: None,
+            rate_limiter: None,
+            tls: None,
+        }
     }
 }
 
@@ -99,6 +120,7 @@ pub struct WrpcService {
     options: Arc<Options>,
     server: RpcServer,
     rpc_handler: Arc<KaspaRpcHandler>,
+    tls_server: Option<Arc<TlsServer>>,
     shutdown: SingleTrigger,
 }
 
@@ -122,11 +144,14 @@ impl WrpcService {
             *encoding,
             rpc_handler.clone(),
             router.interface.clone(),
-            Some(counters),
+            Some(counters.clone()),
             false,
         );
+        // Create a server terminating TLS in its place if enabled
+        let tls_server =
+            options.tls.as_ref().map(|_| Arc::new(TlsServer::new(rpc_handler.clone(), router.interface.clone(), *encoding, counters)));
 
-        WrpcService { options, server, rpc_handler, shutdown: SingleTrigger::default() }
+        WrpcService { options, server, rpc_handler, tls_server, shutdown: SingleTrigger::default() }
     }
 
     /// Start listening on the configured address (will panic if the socket listen() fails)
@@ -135,6 +160,26 @@ impl WrpcService {
         let listen_address = self.options.listen_address.clone();
         self.rpc_handler.server.start();
 
+        if let Some(tls_server) = self.tls_server.clone() {
+            let tls = self.options.tls.as_ref().expect("the TLS server is only created with a TLS configuration");
+            let acceptor =
+                tls::acceptor(tls).unwrap_or_else(|err| panic!("WRPC Server {listen_address} TLS configuration error: {err}"));
+
+            // Spawn a task running the TLS server until the termination signal
+            info!("WRPC Server starting on: {}", listen_address);
+            tokio::spawn(async move {
+                let config = WebSocketConfig { max_message_size: Some(MAX_WRPC_MESSAGE_SIZE), ..Default::default() };
+                match TcpListener::bind(&listen_address).await {
+                    Ok(listener) => {
+                        tls_server.listen(acceptor, listener, config, termination_receiver).await;
+                        info!("WRPC Server stopped on: {}", listen_address);
+                    }
+                    Err(err) => panic!("WRPC Server bind error on {listen_address}: {err:?}"),
+                }
+            });
+            return termination_sender;
+        }
+
         // Spawn a task stopping the server on termination signal
         let service = self.clone();
         tokio::spawn(async move {
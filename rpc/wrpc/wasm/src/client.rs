@@ -18,7 +18,7 @@ use kaspa_notify::connection::ChannelType;
 use kaspa_notify::events::EventType;
 use kaspa_notify::listener;
 use kaspa_notify::notification::Notification as NotificationT;
-use kaspa_rpc_core::api::ctl;
+use kaspa_rpc_core::api::{auth::RpcCredentials, ctl};
 pub use kaspa_rpc_core::wasm::message::*;
 pub use kaspa_rpc_macros::{
     build_wrpc_wasm_bindgen_interface, build_wrpc_wasm_bindgen_subscriptions, declare_typescript_wasm_interface as declare,
@@ -59,6 +59,10 @@ declare! {
          * `networkId` is required when using a resolver.
          */
         networkId?: NetworkId | string;
+        /**
+         * Credentials authenticating each connection (for nodes requiring RPC authentication)
+         */
+        credentials?: IRpcCredentials;
     }
     "#,
 }
@@ -68,11 +72,12 @@ pub struct RpcConfig {
     pub url: Option<String>,
     pub encoding: Option<Encoding>,
     pub network_id: Option<NetworkId>,
+    pub credentials: Option<RpcCredentials>,
 }
 
 impl Default for RpcConfig {
     fn default() -> Self {
-        RpcConfig { url: None, encoding: Some(Encoding::Borsh), network_id: None, resolver: None }
+        RpcConfig { url: None, encoding: Some(Encoding::Borsh), network_id: None, resolver: None, credentials: None }
     }
 }
 
@@ -83,12 +88,16 @@ impl TryFrom<IRpcConfig> for RpcConfig {
         let url = config.try_get_string("url")?;
         let encoding = config.try_get::<Encoding>("encoding")?;
         let network_id = config.try_get::<NetworkId>("networkId")?;
+        let credentials = config
+            .try_get_value("credentials")?
+            .map(|credentials| RpcCredentials::try_from(credentials.unchecked_into::<IRpcCredentials>()))
+            .transpose()?;
 
         if resolver.is_some() && network_id.is_none() {
             return Err(Error::custom("networkId is required when using a resolver"));
         }
 
-        Ok(RpcConfig { resolver, url, encoding, network_id })
+        Ok(RpcConfig { resolver, url, encoding, network_id, credentials })
     }
 }
 
@@ -279,7 +288,7 @@ cfg_if! {
 
 impl RpcClient {
     pub fn new(config: Option<RpcConfig>) -> Result<RpcClient> {
-        let RpcConfig { resolver, url, encoding, network_id } = config.unwrap_or_default();
+        let RpcConfig { resolver, url, encoding, network_id, credentials } = config.unwrap_or_default();
 
         let encoding = encoding.unwrap_or(Encoding::Borsh);
 
@@ -299,6 +308,7 @@ impl RpcClient {
             KaspaRpcClient::new(encoding, url.as_deref(), resolver.clone().map(Into::into), network_id, None)
                 .unwrap_or_else(|err| panic!("{err}")),
         );
+        client.set_credentials(credentials)?;
 
         let rpc_client = RpcClient {
             inner: Arc::new(Inner {
@@ -356,6 +366,14 @@ impl RpcClient {
         Ok(())
     }
 
+    /// Authenticate the current connection with the given credentials, returning the role
+    /// granted by the node. The credentials are retained and used to authenticate
+    /// subsequent connections, including automatic reconnections.
+    /// @see {@link IRpcCredentials} interface for more details.
+    pub async fn authenticate(&self, credentials: IRpcCredentials) -> Result<String> {
+        Ok(self.inner.client.authenticate(credentials.try_into()?).await?)
+    }
+
     /// The current connection status of the RPC client.
     #[wasm_bindgen(getter, js_name = "isConnected")]
     pub fn is_connected(&self) -> bool {
@@ -660,6 +678,10 @@ impl RpcClient {
 
                             match ctl {
                                 Ctl::Connect => {
+                                    // Make sure the `connect` event handlers can issue calls right away
+                                    if let Err(err) = this.inner.client.authenticate_connection().await {
+                                        log_error!("Error while authenticating the RPC connection: {:?}",err);
+                                    }
                                     let listener_id = this.inner.client.register_new_listener(ChannelConnection::new(
                                         "kaspa-wrpc-client-wasm",
                                         this.inner.notification_channel.sender.clone(),
@@ -841,6 +863,36 @@ impl RpcClient {
         Ok(())
     }
 
+    /// Subscribe for a mempool changed notification event.
+    /// Mempool changed notification event is produced when a
+    /// transaction enters or leaves the mempool. The event notification
+    /// will be scoped to the provided list of addresses, or to all
+    /// transactions if the list is empty.
+    #[wasm_bindgen(js_name = subscribeMempoolChanged)]
+    pub async fn subscribe_mempool_changed(&self, addresses: AddressOrStringArrayT) -> Result<()> {
+        if let Some(listener_id) = self.listener_id() {
+            let addresses: Vec<Address> = addresses.try_into()?;
+            self.inner.client.start_notify(listener_id, Scope::MempoolChanged(MempoolChangedScope { addresses })).await?;
+        } else {
+            log_error!("RPC subscribe on a closed connection");
+        }
+
+        Ok(())
+    }
+
+    /// Unsubscribe from mempool changed notification event
+    /// for a specific set of addresses.
+    #[wasm_bindgen(js_name = unsubscribeMempoolChanged)]
+    pub async fn unsubscribe_mempool_changed(&self, addresses: AddressOrStringArrayT) -> Result<()> {
+        if let Some(listener_id) = self.listener_id() {
+            let addresses: Vec<Address> = addresses.try_into()?;
+            self.inner.client.stop_notify(listener_id, Scope::MempoolChanged(MempoolChangedScope { addresses })).await?;
+        } else {
+            log_error!("RPC unsubscribe on a closed connection");
+        }
+        Ok(())
+    }
+
     // TODO: scope variant with field functions
 
     /// Manage subscription for a virtual chain changed notification event.
@@ -881,6 +933,7 @@ build_wrpc_wasm_bindgen_subscriptions!([
     // Manually implemented subscriptions (above)
     // - VirtualChainChanged, // can't used this here due to non-C-style enum variant
     // - UtxosChanged, // can't used this here due to non-C-style enum variant
+    // - MempoolChanged, // can't used this here due to non-C-style enum variant
     // - VirtualDaaScoreChanged,
     /// Manage subscription for a block added notification event.
     /// Block added notification event is produced when a new
@@ -944,6 +997,10 @@ build_wrpc_wasm_bindgen_interface!(
         /// This call is primarily used by gRPC clients.
         /// For wRPC clients, use {@link RpcClient.getServerInfo}.
         GetInfo,
+        /// Retrieves the mempool relay policy currently in effect.
+        /// Requires the node to run with `--unsaferpc`.
+        /// Returned information: Mempool relay policy.
+        GetMempoolPolicy,
         /// Provides a list of addresses of known peers in the Kaspa
         /// network that the node can potentially connect to.
         /// Returned information: List of peer addresses.
@@ -987,12 +1044,21 @@ build_wrpc_wasm_bindgen_interface!(
         /// Adds a peer to the Kaspa node's list of known peers.
         /// Returned information: None.
         AddPeer,
+        /// Creates a consistent checkpoint of the node databases in a
+        /// directory on the node host, from which a node can be started.
+        /// Requires the node to run with `--unsaferpc`.
+        /// Returned information: Created database checkpoints.
+        Backup,
         /// Bans a peer from connecting to the Kaspa node for a specified duration.
         /// Returned information: None.
         Ban,
         /// Estimates the network's current hash rate in hashes per second.
         /// Returned information: Estimated network hashes per second.
         EstimateNetworkHashesPerSecond,
+        /// Retrieves a page of the history of an address from the address
+        /// history index. Requires the node to run with `--addresshistoryindex`.
+        /// Returned information: List of address history entries.
+        GetAddressHistory,
         /// Retrieves the balance of a specific address in the Kaspa BlockDAG.
         /// Returned information: Balance of the address.
         GetBalanceByAddress,
@@ -1032,6 +1098,14 @@ build_wrpc_wasm_bindgen_interface!(
         /// Retrieves information about a subnetwork in the Kaspa BlockDAG.
         /// Returned information: Subnetwork information.
         GetSubnetwork,
+        /// Retrieves an accepted transaction from the transaction index.
+        /// Requires the node to run with `--txindex`.
+        /// Returned information: Transaction, containing and accepting block hashes.
+        GetTransaction,
+        /// Retrieves the containing and accepting blocks of transactions
+        /// from the transaction index. Requires the node to run with `--txindex`.
+        /// Returned information: List of transaction acceptances.
+        GetTransactionAcceptance,
         /// Retrieves unspent transaction outputs (UTXOs) associated with
         /// specific addresses.
         /// Returned information: List of UTXOs.
@@ -1042,6 +1116,10 @@ build_wrpc_wasm_bindgen_interface!(
         /// Resolves a finality conflict in the Kaspa BlockDAG.
         /// Returned information: None.
         ResolveFinalityConflict,
+        /// Updates the mempool relay policy, evicting the transactions which
+        /// no longer satisfy it. Requires the node to run with `--unsaferpc`.
+        /// Returned information: Updated policy, number of evicted transactions.
+        SetMempoolPolicy,
         /// Submits a block to the Kaspa network.
         /// Returned information: None.
         SubmitBlock,
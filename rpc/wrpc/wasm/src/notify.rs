@@ -25,6 +25,7 @@ export enum RpcEventType {
     VirtualDaaScoreChanged = "virtual-daa-score-changed",
     PruningPointUtxoSetOverride = "pruning-point-utxo-set-override",
     NewBlockTemplate = "new-block-template",
+    MempoolChanged = "mempool-changed",
 }
 
 /**
@@ -40,7 +41,8 @@ export type RpcEventData = IBlockAdded
     | ISinkBlueScoreChanged 
     | IVirtualDaaScoreChanged 
     | IPruningPointUtxoSetOverride 
-    | INewBlockTemplate;
+    | INewBlockTemplate 
+    | IMempoolChanged;
 
 /**
  * RPC notification event data map.
@@ -59,6 +61,7 @@ export type RpcEventMap = {
     "virtual-daa-score-changed" : IVirtualDaaScoreChanged,
     "pruning-point-utxo-set-override" : IPruningPointUtxoSetOverride,
     "new-block-template" : INewBlockTemplate,
+    "mempool-changed" : IMempoolChanged,
 }
 
 /**
@@ -84,6 +87,7 @@ export type RpcEvent = {
  * {@link RpcClient.subscribeSinkBlueScoreChanged},
  * {@link RpcClient.subscribePruningPointUtxoSetOverride},
  * {@link RpcClient.subscribeNewBlockTemplate},
+ * {@link RpcClient.subscribeMempoolChanged},
  * 
  * @category Node RPC
  */
@@ -241,3 +245,18 @@ declare! {
     }
     "#,
 }
+
+declare! {
+    IMempoolChanged,
+    r#"
+    /**
+     * Mempool changed notification event is produced when a transaction
+     * enters or leaves the mempool of the node.
+     * 
+     * @category Node RPC
+     */
+    export interface IMempoolChanged {
+        [key: string]: any;
+    }
+    "#,
+}
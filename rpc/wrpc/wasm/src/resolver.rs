@@ -171,7 +171,7 @@ impl Resolver {
     /// @see {@link IResolverConnect}, {@link RpcClient}
     pub async fn connect(&self, options: IResolverConnect) -> Result<RpcClient> {
         let ResolverConnect { encoding, network_id } = options.try_into()?;
-        let config = RpcConfig { resolver: Some(self.clone()), url: None, encoding, network_id: Some(network_id) };
+        let config = RpcConfig { resolver: Some(self.clone()), url: None, encoding, network_id: Some(network_id), credentials: None };
         let client = RpcClient::new(Some(config))?;
         client.connect(None).await?;
         Ok(client)
@@ -48,6 +48,8 @@ impl ClientManager {
             false,
             Some(500_000),
             Default::default(),
+            None,
+            None,
         )
         .await
         .unwrap()
@@ -71,6 +73,8 @@ impl ClientManager {
             false,
             Some(500_000),
             Default::default(),
+            None,
+            None,
         )
         .await
         .unwrap()
@@ -93,7 +97,8 @@ pub struct Daemon {
     shutdown_requested: Listener,
     workers: Option<Vec<std::thread::JoinHandle<()>>>,
 
-    _appdir_tempdir: TempDir,
+    /// The application directory, unless given by the args
+    _appdir_tempdir: Option<TempDir>,
 }
 
 impl Daemon {
@@ -135,8 +140,10 @@ impl Daemon {
     }
 
     pub fn with_manager(client_manager: Arc<ClientManager>, fd_total_budget: i32) -> Daemon {
-        let appdir_tempdir = get_kaspa_tempdir();
-        client_manager.args.write().appdir = Some(appdir_tempdir.path().to_str().unwrap().to_owned());
+        let appdir_tempdir = client_manager.args.read().appdir.is_none().then(get_kaspa_tempdir);
+        if let Some(appdir_tempdir) = appdir_tempdir.as_ref() {
+            client_manager.args.write().appdir = Some(appdir_tempdir.path().to_str().unwrap().to_owned());
+        }
         let (core, _) = create_core_with_runtime(&Default::default(), &client_manager.args.read(), fd_total_budget);
         let async_service = &Arc::downcast::<AsyncRuntime>(core.find(AsyncRuntime::IDENT).unwrap().arc_any()).unwrap();
         let rpc_core_service = &Arc::downcast::<RpcCoreService>(async_service.find(RpcCoreService::IDENT).unwrap().arc_any()).unwrap();
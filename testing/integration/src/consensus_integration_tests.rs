@@ -38,7 +38,7 @@ use kaspa_consensus_core::tx::{ScriptPublicKey, Transaction, TransactionInput, T
 use kaspa_consensus_core::{blockhash, hashing, BlockHashMap, BlueWorkType};
 use kaspa_consensus_notify::root::ConsensusNotificationRoot;
 use kaspa_consensus_notify::service::NotifyService;
-use kaspa_consensusmanager::ConsensusManager;
+use kaspa_consensusmanager::{ConsensusManager, ConsensusProxy, FinalityConflictResolutionError};
 use kaspa_core::task::tick::TickService;
 use kaspa_core::time::unix_now;
 use kaspa_database::utils::get_kaspa_tempdir;
@@ -845,6 +845,8 @@ impl KaspadGoParams {
             max_block_level: self.MaxBlockLevel,
             pruning_proof_m: self.PruningProofM,
             payload_activation: ForkActivation::never(),
+            tx_introspection_activation: ForkActivation::never(),
+            byte_string_opcodes_activation: ForkActivation::never(),
         }
     }
 }
@@ -962,6 +964,8 @@ async fn json_test(file_path: &str, concurrency: bool) {
         &notify_service.notifier(),
         subscription_context.clone(),
         Some(UtxoIndexProxy::new(utxoindex.clone())),
+        None,
+        None,
     ));
 
     let async_runtime = Arc::new(AsyncRuntime::new(2));
@@ -1757,13 +1761,316 @@ async fn staging_consensus_test() {
     core.bind(consensus_manager.clone());
     let joins = core.start();
 
-    let staging = consensus_manager.new_staging_consensus();
+    let reservation = consensus_manager.try_reserve_staging().unwrap();
+    assert!(consensus_manager.try_reserve_staging().is_none());
+    let staging = consensus_manager.new_staging_consensus(&reservation);
     staging.commit();
 
     core.shutdown();
     core.join(joins);
 }
 
+/// Params under which a chain of a few hundred blocks has a pruning point other than genesis
+fn shallow_pruning_config() -> Config {
+    ConfigBuilder::new(DEVNET_PARAMS)
+        .skip_proof_of_work()
+        .edit_consensus_params(|p| {
+            p.ghostdag_k = 2;
+            p.mergeset_size_limit = 20;
+            p.finality_depth = 10;
+            p.merge_depth = 5;
+            // 2 * finality_depth + 4 * mergeset_size_limit * ghostdag_k + 2 * ghostdag_k + 2
+            p.pruning_depth = 186;
+        })
+        .build()
+}
+
+fn start_consensus_manager(config: &Config, db_path: &Path) -> (Arc<ConsensusManager>, Arc<Core>, Vec<std::thread::JoinHandle<()>>) {
+    let meta_db =
+        kaspa_database::prelude::ConnBuilder::default().with_db_path(db_path.join("meta")).with_files_limit(5).build().unwrap();
+    let (notification_send, _notification_recv) = unbounded();
+    let consensus_factory = Arc::new(ConsensusFactory::new(
+        meta_db,
+        config,
+        db_path.join("consensus"),
+        4,
+        Arc::new(ConsensusNotificationRoot::new(notification_send)),
+        Arc::new(ProcessingCounters::default()),
+        Arc::new(TxScriptCacheCounters::default()),
+        200,
+    ));
+    let consensus_manager = Arc::new(ConsensusManager::new(consensus_factory));
+    let core = Arc::new(Core::new());
+    core.bind(consensus_manager.clone());
+    let joins = core.start();
+    (consensus_manager, core, joins)
+}
+
+/// Waits for the pruning processor, which advances the pruning point in the background, to settle on a pruning point
+async fn wait_for_pruning_point(session: &ConsensusProxy) -> Hash {
+    let mut pruning_point = session.async_pruning_point().await;
+    for _ in 0..100 {
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        let next = session.async_pruning_point().await;
+        if next == pruning_point {
+            break;
+        }
+        pruning_point = next;
+    }
+    pruning_point
+}
+
+/// Builds a chain of `len` UTXO valid blocks over genesis on a fresh consensus instance
+async fn build_chain_over_genesis(config: &Config, first_hash: u64, len: u64) -> Vec<Block> {
+    build_chain_over_prefix(config, &[], first_hash, len).await
+}
+
+/// Builds a chain of `len` UTXO valid blocks over the `prefix` chain on a fresh consensus instance
+async fn build_chain_over_prefix(config: &Config, prefix: &[Block], first_hash: u64, len: u64) -> Vec<Block> {
+    let consensus = TestConsensus::new(config);
+    let wait_handles = consensus.init();
+    for block in prefix.iter().cloned() {
+        consensus.validate_and_insert_block(block).virtual_state_task.await.unwrap();
+    }
+    let mut parent = prefix.last().map_or(config.genesis.hash, |block| block.hash());
+    let mut blocks = Vec::with_capacity(len as usize);
+    for hash in (first_hash..first_hash + len).map(Hash::from) {
+        consensus.add_utxo_valid_block_with_parents(hash, vec![parent], vec![]).await.unwrap();
+        blocks.push(consensus.get_block(hash).unwrap());
+        parent = hash;
+    }
+    consensus.shutdown(wait_handles);
+    blocks
+}
+
+#[tokio::test]
+async fn resolve_finality_conflict_test() {
+    use kaspa_consensus_notify::notification::Notification;
+
+    init_allocator_with_default_settings();
+    const FINALITY_DEPTH: u64 = 10;
+    let config = ConfigBuilder::new(DEVNET_PARAMS)
+        .skip_proof_of_work()
+        .edit_consensus_params(|p| {
+            p.finality_depth = FINALITY_DEPTH;
+            p.merge_depth = FINALITY_DEPTH / 2;
+        })
+        .build();
+
+    // Two chains sharing only genesis, where the second is heavier but is received only after the first is final
+    let current_chain = build_chain_over_genesis(&config, 1, 2 * FINALITY_DEPTH).await;
+    let conflicting_chain = build_chain_over_genesis(&config, 1001, 3 * FINALITY_DEPTH).await;
+
+    let db_tempdir = get_kaspa_tempdir();
+    let db_path = db_tempdir.path().to_owned();
+    let meta_db =
+        kaspa_database::prelude::ConnBuilder::default().with_db_path(db_path.join("meta")).with_files_limit(5).build().unwrap();
+
+    let (notification_send, notification_recv) = unbounded();
+    let consensus_factory = Arc::new(ConsensusFactory::new(
+        meta_db,
+        &config,
+        db_path.join("consensus"),
+        4,
+        Arc::new(ConsensusNotificationRoot::new(notification_send)),
+        Arc::new(ProcessingCounters::default()),
+        Arc::new(TxScriptCacheCounters::default()),
+        200,
+    ));
+    let consensus_manager = Arc::new(ConsensusManager::new(consensus_factory));
+
+    let core = Arc::new(Core::new());
+    core.bind(consensus_manager.clone());
+    let joins = core.start();
+
+    let session = consensus_manager.consensus().session().await;
+    for block in current_chain.iter().chain(conflicting_chain.iter()).cloned() {
+        session.validate_and_insert_block(block).virtual_state_task.await.unwrap();
+    }
+    // The conflicting chain violates finality so the sink remains on the current chain
+    assert_eq!(session.async_get_sink().await, current_chain.last().unwrap().hash());
+    drop(session);
+
+    let finality_block_hash = conflicting_chain[0].hash();
+    consensus_manager.resolve_finality_conflict(finality_block_hash).await.unwrap();
+
+    let session = consensus_manager.consensus().session().await;
+    assert_eq!(session.async_get_sink().await, conflicting_chain.last().unwrap().hash());
+    assert!(session.async_get_block_status(current_chain[0].hash()).await.is_none());
+    drop(session);
+
+    // The conflict is resolved, so resolving it once more fails
+    assert!(consensus_manager.resolve_finality_conflict(finality_block_hash).await.is_err());
+
+    let mut resolved = false;
+    while let Ok(notification) = notification_recv.try_recv() {
+        if let Notification::FinalityConflictResolved(notification) = notification {
+            assert_eq!(notification.finality_block_hash, finality_block_hash);
+            resolved = true;
+        }
+    }
+    assert!(resolved);
+
+    core.shutdown();
+    core.join(joins);
+}
+
+#[tokio::test]
+async fn resolve_finality_conflict_above_pruning_point_test() {
+    init_allocator_with_default_settings();
+    let config = shallow_pruning_config();
+    let finality_depth = config.finality_depth;
+
+    // Two chains forking from a common prefix deeper than the pruning depth, so that the pruning point is not genesis.
+    // The headers of the longer conflicting chain point at a later pruning point than the one of the current chain.
+    let prefix = build_chain_over_genesis(&config, 1, 250).await;
+    let current_chain = build_chain_over_prefix(&config, &prefix, 1001, 2 * finality_depth).await;
+    let conflicting_chain = build_chain_over_prefix(&config, &prefix, 2001, 3 * finality_depth).await;
+
+    let db_tempdir = get_kaspa_tempdir();
+    let (consensus_manager, core, joins) = start_consensus_manager(&config, db_tempdir.path());
+
+    let session = consensus_manager.consensus().session().await;
+    for block in prefix.iter().chain(current_chain.iter()).chain(conflicting_chain.iter()).cloned() {
+        session.validate_and_insert_block(block).virtual_state_task.await.unwrap();
+    }
+    assert_eq!(session.async_get_sink().await, current_chain.last().unwrap().hash());
+    let pruning_point = wait_for_pruning_point(&session).await;
+    assert_ne!(pruning_point, config.genesis.hash);
+    drop(session);
+
+    // Resolving the conflict while the staging consensus is in use fails
+    let reservation = consensus_manager.try_reserve_staging().unwrap();
+    let finality_block_hash = conflicting_chain[0].hash();
+    assert!(matches!(
+        consensus_manager.resolve_finality_conflict(finality_block_hash).await,
+        Err(FinalityConflictResolutionError::StagingInUse)
+    ));
+    drop(reservation);
+
+    consensus_manager.resolve_finality_conflict(finality_block_hash).await.unwrap();
+
+    let session = consensus_manager.consensus().session().await;
+    let sink = conflicting_chain.last().unwrap().hash();
+    assert_eq!(session.async_get_sink().await, sink);
+    assert!(session.async_get_block_status(current_chain[0].hash()).await.is_none());
+    // The rebuilt consensus starts from the previous pruning point and may have advanced it along the conflicting chain
+    let resolved_pruning_point = session.async_pruning_point().await;
+    assert!(session.async_is_chain_ancestor_of(pruning_point, resolved_pruning_point).await.unwrap());
+    assert!(session.async_is_chain_ancestor_of(resolved_pruning_point, sink).await.unwrap());
+    drop(session);
+
+    core.shutdown();
+    core.join(joins);
+}
+
+/// Splits a UTXO snapshot file into its length-prefixed records
+fn read_snapshot_records(path: &Path) -> Vec<Vec<u8>> {
+    let bytes = std::fs::read(path).unwrap();
+    let mut records = Vec::new();
+    let mut rest = bytes.as_slice();
+    while !rest.is_empty() {
+        let (len, tail) = rest.split_at(8);
+        let (record, tail) = tail.split_at(u64::from_le_bytes(len.try_into().unwrap()) as usize);
+        records.push(record.to_vec());
+        rest = tail;
+    }
+    records
+}
+
+fn write_snapshot_records(path: &Path, records: &[Vec<u8>]) {
+    let mut bytes = Vec::new();
+    for record in records {
+        bytes.extend((record.len() as u64).to_le_bytes());
+        bytes.extend(record);
+    }
+    std::fs::write(path, bytes).unwrap();
+}
+
+#[tokio::test]
+async fn utxo_snapshot_test() {
+    init_allocator_with_default_settings();
+    let config = shallow_pruning_config();
+    let chain = build_chain_over_genesis(&config, 1, 250).await;
+
+    let db_tempdir = get_kaspa_tempdir();
+    let db_path = db_tempdir.path().to_owned();
+    let (source, source_core, source_joins) = start_consensus_manager(&config, &db_path.join("source"));
+    let session = source.consensus().session().await;
+    for block in chain.iter().cloned() {
+        session.validate_and_insert_block(block).virtual_state_task.await.unwrap();
+    }
+    let pruning_point = wait_for_pruning_point(&session).await;
+    assert_ne!(pruning_point, config.genesis.hash);
+    let headers_selected_tip = session.async_get_headers_selected_tip().await;
+    drop(session);
+
+    let snapshot_path = db_path.join("snapshot");
+    source.export_utxo_snapshot(&snapshot_path).await.unwrap();
+    source_core.shutdown();
+    source_core.join(source_joins);
+
+    // The records are the snapshot header, the pruning points, the proof and the trusted data, followed by the header
+    // chunks and the UTXO chunks, each terminated by an empty chunk, and the UTXO set MuHash
+    let records = read_snapshot_records(&snapshot_path);
+    let empty_chunk = bincode::serialize(&Vec::<Arc<Header>>::new()).unwrap();
+    let headers_end = 4 + records[4..].iter().position(|record| *record == empty_chunk).unwrap();
+    assert!(headers_end > 4);
+    let utxos_start = headers_end + 1;
+    assert_ne!(records[utxos_start], empty_chunk);
+
+    let (target, target_core, target_joins) = start_consensus_manager(&config, &db_path.join("target"));
+
+    // A UTXO which does not match the UTXO commitment of the pruning point, with a consistent snapshot MuHash
+    let mut tampered = records.clone();
+    let mut utxos: Vec<(TransactionOutpoint, UtxoEntry)> = bincode::deserialize(&tampered[utxos_start]).unwrap();
+    utxos[0].1.amount += 1;
+    tampered[utxos_start] = bincode::serialize(&utxos).unwrap();
+    let mut multiset = MuHash::new();
+    for record in &tampered[utxos_start..tampered.len() - 2] {
+        let utxos: Vec<(TransactionOutpoint, UtxoEntry)> = bincode::deserialize(record).unwrap();
+        utxos.iter().for_each(|(outpoint, entry)| multiset.add_utxo(outpoint, entry));
+    }
+    *tampered.last_mut().unwrap() = bincode::serialize(&multiset.finalize()).unwrap();
+    let tampered_path = db_path.join("tampered_utxos");
+    write_snapshot_records(&tampered_path, &tampered);
+    assert!(target.import_utxo_snapshot(&tampered_path).await.is_err());
+
+    // A header whose hash no longer matches the parents of its children
+    let mut tampered = records.clone();
+    let mut headers: Vec<Arc<Header>> = bincode::deserialize(&tampered[4]).unwrap();
+    Arc::make_mut(&mut headers[0]).timestamp += 1;
+    tampered[4] = bincode::serialize(&headers).unwrap();
+    let tampered_path = db_path.join("tampered_headers");
+    write_snapshot_records(&tampered_path, &tampered);
+    assert!(target.import_utxo_snapshot(&tampered_path).await.is_err());
+
+    // A headers selected tip blue work higher than the actual one, which the proof is validated with. The blue work is
+    // the last field of the snapshot header, encoded as little-endian limbs, so this raises its most significant byte.
+    let mut tampered = records.clone();
+    *tampered[0].last_mut().unwrap() ^= 1;
+    let tampered_path = db_path.join("tampered_blue_work");
+    write_snapshot_records(&tampered_path, &tampered);
+    assert!(target.import_utxo_snapshot(&tampered_path).await.is_err());
+
+    // The failed imports left the current consensus in place and released the staging consensus
+    let session = target.consensus().session().await;
+    assert_eq!(session.async_pruning_point().await, config.genesis.hash);
+    drop(session);
+
+    target.import_utxo_snapshot(&snapshot_path).await.unwrap();
+    let session = target.consensus().session().await;
+    assert_eq!(session.async_pruning_point().await, pruning_point);
+    assert_eq!(session.async_get_headers_selected_tip().await, headers_selected_tip);
+    drop(session);
+
+    // A snapshot without more blue work than the current consensus is refused
+    assert!(target.import_utxo_snapshot(&snapshot_path).await.is_err());
+
+    target_core.shutdown();
+    target_core.join(target_joins);
+}
+
 /// Tests the KIP-10 transaction introspection opcode activation by verifying that:
 /// 1. Transactions using these opcodes are rejected before the activation DAA score
 /// 2. The same transactions are accepted at and after the activation score
@@ -331,6 +331,69 @@ async fn daemon_utxos_propagation_test() {
     }
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn daemon_backup_test() {
+    init_allocator_with_default_settings();
+    kaspa_core::log::try_init_logger("INFO");
+
+    let args = Args {
+        simnet: true,
+        unsafe_rpc: true,
+        utxoindex: true,
+        enable_unsynced_mining: true,
+        disable_upnp: true, // UPnP registration might take some time and is not needed for this test
+        ..Default::default()
+    };
+    let total_fd_limit = 10;
+
+    let mut kaspad1 = Daemon::new_random_with_args(args.clone(), total_fd_limit);
+    let rpc_client1 = kaspad1.start().await;
+    let miner_address = Address::new(kaspad1.network.into(), kaspa_addresses::Version::PubKey, &[0; 32]);
+
+    const BLOCKS: u64 = 10;
+    for _ in 0..BLOCKS {
+        let template = rpc_client1.get_block_template(miner_address.clone(), vec![]).await.unwrap();
+        rpc_client1.submit_block(template.block, false).await.unwrap();
+    }
+    let check_client = rpc_client1.clone();
+    wait_for(
+        50,
+        20,
+        move || {
+            async fn daa_score_reached(client: GrpcClient) -> bool {
+                client.get_server_info().await.unwrap().virtual_daa_score == BLOCKS
+            }
+            Box::pin(daa_score_reached(check_client.clone()))
+        },
+        "the node did not add all the blocks",
+    )
+    .await;
+    let dag_info = rpc_client1.get_block_dag_info().await.unwrap();
+    let miner_balance = rpc_client1.get_balance_by_address(miner_address.clone()).await.unwrap();
+    assert!(miner_balance > 0);
+
+    let backup_tempdir = kaspa_database::utils::get_kaspa_tempdir();
+    let backup_dir = backup_tempdir.path().join("backup").to_str().unwrap().to_owned();
+    let databases = rpc_client1.backup(backup_dir.clone()).await.unwrap();
+    assert_eq!(databases.iter().map(|database| database.name.as_str()).collect::<Vec<_>>(), vec!["consensus", "meta", "utxoindex"]);
+
+    rpc_client1.disconnect().await.unwrap();
+    drop(rpc_client1);
+    kaspad1.shutdown();
+
+    // A node started from the backup has the DAG and the UTXO index of the node it was taken from
+    let mut kaspad2 = Daemon::new_random_with_args(Args { appdir: Some(backup_dir), ..args }, total_fd_limit);
+    let rpc_client2 = kaspad2.start().await;
+    let restored_dag_info = rpc_client2.get_block_dag_info().await.unwrap();
+    assert_eq!(restored_dag_info.sink, dag_info.sink);
+    assert_eq!(restored_dag_info.block_count, dag_info.block_count);
+    assert_eq!(rpc_client2.get_balance_by_address(miner_address).await.unwrap(), miner_balance);
+
+    rpc_client2.disconnect().await.unwrap();
+    drop(rpc_client2);
+    kaspad2.shutdown();
+}
+
 // The following test runtime parameters are required for a graceful shutdown of the gRPC server
 #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
 async fn daemon_cleaning_test() {
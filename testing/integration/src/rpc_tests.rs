@@ -11,7 +11,7 @@ use kaspa_hashes::Hash;
 use kaspa_notify::{
     connection::{ChannelConnection, ChannelType},
     scope::{
-        BlockAddedScope, FinalityConflictScope, NewBlockTemplateScope, PruningPointUtxoSetOverrideScope, Scope,
+        BlockAddedScope, FinalityConflictScope, MempoolChangedScope, NewBlockTemplateScope, PruningPointUtxoSetOverrideScope, Scope,
         SinkBlueScoreChangedScope, UtxosChangedScope, VirtualChainChangedScope, VirtualDaaScoreChangedScope,
     },
 };
@@ -49,6 +49,8 @@ async fn sanity_test() {
         enable_unsynced_mining: true,
         block_template_cache_lifetime: Some(0),
         utxoindex: true,
+        txindex: true,
+        addresshistoryindex: true,
         unsafe_rpc: true,
         ..Default::default()
     };
@@ -162,6 +164,21 @@ async fn sanity_test() {
                     assert!(response.added_chain_block_hashes.contains(&block_hash));
                     assert!(response.removed_chain_block_hashes.is_empty());
 
+                    // Headers following genesis are returned towards the sink,
+                    let response =
+                        rpc_client.get_headers_call(None, GetHeadersRequest::new(SIMNET_GENESIS.hash, 10, true, true)).await.unwrap();
+                    assert_eq!(response.headers.iter().map(|header| header.hash).collect::<Vec<_>>(), vec![block_hash]);
+                    assert_eq!(response.verbose_data.len(), 1);
+                    assert_eq!(response.verbose_data[0].hash, block_hash);
+                    assert_eq!(response.verbose_data[0].selected_parent_hash, SIMNET_GENESIS.hash);
+                    assert!(response.verbose_data[0].is_chain_block);
+
+                    // and those preceding the sink back to genesis, without verbose data unless requested
+                    let response =
+                        rpc_client.get_headers_call(None, GetHeadersRequest::new(block_hash, 10, false, false)).await.unwrap();
+                    assert_eq!(response.headers.iter().map(|header| header.hash).collect::<Vec<_>>(), vec![SIMNET_GENESIS.hash]);
+                    assert!(response.verbose_data.is_empty());
+
                     let result =
                         rpc_client.get_current_block_color_call(None, GetCurrentBlockColorRequest { hash: SIMNET_GENESIS.hash }).await;
 
@@ -384,7 +401,7 @@ async fn sanity_test() {
                         )
                         .await;
 
-                    // Err because it's currently unimplemented
+                    // Err because the block is unknown
                     assert!(response_result.is_err());
                 })
             }
@@ -392,12 +409,15 @@ async fn sanity_test() {
             KaspadPayloadOps::GetHeaders => {
                 let rpc_client = client.clone();
                 tst!(op, {
-                    let response_result = rpc_client
-                        .get_headers_call(None, GetHeadersRequest { start_hash: SIMNET_GENESIS.hash, limit: 1, is_ascending: true })
-                        .await;
+                    // Non-existing blocks should return an error
+                    let result = rpc_client.get_headers_call(None, GetHeadersRequest::new(999.into(), 1, true, false)).await;
+                    assert!(result.is_err());
 
-                    // Err because it's currently unimplemented
-                    assert!(response_result.is_err());
+                    // The start hash is exclusive, so nothing precedes genesis (see SubmitBlock for headers of actual blocks)
+                    let response =
+                        rpc_client.get_headers_call(None, GetHeadersRequest::new(SIMNET_GENESIS.hash, 1, false, true)).await.unwrap();
+                    assert!(response.headers.is_empty());
+                    assert!(response.verbose_data.is_empty());
                 })
             }
 
@@ -493,6 +513,81 @@ async fn sanity_test() {
                 })
             }
 
+            KaspadPayloadOps::GetTransaction => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    // An unknown transaction is reported as not found
+                    let result = rpc_client.get_transaction_call(None, GetTransactionRequest::new(999.into(), false)).await;
+                    assert!(result.is_err());
+                })
+            }
+
+            KaspadPayloadOps::GetTransactionAcceptance => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    // Unknown transactions are omitted from the response
+                    let response = rpc_client
+                        .get_transaction_acceptance_call(None, GetTransactionAcceptanceRequest::new(vec![999.into()]))
+                        .await
+                        .unwrap();
+                    assert!(response.acceptances.is_empty());
+                })
+            }
+
+            KaspadPayloadOps::GetAddressHistory => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    // An address with no activity has an empty history
+                    let address = Address::new(Prefix::Simnet, Version::PubKey, &[7u8; 32]);
+                    let response =
+                        rpc_client.get_address_history_call(None, GetAddressHistoryRequest::new(address, 0, 10)).await.unwrap();
+                    assert!(response.entries.is_empty());
+                    assert!(response.next_daa_score.is_none());
+                })
+            }
+
+            KaspadPayloadOps::GetMempoolPolicy => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    let response = rpc_client.get_mempool_policy_call(None, GetMempoolPolicyRequest {}).await.unwrap();
+                    assert!(response.policy.maximum_transaction_count > 0);
+                })
+            }
+
+            KaspadPayloadOps::SetMempoolPolicy => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    // Setting the current value back is a no-op which evicts nothing
+                    let policy = rpc_client.get_mempool_policy_call(None, GetMempoolPolicyRequest {}).await.unwrap().policy;
+                    let request = SetMempoolPolicyRequest {
+                        minimum_relay_transaction_fee: Some(policy.minimum_relay_transaction_fee),
+                        ..Default::default()
+                    };
+                    let response = rpc_client.set_mempool_policy_call(None, request).await.unwrap();
+                    assert_eq!(response.policy, policy);
+                    assert_eq!(response.evicted_transaction_count, 0);
+
+                    // An invalid policy is rejected
+                    let request = SetMempoolPolicyRequest { maximum_transaction_count: Some(0), ..Default::default() };
+                    assert!(rpc_client.set_mempool_policy_call(None, request).await.is_err());
+                })
+            }
+
+            KaspadPayloadOps::Backup => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    let backup_tempdir = kaspa_database::utils::get_kaspa_tempdir();
+                    let target_dir = backup_tempdir.path().join("backup").to_str().unwrap().to_owned();
+                    let response = rpc_client.backup_call(None, BackupRequest { target_dir: target_dir.clone() }).await.unwrap();
+                    let names = response.databases.iter().map(|database| database.name.as_str()).collect::<Vec<_>>();
+                    assert_eq!(names, vec!["consensus", "meta", "utxoindex"]);
+                    assert!(response.databases.iter().all(|database| std::path::Path::new(&database.path).is_dir()));
+
+                    // A backup never overwrites a previous one
+                    assert!(rpc_client.backup_call(None, BackupRequest { target_dir }).await.is_err());
+                })
+            }
+
             KaspadPayloadOps::Ping => {
                 let rpc_client = client.clone();
                 tst!(op, {
@@ -717,6 +812,13 @@ async fn sanity_test() {
                         .unwrap();
                 })
             }
+            KaspadPayloadOps::NotifyMempoolChanged => {
+                let rpc_client = client.clone();
+                let id = listener_id;
+                tst!(op, {
+                    rpc_client.start_notify(id, MempoolChangedScope::new(vec![]).into()).await.unwrap();
+                })
+            }
             KaspadPayloadOps::StopNotifyingUtxosChanged => {
                 let rpc_client = client.clone();
                 let id = listener_id;
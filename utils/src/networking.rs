@@ -8,6 +8,7 @@ use std::{
     ops::Deref,
     str::FromStr,
 };
+use thiserror::Error;
 use uuid::Uuid;
 use wasm_bindgen::prelude::*;
 
@@ -341,6 +342,88 @@ impl Display for ContextualNetAddress {
         }
     }
 }
+
+/// Errors of parsing a [`NamedNetAddress`]
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum NamedNetAddressError {
+    #[error("missing port in address {0}")]
+    MissingPort(String),
+
+    #[error("invalid port in address {0}")]
+    InvalidPort(String),
+
+    #[error("invalid host name {0}")]
+    InvalidHost(String),
+
+    #[error("{0} is an ip address, not a host name")]
+    IpAddress(String),
+}
+
+/// The suffix of the host names of Tor onion services
+const ONION_SUFFIX: &str = ".onion";
+
+/// The maximum length of a host name, as per RFC 1035
+const MAX_HOST_NAME_LEN: usize = 253;
+
+/// The maximum length of a label of a host name, as per RFC 1035
+const MAX_HOST_LABEL_LEN: usize = 63;
+
+/// A network address designated by a host name rather than an ip, typically a Tor onion service.
+///
+/// Such addresses are resolved by the SOCKS5 proxy the connection is dialed through, never locally.
+#[derive(PartialEq, Eq, Hash, Clone, Serialize, Deserialize, Debug, BorshSerialize, BorshDeserialize)]
+pub struct NamedNetAddress {
+    pub host: String,
+    pub port: u16,
+}
+
+impl NamedNetAddress {
+    pub fn new(host: String, port: u16) -> Self {
+        Self { host, port }
+    }
+
+    /// Indicates whether this address is a Tor onion service
+    pub fn is_onion(&self) -> bool {
+        self.host.ends_with(ONION_SUFFIX)
+    }
+
+    /// Checks that `host` is a syntactically valid host name, which is not an ip address
+    pub fn validate_host(host: &str) -> Result<(), NamedNetAddressError> {
+        let trimmed = host.trim_start_matches('[').trim_end_matches(']');
+        if IpAddr::from_str(trimmed).is_ok() {
+            return Err(NamedNetAddressError::IpAddress(host.to_owned()));
+        }
+        let is_valid_label = |label: &str| {
+            !label.is_empty()
+                && label.len() <= MAX_HOST_LABEL_LEN
+                && !label.starts_with('-')
+                && !label.ends_with('-')
+                && label.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'-')
+        };
+        if host.is_empty() || host.len() > MAX_HOST_NAME_LEN || !host.split('.').all(is_valid_label) {
+            return Err(NamedNetAddressError::InvalidHost(host.to_owned()));
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for NamedNetAddress {
+    type Err = NamedNetAddressError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (host, port) = s.rsplit_once(':').ok_or_else(|| NamedNetAddressError::MissingPort(s.to_owned()))?;
+        let port = u16::from_str(port).map_err(|_| NamedNetAddressError::InvalidPort(s.to_owned()))?;
+        Self::validate_host(host)?;
+        Ok(Self::new(host.to_ascii_lowercase(), port))
+    }
+}
+
+impl Display for NamedNetAddress {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.host, self.port)
+    }
+}
+
 #[derive(PartialEq, Eq, Hash, Copy, Clone, Serialize, Deserialize, Debug, Default)]
 #[repr(transparent)]
 pub struct PeerId(pub Uuid);
@@ -447,6 +530,30 @@ mod tests {
         assert!(addr_v6.is_ok());
     }
 
+    #[test]
+    fn test_named_net_address_from_str() {
+        let addr = NamedNetAddress::from_str("Kaspadxyz2ruzvtwoiqbuzhvrdjmcvg7tgxnc5rplwhsbd5e2fkaxyad.onion:16111").unwrap();
+        assert_eq!(addr.host, "kaspadxyz2ruzvtwoiqbuzhvrdjmcvg7tgxnc5rplwhsbd5e2fkaxyad.onion");
+        assert_eq!(addr.port, 16111);
+        assert!(addr.is_onion());
+        assert_eq!(NamedNetAddress::from_str(&addr.to_string()).unwrap(), addr);
+
+        let addr = NamedNetAddress::from_str("seeder1.kaspad.net:16111").unwrap();
+        assert!(!addr.is_onion());
+
+        assert_eq!(NamedNetAddress::from_str("node.kaspa.org"), Err(NamedNetAddressError::MissingPort("node.kaspa.org".to_owned())));
+        assert!(matches!(NamedNetAddress::from_str("node.kaspa.org:65536"), Err(NamedNetAddressError::InvalidPort(_))));
+        assert!(matches!(NamedNetAddress::from_str("1.2.3.4:5678"), Err(NamedNetAddressError::IpAddress(_))));
+        assert!(matches!(NamedNetAddress::from_str("[2a01:4f8:191:1143::2]:5678"), Err(NamedNetAddressError::IpAddress(_))));
+        for host in ["", "node..org", "-node.org", "node-.org", "no de.org", "node_1.org"] {
+            assert!(
+                matches!(NamedNetAddress::from_str(&format!("{host}:16111")), Err(NamedNetAddressError::InvalidHost(_))),
+                "{host}"
+            );
+        }
+        assert!(NamedNetAddress::from_str(&format!("{}.onion:16111", "a".repeat(MAX_HOST_LABEL_LEN + 1))).is_err());
+    }
+
     #[test]
     fn test_prefix_bucket() {
         let prefix_bytes: [u8; 2] = [42u8, 43u8];
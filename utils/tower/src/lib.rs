@@ -2,5 +2,6 @@ cfg_if::cfg_if! {
     if #[cfg(not(target_arch = "wasm32"))] {
         pub mod counters;
         pub mod middleware;
+        pub mod socks5;
     }
 }
@@ -0,0 +1,245 @@
+//!
+//! Outbound connections through a SOCKS5 proxy (RFC 1928), such as the one exposed by Tor.
+//!
+//! Host names are sent to the proxy as is, so that they get resolved on its side. This is what makes
+//! onion services reachable and keeps the DNS queries of clear net host names from leaking locally.
+//!
+
+use http::Uri;
+use hyper_util::rt::TokioIo;
+use std::{
+    future::Future,
+    io::{Error, ErrorKind, Result},
+    net::{IpAddr, SocketAddr},
+    pin::Pin,
+    task::{Context, Poll},
+};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpStream,
+};
+use tower::Service;
+
+const VERSION: u8 = 0x05;
+const NO_AUTHENTICATION: u8 = 0x00;
+const NO_ACCEPTABLE_METHOD: u8 = 0xff;
+const CONNECT_COMMAND: u8 = 0x01;
+const RESERVED: u8 = 0x00;
+const SUCCEEDED: u8 = 0x00;
+
+const ATYP_IPV4: u8 = 0x01;
+const ATYP_DOMAIN_NAME: u8 = 0x03;
+const ATYP_IPV6: u8 = 0x04;
+
+/// Opens a TCP connection to `host:port` tunneled through the SOCKS5 proxy listening at `proxy`
+pub async fn connect(proxy: SocketAddr, host: &str, port: u16) -> Result<TcpStream> {
+    let mut stream = TcpStream::connect(proxy).await?;
+
+    // Method negotiation, only the anonymous access is supported
+    stream.write_all(&[VERSION, 1, NO_AUTHENTICATION]).await?;
+    let mut reply = [0u8; 2];
+    stream.read_exact(&mut reply).await?;
+    match reply {
+        [VERSION, NO_AUTHENTICATION] => {}
+        [VERSION, NO_ACCEPTABLE_METHOD] => {
+            return Err(Error::new(ErrorKind::PermissionDenied, "SOCKS5 proxy requires an authentication"));
+        }
+        _ => return Err(Error::new(ErrorKind::InvalidData, "unexpected SOCKS5 method selection reply")),
+    }
+
+    // Connect request
+    let mut request = vec![VERSION, CONNECT_COMMAND, RESERVED];
+    match host.trim_start_matches('[').trim_end_matches(']').parse::<IpAddr>() {
+        Ok(IpAddr::V4(ip)) => {
+            request.push(ATYP_IPV4);
+            request.extend_from_slice(&ip.octets());
+        }
+        Ok(IpAddr::V6(ip)) => {
+            request.push(ATYP_IPV6);
+            request.extend_from_slice(&ip.octets());
+        }
+        Err(_) => {
+            let len = u8::try_from(host.len()).map_err(|_| Error::new(ErrorKind::InvalidInput, "host name is too long"))?;
+            request.push(ATYP_DOMAIN_NAME);
+            request.push(len);
+            request.extend_from_slice(host.as_bytes());
+        }
+    }
+    request.extend_from_slice(&port.to_be_bytes());
+    stream.write_all(&request).await?;
+
+    // Connect reply, the bound address is of no use to us but must be consumed
+    let mut reply = [0u8; 4];
+    stream.read_exact(&mut reply).await?;
+    if reply[0] != VERSION {
+        return Err(Error::new(ErrorKind::InvalidData, "unexpected SOCKS5 connect reply"));
+    }
+    if reply[1] != SUCCEEDED {
+        return Err(reply_error(reply[1]));
+    }
+    let address_len = match reply[3] {
+        ATYP_IPV4 => 4,
+        ATYP_IPV6 => 16,
+        ATYP_DOMAIN_NAME => stream.read_u8().await? as usize,
+        _ => return Err(Error::new(ErrorKind::InvalidData, "unexpected SOCKS5 bound address type")),
+    };
+    let mut bound_address = vec![0u8; address_len + 2];
+    stream.read_exact(&mut bound_address).await?;
+
+    Ok(stream)
+}
+
+fn reply_error(code: u8) -> Error {
+    let (kind, reason) = match code {
+        0x01 => (ErrorKind::Other, "general SOCKS server failure"),
+        0x02 => (ErrorKind::PermissionDenied, "connection not allowed by ruleset"),
+        0x03 => (ErrorKind::Other, "network unreachable"),
+        0x04 => (ErrorKind::Other, "host unreachable"),
+        0x05 => (ErrorKind::ConnectionRefused, "connection refused"),
+        0x06 => (ErrorKind::TimedOut, "TTL expired"),
+        0x07 => (ErrorKind::Unsupported, "command not supported"),
+        0x08 => (ErrorKind::Unsupported, "address type not supported"),
+        _ => (ErrorKind::Other, "unknown error"),
+    };
+    Error::new(kind, format!("SOCKS5 proxy: {reason}"))
+}
+
+/// A connector dialing the authority of the requested uri through a SOCKS5 proxy, to be
+/// used with `tonic::transport::Endpoint::connect_with_connector`
+#[derive(Clone, Copy, Debug)]
+pub struct Socks5Connector {
+    proxy: SocketAddr,
+}
+
+impl Socks5Connector {
+    pub fn new(proxy: SocketAddr) -> Self {
+        Self { proxy }
+    }
+}
+
+impl Service<Uri> for Socks5Connector {
+    type Response = TokioIo<TcpStream>;
+    type Error = Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response>> + Send>>;
+
+    fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, uri: Uri) -> Self::Future {
+        let proxy = self.proxy;
+        Box::pin(async move {
+            let host = uri.host().ok_or_else(|| Error::new(ErrorKind::InvalidInput, format!("missing host in uri {uri}")))?;
+            let port = match (uri.port_u16(), uri.scheme_str()) {
+                (Some(port), _) => port,
+                (None, Some("https")) => 443,
+                (None, _) => 80,
+            };
+            Ok(TokioIo::new(connect(proxy, host, port).await?))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::{
+        net::TcpListener,
+        sync::oneshot::{channel, Sender},
+    };
+
+    /// A minimal SOCKS5 proxy serving a single connection, which reports the requested destination
+    /// and routes the connection to `target` whatever it is
+    async fn stand_in_proxy(target: SocketAddr, destination: Sender<(u8, Vec<u8>, u16)>) -> SocketAddr {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let (mut client, _) = listener.accept().await.unwrap();
+            let mut greeting = [0u8; 3];
+            client.read_exact(&mut greeting).await.unwrap();
+            assert_eq!(greeting, [VERSION, 1, NO_AUTHENTICATION]);
+            client.write_all(&[VERSION, NO_AUTHENTICATION]).await.unwrap();
+
+            let mut request = [0u8; 4];
+            client.read_exact(&mut request).await.unwrap();
+            assert_eq!(request[..3], [VERSION, CONNECT_COMMAND, RESERVED]);
+            let len = match request[3] {
+                ATYP_IPV4 => 4,
+                ATYP_IPV6 => 16,
+                _ => client.read_u8().await.unwrap() as usize,
+            };
+            let mut host = vec![0u8; len];
+            client.read_exact(&mut host).await.unwrap();
+            let port = client.read_u16().await.unwrap();
+            destination.send((request[3], host, port)).unwrap();
+
+            let mut server = TcpStream::connect(target).await.unwrap();
+            client.write_all(&[VERSION, SUCCEEDED, RESERVED, ATYP_IPV4, 127, 0, 0, 1, 0, 0]).await.unwrap();
+            tokio::io::copy_bidirectional(&mut client, &mut server).await.ok();
+        });
+        address
+    }
+
+    async fn echo_server() -> SocketAddr {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let (mut reader, mut writer) = stream.split();
+            tokio::io::copy(&mut reader, &mut writer).await.ok();
+        });
+        address
+    }
+
+    #[tokio::test]
+    async fn test_connect_through_proxy() {
+        let onion = "kaspadxyz2ruzvtwoiqbuzhvrdjmcvg7tgxnc5rplwhsbd5e2fkaxyad.onion";
+        let (sender, receiver) = channel();
+        let proxy = stand_in_proxy(echo_server().await, sender).await;
+        let mut stream = connect(proxy, onion, 16111).await.unwrap();
+        assert_eq!(receiver.await.unwrap(), (ATYP_DOMAIN_NAME, onion.as_bytes().to_vec(), 16111));
+
+        stream.write_all(b"kaspa").await.unwrap();
+        let mut echo = [0u8; 5];
+        stream.read_exact(&mut echo).await.unwrap();
+        assert_eq!(&echo, b"kaspa");
+
+        let (sender, receiver) = channel();
+        let proxy = stand_in_proxy(echo_server().await, sender).await;
+        connect(proxy, "[2a01:4f8:191:1143::2]", 16110).await.unwrap();
+        let ip: std::net::Ipv6Addr = "2a01:4f8:191:1143::2".parse().unwrap();
+        assert_eq!(receiver.await.unwrap(), (ATYP_IPV6, ip.octets().to_vec(), 16110));
+
+        let (sender, receiver) = channel();
+        let proxy = stand_in_proxy(echo_server().await, sender).await;
+        let mut connector = Socks5Connector::new(proxy);
+        connector.call("http://node.kaspa.org:16210".parse().unwrap()).await.unwrap();
+        assert_eq!(receiver.await.unwrap(), (ATYP_DOMAIN_NAME, b"node.kaspa.org".to_vec(), 16210));
+    }
+
+    #[tokio::test]
+    async fn test_proxy_failures() {
+        // The proxy refuses the connection
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let proxy = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let (mut client, _) = listener.accept().await.unwrap();
+            let mut request = [0u8; 3 + 4 + 1 + 9 + 2];
+            client.write_all(&[VERSION, NO_AUTHENTICATION]).await.unwrap();
+            client.read_exact(&mut request).await.unwrap();
+            client.write_all(&[VERSION, 0x05, RESERVED, ATYP_IPV4, 0, 0, 0, 0, 0, 0]).await.unwrap();
+        });
+        let err = connect(proxy, "localhost", 16111).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ConnectionRefused);
+
+        // The proxy requires an authentication
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let proxy = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let (mut client, _) = listener.accept().await.unwrap();
+            client.write_all(&[VERSION, NO_ACCEPTABLE_METHOD]).await.unwrap();
+        });
+        let err = connect(proxy, "localhost", 16111).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::PermissionDenied);
+    }
+}
@@ -8,6 +8,7 @@
 use crate::imports::*;
 use crate::tx::{Fees, GeneratorSummary, PaymentDestination};
 use kaspa_addresses::Address;
+use kaspa_rpc_core::api::auth::RpcCredentials;
 
 #[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
 #[serde(rename_all = "camelCase")]
@@ -51,6 +52,8 @@ pub struct ConnectRequest {
     pub block_async_connect: bool,
     // require node to be synced, fail otherwise
     pub require_sync: bool,
+    // credentials for nodes requiring RPC authentication
+    pub credentials: Option<RpcCredentials>,
 }
 
 impl Default for ConnectRequest {
@@ -61,6 +64,7 @@ impl Default for ConnectRequest {
             retry_on_error: true,
             block_async_connect: true,
             require_sync: true,
+            credentials: None,
         }
     }
 }
@@ -85,6 +89,10 @@ impl ConnectRequest {
     pub fn with_require_sync(self, require_sync: bool) -> Self {
         ConnectRequest { require_sync, ..self }
     }
+
+    pub fn with_credentials(self, credentials: Option<RpcCredentials>) -> Self {
+        ConnectRequest { credentials, ..self }
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
@@ -63,7 +63,16 @@ pub trait WalletApi: Send + Sync + AnySync {
         let retry_on_error = false;
         let block_async_connect = true;
         let require_sync = true;
-        self.connect_call(ConnectRequest { url, network_id: *network_id, retry_on_error, block_async_connect, require_sync }).await?;
+        let credentials = None;
+        self.connect_call(ConnectRequest {
+            url,
+            network_id: *network_id,
+            retry_on_error,
+            block_async_connect,
+            require_sync,
+            credentials,
+        })
+        .await?;
         Ok(())
     }
 
@@ -379,6 +379,50 @@ impl RpcApi for RpcCoreMock {
         Err(RpcError::NotImplemented)
     }
 
+    async fn get_transaction_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        _request: GetTransactionRequest,
+    ) -> RpcResult<GetTransactionResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_transaction_acceptance_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        _request: GetTransactionAcceptanceRequest,
+    ) -> RpcResult<GetTransactionAcceptanceResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_address_history_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        _request: GetAddressHistoryRequest,
+    ) -> RpcResult<GetAddressHistoryResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_mempool_policy_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        _request: GetMempoolPolicyRequest,
+    ) -> RpcResult<GetMempoolPolicyResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn set_mempool_policy_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        _request: SetMempoolPolicyRequest,
+    ) -> RpcResult<SetMempoolPolicyResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn backup_call(&self, _connection: Option<&DynRpcConnection>, _request: BackupRequest) -> RpcResult<BackupResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
     // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
     // Notification API
 
@@ -80,7 +80,7 @@ impl WalletApi for super::Wallet {
     async fn connect_call(self: Arc<Self>, request: ConnectRequest) -> Result<ConnectResponse> {
         use workflow_rpc::client::{ConnectOptions, ConnectStrategy};
 
-        let ConnectRequest { url, network_id, retry_on_error, block_async_connect, require_sync } = request;
+        let ConnectRequest { url, network_id, retry_on_error, block_async_connect, require_sync, credentials } = request;
 
         if let Some(wrpc_client) = self.try_wrpc_client().as_ref() {
             let strategy = if retry_on_error { ConnectStrategy::Retry } else { ConnectStrategy::Fallback };
@@ -90,6 +90,7 @@ impl WalletApi for super::Wallet {
                 .transpose()?;
             let options = ConnectOptions { block_async_connect, strategy, url, ..Default::default() };
             wrpc_client.disconnect().await?;
+            wrpc_client.set_credentials(credentials)?;
 
             self.set_network_id(&network_id)?;
 
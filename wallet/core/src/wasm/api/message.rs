@@ -8,6 +8,7 @@ use crate::tx::{Fees, PaymentDestination, PaymentOutputs};
 use crate::wasm::tx::fees::IFees;
 use crate::wasm::tx::GeneratorSummary;
 use js_sys::Array;
+use kaspa_rpc_core::{api::auth::RpcCredentials, wasm::message::IRpcCredentials};
 use serde_wasm_bindgen::from_value;
 use workflow_wasm::serde::to_value;
 
@@ -163,6 +164,8 @@ declare! {
         block? : boolean;
         // require node to be synced (fail otherwise)
         requireSync? : boolean;
+        // credentials for nodes requiring RPC authentication
+        credentials? : IRpcCredentials;
     }
     "#,
 }
@@ -173,7 +176,11 @@ try_from! ( args: IConnectRequest, ConnectRequest, {
     let retry_on_error = args.try_get_bool("retryOnError")?.unwrap_or(true);
     let block_async_connect = args.try_get_bool("block")?.unwrap_or(false);
     let require_sync = args.try_get_bool("requireSync")?.unwrap_or(true);
-    Ok(ConnectRequest { url, network_id, retry_on_error, block_async_connect, require_sync })
+    let credentials = args
+        .try_get_value("credentials")?
+        .map(|credentials| RpcCredentials::try_from(credentials.unchecked_into::<IRpcCredentials>()))
+        .transpose()?;
+    Ok(ConnectRequest { url, network_id, retry_on_error, block_async_connect, require_sync, credentials })
 });
 
 declare! {